@@ -0,0 +1,10 @@
+//! Build script: compiles the gRPC service definition when the `grpc` feature is enabled
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto/sentinel.proto");
+
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/sentinel.proto")?;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#ifndef MCP_SENTINEL_H
+#define MCP_SENTINEL_H
+
+/* Generated by cbindgen from src/bindings/ffi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Scan `len` bytes of UTF-8 source code
+//
+// `filename` (NUL-terminated, may be NULL) selects language-specific rules.
+// Returns a JSON array of findings in the same shape as `vulnerabilities` in
+// the JSON report.
+//
+// # Safety
+//
+// `content` must point to `len` readable bytes, and `filename` must be NULL
+// or a valid NUL-terminated string.
+char *mcp_sentinel_scan_buffer(const uint8_t *content, size_t len, const char *filename);
+
+#if defined(MCP_SENTINEL_NATIVE)
+// Scan a directory and return the full JSON report
+//
+// Blocks the calling thread until the scan finishes.
+//
+// # Safety
+//
+// `path` must be a valid NUL-terminated string.
+char *mcp_sentinel_scan_path(const char *path);
+#endif
+
+// Message for the last failed call on this thread, or NULL
+//
+// The pointer stays valid until the next call on the same thread; don't free it.
+const char *mcp_sentinel_last_error(void);
+
+// Library version as a static NUL-terminated string
+const char *mcp_sentinel_version(void);
+
+// Free a string returned by this library
+//
+// # Safety
+//
+// `s` must be NULL or a pointer returned by a `mcp_sentinel_scan_*` function
+// that hasn't been freed yet.
+void mcp_sentinel_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MCP_SENTINEL_H */
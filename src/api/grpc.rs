@@ -0,0 +1,190 @@
+//! gRPC API for scan-as-a-service
+//!
+//! Implements `mcp_sentinel.v1.ScanService` from `proto/sentinel.proto` on top
+//! of the shared [`JobQueue`]. Findings are streamed through a bounded channel,
+//! so slow consumers apply backpressure instead of buffering whole results.
+
+use anyhow::Result;
+use std::net::SocketAddr;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+use super::jobs::{Job, JobQueue, JobStatus};
+use crate::models::scan_result::ScanSummary;
+use crate::models::vulnerability::{Severity, Vulnerability};
+
+/// Generated protobuf types and service traits
+pub mod proto {
+    tonic::include_proto!("mcp_sentinel.v1");
+}
+
+use proto::scan_service_server::{ScanService, ScanServiceServer};
+
+/// Findings buffered per stream before the sender waits on the client
+const STREAM_BUFFER: usize = 32;
+
+/// gRPC service backed by the daemon job queue
+pub struct GrpcScanService {
+    jobs: JobQueue,
+}
+
+impl GrpcScanService {
+    pub fn new(jobs: JobQueue) -> Self {
+        Self { jobs }
+    }
+}
+
+#[tonic::async_trait]
+impl ScanService for GrpcScanService {
+    async fn submit_scan(
+        &self,
+        request: Request<proto::SubmitScanRequest>,
+    ) -> Result<Response<proto::SubmitScanResponse>, Status> {
+        let target = request.into_inner().target;
+        if target.trim().is_empty() {
+            return Err(Status::invalid_argument("target is required"));
+        }
+
+        let job_id = self.jobs.submit(target).await;
+        Ok(Response::new(proto::SubmitScanResponse { job_id }))
+    }
+
+    async fn get_result(
+        &self,
+        request: Request<proto::GetResultRequest>,
+    ) -> Result<Response<proto::GetResultResponse>, Status> {
+        let job_id = request.into_inner().job_id;
+        let job = self
+            .jobs
+            .get(&job_id)
+            .await
+            .ok_or_else(|| Status::not_found(format!("job '{}' not found", job_id)))?;
+
+        Ok(Response::new(job.into()))
+    }
+
+    type StreamFindingsStream = ReceiverStream<Result<proto::Finding, Status>>;
+
+    async fn stream_findings(
+        &self,
+        request: Request<proto::StreamFindingsRequest>,
+    ) -> Result<Response<Self::StreamFindingsStream>, Status> {
+        let job_id = request.into_inner().job_id;
+        if self.jobs.get(&job_id).await.is_none() {
+            return Err(Status::not_found(format!("job '{}' not found", job_id)));
+        }
+
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let jobs = self.jobs.clone();
+        tokio::spawn(async move {
+            let Some(job) = jobs.wait(&job_id).await else {
+                return;
+            };
+
+            if job.status == JobStatus::Failed {
+                let message = job.error.unwrap_or_else(|| "scan failed".to_string());
+                let _ = tx.send(Err(Status::internal(message))).await;
+                return;
+            }
+
+            for vuln in job.result.iter().flat_map(|r| r.vulnerabilities.iter()) {
+                if tx.send(Ok(vuln.into())).await.is_err() {
+                    break; // Client went away
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Serve the gRPC API on `addr` until the process exits
+pub async fn serve(addr: SocketAddr, jobs: JobQueue) -> Result<()> {
+    info!("🔌 gRPC API listening on {}", addr);
+    tonic::transport::Server::builder()
+        .add_service(ScanServiceServer::new(GrpcScanService::new(jobs)))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
+
+impl From<Severity> for proto::Severity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Info => proto::Severity::Info,
+            Severity::Low => proto::Severity::Low,
+            Severity::Medium => proto::Severity::Medium,
+            Severity::High => proto::Severity::High,
+            Severity::Critical => proto::Severity::Critical,
+        }
+    }
+}
+
+impl From<JobStatus> for proto::JobStatus {
+    fn from(status: JobStatus) -> Self {
+        match status {
+            JobStatus::Queued => proto::JobStatus::Queued,
+            JobStatus::Running => proto::JobStatus::Running,
+            JobStatus::Completed => proto::JobStatus::Completed,
+            JobStatus::Failed => proto::JobStatus::Failed,
+            JobStatus::Cancelled => proto::JobStatus::Cancelled,
+        }
+    }
+}
+
+impl From<&ScanSummary> for proto::ScanSummary {
+    fn from(summary: &ScanSummary) -> Self {
+        Self {
+            total_issues: summary.total_issues as u32,
+            critical: summary.critical as u32,
+            high: summary.high as u32,
+            medium: summary.medium as u32,
+            low: summary.low as u32,
+            info: summary.info as u32,
+            triaged: summary.triaged as u32,
+            risk_score: summary.risk_score as u32,
+        }
+    }
+}
+
+impl From<&Vulnerability> for proto::Finding {
+    fn from(vuln: &Vulnerability) -> Self {
+        let location = vuln.location.as_ref();
+        Self {
+            id: vuln.id.clone(),
+            vuln_type: serde_json::to_value(&vuln.vuln_type)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            rule_id: vuln.rule_id.clone().unwrap_or_default(),
+            fingerprint: vuln.fingerprint.clone().unwrap_or_default(),
+            severity: proto::Severity::from(vuln.severity) as i32,
+            confidence: vuln.confidence,
+            title: vuln.title.clone(),
+            description: vuln.description.clone(),
+            file: location.map(|l| l.file.clone()).unwrap_or_default(),
+            line: location.and_then(|l| l.line).unwrap_or(0) as u32,
+            column: location.and_then(|l| l.column).unwrap_or(0) as u32,
+            remediation: vuln.remediation.clone().unwrap_or_default(),
+            code_snippet: vuln.code_snippet.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Job> for proto::GetResultResponse {
+    fn from(job: Job) -> Self {
+        let result = job.result.as_ref();
+        Self {
+            job_id: job.id.clone(),
+            status: proto::JobStatus::from(job.status) as i32,
+            error: job.error.clone().unwrap_or_default(),
+            scan_id: result.map(|r| r.scan_id.clone()).unwrap_or_default(),
+            summary: result.map(|r| (&r.summary).into()),
+            findings: result
+                .map(|r| r.vulnerabilities.iter().map(Into::into).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
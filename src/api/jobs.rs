@@ -0,0 +1,281 @@
+//! Scan job queue
+//!
+//! Jobs submitted through the daemon APIs run on background tasks, bounded by
+//! a semaphore so a burst of submissions can't exhaust the host. Finished
+//! results are written to the history store when one is configured.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{Notify, RwLock, Semaphore};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::models::{config::ScanConfig, scan_result::ScanResult};
+use crate::scanner::Scanner;
+use crate::storage::history::HistoryStore;
+
+/// Lifecycle state of a scan job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// A submitted scan job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub target: String,
+    pub status: JobStatus,
+    pub submitted_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Partial for cancelled jobs (`metadata.incomplete` is set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ScanResult>,
+    #[serde(skip)]
+    cancel: CancellationToken,
+}
+
+impl Job {
+    /// Whether the job has reached a terminal state
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+/// Shared queue of scan jobs
+#[derive(Clone)]
+pub struct JobQueue {
+    jobs: Arc<RwLock<HashMap<String, Job>>>,
+    permits: Arc<Semaphore>,
+    finished: Arc<Notify>,
+    history: Option<Arc<HistoryStore>>,
+    config: ScanConfig,
+}
+
+impl JobQueue {
+    /// Create a queue running at most `max_concurrent` scans at once
+    pub fn new(
+        config: ScanConfig,
+        max_concurrent: usize,
+        history: Option<Arc<HistoryStore>>,
+    ) -> Self {
+        Self {
+            jobs: Arc::new(RwLock::new(HashMap::new())),
+            permits: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            finished: Arc::new(Notify::new()),
+            history,
+            config,
+        }
+    }
+
+    /// Queue a scan of `target` and return the job ID immediately
+    pub async fn submit(&self, target: impl Into<String>) -> String {
+        let target = target.into();
+        let id = Uuid::new_v4().to_string();
+
+        self.jobs.write().await.insert(
+            id.clone(),
+            Job {
+                id: id.clone(),
+                target: target.clone(),
+                status: JobStatus::Queued,
+                submitted_at: Utc::now(),
+                finished_at: None,
+                error: None,
+                result: None,
+                cancel: CancellationToken::new(),
+            },
+        );
+
+        let queue = self.clone();
+        let job_id = id.clone();
+        tokio::spawn(async move {
+            queue.run(job_id, target).await;
+        });
+
+        id
+    }
+
+    /// Look up a job by ID
+    pub async fn get(&self, id: &str) -> Option<Job> {
+        self.jobs.read().await.get(id).cloned()
+    }
+
+    /// Request cancellation of a queued or running job
+    ///
+    /// Queued jobs never start; running jobs stop after the current file and
+    /// keep their partial result. Returns the job, or `None` if it doesn't exist.
+    pub async fn cancel(&self, id: &str) -> Option<Job> {
+        let job = self.get(id).await?;
+        if !job.is_finished() {
+            info!("Cancelling job {}", id);
+            job.cancel.cancel();
+        }
+        Some(job)
+    }
+
+    /// Wait until a job has finished (completed, failed, or cancelled) and return it
+    ///
+    /// Returns `None` if no job with this ID exists.
+    pub async fn wait(&self, id: &str) -> Option<Job> {
+        loop {
+            // Register interest before checking so a completion in between isn't missed
+            let notified = self.finished.notified();
+            let job = self.get(id).await?;
+            if job.is_finished() {
+                return Some(job);
+            }
+            notified.await;
+        }
+    }
+
+    /// List all jobs known to this daemon instance, newest first
+    pub async fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.jobs.read().await.values().cloned().collect();
+        jobs.sort_by_key(|j| std::cmp::Reverse(j.submitted_at));
+        jobs
+    }
+
+    async fn run(&self, id: String, target: String) {
+        let _permit = match self.permits.acquire().await {
+            Ok(permit) => permit,
+            Err(_) => return, // Semaphore closed: daemon shutting down
+        };
+
+        let Some(cancel) = self.get(&id).await.map(|job| job.cancel) else {
+            return;
+        };
+        if cancel.is_cancelled() {
+            self.update(&id, |job| job.status = JobStatus::Cancelled)
+                .await;
+            info!("Job {} cancelled before it started", id);
+            return;
+        }
+
+        self.update(&id, |job| job.status = JobStatus::Running)
+            .await;
+        info!("Job {} started: {}", id, target);
+
+        let scanner = Scanner::builder()
+            .config(self.config.clone())
+            .cancellation_token(cancel)
+            .build();
+        let outcome = if PathBuf::from(&target).is_dir() {
+            scanner.scan_directory(&target).await
+        } else {
+            Err(anyhow::anyhow!(
+                "Target must be an existing directory: '{}'",
+                target
+            ))
+        };
+
+        match outcome {
+            Ok(result) if result.metadata.incomplete => {
+                warn!("Job {} cancelled", id);
+                self.update(&id, |job| {
+                    job.status = JobStatus::Cancelled;
+                    job.result = Some(result);
+                })
+                .await;
+            }
+            Ok(result) => {
+                if let Some(history) = &self.history {
+                    if let Err(e) = history.record(&result) {
+                        error!("Failed to store result of job {} in history: {}", id, e);
+                    }
+                }
+                self.update(&id, |job| {
+                    job.status = JobStatus::Completed;
+                    job.result = Some(result);
+                })
+                .await;
+                info!("Job {} completed", id);
+            }
+            Err(e) => {
+                error!("Job {} failed: {}", id, e);
+                self.update(&id, |job| {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(format!("{:#}", e));
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn update(&self, id: &str, apply: impl FnOnce(&mut Job)) {
+        let mut finished = false;
+        if let Some(job) = self.jobs.write().await.get_mut(id) {
+            apply(job);
+            if job.is_finished() {
+                job.finished_at = Some(Utc::now());
+                finished = true;
+            }
+        }
+        if finished {
+            self.finished.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_job_completes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("server.py"), "import os\n").unwrap();
+
+        let queue = JobQueue::new(ScanConfig::default(), 1, None);
+        let id = queue.submit(temp_dir.path().to_string_lossy()).await;
+
+        for _ in 0..100 {
+            if queue.get(&id).await.unwrap().status == JobStatus::Completed {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        panic!("job did not complete");
+    }
+
+    #[tokio::test]
+    async fn test_missing_target_fails() {
+        let queue = JobQueue::new(ScanConfig::default(), 1, None);
+        let id = queue.submit("/definitely/not/a/real/path").await;
+
+        let job = queue.wait(&id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let queue = JobQueue::new(ScanConfig::default(), 1, None);
+
+        // Hold the only permit so the job stays queued
+        let permit = queue.permits.clone().acquire_owned().await.unwrap();
+        let id = queue.submit(temp_dir.path().to_string_lossy()).await;
+        assert_eq!(queue.cancel(&id).await.unwrap().status, JobStatus::Queued);
+        drop(permit);
+
+        let job = queue.wait(&id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Cancelled);
+        assert!(queue.cancel("missing").await.is_none());
+    }
+}
@@ -0,0 +1,12 @@
+//! Service APIs for running MCP Sentinel as a long-lived daemon
+//!
+//! - `jobs` - Background scan job queue shared by all APIs
+//! - `rest` - HTTP/JSON API (`mcp-sentinel serve`)
+//! - `grpc` - Protobuf service (`serve --grpc-listen`, `grpc` feature)
+//! - `mcp_server` - MCP over stdio so agents can call Sentinel as a tool (`mcp-serve`)
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod jobs;
+pub mod mcp_server;
+pub mod rest;
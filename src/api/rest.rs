@@ -0,0 +1,210 @@
+//! REST API for the long-running daemon
+//!
+//! # Endpoints
+//!
+//! | Method | Path | Description |
+//! |--------|------|-------------|
+//! | GET | `/health` | Liveness probe |
+//! | POST | `/api/v1/scans` | Submit a scan job (`{"target": "..."}`) |
+//! | GET | `/api/v1/scans` | List jobs submitted to this daemon |
+//! | GET | `/api/v1/scans/:id` | Job status and result |
+//! | GET | `/api/v1/history` | Recent stored scans (`?limit=N`) |
+//! | GET | `/api/v1/history/:scan_id` | Full stored scan result |
+//! | GET | `/api/v1/whitelist` | Whitelisted items |
+//! | POST | `/api/v1/whitelist` | Add an item (`{"item_type", "name", "hash"}`) |
+//! | DELETE | `/api/v1/whitelist/:hash` | Remove an item |
+//! | GET | `/metrics` | Prometheus metrics |
+//!
+//! Errors are returned as `{"error": "..."}` with an appropriate status code.
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::info;
+
+use super::jobs::JobQueue;
+use crate::storage::history::HistoryStore;
+use crate::storage::whitelist::{Whitelist, WhitelistEntry};
+
+/// Shared state behind every handler
+pub struct AppState {
+    pub jobs: JobQueue,
+    pub history: Arc<HistoryStore>,
+    pub whitelist_path: PathBuf,
+    /// Serializes read-modify-write cycles on the whitelist file
+    pub whitelist_lock: Mutex<()>,
+}
+
+/// API error rendered as a JSON body
+pub struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(what: impl Into<String>) -> Self {
+        Self(StatusCode::NOT_FOUND, what.into())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<T, ApiError>;
+
+/// Build the daemon router (including `/metrics`)
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/api/v1/scans", get(list_jobs).post(submit_scan))
+        .route("/api/v1/scans/:id", get(get_job).delete(cancel_job))
+        .route("/api/v1/history", get(list_history))
+        .route("/api/v1/history/:scan_id", get(get_history))
+        .route("/api/v1/whitelist", get(list_whitelist).post(add_whitelist))
+        .route("/api/v1/whitelist/:hash", delete(remove_whitelist))
+        .with_state(state)
+        .merge(crate::utils::metrics::router())
+}
+
+/// Serve the REST API on `addr` until the process exits
+pub async fn serve(addr: SocketAddr, state: Arc<AppState>) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("🌐 REST API listening on http://{}", addr);
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+async fn health() -> impl IntoResponse {
+    Json(json!({ "status": "ok", "version": crate::VERSION }))
+}
+
+#[derive(Deserialize)]
+struct ScanRequest {
+    target: String,
+}
+
+async fn submit_scan(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ScanRequest>,
+) -> ApiResult<impl IntoResponse> {
+    if request.target.trim().is_empty() {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            "target is required".into(),
+        ));
+    }
+
+    let job_id = state.jobs.submit(request.target).await;
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({ "job_id": job_id, "status": "queued" })),
+    ))
+}
+
+async fn list_jobs(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    Json(state.jobs.list().await)
+}
+
+async fn get_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    state
+        .jobs
+        .get(&id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("job '{}' not found", id)))
+}
+
+async fn cancel_job(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    state
+        .jobs
+        .cancel(&id)
+        .await
+        .map(|job| (StatusCode::ACCEPTED, Json(job)))
+        .ok_or_else(|| ApiError::not_found(format!("job '{}' not found", id)))
+}
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    limit: Option<usize>,
+}
+
+async fn list_history(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HistoryQuery>,
+) -> ApiResult<impl IntoResponse> {
+    Ok(Json(state.history.recent(query.limit.unwrap_or(20))?))
+}
+
+async fn get_history(
+    State(state): State<Arc<AppState>>,
+    Path(scan_id): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    state
+        .history
+        .get(&scan_id)?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("scan '{}' not found", scan_id)))
+}
+
+async fn list_whitelist(State(state): State<Arc<AppState>>) -> ApiResult<impl IntoResponse> {
+    let _guard = state.whitelist_lock.lock().await;
+    Ok(Json(Whitelist::load(&state.whitelist_path)?.entries))
+}
+
+#[derive(Deserialize)]
+struct WhitelistRequest {
+    item_type: String,
+    name: String,
+    hash: String,
+}
+
+async fn add_whitelist(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<WhitelistRequest>,
+) -> ApiResult<impl IntoResponse> {
+    let _guard = state.whitelist_lock.lock().await;
+    let mut whitelist = Whitelist::load(&state.whitelist_path)?;
+    let entry = WhitelistEntry::new(request.item_type, request.name, request.hash);
+    whitelist.add(entry.clone());
+    whitelist.save(&state.whitelist_path)?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+async fn remove_whitelist(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    let _guard = state.whitelist_lock.lock().await;
+    let mut whitelist = Whitelist::load(&state.whitelist_path)?;
+    if !whitelist.remove(&hash) {
+        return Err(ApiError::not_found(format!(
+            "hash '{}' not whitelisted",
+            hash
+        )));
+    }
+    whitelist.save(&state.whitelist_path)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,12 @@
+//! Language bindings for embedding the scanner in other ecosystems
+//!
+//! - `python` - PyO3 extension module (`python` feature, built with maturin)
+//! - `wasm` - wasm-bindgen API for browsers and Node (`wasm` feature)
+//! - `ffi` - C ABI with `include/mcp_sentinel.h` (`ffi` feature)
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,179 @@
+//! Python bindings
+//!
+//! Builds the `mcp_sentinel` extension module with maturin:
+//!
+//! ```text
+//! maturin develop --features python
+//! ```
+//!
+//! ```python
+//! import mcp_sentinel
+//!
+//! report = mcp_sentinel.scan_path("./my-mcp-server")
+//! for finding in report.findings:
+//!     print(finding.severity, finding.title, finding.file, finding.line)
+//!
+//! findings = mcp_sentinel.scan_content(source, "server.py")
+//! ```
+//!
+//! Scans release the GIL, so other Python threads keep running meanwhile.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::models::{config::ScanConfig, scan_result::ScanResult, vulnerability::Vulnerability};
+use crate::scanner::Scanner;
+
+/// A single finding, mirroring `Vulnerability` with flattened location
+#[pyclass(module = "mcp_sentinel", name = "Finding", get_all, frozen)]
+#[derive(Debug, Clone)]
+pub struct PyFinding {
+    id: String,
+    rule_id: Option<String>,
+    vuln_type: String,
+    severity: String,
+    confidence: f32,
+    title: String,
+    description: String,
+    file: Option<String>,
+    line: Option<usize>,
+    column: Option<usize>,
+    remediation: Option<String>,
+    code_snippet: Option<String>,
+}
+
+#[pymethods]
+impl PyFinding {
+    fn __repr__(&self) -> String {
+        format!(
+            "Finding(id={:?}, severity={:?}, title={:?}, file={:?}, line={:?})",
+            self.id, self.severity, self.title, self.file, self.line
+        )
+    }
+}
+
+impl From<&Vulnerability> for PyFinding {
+    fn from(vuln: &Vulnerability) -> Self {
+        let location = vuln.location.as_ref();
+        Self {
+            id: vuln.id.clone(),
+            rule_id: vuln.rule_id.clone(),
+            vuln_type: serde_name(&vuln.vuln_type),
+            severity: serde_name(&vuln.severity),
+            confidence: vuln.confidence,
+            title: vuln.title.clone(),
+            description: vuln.description.clone(),
+            file: location.map(|l| l.file.clone()),
+            line: location.and_then(|l| l.line),
+            column: location.and_then(|l| l.column),
+            remediation: vuln.remediation.clone(),
+            code_snippet: vuln.code_snippet.clone(),
+        }
+    }
+}
+
+/// Result of `scan_path`
+#[pyclass(module = "mcp_sentinel", name = "ScanReport", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyScanReport {
+    #[pyo3(get)]
+    scan_id: String,
+    #[pyo3(get)]
+    target: String,
+    #[pyo3(get)]
+    risk_score: u8,
+    #[pyo3(get)]
+    incomplete: bool,
+    #[pyo3(get)]
+    findings: Vec<PyFinding>,
+    json: String,
+}
+
+#[pymethods]
+impl PyScanReport {
+    /// Full report in the same JSON format as `mcp-sentinel scan --output json`
+    fn to_json(&self) -> String {
+        self.json.clone()
+    }
+
+    fn __len__(&self) -> usize {
+        self.findings.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ScanReport(target={:?}, findings={}, risk_score={})",
+            self.target,
+            self.findings.len(),
+            self.risk_score
+        )
+    }
+}
+
+impl TryFrom<&ScanResult> for PyScanReport {
+    type Error = anyhow::Error;
+
+    fn try_from(result: &ScanResult) -> anyhow::Result<Self> {
+        Ok(Self {
+            scan_id: result.scan_id.clone(),
+            target: result.target.clone(),
+            risk_score: result.summary.risk_score,
+            incomplete: result.metadata.incomplete,
+            findings: result.vulnerabilities.iter().map(Into::into).collect(),
+            json: crate::output::json::generate(result)?,
+        })
+    }
+}
+
+/// Scan a directory and return a `ScanReport`
+#[pyfunction]
+#[pyo3(signature = (path, min_confidence = 0.0))]
+fn scan_path(py: Python<'_>, path: String, min_confidence: f32) -> PyResult<PyScanReport> {
+    if !std::path::Path::new(&path).is_dir() {
+        return Err(PyValueError::new_err(format!(
+            "'{}' is not a directory",
+            path
+        )));
+    }
+
+    let scanner = Scanner::builder().min_confidence(min_confidence).build();
+    let result = py.allow_threads(|| {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(scanner.scan_directory(&path))
+    });
+
+    result
+        .and_then(|r| PyScanReport::try_from(&r))
+        .map_err(|e| PyRuntimeError::new_err(format!("{:#}", e)))
+}
+
+/// Scan in-memory source code; `filename` selects language-specific rules
+#[pyfunction]
+#[pyo3(signature = (content, filename = "<memory>"))]
+fn scan_content(py: Python<'_>, content: &str, filename: &str) -> Vec<PyFinding> {
+    let scanner = Scanner::new(ScanConfig::default());
+    py.allow_threads(|| scanner.scan_content(content, filename))
+        .iter()
+        .map(Into::into)
+        .collect()
+}
+
+#[pymodule]
+fn mcp_sentinel(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("__version__", crate::VERSION)?;
+    m.add_class::<PyFinding>()?;
+    m.add_class::<PyScanReport>()?;
+    m.add_function(wrap_pyfunction!(scan_path, m)?)?;
+    m.add_function(wrap_pyfunction!(scan_content, m)?)?;
+    Ok(())
+}
+
+/// Serialized name of a unit enum variant (e.g. `"critical"`)
+fn serde_name<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
@@ -0,0 +1,47 @@
+//! WASM bindings
+//!
+//! Exposes the I/O-free detector core to JavaScript so editor extensions and
+//! web UIs can scan snippets and tool descriptions client-side:
+//!
+//! ```text
+//! wasm-pack build --target web -- --no-default-features --features wasm
+//! ```
+//!
+//! ```js
+//! import init, { scan_text } from "mcp_sentinel";
+//!
+//! await init();
+//! const findings = scan_text(source, "server.py");
+//! findings.forEach((f) => console.log(f.severity, f.title, f.location?.line));
+//! ```
+//!
+//! Findings use the same shape as `vulnerabilities` in the JSON report.
+
+use wasm_bindgen::prelude::*;
+
+use crate::detectors::{self, DetectorKind};
+use crate::models::config::ScanConfig;
+
+/// Scan `content` with all detectors; `filename` selects language-specific rules
+#[wasm_bindgen]
+pub fn scan_text(content: &str, filename: &str) -> Result<JsValue, JsError> {
+    let findings = detectors::scan_content(content, filename, &ScanConfig::default());
+    Ok(serde_wasm_bindgen::to_value(&findings)?)
+}
+
+/// Scan an MCP tool description for tool poisoning and prompt injection
+#[wasm_bindgen]
+pub fn scan_tool_description(description: &str) -> Result<JsValue, JsError> {
+    let config = ScanConfig {
+        detectors: vec![DetectorKind::ToolPoisoning, DetectorKind::PromptInjection],
+        ..ScanConfig::default()
+    };
+    let findings = detectors::scan_content(description, "<tool description>", &config);
+    Ok(serde_wasm_bindgen::to_value(&findings)?)
+}
+
+/// Crate version, for display in extension UIs
+#[wasm_bindgen]
+pub fn version() -> String {
+    crate::VERSION.to_string()
+}
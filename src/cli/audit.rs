@@ -1,12 +1,42 @@
 //! Audit command implementation
 
 use anyhow::Result;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::warn;
 
 use super::types::{LlmProvider, OutputFormat};
+use crate::models::scan_result::ScanResult;
+use crate::output::terminal;
+use crate::scanner::Scanner;
+use crate::utils::clients::{self, Client};
+use crate::utils::package::{self, PackageSpec};
+use crate::utils::provenance;
+
+/// Where a discovered server's code comes from
+enum Source {
+    /// A directory on this machine
+    Local(PathBuf),
+    /// A registry package its launcher downloads
+    Package(PackageSpec),
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Local(root) => write!(f, "{}", root.display()),
+            Source::Package(spec) => write!(f, "{}", spec.requirement()),
+        }
+    }
+}
 
 #[allow(clippy::too_many_arguments)]
 pub async fn execute(
-    target: String,
+    target: Option<String>,
+    discover: bool,
+    yes: bool,
     include_proxy: bool,
     duration: u64,
     comprehensive: bool,
@@ -16,6 +46,129 @@ pub async fn execute(
     output: OutputFormat,
     output_file: Option<String>,
 ) -> Result<()> {
+    if discover {
+        return discover_servers(yes).await;
+    }
+
     // Phase 2/4 implementation
     anyhow::bail!("Audit command not yet implemented - Phase 2/4")
 }
+
+/// List the servers of every MCP client configuration on this machine, then
+/// scan the ones that run from a local directory or an npm/PyPI package,
+/// asking first unless `scan_all`
+async fn discover_servers(scan_all: bool) -> Result<()> {
+    let configs = clients::discover();
+    if configs.is_empty() {
+        println!("No MCP client configurations found");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Client", "Server", "Runs", "Config"]);
+    let mut scannable: Vec<(Client, String, Source)> = Vec::new();
+    for config in &configs {
+        for server in &config.servers {
+            let runs = match (&server.command, &server.url) {
+                (Some(command), _) => std::iter::once(command)
+                    .chain(&server.args)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                (None, Some(url)) => url.clone(),
+                (None, None) => String::new(),
+            };
+            table.add_row(vec![
+                config.client.name().to_string(),
+                server.name.clone(),
+                runs,
+                config.path.display().to_string(),
+            ]);
+            // A package launcher's path arguments are data for the server
+            // (`npx -y server-filesystem ~/docs`), not its code
+            let source = clients::launched_package(server)
+                .map(Source::Package)
+                .or_else(|| clients::local_root(server).map(Source::Local));
+            if let Some(source) = source {
+                scannable.push((config.client, server.name.clone(), source));
+            }
+        }
+    }
+    let total: usize = configs.iter().map(|c| c.servers.len()).sum();
+    println!(
+        "🔎 {} servers in {} client configurations",
+        total,
+        configs.len()
+    );
+    println!("{}", table);
+
+    if total > scannable.len() {
+        println!(
+            "ℹ️  {} servers run from a URL or an unrecognized launcher and are not scanned",
+            total - scannable.len()
+        );
+    }
+    if scannable.is_empty() {
+        return Ok(());
+    }
+    let interactive = std::io::stdin().is_terminal();
+    if !scan_all && !interactive {
+        println!(
+            "Run with --yes to scan the {} servers in local directories or packages",
+            scannable.len()
+        );
+        return Ok(());
+    }
+
+    let scanner = Scanner::builder().build();
+    let mut stdin = BufReader::new(tokio::io::stdin());
+    for (client, name, source) in scannable {
+        if !scan_all {
+            eprint!("Scan {} ({}, {})? [y/N] ", name, client.name(), source);
+            let mut answer = String::new();
+            stdin.read_line(&mut answer).await?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                continue;
+            }
+        }
+        let result = match &source {
+            Source::Local(root) => scanner.scan_directory(root).await,
+            Source::Package(spec) => scan_package(&scanner, spec).await,
+        };
+        match result {
+            Ok(result) => terminal::render(&result)?,
+            Err(e) => warn!("Could not scan {} ({}): {:#}", name, source, e),
+        }
+    }
+    Ok(())
+}
+
+/// Download the version of `spec` its launcher would run and scan it,
+/// including its provenance
+async fn scan_package(scanner: &Scanner, spec: &PackageSpec) -> Result<ScanResult> {
+    let workdir = tempfile::tempdir()?;
+    let dest = workdir.path().to_path_buf();
+    let fetch_spec = spec.clone();
+    let fetched = tokio::task::spawn_blocking(move || package::fetch(&fetch_spec, &dest)).await??;
+    let resolved = PackageSpec::new(
+        spec.ecosystem,
+        spec.name.clone(),
+        package::artifact_version(spec, &fetched.artifact).or_else(|| spec.version.clone()),
+    );
+    println!("📦 Scanning {}", resolved.requirement());
+
+    let mut result = scanner.scan_directory(&fetched.root).await?;
+    result.target = resolved.requirement();
+    match provenance::verify(&resolved, &fetched.artifact, &fetched.root).await {
+        Ok(findings) => result.add_vulnerabilities(findings),
+        Err(e) => warn!(
+            "Provenance check of {} failed: {:#}",
+            resolved.requirement(),
+            e
+        ),
+    }
+    Ok(result)
+}
@@ -0,0 +1,25 @@
+//! Badge command implementation
+
+use anyhow::{Context, Result};
+
+use crate::models::scan_result::ScanResult;
+
+/// Write an SVG status badge for the JSON scan report at `input` to
+/// `output`, or to stdout
+pub async fn execute(input: String, output: Option<String>) -> Result<()> {
+    let json = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read scan report '{}'", input))?;
+    let result: ScanResult = serde_json::from_str(&json)
+        .with_context(|| format!("'{}' is not a JSON scan report (scan --output json)", input))?;
+    let svg = crate::output::badge::generate(&result)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, svg)
+                .with_context(|| format!("Failed to write badge to '{}'", path))?;
+            println!("✅ Badge saved to: {}", path);
+        }
+        None => print!("{}", svg),
+    }
+    Ok(())
+}
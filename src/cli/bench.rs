@@ -0,0 +1,172 @@
+//! Bench command implementation
+
+use anyhow::{Context, Result};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::detectors::DetectorKind;
+use crate::models::config::{AppConfig, ScanConfig};
+use crate::models::project_config::ProjectConfig;
+
+/// Time spent and matches found by one detector
+#[derive(Default)]
+struct DetectorStats {
+    time: Duration,
+    matches: usize,
+    /// Slowest file for this detector
+    slowest: Option<(Duration, String)>,
+}
+
+/// Time every enabled detector on every file of `target`, and report per
+/// detector wall time and matches, and the `top` slowest files
+pub async fn execute(target: String, top: usize, config: Option<String>) -> Result<()> {
+    let target_path = PathBuf::from(&target);
+    if !target_path.is_dir() {
+        anyhow::bail!("Target must be a directory: '{}'", target);
+    }
+
+    let mut scan_config = ScanConfig {
+        rule_bundle: super::rules::installed_bundle(&AppConfig::default()),
+        ..Default::default()
+    };
+    let project = match &config {
+        Some(path) => Some(ProjectConfig::load(Path::new(path))?),
+        None => ProjectConfig::discover(&target_path)?,
+    };
+    if let Some(project) = project {
+        project.apply_to(&mut scan_config)?;
+    }
+
+    let files = crate::utils::file::discover_files(&target_path, &scan_config.exclude_patterns)
+        .context("Failed to discover files")?;
+
+    let mut detectors: Vec<(DetectorKind, DetectorStats)> = scan_config
+        .detectors
+        .iter()
+        .map(|d| (*d, DetectorStats::default()))
+        .collect();
+    // (time, file, slowest detector)
+    let mut file_times: Vec<(Duration, String, Option<DetectorKind>)> = Vec::new();
+    let mut bytes = 0u64;
+
+    for file in &files {
+        let content = match std::fs::metadata(file) {
+            Ok(metadata) if metadata.len() > scan_config.max_file_size as u64 => continue,
+            _ => match crate::utils::file::read_file(file) {
+                Ok(content) => content,
+                Err(e) => {
+                    debug!("Skipping file {}: {}", file.display(), e);
+                    continue;
+                }
+            },
+        };
+        bytes += content.len() as u64;
+        let file_path = file.to_string_lossy().to_string();
+
+        let mut file_time = Duration::ZERO;
+        let mut slowest: Option<(Duration, DetectorKind)> = None;
+        for (detector, stats) in &mut detectors {
+            let start = Instant::now();
+            let run = detector.run(&content, &file_path, &scan_config);
+            let elapsed = start.elapsed();
+
+            match run {
+                Ok(vulns) => stats.matches += vulns.len(),
+                Err(e) => warn!(
+                    "{} detector failed on {}: {}",
+                    detector.name(),
+                    file_path,
+                    e
+                ),
+            }
+            stats.time += elapsed;
+            if stats.slowest.as_ref().is_none_or(|(t, _)| elapsed > *t) {
+                stats.slowest = Some((elapsed, file_path.clone()));
+            }
+            file_time += elapsed;
+            if slowest.is_none_or(|(t, _)| elapsed > t) {
+                slowest = Some((elapsed, *detector));
+            }
+        }
+        file_times.push((file_time, file_path, slowest.map(|(_, d)| d)));
+    }
+
+    let total: Duration = detectors.iter().map(|(_, stats)| stats.time).sum();
+    println!(
+        "⏱️  {} detectors on {} files ({} KB) in {}",
+        detectors.len(),
+        file_times.len(),
+        bytes / 1024,
+        format_duration(total)
+    );
+    println!();
+
+    detectors.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.time));
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Detector", "Time", "Share", "Matches", "Slowest file"]);
+    for (detector, stats) in &detectors {
+        let share = match total.as_secs_f64() {
+            t if t > 0.0 => stats.time.as_secs_f64() / t * 100.0,
+            _ => 0.0,
+        };
+        let slowest = stats
+            .slowest
+            .as_ref()
+            .map(|(time, file)| {
+                format!(
+                    "{} ({})",
+                    relative(file, &target_path),
+                    format_duration(*time)
+                )
+            })
+            .unwrap_or_default();
+        table.add_row(vec![
+            detector.id().to_string(),
+            format_duration(stats.time),
+            format!("{:.1}%", share),
+            stats.matches.to_string(),
+            slowest,
+        ]);
+    }
+    println!("{}", table);
+
+    if top == 0 || file_times.is_empty() {
+        return Ok(());
+    }
+    file_times.sort_by_key(|(time, _, _)| std::cmp::Reverse(*time));
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Slowest files", "Time", "Slowest detector"]);
+    for (time, file, slowest) in file_times.iter().take(top) {
+        table.add_row(vec![
+            relative(file, &target_path),
+            format_duration(*time),
+            slowest.map(|d| d.id().to_string()).unwrap_or_default(),
+        ]);
+    }
+    println!();
+    println!("{}", table);
+    Ok(())
+}
+
+fn relative(file: &str, root: &Path) -> String {
+    Path::new(file)
+        .strip_prefix(root)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| file.to_string())
+}
+
+fn format_duration(duration: Duration) -> String {
+    match duration.as_micros() {
+        0..=999 => format!("{}µs", duration.as_micros()),
+        1_000..=999_999 => format!("{:.1}ms", duration.as_secs_f64() * 1000.0),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
@@ -0,0 +1,93 @@
+//! Fuzz command implementation
+
+use anyhow::{Context, Result};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use super::types::{OutputFormat, SeverityLevel};
+use crate::engines::dynamic_analysis::fuzz::{self, DEFAULT_CASE_TIMEOUT};
+use crate::models::scan_result::ScanResult;
+
+/// Arguments of `mcp-sentinel fuzz`
+#[derive(clap::Args, Debug, Clone)]
+pub struct FuzzArgs {
+    /// Seconds the server gets to recover after each case
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_CASE_TIMEOUT.as_secs())]
+    pub case_timeout: u64,
+
+    /// Directory to start the server in (default: current directory)
+    #[arg(long, value_name = "PATH")]
+    pub cwd: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "terminal")]
+    pub output: OutputFormat,
+
+    /// Save report to file
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Exit with code 1 if findings >= level
+    #[arg(long, value_enum)]
+    pub fail_on: Option<SeverityLevel>,
+
+    /// Run the server without a sandbox
+    ///
+    /// The server is untrusted code: unconfined, it can read and change
+    /// every file you can, and only its environment is scrubbed.
+    #[arg(long)]
+    pub unsandboxed: bool,
+
+    /// MCP server command to fuzz (stdio transport)
+    #[arg(
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        value_name = "COMMAND"
+    )]
+    pub command: Vec<String>,
+}
+
+pub async fn execute(args: FuzzArgs) -> Result<()> {
+    let dir = match &args.cwd {
+        Some(dir) => dir.into(),
+        None => std::env::current_dir()?,
+    };
+    let start = Instant::now();
+    let findings = fuzz::fuzz(
+        &args.command,
+        &dir,
+        Duration::from_secs(args.case_timeout),
+        args.unsandboxed,
+    )
+    .await
+    .context("Fuzzing failed")?;
+
+    let mut result = ScanResult::new(args.command.join(" "), vec!["fuzz".to_string()]);
+    result.add_vulnerabilities(findings);
+    result.set_duration(start.elapsed().as_millis() as u64);
+
+    match args.output {
+        OutputFormat::Terminal => crate::output::terminal::render(&result)?,
+        OutputFormat::Json => {
+            let json = crate::output::json::generate(&result)?;
+            match &args.output_file {
+                Some(path) => {
+                    std::fs::write(path, &json)
+                        .with_context(|| format!("Failed to write report to '{}'", path))?;
+                    info!("Report saved to: {}", path);
+                    println!("✅ Report saved to: {}", path);
+                }
+                None => println!("{}", json),
+            }
+        }
+        other => anyhow::bail!("Output format {:?} not yet implemented", other),
+    }
+
+    if let Some(threshold) = args.fail_on {
+        if result.has_issues_at_level(threshold.clone().into()) {
+            anyhow::bail!("Found robustness issues at or above {:?} level", threshold);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Gc command implementation
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+use crate::engines::runtime_proxy::audit;
+use crate::models::config::{AppConfig, RetentionPolicy};
+use crate::storage::history::HistoryStore;
+
+/// Prune the history database and audit logs, with `max_age_days` and
+/// `max_size_mb` overriding the configured retention of both
+pub async fn execute(
+    max_age_days: Option<u32>,
+    max_size_mb: Option<u64>,
+    audit_logs: Vec<String>,
+) -> Result<()> {
+    let app = AppConfig::default();
+    let policy = |configured: RetentionPolicy| RetentionPolicy {
+        max_age_days: max_age_days.or(configured.max_age_days),
+        max_size_mb: max_size_mb.or(configured.max_size_mb),
+    };
+
+    if app.history_path.exists() {
+        // sled allows one process at a time
+        let history = HistoryStore::open(&app.history_path)
+            .context("Is `serve` or `schedule run` using the history database?")?;
+        let pruned = history.prune(&policy(app.retention.history.clone()))?;
+        println!(
+            "✅ Pruned {} scans from history ({} kept)",
+            pruned,
+            history.len()
+        );
+    }
+
+    let retention = policy(app.retention.audit_log.clone());
+    let mut logs = vec![app.audit_log_path];
+    logs.extend(audit_logs.into_iter().map(PathBuf::from));
+    logs.dedup();
+    for log in logs.iter().filter(|log| log.exists()) {
+        let pruned = audit::prune(log, &retention)?;
+        println!("✅ Pruned {} entries from {}", pruned, log.display());
+    }
+    Ok(())
+}
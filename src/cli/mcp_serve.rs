@@ -0,0 +1,11 @@
+//! MCP server command implementation
+
+use anyhow::Result;
+
+use crate::api::mcp_server::McpServer;
+use crate::models::config::ScanConfig;
+
+/// Serve MCP over stdio until the client disconnects
+pub async fn execute() -> Result<()> {
+    McpServer::new(ScanConfig::default()).run_stdio().await
+}
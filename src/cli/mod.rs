@@ -1,11 +1,19 @@
 //! Command-line interface implementations for all mcp-sentinel commands
 
 pub mod audit;
+pub mod badge;
+pub mod bench;
+pub mod fuzz;
+pub mod gc;
 pub mod init;
+pub mod mcp_serve;
 pub mod monitor;
 pub mod proxy;
 pub mod rules;
 pub mod scan;
+pub mod schedule;
+pub mod serve;
+pub mod triage;
 pub mod types;
 pub mod whitelist;
 
@@ -1,9 +1,21 @@
 //! Monitor command implementation
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{error, info, warn};
 
 use super::types::SeverityLevel;
+use crate::models::config::ScanConfig;
+use crate::models::vulnerability::Severity;
+use crate::scanner::Scanner;
 
+/// Rescan a directory on a fixed interval until interrupted
+///
+/// Each pass is recorded in the process metrics, so when `metrics_addr` is set
+/// the monitor doubles as a Prometheus target for continuous posture tracking.
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     target: String,
     interval: u64,
@@ -11,7 +23,82 @@ pub async fn execute(
     daemon: bool,
     pid_file: Option<String>,
     alert_on: Option<SeverityLevel>,
+    metrics_addr: Option<SocketAddr>,
 ) -> Result<()> {
-    // Phase 3 implementation
-    anyhow::bail!("Monitor command not yet implemented - Phase 3")
+    let target_path = PathBuf::from(&target);
+    if !target_path.is_dir() {
+        anyhow::bail!(
+            "Target must be an existing directory: '{}'\nPlease provide a directory to monitor.",
+            target
+        );
+    }
+
+    if watch {
+        // File watching lands with the notify integration; interval rescans still apply
+        warn!(
+            "--watch is not supported yet, falling back to {}s interval rescans",
+            interval
+        );
+    }
+
+    if daemon {
+        info!("Running in foreground; use a process supervisor (systemd, launchd) to daemonize");
+    }
+
+    if let Some(pid_path) = &pid_file {
+        std::fs::write(pid_path, std::process::id().to_string())
+            .with_context(|| format!("Failed to write PID file '{}'", pid_path))?;
+    }
+
+    if let Some(addr) = metrics_addr {
+        tokio::spawn(async move {
+            if let Err(e) = crate::utils::metrics::serve(addr).await {
+                error!("Metrics endpoint on {} failed: {}", addr, e);
+            }
+        });
+    }
+
+    let scanner = Scanner::new(ScanConfig::default());
+    let threshold: Option<Severity> = alert_on.map(Into::into);
+    let period = Duration::from_secs(interval.max(1));
+
+    info!("👀 Monitoring {} every {}s", target, period.as_secs());
+
+    loop {
+        match scanner.scan_directory(&target_path).await {
+            Ok(result) => {
+                info!(
+                    "Monitor pass: {} issues ({} critical, {} high), risk score {}",
+                    result.summary.total_issues,
+                    result.summary.critical,
+                    result.summary.high,
+                    result.summary.risk_score
+                );
+
+                if let Some(level) = threshold {
+                    if result.has_issues_at_level(level) {
+                        warn!(
+                            "🚨 Vulnerabilities at or above {:?} found in {}",
+                            level, target
+                        );
+                    }
+                }
+            }
+            Err(e) => error!("Monitor scan of '{}' failed: {}", target, e),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(period) => {}
+            _ = tokio::signal::ctrl_c() => {
+                info!("Monitor stopped");
+                break;
+            }
+        }
+    }
+
+    if let Some(pid_path) = &pid_file {
+        let _ = std::fs::remove_file(pid_path);
+    }
+
+    Ok(())
 }
@@ -1,20 +1,403 @@
 //! Proxy command implementation
 
 use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
 
 use super::types::SeverityLevel;
+use crate::engines::runtime_proxy::audit::{self, AuditFormat};
+use crate::engines::runtime_proxy::auth::{ClientCredentials, SecretRef, TokenSource};
+use crate::engines::runtime_proxy::events::EVENT_BUFFER;
+use crate::engines::runtime_proxy::guardrails::Guardrails;
+use crate::engines::runtime_proxy::http::{self, Upstream};
+use crate::engines::runtime_proxy::multiplex::{self, Backend};
+use crate::engines::runtime_proxy::{self, jsonrpc, quarantine, Interceptor, ProxyConfig};
+use crate::engines::runtime_proxy::{dashboard, reload};
+use crate::models::config::AppConfig;
+use crate::storage::allowlist;
 
-#[allow(clippy::too_many_arguments)]
-pub async fn execute(
-    config: Option<String>,
-    port: u16,
-    guardrails: Option<String>,
-    log_traffic: bool,
-    log_file: Option<String>,
-    block_on_risk: Option<SeverityLevel>,
-    alert_webhook: Option<String>,
-    dashboard: bool,
-) -> Result<()> {
-    // Phase 3 implementation
-    anyhow::bail!("Proxy command not yet implemented - Phase 3")
+/// Arguments of `mcp-sentinel proxy`
+#[derive(clap::Args, Debug, Clone)]
+pub struct ProxyArgs {
+    /// MCP configuration file whose servers to proxy, all behind this one
+    /// proxy; their tools are named `<server>__<tool>`
+    #[arg(short, long, conflicts_with_all = ["command", "upstream"])]
+    pub config: Option<String>,
+
+    /// Proxy listen port
+    #[arg(short, long, default_value = "8080")]
+    pub port: u16,
+
+    /// Guardrails policy file (YAML; default: ~/.mcp-sentinel/guardrails.yaml
+    /// if present)
+    #[arg(short, long)]
+    pub guardrails: Option<String>,
+
+    /// Name of the proxied server, selecting its section of the guardrails
+    #[arg(long, value_name = "NAME")]
+    pub server_name: Option<String>,
+
+    /// Save all MCP traffic to log file
+    #[arg(long)]
+    pub log_traffic: bool,
+
+    /// Traffic log destination
+    #[arg(long)]
+    pub log_file: Option<String>,
+
+    /// Block requests >= risk level
+    #[arg(long, value_enum)]
+    pub block_on_risk: Option<SeverityLevel>,
+
+    /// Send alerts to webhook URL
+    #[arg(long)]
+    pub alert_webhook: Option<String>,
+
+    /// Append tool calls, findings, and blocked messages to this JSONL file
+    /// (default with --config: ~/.mcp-sentinel/audit.jsonl)
+    #[arg(long, value_name = "PATH")]
+    pub audit_log: Option<PathBuf>,
+
+    /// Format of --audit-log: MCP Sentinel's own entries, or OCSF Detection
+    /// Findings for SIEM pipelines (findings and blocked messages only)
+    #[arg(long, value_enum, default_value = "jsonl", requires = "audit_log")]
+    pub audit_format: AuditFormat,
+
+    /// Show live sessions, tool calls, blocked messages, and findings on
+    /// the terminal
+    #[arg(short, long)]
+    pub dashboard: bool,
+
+    /// Largest JSON-RPC message passed through, in bytes
+    #[arg(long, default_value_t = jsonrpc::DEFAULT_MAX_MESSAGE_BYTES)]
+    pub max_message_size: usize,
+
+    /// Refuse to start servers that are not on the signed allowlist
+    /// written by `scan --approve`
+    #[arg(long)]
+    pub quarantine: bool,
+
+    /// Allowlist for --quarantine (default: ~/.mcp-sentinel/allowlist.json)
+    #[arg(long, value_name = "PATH", requires = "quarantine")]
+    pub allowlist: Option<PathBuf>,
+
+    /// Public key the allowlist must be signed with (default:
+    /// ~/.mcp-sentinel/allowlist.pub)
+    #[arg(long, value_name = "PATH", requires = "quarantine")]
+    pub allowlist_key: Option<PathBuf>,
+
+    /// Remote MCP server to proxy: a Streamable HTTP endpoint (https://) or
+    /// a WebSocket (wss://); unencrypted only on this machine
+    #[arg(long, value_name = "URL", conflicts_with_all = ["command", "quarantine"])]
+    pub upstream: Option<String>,
+
+    /// CA certificates (PEM) trusted for the upstream instead of the system
+    /// roots
+    #[arg(long, value_name = "PATH", requires = "upstream")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// Client certificate (PEM) presented to the upstream
+    #[arg(long, value_name = "PATH", requires_all = ["upstream", "client_key"])]
+    pub client_cert: Option<PathBuf>,
+
+    /// Private key (PKCS#8 PEM) of --client-cert
+    #[arg(long, value_name = "PATH", requires = "client_cert")]
+    pub client_key: Option<PathBuf>,
+
+    /// Bearer token for the upstream, as a secret reference: env:NAME,
+    /// file:PATH, or cmd:COMMAND
+    #[arg(
+        long,
+        value_name = "SECRET",
+        requires = "upstream",
+        conflicts_with = "oauth_token_url"
+    )]
+    pub upstream_token: Option<String>,
+
+    /// OAuth token endpoint; the upstream token is obtained with the client
+    /// credentials grant
+    #[arg(
+        long,
+        value_name = "URL",
+        requires_all = ["upstream", "oauth_client_id", "oauth_client_secret"]
+    )]
+    pub oauth_token_url: Option<String>,
+
+    /// OAuth client ID
+    #[arg(long, value_name = "ID", requires = "oauth_token_url")]
+    pub oauth_client_id: Option<String>,
+
+    /// OAuth client secret, as a secret reference (see --upstream-token)
+    #[arg(long, value_name = "SECRET", requires = "oauth_token_url")]
+    pub oauth_client_secret: Option<String>,
+
+    /// OAuth scope to request
+    #[arg(long, value_name = "SCOPE", requires = "oauth_token_url")]
+    pub oauth_scope: Option<String>,
+
+    /// MCP server command to run behind the proxy (stdio transport)
+    #[arg(last = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+}
+
+pub async fn execute(args: ProxyArgs) -> Result<()> {
+    if args.command.is_empty() && args.upstream.is_none() && args.config.is_none() {
+        // Phase 3 implementation
+        anyhow::bail!(
+            "HTTP proxying on --port is not yet implemented - Phase 3\n\
+             To proxy a stdio server, pass its command after `--`: \
+             mcp-sentinel proxy -- npx my-mcp-server\n\
+             To proxy a remote server: mcp-sentinel proxy --upstream https://host/mcp\n\
+             To proxy every server of an MCP configuration: \
+             mcp-sentinel proxy --config claude_desktop_config.json"
+        );
+    }
+    for (set, flag) in [
+        (args.log_traffic || args.log_file.is_some(), "--log-traffic"),
+        (args.alert_webhook.is_some(), "--alert-webhook"),
+    ] {
+        if set {
+            warn!("{} is not supported by the proxy yet and is ignored", flag);
+        }
+    }
+
+    if args.quarantine && args.config.is_none() {
+        let (allowlist, public_key) = allowlist_paths(&args);
+        if let Some(reason) = quarantine::check(&args.command, &allowlist, &public_key) {
+            return runtime_proxy::stdio::refuse(&reason, args.max_message_size).await;
+        }
+    }
+
+    // Watched for changes even before it exists
+    let guardrails_path = match &args.guardrails {
+        Some(path) => Some(PathBuf::from(path)),
+        None => AppConfig::default().proxy.guardrails_path,
+    };
+    let guardrails = match &guardrails_path {
+        Some(path) if args.guardrails.is_some() || path.exists() => Guardrails::load(path)?,
+        _ => Guardrails::default(),
+    };
+    let guardrails = with_audit_log(&args, guardrails);
+    let config = ProxyConfig {
+        max_message_bytes: args.max_message_size,
+        block_on: args.block_on_risk.clone().map(Into::into),
+        guardrails: Guardrails::default(),
+        server: None,
+    };
+    let configured = match &args.config {
+        Some(path) => Some(multiplex::servers(Path::new(path))?),
+        None => None,
+    };
+    // The proxied servers, by name, with their policies
+    let servers: Vec<(Option<String>, Guardrails)> = match &configured {
+        Some(configured) => configured
+            .keys()
+            .map(|name| (Some(name.clone()), rules(&guardrails, Some(name))))
+            .collect(),
+        None => {
+            let name = args.server_name.as_deref();
+            vec![(args.server_name.clone(), rules(&guardrails, name))]
+        }
+    };
+    if args.dashboard && servers.iter().any(|(_, rules)| prompts(rules)) {
+        anyhow::bail!(DASHBOARD_PROMPTS);
+    }
+
+    let mut dashboard = None;
+    let mut dashboard_events = None;
+    if args.dashboard {
+        let (sender, events) = mpsc::channel(EVENT_BUFFER);
+        dashboard_events = Some(sender);
+        let target = match (&args.config, &args.upstream) {
+            (Some(path), _) => format!("{} servers of {}", servers.len(), path),
+            (None, Some(url)) => url.clone(),
+            (None, None) => args.command.join(" "),
+        };
+        let shutdown = CancellationToken::new();
+        let closed = shutdown.clone();
+        let view = tokio::spawn(async move {
+            if let Err(e) = dashboard::run(events, target, closed).await {
+                warn!("Dashboard unavailable: {:#}", e);
+            }
+        });
+        dashboard = Some((view, shutdown));
+    }
+
+    let audit_log = match (&args.audit_log, &args.config) {
+        (Some(path), _) => Some(path.clone()),
+        (None, Some(_)) => Some(AppConfig::default().audit_log_path),
+        (None, None) => None,
+    };
+    let app = AppConfig::default();
+    if !app.retention.audit_log.keeps_everything() {
+        // Blocked content is recorded even without --audit-log
+        let dlp_log = guardrails.dlp.audit_log.clone();
+        let mut logs: Vec<PathBuf> = audit_log.iter().cloned().collect();
+        logs.push(dlp_log.unwrap_or(app.audit_log_path));
+        logs.dedup();
+        tokio::spawn(audit::prune_periodically(logs, app.retention.audit_log));
+    }
+    let mut interceptors: Vec<Interceptor> = servers
+        .into_iter()
+        .map(|(server, guardrails)| {
+            let interceptor = Interceptor::new(ProxyConfig {
+                guardrails,
+                server: server.clone(),
+                ..config.clone()
+            });
+            match (&audit_log, &dashboard_events) {
+                (Some(log), forward) => {
+                    let (sender, events) = mpsc::channel(EVENT_BUFFER);
+                    tokio::spawn(audit::record(
+                        events,
+                        server,
+                        Some(log.clone()),
+                        args.audit_format,
+                        forward.clone(),
+                    ));
+                    interceptor.with_events(sender)
+                }
+                (None, Some(forward)) => interceptor.with_events(forward.clone()),
+                (None, None) => interceptor,
+            }
+        })
+        .collect();
+
+    if let Some(path) = guardrails_path {
+        // Clones share their policy with the interceptors in use
+        let sessions = interceptors.clone();
+        let (reloading, showing) = (args.clone(), args.dashboard);
+        tokio::spawn(reload::watch(path, move |guardrails| {
+            let guardrails = with_audit_log(&reloading, guardrails);
+            let reloaded: Vec<Guardrails> = sessions
+                .iter()
+                .map(|session| rules(&guardrails, session.config().server.as_deref()))
+                .collect();
+            if showing && reloaded.iter().any(prompts) {
+                warn!("Keeping the current guardrails: {}", DASHBOARD_PROMPTS);
+                return;
+            }
+            for (session, guardrails) in sessions.iter().zip(reloaded) {
+                session.reload(guardrails);
+            }
+        }));
+    }
+
+    let result = match configured {
+        Some(configured) => {
+            let backends = configured
+                .iter()
+                .zip(interceptors)
+                .map(|((name, server), interceptor)| Backend::new(name, server, interceptor))
+                .collect();
+            proxy_all(&args, backends).await
+        }
+        None => proxy(&args, interceptors.swap_remove(0)).await,
+    };
+    if let Some((view, shutdown)) = dashboard {
+        // Restores the terminal
+        shutdown.cancel();
+        let _ = view.await;
+    }
+    result
+}
+
+/// Approval prompts and the dashboard would fight over the terminal
+const DASHBOARD_PROMPTS: &str = "--dashboard needs approval_webhook in the guardrails: \
+                                 approvals cannot be asked on the terminal while it shows \
+                                 the dashboard";
+
+/// Whether `guardrails` ask for approvals on the terminal
+fn prompts(guardrails: &Guardrails) -> bool {
+    !guardrails.require_approval.is_empty() && guardrails.approval_webhook.is_none()
+}
+
+/// The guardrails of one proxied server
+fn rules(guardrails: &Guardrails, server: Option<&str>) -> Guardrails {
+    match server {
+        Some(name) => guardrails.clone().for_server(name),
+        None => guardrails.clone(),
+    }
+}
+
+/// Record blocked content with everything else, unless the guardrails name
+/// their own audit log or the audit log is in OCSF
+fn with_audit_log(args: &ProxyArgs, mut guardrails: Guardrails) -> Guardrails {
+    if guardrails.dlp.audit_log.is_none() && args.audit_format == AuditFormat::Jsonl {
+        guardrails.dlp.audit_log = args.audit_log.clone();
+    }
+    guardrails
+}
+
+/// Allowlist and public key of --quarantine
+fn allowlist_paths(args: &ProxyArgs) -> (PathBuf, PathBuf) {
+    let app = AppConfig::default();
+    let allowlist = args.allowlist.clone().unwrap_or(app.allowlist_path);
+    let public_key = args
+        .allowlist_key
+        .clone()
+        .unwrap_or_else(|| allowlist::public_key_path(&app.allowlist_key_path));
+    (allowlist, public_key)
+}
+
+/// Proxy the servers of --config, leaving out those --quarantine refuses
+async fn proxy_all(args: &ProxyArgs, mut backends: Vec<Backend>) -> Result<()> {
+    if args.quarantine {
+        let (allowlist, public_key) = allowlist_paths(args);
+        backends.retain(|backend| {
+            match quarantine::check(&backend.command, &allowlist, &public_key) {
+                Some(reason) => {
+                    warn!("Not starting MCP server '{}': {}", backend.name, reason);
+                    false
+                }
+                None => true,
+            }
+        });
+        if backends.is_empty() {
+            let reason = "none of the configured MCP servers is on the allowlist";
+            return runtime_proxy::stdio::refuse(reason, args.max_message_size).await;
+        }
+    }
+    multiplex::run(backends, args.max_message_size).await
+}
+
+async fn proxy(args: &ProxyArgs, interceptor: Interceptor) -> Result<()> {
+    match &args.upstream {
+        Some(url) => {
+            let upstream = upstream(args, url).await?;
+            match upstream.url.scheme() {
+                "ws" | "wss" => runtime_proxy::websocket::run(upstream, interceptor).await,
+                _ => runtime_proxy::http::run(upstream, interceptor).await,
+            }
+        }
+        None => runtime_proxy::stdio::run(&args.command, interceptor).await,
+    }
+}
+
+/// The remote server of `--upstream` and its TLS and auth settings
+async fn upstream(args: &ProxyArgs, url: &str) -> Result<Upstream> {
+    let token = match (&args.upstream_token, &args.oauth_token_url) {
+        (Some(secret), _) => Some(TokenSource::Static(
+            secret.parse::<SecretRef>()?.resolve().await?,
+        )),
+        (None, Some(token_url)) => Some(TokenSource::oauth(ClientCredentials {
+            token_url: http::parse_url(token_url)?.to_string(),
+            client_id: args.oauth_client_id.clone().unwrap_or_default(),
+            client_secret: args
+                .oauth_client_secret
+                .as_deref()
+                .unwrap_or_default()
+                .parse()?,
+            scope: args.oauth_scope.clone(),
+        })),
+        (None, None) => None,
+    };
+    Ok(Upstream {
+        url: http::parse_url(url)?,
+        ca_bundle: args.ca_bundle.clone(),
+        client_identity: args.client_cert.clone().zip(args.client_key.clone()),
+        token,
+    })
 }
@@ -1,6 +1,23 @@
 //! Rules command implementation
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::detectors::bundle::{self, RuleBundle, BUILTIN};
+use crate::models::config::AppConfig;
+use crate::storage::offline;
+use crate::utils::network;
+
+/// Where `rules update` downloads bundles from unless `--url` is given
+const DEFAULT_RELEASE_URL: &str =
+    "https://github.com/beejak/MCP_Sentinel/releases/latest/download/";
+
+/// Asset name of the bundle in a release
+const BUNDLE_ASSET: &str = "rules-bundle.json";
+
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub async fn validate(path: String) -> Result<()> {
     // Phase 3/4 implementation
@@ -16,3 +33,108 @@ pub async fn test(rules: String, traffic: String) -> Result<()> {
     // Phase 3/4 implementation
     anyhow::bail!("Rules test not yet implemented - Phase 3/4")
 }
+
+/// Download the latest signed rule bundle and install it if it is newer
+/// than the one in use
+pub async fn update(url: Option<String>) -> Result<()> {
+    // Nothing downloaded could be verified
+    bundle::release_key()?;
+    network::ensure_online("Rule updates")?;
+    let url = url.unwrap_or_else(|| DEFAULT_RELEASE_URL.to_string());
+    let bundle_url = if url.ends_with(".json") {
+        url
+    } else {
+        format!("{}/{}", url.trim_end_matches('/'), BUNDLE_ASSET)
+    };
+
+    let client = reqwest::Client::builder().timeout(UPDATE_TIMEOUT).build()?;
+    let json = fetch(&client, &bundle_url).await?;
+    let signature = String::from_utf8(fetch(&client, &format!("{}.sig", bundle_url)).await?)
+        .context("Bundle signature is not text")?;
+    let downloaded = bundle::verify_release(&json, &signature)
+        .with_context(|| format!("Refusing to install rule bundle from {}", bundle_url))?;
+
+    let app = AppConfig::default();
+    let current = installed_bundle(&app)
+        .map(|b| b.version)
+        .unwrap_or(0)
+        .max(BUILTIN.version);
+    if downloaded.version <= current {
+        println!("Rule bundle is up to date (version {})", current);
+        return Ok(());
+    }
+
+    let path = app.rule_bundle_path();
+    std::fs::create_dir_all(&app.rules_path)
+        .with_context(|| format!("Failed to create {}", app.rules_path.display()))?;
+    std::fs::write(&path, &json).with_context(|| format!("Failed to write {}", path.display()))?;
+    std::fs::write(bundle::signature_path(&path), signature)?;
+
+    println!(
+        "✅ Updated rule bundle {} -> {} ({} rules)",
+        current,
+        downloaded.version,
+        downloaded.rules.len()
+    );
+    Ok(())
+}
+
+/// Pack the rule bundle and IOC feed cache for an air-gapped machine
+pub async fn export_bundle(path: String) -> Result<()> {
+    let manifest = offline::export(&AppConfig::default(), Path::new(&path))?;
+    println!(
+        "✅ Exported rule bundle {} and {} IOC feed(s) to {}",
+        manifest
+            .rule_bundle_version
+            .map_or_else(|| "(built-in)".to_string(), |v| v.to_string()),
+        manifest.feeds,
+        path
+    );
+    Ok(())
+}
+
+/// Install an offline bundle created by `rules export-bundle`
+pub async fn import_bundle(path: String) -> Result<()> {
+    let manifest = offline::import(&AppConfig::default(), Path::new(&path))?;
+    println!(
+        "✅ Imported {} IOC feed(s) from {} (exported {} by v{})",
+        manifest.feeds,
+        path,
+        manifest.created_at.format("%Y-%m-%d"),
+        manifest.exported_by
+    );
+    Ok(())
+}
+
+/// The bundle installed by `rules update`, if any
+///
+/// A bundle that fails signature verification is ignored with a warning so
+/// scans fall back to the built-in rules.
+pub fn installed_bundle(app: &AppConfig) -> Option<RuleBundle> {
+    let path = app.rule_bundle_path();
+    if !path.is_file() {
+        return None;
+    }
+    match RuleBundle::load_verified(&path) {
+        Ok(bundle) => {
+            debug!("Using rule bundle version {}", bundle.version);
+            Some(bundle)
+        }
+        Err(e) => {
+            warn!("Ignoring installed rule bundle: {:#}", e);
+            None
+        }
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .bytes()
+        .await?;
+    Ok(body.to_vec())
+}
@@ -1,26 +1,266 @@
 //! Scan command implementation
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use super::types::{LlmProvider, OutputFormat, ScanMode, SeverityLevel};
-use crate::models::config::ScanConfig;
-use crate::scanner::Scanner;
-
-#[allow(clippy::too_many_arguments)]
-pub async fn execute(
-    target: String,
-    mode: ScanMode,
-    _llm_provider: Option<LlmProvider>,
-    _llm_model: Option<String>,
-    _llm_api_key: Option<String>,
-    output: OutputFormat,
-    output_file: Option<String>,
-    _severity: SeverityLevel,
-    fail_on: Option<SeverityLevel>,
-    _config: Option<String>,
-) -> Result<()> {
+use super::types::{LlmProvider, OutputFormat, ScanMode, ScanProfile, SeverityLevel};
+use crate::engines::ai_analysis::{LlmClient, DEFAULT_OLLAMA_URL};
+use crate::engines::dynamic_analysis::{self, LaunchOptions};
+use crate::integrations::github::{self, GithubClient};
+use crate::integrations::jira::JiraClient;
+use crate::models::config::{AppConfig, LlmConfig, ScanConfig};
+use crate::models::project_config::ProjectConfig;
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::Severity;
+use crate::output::i18n::{self, Language};
+use crate::output::jsonl::JsonlWriter;
+use crate::output::terminal::{GroupBy, RenderOptions, SortBy};
+use crate::remediation::{autofix, llm_fix};
+use crate::scanner::{ScanEvent, Scanner};
+use crate::storage::allowlist::{self, ServerAllowlist};
+use crate::storage::triage::TriageStore;
+use crate::utils::network;
+
+/// Arguments of `mcp-sentinel scan`
+#[derive(clap::Args, Debug, Clone)]
+pub struct ScanArgs {
+    /// Path to MCP server directory, GitHub URL, or config file
+    #[arg(value_name = "TARGET")]
+    pub target: String,
+
+    /// Scanning mode
+    #[arg(long, value_enum, default_value = "quick")]
+    pub mode: ScanMode,
+
+    /// LLM provider for deep mode
+    #[arg(long, value_enum)]
+    pub llm_provider: Option<LlmProvider>,
+
+    /// Specific model name
+    #[arg(long)]
+    pub llm_model: Option<String>,
+
+    /// API key (or use env var; MCP_SENTINEL_API_KEY is also read)
+    #[arg(long, env = "SENTINEL_LLM_API_KEY", hide_env_values = true)]
+    pub llm_api_key: Option<String>,
+
+    /// Output format
+    #[arg(
+        short,
+        long,
+        value_enum,
+        env = "SENTINEL_OUTPUT",
+        default_value = "terminal"
+    )]
+    pub output: OutputFormat,
+
+    /// Save report to file
+    #[arg(long, value_name = "PATH")]
+    pub output_file: Option<String>,
+
+    /// Print one tab-separated line per open finding (severity, rule, file,
+    /// line), in a format that stays the same across versions
+    #[arg(long, conflicts_with = "output")]
+    pub porcelain: bool,
+
+    /// Show the rule, pattern, and matched span of each finding in the
+    /// terminal report
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Headings to list findings under in the terminal report
+    #[arg(long, value_enum, default_value = "severity")]
+    pub group_by: GroupBy,
+
+    /// Order of groups and findings in the terminal report: most severe
+    /// first, largest group first, or by path
+    #[arg(long, value_enum, default_value = "severity")]
+    pub sort: SortBy,
+
+    /// Print only the severity counts, risk score, and top 5 findings in
+    /// the terminal report
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Print where the scan spent its time (discovery, each detector) and
+    /// which files it skipped, to stderr
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Language of the terminal report's labels and finding text
+    /// (machine-readable formats stay in English)
+    #[arg(long, value_enum, default_value = "en")]
+    pub lang: Language,
+
+    /// Preset of detectors, confidence threshold, and fail-on level:
+    /// strict, balanced, permissive, or ci
+    #[arg(long, value_enum, env = "SENTINEL_PROFILE")]
+    pub profile: Option<ScanProfile>,
+
+    /// Minimum severity to report (default: the profile's, else low)
+    #[arg(long, value_enum)]
+    pub severity: Option<SeverityLevel>,
+
+    /// Exit with code 1 if vulnerabilities >= level found (default: the
+    /// profile's, else never)
+    #[arg(long, value_enum, env = "SENTINEL_FAIL_ON")]
+    pub fail_on: Option<SeverityLevel>,
+
+    /// Also skip files matching this pattern (gitignore syntax; repeatable,
+    /// or comma-separated in the env var)
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        env = "SENTINEL_EXCLUDE",
+        value_delimiter = ','
+    )]
+    pub exclude: Vec<String>,
+
+    /// Number of files to read concurrently, ahead of the detectors (default 16)
+    #[arg(long, value_name = "N")]
+    pub concurrency: Option<usize>,
+
+    /// Project config file (default: TARGET/sentinel.toml if present)
+    #[arg(short, long)]
+    pub config: Option<String>,
+
+    /// YARA rule file or directory to evaluate (repeatable; needs the `yara` feature)
+    #[arg(long = "yara-rules", value_name = "PATH")]
+    pub yara_rules: Vec<PathBuf>,
+
+    /// IOC list of malicious domains, IPs, and SHA-256 file hashes (repeatable)
+    #[arg(long = "ioc-list", value_name = "PATH")]
+    pub ioc_lists: Vec<PathBuf>,
+
+    /// Print unified diffs for mechanically fixable findings without changing files
+    #[arg(long, conflicts_with = "fix")]
+    pub fix_dry_run: bool,
+
+    /// Apply fixes for mechanically fixable findings in place
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Ask the LLM to propose patches for findings without a mechanical fix
+    ///
+    /// Each patch is shown for review and only applied after confirmation.
+    #[arg(long, requires = "llm_provider", conflicts_with = "fix_dry_run")]
+    pub llm_fix: bool,
+
+    /// Also launch the server and scan the tools, prompts, and resources it
+    /// exposes at runtime
+    ///
+    /// This runs the server's code on this machine. It runs in a sandbox
+    /// (bubblewrap on Linux, sandbox-exec on macOS) that keeps it from
+    /// changing your files or reading your home directory, but it can still
+    /// use the network. Without a sandbox the scan fails unless
+    /// --dynamic-unsandboxed is given.
+    #[arg(long)]
+    pub dynamic: bool,
+
+    /// Run the server for --dynamic without a sandbox
+    ///
+    /// Only for servers you trust: unconfined, the server can read and change
+    /// every file you can, and only its environment is scrubbed.
+    #[arg(long, requires = "dynamic")]
+    pub dynamic_unsandboxed: bool,
+
+    /// Command that starts the server for --dynamic and --approve (default:
+    /// inferred from package.json or server.py)
+    #[arg(long, value_name = "CMD")]
+    pub server_command: Option<String>,
+
+    /// Seconds to wait for the server during --dynamic
+    #[arg(long, value_name = "SECS", default_value_t = 30)]
+    pub dynamic_timeout: u64,
+
+    /// Seconds to keep watching the server's file and network activity
+    /// after enumeration during --dynamic
+    #[arg(long, value_name = "SECS", default_value_t = 5)]
+    pub observe: u64,
+
+    /// If the scan passes (no open findings at or above --fail-on, default
+    /// high), add the server command to the signed allowlist that
+    /// `proxy --quarantine` enforces. The command must match the one the
+    /// client runs; pass it with --server-command.
+    #[arg(long)]
+    pub approve: bool,
+
+    /// File, update, and close Jira issues for the findings, as configured
+    /// in the [jira] table of sentinel.toml
+    #[arg(long)]
+    pub jira: bool,
+
+    /// Open GitHub issues for findings at or above --fail-on (default high)
+    /// that never had one
+    #[arg(long)]
+    pub github_issues: bool,
+
+    /// Publish the scan as a GitHub check run with annotations
+    #[arg(long)]
+    pub github_check: bool,
+
+    /// Repository for --github-issues and --github-check
+    #[arg(long, env = "GITHUB_REPOSITORY", value_name = "OWNER/REPO")]
+    pub github_repo: Option<String>,
+
+    /// Commit the check run is for (default: the target's checked-out commit)
+    #[arg(long, env = "GITHUB_SHA", value_name = "SHA")]
+    pub github_sha: Option<String>,
+}
+
+pub async fn execute(args: ScanArgs) -> Result<()> {
+    let ScanArgs {
+        target,
+        mode,
+        llm_provider,
+        llm_model,
+        llm_api_key,
+        output,
+        output_file,
+        porcelain,
+        explain,
+        group_by,
+        sort,
+        summary_only,
+        stats,
+        lang,
+        profile,
+        severity,
+        fail_on,
+        exclude,
+        concurrency,
+        config,
+        yara_rules,
+        ioc_lists,
+        fix_dry_run,
+        fix,
+        llm_fix,
+        dynamic,
+        dynamic_unsandboxed,
+        server_command,
+        dynamic_timeout,
+        observe,
+        approve,
+        jira,
+        github_issues,
+        github_check,
+        github_repo,
+        github_sha,
+    } = args;
+
+    let output = match porcelain {
+        true => OutputFormat::Porcelain,
+        false => output,
+    };
+    let llm_api_key = llm_api_key.or_else(|| std::env::var("MCP_SENTINEL_API_KEY").ok());
+    let fail_on = fail_level(profile, fail_on);
+
     info!("📂 Scanning: {}", target);
     debug!("Mode: {:?}", mode);
     debug!("Output format: {:?}", output);
@@ -43,29 +283,209 @@ pub async fn execute(
         );
     }
 
-    // Create scanner configuration
-    let config = ScanConfig::default();
-    let scanner = Scanner::new(config);
+    // Create scanner configuration, layering sentinel.toml on top
+    let mut project = match &config {
+        Some(path) => Some(ProjectConfig::load(Path::new(path))?),
+        None => ProjectConfig::discover(&target_path)?,
+    };
+    if !yara_rules.is_empty() {
+        let project = project.get_or_insert_with(ProjectConfig::default);
+        project.yara.rules.extend(yara_rules);
+    }
+    if !ioc_lists.is_empty() {
+        let project = project.get_or_insert_with(ProjectConfig::default);
+        project.ioc.lists.extend(ioc_lists);
+    }
+    if let Some(project) = project.as_mut().filter(|p| !p.ioc.feeds.is_empty()) {
+        let cache_dir = AppConfig::default().feeds_cache_path();
+        let cached = crate::storage::feeds::refresh(&project.ioc.feeds, &cache_dir).await;
+        project.ioc.lists.extend(cached);
+    }
+    let jira_config = match (jira, &project) {
+        (false, _) => None,
+        (
+            true,
+            Some(ProjectConfig {
+                jira: Some(jira), ..
+            }),
+        ) => Some(jira.clone()),
+        (true, _) => anyhow::bail!("--jira needs a [jira] table in sentinel.toml"),
+    };
+    let mut config = layered_config(profile, severity, project.as_ref())?;
+    config.rule_bundle = super::rules::installed_bundle(&AppConfig::default());
+    config.exclude_patterns.extend(exclude);
+    if let Some(concurrency) = concurrency {
+        config.io_concurrency = concurrency.max(1);
+    }
+    let cancel = CancellationToken::new();
+    let scanner = Scanner::builder()
+        .config(config)
+        .cancellation_token(cancel.clone())
+        .build();
+
+    // First Ctrl-C stops the scan and reports what was found so far; the
+    // handler replaces the default one, so a second Ctrl-C has to exit
+    let ctrl_c = tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            warn!("Interrupted, stopping scan (Ctrl-C again to exit now)...");
+            cancel.cancel();
+        }
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(INTERRUPTED);
+        }
+    });
+
+    let triage = TriageStore::load(&TriageStore::path_for(&target_path))?;
+    // JSONL reports are written as files are scanned, so a crash keeps
+    // what was found so far
+    let mut report_writer = match (&output, &output_file) {
+        (OutputFormat::Jsonl, Some(path)) => Some(JsonlWriter::create(Path::new(path))?),
+        _ => None,
+    };
 
     // Run scan
-    let result = match scanner.scan_directory(&target_path).await {
+    let mut seen = HashMap::new();
+    let scan = scanner.scan_directory_with_callback(&target_path, |event| {
+        let (Some(writer), ScanEvent::FileScanned { findings, .. }) =
+            (report_writer.as_mut(), event)
+        else {
+            return;
+        };
+        for mut vuln in findings {
+            // Same IDs as the findings get when added to the result
+            vuln.identify(&target_path, &mut seen);
+            vuln.triage = vuln
+                .fingerprint
+                .as_ref()
+                .and_then(|f| triage.get(f))
+                .cloned();
+            if let Err(e) = writer.write_finding(&vuln) {
+                warn!("Failed to write finding to report: {:#}", e);
+            }
+        }
+    });
+    let result = match scan.await {
         Ok(r) => r,
         Err(e) => {
             error!("Scan failed for '{}': {}", target, e);
             return Err(e).context(format!("Failed to scan directory '{}'", target));
         }
     };
+    ctrl_c.abort();
+    // Nothing is left to stop gracefully, so Ctrl-C exits right away
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(INTERRUPTED);
+        }
+    });
+
+    let mut result = result;
+    if dynamic {
+        dynamic_scan(
+            &target_path,
+            server_command.clone(),
+            LaunchOptions {
+                timeout: Duration::from_secs(dynamic_timeout),
+                observe: Duration::from_secs(observe),
+                expected_hosts: Vec::new(),
+                unsandboxed: dynamic_unsandboxed,
+            },
+            scanner.config(),
+            &mut result,
+        )
+        .await?;
+    }
+
+    // Attach triage decisions recorded with `mcp-sentinel triage`
+    triage.apply(&mut result);
+
+    if result.metadata.incomplete {
+        warn!("Scan was cancelled; results are incomplete");
+    }
+
+    // Status messages must not interleave with a report on stdout
+    let report_on_stdout = output_file.is_none() && !matches!(output, OutputFormat::Terminal);
+
+    // Always plan fixes so reports can mark auto-fixable findings
+    let patches = autofix::plan(&mut result)?;
+    if fix_dry_run {
+        for patch in &patches {
+            print!("{}", patch.unified_diff());
+        }
+        if patches.is_empty() {
+            // Keep stdout an (empty) patch
+            eprintln!("No mechanically fixable findings.");
+        }
+        return Ok(());
+    }
+    if fix {
+        for patch in &patches {
+            patch.apply()?;
+            status(
+                format!(
+                    "🔧 Fixed {} finding(s) in {}",
+                    patch.fixes.len(),
+                    patch.path.display()
+                ),
+                report_on_stdout,
+            );
+        }
+        // Fixed findings no longer need attention in this run's report
+        result.vulnerabilities.retain(|v| !v.auto_fixable);
+        result.update_summary();
+    }
+
+    if let (true, Some(provider)) = (llm_fix, llm_provider) {
+        match LlmClient::new(llm_config(provider, llm_model, llm_api_key)?) {
+            Ok(client) => review_llm_patches(&client, &mut result).await?,
+            Err(e) if network::is_offline() => warn!("Skipping LLM patch review: {:#}", e),
+            Err(e) => return Err(e),
+        }
+    }
 
     // Output results
     match output {
         OutputFormat::Terminal => {
-            if let Err(e) = crate::output::terminal::render(&result) {
+            // Issues filed below stay in English
+            let mut report = result.clone();
+            i18n::set_language(lang);
+            i18n::localize(&mut report, lang);
+            let options = RenderOptions {
+                explain,
+                group_by,
+                sort,
+                summary_only,
+            };
+            if let Err(e) = crate::output::terminal::render_with(&report, &options) {
                 error!("Failed to render terminal output: {}", e);
                 return Err(e);
             }
         }
-        OutputFormat::Json => {
-            let json = match crate::output::json::generate(&result) {
+        OutputFormat::Jsonl if report_writer.is_some() => {
+            let path = output_file.as_deref().unwrap_or_default();
+            if let Some(writer) = report_writer.take() {
+                writer
+                    .finish(&result)
+                    .with_context(|| format!("Failed to write report to '{}'", path))?;
+            }
+            info!("Report saved to: {}", path);
+            println!("✅ Report saved to: {}", path);
+        }
+        OutputFormat::Json
+        | OutputFormat::Jsonl
+        | OutputFormat::Html
+        | OutputFormat::Defectdojo
+        | OutputFormat::Ocsf
+        | OutputFormat::Porcelain => {
+            let generate = match output {
+                OutputFormat::Defectdojo => crate::output::defectdojo::generate,
+                OutputFormat::Html => crate::output::html::generate,
+                OutputFormat::Ocsf => crate::output::ocsf::generate,
+                OutputFormat::Porcelain => crate::output::porcelain::generate,
+                OutputFormat::Jsonl => crate::output::jsonl::generate,
+                _ => crate::output::json::generate,
+            };
+            let json = match generate(&result) {
                 Ok(j) => j,
                 Err(e) => {
                     error!("Failed to generate JSON report: {}", e);
@@ -80,6 +500,8 @@ pub async fn execute(
                 }
                 info!("Report saved to: {}", file_path);
                 println!("✅ Report saved to: {}", file_path);
+            } else if json.ends_with('\n') {
+                print!("{}", json);
             } else {
                 println!("{}", json);
             }
@@ -90,23 +512,377 @@ pub async fn execute(
         }
     }
 
+    if stats {
+        crate::output::terminal::print_stats(&result);
+    }
+
+    if let Some(jira) = jira_config {
+        let report = JiraClient::new(jira)?.sync(&result).await?;
+        status(
+            format!(
+                "🎫 Jira: {} created, {} updated, {} closed",
+                report.created.len(),
+                report.updated.len(),
+                report.closed.len()
+            ),
+            report_on_stdout,
+        );
+        for key in &report.created {
+            status(format!("   new issue {}", key), report_on_stdout);
+        }
+    }
+
+    let approve_threshold = fail_on
+        .clone()
+        .map(Into::into)
+        .unwrap_or(AppConfig::default().scan.fail_on);
+
+    if github_issues || github_check {
+        let repo = github_repo.context(
+            "--github-issues and --github-check need --github-repo (or GITHUB_REPOSITORY)",
+        )?;
+        let github = GithubClient::connect(&repo).await?;
+        if github_issues {
+            let opened = github.open_issues(&result, approve_threshold).await?;
+            status(
+                format!("🐙 Opened {} GitHub issue(s) in {}", opened.len(), repo),
+                report_on_stdout,
+            );
+        }
+        if github_check {
+            let sha = match github_sha {
+                Some(sha) => sha,
+                None => github::head_commit(&target_path).await?,
+            };
+            let root = github::checkout_root(&target_path).await;
+            let url = github
+                .publish_check(&sha, &result, &root, approve_threshold)
+                .await?;
+            status(format!("🐙 Published check run {}", url), report_on_stdout);
+        }
+    }
+
+    let failed = fail_on
+        .clone()
+        .is_some_and(|threshold| result.has_issues_at_level(threshold.into()));
+    if approve && !failed {
+        let approved = approve_server(&target_path, server_command, approve_threshold, &result)?;
+        status(approved, report_on_stdout);
+    }
+    report_result(&result, failed, report_on_stdout)?;
+
     // Check fail_on threshold
-    if let Some(threshold) = fail_on {
-        let threshold_severity = match threshold {
-            SeverityLevel::Low => crate::models::vulnerability::Severity::Low,
-            SeverityLevel::Medium => crate::models::vulnerability::Severity::Medium,
-            SeverityLevel::High => crate::models::vulnerability::Severity::High,
-            SeverityLevel::Critical => crate::models::vulnerability::Severity::Critical,
+    if let Some(threshold) = fail_on.filter(|_| failed) {
+        warn!(
+            "Vulnerabilities found at or above {:?} threshold: {} critical, {} high",
+            threshold, result.summary.critical, result.summary.high
+        );
+        anyhow::bail!("Found vulnerabilities at or above {:?} level", threshold);
+    }
+
+    Ok(())
+}
+
+/// Scan settings of `--profile`, `--severity`, and `sentinel.toml`
+///
+/// Later layers win: `--severity` replaces the profile's minimum severity,
+/// and `sentinel.toml` adds its overrides and rule files on top of both
+/// without resetting the profile's thresholds or detectors.
+fn layered_config(
+    profile: Option<ScanProfile>,
+    severity: Option<SeverityLevel>,
+    project: Option<&ProjectConfig>,
+) -> Result<ScanConfig> {
+    let mut config = ScanConfig::default();
+    if let Some(profile) = profile {
+        debug!("Profile: {:?}", profile);
+        profile.apply_to(&mut config);
+    }
+    if let Some(severity) = severity {
+        config.min_severity = severity.into();
+    }
+    if let Some(project) = project {
+        debug!(
+            "Loaded project config with {} severity overrides",
+            project.severity_overrides.len()
+        );
+        project.apply_to(&mut config)?;
+    }
+    Ok(config)
+}
+
+/// `--fail-on`, else the profile's level
+fn fail_level(
+    profile: Option<ScanProfile>,
+    fail_on: Option<SeverityLevel>,
+) -> Option<SeverityLevel> {
+    fail_on.or(profile.map(ScanProfile::fail_on))
+}
+
+/// Print `SENTINEL_RESULT critical=1 high=4 medium=10 low=0 risk=73
+/// status=fail` as the scan's last line, for shell steps to branch on, and
+/// set the same keys as step outputs when running in GitHub Actions
+///
+/// The line goes to stderr when the report itself is on stdout, so the
+/// report stays parseable.
+fn report_result(result: &ScanResult, failed: bool, report_on_stdout: bool) -> Result<()> {
+    let summary = &result.summary;
+    let fields = [
+        ("critical", summary.critical.to_string()),
+        ("high", summary.high.to_string()),
+        ("medium", summary.medium.to_string()),
+        ("low", summary.low.to_string()),
+        ("risk", summary.risk_score.to_string()),
+        ("status", if failed { "fail" } else { "pass" }.to_string()),
+    ];
+    let pairs: Vec<String> = fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    status(
+        format!("SENTINEL_RESULT {}", pairs.join(" ")),
+        report_on_stdout,
+    );
+
+    if let Some(path) = std::env::var_os("GITHUB_OUTPUT") {
+        let mut outputs = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| {
+                format!("Failed to open GITHUB_OUTPUT '{}'", path.to_string_lossy())
+            })?;
+        for pair in &pairs {
+            writeln!(outputs, "{}", pair)?;
+        }
+    }
+    Ok(())
+}
+
+/// Print a message for the user, on stderr when the report itself is on
+/// stdout
+fn status(message: impl std::fmt::Display, report_on_stdout: bool) {
+    if report_on_stdout {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+/// Add the server to the quarantine allowlist if `result` passes, returning
+/// the confirmation to show
+fn approve_server(
+    target: &Path,
+    command: Option<String>,
+    threshold: Severity,
+    result: &ScanResult,
+) -> Result<String> {
+    let command = match command {
+        Some(command) => command.split_whitespace().map(str::to_string).collect(),
+        None => dynamic_analysis::infer_command(target)
+            .context("Cannot tell how this server is started; pass --server-command")?,
+    };
+    if result.metadata.incomplete {
+        anyhow::bail!(
+            "Not approving '{}': the scan is incomplete",
+            command.join(" ")
+        );
+    }
+    if result.has_issues_at_level(threshold) {
+        anyhow::bail!(
+            "Not approving '{}': open findings at or above {:?}",
+            command.join(" "),
+            threshold
+        );
+    }
+
+    let app = AppConfig::default();
+    let key = allowlist::load_or_create_signing_key(&app.allowlist_key_path)?;
+    let mut servers = ServerAllowlist::load(&app.allowlist_path)?;
+    servers.approve(&command, target.display().to_string());
+    servers.save_signed(&app.allowlist_path, &key)?;
+    Ok(format!(
+        "✅ Approved '{}' in {} (public key: {})",
+        command.join(" "),
+        app.allowlist_path.display(),
+        allowlist::public_key_path(&app.allowlist_key_path).display()
+    ))
+}
+
+/// Launch the server in `target` and add the findings for its live surface
+/// and behavior
+async fn dynamic_scan(
+    target: &Path,
+    command: Option<String>,
+    mut options: LaunchOptions,
+    config: &ScanConfig,
+    result: &mut ScanResult,
+) -> Result<()> {
+    let command = match command {
+        Some(command) => command.split_whitespace().map(str::to_string).collect(),
+        None => dynamic_analysis::infer_command(target)
+            .context("Cannot tell how to start this server; pass --server-command")?,
+    };
+    // Hosts found in the source are the ones the server may talk to
+    let hosts: BTreeSet<&str> = result.egress.iter().map(|e| e.host.as_str()).collect();
+    options.expected_hosts = hosts.into_iter().map(str::to_string).collect();
+    let run = dynamic_analysis::launch(&command, target, &options)
+        .await
+        .context("Dynamic analysis failed")?;
+    result.add_vulnerabilities(dynamic_analysis::analyze(&run, &result.tools, config));
+    result.engines.push("dynamic".to_string());
+    Ok(())
+}
+
+/// Exit status on a forced Ctrl-C, as if SIGINT had killed the process
+const INTERRUPTED: i32 = 130;
+
+/// Findings sent to the LLM per scan, to bound cost
+const MAX_LLM_FIXES: usize = 10;
+
+fn llm_config(
+    provider: LlmProvider,
+    model: Option<String>,
+    api_key: Option<String>,
+) -> Result<LlmConfig> {
+    let require_key = || {
+        api_key
+            .clone()
+            .context("--llm-api-key (or MCP_SENTINEL_API_KEY) is required for this provider")
+    };
+    Ok(match provider {
+        LlmProvider::Openai => LlmConfig::OpenAI {
+            api_key: require_key()?,
+            model: model.unwrap_or_else(|| "gpt-4o".to_string()),
+        },
+        LlmProvider::Anthropic => LlmConfig::Anthropic {
+            api_key: require_key()?,
+            model: model.unwrap_or_else(|| "claude-3-5-sonnet-latest".to_string()),
+        },
+        LlmProvider::Local => LlmConfig::Ollama {
+            base_url: DEFAULT_OLLAMA_URL.to_string(),
+            model: model.unwrap_or_else(|| "llama3.1".to_string()),
+        },
+    })
+}
+
+/// Propose LLM patches for open findings that have no mechanical fix
+///
+/// Proposals are recorded on the findings for the report. They are applied
+/// only when stdin is a terminal and the user confirms each one.
+async fn review_llm_patches(client: &LlmClient, result: &mut ScanResult) -> Result<()> {
+    let interactive = std::io::stdin().is_terminal();
+    if !interactive {
+        warn!("stdin is not a terminal; LLM patches will be reported but not applied");
+    }
+
+    let candidates = result
+        .vulnerabilities
+        .iter_mut()
+        .filter(|v| v.is_open() && !v.auto_fixable && v.severity >= Severity::Medium)
+        .take(MAX_LLM_FIXES);
+    let mut stdin = BufReader::new(tokio::io::stdin());
+
+    for vuln in candidates {
+        let patch = match llm_fix::propose(client, vuln).await {
+            Ok(Some(patch)) => patch,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("No LLM patch for {}: {:#}", vuln.id, e);
+                continue;
+            }
         };
+        if !interactive {
+            continue;
+        }
 
-        if result.has_issues_at_level(threshold_severity) {
-            warn!(
-                "Vulnerabilities found at or above {:?} threshold: {} critical, {} high",
-                threshold, result.summary.critical, result.summary.high
-            );
-            anyhow::bail!("Found vulnerabilities at or above {:?} level", threshold);
+        eprintln!("\n🤖 Proposed patch for {} ({}):", vuln.id, vuln.title);
+        eprint!("{}", patch.unified_diff());
+        eprint!("Apply this patch? [y/N] ");
+        let mut answer = String::new();
+        stdin.read_line(&mut answer).await?;
+        if answer.trim().eq_ignore_ascii_case("y") {
+            patch.apply()?;
+            eprintln!("🔧 Applied patch to {}", patch.path.display());
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::DetectorKind;
+    use crate::models::project_config::PathOverrides;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        scan: ScanArgs,
+    }
+
+    #[test]
+    fn test_flags_override_profile() {
+        let config = layered_config(
+            Some(ScanProfile::Permissive),
+            Some(SeverityLevel::Low),
+            None,
+        )
+        .unwrap();
+        assert_eq!(config.min_severity, Severity::Low);
+        // The rest of the profile still applies
+        assert_eq!(config.min_confidence, 0.8);
+
+        let profile = Some(ScanProfile::Permissive);
+        assert_eq!(fail_level(profile, None), Some(SeverityLevel::Critical));
+        assert_eq!(
+            fail_level(profile, Some(SeverityLevel::Medium)),
+            Some(SeverityLevel::Medium)
+        );
+        assert_eq!(fail_level(None, None), None);
+    }
+
+    #[test]
+    fn test_project_config_layers_on_profile() {
+        let project: ProjectConfig = toml::from_str(
+            r#"
+            [[severity_overrides]]
+            detector = "secrets"
+            severity = "critical"
+
+            [[override]]
+            paths = ["tests/**"]
+            disable = ["secrets"]
+            "#,
+        )
+        .unwrap();
+
+        let config = layered_config(Some(ScanProfile::Ci), None, Some(&project)).unwrap();
+        assert_eq!(config.min_confidence, 0.7);
+        assert_eq!(config.detectors, DetectorKind::ALL);
+        assert_eq!(config.severity_overrides.len(), 1);
+        assert_eq!(
+            config.path_overrides,
+            PathOverrides::from(project.path_overrides.clone())
+        );
+    }
+
+    #[test]
+    fn test_env_vars_and_flag_precedence() {
+        std::env::set_var("SENTINEL_FAIL_ON", "high");
+        std::env::set_var("SENTINEL_EXCLUDE", "vendor/,dist/");
+        let from_env = Cli::try_parse_from(["scan", "."]);
+        let from_flags =
+            Cli::try_parse_from(["scan", ".", "--fail-on", "critical", "--exclude", "build/"]);
+        std::env::remove_var("SENTINEL_FAIL_ON");
+        std::env::remove_var("SENTINEL_EXCLUDE");
+
+        let from_env = from_env.unwrap().scan;
+        assert_eq!(from_env.fail_on, Some(SeverityLevel::High));
+        assert_eq!(from_env.exclude, vec!["vendor/", "dist/"]);
+
+        // Flags win over the environment
+        let from_flags = from_flags.unwrap().scan;
+        assert_eq!(from_flags.fail_on, Some(SeverityLevel::Critical));
+        assert_eq!(from_flags.exclude, vec!["build/"]);
+    }
+}
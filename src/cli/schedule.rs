@@ -0,0 +1,250 @@
+//! Schedule command implementation
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+use super::types::SeverityLevel;
+use crate::models::config::{AppConfig, ScanConfig};
+use crate::scanner::Scanner;
+use crate::storage::history::HistoryStore;
+use crate::storage::schedule::{Schedule, ScheduleStore};
+use crate::storage::triage::TriageStore;
+use crate::utils::notify::{self, Notification, Notifier};
+
+/// Longest the scheduler waits before re-reading the schedules
+const RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// New findings listed in a notification; the rest are counted
+const MAX_NOTIFIED_FINDINGS: usize = 10;
+
+fn schedules_path() -> PathBuf {
+    AppConfig::default().schedules_path
+}
+
+pub async fn add(
+    cron: String,
+    target: String,
+    notify: Vec<String>,
+    min_severity: SeverityLevel,
+) -> Result<()> {
+    let target_path = Path::new(&target);
+    if !target_path.is_dir() {
+        anyhow::bail!("Target must be an existing directory: '{}'", target);
+    }
+    // The scheduler may run from another directory
+    let target = target_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve '{}'", target))?
+        .to_string_lossy()
+        .into_owned();
+    for spec in &notify {
+        notify::validate(spec)?;
+    }
+
+    let schedule = Schedule::new(cron, target, notify, min_severity.into())?;
+    let path = schedules_path();
+    let mut store = ScheduleStore::load(&path)?;
+    store.schedules.push(schedule.clone());
+    store.save(&path)?;
+    println!(
+        "✅ Scheduled {} ({}) of {}; next run {}",
+        schedule.id,
+        schedule.cron,
+        schedule.target,
+        next_run(&schedule)
+    );
+    println!("   Scans run while `mcp-sentinel schedule run` is running");
+    Ok(())
+}
+
+pub async fn list() -> Result<()> {
+    let store = ScheduleStore::load(&schedules_path())?;
+    if store.schedules.is_empty() {
+        println!("No scheduled scans");
+        return Ok(());
+    }
+
+    for schedule in &store.schedules {
+        let notify = match schedule.notify.is_empty() {
+            true => "-".to_string(),
+            false => schedule.notify.join(","),
+        };
+        println!(
+            "{}  {:<15} {:<40} notify {}  next {}",
+            schedule.id,
+            schedule.cron,
+            schedule.target,
+            notify,
+            next_run(schedule)
+        );
+    }
+    Ok(())
+}
+
+pub async fn remove(id: String) -> Result<()> {
+    let path = schedules_path();
+    let mut store = ScheduleStore::load(&path)?;
+    if !store.remove(&id) {
+        anyhow::bail!("No scheduled scan with ID '{}'", id);
+    }
+    store.save(&path)?;
+    println!("✅ Removed scheduled scan {}", id);
+    Ok(())
+}
+
+/// Run scheduled scans until interrupted
+///
+/// Each scan is recorded in the history database and compared with the
+/// previous scan of the same target; new findings at or above the
+/// schedule's severity are sent to its notification targets.
+pub async fn run() -> Result<()> {
+    let app = AppConfig::default();
+    let history = HistoryStore::open(&app.history_path)?.with_retention(app.retention.history);
+    let scanner = Scanner::new(ScanConfig::default());
+    info!(
+        "⏰ Scheduler started; schedules in {}",
+        app.schedules_path.display()
+    );
+
+    // Runs up to this time have been handled
+    let mut since = Local::now();
+    loop {
+        let store = ScheduleStore::load(&app.schedules_path).unwrap_or_else(|e| {
+            warn!("{:#}", e);
+            ScheduleStore::default()
+        });
+        let now = Local::now();
+        for schedule in &store.schedules {
+            let cron = match schedule.cron_schedule() {
+                Ok(cron) => cron,
+                Err(e) => {
+                    warn!("Skipping schedule {}: {:#}", schedule.id, e);
+                    continue;
+                }
+            };
+            if cron.next_after(since).is_some_and(|due| due <= now) {
+                run_scheduled(&scanner, &history, schedule).await;
+            }
+        }
+        since = now;
+
+        let next = store
+            .schedules
+            .iter()
+            .filter_map(|schedule| schedule.cron_schedule().ok()?.next_after(now))
+            .min();
+        let wait = next
+            .and_then(|next| (next - Local::now()).to_std().ok())
+            .unwrap_or(Duration::ZERO)
+            .min(RELOAD_INTERVAL);
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => {
+                info!("Scheduler stopped");
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn run_scheduled(scanner: &Scanner, history: &HistoryStore, schedule: &Schedule) {
+    info!(
+        "Running scheduled scan {} of {}",
+        schedule.id, schedule.target
+    );
+    let previous = history.latest_for(&schedule.target).unwrap_or_else(|e| {
+        warn!("{:#}", e);
+        None
+    });
+    let mut result = match scanner.scan_directory(&schedule.target).await {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Scheduled scan of '{}' failed: {:#}", schedule.target, e);
+            return;
+        }
+    };
+    let triage = TriageStore::load(&TriageStore::path_for(Path::new(&schedule.target)));
+    match triage {
+        Ok(triage) => triage.apply(&mut result),
+        Err(e) => warn!("{:#}", e),
+    }
+    if let Err(e) = history.record(&result) {
+        warn!("Failed to record scan of '{}': {:#}", schedule.target, e);
+    }
+    info!(
+        "Scheduled scan of {}: {} issues ({} critical, {} high), risk score {}",
+        schedule.target,
+        result.summary.total_issues,
+        result.summary.critical,
+        result.summary.high,
+        result.summary.risk_score
+    );
+
+    let Some(previous) = previous else {
+        info!(
+            "First scan of {}; later scans are compared with it",
+            schedule.target
+        );
+        return;
+    };
+    let new: Vec<_> = result
+        .new_findings(&previous)
+        .into_iter()
+        .filter(|v| v.severity >= schedule.min_severity)
+        .collect();
+    if new.is_empty() {
+        return;
+    }
+    warn!("🚨 {} new findings in {}", new.len(), schedule.target);
+
+    let mut lines: Vec<String> = new
+        .iter()
+        .take(MAX_NOTIFIED_FINDINGS)
+        .map(|v| {
+            let file = v.location.as_ref().map(|l| l.file.as_str()).unwrap_or("-");
+            format!("[{}] {} ({})", v.severity.to_badge(), v.title, file)
+        })
+        .collect();
+    if new.len() > MAX_NOTIFIED_FINDINGS {
+        lines.push(format!("...and {} more", new.len() - MAX_NOTIFIED_FINDINGS));
+    }
+    let notification = Notification {
+        title: format!(
+            "MCP Sentinel: {} new finding(s) in {}",
+            new.len(),
+            schedule.target
+        ),
+        lines,
+        details: json!({
+            "schedule": schedule.id,
+            "target": schedule.target,
+            "scan_id": result.scan_id,
+            "previous_scan_id": previous.scan_id,
+            "new_findings": new,
+        }),
+    };
+    for target in &schedule.notify {
+        let sent = match target.parse::<Notifier>() {
+            Ok(notifier) => notifier.send(&notification).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            warn!("{:#}", e);
+        }
+    }
+}
+
+fn next_run(schedule: &Schedule) -> String {
+    match schedule
+        .cron_schedule()
+        .ok()
+        .and_then(|c| c.next_after(Local::now()))
+    {
+        Some(next) => next.format("%Y-%m-%d %H:%M").to_string(),
+        None => "never".to_string(),
+    }
+}
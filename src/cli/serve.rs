@@ -0,0 +1,68 @@
+//! Serve command implementation
+
+use anyhow::Result;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::api::{jobs::JobQueue, rest::AppState};
+use crate::models::config::{AppConfig, ScanConfig};
+use crate::storage::history::HistoryStore;
+
+/// Run the daemon: REST (and optionally gRPC) APIs for scan jobs, history,
+/// and whitelist management
+pub async fn execute(
+    listen: SocketAddr,
+    max_jobs: usize,
+    grpc_listen: Option<SocketAddr>,
+) -> Result<()> {
+    for addr in std::iter::once(listen).chain(grpc_listen) {
+        if !addr.ip().is_loopback() {
+            // The APIs accept arbitrary filesystem paths as scan targets
+            warn!(
+                "Listening on non-loopback address {}: any host that can reach it can scan local paths",
+                addr
+            );
+        }
+    }
+
+    let app_config = AppConfig::default();
+    let history = HistoryStore::open(&app_config.history_path)?
+        .with_retention(app_config.retention.history.clone());
+    let history = Arc::new(history);
+    let jobs = JobQueue::new(ScanConfig::default(), max_jobs, Some(history.clone()));
+
+    let state = Arc::new(AppState {
+        jobs: jobs.clone(),
+        history,
+        whitelist_path: app_config.whitelist_path,
+        whitelist_lock: Mutex::new(()),
+    });
+
+    let grpc = async move {
+        match grpc_listen {
+            Some(addr) => serve_grpc(addr, jobs).await,
+            None => std::future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        result = crate::api::rest::serve(listen, state) => result,
+        result = grpc => result,
+        _ = tokio::signal::ctrl_c() => {
+            println!("\n👋 Daemon stopped");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+async fn serve_grpc(addr: SocketAddr, jobs: JobQueue) -> Result<()> {
+    crate::api::grpc::serve(addr, jobs).await
+}
+
+#[cfg(not(feature = "grpc"))]
+async fn serve_grpc(_addr: SocketAddr, _jobs: JobQueue) -> Result<()> {
+    anyhow::bail!("--grpc-listen requires mcp-sentinel to be built with the `grpc` feature")
+}
@@ -0,0 +1,36 @@
+//! Triage command implementation
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::models::vulnerability::TriageState;
+use crate::storage::triage::TriageStore;
+
+pub async fn execute(
+    fingerprint: String,
+    state: TriageState,
+    reason: Option<String>,
+    project: String,
+) -> Result<()> {
+    let path = TriageStore::path_for(&PathBuf::from(project));
+    let mut store = TriageStore::load(&path)?;
+
+    if state == TriageState::Open && store.get(&fingerprint).is_none() {
+        anyhow::bail!("Finding {} has not been triaged", fingerprint);
+    }
+
+    store.set(&fingerprint, state, reason);
+    store.save(&path)?;
+
+    if state == TriageState::Open {
+        println!("✅ Reopened {}", fingerprint);
+    } else {
+        println!(
+            "✅ Marked {} as {} in {}",
+            fingerprint,
+            state,
+            path.display()
+        );
+    }
+    Ok(())
+}
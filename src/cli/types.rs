@@ -1,29 +1,43 @@
 //! Common CLI types and enums
 
-#[derive(Clone, Debug)]
+use crate::detectors::DetectorKind;
+use crate::models::config::ScanConfig;
+use crate::models::vulnerability::Severity;
+
+#[derive(clap::ValueEnum, Clone, Debug)]
 pub enum ScanMode {
     Quick,
     Deep,
 }
 
-#[derive(Clone, Debug)]
+#[derive(clap::ValueEnum, Clone, Debug)]
 pub enum LlmProvider {
     Openai,
     Anthropic,
     Local,
 }
 
-#[derive(Clone, Debug)]
+#[derive(clap::ValueEnum, Clone, Debug)]
 pub enum OutputFormat {
     Terminal,
     Json,
+    /// One finding per line, written as the scan runs, then a summary line
+    Jsonl,
     Html,
     Pdf,
     Sarif,
+    /// DefectDojo Generic Findings Import
+    Defectdojo,
+    /// OCSF Vulnerability Findings, one per line
+    Ocsf,
+    /// Selected with --porcelain
+    #[value(skip)]
+    Porcelain,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SeverityLevel {
+    Info,
     Low,
     Medium,
     High,
@@ -33,6 +47,7 @@ pub enum SeverityLevel {
 impl From<crate::models::vulnerability::Severity> for SeverityLevel {
     fn from(severity: crate::models::vulnerability::Severity) -> Self {
         match severity {
+            crate::models::vulnerability::Severity::Info => SeverityLevel::Info,
             crate::models::vulnerability::Severity::Low => SeverityLevel::Low,
             crate::models::vulnerability::Severity::Medium => SeverityLevel::Medium,
             crate::models::vulnerability::Severity::High => SeverityLevel::High,
@@ -44,6 +59,7 @@ impl From<crate::models::vulnerability::Severity> for SeverityLevel {
 impl From<SeverityLevel> for crate::models::vulnerability::Severity {
     fn from(level: SeverityLevel) -> Self {
         match level {
+            SeverityLevel::Info => crate::models::vulnerability::Severity::Info,
             SeverityLevel::Low => crate::models::vulnerability::Severity::Low,
             SeverityLevel::Medium => crate::models::vulnerability::Severity::Medium,
             SeverityLevel::High => crate::models::vulnerability::Severity::High,
@@ -51,3 +67,111 @@ impl From<SeverityLevel> for crate::models::vulnerability::Severity {
         }
     }
 }
+
+/// Preset for `scan --profile`: which detectors run, which findings are
+/// reported, and the level `--fail-on` defaults to
+///
+/// Explicit flags (`--severity`, `--fail-on`) still win over the profile,
+/// and `sentinel.toml` overrides apply on top of it.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanProfile {
+    /// Every detector and every finding; fail on low or above
+    Strict,
+    /// Every detector, findings with confidence 0.5 or more; fail on high
+    Balanced,
+    /// Exploitable code only (no hygiene checks like pinning or transport),
+    /// medium or above with confidence 0.8 or more; fail on critical
+    Permissive,
+    /// Like balanced but only findings with confidence 0.7 or more, so
+    /// pipelines break on likely issues only; fail on high
+    Ci,
+}
+
+/// Detectors for configuration and supply-chain hygiene rather than
+/// exploitable code, left out by the permissive profile
+const HYGIENE_DETECTORS: &[DetectorKind] = &[
+    DetectorKind::InsecureTransport,
+    DetectorKind::FilePermissions,
+    DetectorKind::Dockerfile,
+    DetectorKind::Manifests,
+    DetectorKind::Iac,
+    DetectorKind::Dependencies,
+];
+
+impl ScanProfile {
+    /// Set the profile's detectors and thresholds on `config`
+    pub fn apply_to(self, config: &mut ScanConfig) {
+        let (min_severity, min_confidence) = match self {
+            ScanProfile::Strict => (Severity::Info, 0.0),
+            ScanProfile::Balanced => (Severity::Low, 0.5),
+            ScanProfile::Permissive => (Severity::Medium, 0.8),
+            ScanProfile::Ci => (Severity::Low, 0.7),
+        };
+        config.min_severity = min_severity;
+        config.min_confidence = min_confidence;
+        config.detectors = DetectorKind::ALL
+            .iter()
+            .copied()
+            .filter(|d| self != ScanProfile::Permissive || !HYGIENE_DETECTORS.contains(d))
+            .collect();
+    }
+
+    /// Level the scan fails at unless `--fail-on` is given
+    pub fn fail_on(self) -> SeverityLevel {
+        match self {
+            ScanProfile::Strict => SeverityLevel::Low,
+            ScanProfile::Balanced | ScanProfile::Ci => SeverityLevel::High,
+            ScanProfile::Permissive => SeverityLevel::Critical,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(profile: ScanProfile) -> ScanConfig {
+        let mut config = ScanConfig::default();
+        profile.apply_to(&mut config);
+        config
+    }
+
+    #[test]
+    fn test_profile_thresholds() {
+        for (profile, severity, confidence) in [
+            (ScanProfile::Strict, Severity::Info, 0.0),
+            (ScanProfile::Balanced, Severity::Low, 0.5),
+            (ScanProfile::Permissive, Severity::Medium, 0.8),
+            (ScanProfile::Ci, Severity::Low, 0.7),
+        ] {
+            let config = applied(profile);
+            assert_eq!(config.min_severity, severity, "{:?}", profile);
+            assert_eq!(config.min_confidence, confidence, "{:?}", profile);
+        }
+
+        assert_eq!(ScanProfile::Strict.fail_on(), SeverityLevel::Low);
+        assert_eq!(ScanProfile::Balanced.fail_on(), SeverityLevel::High);
+        assert_eq!(ScanProfile::Permissive.fail_on(), SeverityLevel::Critical);
+        assert_eq!(ScanProfile::Ci.fail_on(), SeverityLevel::High);
+    }
+
+    #[test]
+    fn test_profile_detectors() {
+        for profile in [ScanProfile::Strict, ScanProfile::Balanced, ScanProfile::Ci] {
+            assert_eq!(
+                applied(profile).detectors,
+                DetectorKind::ALL,
+                "{:?}",
+                profile
+            );
+        }
+        // Permissive leaves out the hygiene checks only
+        let permissive = applied(ScanProfile::Permissive).detectors;
+        assert_eq!(
+            permissive.len(),
+            DetectorKind::ALL.len() - HYGIENE_DETECTORS.len()
+        );
+        assert!(HYGIENE_DETECTORS.iter().all(|d| !permissive.contains(d)));
+        assert!(permissive.contains(&DetectorKind::Secrets));
+    }
+}
@@ -1,28 +1,74 @@
 //! Whitelist command implementation
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+use crate::models::config::AppConfig;
+use crate::storage::whitelist::{Whitelist, WhitelistEntry};
+
+fn whitelist_path() -> PathBuf {
+    AppConfig::default().whitelist_path
+}
 
 pub async fn add(item_type: String, name: String, hash: String) -> Result<()> {
-    // Phase 4 implementation
-    anyhow::bail!("Whitelist add not yet implemented - Phase 4")
+    let path = whitelist_path();
+    let mut whitelist = Whitelist::load(&path)?;
+    whitelist.add(WhitelistEntry::new(item_type, &name, &hash));
+    whitelist.save(&path)?;
+    println!("✅ Whitelisted {} ({})", name, hash);
+    Ok(())
 }
 
 pub async fn remove(hash: String) -> Result<()> {
-    // Phase 4 implementation
-    anyhow::bail!("Whitelist remove not yet implemented - Phase 4")
+    let path = whitelist_path();
+    let mut whitelist = Whitelist::load(&path)?;
+    if !whitelist.remove(&hash) {
+        anyhow::bail!("No whitelist entry with hash '{}'", hash);
+    }
+    whitelist.save(&path)?;
+    println!("✅ Removed {} from whitelist", hash);
+    Ok(())
 }
 
 pub async fn list() -> Result<()> {
-    // Phase 4 implementation
-    anyhow::bail!("Whitelist list not yet implemented - Phase 4")
+    let whitelist = Whitelist::load(&whitelist_path())?;
+    if whitelist.entries.is_empty() {
+        println!("Whitelist is empty");
+        return Ok(());
+    }
+
+    for entry in &whitelist.entries {
+        println!(
+            "{:<8} {:<30} {}  (added {})",
+            entry.item_type,
+            entry.name,
+            entry.hash,
+            entry.added_at.format("%Y-%m-%d")
+        );
+    }
+    Ok(())
 }
 
 pub async fn export(path: String) -> Result<()> {
-    // Phase 4 implementation
-    anyhow::bail!("Whitelist export not yet implemented - Phase 4")
+    let whitelist = Whitelist::load(&whitelist_path())?;
+    whitelist.save(&PathBuf::from(&path))?;
+    println!(
+        "✅ Exported {} entries to {}",
+        whitelist.entries.len(),
+        path
+    );
+    Ok(())
 }
 
 pub async fn import(path: String) -> Result<()> {
-    // Phase 4 implementation
-    anyhow::bail!("Whitelist import not yet implemented - Phase 4")
+    let imported = Whitelist::load(&PathBuf::from(&path))
+        .with_context(|| format!("Failed to import whitelist from '{}'", path))?;
+    let count = imported.entries.len();
+
+    let target = whitelist_path();
+    let mut whitelist = Whitelist::load(&target)?;
+    whitelist.merge(imported);
+    whitelist.save(&target)?;
+    println!("✅ Imported {} entries from {}", count, path);
+    Ok(())
 }
@@ -0,0 +1,304 @@
+//! Versioned, signed rule bundles
+//!
+//! Pattern sets that need no code beyond "regex per line" can ship as data,
+//! so new patterns reach users through `mcp-sentinel rules update` instead of
+//! a new binary. So far only the SQL injection patterns do; every other
+//! detector still compiles its patterns in. A bundle is a JSON document with
+//! a version and a list of rules, each attributed to an existing detector:
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "rules": [{
+//!     "id": "execute-concatenation",
+//!     "detector": "sql_injection",
+//!     "vuln_type": "sql_injection",
+//!     "severity": "critical",
+//!     "cwe": "CWE-89",
+//!     "title": "SQL Injection Pattern Detected",
+//!     "description": "Potential SQL injection via string concatenation",
+//!     "pattern": "execute\\s*\\([^)]*\\+[^)]*\\)"
+//!   }]
+//! }
+//! ```
+//!
+//! The binary embeds [`BUILTIN`] (`rules/builtin.json`). Downloaded bundles
+//! come with a detached Ed25519 signature (`<bundle>.sig`, base64) that must
+//! verify against the release signing key in `rules/release-signing-key.pub`,
+//! and are only used when their version is newer than the built-in one.
+//!
+//! The key file ships without a key (see CONTRIBUTING.md), so bundle
+//! verification is disabled: `rules update` and `rules import-bundle` refuse
+//! every bundle, installed ones are ignored, and scans use only [`BUILTIN`].
+//!
+//! Like other pattern detectors, only the first matching rule of a detector
+//! is reported per line.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::DetectorKind;
+use crate::models::config::ScanConfig;
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+/// Rules compiled into the binary
+pub static BUILTIN: Lazy<RuleBundle> = Lazy::new(|| {
+    RuleBundle::parse(include_str!("../../rules/builtin.json")).expect("built-in rule bundle")
+});
+
+/// Public half of the key release bundles are signed with, unset until a
+/// maintainer fills in `rules/release-signing-key.pub`
+static RELEASE_SIGNING_KEY: Lazy<Option<VerifyingKey>> = Lazy::new(|| {
+    let encoded = key_material(include_str!("../../rules/release-signing-key.pub"));
+    (!encoded.is_empty()).then(|| parse_public_key(&encoded).expect("release signing key"))
+});
+
+/// A versioned set of pattern rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleBundle {
+    /// Increases with every published bundle
+    pub version: u64,
+    pub rules: Vec<BundleRule>,
+    #[serde(skip)]
+    compiled: Vec<Regex>,
+}
+
+/// One pattern rule in a bundle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleRule {
+    /// Rule ID, reported as `<detector>/<id>`
+    pub id: String,
+    pub detector: DetectorKind,
+    pub vuln_type: VulnerabilityType,
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwe: Option<String>,
+    pub title: String,
+    pub description: String,
+    /// Regex matched against each line
+    pub pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impact: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    #[serde(default = "default_confidence")]
+    pub confidence: f32,
+}
+
+fn default_confidence() -> f32 {
+    0.8
+}
+
+impl PartialEq for RuleBundle {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.rules == other.rules
+    }
+}
+
+impl RuleBundle {
+    /// Parse a bundle and compile its patterns
+    pub fn parse(json: &str) -> Result<Self> {
+        let mut bundle: Self = serde_json::from_str(json).context("Invalid rule bundle")?;
+        bundle.compiled = bundle
+            .rules
+            .iter()
+            .map(|rule| {
+                Regex::new(&rule.pattern)
+                    .with_context(|| format!("Invalid pattern in rule '{}'", rule.id))
+            })
+            .collect::<Result<_>>()?;
+        Ok(bundle)
+    }
+
+    /// Load a bundle after checking its detached signature (`<path>.sig`)
+    /// against the release signing key
+    pub fn load_verified(path: &Path) -> Result<Self> {
+        let json = std::fs::read(path)
+            .with_context(|| format!("Failed to read rule bundle {}", path.display()))?;
+        let signature = std::fs::read_to_string(signature_path(path))
+            .with_context(|| format!("Missing signature for rule bundle {}", path.display()))?;
+        verify(release_key()?, &json, &signature)
+            .with_context(|| format!("Rule bundle {} failed verification", path.display()))?;
+        Self::parse(std::str::from_utf8(&json)?)
+    }
+
+    /// Findings of the rules attributed to `detector`
+    pub fn detect(
+        &self,
+        detector: DetectorKind,
+        content: &str,
+        file_path: &str,
+    ) -> Vec<Vulnerability> {
+        let rules: Vec<(&BundleRule, &Regex)> = self
+            .rules
+            .iter()
+            .zip(&self.compiled)
+            .filter(|(rule, _)| rule.detector == detector)
+            .collect();
+        if rules.is_empty() {
+            return Vec::new();
+        }
+
+        let mut vulnerabilities = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            // Every occurrence of the first rule that matches the line
+            let Some((rule, regex)) = rules.iter().find(|(_, regex)| regex.is_match(line)) else {
+                continue;
+            };
+            for m in regex.find_iter(line) {
+                let mut vuln = Vulnerability::new(
+                    format!("RULE-{:03}", vulnerabilities.len() + 1),
+                    rule.vuln_type.clone(),
+                    rule.severity,
+                    rule.title.clone(),
+                    rule.description.clone(),
+                )
+                .with_rule_id(rule.id.clone())
+                .with_pattern(rule.pattern.clone())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_code_snippet(line.to_string())
+                .with_confidence(rule.confidence);
+                if let Some(impact) = &rule.impact {
+                    vuln = vuln.with_impact(impact.clone());
+                }
+                if let Some(remediation) = &rule.remediation {
+                    vuln = vuln.with_remediation(remediation.clone());
+                }
+                let mut evidence = HashMap::new();
+                evidence.insert(
+                    "bundle_version".to_string(),
+                    serde_json::json!(self.version),
+                );
+                if let Some(cwe) = &rule.cwe {
+                    evidence.insert("cwe".to_string(), serde_json::json!(cwe));
+                }
+                vulnerabilities.push(vuln.with_evidence(evidence));
+            }
+        }
+
+        vulnerabilities
+    }
+}
+
+/// The bundle whose rules run: an installed bundle newer than the built-in
+/// one, otherwise [`BUILTIN`]
+pub fn active(config: &ScanConfig) -> &RuleBundle {
+    config
+        .rule_bundle
+        .as_ref()
+        .filter(|bundle| bundle.version > BUILTIN.version)
+        .unwrap_or(&BUILTIN)
+}
+
+/// Where the detached signature of a bundle file lives
+pub fn signature_path(bundle: &Path) -> PathBuf {
+    let mut name = bundle.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Check a base64 Ed25519 `signature` over `data`
+pub fn verify(key: &VerifyingKey, data: &[u8], signature: &str) -> Result<()> {
+    let bytes = STANDARD
+        .decode(signature.trim())
+        .context("Signature is not valid base64")?;
+    let signature = Signature::from_slice(&bytes).context("Malformed signature")?;
+    key.verify(data, &signature)
+        .context("Signature does not match the release signing key")
+}
+
+/// Verify a downloaded bundle with the release signing key and parse it
+pub fn verify_release(json: &[u8], signature: &str) -> Result<RuleBundle> {
+    verify(release_key()?, json, signature)?;
+    RuleBundle::parse(std::str::from_utf8(json)?)
+}
+
+/// The key release bundles must be signed with; an error in builds without one
+pub fn release_key() -> Result<&'static VerifyingKey> {
+    RELEASE_SIGNING_KEY.as_ref().context(
+        "This build has no release signing key (rules/release-signing-key.pub), \
+         so rule bundles cannot be verified",
+    )
+}
+
+/// The key in a public key file, without `#` comment lines
+fn key_material(file: &str) -> String {
+    file.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .collect()
+}
+
+fn parse_public_key(encoded: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = STANDARD
+        .decode(encoded.trim())?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Ed25519 public keys are 32 bytes"))?;
+    Ok(VerifyingKey::from_bytes(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_builtin_bundle_detects_sql_injection() {
+        let content = "cursor.execute(\"SELECT * FROM users WHERE id = \" + user_id)\n\
+                       cursor.execute(\"SELECT * FROM users WHERE id = ?\", (user_id,))\n";
+        let vulns = BUILTIN.detect(DetectorKind::SqlInjection, content, "db.py");
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("execute-concatenation"));
+        assert_eq!(
+            vulns[0].pattern.as_deref(),
+            Some(BUILTIN.rules[0].pattern.as_str())
+        );
+        assert_eq!(vulns[0].severity, Severity::Critical);
+        assert!(BUILTIN
+            .detect(DetectorKind::Secrets, content, "db.py")
+            .is_empty());
+        // A filled-in release key must parse
+        Lazy::force(&RELEASE_SIGNING_KEY);
+    }
+
+    #[test]
+    fn test_signature_verification() {
+        let signing = SigningKey::from_bytes(&[7; 32]);
+        let json = br#"{"version": 2, "rules": []}"#;
+        let signature = STANDARD.encode(signing.sign(json).to_bytes());
+
+        assert!(verify(&signing.verifying_key(), json, &signature).is_ok());
+        let tampered = br#"{"version": 3, "rules": []}"#;
+        assert!(verify(&signing.verifying_key(), tampered, &signature).is_err());
+        // Not signed with the release key
+        assert!(verify_release(json, &signature).is_err());
+
+        let file = format!(
+            "# comment\n{}\n",
+            STANDARD.encode(signing.verifying_key().as_bytes())
+        );
+        assert_eq!(
+            parse_public_key(&key_material(&file)).unwrap(),
+            signing.verifying_key()
+        );
+    }
+
+    #[test]
+    fn test_active_bundle_must_be_newer() {
+        let mut config = ScanConfig::default();
+        assert_eq!(active(&config).version, BUILTIN.version);
+
+        let newer = format!(r#"{{"version": {}, "rules": []}}"#, BUILTIN.version + 1);
+        config.rule_bundle = Some(RuleBundle::parse(&newer).unwrap());
+        assert!(active(&config).rules.is_empty());
+
+        config.rule_bundle = Some(RuleBundle::parse(r#"{"version": 0, "rules": []}"#).unwrap());
+        assert_eq!(active(&config).version, BUILTIN.version);
+    }
+}
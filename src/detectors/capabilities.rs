@@ -0,0 +1,221 @@
+//! Server capability inference
+//!
+//! Infers what a server can do from the modules it imports and the APIs it
+//! calls: read files, write files, run processes, talk to the network, and
+//! read the environment. Every use is kept with its location; the scan
+//! report condenses them into a capability matrix so a reviewer can ask
+//! whether, say, a weather server has any business spawning processes.
+//!
+//! This is an inventory, not a detector: it produces no findings.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Something a server can do to its host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    ReadsFiles,
+    WritesFiles,
+    ExecutesProcesses,
+    Network,
+    Environment,
+}
+
+impl Capability {
+    /// All capabilities, in matrix order
+    pub const ALL: [Capability; 5] = [
+        Capability::ReadsFiles,
+        Capability::WritesFiles,
+        Capability::ExecutesProcesses,
+        Capability::Network,
+        Capability::Environment,
+    ];
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Capability::ReadsFiles => "Reads files",
+            Capability::WritesFiles => "Writes files",
+            Capability::ExecutesProcesses => "Executes processes",
+            Capability::Network => "Network access",
+            Capability::Environment => "Reads environment",
+        }
+    }
+}
+
+/// One import or API call granting a capability
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityUse {
+    pub capability: Capability,
+    /// The import or call as written
+    pub signal: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// Files whose imports and calls are understood
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "py", "js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx", "go", "rs",
+];
+
+/// Imports and calls per capability (Python, JavaScript/TypeScript, Go, Rust)
+static SIGNALS: Lazy<Vec<(Capability, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Capability::ReadsFiles,
+            Regex::new(r#"\bopen\s*\(|\.read_(?:text|bytes)\s*\(|\bfs\.(?:promises\.)?(?:readFile|readdir|createReadStream|stat)\w*|\bread(?:File|dir)(?:Sync)?\s*\(|\bos\.(?:listdir|walk|scandir)\s*\(|\bglob\.i?glob\s*\(|\bos\.(?:ReadFile|Open|ReadDir)\s*\(|\bioutil\.ReadFile\s*\(|\bfs::(?:read|read_to_string|read_dir)\s*\(|\bFile::open\s*\("#).unwrap(),
+        ),
+        (
+            Capability::WritesFiles,
+            Regex::new(r#"\bopen\s*\([^)]*["'](?:w|a|x|r\+)b?\+?["']|\.write_(?:text|bytes)\s*\(|\bfs\.(?:promises\.)?(?:writeFile|appendFile|unlink|rm|rmdir|rename|mkdir|copyFile|createWriteStream)\w*|\b(?:writeFile|appendFile)(?:Sync)?\s*\(|\bos\.(?:remove|unlink|rename|replace|makedirs|mkdir|rmdir)\s*\(|\bshutil\.(?:rmtree|move|copy\w*)\s*\(|\bos\.(?:WriteFile|Create|Remove|RemoveAll|Rename|Mkdir\w*)\s*\(|\bfs::(?:write|remove_file|remove_dir_all|create_dir_all|rename|copy)\s*\(|\bFile::create\s*\("#).unwrap(),
+        ),
+        (
+            Capability::ExecutesProcesses,
+            Regex::new(r#"\bimport\s+subprocess\b|\bfrom\s+subprocess\s+import\b|\bsubprocess\.\w+|\bos\.(?:system|popen|exec\w*|spawn\w*)\s*\(|\basyncio\.create_subprocess_\w+|["'](?:node:)?child_process["']|\bexec(?:Sync|File|FileSync)\s*\(|\bspawn(?:Sync)?\s*\(|["'](?:execa|shelljs|cross-spawn)["']|"os/exec"|\bexec\.Command(?:Context)?\s*\(|\bstd::process::Command\b|\bCommand::new\s*\("#).unwrap(),
+        ),
+        (
+            Capability::Network,
+            Regex::new(r#"\bimport\s+(?:requests|httpx|aiohttp|urllib3|socket|smtplib|websockets?)\b|\bfrom\s+(?:requests|httpx|aiohttp|urllib\.request|urllib3|socket|smtplib|websockets?)\s+import\b|\b(?:requests|httpx)\.(?:get|post|put|patch|delete|request|Client|AsyncClient)\b|\burlopen\s*\(|\bfetch\s*\(|["'](?:axios|node-fetch|got|undici|ws|nodemailer)["']|\baxios\.\w+\s*\(|["'](?:node:)?(?:https?|net|dgram|tls)["']|\bhttps?\.(?:request|get)\s*\(|\bnet\.(?:connect|createConnection)\s*\(|\bnew\s+WebSocket\s*\(|"net/http"|\bhttp\.(?:Get|Post|NewRequest\w*)\s*\(|\bnet\.Dial\w*\s*\(|\breqwest::|\bTcpStream::connect\s*\("#).unwrap(),
+        ),
+        (
+            Capability::Environment,
+            Regex::new(r#"\bos\.environ\b|\bos\.getenv\s*\(|\bload_dotenv\s*\(|\bprocess\.env\b|["']dotenv(?:/config)?["']|\bos\.(?:Getenv|LookupEnv|Environ)\s*\(|\benv::vars?(?:_os)?\s*\(|\benv!\s*\("#).unwrap(),
+        ),
+    ]
+});
+
+/// Whether `file_path` is source code this module understands
+pub fn is_source(file_path: &str) -> bool {
+    std::path::Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e))
+}
+
+/// Every capability use in a source file
+pub fn extract_uses(content: &str, file_path: &str) -> Vec<CapabilityUse> {
+    if !is_source(file_path) {
+        return Vec::new();
+    }
+
+    let mut uses = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let code = line.trim_start();
+        if code.starts_with('#') || code.starts_with("//") || code.starts_with('*') {
+            continue;
+        }
+        for (capability, pattern) in SIGNALS.iter() {
+            let Some(m) = pattern
+                .find_iter(line)
+                .find(|m| !is_definition(&line[..m.start()]))
+            else {
+                continue;
+            };
+            uses.push(CapabilityUse {
+                capability: *capability,
+                signal: m.as_str().trim_end_matches(['(', ' ']).to_string(),
+                file: file_path.to_string(),
+                line: line_num + 1,
+            });
+        }
+    }
+    uses
+}
+
+/// Whether a match follows `def`, `function`, or `fn`: the server defines
+/// a function of that name rather than calling the API
+fn is_definition(before: &str) -> bool {
+    let before = before.trim_end();
+    ["def", "function", "fn", "func"]
+        .iter()
+        .any(|keyword| before.ends_with(keyword))
+}
+
+/// Uses grouped by capability, in [`Capability::ALL`] order; capabilities
+/// without uses get an empty row
+pub fn matrix(uses: &[CapabilityUse]) -> Vec<(Capability, Vec<&CapabilityUse>)> {
+    Capability::ALL
+        .iter()
+        .map(|capability| {
+            let rows = uses
+                .iter()
+                .filter(|u| u.capability == *capability)
+                .collect();
+            (*capability, rows)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(content: &str, file_path: &str) -> Vec<Capability> {
+        let mut found: Vec<Capability> = extract_uses(content, file_path)
+            .into_iter()
+            .map(|u| u.capability)
+            .collect();
+        found.dedup();
+        found
+    }
+
+    #[test]
+    fn test_python_capabilities() {
+        let content = r#"
+import os
+import subprocess
+import httpx
+
+API_KEY = os.environ["WEATHER_API_KEY"]
+
+# subprocess is only used in tests
+def fetch(city):
+    return httpx.get(f"https://api.example.com/{city}")
+
+def run(cmd):
+    return subprocess.run(cmd, shell=True)
+"#;
+        assert_eq!(
+            capabilities(content, "server.py"),
+            vec![
+                Capability::ExecutesProcesses,
+                Capability::Network,
+                Capability::Environment,
+                Capability::Network,
+                Capability::ExecutesProcesses,
+            ]
+        );
+        let uses = extract_uses(content, "server.py");
+        assert_eq!(uses[0].signal, "import subprocess");
+        assert_eq!(uses[0].line, 3);
+    }
+
+    #[test]
+    fn test_javascript_capabilities() {
+        let content = r#"
+import { readFile, writeFile } from "node:fs/promises";
+import { execSync } from "node:child_process";
+
+const token = process.env.GITHUB_TOKEN;
+const data = await readFile(path, "utf8");
+await writeFile(out, data);
+"#;
+        let rows = matrix(&extract_uses(content, "index.ts"))
+            .into_iter()
+            .map(|(capability, uses)| (capability, uses.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (Capability::ReadsFiles, 1),
+                (Capability::WritesFiles, 1),
+                (Capability::ExecutesProcesses, 1),
+                (Capability::Network, 0),
+                (Capability::Environment, 1),
+            ]
+        );
+        assert!(extract_uses(content, "README.md").is_empty());
+    }
+}
@@ -9,6 +9,9 @@
 //! - **Python**: `eval()`, `exec()`, `compile()`, `__import__()`
 //! - **JavaScript**: `eval()`, `Function()` constructor, `vm.runInNewContext()`
 //! - **Ruby**: `eval()`, `instance_eval()`, `class_eval()`
+//! - **PHP**: `eval()`, `assert()`, and `include`/`require`, `call_user_func()`,
+//!   or `extract()` driven by request parameters
+//! - **Java**: `ScriptEngine.eval()`, JNDI `lookup()` of dynamic names
 //!
 //! # CWE Reference
 //!
@@ -20,7 +23,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
 
-use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
 
 /// Code injection pattern definition
 struct CodeInjectionPattern {
@@ -194,6 +197,63 @@ static CODE_INJECTION_PATTERNS: Lazy<Vec<CodeInjectionPattern>> = Lazy::new(|| {
             description: "Code execution using preg_replace with /e modifier detected",
             severity: Severity::Critical,
         },
+        // PHP - include/require driven by request parameters (LFI/RFI)
+        CodeInjectionPattern {
+            name: "PHP include/require of request data",
+            language: "PHP",
+            regex: Regex::new(
+                r#"\b(?:include|require)(?:_once)?\b[^;]*\$_(?:GET|POST|REQUEST|COOKIE)\b"#,
+            )
+            .unwrap(),
+            description: "File inclusion path taken from request parameters detected",
+            severity: Severity::Critical,
+        },
+        // PHP - user-controlled callables
+        CodeInjectionPattern {
+            name: "PHP call_user_func() with request data",
+            language: "PHP",
+            regex: Regex::new(
+                r#"\b(?:call_user_func(?:_array)?|forward_static_call(?:_array)?|array_map|array_filter|usort)\s*\(\s*\$_(?:GET|POST|REQUEST|COOKIE)\b|\$_(?:GET|POST|REQUEST|COOKIE)\s*\[[^\]]+\]\s*\("#,
+            )
+            .unwrap(),
+            description: "Function name or callable taken from request parameters detected",
+            severity: Severity::Critical,
+        },
+        // PHP - extract()/parse_str() overwriting local variables
+        CodeInjectionPattern {
+            name: "PHP extract() on request data",
+            language: "PHP",
+            regex: Regex::new(
+                r#"\bextract\s*\(\s*\$_(?:GET|POST|REQUEST|COOKIE|FILES|SERVER)\b|\bparse_str\s*\([^,)]*\)"#,
+            )
+            .unwrap(),
+            description: "Request parameters imported as local variables, letting callers \
+                          overwrite any variable in scope",
+            severity: Severity::High,
+        },
+        // Java - ScriptEngine.eval()
+        CodeInjectionPattern {
+            name: "Java ScriptEngine.eval()",
+            language: "Java",
+            regex: Regex::new(
+                r#"(?:\b\w*[eE]ngine|getEngineBy(?:Name|Extension|MimeType)\s*\([^)]*\))\s*\.eval\s*\("#,
+            )
+            .unwrap(),
+            description: "Script evaluation through javax.script (Nashorn, Rhino, Groovy) detected",
+            severity: Severity::Critical,
+        },
+        // Java - JNDI lookup with a non-literal name (Log4Shell-style)
+        CodeInjectionPattern {
+            name: "Java JNDI lookup with dynamic name",
+            language: "Java",
+            regex: Regex::new(
+                r#"\b(?:new\s+InitialContext\s*\(\s*\)|\w*[cC]ontext|\w*[cC]tx)\s*\.lookup\s*\(\s*(?:[a-z_][\w.]*|"[^"]*"\s*\+)"#,
+            )
+            .unwrap(),
+            description: "JNDI lookup of a user-controlled name can load remote classes \
+                          over LDAP/RMI",
+            severity: Severity::Critical,
+        },
     ]
 });
 
@@ -231,9 +291,7 @@ pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
         }
 
         for pattern in CODE_INJECTION_PATTERNS.iter() {
-            if pattern.regex.is_match(line) {
-                let column = line.find(pattern.regex.as_str()).unwrap_or(0) + 1;
-
+            for m in pattern.regex.find_iter(line) {
                 let vuln = Vulnerability::new(
                     format!("CODE-INJ-{:03}", id_counter),
                     VulnerabilityType::CodeInjection,
@@ -241,11 +299,9 @@ pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
                     format!("{} Detected", pattern.name),
                     pattern.description.to_string(),
                 )
-                .with_location(
-                    Location::new(file_path)
-                        .with_line(line_num + 1)
-                        .with_column(column),
-                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
                 .with_impact(
                     "Attackers can execute arbitrary code on the server, \
                      leading to complete system compromise, data theft, or \
@@ -299,6 +355,27 @@ mod tests {
         assert!(vulns.iter().any(|v| v.title.contains("eval")));
     }
 
+    #[test]
+    fn test_every_match_on_a_line() {
+        let content = "total = eval(a) + eval(b)";
+
+        let vulns: Vec<_> = detect(content, "test.py")
+            .unwrap()
+            .into_iter()
+            .filter(|v| v.rule_id.as_deref() == Some("python-eval-usage"))
+            .collect();
+        let spans: Vec<_> = vulns
+            .iter()
+            .map(|v| {
+                let location = v.location.as_ref().unwrap();
+                (location.column.unwrap(), location.end_column.unwrap())
+            })
+            .collect();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(&content[spans[0].0 - 1..spans[0].1 - 1], "eval(");
+        assert!(spans[1].0 > spans[0].1);
+    }
+
     #[test]
     fn test_detect_python_exec() {
         let content = r#"exec(user_code)"#;
@@ -361,6 +438,43 @@ mod tests {
         assert!(!vulns.is_empty());
     }
 
+    #[test]
+    fn test_php_request_driven_calls() {
+        let content = r#"
+            include($_GET['page'] . '.php');
+            $result = call_user_func($_POST['action'], $args);
+            extract($_REQUEST);
+            require_once __DIR__ . '/config.php';
+        "#;
+
+        let vulns = detect(content, "index.php").unwrap();
+        let titles: Vec<&str> = vulns.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "PHP include/require of request data Detected",
+                "PHP call_user_func() with request data Detected",
+                "PHP extract() on request data Detected",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_java_script_engine_and_jndi() {
+        let content = r#"
+            Object result = engine.eval(userScript);
+            Object obj = new InitialContext().lookup(request.getParameter("resource"));
+            DataSource ds = (DataSource) ctx.lookup("java:comp/env/jdbc/db");
+        "#;
+
+        let vulns = detect(content, "Handler.java").unwrap();
+        assert!(vulns.iter().any(|v| v.title.contains("ScriptEngine")));
+        assert!(vulns.iter().any(|v| v.title.contains("JNDI")));
+        assert!(!vulns
+            .iter()
+            .any(|v| v.location.as_ref().unwrap().line == Some(4)));
+    }
+
     #[test]
     fn test_no_false_positives_safe_code() {
         let content = r#"
@@ -5,7 +5,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
 
-use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
 
 /// Command injection pattern definition
 struct CommandInjectionPattern {
@@ -70,6 +70,54 @@ static COMMAND_INJECTION_PATTERNS: Lazy<Vec<CommandInjectionPattern>> = Lazy::ne
             severity: Severity::High,
             description: "Using Function constructor can lead to code injection",
         },
+        // Java
+        CommandInjectionPattern {
+            name: "Runtime.exec() usage",
+            language: "Java",
+            regex: Regex::new(r#"\bRuntime\.getRuntime\s*\(\s*\)\s*\.exec\s*\("#).unwrap(),
+            severity: Severity::Critical,
+            description: "Runtime.exec() with a command string splits on whitespace and runs \
+                          whatever the string names, including user-supplied parts",
+        },
+        CommandInjectionPattern {
+            name: "ProcessBuilder via shell",
+            language: "Java",
+            regex: Regex::new(
+                r#"\bnew\s+ProcessBuilder\s*\(\s*(?:(?:Arrays\.asList|List\.of)\s*\(\s*)?"(?:/bin/)?(?:sh|bash|cmd(?:\.exe)?|powershell)""#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            description: "Running commands through a shell interprets metacharacters in \
+                          any user-built argument",
+        },
+        // Rust
+        CommandInjectionPattern {
+            name: "std::process::Command via shell",
+            language: "Rust",
+            regex: Regex::new(
+                r#"Command::new\s*\(\s*"(?:/bin/)?(?:sh|bash|zsh|cmd(?:\.exe)?|powershell)""#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            description: "Running commands through a shell (sh -c) interprets metacharacters \
+                          in any user-built argument",
+        },
+        CommandInjectionPattern {
+            name: "std::process::Command with dynamic program",
+            language: "Rust",
+            regex: Regex::new(r#"Command::new\s*\(\s*(?:&?format!|&?[a-z_][\w.]*\s*\))"#).unwrap(),
+            severity: Severity::High,
+            description: "Choosing the program to run from a variable can execute \
+                          attacker-chosen binaries",
+        },
+        CommandInjectionPattern {
+            name: "std::process::Command argument built with format!",
+            language: "Rust",
+            regex: Regex::new(r#"\.args?\s*\(\s*(?:&|\[\s*)?format!"#).unwrap(),
+            severity: Severity::Medium,
+            description: "Arguments built from user input can inject options (e.g. --output) \
+                          into the invoked program",
+        },
     ]
 });
 
@@ -79,6 +127,8 @@ struct SensitiveFilePattern {
     regex: Regex,
     severity: Severity,
     description: &'static str,
+    /// Replaces the default "ask for consent" advice
+    remediation: Option<&'static str>,
 }
 
 static SENSITIVE_FILE_PATTERNS: Lazy<Vec<SensitiveFilePattern>> = Lazy::new(|| {
@@ -86,56 +136,79 @@ static SENSITIVE_FILE_PATTERNS: Lazy<Vec<SensitiveFilePattern>> = Lazy::new(|| {
         // SSH Keys
         SensitiveFilePattern {
             name: "SSH Private Key Access",
-            regex: Regex::new(r#"['"](~?/?\.ssh/id_(rsa|ed25519|ecdsa|dsa))['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.ssh/id_(rsa|ed25519|ecdsa|dsa))['"]"#).unwrap(),
             severity: Severity::Critical,
             description: "Accessing SSH private keys without user permission",
+            remediation: None,
         },
         SensitiveFilePattern {
             name: "SSH Known Hosts Access",
-            regex: Regex::new(r#"['"](~?/?\.ssh/known_hosts)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.ssh/known_hosts)['"]"#).unwrap(),
             severity: Severity::High,
             description: "Accessing SSH known_hosts file",
+            remediation: None,
         },
         // AWS Credentials
         SensitiveFilePattern {
             name: "AWS Credentials Access",
-            regex: Regex::new(r#"['"](~?/?\.aws/credentials)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.aws/credentials)['"]"#).unwrap(),
             severity: Severity::Critical,
             description: "Accessing AWS credentials file",
+            remediation: None,
         },
         SensitiveFilePattern {
             name: "AWS Config Access",
-            regex: Regex::new(r#"['"](~?/?\.aws/config)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.aws/config)['"]"#).unwrap(),
             severity: Severity::High,
             description: "Accessing AWS configuration file",
+            remediation: None,
         },
         // GCP Credentials
         SensitiveFilePattern {
             name: "GCP Credentials Access",
-            regex: Regex::new(r#"['"](~?/?\.config/gcloud/[^'"]*)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.config/gcloud/[^'"]*)['"]"#).unwrap(),
             severity: Severity::Critical,
             description: "Accessing Google Cloud credentials",
+            remediation: None,
         },
         // Environment Files
         SensitiveFilePattern {
             name: ".env File Access",
-            regex: Regex::new(r#"['"]\.env(\.local|\.production)?['""]#).unwrap(),
+            regex: Regex::new(r#"['"]\.env(\.local|\.production)?['"]"#).unwrap(),
             severity: Severity::High,
             description: "Accessing environment variable files that may contain secrets",
+            remediation: None,
         },
         // Shell RC Files (can contain secrets)
         SensitiveFilePattern {
             name: "Shell RC File Access",
-            regex: Regex::new(r#"['"](~?/?\.bashrc|~?/?\.zshrc|~?/?\.profile)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](~?/?\.bashrc|~?/?\.zshrc|~?/?\.profile)['"]"#).unwrap(),
             severity: Severity::Medium,
             description: "Accessing shell configuration files that may contain secrets",
+            remediation: None,
         },
         // Browser Data
         SensitiveFilePattern {
             name: "Browser Cookie Access",
-            regex: Regex::new(r#"['"](.*/(Chrome|Firefox|Safari)/.*[Cc]ookies?.*)['""]#).unwrap(),
+            regex: Regex::new(r#"['"](.*/(Chrome|Firefox|Safari)/.*[Cc]ookies?.*)['"]"#).unwrap(),
             severity: Severity::Critical,
             description: "Accessing browser cookies without user permission",
+            remediation: None,
+        },
+        // Credentials compiled into a Rust binary
+        SensitiveFilePattern {
+            name: "Credential File Embedded at Compile Time",
+            regex: Regex::new(
+                r#"\binclude_(?:str|bytes)!\s*\(\s*"([^"]*(?:\.env(?:\.\w+)?|\.pem|\.key|\.p12|\.pfx|id_rsa|id_ed25519|credentials(?:\.json)?|secrets?\.(?:json|toml|ya?ml)|\.npmrc|\.pypirc))""#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            description: "include_str!/include_bytes! copies a credential file into the \
+                          compiled binary, where anyone with the binary can extract it",
+            remediation: Some(
+                "Load credentials at runtime from the environment or a secrets manager \
+                 instead of embedding them with include_str!/include_bytes!",
+            ),
         },
     ]
 });
@@ -147,7 +220,7 @@ pub fn detect_command_injection(content: &str, file_path: &str) -> Result<Vec<Vu
 
     for (line_num, line) in content.lines().enumerate() {
         for pattern in COMMAND_INJECTION_PATTERNS.iter() {
-            if pattern.regex.is_match(line) {
+            for m in pattern.regex.find_iter(line) {
                 let vuln = Vulnerability::new(
                     format!("CMD-{:03}", id_counter),
                     VulnerabilityType::CommandInjection,
@@ -155,12 +228,17 @@ pub fn detect_command_injection(content: &str, file_path: &str) -> Result<Vec<Vu
                     format!("Command Injection: {}", pattern.name),
                     pattern.description.to_string(),
                 )
-                .with_location(Location::new(file_path).with_line(line_num + 1))
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
                 .with_impact("Attackers can execute arbitrary system commands")
                 .with_remediation(format!(
                     "Use safe alternatives:\n\
                      - Python: Use subprocess.run() with array arguments and shell=False\n\
                      - JavaScript: Use child_process.execFile() or spawn() with array arguments\n\
+                     - Java: Use ProcessBuilder with a fixed program and separate arguments\n\
+                     - Rust: Use Command::new(program).args([...]) without a shell, and end \
+                     options with \"--\" before user-supplied values\n\
                      - Always validate and sanitize user input"
                 ))
                 .with_code_snippet(line.trim().to_string())
@@ -182,17 +260,15 @@ pub fn detect_command_injection(content: &str, file_path: &str) -> Result<Vec<Vu
 }
 
 /// Detect sensitive file access
-pub fn detect_sensitive_file_access(
-    content: &str,
-    file_path: &str,
-) -> Result<Vec<Vulnerability>> {
+pub fn detect_sensitive_file_access(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
     let mut vulnerabilities = Vec::new();
     let mut id_counter = 1;
 
     for (line_num, line) in content.lines().enumerate() {
         for pattern in SENSITIVE_FILE_PATTERNS.iter() {
-            if let Some(captures) = pattern.regex.captures(line) {
+            for captures in pattern.regex.captures_iter(line) {
                 let file_accessed = captures.get(1).map(|m| m.as_str()).unwrap_or("unknown");
+                let span = captures.get(0).map_or(0..0, |m| m.range());
 
                 let vuln = Vulnerability::new(
                     format!("FILE-{:03}", id_counter),
@@ -201,16 +277,17 @@ pub fn detect_sensitive_file_access(
                     format!("Sensitive File Access: {}", pattern.name),
                     pattern.description.to_string(),
                 )
-                .with_location(Location::new(file_path).with_line(line_num + 1))
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, span))
                 .with_impact(format!(
                     "Unauthorized access to {} can expose sensitive credentials",
                     file_accessed
                 ))
-                .with_remediation(
+                .with_remediation(pattern.remediation.unwrap_or(
                     "Request explicit user permission before accessing sensitive files.\n\
-                     Use MCP prompts to ask for user consent."
-                        .to_string(),
-                )
+                     Use MCP prompts to ask for user consent.",
+                ))
                 .with_code_snippet(line.trim().to_string())
                 .with_confidence(0.90);
 
@@ -270,6 +347,58 @@ mod tests {
         assert!(vulns.len() >= 3);
     }
 
+    #[test]
+    fn test_detect_command_injection_java() {
+        let content = r#"
+            Process p = Runtime.getRuntime().exec("convert " + fileName + " out.png");
+            new ProcessBuilder("sh", "-c", command).start();
+            new ProcessBuilder("git", "status").start();
+        "#;
+
+        let vulns = detect_command_injection(content, "Tools.java").unwrap();
+        assert!(vulns.iter().any(|v| v.title.contains("Runtime.exec")));
+        assert!(vulns
+            .iter()
+            .any(|v| v.title.contains("ProcessBuilder via shell")));
+        assert!(!vulns
+            .iter()
+            .any(|v| v.location.as_ref().unwrap().line == Some(4)));
+    }
+
+    #[test]
+    fn test_detect_command_injection_rust() {
+        let content = r#"
+            let out = Command::new("sh").arg("-c").arg(&cmd).output()?;
+            let child = Command::new(&tool_path).spawn()?;
+            Command::new("git").arg(format!("--upload-pack={}", pack)).status()?;
+            Command::new("git").args(["status", "--short"]).status()?;
+        "#;
+
+        let vulns = detect_command_injection(content, "main.rs").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert!(vulns
+            .iter()
+            .all(|v| v.evidence.as_ref().unwrap()["language"] == "Rust"));
+    }
+
+    #[test]
+    fn test_sensitive_file_patterns_end_at_closing_quote() {
+        let cases = [
+            ("'~/.ssh/known_hosts'", "Known Hosts"),
+            ("\"~/.aws/config\"", "AWS Config"),
+            ("\"~/.config/gcloud/adc.json\"", "GCP"),
+            ("'.env.production'", ".env"),
+            ("\"~/.zshrc\"", "Shell RC"),
+            ("\"/u/Chrome/Default/Cookies\"", "Cookie"),
+        ];
+        for (path, name) in cases {
+            let line = format!("data = open({}).read()", path);
+            let vulns = detect_sensitive_file_access(&line, "test.py").unwrap();
+            assert_eq!(vulns.len(), 1, "{}", line);
+            assert!(vulns[0].title.contains(name), "{}", vulns[0].title);
+        }
+    }
+
     #[test]
     fn test_detect_sensitive_file_access() {
         let content = r#"
@@ -287,6 +416,16 @@ mod tests {
         assert!(vulns.iter().any(|v| v.title.contains(".env")));
     }
 
+    #[test]
+    fn test_detect_embedded_credential_file() {
+        let content = r#"const KEY: &str = include_str!("../secrets/service.pem");"#;
+
+        let vulns = detect_sensitive_file_access(content, "main.rs").unwrap();
+        assert_eq!(vulns.len(), 1);
+        let remediation = vulns[0].remediation.as_deref().unwrap();
+        assert!(remediation.contains("at runtime"));
+    }
+
     #[test]
     fn test_no_false_positives() {
         let content = r#"
@@ -0,0 +1,376 @@
+//! Dependency pinning and lockfile hygiene
+//!
+//! MCP servers are usually installed with `npx`/`uvx`, which resolve
+//! dependencies fresh on every install. A wildcard version or an unpinned git
+//! dependency means a compromised upstream release lands silently the next
+//! time someone installs the server.
+//!
+//! Per-file checks cover `package.json`, `requirements*.txt`, and
+//! `pyproject.toml`:
+//!
+//! - `unpinned-version` - `*`, `latest`, empty, or open-ended (`>=`) versions,
+//!   and bare requirement names
+//! - `unpinned-git-dependency` - git URLs without a commit SHA
+//!
+//! `missing-lockfile` needs to know which other files exist, so it is a
+//! separate server-level pass over [`Manifest`]s (see
+//! [`super::scan_lockfiles`]).
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// A git reference pinned to a full commit SHA (`#<sha>` or `@<sha>`)
+static COMMIT_PIN: Lazy<Regex> = Lazy::new(|| Regex::new(r#"[#@][0-9a-f]{40}\b"#).unwrap());
+
+/// A requirement line: name, optional extras, and the rest (specifiers, markers)
+static REQUIREMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^([A-Za-z0-9][A-Za-z0-9._\-]*)\s*(?:\[[^\]]*\])?\s*(.*)$"#).unwrap()
+});
+
+/// npm `owner/repo` GitHub shorthand
+static GITHUB_SHORTHAND: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^[\w.\-]+/[\w.\-]+(?:#.*)?$"#).unwrap());
+
+/// A manifest that should be accompanied by a lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub path: String,
+    pub has_lockfile: bool,
+}
+
+fn file_name(file_path: &str) -> &str {
+    file_path.rsplit(['/', '\\']).next().unwrap_or(file_path)
+}
+
+/// Whether `file_path` is a dependency manifest this detector reads
+pub fn is_manifest(file_path: &str) -> bool {
+    let name = file_name(file_path);
+    name == "package.json"
+        || name == "pyproject.toml"
+        || (name.starts_with("requirements") && name.ends_with(".txt"))
+}
+
+/// Lockfiles that satisfy a manifest, or an empty slice if the manifest is
+/// its own lock (`requirements.txt`)
+pub fn lockfiles_for(file_path: &str) -> &'static [&'static str] {
+    match file_name(file_path) {
+        "package.json" => &[
+            "package-lock.json",
+            "npm-shrinkwrap.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "bun.lock",
+            "bun.lockb",
+        ],
+        "pyproject.toml" => &["uv.lock", "poetry.lock", "pdm.lock", "requirements.txt"],
+        _ => &[],
+    }
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let name = file_name(file_path);
+    let lines: Vec<&str> = content.lines().collect();
+
+    let mut report = |rule: &str, package: &str, spec: &str, line: usize| {
+        let source = lines.get(line - 1).copied();
+        vulnerabilities.push(finding(rule, package, spec, file_path, line, source));
+    };
+    // First line mentioning `needle` (the manifests are small)
+    let locate = |needle: &str| {
+        lines
+            .iter()
+            .position(|l| l.contains(needle))
+            .map_or(1, |i| i + 1)
+    };
+
+    if name == "package.json" {
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+            return Ok(Vec::new());
+        };
+        for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+            let Some(deps) = manifest.get(section).and_then(|d| d.as_object()) else {
+                continue;
+            };
+            for (package, spec) in deps {
+                let spec = spec.as_str().unwrap_or_default().trim();
+                let line = locate(&format!("\"{}\"", package));
+                if let Some(rule) = npm_spec_problem(spec) {
+                    report(rule, package, spec, line);
+                }
+            }
+        }
+    } else if name == "pyproject.toml" {
+        let Ok(manifest) = content.parse::<toml::Table>() else {
+            return Ok(Vec::new());
+        };
+        let pep508 = manifest
+            .get("project")
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.as_str());
+        for requirement in pep508 {
+            if let Some((package, rule)) = requirement_problem(requirement, false) {
+                report(rule, package, requirement, locate(requirement));
+            }
+        }
+
+        let poetry = manifest
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_table());
+        for (package, spec) in poetry.into_iter().flatten() {
+            let rule = match spec {
+                toml::Value::String(version) if version.trim() == "*" => Some("unpinned-version"),
+                toml::Value::Table(table) if table.contains_key("git") => {
+                    (!table.contains_key("rev")).then_some("unpinned-git-dependency")
+                }
+                _ => None,
+            };
+            if let Some(rule) = rule {
+                let spec = spec.to_string();
+                report(rule, package, &spec, locate(&format!("{} ", package)));
+            }
+        }
+    } else if is_manifest(file_path) {
+        for (index, line) in lines.iter().enumerate() {
+            let requirement = line.split(" #").next().unwrap_or(line).trim();
+            if requirement.is_empty()
+                || requirement.starts_with('#')
+                || (requirement.starts_with('-') && !requirement.starts_with("-e"))
+            {
+                continue;
+            }
+            if let Some((package, rule)) = requirement_problem(requirement, true) {
+                report(rule, package, requirement, index + 1);
+            }
+        }
+    }
+
+    vulnerabilities.sort_by_key(|v| v.location.as_ref().and_then(|l| l.line));
+    Ok(vulnerabilities)
+}
+
+/// What is wrong with an npm version spec, if anything
+///
+/// Caret and tilde ranges are normal in npm and held in place by the
+/// lockfile, so only specs that float across major versions are flagged.
+fn npm_spec_problem(spec: &str) -> Option<&'static str> {
+    let is_git = spec.starts_with("git")
+        || spec.starts_with("github:")
+        || spec.contains("github.com/")
+        || GITHUB_SHORTHAND.is_match(spec);
+    if is_git {
+        return (!COMMIT_PIN.is_match(spec)).then_some("unpinned-git-dependency");
+    }
+    let floating = matches!(spec, "" | "*" | "x" | "latest" | "next")
+        || (spec.starts_with('>') && !spec.contains('<'));
+    floating.then_some("unpinned-version")
+}
+
+/// Package name and problem of a PEP 508 requirement or requirements.txt line
+fn requirement_problem(requirement: &str, bare_is_unpinned: bool) -> Option<(&str, &'static str)> {
+    // `git+https://...#egg=name` (requirements.txt) or `name @ git+https://...`
+    if let Some(url) = requirement
+        .split_whitespace()
+        .find(|part| part.starts_with("git+"))
+    {
+        let package = match url.split_once("egg=") {
+            Some((_, egg)) => egg,
+            None => requirement.split('@').next().unwrap_or(url).trim(),
+        };
+        return (!COMMIT_PIN.is_match(url)).then_some((package, "unpinned-git-dependency"));
+    }
+
+    let captures = REQUIREMENT.captures(requirement)?;
+    let package = captures.get(1)?.as_str();
+    let rest = captures.get(2).map_or("", |m| m.as_str());
+    let specifier = rest.split(';').next().unwrap_or_default().trim();
+
+    // Other direct URL references (`name @ https://.../pkg.whl`) are left alone
+    let floating = !specifier.starts_with('@')
+        && ((specifier.is_empty() && bare_is_unpinned)
+            || specifier == "*"
+            || (specifier.starts_with('>') && !specifier.contains('<')));
+    floating.then_some((package, "unpinned-version"))
+}
+
+fn finding(
+    rule: &str,
+    package: &str,
+    spec: &str,
+    file_path: &str,
+    line: usize,
+    source: Option<&str>,
+) -> Vulnerability {
+    let (severity, title, description, remediation, cwe) = match rule {
+        "unpinned-git-dependency" => (
+            Severity::Medium,
+            format!("Git Dependency '{}' Not Pinned to a Commit", package),
+            "The dependency follows a branch or tag, which the repository owner (or an \
+             attacker with push access) can move at any time",
+            "Pin the git URL to a full commit SHA",
+            "CWE-829",
+        ),
+        _ => (
+            Severity::Low,
+            format!("Unpinned Dependency '{}'", package),
+            "The version range accepts any future release, including a compromised one",
+            "Pin an exact version or a bounded range, and commit a lockfile",
+            "CWE-1357",
+        ),
+    };
+
+    let mut evidence = HashMap::new();
+    evidence.insert("cwe".to_string(), serde_json::json!(cwe));
+    evidence.insert("package".to_string(), serde_json::json!(package));
+    evidence.insert("version_spec".to_string(), serde_json::json!(spec));
+
+    let mut vuln = Vulnerability::new(
+        format!("DEP-{:03}", line),
+        VulnerabilityType::SupplyChainAttack,
+        severity,
+        title,
+        description,
+    )
+    .with_rule_id(rule)
+    .with_location(Location::new(file_path).with_line(line))
+    .with_remediation(remediation)
+    .with_confidence(0.9)
+    .with_evidence(evidence);
+    if let Some(source) = source {
+        vuln = vuln.with_code_snippet(source.trim().to_string());
+    }
+    vuln
+}
+
+/// Report manifests that have no lockfile next to them (or in a parent
+/// directory, for workspaces)
+pub fn detect_missing_lockfiles(manifests: &[Manifest]) -> Vec<Vulnerability> {
+    manifests
+        .iter()
+        .filter(|m| !m.has_lockfile && !lockfiles_for(&m.path).is_empty())
+        .map(|m| {
+            let mut evidence = HashMap::new();
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-1357"));
+            evidence.insert(
+                "expected_lockfiles".to_string(),
+                serde_json::json!(lockfiles_for(&m.path)),
+            );
+
+            Vulnerability::new(
+                "DEP-LOCK",
+                VulnerabilityType::SupplyChainAttack,
+                Severity::Medium,
+                format!("No Lockfile for {}", file_name(&m.path)),
+                "Without a lockfile every install resolves dependencies afresh, so a \
+                 malicious release of any transitive dependency is picked up silently",
+            )
+            .with_rule_id("missing-lockfile")
+            .with_location(Location::new(&m.path))
+            .with_remediation(
+                "Generate and commit a lockfile (npm install / uv lock / poetry lock) and \
+                 install with it (npm ci, uv sync --frozen)",
+            )
+            .with_confidence(0.9)
+            .with_evidence(evidence)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_package_json() {
+        let content = r#"{
+  "name": "mcp-server-demo",
+  "dependencies": {
+    "@modelcontextprotocol/sdk": "^1.0.0",
+    "left-pad": "*",
+    "zod": "latest",
+    "helper": "github:someone/helper#main",
+    "pinned": "git+https://github.com/a/b.git#0123456789abcdef0123456789abcdef01234567"
+  }
+}"#;
+        let vulns = detect(content, "package.json").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "unpinned-version",
+                "unpinned-version",
+                "unpinned-git-dependency"
+            ]
+        );
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(5));
+    }
+
+    #[test]
+    fn test_requirements_txt() {
+        let content = "# deps\nmcp==1.2.0\nrequests\nhttpx>=0.27\npydantic>=2,<3\ngit+https://github.com/a/tool.git@main#egg=tool\n-r base.txt\n";
+        let vulns = detect(content, "requirements-dev.txt").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "unpinned-version",
+                "unpinned-version",
+                "unpinned-git-dependency"
+            ]
+        );
+        assert_eq!(vulns[2].evidence.as_ref().unwrap()["package"], "tool");
+    }
+
+    #[test]
+    fn test_pyproject() {
+        let content = r#"
+[project]
+dependencies = ["mcp>=1.0", "anyio>=4,<5", "tool @ git+https://github.com/a/tool.git"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+fastmcp = "*"
+"#;
+        let vulns = detect(content, "pyproject.toml").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "unpinned-version",
+                "unpinned-git-dependency",
+                "unpinned-version"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_lockfiles() {
+        let manifests = vec![
+            Manifest {
+                path: "a/package.json".into(),
+                has_lockfile: false,
+            },
+            Manifest {
+                path: "b/package.json".into(),
+                has_lockfile: true,
+            },
+            Manifest {
+                path: "requirements.txt".into(),
+                has_lockfile: false,
+            },
+        ];
+        let vulns = detect_missing_lockfiles(&manifests);
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].location.as_ref().unwrap().file, "a/package.json");
+    }
+}
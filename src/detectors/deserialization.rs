@@ -8,7 +8,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
 
-use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
 
 struct DeserializationPattern {
     name: &'static str,
@@ -18,6 +18,10 @@ struct DeserializationPattern {
     severity: Severity,
 }
 
+/// Argument names that usually hold request data in Rust handlers
+const UNTRUSTED_RUST_INPUT: &str =
+    r#"&?(?:body|req|request|input|payload|data|bytes|buf|buffer|msg|message|params|args)\b"#;
+
 static DESERIALIZATION_PATTERNS: Lazy<Vec<DeserializationPattern>> = Lazy::new(|| {
     vec![
         // Python - pickle.loads
@@ -60,6 +64,17 @@ static DESERIALIZATION_PATTERNS: Lazy<Vec<DeserializationPattern>> = Lazy::new(|
             description: "Unsafe Java object deserialization detected",
             severity: Severity::Critical,
         },
+        // Java - SnakeYAML before 2.0 resolves global tags to arbitrary classes
+        // unless a SafeConstructor is used
+        DeserializationPattern {
+            name: "Java SnakeYAML default constructor",
+            language: "Java",
+            regex: Regex::new(r#"\bnew\s+Yaml\s*\(\s*(?:new\s+Constructor\s*\([^)]*\)\s*)?\)"#)
+                .unwrap(),
+            description: "SnakeYAML without SafeConstructor can instantiate arbitrary classes \
+                          from YAML tags",
+            severity: Severity::Critical,
+        },
         // PHP - unserialize
         DeserializationPattern {
             name: "PHP unserialize()",
@@ -84,6 +99,33 @@ static DESERIALIZATION_PATTERNS: Lazy<Vec<DeserializationPattern>> = Lazy::new(|
             description: "Unsafe deserialization using node-serialize detected",
             severity: Severity::Critical,
         },
+        // Rust - bincode has no size limit by default, so a length prefix in
+        // untrusted bytes can request gigabytes of allocation
+        DeserializationPattern {
+            name: "Rust bincode deserialization of untrusted bytes",
+            language: "Rust",
+            regex: Regex::new(&format!(
+                r#"\bbincode::(?:deserialize(?:_from)?|decode_from_slice|serde::decode_from_slice)\s*\(\s*{}"#,
+                UNTRUSTED_RUST_INPUT
+            ))
+            .unwrap(),
+            description: "bincode deserialization of request data without a size limit \
+                          can exhaust memory",
+            severity: Severity::High,
+        },
+        // Rust - serde_yaml expands aliases and accepts arbitrary tags
+        DeserializationPattern {
+            name: "Rust serde_yaml deserialization of untrusted input",
+            language: "Rust",
+            regex: Regex::new(&format!(
+                r#"\bserde_yaml::from_(?:str|slice|reader)\s*(?:::<[^>]*>)?\s*\(\s*{}"#,
+                UNTRUSTED_RUST_INPUT
+            ))
+            .unwrap(),
+            description: "YAML from request data can use alias expansion to exhaust memory \
+                          or CPU, and tagged values may target unexpected enum variants",
+            severity: Severity::Medium,
+        },
     ]
 });
 
@@ -93,7 +135,7 @@ pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
 
     for (line_num, line) in content.lines().enumerate() {
         for pattern in DESERIALIZATION_PATTERNS.iter() {
-            if pattern.regex.is_match(line) {
+            for m in pattern.regex.find_iter(line) {
                 let vuln = Vulnerability::new(
                     format!("DESER-{:03}", id_counter),
                     VulnerabilityType::UnsafeDeserialization,
@@ -101,18 +143,20 @@ pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
                     format!("{} Detected", pattern.name),
                     pattern.description.to_string(),
                 )
-                .with_location(Location::new(file_path).with_line(line_num + 1))
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
                 .with_impact(
                     "Attackers can craft malicious serialized objects that execute \
                      arbitrary code when deserialized, leading to full system compromise."
                         .to_string(),
                 )
-                .with_remediation(
-                    format!("For {}: Use safe alternatives like JSON, or implement strict \
+                .with_remediation(format!(
+                    "For {}: Use safe alternatives like JSON, or implement strict \
                              type checking and validation before deserialization. \
                              Consider using allowlists for allowed classes.",
-                            pattern.language)
-                )
+                    pattern.language
+                ))
                 .with_code_snippet(line.to_string())
                 .with_confidence(0.88);
 
@@ -147,4 +191,26 @@ mod tests {
         let vulns = detect(content, "test.py").unwrap();
         assert!(!vulns.is_empty());
     }
+
+    #[test]
+    fn test_detect_snakeyaml() {
+        let content = r#"
+Yaml yaml = new Yaml();
+Yaml safe = new Yaml(new SafeConstructor(new LoaderOptions()));
+"#;
+        let vulns = detect(content, "Config.java").unwrap();
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(2));
+    }
+
+    #[test]
+    fn test_detect_rust_untrusted_input() {
+        let content = r#"
+let msg: Message = bincode::deserialize(&body)?;
+let spec: Spec = serde_yaml::from_str(&payload)?;
+let config: Config = serde_yaml::from_str(&std::fs::read_to_string(path)?)?;
+"#;
+        let vulns = detect(content, "handler.rs").unwrap();
+        assert_eq!(vulns.len(), 2);
+    }
 }
@@ -0,0 +1,343 @@
+//! Dockerfile security checks
+//!
+//! Many MCP servers ship a Dockerfile that users build and run with their
+//! credentials mounted. These checks only run on files recognised by
+//! [`is_dockerfile`] and cover the usual container pitfalls:
+//!
+//! - `runs-as-root` - the final stage never switches to a non-root `USER`
+//! - `add-from-url` - `ADD https://...` fetches unverified remote content
+//! - `secret-in-env` - credentials baked into `ENV`/`ARG` (visible in image history)
+//! - `latest-tag` - `FROM` without a pinned tag or digest
+//! - `curl-pipe-shell` - `RUN curl ... | sh` installs
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+static CURL_PIPE_SHELL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:curl|wget)\b[^|]*\|\s*(?:sudo\s+)?(?:sh|bash|zsh|python3?)\b"#).unwrap()
+});
+
+/// A container check and how its findings are reported
+struct Check {
+    rule: &'static str,
+    vuln_type: VulnerabilityType,
+    severity: Severity,
+    cwe: &'static str,
+    description: &'static str,
+    remediation: &'static str,
+}
+
+const RUNS_AS_ROOT: Check = Check {
+    rule: "runs-as-root",
+    vuln_type: VulnerabilityType::InsecureContainerConfig,
+    severity: Severity::Medium,
+    cwe: "CWE-250",
+    description: "The final stage never switches to a non-root USER, so the server process \
+                  runs as root inside the container",
+    remediation: "Create an unprivileged user and add `USER <name>` to the final stage",
+};
+
+const ADD_FROM_URL: Check = Check {
+    rule: "add-from-url",
+    vuln_type: VulnerabilityType::SupplyChainAttack,
+    severity: Severity::Medium,
+    cwe: "CWE-494",
+    description: "ADD downloads remote content at build time without verifying its integrity",
+    remediation: "Download with RUN curl and verify a checksum, or use ADD --checksum=sha256:...",
+};
+
+const SECRET_IN_ENV: Check = Check {
+    rule: "secret-in-env",
+    vuln_type: VulnerabilityType::HardcodedCredentials,
+    severity: Severity::High,
+    cwe: "CWE-798",
+    description: "ENV and ARG values are stored in the image metadata and history, where \
+                  anyone who can pull the image can read them",
+    remediation: "Pass secrets at runtime (environment or mounted files) or use BuildKit \
+                  secret mounts (RUN --mount=type=secret)",
+};
+
+const LATEST_TAG: Check = Check {
+    rule: "latest-tag",
+    vuln_type: VulnerabilityType::SupplyChainAttack,
+    severity: Severity::Low,
+    cwe: "CWE-1357",
+    description: "The base image uses the mutable `latest` tag (or no tag), so builds \
+                  silently pick up whatever the registry serves",
+    remediation: "Pin the base image to a version tag, ideally with a digest \
+                  (image:1.2.3@sha256:...)",
+};
+
+const CURL_PIPE_SHELL_CHECK: Check = Check {
+    rule: "curl-pipe-shell",
+    vuln_type: VulnerabilityType::SupplyChainAttack,
+    severity: Severity::High,
+    cwe: "CWE-494",
+    description: "A script downloaded at build time is executed without any integrity check",
+    remediation: "Download the script, verify its checksum or signature, then run it; prefer \
+                  distribution packages",
+};
+
+/// Whether `file_path` names a Dockerfile (`Dockerfile`, `Dockerfile.dev`,
+/// `api.dockerfile`, `Containerfile`)
+pub fn is_dockerfile(file_path: &str) -> bool {
+    let name = file_path
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(file_path)
+        .to_ascii_lowercase();
+    name == "dockerfile"
+        || name == "containerfile"
+        || name.starts_with("dockerfile.")
+        || name.ends_with(".dockerfile")
+}
+
+/// One instruction, with line continuations joined
+struct Instruction {
+    line: usize,
+    keyword: String,
+    args: String,
+    source: String,
+}
+
+fn parse_instructions(content: &str) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if pending.is_none() && (trimmed.is_empty() || trimmed.starts_with('#')) {
+            continue;
+        }
+
+        let (start, mut text) = pending.take().unwrap_or((line_num + 1, String::new()));
+        match trimmed.strip_suffix('\\') {
+            Some(continued) => {
+                text.push_str(continued);
+                text.push(' ');
+                pending = Some((start, text));
+            }
+            None => {
+                text.push_str(trimmed);
+                let (keyword, args) = text
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((text.as_str(), ""));
+                instructions.push(Instruction {
+                    line: start,
+                    keyword: keyword.to_ascii_uppercase(),
+                    args: args.trim().to_string(),
+                    source: text.clone(),
+                });
+            }
+        }
+    }
+
+    instructions
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    if !is_dockerfile(file_path) {
+        return Ok(Vec::new());
+    }
+
+    let mut vulnerabilities = Vec::new();
+    let mut stages = HashSet::new();
+    let mut last_from = None;
+    let mut user = None;
+
+    for instruction in parse_instructions(content) {
+        let args = instruction.args.as_str();
+        let line = instruction.line;
+        let snippet = Some(instruction.source.as_str());
+        match instruction.keyword.as_str() {
+            "FROM" => {
+                last_from = Some(line);
+                user = None; // USER does not carry over between stages
+
+                let words: Vec<&str> = args
+                    .split_whitespace()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect();
+                let image = words.first().copied().unwrap_or_default();
+                if let [_, alias, name] = words.as_slice() {
+                    if alias.eq_ignore_ascii_case("as") {
+                        stages.insert(name.to_ascii_lowercase());
+                    }
+                }
+
+                if !is_pinned(image, &stages) {
+                    let title = format!("Unpinned Base Image '{}'", image);
+                    vulnerabilities.push(finding(&LATEST_TAG, title, line, snippet, file_path));
+                }
+            }
+            "USER" => user = Some(args.to_string()),
+            "ADD"
+                if args
+                    .split_whitespace()
+                    .any(|w| w.starts_with("http://") || w.starts_with("https://")) =>
+            {
+                let title = "ADD From Remote URL".to_string();
+                vulnerabilities.push(finding(&ADD_FROM_URL, title, line, snippet, file_path));
+            }
+            "ENV" | "ARG" => {
+                if let Some(name) = secret_variable(args) {
+                    let title = format!("Secret in {} '{}'", instruction.keyword, name);
+                    vulnerabilities.push(finding(&SECRET_IN_ENV, title, line, snippet, file_path));
+                }
+            }
+            "RUN" if CURL_PIPE_SHELL.is_match(args) => {
+                let title = "Remote Script Piped to Shell".to_string();
+                vulnerabilities.push(finding(
+                    &CURL_PIPE_SHELL_CHECK,
+                    title,
+                    line,
+                    snippet,
+                    file_path,
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // Reported once, on the final stage
+    let runs_as_root = match user.as_deref() {
+        Some(user) => matches!(user.split(':').next(), Some("root" | "0")),
+        None => true,
+    };
+    if let (Some(line), true) = (last_from, runs_as_root) {
+        let title = "Container Runs as Root".to_string();
+        vulnerabilities.push(finding(&RUNS_AS_ROOT, title, line, None, file_path));
+    }
+
+    Ok(vulnerabilities)
+}
+
+/// Whether a `FROM` image reference is pinned to a tag or digest
+fn is_pinned(image: &str, stages: &HashSet<String>) -> bool {
+    if image.contains('@')
+        || image.eq_ignore_ascii_case("scratch")
+        || image.starts_with('$')
+        || stages.contains(&image.to_ascii_lowercase())
+    {
+        return true;
+    }
+    // A ':' after the last '/' is a tag; one before it is a registry port
+    let name = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        Some((_, tag)) => tag != "latest",
+        None => false,
+    }
+}
+
+/// Name of the first credential-like variable set by an `ENV`/`ARG` line
+fn secret_variable(args: &str) -> Option<&str> {
+    // `ENV KEY value` (legacy) or `ENV KEY=value OTHER=value`
+    let names: Vec<&str> = if args.contains('=') {
+        args.split_whitespace()
+            .filter_map(|pair| pair.split_once('=').map(|(name, _)| name))
+            .collect()
+    } else {
+        args.split_whitespace().take(1).collect()
+    };
+    names
+        .into_iter()
+        .find(|name| super::secrets::is_secret_name(name))
+}
+
+fn finding(
+    check: &Check,
+    title: String,
+    line: usize,
+    snippet: Option<&str>,
+    file_path: &str,
+) -> Vulnerability {
+    let mut evidence = HashMap::new();
+    evidence.insert("cwe".to_string(), serde_json::json!(check.cwe));
+
+    let mut vuln = Vulnerability::new(
+        format!("DOCKER-{:03}", line),
+        check.vuln_type.clone(),
+        check.severity,
+        title,
+        check.description,
+    )
+    .with_rule_id(check.rule)
+    .with_location(Location::new(file_path).with_line(line))
+    .with_remediation(check.remediation)
+    .with_confidence(0.85)
+    .with_evidence(evidence);
+    if let Some(snippet) = snippet {
+        vuln = vuln.with_code_snippet(snippet.to_string());
+    }
+    vuln
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_is_dockerfile() {
+        assert!(is_dockerfile("Dockerfile"));
+        assert!(is_dockerfile("deploy/Dockerfile.prod"));
+        assert!(is_dockerfile("api.dockerfile"));
+        assert!(is_dockerfile("Containerfile"));
+        assert!(!is_dockerfile("dockerfile_parser.py"));
+    }
+
+    #[test]
+    fn test_detect_insecure_dockerfile() {
+        let content = r#"
+FROM python:latest
+ARG GITHUB_TOKEN
+ENV OPENAI_API_KEY=sk-live-123 LOG_LEVEL=info
+ENV TOKENIZERS_PARALLELISM=false
+ADD https://example.com/tool.tar.gz /opt/
+RUN apt-get update && \
+    curl -fsSL https://get.example.sh | bash
+CMD ["python", "server.py"]
+"#;
+        let vulns = detect(content, "Dockerfile").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "latest-tag",
+                "secret-in-env",
+                "secret-in-env",
+                "add-from-url",
+                "curl-pipe-shell",
+                "runs-as-root"
+            ]
+        );
+        // Continuation lines report the instruction's first line
+        assert_eq!(vulns[4].location.as_ref().unwrap().line, Some(7));
+    }
+
+    #[test]
+    fn test_hardened_dockerfile_not_flagged() {
+        let content = r#"
+FROM node:20-alpine AS build
+WORKDIR /app
+RUN npm ci
+FROM registry.local:5000/base@sha256:abcd
+COPY --from=build /app /app
+RUN adduser -D mcp
+USER mcp
+"#;
+        assert!(detect(content, "Dockerfile").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_only_runs_on_dockerfiles() {
+        let content = "FROM ubuntu\nRUN curl https://x.sh | sh";
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,327 @@
+//! Hardcoded network egress endpoint analysis
+//!
+//! Extracts every URL, IP address, and quoted domain in the source and
+//! classifies where it points. The full list becomes the scan's egress
+//! inventory (what this server can talk to); endpoints that are typical
+//! exfiltration channels - paste sites, webhook collectors, tunnels, and raw
+//! public IPs - are also reported as findings.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// Where an endpoint points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointKind {
+    /// Localhost and private network ranges
+    Local,
+    /// Error reporting and analytics services
+    Telemetry,
+    /// Paste and anonymous file drop sites
+    PasteSite,
+    /// Webhook and request collectors, chat bot APIs
+    Webhook,
+    /// Tunnelling and out-of-band interaction services
+    Tunnel,
+    /// A public IP address instead of a host name
+    RawIp,
+    /// Anything else
+    Other,
+}
+
+impl EndpointKind {
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            EndpointKind::Local => "Local",
+            EndpointKind::Telemetry => "Telemetry",
+            EndpointKind::PasteSite => "Paste site",
+            EndpointKind::Webhook => "Webhook",
+            EndpointKind::Tunnel => "Tunnel",
+            EndpointKind::RawIp => "Raw IP",
+            EndpointKind::Other => "Other",
+        }
+    }
+
+    /// Severity of a finding for this kind, if it is suspicious at all
+    fn severity(&self) -> Option<Severity> {
+        match self {
+            EndpointKind::PasteSite | EndpointKind::Webhook | EndpointKind::Tunnel => {
+                Some(Severity::High)
+            }
+            EndpointKind::RawIp => Some(Severity::Medium),
+            EndpointKind::Local | EndpointKind::Telemetry | EndpointKind::Other => None,
+        }
+    }
+}
+
+/// One hardcoded endpoint
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Endpoint {
+    /// The URL, IP, or domain as written
+    pub value: String,
+    pub host: String,
+    pub kind: EndpointKind,
+    pub file: String,
+    pub line: usize,
+}
+
+static URL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(?:https?|wss?|ftp)://([^\s"'`<>()\[\]{},;\\]+)"#).unwrap());
+static IPV4: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(?:\d{1,3}\.){3}\d{1,3}(?::\d{1,5})?\b"#).unwrap());
+static QUOTED_DOMAIN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"["'`]((?:[a-z0-9-]+\.)+(?:com|net|org|io|dev|app|sh|me|co|ai|xyz|top|site|online|ru|cn|su|tk))["'`]"#)
+        .unwrap()
+});
+
+/// Host suffixes per kind, checked in order
+static HOST_KINDS: &[(EndpointKind, &[&str])] = &[
+    (
+        EndpointKind::Telemetry,
+        &[
+            "sentry.io",
+            "segment.io",
+            "segment.com",
+            "mixpanel.com",
+            "amplitude.com",
+            "posthog.com",
+            "datadoghq.com",
+            "datadoghq.eu",
+            "google-analytics.com",
+            "googletagmanager.com",
+            "honeycomb.io",
+            "bugsnag.com",
+            "rollbar.com",
+            "newrelic.com",
+        ],
+    ),
+    (
+        EndpointKind::PasteSite,
+        &[
+            "pastebin.com",
+            "paste.ee",
+            "hastebin.com",
+            "ghostbin.com",
+            "rentry.co",
+            "dpaste.org",
+            "termbin.com",
+            "transfer.sh",
+            "0x0.st",
+            "file.io",
+            "gist.githubusercontent.com",
+        ],
+    ),
+    (
+        EndpointKind::Webhook,
+        &[
+            "webhook.site",
+            "hooks.slack.com",
+            "requestbin.com",
+            "requestbin.net",
+            "pipedream.net",
+            "api.telegram.org",
+            "hookbin.com",
+            "beeceptor.com",
+        ],
+    ),
+    (
+        EndpointKind::Tunnel,
+        &[
+            "ngrok.io",
+            "ngrok.app",
+            "ngrok-free.app",
+            "trycloudflare.com",
+            "loca.lt",
+            "serveo.net",
+            "localhost.run",
+            "interact.sh",
+            "oast.fun",
+            "oast.pro",
+            "oast.live",
+            "burpcollaborator.net",
+        ],
+    ),
+];
+
+/// Discord webhooks share a host with the regular API
+const DISCORD_WEBHOOK_PATH: &str = "/api/webhooks/";
+
+/// Find and classify every hardcoded endpoint in one file
+pub fn extract_endpoints(content: &str, file_path: &str) -> Vec<Endpoint> {
+    let mut endpoints = Vec::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        let mut push = |value: &str, host: &str, kind| {
+            endpoints.push(Endpoint {
+                value: value.to_string(),
+                host: host.to_string(),
+                kind,
+                file: file_path.to_string(),
+                line: line_num + 1,
+            })
+        };
+
+        let mut covered = Vec::new();
+        for caps in URL.captures_iter(line) {
+            let url = caps.get(0).unwrap();
+            let rest = &caps[1];
+            let authority = rest.split('/').next().unwrap_or(rest);
+            let host = authority
+                .rsplit('@')
+                .next()
+                .unwrap_or(authority)
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let kind = if (host == "discord.com" || host == "discordapp.com")
+                && rest.contains(DISCORD_WEBHOOK_PATH)
+            {
+                EndpointKind::Webhook
+            } else {
+                classify_host(&host)
+            };
+            push(url.as_str(), &host, kind);
+            covered.push(url.range());
+        }
+
+        let outside_urls = |m: &regex::Match| {
+            !covered
+                .iter()
+                .any(|r| r.start <= m.start() && m.end() <= r.end)
+        };
+        for m in IPV4.find_iter(line).filter(|m| outside_urls(m)) {
+            let host = m.as_str().split(':').next().unwrap_or_default();
+            if host.parse::<Ipv4Addr>().is_ok() {
+                push(m.as_str(), host, classify_host(host));
+            }
+        }
+        for caps in QUOTED_DOMAIN.captures_iter(line) {
+            let m = caps.get(1).unwrap();
+            if outside_urls(&m) {
+                push(m.as_str(), m.as_str(), classify_host(m.as_str()));
+            }
+        }
+    }
+
+    endpoints
+}
+
+/// Classify a bare host name or IP address
+pub fn classify_host(host: &str) -> EndpointKind {
+    if let Ok(ip) = host.parse::<Ipv4Addr>() {
+        return if ip.is_loopback() || ip.is_private() || ip.is_unspecified() || ip.is_link_local() {
+            EndpointKind::Local
+        } else {
+            EndpointKind::RawIp
+        };
+    }
+    if host == "localhost" || host.ends_with(".localhost") || host.ends_with(".local") {
+        return EndpointKind::Local;
+    }
+
+    HOST_KINDS
+        .iter()
+        .find(|(_, suffixes)| {
+            suffixes
+                .iter()
+                .any(|s| host == *s || host.ends_with(&format!(".{}", s)))
+        })
+        .map_or(EndpointKind::Other, |(kind, _)| *kind)
+}
+
+/// Report endpoints that are typical exfiltration channels
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+
+    for endpoint in extract_endpoints(content, file_path) {
+        let Some(severity) = endpoint.kind.severity() else {
+            continue;
+        };
+        let label = endpoint.kind.label();
+
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("EGRESS-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::DataExfiltration,
+                severity,
+                format!("Suspicious Egress Endpoint: {} ({})", endpoint.host, label),
+                format!(
+                    "Hardcoded {} endpoint '{}'; these are common exfiltration and callback channels",
+                    label.to_lowercase(),
+                    endpoint.value
+                ),
+            )
+            .with_rule_id(super::rule_slug(label))
+            .with_location(Location::new(file_path).with_line(endpoint.line))
+            .with_impact("Data sent to this endpoint leaves your control and is hard to trace")
+            .with_remediation(
+                "Confirm why the server contacts this endpoint; remove it or make it configurable \
+                 and documented if it is legitimate",
+            )
+            .with_code_snippet(
+                lines
+                    .get(endpoint.line - 1)
+                    .map(|l| l.trim().to_string())
+                    .unwrap_or_default(),
+            )
+            .with_confidence(0.7),
+        );
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_host() {
+        assert_eq!(
+            classify_host("o123.ingest.sentry.io"),
+            EndpointKind::Telemetry
+        );
+        assert_eq!(classify_host("pastebin.com"), EndpointKind::PasteSite);
+        assert_eq!(classify_host("abc123.ngrok-free.app"), EndpointKind::Tunnel);
+        assert_eq!(classify_host("192.168.1.10"), EndpointKind::Local);
+        assert_eq!(classify_host("45.33.32.156"), EndpointKind::RawIp);
+        assert_eq!(classify_host("api.github.com"), EndpointKind::Other);
+    }
+
+    #[test]
+    fn test_extract_endpoints() {
+        let content = r#"
+SENTRY = "https://abc@o1.ingest.sentry.io/42"
+requests.post("https://discord.com/api/webhooks/123/token", json=data)
+sock.connect(("45.33.32.156", 4444))
+BASE = "api.example.com"
+"#;
+        let endpoints = extract_endpoints(content, "server.py");
+        let kinds: Vec<_> = endpoints
+            .iter()
+            .map(|e| (e.host.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("o1.ingest.sentry.io", EndpointKind::Telemetry),
+                ("discord.com", EndpointKind::Webhook),
+                ("45.33.32.156", EndpointKind::RawIp),
+                ("api.example.com", EndpointKind::Other),
+            ]
+        );
+
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("webhook"));
+        assert_eq!(vulns[1].severity, Severity::Medium);
+    }
+}
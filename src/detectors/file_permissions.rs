@@ -0,0 +1,148 @@
+//! Overly permissive file permission detection - CWE-732
+//!
+//! MCP servers frequently create cache directories, sockets, and token files.
+//! Creating them world-writable (or clearing the umask first) lets any local
+//! user tamper with cached tool output, hijack a socket, or swap a script the
+//! server later executes.
+//!
+//! A mode is flagged when the "other" digit grants write access (`0o777`,
+//! `0o666`, `0o772`, ...).
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct PermissionPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    remediation: &'static str,
+}
+
+/// An octal mode literal whose "other" digit includes write (2, 3, 6, 7)
+const WORLD_WRITABLE_MODE: &str = r#"(?:\b0o?[0-7]?[0-7]{2}[2367]\b|["']0?[0-7]{2}[2367]["'])"#;
+
+static PERMISSION_PATTERNS: Lazy<Vec<PermissionPattern>> = Lazy::new(|| {
+    let pattern = |name, language, regex: &str, remediation| PermissionPattern {
+        name,
+        language,
+        regex: Regex::new(&regex.replace("{MODE}", WORLD_WRITABLE_MODE)).unwrap(),
+        remediation,
+    };
+    vec![
+        pattern(
+            "World-writable chmod command",
+            "Shell",
+            r#"\bchmod\s+(?:-[A-Za-z]+\s+)*(?:0?[0-7]?[0-7]{2}[2367]|(?:a|o|ugo)\+w)\b"#,
+            "Grant only the owner write access (e.g. chmod 700 for directories, 600 for files)",
+        ),
+        pattern(
+            "World-writable mode passed to chmod",
+            "Python/JavaScript",
+            r#"\b(?:f|l)?chmod(?:Sync)?\s*\(.*,\s*{MODE}"#,
+            "Use 0o700 for directories and 0o600 for files the server owns",
+        ),
+        pattern(
+            "World-writable permission bits passed to chmod",
+            "Python",
+            r#"\bchmod\s*\(.*\bS_I(?:WOTH|RWXO)\b"#,
+            "Drop stat.S_IWOTH/S_IRWXO; only the owning user should be able to write",
+        ),
+        pattern(
+            "File or directory created world-writable",
+            "Python/JavaScript",
+            r#"\b(?:os\.(?:open|mkdir|makedirs)|fs\.\w+)\s*\(.*(?:,|\bmode\s*[=:])\s*{MODE}"#,
+            "Create files with mode 0o600 and directories with 0o700, or omit the mode \
+             and rely on a restrictive umask",
+        ),
+        pattern(
+            "umask cleared to 0",
+            "Python/JavaScript/Shell",
+            r#"\bumask(?:\s*\(\s*0(?:o?0+)?\s*\)|\s+0+\b)"#,
+            "Keep the default umask, or set 0o077 so new files are private to the server user",
+        ),
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = PERMISSION_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-732"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("PERM-{:03}", id_counter),
+                    VulnerabilityType::InsecurePermissions,
+                    Severity::Medium,
+                    format!("{} Detected", pattern.name),
+                    "Files or directories are created with permissions that let any local \
+                     user modify them"
+                        .to_string(),
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Other local users or compromised processes can tamper with cached \
+                     data, sockets, or scripts the server trusts",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.8)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_python_permissions() {
+        let content = r#"
+os.chmod(cache_dir, 0o777)
+os.chmod(sock, stat.S_IRWXU | stat.S_IRWXO)
+os.makedirs(path, mode=0o777, exist_ok=True)
+os.umask(0)
+subprocess.run("chmod -R 777 /tmp/mcp", shell=True)
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 5);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::InsecurePermissions);
+    }
+
+    #[test]
+    fn test_detect_js_permissions() {
+        let content = "fs.chmodSync(sock, 0o666);\nfs.writeFileSync(p, data, { mode: 0o666 });\nprocess.umask(0);";
+        let vulns = detect(content, "server.js").unwrap();
+        assert_eq!(vulns.len(), 3);
+    }
+
+    #[test]
+    fn test_private_modes_not_flagged() {
+        let content = r#"
+os.chmod(token_file, 0o600)
+os.makedirs(path, mode=0o755, exist_ok=True)
+fs.mkdirSync(dir, { mode: 0o700 });
+os.umask(0o077)
+"#;
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+}
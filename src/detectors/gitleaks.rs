@@ -0,0 +1,406 @@
+//! Gitleaks configuration import
+//!
+//! Reads the rules and allowlists of a `gitleaks.toml` (v8 format) so teams
+//! that already tuned gitleaks can reuse that work. Imported rules run inside
+//! the `secrets` detector alongside the built-in patterns, and the global
+//! allowlist also suppresses built-in findings.
+//!
+//! Supported: `regex`, `secretGroup`, `entropy`, `keywords`, `path`, and
+//! allowlists with `regexes`, `regexTarget`, `paths`, `stopwords`, and
+//! `condition`. Git-specific settings (`commits`, `[extend]`) are ignored
+//! since scans work on files, not history.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::secrets::shannon_entropy;
+
+/// Gitleaks file name looked up in the scan target
+pub const GITLEAKS_CONFIG_FILE: &str = ".gitleaks.toml";
+
+/// Raw `gitleaks.toml` contents (unknown keys are ignored)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitleaksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<RuleConfig>,
+
+    /// Global allowlist (`[allowlist]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowlist: Option<AllowlistConfig>,
+
+    /// Global allowlists (`[[allowlists]]`, gitleaks 8.21+)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowlists: Vec<AllowlistConfig>,
+}
+
+/// One `[[rules]]` entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleConfig {
+    pub id: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_group: Option<usize>,
+
+    /// Minimum Shannon entropy of the secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+
+    /// Regex the file path must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowlist: Option<AllowlistConfig>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowlists: Vec<AllowlistConfig>,
+}
+
+/// An `[allowlist]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllowlistConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// `"OR"` (default) or `"AND"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regexes: Vec<String>,
+
+    /// `"secret"` (default), `"match"`, or `"line"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex_target: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stopwords: Vec<String>,
+}
+
+/// Compiled gitleaks rules, ready for the secrets detector
+///
+/// (De)serializes as the raw [`GitleaksConfig`], so it can live in
+/// `ScanConfig` while regexes are compiled only once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "GitleaksConfig", into = "GitleaksConfig")]
+pub struct GitleaksRules {
+    source: GitleaksConfig,
+    pub(crate) rules: Vec<Rule>,
+    pub(crate) allowlists: Vec<Allowlist>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Rule {
+    pub id: String,
+    pub description: String,
+    pub regex: Option<Regex>,
+    pub secret_group: usize,
+    pub entropy: Option<f64>,
+    /// Lowercased
+    pub keywords: Vec<String>,
+    pub path: Option<Regex>,
+    pub allowlists: Vec<Allowlist>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegexTarget {
+    Secret,
+    Match,
+    Line,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Allowlist {
+    require_all: bool,
+    regexes: Vec<Regex>,
+    target: RegexTarget,
+    paths: Vec<Regex>,
+    /// Lowercased
+    stopwords: Vec<String>,
+}
+
+/// What a candidate finding looked like, for allowlist checks
+pub(crate) struct Candidate<'a> {
+    pub file_path: &'a str,
+    pub line: &'a str,
+    pub matched: &'a str,
+    pub secret: &'a str,
+}
+
+impl GitleaksRules {
+    /// Load and compile a gitleaks config file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: GitleaksConfig = toml::from_str(&content)
+            .with_context(|| format!("Invalid gitleaks config {}", path.display()))?;
+        Self::try_from(config).with_context(|| format!("In {}", path.display()))
+    }
+
+    /// Raw configuration these rules were compiled from
+    pub fn source(&self) -> &GitleaksConfig {
+        &self.source
+    }
+
+    /// Number of imported rules
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether a global allowlist suppresses this candidate
+    pub(crate) fn allows(&self, candidate: &Candidate) -> bool {
+        self.allowlists.iter().any(|a| a.allows(candidate))
+    }
+
+    /// Whether a global allowlist skips this file entirely
+    pub(crate) fn allows_path(&self, file_path: &str) -> bool {
+        self.allowlists.iter().any(|a| a.allows_path(file_path))
+    }
+}
+
+impl TryFrom<GitleaksConfig> for GitleaksRules {
+    type Error = anyhow::Error;
+
+    fn try_from(source: GitleaksConfig) -> Result<Self> {
+        let rules = source
+            .rules
+            .iter()
+            .map(Rule::compile)
+            .collect::<Result<Vec<_>>>()?;
+        let allowlists = compile_allowlists(&source.allowlist, &source.allowlists)
+            .context("Invalid global allowlist")?;
+        Ok(Self {
+            source,
+            rules,
+            allowlists,
+        })
+    }
+}
+
+impl From<GitleaksRules> for GitleaksConfig {
+    fn from(rules: GitleaksRules) -> Self {
+        rules.source
+    }
+}
+
+impl PartialEq for GitleaksRules {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Rule {
+    fn compile(config: &RuleConfig) -> Result<Self> {
+        let context = || format!("Invalid gitleaks rule '{}'", config.id);
+        anyhow::ensure!(
+            config.regex.is_some() || config.path.is_some(),
+            "Gitleaks rule '{}' needs a regex or a path",
+            config.id
+        );
+        Ok(Self {
+            id: config.id.clone(),
+            description: config.description.clone(),
+            regex: config
+                .regex
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .with_context(context)?,
+            secret_group: config.secret_group.unwrap_or(0),
+            entropy: config.entropy,
+            keywords: config.keywords.iter().map(|k| k.to_lowercase()).collect(),
+            path: config
+                .path
+                .as_deref()
+                .map(Regex::new)
+                .transpose()
+                .with_context(context)?,
+            allowlists: compile_allowlists(&config.allowlist, &config.allowlists)
+                .with_context(context)?,
+        })
+    }
+
+    /// Whether the rule's keywords (if any) occur in `line`
+    pub fn keywords_match(&self, line: &str) -> bool {
+        if self.keywords.is_empty() {
+            return true;
+        }
+        let line = line.to_lowercase();
+        self.keywords.iter().any(|k| line.contains(k.as_str()))
+    }
+
+    /// Whether the rule applies to `file_path`
+    pub fn path_matches(&self, file_path: &str) -> bool {
+        self.path.as_ref().is_none_or(|p| p.is_match(file_path))
+    }
+
+    /// Whether the secret meets the entropy threshold and no allowlist applies
+    pub fn accepts(&self, candidate: &Candidate) -> bool {
+        self.entropy
+            .is_none_or(|min| shannon_entropy(candidate.secret) >= min)
+            && !self.allowlists.iter().any(|a| a.allows(candidate))
+    }
+}
+
+impl Allowlist {
+    fn compile(config: &AllowlistConfig) -> Result<Self> {
+        let target = match config.regex_target.as_deref() {
+            None | Some("secret") => RegexTarget::Secret,
+            Some("match") => RegexTarget::Match,
+            Some("line") => RegexTarget::Line,
+            Some(other) => anyhow::bail!("Unknown regexTarget '{}'", other),
+        };
+        let require_all = match config.condition.as_deref() {
+            None => false,
+            Some(c) if c.eq_ignore_ascii_case("or") => false,
+            Some(c) if c.eq_ignore_ascii_case("and") => true,
+            Some(other) => anyhow::bail!("Unknown allowlist condition '{}'", other),
+        };
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| Regex::new(p).with_context(|| format!("Invalid regex '{}'", p)))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(Self {
+            require_all,
+            regexes: compile(&config.regexes)?,
+            target,
+            paths: compile(&config.paths)?,
+            stopwords: config.stopwords.iter().map(|s| s.to_lowercase()).collect(),
+        })
+    }
+
+    fn allows(&self, candidate: &Candidate) -> bool {
+        let text = match self.target {
+            RegexTarget::Secret => candidate.secret,
+            RegexTarget::Match => candidate.matched,
+            RegexTarget::Line => candidate.line,
+        };
+        let secret = candidate.secret.to_lowercase();
+
+        let checks = [
+            (!self.regexes.is_empty()).then(|| self.regexes.iter().any(|r| r.is_match(text))),
+            (!self.paths.is_empty()).then(|| self.path_listed(candidate.file_path)),
+            (!self.stopwords.is_empty())
+                .then(|| self.stopwords.iter().any(|s| secret.contains(s.as_str()))),
+        ];
+        let mut set = checks.iter().flatten().peekable();
+        if set.peek().is_none() {
+            return false;
+        }
+        if self.require_all {
+            set.all(|&matched| matched)
+        } else {
+            set.any(|&matched| matched)
+        }
+    }
+
+    /// Whether the path alone is enough to allow everything in the file
+    fn allows_path(&self, file_path: &str) -> bool {
+        let only_paths = self.regexes.is_empty() && self.stopwords.is_empty();
+        (only_paths || !self.require_all) && self.path_listed(file_path)
+    }
+
+    fn path_listed(&self, file_path: &str) -> bool {
+        self.paths.iter().any(|p| p.is_match(file_path))
+    }
+}
+
+fn compile_allowlists(
+    single: &Option<AllowlistConfig>,
+    many: &[AllowlistConfig],
+) -> Result<Vec<Allowlist>> {
+    single.iter().chain(many).map(Allowlist::compile).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+        title = "acme"
+
+        [[rules]]
+        id = "acme-internal-token"
+        description = "ACME internal token"
+        regex = '''acme_(?:live|test)_([a-z0-9]{24})'''
+        secretGroup = 1
+        keywords = ["acme_"]
+
+        [rules.allowlist]
+        regexes = ['''^0+$''']
+
+        [allowlist]
+        paths = ['''(^|/)fixtures/''']
+        stopwords = ["example"]
+    "#;
+
+    #[test]
+    fn test_parse_and_compile() {
+        let config: GitleaksConfig = toml::from_str(CONFIG).unwrap();
+        let rules = GitleaksRules::try_from(config).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules.rules[0].secret_group, 1);
+        assert!(rules.rules[0].keywords_match("token = ACME_live_abc"));
+        assert!(rules.allows_path("server/fixtures/keys.py"));
+        assert!(!rules.allows_path("server/main.py"));
+    }
+
+    #[test]
+    fn test_allowlist_condition() {
+        let and = Allowlist::compile(&AllowlistConfig {
+            condition: Some("AND".into()),
+            regexes: vec!["^test".into()],
+            paths: vec!["tests/".into()],
+            ..Default::default()
+        })
+        .unwrap();
+        let candidate = |file_path, secret| Candidate {
+            file_path,
+            line: "",
+            matched: "",
+            secret,
+        };
+        assert!(and.allows(&candidate("tests/a.py", "test-123")));
+        assert!(!and.allows(&candidate("src/a.py", "test-123")));
+        assert!(!and.allows_path("tests/a.py"));
+    }
+
+    #[test]
+    fn test_invalid_regex_rejected() {
+        let config: GitleaksConfig = toml::from_str(
+            r#"
+            [[rules]]
+            id = "broken"
+            regex = "(unclosed"
+            "#,
+        )
+        .unwrap();
+        assert!(GitleaksRules::try_from(config).is_err());
+    }
+}
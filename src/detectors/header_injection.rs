@@ -0,0 +1,148 @@
+//! HTTP header / CRLF injection detection - CWE-113, CWE-93
+//!
+//! A header value containing `\r\n` ends the header early, letting the
+//! attacker add headers (`Set-Cookie`, `Location`) or split the response. MCP
+//! servers exposed over HTTP/SSE set headers from query parameters more often
+//! than one would hope, e.g. echoing a session or callback value.
+//!
+//! Patterns flag header-setting calls in Python (Flask, Django, Tornado,
+//! `http.server`) and Node (http, Express, Koa) whose value comes from the
+//! request, plus literal CR/LF sequences written into header values. Lines
+//! that strip CR/LF or URL-encode the value are skipped.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct HeaderPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    cwe: &'static str,
+}
+
+/// Removing line breaks or encoding the value before it reaches the header
+static SANITIZED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\.replace(?:All)?\s*\([^)]*\\[rn]|\.splitlines\s*\(\s*\)|\bencodeURI(?:Component)?\s*\(|\b(?:quote|quote_plus|urlencode)\s*\("#,
+    )
+    .unwrap()
+});
+
+static HEADER_PATTERNS: Lazy<Vec<HeaderPattern>> = Lazy::new(|| {
+    vec![
+        HeaderPattern {
+            name: "CRLF sequence in header value",
+            language: "Python/JavaScript",
+            regex: Regex::new(
+                r#"(?:\b(?:setHeader|writeHead|set_header|send_header|add_header)\s*\(|\.headers\s*\[[^\]]+\]\s*=|\.headers\.(?:set|add|append)\s*\(|\bres\.(?:set|header|append)\s*\()[^\n]*(?:\\r|\\n|%0[dDaA])"#,
+            )
+            .unwrap(),
+            cwe: "CWE-93",
+        },
+        HeaderPattern {
+            name: "Response header set from request data",
+            language: "Python",
+            regex: Regex::new(concat!(
+                r#"(?:\.headers\s*\[[^\]]+\]|\bresponse\s*\[\s*["'][\w-]+["']\s*\])\s*=\s*[^\n]*\brequest\.(?:args|form|values|headers|cookies|json|GET|POST|META|query_params)\b"#,
+                r#"|\.headers\.(?:add|set|setdefault)\s*\([^\n]*\brequest\.(?:args|form|values|headers|cookies|json|GET|POST|META|query_params)\b"#,
+                r#"|\.(?:send_header|set_header|add_header)\s*\([^\n]*\b(?:request\.(?:args|form|values|headers|cookies|json|GET|POST|META|query_params|arguments)\b|self\.(?:path\b|get_argument\s*\(|get_query_argument\s*\())"#,
+            ))
+            .unwrap(),
+            cwe: "CWE-113",
+        },
+        HeaderPattern {
+            name: "Response header set from request data",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(
+                r#"\b(?:res|response|reply|ctx)\.(?:setHeader|set|header|append|writeHead)\s*\([^\n]*\b(?:req|request|ctx\.request|ctx)\.(?:query|body|params|headers|cookies|url|originalUrl)\b"#,
+            )
+            .unwrap(),
+            cwe: "CWE-113",
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        if SANITIZED.is_match(line) {
+            continue;
+        }
+        let Some(pattern) = HEADER_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!(pattern.cwe));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("CRLF-{:03}", id_counter),
+                    VulnerabilityType::HeaderInjection,
+                    Severity::Medium,
+                    format!("{} Detected", pattern.name),
+                    "An HTTP response header value can contain CR/LF characters, letting the \
+                     attacker add headers or split the response",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Injected Set-Cookie or Location headers enable session fixation and \
+                     redirects; response splitting can poison caches shared by other clients",
+                )
+                .with_remediation(
+                    "Reject or strip \\r and \\n from header values (or URL-encode them), and only \
+                     echo request data into headers after validating it against an allowlist",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.7)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_header_injection() {
+        let content = r#"
+resp.headers["X-Session"] = request.args.get("session")
+self.send_header("Location", self.path)
+res.setHeader("X-Callback", req.query.callback);
+res.setHeader("X-Debug", "ok\r\nSet-Cookie: admin=1");
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 4);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::HeaderInjection);
+        assert_eq!(vulns[0].evidence.as_ref().unwrap()["cwe"], "CWE-113");
+        assert_eq!(
+            vulns[3].rule_id.as_deref(),
+            Some("crlf-sequence-in-header-value")
+        );
+    }
+
+    #[test]
+    fn test_sanitized_headers_not_flagged() {
+        let content = r#"
+resp.headers["X-Session"] = request.args.get("session", "").replace("\r", "").replace("\n", "")
+res.setHeader("Content-Disposition", `attachment; filename="${encodeURIComponent(req.query.name)}"`);
+res.setHeader("Content-Type", "application/json");
+"#;
+        assert!(detect(content, "server.js").unwrap().is_empty());
+    }
+}
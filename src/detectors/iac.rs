@@ -0,0 +1,547 @@
+//! Infrastructure-as-code misconfiguration checks (Terraform, CloudFormation)
+//!
+//! MCP server repositories often carry the IaC that deploys them. These
+//! checks cover the misconfigurations that expose the server or its data:
+//!
+//! - `public-s3-bucket` - public ACLs or a disabled public access block
+//! - `open-security-group` - ingress from `0.0.0.0/0` or `::/0`
+//! - `hardcoded-credentials` - literal keys and passwords in providers,
+//!   resources, `.tfvars`, or template properties
+//!
+//! Terraform is read block by block (no HCL parser needed for these checks);
+//! CloudFormation templates are parsed as YAML, which also covers JSON.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// An IaC check and how its findings are reported
+struct Check {
+    rule: &'static str,
+    vuln_type: VulnerabilityType,
+    severity: Severity,
+    cwe: &'static str,
+    description: &'static str,
+    remediation: &'static str,
+}
+
+const PUBLIC_BUCKET: Check = Check {
+    rule: "public-s3-bucket",
+    vuln_type: VulnerabilityType::CloudMisconfiguration,
+    severity: Severity::High,
+    cwe: "CWE-732",
+    description: "The S3 bucket can be made or is publicly readable",
+    remediation: "Use a private ACL and enable all four S3 public access block settings",
+};
+
+const OPEN_SECURITY_GROUP: Check = Check {
+    rule: "open-security-group",
+    vuln_type: VulnerabilityType::CloudMisconfiguration,
+    severity: Severity::High,
+    cwe: "CWE-284",
+    description: "Ingress is allowed from any address on the internet",
+    remediation: "Restrict ingress to known CIDR ranges or put the server behind a load \
+                  balancer or VPN",
+};
+
+const HARDCODED_CREDENTIALS: Check = Check {
+    rule: "hardcoded-credentials",
+    vuln_type: VulnerabilityType::HardcodedCredentials,
+    severity: Severity::Critical,
+    cwe: "CWE-798",
+    description: "A credential is written as a literal in infrastructure code",
+    remediation: "Use environment credentials, a variable marked sensitive, or a secrets \
+                  manager reference, and rotate the exposed value",
+};
+
+/// Addresses meaning "anywhere"
+const ANY_ADDRESS: [&str; 2] = ["0.0.0.0/0", "::/0"];
+
+/// `resource "aws_s3_bucket" "name" {` and nested `ingress {`
+static TF_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*([\w-]+)((?:\s+"[^"]*")*)\s*\{\s*$"#).unwrap());
+
+/// `key = value`
+static TF_ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*([\w-]+)\s*=\s*(.*?)\s*$"#).unwrap());
+
+/// Whether `file_path` is a Terraform file
+fn is_terraform(file_path: &str) -> bool {
+    file_path.ends_with(".tf") || file_path.ends_with(".tfvars")
+}
+
+/// Whether content looks like a CloudFormation template
+fn is_cloudformation(content: &str) -> bool {
+    content.contains("AWSTemplateFormatVersion")
+        || (content.contains("Resources") && content.contains("AWS::"))
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    if is_terraform(file_path) {
+        Ok(detect_terraform(content, file_path))
+    } else if is_cloudformation(content) {
+        Ok(detect_cloudformation(content, file_path))
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+fn finding(
+    check: &Check,
+    title: String,
+    file_path: &str,
+    line: usize,
+    snippet: &str,
+) -> Vulnerability {
+    let mut evidence = HashMap::new();
+    evidence.insert("cwe".to_string(), serde_json::json!(check.cwe));
+
+    Vulnerability::new(
+        format!("IAC-{:03}", line),
+        check.vuln_type.clone(),
+        check.severity,
+        title,
+        check.description,
+    )
+    .with_rule_id(check.rule)
+    .with_location(Location::new(file_path).with_line(line))
+    .with_remediation(check.remediation)
+    .with_code_snippet(snippet.trim().to_string())
+    .with_confidence(0.85)
+    .with_evidence(evidence)
+}
+
+/// An open Terraform block and the attributes set directly in it
+struct Block {
+    kind: String,
+    labels: Vec<String>,
+    attributes: Vec<(usize, String, String)>,
+}
+
+impl Block {
+    fn label(&self) -> &str {
+        self.labels.first().map(String::as_str).unwrap_or_default()
+    }
+
+    fn attribute(&self, key: &str) -> Option<&(usize, String, String)> {
+        self.attributes.iter().find(|(_, k, _)| k == key)
+    }
+}
+
+fn detect_terraform(content: &str, file_path: &str) -> Vec<Vulnerability> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+    let mut stack: Vec<Block> = Vec::new();
+    // A list attribute spanning several lines: (line, key, value so far)
+    let mut pending: Option<(usize, String, String)> = None;
+
+    for (index, raw) in lines.iter().enumerate() {
+        let line = raw.split(" #").next().unwrap_or(raw);
+        if line.trim_start().starts_with('#') || line.trim_start().starts_with("//") {
+            continue;
+        }
+
+        let attribute = match pending.take() {
+            Some((start, key, mut value)) => {
+                value.push_str(line.trim());
+                if line.contains(']') {
+                    Some((start, key, value))
+                } else {
+                    pending = Some((start, key, value));
+                    continue;
+                }
+            }
+            None => TF_ATTRIBUTE.captures(line).and_then(|caps| {
+                let value = caps[2].to_string();
+                if value.starts_with('[') && !value.contains(']') {
+                    pending = Some((index + 1, caps[1].to_string(), value));
+                    None
+                } else {
+                    Some((index + 1, caps[1].to_string(), value))
+                }
+            }),
+        };
+
+        if let Some((line_num, key, value)) = attribute {
+            // Literal strings only; interpolations and references are fine
+            let literal = value.len() > 2 && value.starts_with('"') && !value.contains("${");
+            if literal && super::secrets::is_secret_name(&key) {
+                let title = format!("Hardcoded Credential '{}'", key);
+                vulnerabilities.push(finding(
+                    &HARDCODED_CREDENTIALS,
+                    title,
+                    file_path,
+                    line_num,
+                    lines[line_num - 1],
+                ));
+            }
+            if let Some(block) = stack.last_mut() {
+                block.attributes.push((line_num, key, value));
+            }
+        }
+
+        let opens = line.matches('{').count();
+        let closes = line.matches('}').count();
+        if opens > closes {
+            let block = match TF_BLOCK.captures(line) {
+                Some(caps) => Block {
+                    kind: caps[1].to_string(),
+                    labels: caps[2]
+                        .split('"')
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    attributes: Vec::new(),
+                },
+                // `tags = {` and other map values
+                None => Block {
+                    kind: String::new(),
+                    labels: Vec::new(),
+                    attributes: Vec::new(),
+                },
+            };
+            stack.push(block);
+        } else {
+            for _ in opens..closes {
+                if let Some(block) = stack.pop() {
+                    check_terraform_block(
+                        &block,
+                        stack.last(),
+                        file_path,
+                        &lines,
+                        &mut vulnerabilities,
+                    );
+                }
+            }
+        }
+    }
+
+    vulnerabilities
+}
+
+fn check_terraform_block(
+    block: &Block,
+    parent: Option<&Block>,
+    file_path: &str,
+    lines: &[&str],
+    out: &mut Vec<Vulnerability>,
+) {
+    let mut report = |check: &Check, title: String, line: usize| {
+        out.push(finding(check, title, file_path, line, lines[line - 1]));
+    };
+
+    let resource = if block.kind == "resource" {
+        block.label()
+    } else {
+        ""
+    };
+
+    // Public S3 ACLs and disabled public access blocks
+    if let ("aws_s3_bucket" | "aws_s3_bucket_acl", Some((line, _, acl))) =
+        (resource, block.attribute("acl"))
+    {
+        if acl.contains("public-read") {
+            report(
+                &PUBLIC_BUCKET,
+                format!("Public S3 Bucket ACL {}", acl),
+                *line,
+            );
+        }
+    }
+    if resource == "aws_s3_bucket_public_access_block" {
+        let disabled = block.attributes.iter().find(|(_, key, value)| {
+            matches!(
+                key.as_str(),
+                "block_public_acls"
+                    | "block_public_policy"
+                    | "ignore_public_acls"
+                    | "restrict_public_buckets"
+            ) && value == "false"
+        });
+        if let Some((line, key, _)) = disabled {
+            report(
+                &PUBLIC_BUCKET,
+                format!("S3 Public Access Block Disables {}", key),
+                *line,
+            );
+        }
+    }
+
+    // Ingress from anywhere
+    let is_ingress = match resource {
+        "aws_security_group_rule" => block
+            .attribute("type")
+            .is_some_and(|(_, _, t)| t.contains("ingress")),
+        "aws_vpc_security_group_ingress_rule" => true,
+        "google_compute_firewall" => block
+            .attribute("direction")
+            .is_none_or(|(_, _, d)| d.contains("INGRESS")),
+        _ => {
+            block.kind == "ingress"
+                && parent.is_some_and(|p| p.kind == "resource" && p.label() == "aws_security_group")
+        }
+    };
+    if is_ingress {
+        let open = block.attributes.iter().find(|(_, key, value)| {
+            matches!(
+                key.as_str(),
+                "cidr_blocks" | "ipv6_cidr_blocks" | "cidr_ipv4" | "cidr_ipv6" | "source_ranges"
+            ) && ANY_ADDRESS.iter().any(|any| value.contains(any))
+        });
+        if let Some((line, _, _)) = open {
+            let port = block
+                .attribute("from_port")
+                .map(|(_, _, p)| format!(" on port {}", p))
+                .unwrap_or_default();
+            report(
+                &OPEN_SECURITY_GROUP,
+                format!("Ingress Open to the Internet{}", port),
+                *line,
+            );
+        }
+    }
+}
+
+fn detect_cloudformation(content: &str, file_path: &str) -> Vec<Vulnerability> {
+    let Ok(template) = serde_yaml::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let Some(resources) = template.get("Resources").and_then(Value::as_mapping) else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    // Line of the first `key` at or after `from`, or `from` itself
+    let locate = |from: usize, key: &str| -> usize {
+        (from..lines.len())
+            .find(|&i| {
+                let trimmed = lines[i].trim_start().trim_start_matches("- ");
+                trimmed.starts_with(key) || trimmed.starts_with(&format!("\"{}\"", key))
+            })
+            .unwrap_or(from)
+    };
+    let mut vulnerabilities = Vec::new();
+    let mut report = |check: &Check, title: String, index: usize| {
+        let snippet = lines.get(index).copied().unwrap_or_default();
+        vulnerabilities.push(finding(check, title, file_path, index + 1, snippet));
+    };
+
+    for (logical_id, resource) in resources {
+        let logical_id = logical_id.as_str().unwrap_or_default();
+        let at = locate(0, logical_id);
+        let kind = resource
+            .get("Type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let Some(properties) = resource.get("Properties") else {
+            continue;
+        };
+
+        match kind {
+            "AWS::S3::Bucket" => {
+                let acl = properties.get("AccessControl").and_then(Value::as_str);
+                if let Some(acl @ ("PublicRead" | "PublicReadWrite")) = acl {
+                    let title = format!("Public S3 Bucket ACL {}", acl);
+                    report(&PUBLIC_BUCKET, title, locate(at, "AccessControl"));
+                }
+                let block = properties.get("PublicAccessBlockConfiguration");
+                let disabled = [
+                    "BlockPublicAcls",
+                    "BlockPublicPolicy",
+                    "IgnorePublicAcls",
+                    "RestrictPublicBuckets",
+                ]
+                .into_iter()
+                .find(|key| block.and_then(|b| b.get(key)).and_then(Value::as_bool) == Some(false));
+                if let Some(key) = disabled {
+                    let title = format!("S3 Public Access Block Disables {}", key);
+                    report(&PUBLIC_BUCKET, title, locate(at, key));
+                }
+            }
+            "AWS::EC2::SecurityGroup" | "AWS::EC2::SecurityGroupIngress" => {
+                let rules: Vec<&Value> = match properties.get("SecurityGroupIngress") {
+                    Some(Value::Sequence(rules)) => rules.iter().collect(),
+                    _ if kind == "AWS::EC2::SecurityGroupIngress" => vec![properties],
+                    _ => Vec::new(),
+                };
+                for rule in rules {
+                    let cidr = ["CidrIp", "CidrIpv6"].into_iter().find(|key| {
+                        rule.get(key)
+                            .and_then(Value::as_str)
+                            .is_some_and(|cidr| ANY_ADDRESS.contains(&cidr))
+                    });
+                    if let Some(key) = cidr {
+                        let port = rule
+                            .get("FromPort")
+                            .map(|p| format!(" on port {}", yaml_scalar(p)))
+                            .unwrap_or_default();
+                        let title = format!("Ingress Open to the Internet{}", port);
+                        report(&OPEN_SECURITY_GROUP, title, locate(at, key));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Literal credentials anywhere in the properties (`MasterUserPassword: hunter2`)
+        for key in literal_secret_keys(properties) {
+            let title = format!("Hardcoded Credential '{}'", key);
+            report(&HARDCODED_CREDENTIALS, title, locate(at, &key));
+        }
+    }
+
+    vulnerabilities
+}
+
+/// Secret-looking property names set to plain strings (not `!Ref`, `{{resolve:...}}`, ...)
+fn literal_secret_keys(value: &Value) -> Vec<String> {
+    let mut keys = Vec::new();
+    match value {
+        Value::Mapping(map) => {
+            for (key, value) in map {
+                let key = key.as_str().unwrap_or_default();
+                match value {
+                    Value::String(s)
+                        if !s.is_empty()
+                            && !s.starts_with("{{resolve:")
+                            && super::secrets::is_secret_name(key) =>
+                    {
+                        keys.push(key.to_string())
+                    }
+                    other => keys.extend(literal_secret_keys(other)),
+                }
+            }
+        }
+        Value::Sequence(items) => items
+            .iter()
+            .for_each(|item| keys.extend(literal_secret_keys(item))),
+        _ => {}
+    }
+    keys
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_terraform() {
+        let content = r#"
+provider "aws" {
+  region     = "us-east-1"
+  access_key = "AKIAEXAMPLE"
+  secret_key = var.secret_key
+}
+
+resource "aws_s3_bucket" "artifacts" {
+  bucket = "mcp-artifacts"
+  acl    = "public-read"
+  tags = {
+    Name = "artifacts"
+  }
+}
+
+resource "aws_security_group" "mcp" {
+  ingress {
+    from_port   = 8080
+    to_port     = 8080
+    protocol    = "tcp"
+    cidr_blocks = [
+      "0.0.0.0/0",
+    ]
+  }
+  egress {
+    from_port   = 0
+    to_port     = 0
+    protocol    = "-1"
+    cidr_blocks = ["0.0.0.0/0"]
+  }
+}
+"#;
+        let vulns = detect(content, "infra/main.tf").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "hardcoded-credentials",
+                "public-s3-bucket",
+                "open-security-group"
+            ]
+        );
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(4));
+        assert_eq!(vulns[2].location.as_ref().unwrap().line, Some(21));
+        assert_eq!(vulns[2].title, "Ingress Open to the Internet on port 8080");
+    }
+
+    #[test]
+    fn test_detect_cloudformation() {
+        let content = r#"AWSTemplateFormatVersion: "2010-09-09"
+Resources:
+  Bucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      AccessControl: PublicRead
+  ServerSG:
+    Type: AWS::EC2::SecurityGroup
+    Properties:
+      GroupDescription: MCP server
+      SecurityGroupIngress:
+        - IpProtocol: tcp
+          FromPort: 22
+          ToPort: 22
+          CidrIp: 0.0.0.0/0
+  Database:
+    Type: AWS::RDS::DBInstance
+    Properties:
+      MasterUsername: admin
+      MasterUserPassword: hunter2hunter2
+"#;
+        let vulns = detect(content, "template.yaml").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "public-s3-bucket",
+                "open-security-group",
+                "hardcoded-credentials"
+            ]
+        );
+        assert_eq!(vulns[1].location.as_ref().unwrap().line, Some(15));
+        assert_eq!(vulns[2].location.as_ref().unwrap().line, Some(20));
+    }
+
+    #[test]
+    fn test_safe_iac_not_flagged() {
+        let terraform = r#"
+resource "aws_s3_bucket_public_access_block" "artifacts" {
+  block_public_acls       = true
+  restrict_public_buckets = true
+}
+resource "aws_db_instance" "db" {
+  password = var.db_password
+}
+"#;
+        assert!(detect(terraform, "main.tf").unwrap().is_empty());
+
+        let template = "Resources:\n  Db:\n    Type: AWS::RDS::DBInstance\n    Properties:\n      MasterUserPassword: !Ref DbPassword\n";
+        assert!(detect(template, "template.yaml").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+//! Insecure transport detection - CWE-295, CWE-319
+//!
+//! MCP servers spend most of their time calling other APIs with the user's
+//! tokens. Two mistakes expose that traffic:
+//! - certificate validation turned off (`verify=False`,
+//!   `rejectUnauthorized: false`, `InsecureSkipVerify`), usually to get past
+//!   a self-signed certificate once and never turned back on
+//! - plain `http://` for API calls, and especially for OAuth token exchanges
+//!   and logins, which puts credentials on the wire in clear text
+//!
+//! Loopback URLs (`localhost`, `127.0.0.1`, `[::1]`) are not flagged as
+//! plaintext: local development servers and sidecars rarely have TLS.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct TransportPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    severity: Severity,
+    cwe: &'static str,
+    /// Match only if the line has an `http://` URL to a non-loopback host
+    plaintext_url: bool,
+    remediation: &'static str,
+}
+
+/// `http://` URLs, up to the end of the string literal they sit in
+static HTTP_URL: Lazy<Regex> = Lazy::new(|| Regex::new(r#"http://[^\s"'`)]+"#).unwrap());
+
+static LOOPBACK_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^http://(?:localhost|127\.\d+\.\d+\.\d+|0\.0\.0\.0|\[::1\])(?:[:/?#]|$)"#)
+        .unwrap()
+});
+
+const DISABLE_VERIFICATION: &str = "Keep certificate validation on; for private CAs, pass the \
+                                    CA bundle (verify='/path/ca.pem', ca: fs.readFileSync(...), \
+                                    RootCAs) instead of disabling checks";
+
+static TRANSPORT_PATTERNS: Lazy<Vec<TransportPattern>> = Lazy::new(|| {
+    vec![
+        TransportPattern {
+            name: "TLS certificate verification disabled",
+            language: "Python",
+            regex: Regex::new(
+                r#"\bverify\s*=\s*False\b|\bssl\._create_unverified_context\b|\bcheck_hostname\s*=\s*False\b|\bverify_mode\s*=\s*(?:ssl\.)?CERT_NONE\b|\bcert_reqs\s*=\s*(?:ssl\.)?CERT_NONE\b"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-295",
+            plaintext_url: false,
+            remediation: DISABLE_VERIFICATION,
+        },
+        TransportPattern {
+            name: "TLS certificate verification disabled",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(
+                r#"\brejectUnauthorized\s*:\s*false\b|\bNODE_TLS_REJECT_UNAUTHORIZED\b\s*[=:]\s*["']?0\b"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-295",
+            plaintext_url: false,
+            remediation: DISABLE_VERIFICATION,
+        },
+        TransportPattern {
+            name: "TLS certificate verification disabled",
+            language: "Go/Rust",
+            regex: Regex::new(
+                r#"\bInsecureSkipVerify\s*:\s*true\b|\.danger_accept_invalid_(?:certs|hostnames)\s*\(\s*true\s*\)"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-295",
+            plaintext_url: false,
+            remediation: DISABLE_VERIFICATION,
+        },
+        TransportPattern {
+            name: "TLS certificate verification disabled",
+            language: "Java",
+            regex: Regex::new(
+                r#"\b(?:NoopHostnameVerifier|ALLOW_ALL_HOSTNAME_VERIFIER|TrustAllStrategy)\b|\bsetHostnameVerifier\s*\(\s*\(?[^)]*\)?\s*->\s*true\b"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-295",
+            plaintext_url: false,
+            remediation: DISABLE_VERIFICATION,
+        },
+        TransportPattern {
+            name: "TLS certificate verification disabled",
+            language: "Shell",
+            regex: Regex::new(
+                r#"\bcurl\b[^|;&]*\s(?:-[a-zA-Z]*k[a-zA-Z]*|--insecure)\b|\bwget\b[^|;&]*\s--no-check-certificate\b"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-295",
+            plaintext_url: false,
+            remediation: "Drop -k/--insecure (--no-check-certificate); point curl at the CA \
+                          with --cacert if the server uses a private CA",
+        },
+        // Before the generic API call pattern, so token endpoints get the
+        // higher severity
+        TransportPattern {
+            name: "Credentials exchanged over plaintext HTTP",
+            language: "Any",
+            regex: Regex::new(
+                r#"(?i)http://[^\s"'`]*/(?:oauth2?|token|auth(?:orize)?|login|signin|sign-in|session)\b|http://[^\s"'`]*["'`][^\n]*\b(?:Authorization|Bearer|client_secret|access_token|api_key|password)\b"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            cwe: "CWE-319",
+            plaintext_url: true,
+            remediation: "Use https:// for token endpoints and every request that carries \
+                          credentials; OAuth 2.0 requires TLS for token exchanges",
+        },
+        TransportPattern {
+            name: "API call over plaintext HTTP",
+            language: "Any",
+            regex: Regex::new(
+                r#"\b(?:requests|httpx|session|client|aiohttp|urllib\.request|axios|got|ky|superagent|http)\s*\.\s*(?:get|post|put|patch|delete|request|urlopen|Get|Post)\s*\(\s*(?:["'`]\w+["'`]\s*,\s*)?["'`]http://|\bfetch\s*\(\s*["'`]http://|\b(?i:base_?url|api_?url|endpoint)\s*[:=]\s*["'`]http://"#,
+            )
+            .unwrap(),
+            severity: Severity::Medium,
+            cwe: "CWE-319",
+            plaintext_url: true,
+            remediation: "Use https:// for API endpoints so requests and responses can't be \
+                          read or modified in transit",
+        },
+    ]
+});
+
+/// Whether `line` contains an `http://` URL to something other than loopback
+fn has_remote_http_url(line: &str) -> bool {
+    HTTP_URL
+        .find_iter(line)
+        .any(|m| !LOOPBACK_URL.is_match(m.as_str()))
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = TRANSPORT_PATTERNS
+            .iter()
+            .find(|p| p.regex.is_match(line) && (!p.plaintext_url || has_remote_http_url(line)))
+        else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!(pattern.cwe));
+
+            let (description, impact) = if pattern.plaintext_url {
+                (
+                    "Traffic is sent over unencrypted HTTP",
+                    "Anyone on the network path can read tokens, credentials, and response data, \
+                     or alter responses the server trusts",
+                )
+            } else {
+                (
+                    "TLS certificate or hostname validation is disabled",
+                    "An attacker on the network path can impersonate the upstream service with any \
+                     certificate and read or alter the traffic, including API keys sent with it",
+                )
+            };
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("TRANSPORT-{:03}", id_counter),
+                    VulnerabilityType::InsecureTransport,
+                    pattern.severity,
+                    format!("{} Detected", pattern.name),
+                    description,
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(impact)
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(if pattern.plaintext_url { 0.7 } else { 0.9 })
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_disabled_verification() {
+        let content = r#"
+resp = requests.get(url, headers=headers, verify=False)
+ctx = ssl._create_unverified_context()
+const agent = new https.Agent({ rejectUnauthorized: false });
+process.env.NODE_TLS_REJECT_UNAUTHORIZED = "0";
+tr := &http.Transport{TLSClientConfig: &tls.Config{InsecureSkipVerify: true}}
+let client = reqwest::Client::builder().danger_accept_invalid_certs(true).build()?;
+curl -sk https://internal.example.com/install.sh
+"#;
+        let vulns = detect(content, "client.py").unwrap();
+        assert_eq!(vulns.len(), 7);
+        assert!(vulns.iter().all(|v| v.severity == Severity::High));
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::InsecureTransport);
+        assert_eq!(vulns[0].evidence.as_ref().unwrap()["cwe"], "CWE-295");
+    }
+
+    #[test]
+    fn test_detect_plaintext_http() {
+        let content = r#"
+token = requests.post("http://auth.example.com/oauth/token", data=form)
+const res = await fetch("http://api.example.com/v1/items");
+BASE_URL = "http://api.example.com"
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "credentials-exchanged-over-plaintext-http",
+                "api-call-over-plaintext-http",
+                "api-call-over-plaintext-http",
+            ]
+        );
+        assert_eq!(vulns[0].severity, Severity::High);
+        assert_eq!(vulns[1].severity, Severity::Medium);
+        assert_eq!(vulns[1].evidence.as_ref().unwrap()["cwe"], "CWE-319");
+    }
+
+    #[test]
+    fn test_safe_transport_not_flagged() {
+        let content = r#"
+resp = requests.get("https://api.example.com/v1/items", verify=True)
+const res = await fetch("http://localhost:3000/oauth/token");
+base_url = "http://127.0.0.1:8080"
+xmlns = "http://www.w3.org/2000/svg"
+"#;
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+}
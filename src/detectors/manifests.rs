@@ -0,0 +1,433 @@
+//! Kubernetes and docker-compose manifest checks
+//!
+//! MCP servers often ship a compose file or k8s manifests for self-hosting.
+//! Each YAML document is parsed and checked for the settings that turn a
+//! compromised tool server into a compromised host:
+//!
+//! - `privileged-container` - `privileged: true`
+//! - `host-namespace` - `hostNetwork`/`hostPID`/`hostIPC`, `network_mode: host`
+//! - `host-path-mount` - `hostPath` volumes and absolute bind mounts
+//! - `missing-resource-limits` - containers without CPU/memory limits
+//! - `plaintext-secret-env` - credentials written inline as env values
+//!
+//! Documents that fail to parse (e.g. Helm templates) are skipped.
+
+use anyhow::Result;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// A manifest check and how its findings are reported
+struct Check {
+    rule: &'static str,
+    vuln_type: VulnerabilityType,
+    severity: Severity,
+    cwe: &'static str,
+    description: &'static str,
+    remediation: &'static str,
+}
+
+const PRIVILEGED: Check = Check {
+    rule: "privileged-container",
+    vuln_type: VulnerabilityType::InsecureContainerConfig,
+    severity: Severity::Critical,
+    cwe: "CWE-250",
+    description: "The container runs privileged, with all capabilities and access to host \
+                  devices",
+    remediation: "Remove `privileged: true`; grant only the specific capabilities needed",
+};
+
+const HOST_NAMESPACE: Check = Check {
+    rule: "host-namespace",
+    vuln_type: VulnerabilityType::InsecureContainerConfig,
+    severity: Severity::High,
+    cwe: "CWE-668",
+    description: "The container shares a host namespace, exposing host network services or \
+                  processes to it",
+    remediation: "Use the default isolated namespaces and publish only the ports needed",
+};
+
+const HOST_PATH: Check = Check {
+    rule: "host-path-mount",
+    vuln_type: VulnerabilityType::InsecureContainerConfig,
+    severity: Severity::High,
+    cwe: "CWE-668",
+    description: "A host directory is mounted into the container, so a compromised server \
+                  can read or modify host files",
+    remediation: "Use named volumes or ConfigMaps/Secrets; if a host path is required, mount \
+                  the narrowest directory read-only",
+};
+
+const MISSING_LIMITS: Check = Check {
+    rule: "missing-resource-limits",
+    vuln_type: VulnerabilityType::InsecureContainerConfig,
+    severity: Severity::Low,
+    cwe: "CWE-770",
+    description: "The container has no CPU or memory limits, so a runaway or abused tool can \
+                  starve the host",
+    remediation: "Set resources.limits (k8s) or deploy.resources.limits (compose)",
+};
+
+const PLAINTEXT_SECRET: Check = Check {
+    rule: "plaintext-secret-env",
+    vuln_type: VulnerabilityType::HardcodedCredentials,
+    severity: Severity::High,
+    cwe: "CWE-798",
+    description: "A credential is written inline in the manifest and committed with it",
+    remediation: "Reference a Secret (valueFrom.secretKeyRef) or an env_file kept out of \
+                  version control, and rotate the exposed value",
+};
+
+/// One YAML document and where it starts in the file
+struct Document<'a> {
+    lines: &'a [&'a str],
+    offset: usize,
+    file_path: &'a str,
+}
+
+impl Document<'_> {
+    /// Index (relative to the document) of the first line at or after `from`
+    /// matching `predicate`
+    fn find(&self, from: usize, predicate: impl Fn(&str) -> bool) -> Option<usize> {
+        (from..self.lines.len()).find(|&i| predicate(self.lines[i]))
+    }
+
+    /// First line at or after `from` that sets `key` (optionally containing `value`)
+    fn find_key(&self, from: usize, key: &str, value: Option<&str>) -> Option<usize> {
+        self.find(from, |line| {
+            let line = line.trim_start().trim_start_matches("- ");
+            line.strip_prefix(key)
+                .and_then(|rest| rest.trim_start_matches(['"', '\'']).strip_prefix(':'))
+                .is_some_and(|rest| value.is_none_or(|v| rest.contains(v)))
+        })
+    }
+
+    fn finding(&self, check: &Check, title: String, index: Option<usize>) -> Vulnerability {
+        let index = index.unwrap_or(0);
+        let line = self.offset + index + 1;
+
+        let mut evidence = HashMap::new();
+        evidence.insert("cwe".to_string(), serde_json::json!(check.cwe));
+
+        let mut vuln = Vulnerability::new(
+            format!("MANIFEST-{:03}", line),
+            check.vuln_type.clone(),
+            check.severity,
+            title,
+            check.description,
+        )
+        .with_rule_id(check.rule)
+        .with_location(Location::new(self.file_path).with_line(line))
+        .with_remediation(check.remediation)
+        .with_confidence(0.85)
+        .with_evidence(evidence);
+        if let Some(source) = self.lines.get(index) {
+            vuln = vuln.with_code_snippet(source.trim().to_string());
+        }
+        vuln
+    }
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let lower = file_path.to_ascii_lowercase();
+    if !(lower.ends_with(".yaml") || lower.ends_with(".yml")) {
+        return Ok(Vec::new());
+    }
+    let is_compose = lower
+        .rsplit(['/', '\\'])
+        .next()
+        .is_some_and(|name| name.contains("compose"));
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+
+    // Split on `---` document separators, remembering where each starts
+    let mut start = 0;
+    for end in (0..=lines.len()).filter(|&i| i == lines.len() || lines[i].starts_with("---")) {
+        let doc = Document {
+            lines: &lines[start..end],
+            offset: start,
+            file_path,
+        };
+        start = end + 1;
+
+        let Ok(value) = serde_yaml::from_str::<Value>(&doc.lines.join("\n")) else {
+            continue;
+        };
+        if let Some(spec) = pod_spec(&value) {
+            check_pod_spec(&doc, spec, &mut vulnerabilities);
+        } else if is_compose {
+            if let Some(services) = value.get("services").and_then(Value::as_mapping) {
+                for (name, service) in services {
+                    let name = name.as_str().unwrap_or_default();
+                    check_compose_service(&doc, name, service, &mut vulnerabilities);
+                }
+            }
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+/// The pod spec of a Kubernetes workload, whatever its kind
+fn pod_spec(doc: &Value) -> Option<&Value> {
+    doc.get("apiVersion")?;
+    let spec = doc.get("spec")?;
+    match doc.get("kind")?.as_str()? {
+        "Pod" => Some(spec),
+        "CronJob" => spec
+            .get("jobTemplate")?
+            .get("spec")?
+            .get("template")?
+            .get("spec"),
+        _ => spec.get("template")?.get("spec"),
+    }
+}
+
+fn is_true(value: Option<&Value>) -> bool {
+    value.and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn check_pod_spec(doc: &Document, spec: &Value, out: &mut Vec<Vulnerability>) {
+    for namespace in ["hostNetwork", "hostPID", "hostIPC"] {
+        if is_true(spec.get(namespace)) {
+            let title = format!("Host Namespace Shared ({})", namespace);
+            out.push(doc.finding(&HOST_NAMESPACE, title, doc.find_key(0, namespace, None)));
+        }
+    }
+
+    for volume in spec
+        .get("volumes")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+    {
+        if let Some(host_path) = volume.get("hostPath") {
+            let path = host_path.get("path").and_then(Value::as_str).unwrap_or("?");
+            let line = doc.find_key(0, "path", Some(path));
+            out.push(doc.finding(&HOST_PATH, format!("Host Path Mounted '{}'", path), line));
+        }
+    }
+
+    let containers = ["initContainers", "containers"]
+        .into_iter()
+        .filter_map(|key| spec.get(key).and_then(Value::as_sequence))
+        .flatten();
+    for container in containers {
+        let name = container.get("name").and_then(Value::as_str).unwrap_or("?");
+        let at = doc.find_key(0, "name", Some(name)).unwrap_or(0);
+
+        let security = container.get("securityContext");
+        if is_true(security.and_then(|s| s.get("privileged"))) {
+            let title = format!("Privileged Container '{}'", name);
+            out.push(doc.finding(&PRIVILEGED, title, doc.find_key(at, "privileged", None)));
+        }
+
+        if container
+            .get("resources")
+            .and_then(|r| r.get("limits"))
+            .is_none()
+        {
+            let title = format!("No Resource Limits for '{}'", name);
+            out.push(doc.finding(&MISSING_LIMITS, title, Some(at)));
+        }
+
+        for var in container
+            .get("env")
+            .and_then(Value::as_sequence)
+            .into_iter()
+            .flatten()
+        {
+            let key = var.get("name").and_then(Value::as_str).unwrap_or_default();
+            let value = var.get("value").and_then(Value::as_str).unwrap_or_default();
+            if !value.is_empty() && super::secrets::is_secret_name(key) {
+                let title = format!("Plaintext Secret in Env '{}'", key);
+                out.push(doc.finding(
+                    &PLAINTEXT_SECRET,
+                    title,
+                    doc.find_key(at, "name", Some(key)),
+                ));
+            }
+        }
+    }
+}
+
+fn check_compose_service(
+    doc: &Document,
+    name: &str,
+    service: &Value,
+    out: &mut Vec<Vulnerability>,
+) {
+    let at = doc.find_key(0, name, None).unwrap_or(0);
+
+    if is_true(service.get("privileged")) {
+        let title = format!("Privileged Container '{}'", name);
+        out.push(doc.finding(&PRIVILEGED, title, doc.find_key(at, "privileged", None)));
+    }
+
+    for key in ["network_mode", "pid", "ipc"] {
+        if service.get(key).and_then(Value::as_str) == Some("host") {
+            let title = format!("Host Namespace Shared ({}: host)", key);
+            out.push(doc.finding(&HOST_NAMESPACE, title, doc.find_key(at, key, None)));
+        }
+    }
+
+    for volume in service
+        .get("volumes")
+        .and_then(Value::as_sequence)
+        .into_iter()
+        .flatten()
+    {
+        // Short syntax `/host:/container[:ro]` or long syntax with `source`
+        let source = match volume {
+            Value::String(spec) => spec.split(':').next().unwrap_or_default(),
+            other => other
+                .get("source")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        };
+        if source.starts_with('/') || source.starts_with('~') {
+            let line = doc.find(at, |line| line.contains(source));
+            out.push(doc.finding(&HOST_PATH, format!("Host Path Mounted '{}'", source), line));
+        }
+    }
+
+    let limited = service
+        .get("deploy")
+        .and_then(|d| d.get("resources"))
+        .and_then(|r| r.get("limits"))
+        .is_some()
+        || service.get("mem_limit").is_some()
+        || service.get("cpus").is_some();
+    if !limited {
+        let title = format!("No Resource Limits for '{}'", name);
+        out.push(doc.finding(&MISSING_LIMITS, title, Some(at)));
+    }
+
+    // `environment` is either a map or a list of `KEY=value`
+    let variables: Vec<(String, String)> = match service.get("environment") {
+        Some(Value::Mapping(map)) => map
+            .iter()
+            .filter_map(|(k, v)| {
+                let value = match v {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+                Some((k.as_str()?.to_string(), value))
+            })
+            .collect(),
+        Some(Value::Sequence(list)) => list
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    for (key, value) in variables {
+        // `${VAR}` interpolates from the host environment, which is fine
+        if !value.is_empty() && !value.starts_with('$') && super::secrets::is_secret_name(&key) {
+            let line = doc.find(at, |line| line.contains(key.as_str()));
+            let title = format!("Plaintext Secret in Env '{}'", key);
+            out.push(doc.finding(&PLAINTEXT_SECRET, title, line));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_insecure_deployment() {
+        let content = r#"apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: settings
+---
+apiVersion: apps/v1
+kind: Deployment
+spec:
+  template:
+    spec:
+      hostNetwork: true
+      containers:
+        - name: mcp
+          image: mcp-server:1.0
+          securityContext:
+            privileged: true
+          env:
+            - name: GITHUB_TOKEN
+              value: ghp_live_token
+            - name: LOG_LEVEL
+              value: debug
+      volumes:
+        - name: docker
+          hostPath:
+            path: /var/run/docker.sock
+"#;
+        let vulns = detect(content, "k8s/deployment.yaml").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "host-namespace",
+                "host-path-mount",
+                "privileged-container",
+                "missing-resource-limits",
+                "plaintext-secret-env"
+            ]
+        );
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(11));
+        assert_eq!(vulns[1].location.as_ref().unwrap().line, Some(25));
+        assert_eq!(vulns[4].location.as_ref().unwrap().line, Some(18));
+    }
+
+    #[test]
+    fn test_detect_insecure_compose() {
+        let content = r#"services:
+  mcp:
+    image: mcp-server:1.0
+    privileged: true
+    network_mode: host
+    volumes:
+      - /:/host:ro
+      - data:/data
+    environment:
+      OPENAI_API_KEY: sk-live-abc
+      GITHUB_TOKEN: ${GITHUB_TOKEN}
+"#;
+        let vulns = detect(content, "docker-compose.yml").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "privileged-container",
+                "host-namespace",
+                "host-path-mount",
+                "missing-resource-limits",
+                "plaintext-secret-env"
+            ]
+        );
+        assert_eq!(vulns[4].location.as_ref().unwrap().line, Some(10));
+    }
+
+    #[test]
+    fn test_hardened_manifests_not_flagged() {
+        let compose = r#"services:
+  mcp:
+    image: mcp-server:1.0
+    mem_limit: 512m
+    environment:
+      - OPENAI_API_KEY=${OPENAI_API_KEY}
+"#;
+        assert!(detect(compose, "compose.yaml").unwrap().is_empty());
+
+        // Only compose files are treated as compose
+        assert!(detect(compose, "config.yaml").unwrap().is_empty());
+    }
+}
@@ -15,10 +15,53 @@
 //! - `code_injection` - eval(), exec(), dynamic code execution (20+ patterns)
 //! - `deserialization` - Unsafe pickle, yaml, marshal usage
 //! - `path_traversal` - Directory traversal vulnerabilities
-//! - `sql_injection` - SQL injection via string concatenation
+//! - `sql_injection` - SQL injection via string concatenation (rule bundle)
 //! - `ssrf` - Server-side request forgery patterns
 //!
-//! **Total**: 10 detector types with 80+ detection patterns
+//! **Phase 2 Detectors**:
+//! - `dependencies` - Wildcard versions, unpinned git dependencies, missing lockfiles
+//! - `dockerfile` - Container checks (root user, unpinned images, secrets in ENV, ...)
+//! - `manifests` - Kubernetes and docker-compose hardening (privileged, hostPath, ...)
+//! - `egress` - Hardcoded endpoints, with findings for exfiltration channels
+//! - `iac` - Terraform/CloudFormation (public S3, open security groups, literal credentials)
+//! - `env_exfiltration` - Whole-environment dumps sent over the network or logged
+//! - `file_permissions` - World-writable chmod/mkdir modes and `umask(0)`
+//! - `hidden_unicode` - Zero-width, tag, and bidi control characters; mixed-script homoglyphs
+//! - `header_injection` - Response headers set from request data or containing CR/LF
+//! - `insecure_transport` - Disabled TLS verification, plaintext `http://` API calls and token exchanges
+//! - `ldap_injection` - LDAP search filters built from unescaped input
+//! - `nosql_injection` - MongoDB `$where`, request bodies as filters, Elasticsearch query strings
+//! - `obfuscation` - Encoded payloads fed to eval/exec, char-code and hex-escape string assembly
+//! - `open_redirect` - Redirects to request parameters (OAuth callbacks)
+//! - `prompts_resources` - MCP prompt and resource definitions (injection, URI credentials, unsanitized template variables)
+//! - `prototype_pollution` - `__proto__` writes and deep merges of request data (JS)
+//! - `redos` - Regex literals with nested quantifiers or overlapping alternation
+//! - `shell_script` - Install scripts and entrypoints (curl | sh, eval of downloads, rm -rf)
+//! - `unsafe_reflection` - Java/C# classes, types, and expressions resolved from input
+//! - `xpath_injection` - XPath expressions built by concatenation, interpolation, or formatting
+//! - `unsafe_rust` - Raw pointer operations in `unsafe` blocks of request handlers
+//! - `zip_slip` - Archive extraction that trusts entry names (extractall, tar -P, joins)
+//! - `ssti` - Templates compiled from user input (Jinja2, Mako, EJS, Handlebars, ...)
+//! - `xxe` - XML external entity risks in Python, Java, C/PHP, and JS parsers
+//! - `toxic_flows` - Dangerous tool combinations across a whole server
+//! - `cross_server` - Servers in one MCP config referencing each other's tools (confused deputy)
+//!
+//! **User rules**:
+//! - `yara` - User-provided YARA rules (`yara` feature)
+//! - `ioc` - Domains, IPs, and file hashes from threat-intel IOC lists
+//!
+//! `bundle` holds pattern rules that ship as data (`rules/builtin.json`) and
+//! can be updated with `mcp-sentinel rules update`; they run as part of the
+//! detector they name. Only `sql_injection` has moved there so far.
+//!
+//! `capabilities` is an inventory rather than a detector: what the server
+//! can do to its host, inferred from imports and API calls. `pii` finds
+//! personal data in text for the proxy's DLP rather than in source.
+//!
+//! `gitleaks` imports rules and allowlists from a `gitleaks.toml` into the
+//! secrets detector.
+//!
+//! **Total**: 10 built-in detector types with 80+ detection patterns
 
 // Phase 1.0 detectors
 pub mod code_vulns;
@@ -30,10 +73,625 @@ pub mod tool_poisoning;
 pub mod code_injection;
 pub mod deserialization;
 pub mod path_traversal;
-pub mod sql_injection;
 pub mod ssrf;
 
+// Rule bundles, user rules, and rule imports
+pub mod bundle;
+pub mod gitleaks;
+pub mod ioc;
+pub mod yara;
+
+// Phase 2 detectors
+pub mod capabilities;
+pub mod cross_server;
+pub mod dependencies;
+pub mod dockerfile;
+pub mod egress;
+pub mod env_exfiltration;
+pub mod file_permissions;
+pub mod header_injection;
+pub mod hidden_unicode;
+pub mod iac;
+pub mod insecure_transport;
+pub mod ldap_injection;
+pub mod manifests;
+pub mod nosql_injection;
+pub mod obfuscation;
+pub mod open_redirect;
+pub mod pii;
+pub mod prompts_resources;
+pub mod prototype_pollution;
+pub mod redos;
+pub mod shell_script;
+pub mod ssti;
+pub mod toxic_flows;
+pub mod unsafe_reflection;
+pub mod unsafe_rust;
+pub mod xpath_injection;
+pub mod xxe;
+pub mod zip_slip;
+
 // Phase 2+ detectors (planned)
-// pub mod pii;
-// pub mod toxic_flows;
 // pub mod anomalies;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use tracing::{debug, debug_span, warn};
+
+use crate::models::{
+    config::ScanConfig,
+    mcp_protocol::ToolDefinition,
+    project_config::{apply_severity_overrides, PathPolicy},
+    vulnerability::{Location, Vulnerability},
+};
+
+/// Identifies a detector so it can be enabled, disabled, or configured by name
+///
+/// The serialized (snake_case) form is what configuration files and the
+/// library builder use, e.g. `"command_injection"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectorKind {
+    Secrets,
+    CommandInjection,
+    SensitiveFileAccess,
+    ToolPoisoning,
+    PromptInjection,
+    HiddenUnicode,
+    PromptsResources,
+    CodeInjection,
+    Deserialization,
+    UnsafeReflection,
+    PathTraversal,
+    SqlInjection,
+    NoSqlInjection,
+    LdapInjection,
+    XpathInjection,
+    Ssrf,
+    Xxe,
+    Ssti,
+    OpenRedirect,
+    HeaderInjection,
+    PrototypePollution,
+    Redos,
+    ZipSlip,
+    UnsafeRust,
+    FilePermissions,
+    InsecureTransport,
+    Egress,
+    EnvExfiltration,
+    Obfuscation,
+    Dockerfile,
+    ShellScript,
+    Manifests,
+    Iac,
+    Dependencies,
+    ToxicFlows,
+    CrossServer,
+    Yara,
+    Ioc,
+}
+
+impl DetectorKind {
+    /// All detectors, in the order the scanner runs them
+    pub const ALL: &'static [DetectorKind] = &[
+        DetectorKind::Secrets,
+        DetectorKind::CommandInjection,
+        DetectorKind::SensitiveFileAccess,
+        DetectorKind::ToolPoisoning,
+        DetectorKind::PromptInjection,
+        DetectorKind::HiddenUnicode,
+        DetectorKind::PromptsResources,
+        DetectorKind::CodeInjection,
+        DetectorKind::Deserialization,
+        DetectorKind::UnsafeReflection,
+        DetectorKind::PathTraversal,
+        DetectorKind::SqlInjection,
+        DetectorKind::NoSqlInjection,
+        DetectorKind::LdapInjection,
+        DetectorKind::XpathInjection,
+        DetectorKind::Ssrf,
+        DetectorKind::Xxe,
+        DetectorKind::Ssti,
+        DetectorKind::OpenRedirect,
+        DetectorKind::HeaderInjection,
+        DetectorKind::PrototypePollution,
+        DetectorKind::Redos,
+        DetectorKind::ZipSlip,
+        DetectorKind::UnsafeRust,
+        DetectorKind::FilePermissions,
+        DetectorKind::InsecureTransport,
+        DetectorKind::Egress,
+        DetectorKind::EnvExfiltration,
+        DetectorKind::Obfuscation,
+        DetectorKind::Dockerfile,
+        DetectorKind::ShellScript,
+        DetectorKind::Manifests,
+        DetectorKind::Iac,
+        DetectorKind::Dependencies,
+        DetectorKind::ToxicFlows,
+        DetectorKind::CrossServer,
+        DetectorKind::Yara,
+        DetectorKind::Ioc,
+    ];
+
+    /// Stable identifier used in configuration (snake_case)
+    pub fn id(&self) -> &'static str {
+        match self {
+            DetectorKind::Secrets => "secrets",
+            DetectorKind::CommandInjection => "command_injection",
+            DetectorKind::SensitiveFileAccess => "sensitive_file_access",
+            DetectorKind::ToolPoisoning => "tool_poisoning",
+            DetectorKind::PromptInjection => "prompt_injection",
+            DetectorKind::HiddenUnicode => "hidden_unicode",
+            DetectorKind::PromptsResources => "prompts_resources",
+            DetectorKind::CodeInjection => "code_injection",
+            DetectorKind::Deserialization => "deserialization",
+            DetectorKind::UnsafeReflection => "unsafe_reflection",
+            DetectorKind::PathTraversal => "path_traversal",
+            DetectorKind::SqlInjection => "sql_injection",
+            DetectorKind::NoSqlInjection => "nosql_injection",
+            DetectorKind::LdapInjection => "ldap_injection",
+            DetectorKind::XpathInjection => "xpath_injection",
+            DetectorKind::Ssrf => "ssrf",
+            DetectorKind::Xxe => "xxe",
+            DetectorKind::Ssti => "ssti",
+            DetectorKind::OpenRedirect => "open_redirect",
+            DetectorKind::HeaderInjection => "header_injection",
+            DetectorKind::PrototypePollution => "prototype_pollution",
+            DetectorKind::Redos => "redos",
+            DetectorKind::ZipSlip => "zip_slip",
+            DetectorKind::UnsafeRust => "unsafe_rust",
+            DetectorKind::FilePermissions => "file_permissions",
+            DetectorKind::InsecureTransport => "insecure_transport",
+            DetectorKind::Egress => "egress",
+            DetectorKind::EnvExfiltration => "env_exfiltration",
+            DetectorKind::Obfuscation => "obfuscation",
+            DetectorKind::Dockerfile => "dockerfile",
+            DetectorKind::ShellScript => "shell_script",
+            DetectorKind::Manifests => "manifests",
+            DetectorKind::Iac => "iac",
+            DetectorKind::Dependencies => "dependencies",
+            DetectorKind::ToxicFlows => "toxic_flows",
+            DetectorKind::CrossServer => "cross_server",
+            DetectorKind::Yara => "yara",
+            DetectorKind::Ioc => "ioc",
+        }
+    }
+
+    /// Human-readable name for logs and reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            DetectorKind::Secrets => "Secrets",
+            DetectorKind::CommandInjection => "Command injection",
+            DetectorKind::SensitiveFileAccess => "Sensitive file",
+            DetectorKind::ToolPoisoning => "Tool poisoning",
+            DetectorKind::PromptInjection => "Prompt injection",
+            DetectorKind::HiddenUnicode => "Invisible Unicode and homoglyphs",
+            DetectorKind::PromptsResources => "MCP prompts and resources",
+            DetectorKind::CodeInjection => "Code injection",
+            DetectorKind::Deserialization => "Deserialization",
+            DetectorKind::UnsafeReflection => "Unsafe reflection",
+            DetectorKind::PathTraversal => "Path traversal",
+            DetectorKind::SqlInjection => "SQL injection",
+            DetectorKind::NoSqlInjection => "NoSQL injection",
+            DetectorKind::LdapInjection => "LDAP injection",
+            DetectorKind::XpathInjection => "XPath injection",
+            DetectorKind::Ssrf => "SSRF",
+            DetectorKind::Xxe => "XXE",
+            DetectorKind::Ssti => "SSTI",
+            DetectorKind::OpenRedirect => "Open redirect",
+            DetectorKind::HeaderInjection => "Header injection",
+            DetectorKind::PrototypePollution => "Prototype pollution",
+            DetectorKind::Redos => "ReDoS",
+            DetectorKind::ZipSlip => "Zip slip",
+            DetectorKind::UnsafeRust => "Unsafe Rust",
+            DetectorKind::FilePermissions => "File permissions",
+            DetectorKind::InsecureTransport => "Insecure transport",
+            DetectorKind::Egress => "Egress",
+            DetectorKind::EnvExfiltration => "Environment exfiltration",
+            DetectorKind::Obfuscation => "Obfuscation",
+            DetectorKind::Dockerfile => "Dockerfile",
+            DetectorKind::ShellScript => "Shell scripts",
+            DetectorKind::Manifests => "K8s/compose manifests",
+            DetectorKind::Iac => "IaC",
+            DetectorKind::Dependencies => "Dependency pinning",
+            DetectorKind::ToxicFlows => "Toxic flows",
+            DetectorKind::CrossServer => "Cross-server references",
+            DetectorKind::Yara => "YARA",
+            DetectorKind::Ioc => "Threat intel IOCs",
+        }
+    }
+
+    /// Run this detector on one file's content
+    pub fn run(
+        &self,
+        content: &str,
+        file_path: &str,
+        config: &ScanConfig,
+    ) -> Result<Vec<Vulnerability>> {
+        let mut vulnerabilities = match self {
+            DetectorKind::Secrets => {
+                secrets::detect_with_gitleaks(content, file_path, config.gitleaks.as_ref())
+            }
+            DetectorKind::CommandInjection => {
+                code_vulns::detect_command_injection(content, file_path)
+            }
+            DetectorKind::SensitiveFileAccess => {
+                code_vulns::detect_sensitive_file_access(content, file_path)
+            }
+            DetectorKind::ToolPoisoning => tool_poisoning::detect(content),
+            DetectorKind::PromptInjection => prompt_injection::detect(content),
+            DetectorKind::HiddenUnicode => hidden_unicode::detect(content, file_path),
+            DetectorKind::PromptsResources => prompts_resources::detect(content, file_path),
+            DetectorKind::CodeInjection => code_injection::detect(content, file_path),
+            DetectorKind::Deserialization => deserialization::detect(content, file_path),
+            DetectorKind::UnsafeReflection => unsafe_reflection::detect(content, file_path),
+            DetectorKind::PathTraversal => path_traversal::detect(content, file_path),
+            // Patterns ship in the rule bundle
+            DetectorKind::SqlInjection => Ok(Vec::new()),
+            DetectorKind::NoSqlInjection => nosql_injection::detect(content, file_path),
+            DetectorKind::LdapInjection => ldap_injection::detect(content, file_path),
+            DetectorKind::XpathInjection => xpath_injection::detect(content, file_path),
+            DetectorKind::Ssrf => ssrf::detect(content, file_path),
+            DetectorKind::Xxe => xxe::detect(content, file_path),
+            DetectorKind::Ssti => ssti::detect(content, file_path),
+            DetectorKind::OpenRedirect => open_redirect::detect(content, file_path),
+            DetectorKind::HeaderInjection => header_injection::detect(content, file_path),
+            DetectorKind::PrototypePollution => prototype_pollution::detect(content, file_path),
+            DetectorKind::Redos => redos::detect(content, file_path),
+            DetectorKind::ZipSlip => zip_slip::detect(content, file_path),
+            DetectorKind::UnsafeRust => unsafe_rust::detect(content, file_path),
+            DetectorKind::FilePermissions => file_permissions::detect(content, file_path),
+            DetectorKind::InsecureTransport => insecure_transport::detect(content, file_path),
+            DetectorKind::Egress => egress::detect(content, file_path),
+            DetectorKind::EnvExfiltration => env_exfiltration::detect(content, file_path),
+            DetectorKind::Obfuscation => obfuscation::detect(content, file_path),
+            // A no-op on anything but Dockerfiles
+            DetectorKind::Dockerfile => dockerfile::detect(content, file_path),
+            // A no-op on anything but shell scripts
+            DetectorKind::ShellScript => shell_script::detect(content, file_path),
+            // A no-op on anything but k8s and compose YAML
+            DetectorKind::Manifests => manifests::detect(content, file_path),
+            // A no-op on anything but Terraform and CloudFormation
+            DetectorKind::Iac => iac::detect(content, file_path),
+            // Missing lockfiles are reported by `scan_lockfiles`
+            DetectorKind::Dependencies => dependencies::detect(content, file_path),
+            // Works on the whole tool set, see `scan_tool_set`
+            DetectorKind::ToxicFlows => Ok(Vec::new()),
+            // Works on the servers of MCP configs, see `scan_server_set`
+            DetectorKind::CrossServer => Ok(Vec::new()),
+            // A no-op unless rules are configured
+            DetectorKind::Yara => match &config.yara {
+                Some(rules) => yara::detect(content, file_path, rules),
+                None => Ok(Vec::new()),
+            },
+            // A no-op unless IOC lists are configured
+            DetectorKind::Ioc => match &config.iocs {
+                Some(iocs) => ioc::detect(content, file_path, iocs),
+                None => Ok(Vec::new()),
+            },
+        }?;
+        vulnerabilities.extend(bundle::active(config).detect(*self, content, file_path));
+        Ok(vulnerabilities)
+    }
+}
+
+/// Run all detectors enabled in `config` on in-memory content
+///
+/// Each detector runs independently and failures in one detector don't
+/// affect others: a failing detector is logged at WARN level and skipped.
+/// Detectors disabled for the file by a path override are skipped. Findings
+/// are tagged with their detector and fully qualified rule ID, severity
+/// overrides and path overrides' severity caps are applied, and findings
+/// below the configured minimum severity or confidence are dropped.
+///
+/// Detectors run in the order listed in `ScanConfig::detectors`. This is
+/// I/O-free, so it is also what the WASM bindings call.
+pub fn scan_content(content: &str, file_path: &str, config: &ScanConfig) -> Vec<Vulnerability> {
+    scan_content_with(content, file_path, config, |_, run| run())
+}
+
+/// [`scan_content`], calling each detector through `around`
+///
+/// `around` gets the detector and a function that runs it, so callers can
+/// wrap individual detectors; the scanner times them this way.
+pub fn scan_content_with<F>(
+    content: &str,
+    file_path: &str,
+    config: &ScanConfig,
+    mut around: F,
+) -> Vec<Vulnerability>
+where
+    F: FnMut(DetectorKind, &dyn Fn() -> Result<Vec<Vulnerability>>) -> Result<Vec<Vulnerability>>,
+{
+    let mut vulnerabilities = Vec::new();
+
+    debug!("Running detectors on {}", file_path);
+
+    let policy = PathPolicy::for_file(&config.path_overrides, file_path);
+    for detector in config.detectors.iter().filter(|d| policy.enables(**d)) {
+        let run = || detector.run(content, file_path, config);
+        match debug_span!("detector", name = detector.id()).in_scope(|| around(*detector, &run)) {
+            Ok(mut vulns) => {
+                tag_findings(*detector, &mut vulns);
+                if !vulns.is_empty() {
+                    debug!(
+                        "{} detector found {} issues in {}",
+                        detector.name(),
+                        vulns.len(),
+                        file_path
+                    );
+                }
+                vulnerabilities.extend(vulns)
+            }
+            Err(e) => warn!(
+                "{} detector failed on {}: {}",
+                detector.name(),
+                file_path,
+                e
+            ),
+        }
+    }
+
+    let overridden =
+        apply_severity_overrides(&config.severity_overrides, file_path, &mut vulnerabilities);
+    policy.cap(&mut vulnerabilities);
+    // `retain` visits findings in order, once each
+    let mut overridden = overridden.into_iter();
+    vulnerabilities.retain(|v| meets_thresholds(v, overridden.next().unwrap_or(false), config));
+
+    vulnerabilities
+}
+
+/// Whether a finding clears `min_severity` and `min_confidence`
+///
+/// A severity set by a severity override counts even below `min_severity`:
+/// an override to `info` marks a finding as informational, not as hidden.
+fn meets_thresholds(vuln: &Vulnerability, overridden: bool, config: &ScanConfig) -> bool {
+    (overridden || vuln.severity >= config.min_severity) && vuln.confidence >= config.min_confidence
+}
+
+/// Run the `toxic_flows` detector on the tools of one server
+///
+/// Tools come from [`toxic_flows::extract_tools`] over every scanned file, or
+/// from a live `tools/list`. Findings are tagged, overridden, and filtered
+/// like those of [`scan_content`]. Returns nothing if the detector is
+/// disabled in `config`.
+pub fn scan_tool_set(
+    tools: &[toxic_flows::ToolProfile],
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    if !config.detectors.contains(&DetectorKind::ToxicFlows) {
+        return Vec::new();
+    }
+
+    finish_server_findings(DetectorKind::ToxicFlows, toxic_flows::detect(tools), config)
+}
+
+/// Run the `cross_server` detector on the servers of MCP configuration files
+///
+/// The caller gathers what is known about each server (tool names,
+/// descriptions, source files). Returns nothing if the detector is disabled
+/// in `config`.
+pub fn scan_server_set(
+    servers: &[cross_server::ServerSurface],
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    if !config.detectors.contains(&DetectorKind::CrossServer) || servers.len() < 2 {
+        return Vec::new();
+    }
+
+    finish_server_findings(
+        DetectorKind::CrossServer,
+        cross_server::detect(servers),
+        config,
+    )
+}
+
+/// Run the lockfile check of the `dependencies` detector on a server's manifests
+///
+/// Whether a lockfile exists is decided by the caller (the scanner checks the
+/// filesystem), which keeps the detector itself I/O-free. Returns nothing if
+/// the detector is disabled in `config`.
+pub fn scan_lockfiles(
+    manifests: &[dependencies::Manifest],
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    if !config.detectors.contains(&DetectorKind::Dependencies) {
+        return Vec::new();
+    }
+
+    finish_server_findings(
+        DetectorKind::Dependencies,
+        dependencies::detect_missing_lockfiles(manifests),
+        config,
+    )
+}
+
+/// Run the definition-level detectors on what a live server exposes
+///
+/// `tools` come from `tools/list`; `listings` are raw `prompts/list`,
+/// `resources/list`, and `resources/templates/list` results. Tool findings
+/// are located at `tools/list#<name>`. Findings are tagged, overridden, and
+/// filtered like those of [`scan_content`].
+pub fn scan_live_surface(
+    tools: &[ToolDefinition],
+    listings: &[serde_json::Value],
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    let enabled = |detector| config.detectors.contains(&detector);
+    let mut vulnerabilities = Vec::new();
+
+    for tool in tools {
+        let source = format!("tools/list#{}", tool.name);
+        let description = tool.description.as_str();
+        let mut passes = Vec::new();
+        if enabled(DetectorKind::ToolPoisoning) {
+            let mut findings = tool_poisoning::detect(description).unwrap_or_default();
+            findings.extend(tool_poisoning::detect_description(description));
+            passes.push((DetectorKind::ToolPoisoning, findings));
+        }
+        if enabled(DetectorKind::PromptInjection) {
+            let findings = prompt_injection::detect(description).unwrap_or_default();
+            passes.push((DetectorKind::PromptInjection, findings));
+        }
+        if enabled(DetectorKind::HiddenUnicode) {
+            let surface = format!("{}\n{}", tool.name, description);
+            let findings = hidden_unicode::detect(&surface, &source).unwrap_or_default();
+            passes.push((DetectorKind::HiddenUnicode, findings));
+        }
+
+        for (detector, mut findings) in passes {
+            for vuln in &mut findings {
+                let line = vuln.location.take().and_then(|l| l.line);
+                let location = Location::new(source.clone());
+                vuln.location = Some(match line {
+                    Some(line) => location.with_line(line),
+                    None => location,
+                });
+            }
+            vulnerabilities.extend(finish_server_findings(detector, findings, config));
+        }
+    }
+
+    if enabled(DetectorKind::PromptsResources) {
+        for listing in listings {
+            vulnerabilities.extend(finish_server_findings(
+                DetectorKind::PromptsResources,
+                prompts_resources::detect_live(listing),
+                config,
+            ));
+        }
+    }
+
+    vulnerabilities
+}
+
+/// Tag, override, and filter findings of a server-level pass like
+/// [`scan_content`] does for per-file findings
+///
+/// Findings in files whose path overrides disable `detector` are dropped.
+fn finish_server_findings(
+    detector: DetectorKind,
+    mut vulnerabilities: Vec<Vulnerability>,
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    tag_findings(detector, &mut vulnerabilities);
+    vulnerabilities.retain_mut(|vuln| {
+        let file = vuln
+            .location
+            .as_ref()
+            .map(|l| l.file.clone())
+            .unwrap_or_default();
+        let overridden = apply_severity_overrides(
+            &config.severity_overrides,
+            &file,
+            std::slice::from_mut(vuln),
+        );
+        let policy = PathPolicy::for_file(&config.path_overrides, &file);
+        policy.cap(std::slice::from_mut(vuln));
+        policy.enables(detector) && meets_thresholds(vuln, overridden[0], config)
+    });
+    vulnerabilities
+}
+
+/// Stamp findings with their detector and fully qualified rule ID
+fn tag_findings(detector: DetectorKind, vulnerabilities: &mut [Vulnerability]) {
+    for vuln in vulnerabilities {
+        vuln.detector = Some(detector);
+        vuln.rule_id = Some(match vuln.rule_id.take() {
+            Some(rule) => format!("{}/{}", detector.id(), rule),
+            None => detector.id().to_string(),
+        });
+    }
+}
+
+/// Location of a match on line `line_num` (0-based) of `file_path`
+///
+/// `range` is the match's byte range in `line`, as returned by
+/// `Regex::find_iter`. Columns are 1-based and count characters, so they
+/// line up in editors for non-ASCII source too.
+pub fn match_location(
+    file_path: &str,
+    line_num: usize,
+    line: &str,
+    range: Range<usize>,
+) -> Location {
+    let column = line[..range.start].chars().count() + 1;
+    Location::new(file_path)
+        .with_line(line_num + 1)
+        .with_column(column)
+        .with_end_column(column + line[range].chars().count())
+}
+
+/// Turn a pattern name into a rule ID segment
+///
+/// `"Python shelve usage"` becomes `"python-shelve-usage"`.
+pub fn rule_slug(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+impl std::fmt::Display for DetectorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl std::str::FromStr for DetectorKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized = s.trim().to_ascii_lowercase().replace('-', "_");
+        DetectorKind::ALL
+            .iter()
+            .copied()
+            .find(|d| d.id() == normalized)
+            .ok_or_else(|| anyhow::anyhow!("Unknown detector '{}'", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detector_kind_roundtrip() {
+        for detector in DetectorKind::ALL {
+            assert_eq!(detector.id().parse::<DetectorKind>().unwrap(), *detector);
+        }
+        assert_eq!(
+            "sql-injection".parse::<DetectorKind>().unwrap(),
+            DetectorKind::SqlInjection
+        );
+        assert!("nonexistent".parse::<DetectorKind>().is_err());
+    }
+
+    #[test]
+    fn test_rule_slug() {
+        assert_eq!(rule_slug("Python shelve usage"), "python-shelve-usage");
+        assert_eq!(rule_slug("Python pickle.loads()"), "python-pickle-loads");
+        assert_eq!(
+            rule_slug("Python yaml.load() without SafeLoader"),
+            "python-yaml-load-without-safeloader"
+        );
+    }
+
+    #[test]
+    fn test_match_location() {
+        let line = "é = eval(x)";
+        let start = line.find("eval").unwrap();
+        let location = match_location("a.py", 2, line, start..start + 5);
+        assert_eq!(location.line, Some(3));
+        assert_eq!((location.column, location.end_column), (Some(5), Some(10)));
+    }
+}
@@ -0,0 +1,192 @@
+//! NoSQL injection detection - CWE-943
+//!
+//! Document databases do not parse SQL, but they are still injectable:
+//! - MongoDB `$where` (and `db.eval`) executes JavaScript on the server
+//! - a JSON body passed as a filter lets the caller supply operators, so
+//!   `{"password": {"$ne": ""}}` matches every user
+//! - query documents or Lucene query strings assembled with string
+//!   interpolation can be broken out of like SQL
+//!
+//! Patterns cover the MongoDB drivers and Mongoose in JavaScript/TypeScript
+//! and PyMongo, plus Elasticsearch query strings.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct NoSqlPattern {
+    name: &'static str,
+    regex: Regex,
+    severity: Severity,
+    remediation: &'static str,
+}
+
+/// Collection methods that take a filter document as their first argument
+const FILTER_METHOD: &str = r#"\.(?:find|find_one|findOne|findOneAndUpdate|find_one_and_update|findOneAndDelete|find_one_and_delete|updateOne|update_one|updateMany|update_many|deleteOne|delete_one|deleteMany|delete_many|countDocuments|count_documents|aggregate|where)\s*\(\s*"#;
+
+/// Request data that JSON or query-string parsing can turn into an object
+const REQUEST_OBJECT: &str =
+    r#"(?:req|request|ctx\.request)\.(?:body|query|params|json|args|form|get_json\s*\(\s*\))"#;
+
+static NOSQL_PATTERNS: Lazy<Vec<NoSqlPattern>> = Lazy::new(|| {
+    vec![
+        NoSqlPattern {
+            name: "MongoDB $where with dynamic JavaScript",
+            regex: Regex::new(
+                r#"(?:["']\$where["']|\$where)\s*:\s*(?:f["']|`[^`]*\$\{|["'][^"']*["']\s*\+|[A-Za-z_][\w.]*\s*(?:[,}+]|$))|\bdb\.eval\s*\("#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            remediation: "Replace $where with standard query operators (or $expr); MongoDB \
+                          can disable server-side JavaScript with security.javascriptEnabled: false",
+        },
+        NoSqlPattern {
+            name: "Request object used as MongoDB filter",
+            regex: Regex::new(&format!(
+                r#"{}(?:\{{[^}}]*(?:\.\.\.|\*\*)\s*)?{}\s*[,)}}]"#,
+                FILTER_METHOD, REQUEST_OBJECT
+            ))
+            .unwrap(),
+            severity: Severity::High,
+            remediation: "Build the filter from individually validated fields instead of \
+                          passing or spreading the request body; strip keys starting with '$' \
+                          (mongo-sanitize) or use a schema validator",
+        },
+        // Route params and form fields are always strings; bodies and
+        // query strings can carry objects like {"$ne": ""}
+        NoSqlPattern {
+            name: "Request field used as MongoDB query value",
+            regex: Regex::new(&format!(
+                r#"{}\{{[^}}]*:\s*(?:(?:req|request|ctx\.request)\.(?:body|query)(?:\.\w+|\[["']\w+["']\])|request\.(?:json|get_json\s*\(\s*\))(?:\[["']\w+["']\]|\.get\s*\([^)]*\)))\s*[,}}]"#,
+                FILTER_METHOD
+            ))
+            .unwrap(),
+            severity: Severity::Medium,
+            remediation: "Cast query values to the expected type (String(value), str(value)) \
+                          or validate them with a schema so operator objects are rejected",
+        },
+        NoSqlPattern {
+            name: "Query document built by string interpolation",
+            regex: Regex::new(
+                r#"\b(?:json\.loads|JSON\.parse)\s*\(\s*(?:f["']|`|["'][^"']*["']\s*\+)[^\n]*(?:\$(?:where|regex|ne|eq|gt|gte|lt|lte|in|nin|or|and|expr)\b|\\?["'](?:query|match|term|bool|filter)\\?["']\s*:)"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            remediation: "Construct query documents as objects/dicts and insert user values as \
+                          field values, never by formatting JSON text",
+        },
+        NoSqlPattern {
+            name: "Elasticsearch query string built from input",
+            regex: Regex::new(
+                r#"["']?query_string["']?\s*:\s*\{[^}]*["']?query["']?\s*:\s*(?:f["']|`[^`]*\$\{|["'][^"']*["']\s*\+|[A-Za-z_][\w.\[\]'"]*\s*[,}])|\.search\s*\([^)]*\bq\s*=\s*(?:f["']|["'][^"']*["']\s*[+%])"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            remediation: "Use match/term queries with the user value as a field value, or \
+                          simple_query_string with escaped input, instead of Lucene query_string",
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = NOSQL_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert(
+                "language".to_string(),
+                serde_json::json!("JavaScript/TypeScript, Python"),
+            );
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-943"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("NOSQL-{:03}", id_counter),
+                    VulnerabilityType::NoSqlInjection,
+                    pattern.severity,
+                    format!("{} Detected", pattern.name),
+                    "User input can change the structure of a NoSQL query rather than \
+                     only its values",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Authentication bypass, reading or modifying other users' documents, \
+                     and server-side JavaScript execution",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_mongo_injection() {
+        let content = r#"
+const docs = await users.find({ $where: `this.name == '${name}'` }).toArray();
+const user = await User.findOne(req.body);
+const hits = await users.find({ ...req.query, active: true });
+const user = await User.findOne({ username: req.body.username, password: req.body.password });
+user = db.users.find_one({"email": request.json["email"]})
+"#;
+        let vulns = detect(content, "server.js").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "mongodb-where-with-dynamic-javascript",
+                "request-object-used-as-mongodb-filter",
+                "request-object-used-as-mongodb-filter",
+                "request-field-used-as-mongodb-query-value",
+                "request-field-used-as-mongodb-query-value",
+            ]
+        );
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::NoSqlInjection);
+        assert_eq!(vulns[0].severity, Severity::Critical);
+    }
+
+    #[test]
+    fn test_detect_string_built_queries() {
+        let content = r#"
+query = json.loads(f'{{"name": {{"$regex": "{term}"}}}}')
+body = {"query": {"query_string": {"query": "title:" + term}}}
+es.search(index="docs", q=f"author:{author}")
+"#;
+        let vulns = detect(content, "search.py").unwrap();
+        assert_eq!(vulns.len(), 3);
+    }
+
+    #[test]
+    fn test_safe_queries_not_flagged() {
+        let content = r#"
+const user = await User.findOne({ username: String(req.body.username) });
+const doc = await users.findOne({ _id: req.params.id });
+user = db.users.find_one({"email": str(request.json["email"])})
+body = {"query": {"match": {"title": term}}}
+"#;
+        assert!(detect(content, "server.js").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+//! Obfuscation and encoded payload detection - CWE-506
+//!
+//! Malicious MCP servers rarely ship their payload in plain sight. The usual
+//! trick is an encoding pipeline that only turns into code at runtime:
+//! - a base64/hex/zlib blob decoded straight into `exec`, `eval`, or
+//!   `new Function` (Python, JavaScript, PHP)
+//! - large encoded string literals handed to a decoder
+//! - strings assembled from character codes (`String.fromCharCode` chains,
+//!   `chr(..) + chr(..)`) or long runs of `\xNN` escapes
+//!
+//! C and C++ files are skipped: byte tables and escaped buffers are normal
+//! there.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct ObfuscationPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    severity: Severity,
+    confidence: f32,
+}
+
+static OBFUSCATION_PATTERNS: Lazy<Vec<ObfuscationPattern>> = Lazy::new(|| {
+    vec![
+        ObfuscationPattern {
+            name: "Decoded payload executed",
+            language: "Python",
+            regex: Regex::new(
+                r#"\b(?:exec|eval)\s*\(\s*(?:compile\s*\(\s*)?(?:base64\.(?:b64decode|b32decode|b85decode|decodebytes|urlsafe_b64decode)|codecs\.decode|zlib\.decompress|marshal\.loads|bytes\.fromhex|binascii\.(?:unhexlify|a2b_base64))\s*\("#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            confidence: 0.85,
+        },
+        ObfuscationPattern {
+            name: "Decoded payload executed",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(
+                r#"\b(?:eval|Function|setTimeout|setInterval|runInThisContext|runInNewContext)\s*\(\s*(?:atob\s*\(|Buffer\.from\s*\([^)]*,\s*["'](?:base64|hex)["']\s*\)|unescape\s*\(|decodeURIComponent\s*\(|String\.fromCharCode\s*\()"#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            confidence: 0.85,
+        },
+        ObfuscationPattern {
+            name: "Decoded payload executed",
+            language: "PHP",
+            regex: Regex::new(
+                r#"\b(?:eval|assert|create_function)\s*\(\s*(?:base64_decode|gzinflate|gzuncompress|gzdecode|str_rot13|hex2bin|convert_uudecode)\s*\("#,
+            )
+            .unwrap(),
+            severity: Severity::Critical,
+            confidence: 0.85,
+        },
+        ObfuscationPattern {
+            name: "Large encoded blob decoded at runtime",
+            language: "Python/JavaScript/PHP",
+            regex: Regex::new(
+                r#"\b(?:b64decode|decodebytes|atob|Buffer\.from|base64_decode|unhexlify|fromhex)\s*\(\s*b?["'`][A-Za-z0-9+/=_\-]{200,}["'`]"#,
+            )
+            .unwrap(),
+            severity: Severity::High,
+            confidence: 0.75,
+        },
+        ObfuscationPattern {
+            name: "String assembled from character codes",
+            language: "Python/JavaScript",
+            regex: Regex::new(
+                r#"\bString\.fromCharCode\s*\(\s*(?:(?:0x)?[0-9a-fA-F]+\s*,\s*){8,}|(?:\bString\.fromCharCode\s*\([^)]*\)\s*\+\s*){3,}|(?:\bchr\s*\(\s*\d+\s*\)\s*\+\s*){4,}"#,
+            )
+            .unwrap(),
+            severity: Severity::Medium,
+            confidence: 0.6,
+        },
+        ObfuscationPattern {
+            name: "Long hex-escaped string",
+            language: "Python/JavaScript/PHP",
+            regex: Regex::new(r#"(?:\\x[0-9a-fA-F]{2}){32,}"#).unwrap(),
+            severity: Severity::Medium,
+            confidence: 0.5,
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let lower = file_path.to_ascii_lowercase();
+    if [".c", ".cc", ".cpp", ".h"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = OBFUSCATION_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-506"));
+
+            // Blobs make for unreadable snippets
+            let snippet: String = line.chars().take(200).collect();
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("OBF-{:03}", id_counter),
+                    VulnerabilityType::MaliciousPayload,
+                    pattern.severity,
+                    format!("Obfuscated Code: {}", pattern.name),
+                    "Code is hidden behind an encoding step and only becomes readable at runtime, \
+                     a common way to slip malicious payloads past review",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "The decoded code runs with the server's privileges and can steal credentials, \
+                     open a reverse shell, or tamper with tool results",
+                )
+                .with_remediation(
+                    "Decode the payload offline and review it; legitimate code has no reason to \
+                     execute encoded strings. Remove the package if its origin is unknown.",
+                )
+                .with_code_snippet(snippet)
+                .with_confidence(pattern.confidence)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_decode_and_execute() {
+        let content = r#"
+exec(base64.b64decode(PAYLOAD))
+eval(atob(encoded));
+new Function(Buffer.from(blob, 'base64').toString())();
+<?php eval(gzinflate(base64_decode($p))); ?>
+"#;
+        let vulns = detect(content, "index.js").unwrap();
+        assert_eq!(vulns.len(), 4);
+        assert!(vulns.iter().all(|v| v.severity == Severity::Critical));
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::MaliciousPayload);
+    }
+
+    #[test]
+    fn test_detect_blobs_and_char_codes() {
+        let blob = "QUFB".repeat(60);
+        let content = format!(
+            "data = base64.b64decode(\"{}\")\n\
+             var s = String.fromCharCode(114, 101, 113, 117, 105, 114, 101, 40, 39);\n\
+             cmd = chr(111) + chr(115) + chr(46) + chr(115) + chr(121)\n\
+             sc = \"{}\"\n",
+            blob,
+            "\\x90".repeat(40)
+        );
+        let vulns = detect(&content, "loader.py").unwrap();
+        let rules: Vec<_> = vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect();
+        assert_eq!(
+            rules,
+            vec![
+                "large-encoded-blob-decoded-at-runtime",
+                "string-assembled-from-character-codes",
+                "string-assembled-from-character-codes",
+                "long-hex-escaped-string",
+            ]
+        );
+        assert!(detect(&content, "tables.c").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_plain_decoding_not_flagged() {
+        let content = r#"
+token = base64.b64decode(header.split(" ")[1])
+const json = JSON.parse(atob(payload));
+const sep = String.fromCharCode(10);
+"#;
+        assert!(detect(content, "auth.py").unwrap().is_empty());
+    }
+}
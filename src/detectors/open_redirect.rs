@@ -0,0 +1,134 @@
+//! Open redirect detection - CWE-601
+//!
+//! Many MCP servers embed a small web callback for OAuth flows. Redirecting to
+//! a URL taken straight from the request (`?next=`, `?redirect_uri=`) lets an
+//! attacker bounce users - or authorization codes - to a site they control.
+//!
+//! Patterns flag redirect calls whose target is read directly from request
+//! parameters in Flask, Django, FastAPI/Starlette, and Express.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct RedirectPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+}
+
+static REDIRECT_PATTERNS: Lazy<Vec<RedirectPattern>> = Lazy::new(|| {
+    vec![
+        RedirectPattern {
+            name: "Flask redirect to request parameter",
+            language: "Python",
+            regex: Regex::new(r#"\bredirect\s*\(\s*request\.(?:args|form|values)\b"#).unwrap(),
+        },
+        RedirectPattern {
+            name: "Django redirect to request parameter",
+            language: "Python",
+            regex: Regex::new(
+                r#"\b(?:HttpResponseRedirect|redirect)\s*\(\s*request\.(?:GET|POST)\b"#,
+            )
+            .unwrap(),
+        },
+        RedirectPattern {
+            name: "Starlette RedirectResponse to request parameter",
+            language: "Python",
+            regex: Regex::new(r#"\bRedirectResponse\s*\(\s*(?:url\s*=\s*)?request\.query_params\b"#)
+                .unwrap(),
+        },
+        // The optional leading status code covers `res.redirect(302, url)`
+        RedirectPattern {
+            name: "Express redirect to request parameter",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(
+                r#"\b(?:res|response)\.redirect\s*\(\s*(?:\d{3}\s*,\s*)?(?:req|request)\.(?:query|body|params)\b"#,
+            )
+            .unwrap(),
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = REDIRECT_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-601"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("REDIR-{:03}", id_counter),
+                    VulnerabilityType::OpenRedirect,
+                    Severity::Medium,
+                    format!("{} Detected", pattern.name),
+                    "Redirect target is taken directly from request parameters without \
+                     validation"
+                        .to_string(),
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Attackers can send users to phishing sites, or leak OAuth \
+                     authorization codes and tokens to a domain they control",
+                )
+                .with_remediation(
+                    "Redirect only to relative paths or to hosts on an explicit allowlist \
+                     (e.g. Django's url_has_allowed_host_and_scheme); for OAuth, compare \
+                     redirect_uri against the registered values exactly",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.8)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_python_open_redirect() {
+        let content = r#"
+return redirect(request.args.get("next"))
+return HttpResponseRedirect(request.GET["redirect_uri"])
+return RedirectResponse(url=request.query_params["next"])
+"#;
+        let vulns = detect(content, "callback.py").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::OpenRedirect);
+    }
+
+    #[test]
+    fn test_detect_express_open_redirect() {
+        let content = "res.redirect(req.query.next);\nres.redirect(302, req.body.returnTo);";
+        let vulns = detect(content, "server.js").unwrap();
+        assert_eq!(vulns.len(), 2);
+    }
+
+    #[test]
+    fn test_fixed_redirects_not_flagged() {
+        let content = r#"
+return redirect(url_for("index"))
+res.redirect("/login");
+"#;
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+}
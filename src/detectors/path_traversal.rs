@@ -4,13 +4,15 @@ use anyhow::Result;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
 
 static PATH_TRAVERSAL_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
     vec![
-        Regex::new(r#"\.\./"#).unwrap(),
-        Regex::new(r#"\.\.\\"#).unwrap(),
-        Regex::new(r#"%2e%2e/"#).unwrap(),
+        Regex::new(r#"(?:\.\./)+"#).unwrap(),
+        // Windows separators, also escaped in string literals (`"..\\..\\"`)
+        Regex::new(r#"(?:\.\.\\{1,2})+"#).unwrap(),
+        // URL-encoded dots or separators (`%2e%2e/`, `..%5c`)
+        Regex::new(r#"(?i)(?:(?:%2e|\.){2}(?:%2f|%5c|/|\\))+"#).unwrap(),
         Regex::new(r#"\.\.\.\.//\.\.\.\./"#).unwrap(),
         Regex::new(r#"open\s*\([^)]*\+[^)]*\)"#).unwrap(), // open() with concatenation
     ]
@@ -21,27 +23,55 @@ pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
     let mut id_counter = 1;
 
     for (line_num, line) in content.lines().enumerate() {
-        for pattern in PATH_TRAVERSAL_PATTERNS.iter() {
-            if pattern.is_match(line) {
-                let vuln = Vulnerability::new(
-                    format!("PATH-TRAV-{:03}", id_counter),
-                    VulnerabilityType::PathTraversal,
-                    Severity::High,
-                    "Path Traversal Pattern Detected",
-                    "Potential directory traversal vulnerability detected",
-                )
-                .with_location(Location::new(file_path).with_line(line_num + 1))
-                .with_impact("Attackers can access files outside intended directory")
-                .with_remediation("Validate and sanitize file paths, use os.path.abspath(), check path prefix")
-                .with_code_snippet(line.to_string())
-                .with_confidence(0.75);
-
-                vulnerabilities.push(vuln);
-                id_counter += 1;
-                break;
-            }
+        // Every occurrence of the first pattern that matches the line
+        let Some(pattern) = PATH_TRAVERSAL_PATTERNS.iter().find(|p| p.is_match(line)) else {
+            continue;
+        };
+        for m in pattern.find_iter(line) {
+            let vuln = Vulnerability::new(
+                format!("PATH-TRAV-{:03}", id_counter),
+                VulnerabilityType::PathTraversal,
+                Severity::High,
+                "Path Traversal Pattern Detected",
+                "Potential directory traversal vulnerability detected",
+            )
+            .with_location(super::match_location(file_path, line_num, line, m.range()))
+            .with_impact("Attackers can access files outside intended directory")
+            .with_remediation(
+                "Validate and sanitize file paths, use os.path.abspath(), check path prefix",
+            )
+            .with_code_snippet(line.to_string())
+            .with_confidence(0.75);
+
+            vulnerabilities.push(vuln);
+            id_counter += 1;
         }
     }
 
     Ok(vulnerabilities)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(content: &str) -> Vec<(usize, usize)> {
+        detect(content, "files.py")
+            .unwrap()
+            .iter()
+            .map(|v| {
+                let location = v.location.as_ref().unwrap();
+                (location.column.unwrap(), location.end_column.unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_windows_separators() {
+        assert_eq!(spans(r#"path = "..\\..\\windows\\win.ini""#), vec![(9, 17)]);
+        assert_eq!(spans(r"type ..\..\boot.ini"), vec![(6, 12)]);
+        assert_eq!(spans("GET /files/..%5C..%5Cconfig"), vec![(12, 22)]);
+        assert_eq!(spans("GET /files/%2E%2E%2Fetc/passwd"), vec![(12, 21)]);
+        assert_eq!(spans("../../etc/passwd").len(), 1);
+    }
+}
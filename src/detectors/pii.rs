@@ -0,0 +1,186 @@
+//! Personal data detection
+//!
+//! Finds personal data in text: email addresses, US Social Security
+//! numbers, payment card numbers (Luhn-checked), and IBANs (checksum
+//! verified). Checksums keep order numbers and random digit runs out.
+//!
+//! Source code is full of example addresses and test card numbers, so this
+//! does not run as a scan detector. The proxy's DLP uses it on the data
+//! flowing through tool results and sampling requests.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+/// Kind of personal data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiKind {
+    Email,
+    Ssn,
+    PaymentCard,
+    Iban,
+}
+
+impl PiiKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "Email Address",
+            PiiKind::Ssn => "US Social Security Number",
+            PiiKind::PaymentCard => "Payment Card Number",
+            PiiKind::Iban => "IBAN",
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            PiiKind::Email => Severity::Low,
+            PiiKind::Ssn | PiiKind::PaymentCard => Severity::High,
+            PiiKind::Iban => Severity::Medium,
+        }
+    }
+}
+
+static PATTERNS: Lazy<Vec<(PiiKind, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            PiiKind::Email,
+            Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b")
+                .unwrap(),
+        ),
+        (
+            PiiKind::Ssn,
+            Regex::new(r"\b(?:00[1-9]|0[1-9]\d|[1-578]\d\d|6[0-57-9]\d|66[0-57-9])-(?:0[1-9]|[1-9]\d)-(?:000[1-9]|00[1-9]\d|0[1-9]\d\d|[1-9]\d{3})\b").unwrap(),
+        ),
+        (
+            PiiKind::PaymentCard,
+            Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").unwrap(),
+        ),
+        (
+            PiiKind::Iban,
+            Regex::new(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b").unwrap(),
+        ),
+    ]
+});
+
+/// Domains reserved for documentation; addresses there are never real
+const EXAMPLE_DOMAINS: &[&str] = &["example.com", "example.org", "example.net", "test"];
+
+/// Personal data in `content`
+pub fn detect(content: &str, location: &str) -> Vec<Vulnerability> {
+    let mut vulnerabilities = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        for (kind, pattern) in PATTERNS.iter() {
+            for m in pattern.find_iter(line) {
+                if !confirmed(*kind, m.as_str()) {
+                    continue;
+                }
+                vulnerabilities.push(
+                    Vulnerability::new(
+                        format!("PII-{:03}", vulnerabilities.len() + 1),
+                        VulnerabilityType::PiiExposure,
+                        kind.severity(),
+                        format!("{} Found", kind.label()),
+                        format!("Text contains a {}", kind.label().to_lowercase()),
+                    )
+                    .with_rule_id(super::rule_slug(kind.label()))
+                    .with_location(super::match_location(location, line_num, line, m.range()))
+                    .with_code_snippet(mask(m.as_str()))
+                    .with_confidence(0.8),
+                );
+            }
+        }
+    }
+    vulnerabilities
+}
+
+fn confirmed(kind: PiiKind, candidate: &str) -> bool {
+    match kind {
+        PiiKind::Email => {
+            let domain = candidate.rsplit('@').next().unwrap_or_default();
+            let domain = domain.to_ascii_lowercase();
+            !EXAMPLE_DOMAINS
+                .iter()
+                .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)))
+        }
+        PiiKind::Ssn => true,
+        PiiKind::PaymentCard => luhn(candidate),
+        PiiKind::Iban => iban_checksum(candidate),
+    }
+}
+
+/// Luhn check of a 13-19 digit number
+fn luhn(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) || digits.iter().all(|d| *d == digits[0]) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| match (i % 2, d * 2) {
+            (1, doubled) if doubled > 9 => doubled - 9,
+            (1, doubled) => doubled,
+            _ => *d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// ISO 13616 mod-97 check
+fn iban_checksum(candidate: &str) -> bool {
+    let compact: String = candidate.chars().filter(|c| !c.is_whitespace()).collect();
+    if !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+    let rearranged = compact[4..].chars().chain(compact[..4].chars());
+    let mut remainder = 0u32;
+    for c in rearranged {
+        let Some(value) = c.to_digit(36) else {
+            return false;
+        };
+        for digit in value.to_string().chars() {
+            remainder = (remainder * 10 + digit.to_digit(10).unwrap_or(0)) % 97;
+        }
+    }
+    remainder == 1
+}
+
+/// The last four characters, everything else masked
+fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let keep = chars.len().min(4);
+    let masked = chars.len() - keep;
+    "*".repeat(masked) + &chars[masked..].iter().collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(content: &str) -> Vec<String> {
+        detect(content, "tools/call")
+            .into_iter()
+            .filter_map(|v| v.rule_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_pii() {
+        let content = "Customer: jane.doe@acme-corp.io, SSN 123-45-6789\n\
+                       Card on file: 4111 1111 1111 1111 (order 1234567890123)\n\
+                       Refund to DE89 3704 0044 0532 0130 00; docs: user@example.com";
+        assert_eq!(
+            kinds(content),
+            vec![
+                "email-address",
+                "us-social-security-number",
+                "payment-card-number",
+                "iban",
+            ]
+        );
+        assert_eq!(mask("4111111111111111"), "************1111");
+        assert!(kinds("SSN 000-12-3456, build 2024-01-1234").is_empty());
+    }
+}
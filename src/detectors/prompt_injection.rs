@@ -4,7 +4,7 @@ use anyhow::Result;
 use regex::Regex;
 use once_cell::sync::Lazy;
 
-use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
 
 /// Prompt injection patterns
 static INJECTION_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
@@ -23,7 +23,7 @@ pub fn detect(content: &str) -> Result<Vec<Vulnerability>> {
 
     for (line_num, line) in content.lines().enumerate() {
         for pattern in INJECTION_PATTERNS.iter() {
-            if pattern.is_match(line) {
+            for m in pattern.find_iter(line) {
                 vulnerabilities.push(
                     Vulnerability::new(
                         format!("INJECT-{:03}", id_counter),
@@ -32,7 +32,7 @@ pub fn detect(content: &str) -> Result<Vec<Vulnerability>> {
                         "Prompt Injection Detected",
                         "Content contains potential prompt injection patterns",
                     )
-                    .with_location(Location::new("content").with_line(line_num + 1))
+                    .with_location(super::match_location("content", line_num, line, m.range()))
                     .with_impact("May manipulate LLM to bypass safety measures")
                     .with_remediation("Remove prompt manipulation instructions")
                     .with_code_snippet(line.to_string())
@@ -0,0 +1,153 @@
+//! Prototype pollution detection for JavaScript/TypeScript - CWE-1321
+//!
+//! Writing attacker-controlled keys into objects lets a payload such as
+//! `{"__proto__": {"isAdmin": true}}` add properties to `Object.prototype`,
+//! and with it to every object in the server process. In MCP servers the
+//! payload usually arrives as tool arguments or an HTTP body.
+//!
+//! Patterns flag direct `__proto__` / `constructor.prototype` writes and deep
+//! merge or path-set helpers fed straight from request data or `JSON.parse`.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct PollutionPattern {
+    name: &'static str,
+    regex: Regex,
+    severity: Severity,
+}
+
+/// Request data or freshly parsed JSON, i.e. attacker-controlled objects
+const UNTRUSTED_OBJECT: &str = r#"(?:req|request|ctx\.request)\.(?:body|query|params)|JSON\.parse\s*\(|\bargs\b|\barguments\b|\binput\b"#;
+
+static POLLUTION_PATTERNS: Lazy<Vec<PollutionPattern>> = Lazy::new(|| {
+    let merge_sink = |sink: &str| {
+        Regex::new(&format!(
+            r#"{}\s*\((?:[^()]|\([^()]*\))*(?:{})"#,
+            sink, UNTRUSTED_OBJECT
+        ))
+        .unwrap()
+    };
+    vec![
+        PollutionPattern {
+            name: "__proto__ property write",
+            regex: Regex::new(r#"(?:\[\s*["'`]__proto__["'`]\s*\]|\.__proto__)(?:\s*\.\s*\w+|\s*\[[^\]]+\])*\s*=[^=]"#)
+                .unwrap(),
+            severity: Severity::High,
+        },
+        PollutionPattern {
+            name: "constructor.prototype property write",
+            regex: Regex::new(r#"(?:\.constructor\s*\.\s*prototype|\[\s*["'`]constructor["'`]\s*\]\s*\[\s*["'`]prototype["'`]\s*\])(?:\s*\.\s*\w+|\s*\[[^\]]+\])+\s*=[^=]"#)
+                .unwrap(),
+            severity: Severity::High,
+        },
+        PollutionPattern {
+            name: "Deep merge of untrusted object",
+            regex: merge_sink(
+                r#"(?:(?:\b_|\blodash|\$|\bjQuery)\.(?:merge|mergeWith|defaultsDeep|extend)|\bdeepmerge|\bdeepMerge|\bmergeDeep|\bmerge\.recursive|\bextend)"#,
+            ),
+            severity: Severity::High,
+        },
+        PollutionPattern {
+            name: "Path-based set with untrusted path",
+            regex: merge_sink(
+                r#"\b(?:(?:_|lodash)\.(?:set|setWith|zipObjectDeep)|dot\.set|setValue|dotProp\.set|objectPath\.set)"#,
+            ),
+            severity: Severity::High,
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = POLLUTION_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert(
+                "language".to_string(),
+                serde_json::json!("JavaScript/TypeScript"),
+            );
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-1321"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("PROTO-{:03}", id_counter),
+                    VulnerabilityType::PrototypePollution,
+                    pattern.severity,
+                    format!("{} Detected", pattern.name),
+                    "Attacker-controlled keys can reach Object.prototype, adding \
+                     properties to every object in the process"
+                        .to_string(),
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Polluted prototypes can bypass authorization checks, change \
+                     configuration defaults, or lead to code execution through gadgets",
+                )
+                .with_remediation(
+                    "Reject __proto__, constructor, and prototype keys, merge into \
+                     Object.create(null) objects or Maps, validate input with a schema, \
+                     and keep lodash and merge libraries patched",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_prototype_writes() {
+        let content = r#"
+obj["__proto__"][key] = value;
+target.__proto__.isAdmin = true;
+config.constructor.prototype.polluted = args.value;
+"#;
+        let vulns = detect(content, "server.js").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::PrototypePollution);
+    }
+
+    #[test]
+    fn test_detect_unsafe_merges() {
+        let content = r#"
+const settings = _.merge({}, defaults, req.body);
+const merged = deepmerge(config, JSON.parse(raw));
+_.set(store, req.query.path, req.query.value);
+$.extend(true, options, request.body);
+"#;
+        let vulns = detect(content, "server.ts").unwrap();
+        assert_eq!(vulns.len(), 4);
+    }
+
+    #[test]
+    fn test_safe_code_not_flagged() {
+        let content = r#"
+if (key === "__proto__") continue;
+const isPolluted = obj.__proto__ == Object.prototype;
+const settings = _.merge({}, defaults, overrides);
+const copy = Object.assign({}, req.body);
+"#;
+        assert!(detect(content, "server.js").unwrap().is_empty());
+    }
+}
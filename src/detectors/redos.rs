@@ -0,0 +1,456 @@
+//! Regular expression denial of service (ReDoS) detection - CWE-1333
+//!
+//! MCP servers routinely run tool arguments through regexes for validation
+//! and parsing. In backtracking engines (Python `re`, JavaScript, Java, PHP)
+//! a pattern such as `(a+)+$` takes exponential time on a crafted input, so a
+//! single request can pin a CPU core.
+//!
+//! Regex literals are extracted from the source and analysed for the two
+//! classic constructs:
+//! - **nested quantifiers** - a repeated group containing a repeated atom that
+//!   nothing else in the group separates, e.g. `(\w+\s?)*` or `(.*a)+`
+//! - **overlapping alternation** - a repeated group whose alternatives can
+//!   match the same text, e.g. `(\w|\d)+` or `(a|aa)*`
+//!
+//! Go and Rust regexes are linear-time and are not checked.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+/// Where regex literals appear in a language, and whether the captured text
+/// is a plain string literal that needs its escapes collapsed
+struct RegexSource {
+    language: &'static str,
+    regex: Regex,
+    unescape: bool,
+}
+
+const PYTHON_RE_CALL: &str =
+    r#"\bre\.(?:compile|match|fullmatch|search|findall|finditer|sub|subn|split)\(\s*"#;
+
+static REGEX_SOURCES: Lazy<Vec<RegexSource>> = Lazy::new(|| {
+    let source = |language, pattern: &str, unescape| RegexSource {
+        language,
+        regex: Regex::new(pattern).unwrap(),
+        unescape,
+    };
+    vec![
+        source(
+            "Python",
+            &format!(
+                r#"{}(?:[rR][bB]?|[bB][rR])(?:"([^"\n]*)"|'([^'\n]*)')"#,
+                PYTHON_RE_CALL
+            ),
+            false,
+        ),
+        source(
+            "Python",
+            &format!(
+                r#"{}[bBuU]?(?:"((?:\\.|[^"\\\n])*)"|'((?:\\.|[^'\\\n])*)')"#,
+                PYTHON_RE_CALL
+            ),
+            true,
+        ),
+        // Regex literals only follow an operator or keyword, never an
+        // operand, which keeps division and comments out
+        source(
+            "JavaScript/TypeScript",
+            r#"(?:^|[=(,:!&|?;{}\[]|\breturn)\s*/((?:\\.|\[(?:\\.|[^\]\\\n])*\]|[^/*\\\[\n])(?:\\.|\[(?:\\.|[^\]\\\n])*\]|[^/\\\[\n])*)/[dgimsuvy]*"#,
+            false,
+        ),
+        source(
+            "JavaScript/TypeScript",
+            r#"\bRegExp\(\s*(?:"((?:\\.|[^"\\\n])*)"|'((?:\\.|[^'\\\n])*)'|`([^`]*)`)"#,
+            true,
+        ),
+        source(
+            "Java",
+            r#"\bPattern\.(?:compile|matches)\(\s*"((?:\\.|[^"\\\n])*)""#,
+            true,
+        ),
+        source(
+            "PHP",
+            r#"\bpreg_(?:match_all|match|replace_callback|replace|split)\(\s*(?:'((?:\\.|[^'\\\n])*)'|"((?:\\.|[^"\\\n])*)")"#,
+            true,
+        ),
+    ]
+});
+
+/// Characters used to decide whether two atoms can match the same character;
+/// the atoms' own characters are always tried as well
+const SAMPLE_CHARS: &str = "aZ09_ \t\n.-,;:/\\@#\"'<>=&+*()[]{}|!?~é";
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        // Every risky literal of the first language with one on this line
+        let risky = REGEX_SOURCES.iter().find_map(|source| {
+            let literals: Vec<_> = source
+                .regex
+                .captures_iter(line)
+                .filter_map(|captures| {
+                    let literal = captures.iter().skip(1).flatten().next()?;
+                    let pattern = if source.unescape {
+                        unescape(literal.as_str())
+                    } else {
+                        literal.as_str().to_string()
+                    };
+                    let construct = backtracking_risk(&pattern)?;
+                    Some((literal.range(), pattern, construct))
+                })
+                .collect();
+            (!literals.is_empty()).then_some((source.language, literals))
+        });
+        let Some((language, literals)) = risky else {
+            continue;
+        };
+
+        for (range, pattern, construct) in literals {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-1333"));
+            evidence.insert("regex".to_string(), serde_json::json!(pattern.as_str()));
+            evidence.insert("construct".to_string(), serde_json::json!(construct));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("REDOS-{:03}", id_counter),
+                    VulnerabilityType::RegexDos,
+                    Severity::Medium,
+                    format!("{} in Regex (ReDoS)", construct),
+                    format!(
+                        "The regex /{}/ contains a {}, so matching crafted input can take \
+                         exponential time in a backtracking engine",
+                        pattern,
+                        construct.to_lowercase()
+                    ),
+                )
+                .with_rule_id(super::rule_slug(construct))
+                .with_location(super::match_location(file_path, line_num, line, range))
+                .with_impact(
+                    "A single request with a crafted string can block the server's event loop \
+                     or worker thread, denying service to every client",
+                )
+                .with_remediation(
+                    "Rewrite the pattern so each part of the input can be matched only one way \
+                     (e.g. `(\\w+\\s?)*` -> `\\w+(\\s\\w+)*`), cap input length before matching, \
+                     or use a linear-time engine such as RE2",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.7)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+/// Collapse `\\` and escaped quotes in a string literal, keeping regex escapes
+fn unescape(literal: &str) -> String {
+    let mut pattern = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(next @ ('\\' | '"' | '\'' | '`'))) => {
+                pattern.push(next);
+                chars.next();
+            }
+            _ => pattern.push(c),
+        }
+    }
+    pattern
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantifier {
+    One,
+    Optional,
+    Bounded,
+    Unbounded,
+}
+
+/// A single-character atom or a group, with the quantifier applied to it
+struct Atom {
+    text: String,
+    /// The atom that decides what a group can start with
+    head: String,
+    quantifier: Quantifier,
+    repeats_inside: bool,
+    alternatives: Vec<Vec<Atom>>,
+}
+
+impl Atom {
+    fn plain(text: String) -> Self {
+        Atom {
+            head: text.clone(),
+            text,
+            quantifier: Quantifier::One,
+            repeats_inside: false,
+            alternatives: Vec::new(),
+        }
+    }
+
+    fn repeats(&self) -> bool {
+        self.quantifier == Quantifier::Unbounded || self.repeats_inside
+    }
+}
+
+/// A group being parsed
+#[derive(Default)]
+struct Group {
+    start: usize,
+    alternatives: Vec<Vec<Atom>>,
+    current: Vec<Atom>,
+}
+
+/// The construct that makes `pattern` backtrack exponentially, if any
+fn backtracking_risk(pattern: &str) -> Option<&'static str> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let text = |range: std::ops::Range<usize>| chars[range].iter().collect::<String>();
+    let mut stack = vec![Group::default()];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        i += 1;
+        let mut atom = match chars[start] {
+            '\\' => {
+                i = (i + 1).min(chars.len());
+                Atom::plain(text(start..i))
+            }
+            '[' => {
+                if chars.get(i) == Some(&'^') {
+                    i += 1;
+                }
+                if chars.get(i) == Some(&']') {
+                    i += 1;
+                }
+                while i < chars.len() && chars[i] != ']' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(chars.len());
+                Atom::plain(text(start..i))
+            }
+            '(' => {
+                // Skip (?:, (?=, (?!, (?<=, (?<!, (?<name> and (?P<name>
+                if chars.get(i) == Some(&'?') {
+                    i += 1;
+                    if chars.get(i) == Some(&'P') {
+                        i += 1;
+                    }
+                    if chars.get(i) == Some(&'<') && !matches!(chars.get(i + 1), Some('=' | '!')) {
+                        while i < chars.len() && chars[i] != '>' {
+                            i += 1;
+                        }
+                        i += 1;
+                    } else {
+                        while i < chars.len() && matches!(chars[i], ':' | '=' | '!' | '<') {
+                            i += 1;
+                        }
+                    }
+                }
+                stack.push(Group {
+                    start,
+                    ..Group::default()
+                });
+                continue;
+            }
+            '|' => {
+                let group = stack.last_mut().unwrap();
+                group.alternatives.push(std::mem::take(&mut group.current));
+                continue;
+            }
+            ')' if stack.len() > 1 => {
+                let mut group = stack.pop().unwrap();
+                group.alternatives.push(group.current);
+                let head = group
+                    .alternatives
+                    .first()
+                    .and_then(|alternative| alternative.first())
+                    .map(|atom| atom.head.clone())
+                    .unwrap_or_default();
+                let repeats_inside = group.alternatives.iter().flatten().any(Atom::repeats);
+                Atom {
+                    text: text(group.start..i),
+                    head,
+                    quantifier: Quantifier::One,
+                    repeats_inside,
+                    alternatives: group.alternatives,
+                }
+            }
+            c => Atom::plain(c.to_string()),
+        };
+
+        let (repeat, next) = quantifier(&chars, i);
+        atom.quantifier = repeat;
+        i = next;
+        if repeat == Quantifier::Unbounded {
+            if let Some(construct) = repetition_risk(&atom.alternatives) {
+                return Some(construct);
+            }
+        }
+        stack.last_mut().unwrap().current.push(atom);
+    }
+
+    None
+}
+
+/// Parse the quantifier at `i`, returning it and the index after it
+fn quantifier(chars: &[char], mut i: usize) -> (Quantifier, usize) {
+    let quantifier = match chars.get(i) {
+        Some('*' | '+') => Quantifier::Unbounded,
+        Some('?') => Quantifier::Optional,
+        Some('{') => {
+            let close = match chars[i..].iter().position(|&c| c == '}') {
+                Some(offset) => i + offset,
+                None => return (Quantifier::One, i),
+            };
+            let body: String = chars[i + 1..close].iter().collect();
+            let quantifier = match body.split_once(',') {
+                _ if body.is_empty() => return (Quantifier::One, i),
+                Some((min, "")) if min.chars().all(|c| c.is_ascii_digit()) => Quantifier::Unbounded,
+                Some((min, max))
+                    if (min.chars().chain(max.chars())).all(|c| c.is_ascii_digit()) =>
+                {
+                    Quantifier::Bounded
+                }
+                None if body.chars().all(|c| c.is_ascii_digit()) => Quantifier::Bounded,
+                _ => return (Quantifier::One, i),
+            };
+            i = close;
+            quantifier
+        }
+        _ => return (Quantifier::One, i),
+    };
+    i += 1;
+    // Lazy and possessive modifiers
+    if matches!(chars.get(i), Some('?' | '+')) {
+        i += 1;
+    }
+    (quantifier, i)
+}
+
+/// Why repeating a group with these alternatives is ambiguous, if it is
+fn repetition_risk(alternatives: &[Vec<Atom>]) -> Option<&'static str> {
+    // A repeated atom is only safe if something mandatory that it cannot
+    // match marks where each iteration ends, as `\.` does in `(\w+\.)+`
+    for alternative in alternatives {
+        for (index, repeating) in alternative.iter().enumerate() {
+            if !repeating.repeats() {
+                continue;
+            }
+            let separated = alternative.iter().enumerate().any(|(other, atom)| {
+                other != index
+                    && matches!(atom.quantifier, Quantifier::One | Quantifier::Bounded)
+                    && !overlaps(&repeating.head, &atom.head)
+            });
+            if !separated {
+                return Some("Nested quantifier");
+            }
+        }
+    }
+
+    for (index, first) in alternatives.iter().enumerate() {
+        for second in &alternatives[index + 1..] {
+            if ambiguous(first, second) {
+                return Some("Overlapping alternation");
+            }
+        }
+    }
+    None
+}
+
+/// Whether two alternatives can match the same text: identical, or one is a
+/// single atom that overlaps everything in the other (`\w|\d`, `a|aa`)
+fn ambiguous(first: &[Atom], second: &[Atom]) -> bool {
+    if first.is_empty() || second.is_empty() {
+        return false;
+    }
+    let identical = first.len() == second.len()
+        && first
+            .iter()
+            .zip(second)
+            .all(|(a, b)| a.text == b.text && a.quantifier == b.quantifier);
+    let covers = |single: &[Atom], other: &[Atom]| {
+        single.len() == 1
+            && other
+                .iter()
+                .all(|atom| overlaps(&single[0].head, &atom.head))
+    };
+    identical || covers(first, second) || covers(second, first)
+}
+
+/// Whether two atoms can match the same character
+fn overlaps(first: &str, second: &str) -> bool {
+    let single_char = |atom: &str| Regex::new(&format!("^(?:{})$", atom));
+    let (Ok(first_re), Ok(second_re)) = (single_char(first), single_char(second)) else {
+        // Syntax the regex crate does not support (backreferences, ...)
+        return first == second;
+    };
+    SAMPLE_CHARS
+        .chars()
+        .chain(first.chars())
+        .chain(second.chars())
+        .any(|c| {
+            let c = c.to_string();
+            first_re.is_match(&c) && second_re.is_match(&c)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtracking_risk() {
+        assert_eq!(backtracking_risk(r"^(a+)+$"), Some("Nested quantifier"));
+        assert_eq!(backtracking_risk(r"^(\w+\s?)*$"), Some("Nested quantifier"));
+        assert_eq!(backtracking_risk(r"(.*a){2,}"), Some("Nested quantifier"));
+        assert_eq!(
+            backtracking_risk(r"^(\w|\d)+$"),
+            Some("Overlapping alternation")
+        );
+        assert_eq!(
+            backtracking_risk(r"(?:a|aa)*b"),
+            Some("Overlapping alternation")
+        );
+
+        assert_eq!(backtracking_risk(r"^(\w+\.)+com$"), None);
+        assert_eq!(backtracking_risk(r"^(\d+){3}$"), None);
+        assert_eq!(backtracking_risk(r"^(a|b)*$"), None);
+        assert_eq!(backtracking_risk(r"^[a-z]+(-[a-z]+)*$"), None);
+    }
+
+    #[test]
+    fn test_detect_regex_literals() {
+        let content = r#"
+EMAIL = re.compile(r"^([a-zA-Z0-9]+)*@example\.com$")
+const name = /^(\w+\s?)*$/i.test(input);
+Pattern p = Pattern.compile("^(\\d|\\w)+$");
+const ok = /^[a-z0-9_-]{3,16}$/.test(input);
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::RegexDos);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("nested-quantifier"));
+        let evidence = vulns[2].evidence.as_ref().unwrap();
+        assert_eq!(evidence["regex"], serde_json::json!(r"^(\d|\w)+$"));
+    }
+
+    #[test]
+    fn test_comments_and_division_not_flagged() {
+        let content = r#"
+// (a+)+ is a classic ReDoS pattern
+const ratio = total / (count + 1) / 2;
+"#;
+        assert!(detect(content, "server.js").unwrap().is_empty());
+    }
+}
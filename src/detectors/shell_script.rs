@@ -0,0 +1,325 @@
+//! Shell script checks for install scripts and entrypoints
+//!
+//! `install.sh` one-liners and container entrypoints run with the user's
+//! privileges before any MCP client sandboxing applies, which makes them a
+//! prime supply-chain target. These checks only run on files recognised by
+//! [`is_shell_script`] (`.sh`, `.bash`, `.zsh`, or a shell shebang):
+//!
+//! - `curl-pipe-shell` - a downloaded script piped or substituted into a shell
+//! - `eval-remote-content` - `eval` or `source` of downloaded content
+//! - `unguarded-rm-rf` - `rm -rf "$VAR"` where an empty variable turns the
+//!   target into `/` or the working directory
+//! - `hardcoded-credential` - literal credentials assigned to shell variables
+//!
+//! A recursive delete is not reported when the variable is guarded with
+//! `${VAR:?}`, tested with `-z`/`-n` before the delete, or set from
+//! `mktemp`.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+static SHELL_SHEBANG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^#!\s*/(?:usr/)?bin/(?:env\s+)?(?:ba|z|da|k)?sh\b"#).unwrap());
+
+/// `curl ... | sh`, `bash <(curl ...)` and `sh -c "$(curl ...)"`
+static REMOTE_SCRIPT_SHELL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:curl|wget)\b[^|;&]*\|\s*(?:sudo\s+(?:-\w+\s+)*)?(?:ba|z|da|k)?sh\b|\b(?:ba|z)?sh\s+(?:-s\s+)?<\(\s*(?:curl|wget)\b|\b(?:ba|z)?sh\s+-c\s+["']?\$\(\s*(?:curl|wget)\b"#,
+    )
+    .unwrap()
+});
+
+/// `eval "$(curl ...)"` and `source <(curl ...)`
+static REMOTE_SCRIPT_EVAL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\beval\s+["']?(?:\$\(|`)\s*(?:curl|wget)\b|(?:\bsource|(?:^|[;&|])\s*\.)\s+<\(\s*(?:curl|wget)\b"#,
+    )
+    .unwrap()
+});
+
+/// `rm` with its flags, then a target starting with a variable expansion
+static RM_VARIABLE_TARGET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\brm\s+((?:-{1,2}[\w-]+\s+)+)(?:--\s+)?["']?(\$\{?\w+[^\s;&|"']*)"#).unwrap()
+});
+
+static SHELL_ASSIGNMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(?:(?:export|readonly|local|declare(?:\s+-\w+)*)\s+)?([A-Za-z_]\w*)=("[^"]*"|'[^']*'|[^\s;]*)"#,
+    )
+    .unwrap()
+});
+
+/// A shell check and how its findings are reported
+struct Check {
+    rule: &'static str,
+    vuln_type: VulnerabilityType,
+    severity: Severity,
+    cwe: &'static str,
+    description: &'static str,
+    remediation: &'static str,
+}
+
+const CURL_PIPE_SHELL: Check = Check {
+    rule: "curl-pipe-shell",
+    vuln_type: VulnerabilityType::SupplyChainAttack,
+    severity: Severity::High,
+    cwe: "CWE-494",
+    description: "A script downloaded at run time is executed without any integrity check",
+    remediation: "Download the script to a file, verify its checksum or signature, then run \
+                  it; prefer distribution packages or pinned release artifacts",
+};
+
+const EVAL_REMOTE_CONTENT: Check = Check {
+    rule: "eval-remote-content",
+    vuln_type: VulnerabilityType::SupplyChainAttack,
+    severity: Severity::High,
+    cwe: "CWE-94",
+    description: "Content fetched over the network is evaluated in the current shell, with \
+                  access to its variables and credentials",
+    remediation: "Never eval or source downloaded content; ship the logic in the script \
+                  itself or verify a pinned checksum before loading it",
+};
+
+const UNGUARDED_RM_RF: Check = Check {
+    rule: "unguarded-rm-rf",
+    vuln_type: VulnerabilityType::PathTraversal,
+    severity: Severity::Medium,
+    cwe: "CWE-73",
+    description: "A recursive delete targets a variable that is never checked, so an empty or \
+                  unset value deletes from / or the working directory",
+    remediation: "Use ${VAR:?} so the script aborts when the variable is empty, or test it \
+                  with [ -n \"$VAR\" ] before deleting",
+};
+
+const HARDCODED_CREDENTIAL: Check = Check {
+    rule: "hardcoded-credential",
+    vuln_type: VulnerabilityType::HardcodedCredentials,
+    severity: Severity::High,
+    cwe: "CWE-798",
+    description: "A credential is assigned as a literal in a script that is usually committed \
+                  and published alongside the server",
+    remediation: "Read the credential from the environment or a secret manager at run time \
+                  and rotate the exposed value",
+};
+
+/// Whether a file should get shell checks: a `.sh`/`.bash`/`.zsh` extension
+/// or a `#!/bin/sh`-style shebang
+pub fn is_shell_script(file_path: &str, content: &str) -> bool {
+    let name = file_path.to_ascii_lowercase();
+    [".sh", ".bash", ".zsh"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+        || content
+            .lines()
+            .next()
+            .is_some_and(|line| SHELL_SHEBANG.is_match(line))
+}
+
+/// Commands with `\` continuations joined, paired with their first line
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut commands = Vec::new();
+    let mut pending: Option<(usize, String)> = None;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if pending.is_none() && (trimmed.is_empty() || trimmed.starts_with('#')) {
+            continue;
+        }
+
+        let (start, mut text) = pending.take().unwrap_or((line_num + 1, String::new()));
+        match trimmed.strip_suffix('\\') {
+            Some(continued) => {
+                text.push_str(continued);
+                text.push(' ');
+                pending = Some((start, text));
+            }
+            None => {
+                text.push_str(trimmed);
+                commands.push((start, text));
+            }
+        }
+    }
+    commands.extend(pending);
+
+    commands
+}
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    if !is_shell_script(file_path, content) {
+        return Ok(Vec::new());
+    }
+
+    let mut vulnerabilities = Vec::new();
+    let commands = logical_lines(content);
+
+    for (index, (line, command)) in commands.iter().enumerate() {
+        let (check, title) = if REMOTE_SCRIPT_EVAL.is_match(command) {
+            (
+                &EVAL_REMOTE_CONTENT,
+                "Downloaded Content Evaluated".to_string(),
+            )
+        } else if REMOTE_SCRIPT_SHELL.is_match(command) {
+            (&CURL_PIPE_SHELL, "Remote Script Piped to Shell".to_string())
+        } else if let Some(variable) = unguarded_delete(command, &commands[..index]) {
+            let title = format!("Recursive Delete of Unguarded '${}'", variable);
+            (&UNGUARDED_RM_RF, title)
+        } else if let Some(name) = hardcoded_credential(command) {
+            (
+                &HARDCODED_CREDENTIAL,
+                format!("Hardcoded Credential in '{}'", name),
+            )
+        } else {
+            continue;
+        };
+        vulnerabilities.push(finding(check, title, *line, command, file_path));
+    }
+
+    Ok(vulnerabilities)
+}
+
+/// The variable a recursive `rm` deletes through, unless it is guarded in
+/// the same command or an earlier one
+fn unguarded_delete<'a>(command: &'a str, earlier: &[(usize, String)]) -> Option<&'a str> {
+    let captures = RM_VARIABLE_TARGET.captures(command)?;
+    let recursive = captures[1].split_whitespace().any(|flag| {
+        flag == "--recursive" || (!flag.starts_with("--") && flag.contains(['r', 'R']))
+    });
+    let target = captures.get(2)?.as_str();
+    if !recursive || target.contains(":?") {
+        return None;
+    }
+
+    let variable = target
+        .trim_start_matches(['$', '{'])
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .next()
+        .filter(|name| !name.is_empty())?;
+    let guard = Regex::new(&format!(
+        r#"\$\{{{v}:\?|-[zn]\s+["']?\$\{{?{v}\b|\b{v}=["']?\$\(\s*mktemp\b"#,
+        v = regex::escape(variable)
+    ))
+    .ok()?;
+    let mut checked = earlier
+        .iter()
+        .map(|(_, prev)| prev.as_str())
+        .chain([command]);
+    if checked.any(|text| guard.is_match(text)) {
+        return None;
+    }
+
+    Some(variable)
+}
+
+/// Name of a credential-like variable assigned a literal value
+fn hardcoded_credential(command: &str) -> Option<&str> {
+    let captures = SHELL_ASSIGNMENT.captures(command)?;
+    let name = captures.get(1)?.as_str();
+    let value = captures[2].trim_matches(|c| c == '"' || c == '\'');
+
+    // Expansions and command substitutions read the value at run time
+    let literal = value.len() >= 4
+        && !value.contains(['$', '`'])
+        && !super::secrets::is_placeholder_value(value);
+    (literal && super::secrets::is_secret_name(name)).then_some(name)
+}
+
+fn finding(
+    check: &Check,
+    title: String,
+    line: usize,
+    snippet: &str,
+    file_path: &str,
+) -> Vulnerability {
+    let mut evidence = HashMap::new();
+    evidence.insert("language".to_string(), serde_json::json!("Shell"));
+    evidence.insert("cwe".to_string(), serde_json::json!(check.cwe));
+
+    Vulnerability::new(
+        format!("SHELL-{:03}", line),
+        check.vuln_type.clone(),
+        check.severity,
+        title,
+        check.description,
+    )
+    .with_rule_id(check.rule)
+    .with_location(Location::new(file_path).with_line(line))
+    .with_remediation(check.remediation)
+    .with_code_snippet(snippet.to_string())
+    .with_confidence(0.8)
+    .with_evidence(evidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_is_shell_script() {
+        assert!(is_shell_script("scripts/install.sh", ""));
+        assert!(is_shell_script("entrypoint.bash", ""));
+        assert!(is_shell_script(
+            "bin/setup",
+            "#!/usr/bin/env bash\nset -e\n"
+        ));
+        assert!(!is_shell_script("setup.py", "#!/usr/bin/env python3\n"));
+    }
+
+    #[test]
+    fn test_detect_install_script_risks() {
+        let content = r#"#!/bin/sh
+export GITHUB_TOKEN="ghp_live_0123456789abcdef"
+curl -fsSL https://get.example.sh | sudo bash
+eval "$(curl -s https://example.com/env.sh)"
+bash <(wget -qO- https://example.com/bootstrap)
+rm -rf "$INSTALL_DIR"/
+rm -rf \
+    $PREFIX/lib/server
+"#;
+        let vulns = detect(content, "install.sh").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "hardcoded-credential",
+                "curl-pipe-shell",
+                "eval-remote-content",
+                "curl-pipe-shell",
+                "unguarded-rm-rf",
+                "unguarded-rm-rf",
+            ]
+        );
+        assert_eq!(
+            vulns[4].title,
+            "Recursive Delete of Unguarded '$INSTALL_DIR'"
+        );
+        // Continuation lines report the command's first line
+        assert_eq!(vulns[5].location.as_ref().unwrap().line, Some(7));
+    }
+
+    #[test]
+    fn test_safe_script_not_flagged() {
+        let content = r#"#!/usr/bin/env bash
+set -euo pipefail
+API_KEY="${API_KEY:?set API_KEY}"
+TOKEN=$(cat /run/secrets/token)
+PASSWORD=changeme
+curl -fsSLo install.sh https://get.example.sh && sha256sum -c install.sh.sha256
+rm -rf "${BUILD_DIR:?}/"
+tmp=$(mktemp -d)
+rm -rf "$tmp"
+if [ -n "$CACHE" ]; then rm -rf "$CACHE"; fi
+rm -f "$LOG_FILE"
+"#;
+        assert!(detect(content, "deploy.sh").unwrap().is_empty());
+        assert!(detect("curl https://x.sh | sh", "notes.md")
+            .unwrap()
+            .is_empty());
+    }
+}
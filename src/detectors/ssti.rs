@@ -0,0 +1,158 @@
+//! Server-side template injection (SSTI) detection - CWE-1336
+//!
+//! MCP servers often build prompts or HTML by rendering templates. Compiling a
+//! template *from* tool input (rather than passing input as template data)
+//! lets an attacker run template expressions, which in Jinja2, Mako, EJS, Pug,
+//! and friends means arbitrary code execution.
+//!
+//! Patterns flag template sources that are not plain string literals:
+//! variables, f-strings, template literals, and concatenation.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct SstiPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    remediation: &'static str,
+}
+
+/// A template argument that is not a plain string literal
+const DYNAMIC_ARG: &str = r#"\s*\(\s*(?:f["']|`|[A-Za-z_]|["'][^"']*["']\s*(?:\+|%|\.format))"#;
+
+static SSTI_PATTERNS: Lazy<Vec<SstiPattern>> = Lazy::new(|| {
+    let pattern = |name, language, sink: &str, remediation| SstiPattern {
+        name,
+        language,
+        regex: Regex::new(&format!("{}{}", sink, DYNAMIC_ARG)).unwrap(),
+        remediation,
+    };
+    vec![
+        pattern(
+            "Flask render_template_string with dynamic template",
+            "Python",
+            r#"\brender_template_string"#,
+            "Use render_template() with a template file and pass user input as context variables",
+        ),
+        // `string.Template` only substitutes $names, so it is excluded
+        pattern(
+            "Jinja2/Mako Template built from dynamic input",
+            "Python",
+            r#"(?:\bjinja2\.|\bmako\.template\.|(?:^|[^.\w]))Template"#,
+            "Load templates from files and pass user input as render() arguments; \
+             use jinja2.sandbox.SandboxedEnvironment if templates must be user-defined",
+        ),
+        pattern(
+            "Jinja2 from_string with dynamic template",
+            "Python",
+            r#"\.from_string"#,
+            "Pass user input as render() arguments instead of template source, \
+             or use jinja2.sandbox.SandboxedEnvironment",
+        ),
+        pattern(
+            "EJS render or compile with dynamic template",
+            "JavaScript/TypeScript",
+            r#"\bejs\.(?:render|compile)"#,
+            "Use ejs.renderFile() with a fixed template and pass user input as data",
+        ),
+        pattern(
+            "Handlebars compile with dynamic template",
+            "JavaScript/TypeScript",
+            r#"\b[Hh]andlebars\.(?:compile|precompile)"#,
+            "Compile fixed templates only and pass user input as the context object",
+        ),
+        pattern(
+            "Pug render or compile with dynamic template",
+            "JavaScript/TypeScript",
+            r#"\bpug\.(?:render|compile)"#,
+            "Use pug.renderFile() with a fixed template and pass user input as locals",
+        ),
+        pattern(
+            "Nunjucks renderString with dynamic template",
+            "JavaScript/TypeScript",
+            r#"\bnunjucks\.renderString"#,
+            "Use nunjucks.render() with a template file and pass user input as context",
+        ),
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = SSTI_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-1336"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("SSTI-{:03}", id_counter),
+                    VulnerabilityType::TemplateInjection,
+                    Severity::High,
+                    format!("{} Detected", pattern.name),
+                    "Template source is built from dynamic input; template expressions \
+                     in that input will be evaluated on the server"
+                        .to_string(),
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Attackers can evaluate template expressions, which in most engines \
+                     leads to arbitrary code execution",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_python_ssti() {
+        let content = r#"
+html = render_template_string(request.args["page"])
+out = Template(prompt_template).render(user=name)
+out = env.from_string(f"Hello {name}").render()
+"#;
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 3);
+    }
+
+    #[test]
+    fn test_literal_templates_not_flagged() {
+        let content = r#"
+html = render_template_string("<p>{{ name }}</p>", name=name)
+out = string.Template(text).substitute(name=name)
+tpl = Handlebars.compile("<p>{{name}}</p>")
+"#;
+        assert!(detect(content, "server.py").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_detect_js_ssti() {
+        let content = "const html = ejs.render(req.body.template, data);\nconst t = Handlebars.compile(`<p>${input}</p>`);";
+        let vulns = detect(content, "server.js").unwrap();
+        assert_eq!(vulns.len(), 2);
+    }
+}
@@ -0,0 +1,376 @@
+//! Toxic flow detection across an MCP server's tool set
+//!
+//! A single tool is rarely dangerous on its own. The risk comes from
+//! combinations an agent can chain: a tool that reads private files next to a
+//! tool that makes arbitrary HTTP requests is an exfiltration path, whether
+//! or not either tool is buggy.
+//!
+//! Tools are profiled by capability, either from source code (tool
+//! registrations and their bodies) or from live `tools/list` definitions
+//! (names and descriptions). Flows are reported when a *source* capability
+//! and a *sink* capability live in different tools; a tool that does both by
+//! itself is ordinary glue code and is left to the per-file detectors.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::models::mcp_protocol::ToolDefinition;
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// What a tool can do, as far as flows are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Reads local files, environment, databases, or mailboxes
+    ReadsPrivateData,
+    /// Pulls in content an attacker may control (web pages, issues, messages)
+    ReadsUntrustedContent,
+    /// Sends data off the machine (HTTP, email, webhooks)
+    ExternalNetwork,
+    /// Runs shell commands or evaluates code
+    ExecutesCode,
+    /// Creates, modifies, or deletes files
+    WritesFiles,
+}
+
+/// A tool and its capabilities
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolProfile {
+    pub name: String,
+    pub capabilities: BTreeSet<Capability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// A dangerous source -> sink combination
+struct Flow {
+    name: &'static str,
+    source: Capability,
+    sink: Capability,
+    severity: Severity,
+    description: &'static str,
+}
+
+static FLOWS: &[Flow] = &[
+    Flow {
+        name: "Private data exfiltration",
+        source: Capability::ReadsPrivateData,
+        sink: Capability::ExternalNetwork,
+        severity: Severity::Critical,
+        description: "One tool reads private data and another can send data to arbitrary destinations; \
+                      an injected instruction can chain them to exfiltrate files, secrets, or records",
+    },
+    Flow {
+        name: "Untrusted content to code execution",
+        source: Capability::ReadsUntrustedContent,
+        sink: Capability::ExecutesCode,
+        severity: Severity::High,
+        description: "One tool ingests attacker-controllable content and another executes commands or code; \
+                      a prompt injection in that content can lead to code execution",
+    },
+    Flow {
+        name: "Untrusted content to file writes",
+        source: Capability::ReadsUntrustedContent,
+        sink: Capability::WritesFiles,
+        severity: Severity::Medium,
+        description: "One tool ingests attacker-controllable content and another writes files; \
+                      a prompt injection in that content can plant or overwrite files",
+    },
+];
+
+/// Capability signals in tool names and descriptions
+static DESCRIPTION_SIGNALS: Lazy<Vec<(Capability, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Capability::ReadsPrivateData,
+            Regex::new(r"(?i)\b(read|list|get|search)\w*[ _]+(files?|director(y|ies)|contents?|documents?|emails?|inbox|messages|database|records|secrets?|credentials?|env)\b|\b(query|sql|filesystem|ssh|keychain)\b").unwrap(),
+        ),
+        (
+            Capability::ReadsUntrustedContent,
+            Regex::new(r"(?i)\b(fetch|browse|scrape|crawl|download)\w*\b|\b(web ?page|url|website|issues?|pull requests?|comments?|inbox|emails?|slack|tweets?)\b").unwrap(),
+        ),
+        (
+            Capability::ExternalNetwork,
+            Regex::new(r"(?i)\b(http|https|url|webhook|upload|post|fetch|request|send|email|slack)\w*\b").unwrap(),
+        ),
+        (
+            Capability::ExecutesCode,
+            Regex::new(r"(?i)\b(execute|exec|run|eval)\w*\b.*\b(command|shell|script|code|process)\b|\b(shell|terminal|bash)\b").unwrap(),
+        ),
+        (
+            Capability::WritesFiles,
+            Regex::new(r"(?i)\b(write|save|create|delete|remove|move|edit)\w*[ _]+(files?|director(y|ies))\b").unwrap(),
+        ),
+    ]
+});
+
+/// Capability signals in tool implementations
+static CODE_SIGNALS: Lazy<Vec<(Capability, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            Capability::ReadsPrivateData,
+            Regex::new(r#"\bopen\s*\([^)]*\)|\.read_text\s*\(|readFile(Sync)?\s*\(|os\.environ|process\.env|\.execute\s*\(|\.query\s*\(|os\.listdir|readdir"#).unwrap(),
+        ),
+        (
+            Capability::ReadsUntrustedContent,
+            Regex::new(r#"requests\.get|httpx\.get|urlopen|aiohttp|BeautifulSoup|\bfetch\s*\(|axios\.get|puppeteer|playwright"#).unwrap(),
+        ),
+        (
+            Capability::ExternalNetwork,
+            Regex::new(r#"requests\.(get|post|put|patch|request)|httpx\.|urllib\.request|aiohttp|\bfetch\s*\(|axios|https?\.request|smtplib|nodemailer"#).unwrap(),
+        ),
+        (
+            Capability::ExecutesCode,
+            Regex::new(r#"subprocess\.|os\.system|os\.popen|child_process|\bexecSync\s*\(|\bspawn\s*\(|\beval\s*\(|\bexec\s*\("#).unwrap(),
+        ),
+        (
+            Capability::WritesFiles,
+            Regex::new(r#"open\s*\([^)]*["'][wax]b?\+?["']|\.write_text\s*\(|writeFile(Sync)?\s*\(|fs\.(unlink|rm|rename)|os\.(remove|rename)|shutil\.(rmtree|move|copy)"#).unwrap(),
+        ),
+    ]
+});
+
+/// `@mcp.tool()` / `@server.tool` / `@tool` decorators
+static PY_TOOL_DECORATOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*@(?:\w+\.)*tool\b"#).unwrap());
+static PY_DEF: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^(\s*)(?:async\s+)?def\s+(\w+)"#).unwrap());
+/// `server.tool("name", ...)` / `server.registerTool("name", ...)`
+static JS_TOOL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\.(?:tool|registerTool|addTool)\s*\(\s*(?:\{\s*name\s*:\s*)?["'`]([\w.-]+)["'`]"#)
+        .unwrap()
+});
+
+/// Longest tool body (in lines) considered for capabilities
+const MAX_TOOL_LINES: usize = 200;
+
+/// Profile a live tool definition from its name and description
+pub fn profile_definition(tool: &ToolDefinition) -> ToolProfile {
+    let text = format!("{} {}", tool.name.replace('_', " "), tool.description);
+    ToolProfile {
+        name: tool.name.clone(),
+        capabilities: signals(&DESCRIPTION_SIGNALS, &text),
+        file: None,
+        line: None,
+    }
+}
+
+/// Find tool registrations in source code and profile their bodies
+pub fn extract_tools(content: &str, file_path: &str) -> Vec<ToolProfile> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut tools = Vec::new();
+
+    // Python: decorated functions, body ends at the next dedent
+    let mut i = 0;
+    while i < lines.len() {
+        if PY_TOOL_DECORATOR.is_match(lines[i]) {
+            if let Some((def_line, caps)) = lines[i + 1..]
+                .iter()
+                .take(5)
+                .enumerate()
+                .find_map(|(j, l)| PY_DEF.captures(l).map(|c| (i + 1 + j, c)))
+            {
+                let indent = caps[1].len();
+                let end = (def_line + 1..lines.len().min(def_line + MAX_TOOL_LINES))
+                    .find(|&k| {
+                        let l = lines[k];
+                        !l.trim().is_empty() && l.len() - l.trim_start().len() <= indent
+                    })
+                    .unwrap_or_else(|| lines.len().min(def_line + MAX_TOOL_LINES));
+                tools.push(profile_code(
+                    &caps[2],
+                    &lines[def_line..end],
+                    file_path,
+                    def_line,
+                ));
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    // JavaScript/TypeScript: registration call, body runs to the next one
+    let starts: Vec<(usize, String)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(n, l)| JS_TOOL.captures(l).map(|c| (n, c[1].to_string())))
+        .collect();
+    for (k, (start, name)) in starts.iter().enumerate() {
+        let end = starts
+            .get(k + 1)
+            .map_or(lines.len(), |(next, _)| *next)
+            .min(start + MAX_TOOL_LINES);
+        tools.push(profile_code(name, &lines[*start..end], file_path, *start));
+    }
+
+    tools
+}
+
+fn profile_code(name: &str, body: &[&str], file_path: &str, line: usize) -> ToolProfile {
+    let mut capabilities = signals(&CODE_SIGNALS, &body.join("\n"));
+    capabilities.extend(signals(&DESCRIPTION_SIGNALS, &name.replace('_', " ")));
+    ToolProfile {
+        name: name.to_string(),
+        capabilities,
+        file: Some(file_path.to_string()),
+        line: Some(line + 1),
+    }
+}
+
+fn signals(table: &[(Capability, Regex)], text: &str) -> BTreeSet<Capability> {
+    table
+        .iter()
+        .filter(|(_, re)| re.is_match(text))
+        .map(|(c, _)| *c)
+        .collect()
+}
+
+/// Report dangerous flows between the tools of one server
+pub fn detect(tools: &[ToolProfile]) -> Vec<Vulnerability> {
+    let mut vulnerabilities = Vec::new();
+
+    for flow in FLOWS {
+        let with = |capability| -> Vec<&ToolProfile> {
+            tools
+                .iter()
+                .filter(|t| t.capabilities.contains(&capability))
+                .collect()
+        };
+        let sources = with(flow.source);
+        let sinks = with(flow.sink);
+        let crosses_tools = sources
+            .iter()
+            .any(|s| sinks.iter().any(|k| k.name != s.name));
+        if !crosses_tools {
+            continue;
+        }
+
+        let names = |list: &[&ToolProfile]| -> String {
+            list.iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let sink = sinks[0];
+        let mut location = Location::new(sink.file.as_deref().unwrap_or("<tools/list>"));
+        if let Some(line) = sink.line {
+            location = location.with_line(line);
+        }
+
+        let mut evidence = std::collections::HashMap::new();
+        evidence.insert("sources".to_string(), serde_json::json!(sources));
+        evidence.insert("sinks".to_string(), serde_json::json!(sinks));
+
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("FLOW-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::ToxicFlow,
+                flow.severity,
+                format!("Toxic flow: {}", flow.name),
+                format!(
+                    "{} (sources: {}; sinks: {})",
+                    flow.description,
+                    names(&sources),
+                    names(&sinks)
+                ),
+            )
+            .with_rule_id(super::rule_slug(flow.name))
+            .with_location(location)
+            .with_impact("An agent steered by injected instructions can chain these tools")
+            .with_remediation(
+                "Split these tools into separate servers, restrict their scope (allowlisted paths or hosts), \
+                 or require user confirmation before the sink tool runs",
+            )
+            .with_confidence(0.7)
+            .with_evidence(evidence),
+        );
+    }
+
+    vulnerabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_python_tools() {
+        let content = r#"
+from mcp.server.fastmcp import FastMCP
+mcp = FastMCP("files")
+
+@mcp.tool()
+def read_notes(path: str) -> str:
+    with open(path) as f:
+        return f.read()
+
+@mcp.tool()
+async def post_webhook(url: str, body: str):
+    requests.post(url, data=body)
+"#;
+        let tools = extract_tools(content, "server.py");
+        assert_eq!(tools.len(), 2);
+        assert!(tools[0]
+            .capabilities
+            .contains(&Capability::ReadsPrivateData));
+        assert!(!tools[0].capabilities.contains(&Capability::ExternalNetwork));
+        assert!(tools[1].capabilities.contains(&Capability::ExternalNetwork));
+
+        let vulns = detect(&tools);
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].severity, Severity::Critical);
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(11));
+    }
+
+    #[test]
+    fn test_extract_js_tools() {
+        let content = r#"
+server.tool("fetch_page", { url: z.string() }, async ({ url }) => {
+  const res = await fetch(url);
+  return res.text();
+});
+server.tool("run", { cmd: z.string() }, async ({ cmd }) => {
+  return execSync(cmd).toString();
+});
+"#;
+        let tools = extract_tools(content, "index.ts");
+        assert_eq!(tools.len(), 2);
+        let vulns = detect(&tools);
+        assert!(vulns
+            .iter()
+            .any(|v| v.title.contains("Untrusted content to code execution")));
+    }
+
+    #[test]
+    fn test_single_tool_is_not_a_flow() {
+        let tools = vec![profile_definition(&ToolDefinition {
+            name: "sync_file".into(),
+            description: "Read files from disk and upload them via HTTP".into(),
+            input_schema: serde_json::json!({}),
+        })];
+        assert!(detect(&tools).is_empty());
+    }
+
+    #[test]
+    fn test_profile_live_definitions() {
+        let tools: Vec<ToolProfile> = [
+            ("read_file", "Read the contents of a file"),
+            ("http_request", "Send an HTTP request to any URL"),
+        ]
+        .iter()
+        .map(|(name, description)| {
+            profile_definition(&ToolDefinition {
+                name: name.to_string(),
+                description: description.to_string(),
+                input_schema: serde_json::json!({}),
+            })
+        })
+        .collect();
+        let vulns = detect(&tools);
+        assert!(vulns.iter().any(|v| v.severity == Severity::Critical));
+    }
+}
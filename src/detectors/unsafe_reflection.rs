@@ -0,0 +1,185 @@
+//! Unsafe reflection detection for Java and C# - CWE-470, CWE-917
+//!
+//! Loading a class or type by a name the caller controls lets them
+//! instantiate any gadget on the classpath, and compiling expression strings
+//! (SpEL, OGNL, MVEL, Roslyn scripting, Dynamic LINQ) from input is direct
+//! code execution.
+//!
+//! Only non-literal names are flagged: lowercase identifiers (locals,
+//! parameters, method calls), concatenation, and C# interpolated strings.
+//! `UPPER_CASE` constants and `typeof(...)` are treated as fixed.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct ReflectionPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    vuln_type: VulnerabilityType,
+    severity: Severity,
+    cwe: &'static str,
+    remediation: &'static str,
+}
+
+/// A call argument that is not a string literal or constant
+const DYNAMIC_ARG: &str = r#"\s*\(\s*(?:[a-z_][\w.]*|"[^"]*"\s*\+|\$@?")"#;
+
+const ALLOWLIST_REMEDIATION: &str = "Map user input to an allowlist of known classes or types \
+                                     instead of resolving names directly";
+
+const EXPRESSION_REMEDIATION: &str = "Never compile expressions or scripts from user input; \
+                                      pass input as variables to a fixed expression, or use a \
+                                      restricted context (SimpleEvaluationContext for SpEL)";
+
+static REFLECTION_PATTERNS: Lazy<Vec<ReflectionPattern>> = Lazy::new(|| {
+    let reflection = |name, language, sink: &str| ReflectionPattern {
+        name,
+        language,
+        regex: Regex::new(&format!("{}{}", sink, DYNAMIC_ARG)).unwrap(),
+        vuln_type: VulnerabilityType::UnsafeReflection,
+        severity: Severity::High,
+        cwe: "CWE-470",
+        remediation: ALLOWLIST_REMEDIATION,
+    };
+    let expression = |name, language, sink: &str| ReflectionPattern {
+        name,
+        language,
+        regex: Regex::new(&format!("{}{}", sink, DYNAMIC_ARG)).unwrap(),
+        vuln_type: VulnerabilityType::CodeInjection,
+        severity: Severity::Critical,
+        cwe: "CWE-917",
+        remediation: EXPRESSION_REMEDIATION,
+    };
+    vec![
+        reflection(
+            "Class.forName with dynamic class name",
+            "Java",
+            r#"\bClass\.forName"#,
+        ),
+        reflection(
+            "ClassLoader.loadClass with dynamic class name",
+            "Java",
+            r#"\.loadClass"#,
+        ),
+        reflection(
+            "Reflective method lookup by dynamic name",
+            "Java/C#",
+            r#"\.(?:getMethod|getDeclaredMethod|GetMethod|InvokeMember)"#,
+        ),
+        reflection(
+            "Type.GetType with dynamic type name",
+            "C#",
+            r#"\bType\.GetType"#,
+        ),
+        // `Activator.CreateInstance(typeof(T))` is fixed; a bare variable is not
+        ReflectionPattern {
+            name: "Activator.CreateInstance with dynamic type",
+            language: "C#",
+            regex: Regex::new(
+                r#"\bActivator\.CreateInstance\s*\(\s*(?:[a-z_]\w*\s*[,)]|Type\.GetType\s*\()"#,
+            )
+            .unwrap(),
+            vuln_type: VulnerabilityType::UnsafeReflection,
+            severity: Severity::High,
+            cwe: "CWE-470",
+            remediation: ALLOWLIST_REMEDIATION,
+        },
+        reflection(
+            "Assembly loaded from dynamic path",
+            "C#",
+            r#"\bAssembly\.(?:Load|LoadFrom|LoadFile)"#,
+        ),
+        expression(
+            "Expression language evaluated from dynamic string",
+            "Java",
+            r#"(?:\.parseExpression|\bOgnl\.(?:getValue|parseExpression)|\bMVEL\.(?:eval|compileExpression)|\.createExpression)"#,
+        ),
+        expression(
+            "C# script or expression compiled from dynamic string",
+            "C#",
+            r#"(?:\bCSharpScript\.(?:EvaluateAsync|RunAsync|Create)|\bDynamicExpressionParser\.Parse\w*|\.CompileAssemblyFromSource)"#,
+        ),
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = REFLECTION_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!(pattern.cwe));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("REFL-{:03}", id_counter),
+                    pattern.vuln_type.clone(),
+                    pattern.severity,
+                    format!("{} Detected", pattern.name),
+                    "A class, method, or expression is resolved from a string that may \
+                     come from user input",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Attackers can instantiate arbitrary classes or run arbitrary code \
+                     with the server's privileges",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.7)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_java_reflection() {
+        let content = r#"
+Class<?> handler = Class.forName(request.getParameter("handler"));
+Method m = target.getClass().getMethod(methodName, String.class);
+Object value = parser.parseExpression(userExpression).getValue(context);
+Class<?> driver = Class.forName(JDBC_DRIVER);
+"#;
+        let vulns = detect(content, "Handler.java").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::UnsafeReflection);
+        assert_eq!(vulns[2].vuln_type, VulnerabilityType::CodeInjection);
+    }
+
+    #[test]
+    fn test_detect_csharp_reflection() {
+        let content = r#"
+var plugin = Activator.CreateInstance(Type.GetType(typeName));
+var result = await CSharpScript.EvaluateAsync(code);
+var asm = Assembly.LoadFrom($"{pluginDir}/{name}.dll");
+var safe = Activator.CreateInstance(typeof(DefaultPlugin));
+"#;
+        let vulns = detect(content, "Plugins.cs").unwrap();
+        assert_eq!(vulns.len(), 3);
+        assert_eq!(
+            vulns[0].rule_id.as_deref(),
+            Some("type-gettype-with-dynamic-type-name")
+        );
+    }
+}
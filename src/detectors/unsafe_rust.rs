@@ -0,0 +1,167 @@
+//! Raw pointer handling in request-facing Rust code - CWE-119
+//!
+//! `unsafe` is sometimes justified in low-level crates, but inside the
+//! functions that handle tool calls or HTTP requests it means attacker-sized
+//! buffers meet unchecked pointer arithmetic. Only `.rs` files are checked,
+//! and a finding needs all three:
+//! - a function whose signature takes request data (`req`, `body`, `Json<..>`,
+//!   `&[u8]`, ...)
+//! - an `unsafe` block or `unsafe fn` body inside it
+//! - a raw pointer operation (`from_raw_parts`, `as *mut`, `ptr::read`,
+//!   `transmute`, `get_unchecked`, `set_len`, ...) inside that block
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// How many lines a function signature may span before its body opens
+const SIGNATURE_MAX_LINES: usize = 10;
+
+static FN_SIGNATURE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bfn\s+\w+"#).unwrap());
+
+/// Parameters that carry request data
+static REQUEST_INPUT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:req|request|body|payload|input|params|args|msg|message)\s*:|\b(?:Request|HttpRequest|Json|Bytes|BytesMut|Query|Form|CallToolRequest)\b|&\s*(?:mut\s+)?\[u8\]"#,
+    )
+    .unwrap()
+});
+
+static UNSAFE_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bunsafe\s*(?:\{|fn\b[^{]*\{)"#).unwrap());
+
+static RAW_POINTER_OP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\bfrom_raw_parts(?:_mut)?\s*\(|\bas\s+\*(?:const|mut)\b|\bptr::(?:read|write|copy|copy_nonoverlapping)(?:_unaligned|_volatile)?\s*\(|\btransmute\s*(?:::<[^>]*>)?\s*\(|\.(?:offset|add|sub)\s*\(|\bget_unchecked(?:_mut)?\s*\(|\bset_len\s*\("#,
+    )
+    .unwrap()
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    if !file_path.ends_with(".rs") {
+        return Ok(Vec::new());
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+    let mut depth: usize = 0;
+    let mut request_fn = false;
+    // Brace depth outside the innermost open unsafe block
+    let mut unsafe_depth: Option<usize> = None;
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let code = line.split("//").next().unwrap_or_default();
+
+        if FN_SIGNATURE.is_match(code) && unsafe_depth.is_none() {
+            let mut signature = String::new();
+            for l in lines.iter().skip(line_num).take(SIGNATURE_MAX_LINES) {
+                signature.push_str(l);
+                if l.contains('{') {
+                    break;
+                }
+            }
+            request_fn = REQUEST_INPUT.is_match(&signature);
+        }
+        if unsafe_depth.is_none() && UNSAFE_BLOCK.is_match(code) {
+            unsafe_depth = Some(depth);
+        }
+
+        if let (Some(_), true) = (unsafe_depth, request_fn) {
+            for op in RAW_POINTER_OP.find_iter(code) {
+                let location = super::match_location(file_path, line_num, line, op.range());
+                vulnerabilities.push(finding(id_counter, op.as_str(), line, location));
+                id_counter += 1;
+            }
+        }
+
+        for c in code.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if unsafe_depth.is_some_and(|outer| depth <= outer) {
+            unsafe_depth = None;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+fn finding(id: usize, op: &str, line: &str, location: Location) -> Vulnerability {
+    let mut evidence = HashMap::new();
+    evidence.insert("language".to_string(), serde_json::json!("Rust"));
+    evidence.insert("cwe".to_string(), serde_json::json!("CWE-119"));
+    evidence.insert("operation".to_string(), serde_json::json!(op.trim()));
+
+    Vulnerability::new(
+        format!("UNSAFE-{:03}", id),
+        VulnerabilityType::MemorySafety,
+        Severity::High,
+        "Raw Pointer Operation in Request Handler",
+        "An unsafe block in a function that receives request data performs raw pointer \
+         operations, so unchecked lengths or offsets can come from the caller",
+    )
+    .with_rule_id("raw-pointer-in-request-path")
+    .with_location(location)
+    .with_impact(
+        "Out-of-bounds reads or writes can leak memory contents, crash the server, or allow \
+         code execution",
+    )
+    .with_remediation(
+        "Use safe slice APIs (get, split_at, copy_from_slice) on request data, validate \
+         lengths before any unsafe code, and keep unsafe in small audited helpers",
+    )
+    .with_code_snippet(line.to_string())
+    .with_confidence(0.7)
+    .with_evidence(evidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_pointer_ops_in_handler() {
+        let content = r#"
+async fn handle_upload(body: Bytes) -> Result<Json<Value>> {
+    let header = unsafe {
+        let ptr = body.as_ptr() as *const Header;
+        std::ptr::read_unaligned(ptr)
+    };
+    let len = body.len();
+    Ok(Json(json!({ "len": len })))
+}
+"#;
+        let vulns = detect(content, "src/handlers.rs").unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::MemorySafety);
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(4));
+    }
+
+    #[test]
+    fn test_internal_unsafe_not_flagged() {
+        let content = r#"
+fn checksum(words: &[u32]) -> u32 {
+    unsafe { *words.get_unchecked(0) }
+}
+
+fn handle(req: Request) -> Response {
+    let n = req.len();
+    respond(n)
+}
+"#;
+        assert!(detect(content, "src/lib.rs").unwrap().is_empty());
+        assert!(detect(
+            "fn f(body: &[u8]) { unsafe { body.get_unchecked(9) }; }",
+            "a.py"
+        )
+        .unwrap()
+        .is_empty());
+    }
+}
@@ -0,0 +1,158 @@
+//! XPath injection detection - CWE-643
+//!
+//! Servers that expose XML documents (configs, feeds, SAML metadata) to an
+//! agent often look nodes up with an XPath expression built around the
+//! caller's input. A value like `' or '1'='1` rewrites the predicate, so a
+//! lookup of one user's record returns all of them - and XPath has no access
+//! control within a document.
+//!
+//! Patterns flag XPath evaluation calls in Python (lxml, ElementTree),
+//! Java (javax.xml.xpath, dom4j), C# and browser/Node JavaScript whose
+//! expression is built by concatenation, interpolation, or formatting.
+//! Parameterized queries (`tree.xpath("//user[@name=$name]", name=name)`,
+//! `XPathVariableResolver`) are not matched.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct XpathPattern {
+    name: &'static str,
+    regex: Regex,
+}
+
+/// Methods that evaluate an XPath expression passed as their first argument
+const XPATH_CALL: &str = r#"\.(?:xpath|XPath|evaluate|compile|selectNodes|selectSingleNode|SelectNodes|SelectSingleNode|find|findall|findtext|iterfind|valueOf|createExpression)\s*\(\s*"#;
+
+/// A string literal holding a location path (`/`, `//`, `.//`) with
+/// `containing` somewhere after the path's first step
+fn path_literal(containing: &str) -> String {
+    ["\"", "'", "`"]
+        .iter()
+        .map(|q| format!(r#"{q}\.?//?[\w*@][^{q}\n]*{containing}[^{q}\n]*{q}"#))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+static XPATH_PATTERNS: Lazy<Vec<XpathPattern>> = Lazy::new(|| {
+    vec![
+        XpathPattern {
+            name: "XPath query built by string concatenation",
+            regex: Regex::new(&format!(r#"{}(?:{})\s*\+"#, XPATH_CALL, path_literal(""))).unwrap(),
+        },
+        XpathPattern {
+            name: "XPath query built by interpolation",
+            regex: Regex::new(&format!(
+                r#"{}(?:(?:\bf|\$)(?:{})|(?:{}))"#,
+                XPATH_CALL,
+                path_literal(r"\{"),
+                path_literal(r"\$\{")
+            ))
+            .unwrap(),
+        },
+        XpathPattern {
+            name: "XPath query built by string formatting",
+            regex: Regex::new(&format!(
+                r#"{}(?:(?:{})\s*(?:%|\.format\s*\()|(?:String\.format|string\.Format)\s*\()"#,
+                XPATH_CALL,
+                path_literal(r"(?:%s|\{\})")
+            ))
+            .unwrap(),
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = XPATH_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert(
+                "language".to_string(),
+                serde_json::json!("Python, Java, C#, JavaScript/TypeScript"),
+            );
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-643"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("XPATH-{:03}", id_counter),
+                    VulnerabilityType::XpathInjection,
+                    Severity::High,
+                    format!("{} Detected", pattern.name),
+                    "An XPath expression is assembled from input, so quotes and operators in the \
+                     value change which nodes it selects",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "Attackers can read any part of the XML document, including other users' \
+                     records and credentials, or bypass XPath-based login checks",
+                )
+                .with_remediation(
+                    "Pass input as XPath variables (lxml: tree.xpath(\"//user[@name=$name]\", \
+                     name=value); Java: XPathVariableResolver) instead of building the expression \
+                     as a string; otherwise allowlist the value's characters",
+                )
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(vulns: &[Vulnerability]) -> Vec<&str> {
+        vulns.iter().filter_map(|v| v.rule_id.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_detect_xpath_injection() {
+        let content = r#"
+nodes = tree.xpath(f"//user[@name='{username}']")
+Node n = (Node) xpath.evaluate("//user[@id='" + id + "']", doc, XPathConstants.NODE);
+const res = document.evaluate(`//a[@title='${title}']`, document, null, 9, null);
+item = root.find(".//item[@sku='%s']" % sku)
+var node = doc.SelectSingleNode(string.Format("//user[@name='{0}']", name));
+"#;
+        let vulns = detect(content, "lookup.py").unwrap();
+        assert_eq!(
+            rules(&vulns),
+            vec![
+                "xpath-query-built-by-interpolation",
+                "xpath-query-built-by-string-concatenation",
+                "xpath-query-built-by-interpolation",
+                "xpath-query-built-by-string-formatting",
+                "xpath-query-built-by-string-formatting",
+            ]
+        );
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::XpathInjection);
+        assert_eq!(vulns[0].evidence.as_ref().unwrap()["cwe"], "CWE-643");
+    }
+
+    #[test]
+    fn test_parameterized_xpath_not_flagged() {
+        let content = r#"
+nodes = tree.xpath("//user[@name=$name]", name=username)
+items = root.findall(".//item")
+idx = text.find("/" + sep)
+"#;
+        assert!(detect(content, "lookup.py").unwrap().is_empty());
+    }
+}
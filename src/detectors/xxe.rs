@@ -0,0 +1,205 @@
+//! XML external entity (XXE) detection - CWE-611
+//!
+//! Flags XML parsers configured (or left by default) to resolve external
+//! entities and DTDs, which lets a crafted document read local files, reach
+//! internal services, or exhaust memory.
+//!
+//! Some patterns are only risky when the file has no mitigation in sight:
+//! Python parsers are fine when `defusedxml` is used, and Java factories are
+//! fine once secure processing or DOCTYPE rejection is configured. Those
+//! checks are file-wide, so a mitigation anywhere in the file suppresses them.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+struct XxePattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    /// File-wide mitigation that makes this pattern safe
+    mitigation: Option<&'static Lazy<Regex>>,
+    description: &'static str,
+    remediation: &'static str,
+    severity: Severity,
+}
+
+static DEFUSEDXML: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bdefusedxml\b"#).unwrap());
+
+static JAVA_SECURE_PROCESSING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"FEATURE_SECURE_PROCESSING\s*,\s*true|disallow-doctype-decl"\s*,\s*true|IS_SUPPORTING_EXTERNAL_ENTITIES\s*,\s*(false|Boolean\.FALSE)|SUPPORT_DTD\s*,\s*(false|Boolean\.FALSE)|ACCESS_EXTERNAL_DTD\s*,\s*""#,
+    )
+    .unwrap()
+});
+
+static XXE_PATTERNS: Lazy<Vec<XxePattern>> = Lazy::new(|| {
+    vec![
+        XxePattern {
+            name: "Python lxml parser without defusedxml",
+            language: "Python",
+            regex: Regex::new(r#"\b(?:lxml\.)?etree\.(?:parse|fromstring|XML|XMLParser)\s*\("#)
+                .unwrap(),
+            mitigation: Some(&DEFUSEDXML),
+            description: "XML parsed with lxml/etree without defusedxml; entities and DTDs may be resolved",
+            remediation: "Parse untrusted XML with defusedxml (e.g. defusedxml.lxml or defusedxml.ElementTree), \
+                          or pass XMLParser(resolve_entities=False, no_network=True, load_dtd=False)",
+            severity: Severity::High,
+        },
+        XxePattern {
+            name: "Python xml.dom/sax parser without defusedxml",
+            language: "Python",
+            regex: Regex::new(r#"\b(?:minidom|pulldom|sax)\.(?:parse|parseString)\s*\("#).unwrap(),
+            mitigation: Some(&DEFUSEDXML),
+            description: "XML parsed with the standard library without defusedxml; vulnerable to entity expansion attacks",
+            remediation: "Use the equivalent defusedxml module (defusedxml.minidom, defusedxml.sax, ...)",
+            severity: Severity::Medium,
+        },
+        XxePattern {
+            name: "Python lxml resolve_entities enabled",
+            language: "Python",
+            regex: Regex::new(r#"resolve_entities\s*=\s*True"#).unwrap(),
+            mitigation: None,
+            description: "lxml parser explicitly configured to resolve external entities",
+            remediation: "Set resolve_entities=False (and no_network=True) on the parser",
+            severity: Severity::High,
+        },
+        XxePattern {
+            name: "Java XML factory without secure processing",
+            language: "Java",
+            regex: Regex::new(
+                r#"\b(?:DocumentBuilderFactory|SAXParserFactory|XMLInputFactory|TransformerFactory|SchemaFactory)\.newInstance\s*\("#,
+            )
+            .unwrap(),
+            mitigation: Some(&JAVA_SECURE_PROCESSING),
+            description: "XML factory created without disabling DOCTYPEs or enabling secure processing",
+            remediation: "Call setFeature(\"http://apache.org/xml/features/disallow-doctype-decl\", true), \
+                          or enable XMLConstants.FEATURE_SECURE_PROCESSING and disable external entities",
+            severity: Severity::High,
+        },
+        XxePattern {
+            name: "libxml NOENT or DTDLOAD flag",
+            language: "C/PHP",
+            regex: Regex::new(r#"\b(?:XML_PARSE|LIBXML)_(?:NOENT|DTDLOAD|DTDATTR)\b"#).unwrap(),
+            mitigation: None,
+            description: "libxml parsing flags enable entity substitution or external DTD loading",
+            remediation: "Remove NOENT/DTDLOAD from the parse options and add XML_PARSE_NONET",
+            severity: Severity::High,
+        },
+        XxePattern {
+            name: "PHP entity loader enabled",
+            language: "PHP",
+            regex: Regex::new(r#"libxml_disable_entity_loader\s*\(\s*false\s*\)"#).unwrap(),
+            mitigation: None,
+            description: "External entity loading explicitly re-enabled",
+            remediation: "Do not re-enable the entity loader; parse without LIBXML_NOENT",
+            severity: Severity::High,
+        },
+        XxePattern {
+            name: "libxmljs noent option",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(r#"\bnoent\s*:\s*true"#).unwrap(),
+            mitigation: None,
+            description: "libxmljs parser configured to substitute entities",
+            remediation: "Drop the noent option (it defaults to false)",
+            severity: Severity::High,
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    let active: Vec<&XxePattern> = XXE_PATTERNS
+        .iter()
+        .filter(|p| !p.mitigation.is_some_and(|m| m.is_match(content)))
+        .collect();
+    if active.is_empty() {
+        return Ok(vulnerabilities);
+    }
+
+    for (line_num, line) in content.lines().enumerate() {
+        let Some(pattern) = active.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!("CWE-611"));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("XXE-{:03}", id_counter),
+                    VulnerabilityType::XxeInjection,
+                    pattern.severity,
+                    format!("{} Detected", pattern.name),
+                    pattern.description.to_string(),
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "A crafted XML document can read local files, reach internal services, \
+                     or exhaust memory through entity expansion",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.8)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_lxml_without_defusedxml() {
+        let content = "from lxml import etree\ntree = etree.fromstring(body)";
+        let vulns = detect(content, "server.py").unwrap();
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(
+            vulns[0].rule_id.as_deref(),
+            Some("python-lxml-parser-without-defusedxml")
+        );
+
+        let safe = "from defusedxml.lxml import fromstring\nimport lxml.etree as etree\ntree = etree.fromstring(body)";
+        assert!(detect(safe, "server.py").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_detect_java_factory_without_secure_processing() {
+        let content = "DocumentBuilderFactory dbf = DocumentBuilderFactory.newInstance();";
+        assert_eq!(detect(content, "Parser.java").unwrap().len(), 1);
+
+        let hardened = format!(
+            "{}\ndbf.setFeature(\"http://apache.org/xml/features/disallow-doctype-decl\", true);",
+            content
+        );
+        assert!(detect(&hardened, "Parser.java").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_detect_libxml_noent() {
+        let content =
+            "doc = xmlReadMemory(buf, len, NULL, NULL, XML_PARSE_NOENT | XML_PARSE_DTDLOAD);";
+        let vulns = detect(content, "parse.c").unwrap();
+        // Each flag is reported at its own column
+        let columns: Vec<Option<usize>> = vulns
+            .iter()
+            .map(|v| v.location.as_ref().unwrap().column)
+            .collect();
+        assert_eq!(columns, vec![Some(43), Some(61)]);
+        assert_eq!(vulns[0].severity, Severity::High);
+    }
+}
@@ -0,0 +1,287 @@
+//! YARA rule detector
+//!
+//! Evaluates user-provided YARA rules (via yara-x) against scanned files, for
+//! hunting known-malicious payloads and droppers that regexes can't express.
+//! Needs the `yara` feature; without it, configuring rules is an error.
+//!
+//! Rule metadata drives the finding:
+//!
+//! ```yara
+//! rule base64_python_dropper {
+//!     meta:
+//!         description = "Decodes and executes an embedded payload"
+//!         severity = "critical"
+//!     strings:
+//!         $exec = /exec\(base64\.b64decode\(/
+//!     condition:
+//!         $exec
+//! }
+//! ```
+//!
+//! `severity` defaults to high. Findings get the rule ID `yara/<rule name>`.
+//! Like every detector, only files the scanner reads as text are evaluated.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::models::vulnerability::Vulnerability;
+#[cfg(feature = "yara")]
+use crate::models::vulnerability::{Location, Severity, VulnerabilityType};
+
+/// Rule file extensions picked up from directories
+const RULE_EXTENSIONS: &[&str] = &["yar", "yara"];
+
+/// Compiled YARA rules
+///
+/// (De)serializes as the list of rule paths, so it can live in `ScanConfig`
+/// while rules are compiled only once.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "Vec<PathBuf>", into = "Vec<PathBuf>")]
+pub struct YaraRules {
+    sources: Vec<PathBuf>,
+    #[cfg(feature = "yara")]
+    compiled: std::sync::Arc<yara_x::Rules>,
+}
+
+impl YaraRules {
+    /// Compile rules from files and directories of `.yar`/`.yara` files
+    pub fn load(paths: &[PathBuf]) -> Result<Self> {
+        Self::try_from(paths.to_vec())
+    }
+
+    /// Paths the rules were loaded from
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    #[cfg(feature = "yara")]
+    fn compile(sources: Vec<PathBuf>) -> Result<Self> {
+        use anyhow::Context;
+
+        let mut compiler = yara_x::Compiler::new();
+        for file in rule_files(&sources)? {
+            let source = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            compiler
+                .add_source(source.as_str())
+                .map_err(|e| anyhow::anyhow!("Invalid YARA rules in {}: {}", file.display(), e))?;
+        }
+        Ok(Self {
+            sources,
+            compiled: std::sync::Arc::new(compiler.build()),
+        })
+    }
+
+    #[cfg(not(feature = "yara"))]
+    fn compile(_sources: Vec<PathBuf>) -> Result<Self> {
+        anyhow::bail!(
+            "YARA rules are configured but mcp-sentinel was built without the `yara` feature"
+        )
+    }
+}
+
+impl TryFrom<Vec<PathBuf>> for YaraRules {
+    type Error = anyhow::Error;
+
+    fn try_from(sources: Vec<PathBuf>) -> Result<Self> {
+        Self::compile(sources)
+    }
+}
+
+impl From<YaraRules> for Vec<PathBuf> {
+    fn from(rules: YaraRules) -> Self {
+        rules.sources
+    }
+}
+
+impl PartialEq for YaraRules {
+    fn eq(&self, other: &Self) -> bool {
+        self.sources == other.sources
+    }
+}
+
+impl std::fmt::Debug for YaraRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("YaraRules")
+            .field("sources", &self.sources)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Expand directories into the rule files they contain, sorted by name
+pub fn rule_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = std::fs::read_dir(path)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| is_rule_file(p))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            anyhow::ensure!(path.is_file(), "YARA rules not found: {}", path.display());
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn is_rule_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| RULE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Evaluate `rules` against one file's content
+#[cfg(feature = "yara")]
+pub fn detect(content: &str, file_path: &str, rules: &YaraRules) -> Result<Vec<Vulnerability>> {
+    let mut scanner = yara_x::Scanner::new(&rules.compiled);
+    let results = scanner
+        .scan(content.as_bytes())
+        .map_err(|e| anyhow::anyhow!("YARA scan failed: {}", e))?;
+
+    let mut vulnerabilities = Vec::new();
+    for (i, rule) in results.matching_rules().enumerate() {
+        let mut description = None;
+        let mut severity = Severity::High;
+        for (key, value) in rule.metadata() {
+            if let yara_x::MetaValue::String(value) = value {
+                match key {
+                    "description" => description = Some(value.to_string()),
+                    "severity" => severity = parse_severity(value).unwrap_or(severity),
+                    _ => {}
+                }
+            }
+        }
+
+        let offset = rule
+            .patterns()
+            .flat_map(|p| p.matches())
+            .map(|m| m.range().start)
+            .min();
+        let mut location = Location::new(file_path);
+        let mut snippet = None;
+        if let Some(offset) = offset {
+            let (line, column, text) = line_at(content, offset);
+            location = location.with_line(line).with_column(column);
+            snippet = Some(text.trim().to_string());
+        }
+
+        let mut vuln = Vulnerability::new(
+            format!("YARA-{:03}", i + 1),
+            VulnerabilityType::MaliciousPayload,
+            severity,
+            format!("YARA rule matched: {}", rule.identifier()),
+            description
+                .unwrap_or_else(|| format!("Content matched YARA rule '{}'", rule.identifier())),
+        )
+        .with_rule_id(rule.identifier())
+        .with_location(location)
+        .with_impact("Content matches a signature for known-malicious code")
+        .with_remediation("Inspect the matched content and remove the server if it is malicious")
+        .with_confidence(0.9);
+        if let Some(snippet) = snippet {
+            vuln = vuln.with_code_snippet(snippet);
+        }
+        vulnerabilities.push(vuln);
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(not(feature = "yara"))]
+pub fn detect(_content: &str, _file_path: &str, _rules: &YaraRules) -> Result<Vec<Vulnerability>> {
+    // YaraRules can't be constructed without the feature
+    Ok(Vec::new())
+}
+
+#[cfg(feature = "yara")]
+fn parse_severity(value: &str) -> Option<Severity> {
+    match value.to_ascii_lowercase().as_str() {
+        "critical" => Some(Severity::Critical),
+        "high" => Some(Severity::High),
+        "medium" => Some(Severity::Medium),
+        "low" => Some(Severity::Low),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+/// 1-based line and column of a byte offset, plus the line's text
+#[cfg(feature = "yara")]
+fn line_at(content: &str, offset: usize) -> (usize, usize, &str) {
+    let offset = offset.min(content.len());
+    let start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = content[offset..]
+        .find('\n')
+        .map_or(content.len(), |i| offset + i);
+    let line = content[..start].matches('\n').count() + 1;
+    (line, offset - start + 1, &content[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "yara")]
+    #[test]
+    fn test_line_at() {
+        let content = "import os\nexec(base64.b64decode(x))\n";
+        assert_eq!(line_at(content, 15), (2, 6, "exec(base64.b64decode(x))"));
+        assert_eq!(line_at(content, 0), (1, 1, "import os"));
+    }
+
+    #[test]
+    fn test_rule_files_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.yara"), "").unwrap();
+        std::fs::write(dir.path().join("a.yar"), "").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let files = rule_files(&[dir.path().to_path_buf()]).unwrap();
+        let names: Vec<_> = files.iter().map(|f| f.file_name().unwrap()).collect();
+        assert_eq!(names, ["a.yar", "b.yara"]);
+        assert!(rule_files(&[dir.path().join("missing.yar")]).is_err());
+    }
+
+    #[cfg(feature = "yara")]
+    #[test]
+    fn test_detect_with_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("dropper.yar"),
+            r#"
+            rule base64_python_dropper {
+                meta:
+                    description = "Decodes and executes an embedded payload"
+                    severity = "critical"
+                strings:
+                    $exec = "exec(base64.b64decode("
+                condition:
+                    $exec
+            }
+            "#,
+        )
+        .unwrap();
+
+        let rules = YaraRules::load(&[dir.path().to_path_buf()]).unwrap();
+        let vulns = detect(
+            "import base64\nexec(base64.b64decode(PAYLOAD))\n",
+            "setup.py",
+            &rules,
+        )
+        .unwrap();
+        assert_eq!(vulns.len(), 1);
+        assert_eq!(vulns[0].severity, Severity::Critical);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("base64_python_dropper"));
+        assert_eq!(vulns[0].location.as_ref().unwrap().line, Some(2));
+    }
+
+    #[cfg(not(feature = "yara"))]
+    #[test]
+    fn test_rules_require_feature() {
+        assert!(YaraRules::load(&[]).is_err());
+    }
+}
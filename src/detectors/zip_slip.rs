@@ -0,0 +1,191 @@
+//! Zip slip / archive extraction detection - CWE-22 (CWE-23, CWE-36)
+//!
+//! Archive entry names are attacker-controlled. An entry named
+//! `../../.bashrc` or `/etc/cron.d/job` escapes the extraction directory when
+//! the name is joined to a destination path as-is, which turns "unpack this
+//! file for me" tools into arbitrary file writes.
+//!
+//! Unlike the generic path traversal patterns, these flag extraction code
+//! itself: bulk `extractall` calls, entry names joined to paths, and tar
+//! modes that keep absolute paths. A containment check within
+//! [`CHECK_WINDOW_LINES`] of the extraction suppresses the finding.
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+/// How many lines around an extraction a containment check is looked for
+const CHECK_WINDOW_LINES: usize = 5;
+
+struct ZipSlipPattern {
+    name: &'static str,
+    language: &'static str,
+    regex: Regex,
+    cwe: &'static str,
+    remediation: &'static str,
+}
+
+/// Normalizing an entry path and checking it stays inside the destination
+static CONTAINMENT_CHECK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)\bstarts_?with\s*\(|\bis_relative_to\s*\(|\bcommonpath\s*\(|\brealpath\s*\(|\bgetCanonicalPath\s*\(|\btoRealPath\s*\(|\.normalize\s*\(\s*\)|\bpath\.relative\s*\(|\bfilter\s*=\s*["'](?:data|tar)["']"#,
+    )
+    .unwrap()
+});
+
+static ZIP_SLIP_PATTERNS: Lazy<Vec<ZipSlipPattern>> = Lazy::new(|| {
+    vec![
+        ZipSlipPattern {
+            name: "Tar extraction keeps absolute paths",
+            language: "Python/Shell",
+            regex: Regex::new(
+                r#"\bfilter\s*=\s*["']fully_trusted["']|\btar\b[^|;&]*(?:\s-[a-zA-Z]*P[a-zA-Z]*\b|--absolute-names)"#,
+            )
+            .unwrap(),
+            cwe: "CWE-36",
+            remediation: "Drop -P/--absolute-names (or filter='fully_trusted') so tar strips \
+                          leading '/' from entry names, and extract into a dedicated directory",
+        },
+        ZipSlipPattern {
+            name: "Archive extractall without member validation",
+            language: "Python",
+            regex: Regex::new(r#"\.extractall\s*\(|\bshutil\.unpack_archive\s*\("#).unwrap(),
+            cwe: "CWE-22",
+            remediation: "Pass filter='data' to tarfile.extractall (Python 3.12+), or check every \
+                          member's resolved path stays inside the destination before extracting",
+        },
+        ZipSlipPattern {
+            name: "Archive entry name joined to extraction path",
+            language: "Python",
+            regex: Regex::new(
+                r#"(?:\bos\.path\.join\s*\([^)]*|/\s*)\b(?:member|info|zinfo|tarinfo|entry)\.(?:name|filename)\b"#,
+            )
+            .unwrap(),
+            cwe: "CWE-23",
+            remediation: "Resolve the joined path and verify it is inside the destination \
+                          (os.path.commonpath or Path.is_relative_to) before writing",
+        },
+        ZipSlipPattern {
+            name: "Archive entry name joined to extraction path",
+            language: "JavaScript/TypeScript",
+            regex: Regex::new(
+                r#"\bpath\.(?:join|resolve)\s*\([^)]*\b(?:entry|file|header|zipEntry)\.(?:path|fileName|name|entryName)\b"#,
+            )
+            .unwrap(),
+            cwe: "CWE-23",
+            remediation: "Resolve the joined path and verify it starts with the resolved \
+                          destination plus path.sep before writing",
+        },
+        ZipSlipPattern {
+            name: "Archive entry name joined to extraction path",
+            language: "Java",
+            regex: Regex::new(
+                r#"\b(?:new\s+File|Paths\.get|Path\.of|\.resolve)\s*\([^;]*\b\w*[eE]ntry\.getName\s*\(\s*\)"#,
+            )
+            .unwrap(),
+            cwe: "CWE-23",
+            remediation: "Compare the file's getCanonicalPath() (or normalized Path) against the \
+                          destination directory and reject entries outside it",
+        },
+    ]
+});
+
+pub fn detect(content: &str, file_path: &str) -> Result<Vec<Vulnerability>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut vulnerabilities = Vec::new();
+    let mut id_counter = 1;
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let Some(pattern) = ZIP_SLIP_PATTERNS.iter().find(|p| p.regex.is_match(line)) else {
+            continue;
+        };
+
+        let window = &lines[line_num.saturating_sub(CHECK_WINDOW_LINES)
+            ..lines.len().min(line_num + 1 + CHECK_WINDOW_LINES)];
+        if window.iter().any(|l| CONTAINMENT_CHECK.is_match(l)) {
+            continue;
+        }
+
+        for m in pattern.regex.find_iter(line) {
+            let mut evidence = HashMap::new();
+            evidence.insert("language".to_string(), serde_json::json!(pattern.language));
+            evidence.insert("cwe".to_string(), serde_json::json!(pattern.cwe));
+
+            vulnerabilities.push(
+                Vulnerability::new(
+                    format!("ZIPSLIP-{:03}", id_counter),
+                    VulnerabilityType::PathTraversal,
+                    Severity::High,
+                    format!("Zip Slip: {}", pattern.name),
+                    "Archive entry names are used as file paths without checking they stay inside \
+                     the extraction directory",
+                )
+                .with_rule_id(super::rule_slug(pattern.name))
+                .with_pattern(pattern.regex.as_str())
+                .with_location(super::match_location(file_path, line_num, line, m.range()))
+                .with_impact(
+                    "A crafted archive can overwrite files anywhere the server can write, such as \
+                     shell profiles, SSH keys, or the server's own code",
+                )
+                .with_remediation(pattern.remediation)
+                .with_code_snippet(line.to_string())
+                .with_confidence(0.75)
+                .with_evidence(evidence),
+            );
+            id_counter += 1;
+        }
+    }
+
+    Ok(vulnerabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_unsafe_extraction() {
+        let content = r#"
+with tarfile.open(upload) as tar:
+    tar.extractall(dest)
+for member in archive.infolist():
+    target = os.path.join(dest, member.filename)
+subprocess.run(f"tar -xPf {upload} -C /", shell=True)
+"#;
+        let vulns = detect(content, "tools.py").unwrap();
+        let cwes: Vec<_> = vulns
+            .iter()
+            .map(|v| v.evidence.as_ref().unwrap()["cwe"].clone())
+            .collect();
+        assert_eq!(cwes, vec!["CWE-22", "CWE-23", "CWE-36"]);
+        assert_eq!(vulns[0].vuln_type, VulnerabilityType::PathTraversal);
+    }
+
+    #[test]
+    fn test_detect_js_and_java_joins() {
+        let content = r#"
+zip.on('entry', (entry) => entry.pipe(fs.createWriteStream(path.join(dest, entry.path))));
+File out = new File(destDir, zipEntry.getName());
+"#;
+        let vulns = detect(content, "Extract.java").unwrap();
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(
+            vulns[1].rule_id.as_deref(),
+            Some("archive-entry-name-joined-to-extraction-path")
+        );
+    }
+
+    #[test]
+    fn test_checked_extraction_not_flagged() {
+        let content = r#"
+tar.extractall(dest, filter="data")
+target = os.path.realpath(os.path.join(dest, member.name))
+if not target.startswith(os.path.realpath(dest) + os.sep):
+    raise ValueError("blocked")
+"#;
+        assert!(detect(content, "tools.py").unwrap().is_empty());
+    }
+}
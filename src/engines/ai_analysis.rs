@@ -0,0 +1,123 @@
+//! LLM client for AI-assisted analysis
+//!
+//! A thin, provider-neutral completion API over the providers in
+//! [`LlmConfig`]. Requests are single-turn and deterministic (temperature 0).
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::models::config::LlmConfig;
+use crate::utils::network;
+
+/// Default Ollama endpoint for `--llm-provider local`
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+const MAX_TOKENS: u32 = 2048;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Completion client for the configured provider
+pub struct LlmClient {
+    config: LlmConfig,
+    http: reqwest::Client,
+}
+
+impl LlmClient {
+    /// Hosted providers are refused in offline mode; an Ollama server on
+    /// the loopback interface is still allowed.
+    pub fn new(config: LlmConfig) -> Result<Self> {
+        if !is_local(&config) {
+            network::ensure_online("LLM analysis")?;
+        }
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self { config, http })
+    }
+
+    /// Model name used for responses
+    pub fn model(&self) -> &str {
+        match &self.config {
+            LlmConfig::OpenAI { model, .. }
+            | LlmConfig::Anthropic { model, .. }
+            | LlmConfig::Ollama { model, .. } => model,
+        }
+    }
+
+    /// Send a system + user prompt and return the text of the reply
+    pub async fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        debug!("LLM request to {} ({} chars)", self.model(), prompt.len());
+        let (request, extract): (_, fn(&Value) -> Option<&str>) = match &self.config {
+            LlmConfig::OpenAI { api_key, model } => (
+                self.http
+                    .post("https://api.openai.com/v1/chat/completions")
+                    .bearer_auth(api_key)
+                    .json(&json!({
+                        "model": model,
+                        "temperature": 0,
+                        "max_tokens": MAX_TOKENS,
+                        "messages": [
+                            { "role": "system", "content": system },
+                            { "role": "user", "content": prompt },
+                        ],
+                    })),
+                |v| v["choices"][0]["message"]["content"].as_str(),
+            ),
+            LlmConfig::Anthropic { api_key, model } => (
+                self.http
+                    .post("https://api.anthropic.com/v1/messages")
+                    .header("x-api-key", api_key)
+                    .header("anthropic-version", "2023-06-01")
+                    .json(&json!({
+                        "model": model,
+                        "temperature": 0,
+                        "max_tokens": MAX_TOKENS,
+                        "system": system,
+                        "messages": [{ "role": "user", "content": prompt }],
+                    })),
+                |v| v["content"][0]["text"].as_str(),
+            ),
+            LlmConfig::Ollama { base_url, model } => (
+                self.http
+                    .post(format!("{}/api/chat", base_url.trim_end_matches('/')))
+                    .json(&json!({
+                        "model": model,
+                        "stream": false,
+                        "options": { "temperature": 0 },
+                        "messages": [
+                            { "role": "system", "content": system },
+                            { "role": "user", "content": prompt },
+                        ],
+                    })),
+                |v| v["message"]["content"].as_str(),
+            ),
+        };
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("LLM request to {} failed", self.model()))?
+            .error_for_status()?;
+        let body: Value = response.json().await?;
+        extract(&body)
+            .map(str::to_string)
+            .with_context(|| format!("Unexpected LLM response shape from {}", self.model()))
+    }
+}
+
+/// Whether the provider runs on this machine
+fn is_local(config: &LlmConfig) -> bool {
+    let LlmConfig::Ollama { base_url, .. } = config else {
+        return false;
+    };
+    let Ok(url) = url::Url::parse(base_url) else {
+        return false;
+    };
+    match url.host() {
+        Some(url::Host::Domain(host)) => host.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
@@ -0,0 +1,559 @@
+//! Protocol fuzzing
+//!
+//! Feeds a launched server input a well-behaved client never sends:
+//! malformed JSON-RPC, oversized strings, deeply nested params, and tool
+//! arguments of the wrong type. Each case is followed by a `ping`, and the
+//! server is judged by what happens until the `ping` is answered:
+//! - it exits: a crash (the server is relaunched for the next case)
+//! - the `ping` times out: a hang (likewise relaunched)
+//! - a stack trace shows up in a response or on stderr: an unhandled error
+//!
+//! Non-JSON lines on stdout are reported once; real clients drop the
+//! connection over them.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Child;
+use tracing::{debug, info};
+
+use super::{spawn, Session, DEFAULT_TIMEOUT};
+use crate::engines::runtime_proxy::jsonrpc::DEFAULT_MAX_MESSAGE_BYTES;
+use crate::engines::runtime_proxy::stdio::{read_frame, Frame};
+use crate::models::mcp_protocol::ToolDefinition;
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+/// Default time a server gets to answer the `ping` after a case
+pub const DEFAULT_CASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Length of the oversized strings
+const OVERSIZED_LEN: usize = 1 << 20;
+/// Depth of the nested arrays
+const NESTING_DEPTH: usize = 10_000;
+/// Tools whose arguments are fuzzed
+const MAX_FUZZED_TOOLS: usize = 20;
+/// Stderr lines kept per case
+const STDERR_TAIL: usize = 50;
+
+/// Request ids of the cases and of the `ping` after them
+const CASE_ID: i64 = 900_001;
+const PING_ID: i64 = 900_000;
+
+/// Stack traces of the usual server runtimes
+static STACK_TRACE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"Traceback \(most recent call last\)|File "[^"]+", line \d+|^\s*at .+:\d+:\d+\)?$|panicked at|Unhandled(?:PromiseRejection| exception)|Exception in thread|goroutine \d+ \["#)
+        .unwrap()
+});
+
+/// One input sent to the server
+#[derive(Debug, Clone)]
+pub struct FuzzCase {
+    pub name: String,
+    /// A single line, without the newline
+    pub payload: Vec<u8>,
+}
+
+impl FuzzCase {
+    fn new(name: impl Into<String>, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            name: name.into(),
+            payload: payload.into(),
+        }
+    }
+}
+
+/// How the server handled a case
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Handled,
+    Crashed {
+        status: String,
+    },
+    Hung,
+    /// A stack trace, returned to the client or only logged
+    UnhandledError {
+        trace: String,
+        returned: bool,
+    },
+}
+
+/// Launch `command` in `dir` and run every case against it
+///
+/// The server runs in a sandbox unless `unsandboxed` is set.
+pub async fn fuzz(
+    command: &[String],
+    dir: &Path,
+    case_timeout: Duration,
+    unsandboxed: bool,
+) -> Result<Vec<Vulnerability>> {
+    let home = tempfile::tempdir()?;
+    let mut target = Target::start(command, dir, home.path(), unsandboxed).await?;
+    let cases = cases(&target.tools);
+    info!(
+        "Fuzzing {} with {} cases ({} tools)",
+        command.join(" "),
+        cases.len(),
+        target.tools.len().min(MAX_FUZZED_TOOLS)
+    );
+
+    let mut vulnerabilities = Vec::new();
+    let mut reported_stray_output = false;
+    for case in &cases {
+        let outcome = target.run(case, case_timeout).await;
+        debug!("{}: {:?}", case.name, outcome);
+        if let Some(line) = target.stray_output.take() {
+            if !reported_stray_output {
+                reported_stray_output = true;
+                vulnerabilities.push(stray_output_finding(&line));
+            }
+        }
+        if outcome == Outcome::Handled {
+            continue;
+        }
+        let restart = matches!(outcome, Outcome::Crashed { .. } | Outcome::Hung);
+        vulnerabilities.push(finding(case, &outcome, case_timeout));
+        if restart {
+            target = Target::start(command, dir, home.path(), unsandboxed).await?;
+        }
+    }
+
+    for (i, vuln) in vulnerabilities.iter_mut().enumerate() {
+        vuln.id = format!("FUZZ-{:03}", i + 1);
+    }
+    Ok(vulnerabilities)
+}
+
+/// The cases for a server exposing `tools`
+pub fn cases(tools: &[ToolDefinition]) -> Vec<FuzzCase> {
+    let request = |method: &str, params: Value| {
+        json!({ "jsonrpc": "2.0", "id": CASE_ID, "method": method, "params": params }).to_string()
+    };
+    let huge = "A".repeat(OVERSIZED_LEN);
+    let nested = format!("{}{}", "[".repeat(NESTING_DEPTH), "]".repeat(NESTING_DEPTH));
+
+    let mut cases = vec![
+        FuzzCase::new(
+            "truncated JSON",
+            format!(
+                r#"{{"jsonrpc": "2.0", "id": {}, "method": "tools/li"#,
+                CASE_ID
+            ),
+        ),
+        FuzzCase::new("non-JSON line", "GET / HTTP/1.1"),
+        FuzzCase::new(
+            "invalid UTF-8",
+            &b"{\"jsonrpc\": \"2.0\", \"id\": 900001, \"method\": \"\xff\xfe\"}"[..],
+        ),
+        FuzzCase::new("bare JSON number", "42"),
+        FuzzCase::new("empty batch", "[]"),
+        FuzzCase::new(
+            "batch request",
+            json!([
+                { "jsonrpc": "2.0", "id": CASE_ID, "method": "tools/list" },
+                { "jsonrpc": "2.0", "id": CASE_ID, "method": "tools/list" },
+            ])
+            .to_string(),
+        ),
+        FuzzCase::new(
+            "request without method",
+            json!({ "jsonrpc": "2.0", "id": CASE_ID }).to_string(),
+        ),
+        FuzzCase::new(
+            "wrong JSON-RPC version",
+            json!({ "jsonrpc": "1.0", "id": CASE_ID, "method": "tools/list" }).to_string(),
+        ),
+        FuzzCase::new(
+            "non-string method",
+            json!({ "jsonrpc": "2.0", "id": CASE_ID, "method": 42 }).to_string(),
+        ),
+        FuzzCase::new(
+            "object as request id",
+            json!({ "jsonrpc": "2.0", "id": { "id": [CASE_ID] }, "method": "tools/list" })
+                .to_string(),
+        ),
+        FuzzCase::new("unknown method", request("sentinel/fuzz", json!({}))),
+        FuzzCase::new(
+            "params of the wrong type",
+            request("tools/list", json!("all")),
+        ),
+        FuzzCase::new(
+            "repeated initialize with bad params",
+            request(
+                "initialize",
+                json!({ "protocolVersion": 7, "capabilities": "all" }),
+            ),
+        ),
+        FuzzCase::new("oversized method name", request(&huge, json!({}))),
+        FuzzCase::new(
+            "oversized cursor",
+            request("tools/list", json!({ "cursor": huge })),
+        ),
+        FuzzCase::new(
+            "deeply nested params",
+            format!(
+                r#"{{"jsonrpc": "2.0", "id": {}, "method": "tools/list", "params": {{"cursor": {}}}}}"#,
+                CASE_ID, nested
+            ),
+        ),
+        FuzzCase::new(
+            "call of an unknown tool",
+            request(
+                "tools/call",
+                json!({ "name": "sentinel_fuzz_missing", "arguments": {} }),
+            ),
+        ),
+    ];
+
+    for tool in tools.iter().take(MAX_FUZZED_TOOLS) {
+        let name = &tool.name;
+        let properties = tool
+            .input_schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let call = |arguments: Value| {
+            request(
+                "tools/call",
+                json!({ "name": name, "arguments": arguments }),
+            )
+        };
+
+        cases.push(FuzzCase::new(
+            format!("{} without arguments", name),
+            request("tools/call", json!({ "name": name })),
+        ));
+        cases.push(FuzzCase::new(
+            format!("{} with type-confused arguments", name),
+            call(confused_arguments(&properties)),
+        ));
+        let oversized: serde_json::Map<String, Value> = properties
+            .keys()
+            .map(|key| (key.clone(), json!(huge)))
+            .collect();
+        cases.push(FuzzCase::new(
+            format!("{} with oversized arguments", name),
+            call(Value::Object(oversized)),
+        ));
+        let key = properties.keys().next().map_or("input", String::as_str);
+        cases.push(FuzzCase::new(
+            format!("{} with deeply nested arguments", name),
+            format!(
+                r#"{{"jsonrpc": "2.0", "id": {}, "method": "tools/call", "params": {{"name": {}, "arguments": {{{}: {}}}}}}}"#,
+                CASE_ID,
+                json!(name),
+                json!(key),
+                nested
+            ),
+        ));
+    }
+    cases
+}
+
+/// Arguments with every property set to a value of another type
+fn confused_arguments(properties: &serde_json::Map<String, Value>) -> Value {
+    if properties.is_empty() {
+        // Not even an object
+        return json!(["unexpected"]);
+    }
+    let confused = properties
+        .iter()
+        .map(|(key, schema)| {
+            let value = match schema.get("type").and_then(Value::as_str) {
+                Some("string") => json!(-1),
+                Some("integer" | "number") => json!("NaN"),
+                Some("boolean") => json!("false"),
+                Some("array") => json!({ "0": null }),
+                Some("object") => json!([null]),
+                _ => Value::Null,
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    Value::Object(confused)
+}
+
+/// First stack trace line in `texts`, with the line before it
+fn find_trace<'a>(texts: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    texts.into_iter().find_map(|text| {
+        let lines: Vec<&str> = text.lines().collect();
+        let i = lines.iter().position(|line| STACK_TRACE.is_match(line))?;
+        Some(lines[i.saturating_sub(1)..=i].join("\n"))
+    })
+}
+
+/// All string values in `value`
+fn strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(s) => out.push(s),
+        Value::Array(items) => items.iter().for_each(|v| strings(v, out)),
+        Value::Object(map) => map.values().for_each(|v| strings(v, out)),
+        _ => {}
+    }
+}
+
+/// A running server under test
+struct Target {
+    child: Child,
+    session: Session,
+    tools: Vec<ToolDefinition>,
+    stderr: Arc<Mutex<VecDeque<String>>>,
+    /// A non-JSON line seen on stdout since it was last taken
+    stray_output: Option<String>,
+}
+
+impl Target {
+    async fn start(command: &[String], dir: &Path, home: &Path, unsandboxed: bool) -> Result<Self> {
+        let mut child = spawn(command, dir, home, Stdio::piped(), unsandboxed)?;
+        let stderr = Arc::new(Mutex::new(VecDeque::new()));
+        if let Some(pipe) = child.stderr.take() {
+            let tail = Arc::clone(&stderr);
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(pipe).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let mut tail = tail.lock().unwrap();
+                    if tail.len() == STDERR_TAIL {
+                        tail.pop_front();
+                    }
+                    tail.push_back(line);
+                }
+            });
+        }
+        let mut session = Session::new(&mut child)?;
+        let surface = tokio::time::timeout(DEFAULT_TIMEOUT, session.enumerate())
+            .await
+            .context("MCP server did not finish initializing")??;
+        Ok(Self {
+            child,
+            session,
+            tools: surface.tools,
+            stderr,
+            stray_output: None,
+        })
+    }
+
+    async fn run(&mut self, case: &FuzzCase, timeout: Duration) -> Outcome {
+        self.stderr.lock().unwrap().clear();
+        let exchange = tokio::time::timeout(timeout, self.exchange(&case.payload)).await;
+        let responses = match exchange {
+            Err(_) => return Outcome::Hung,
+            Ok(Ok(Some(responses))) => responses,
+            Ok(Ok(None)) | Ok(Err(_)) => {
+                let status = tokio::time::timeout(Duration::from_secs(1), self.child.wait()).await;
+                return Outcome::Crashed {
+                    status: match status {
+                        Ok(Ok(status)) => status.to_string(),
+                        _ => "closed its output".to_string(),
+                    },
+                };
+            }
+        };
+
+        let mut texts = Vec::new();
+        responses.iter().for_each(|r| strings(r, &mut texts));
+        if let Some(trace) = find_trace(texts) {
+            return Outcome::UnhandledError {
+                trace,
+                returned: true,
+            };
+        }
+        // Give stderr a moment to catch up with stdout
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stderr = self
+            .stderr
+            .lock()
+            .unwrap()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        match find_trace([stderr.join("\n").as_str()]) {
+            Some(trace) => Outcome::UnhandledError {
+                trace,
+                returned: false,
+            },
+            None => Outcome::Handled,
+        }
+    }
+
+    /// Send `payload` and a `ping`; the responses before the `ping`'s, or
+    /// `None` if the server exits
+    async fn exchange(&mut self, payload: &[u8]) -> Result<Option<Vec<Value>>> {
+        self.session.stdin.write_all(payload).await?;
+        self.session.stdin.write_all(b"\n").await?;
+        self.session
+            .send(&json!({ "jsonrpc": "2.0", "id": PING_ID, "method": "ping" }))
+            .await?;
+
+        let mut responses = Vec::new();
+        loop {
+            let line = match read_frame(&mut self.session.stdout, DEFAULT_MAX_MESSAGE_BYTES).await?
+            {
+                None => return Ok(None),
+                Some(Frame::Oversized(len)) => {
+                    debug!("Skipping a {} byte message", len);
+                    continue;
+                }
+                Some(Frame::Message(line)) => line,
+            };
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                self.stray_output.get_or_insert(line);
+                continue;
+            };
+            if message.get("method").is_some() {
+                if let Some(id) = message.get("id") {
+                    self.session
+                        .send(&json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": { "code": -32601, "message": "Not supported during analysis" },
+                        }))
+                        .await?;
+                }
+                continue;
+            }
+            if message.get("id") == Some(&json!(PING_ID)) {
+                return Ok(Some(responses));
+            }
+            responses.push(message);
+        }
+    }
+}
+
+fn finding(case: &FuzzCase, outcome: &Outcome, case_timeout: Duration) -> Vulnerability {
+    let (severity, rule, title, description) = match outcome {
+        Outcome::Crashed { status } => (
+            Severity::High,
+            "fuzz/crash",
+            "Server crashes on malformed input",
+            format!(
+                "The server exited ({}) after receiving: {}",
+                status, case.name
+            ),
+        ),
+        Outcome::Hung => (
+            Severity::Medium,
+            "fuzz/hang",
+            "Server stops responding on malformed input",
+            format!(
+                "The server did not answer a ping within {:?} after receiving: {}",
+                case_timeout, case.name
+            ),
+        ),
+        Outcome::UnhandledError { trace, returned } => (
+            if *returned {
+                Severity::Medium
+            } else {
+                Severity::Low
+            },
+            "fuzz/unhandled-error",
+            "Unhandled error on malformed input",
+            format!(
+                "Receiving {} raised an error that reached the top level{}:\n{}",
+                case.name,
+                if *returned {
+                    ", and its stack trace was sent to the client"
+                } else {
+                    ""
+                },
+                trace
+            ),
+        ),
+        Outcome::Handled => unreachable!("handled cases are not findings"),
+    };
+
+    let mut snippet = String::from_utf8_lossy(&case.payload).into_owned();
+    if snippet.len() > 200 {
+        let end = (0..=200)
+            .rev()
+            .find(|&i| snippet.is_char_boundary(i))
+            .unwrap_or(0);
+        snippet.truncate(end);
+        snippet.push_str("...");
+    }
+    Vulnerability::new(
+        String::new(),
+        VulnerabilityType::Robustness,
+        severity,
+        title,
+        description,
+    )
+    .with_rule_id(rule)
+    .with_location(Location::new(format!("fuzz#{}", case.name)))
+    .with_impact("A client or another server can take the server down or learn its internals")
+    .with_remediation(
+        "Validate requests and tool arguments against their schema before use, cap input sizes \
+         and nesting, and answer failures with JSON-RPC errors instead of raising",
+    )
+    .with_code_snippet(snippet)
+    .with_confidence(0.9)
+}
+
+fn stray_output_finding(line: &str) -> Vulnerability {
+    let sample: String = line.chars().take(200).collect();
+    Vulnerability::new(
+        String::new(),
+        VulnerabilityType::Robustness,
+        Severity::Low,
+        "Server writes non-protocol output to stdout",
+        "The server printed a line that is not JSON-RPC on stdout, which stdio clients treat \
+         as a protocol error",
+    )
+    .with_rule_id("fuzz/non-protocol-output")
+    .with_location(Location::new("fuzz#stdout"))
+    .with_remediation("Send logs and diagnostics to stderr")
+    .with_code_snippet(sample)
+    .with_confidence(0.95)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases() {
+        let tool = ToolDefinition {
+            name: "get_forecast".to_string(),
+            description: String::new(),
+            input_schema: json!({
+                "type": "object",
+                "properties": { "city": { "type": "string" }, "days": { "type": "integer" } },
+            }),
+        };
+        let cases = cases(&[tool]);
+        let case = |name: &str| cases.iter().find(|c| c.name == name).unwrap();
+
+        let confused: Value =
+            serde_json::from_slice(&case("get_forecast with type-confused arguments").payload)
+                .unwrap();
+        assert_eq!(
+            confused["params"]["arguments"],
+            json!({ "city": -1, "days": "NaN" })
+        );
+        assert!(case("oversized cursor").payload.len() > OVERSIZED_LEN);
+        assert!(std::str::from_utf8(&case("invalid UTF-8").payload).is_err());
+        assert!(cases.iter().all(|c| !c.payload.contains(&b'\n')));
+    }
+
+    #[test]
+    fn test_find_trace() {
+        let python = "Error in handler\nTraceback (most recent call last):\n  \
+                      File \"/srv/server.py\", line 12, in call";
+        assert_eq!(
+            find_trace([python]).as_deref(),
+            Some("Error in handler\nTraceback (most recent call last):")
+        );
+        let node = "TypeError: x.map is not a function\n    at handle (/srv/index.js:40:7)";
+        assert!(find_trace([node]).unwrap().contains("index.js:40:7"));
+        assert_eq!(
+            find_trace(["Invalid params: days must be an integer"]),
+            None
+        );
+    }
+}
@@ -0,0 +1,610 @@
+//! Dynamic analysis
+//!
+//! Launches the target MCP server as a child process, talks to it as a
+//! client over stdio, and enumerates what it actually exposes:
+//! `initialize`, then `tools/list`, `prompts/list`, `resources/list`, and
+//! `resources/templates/list` for each capability the server advertises.
+//!
+//! Everything returned goes through the definition-level detectors (see
+//! [`crate::detectors::scan_live_surface`]), and the live tool set is
+//! diffed against the tool registrations static analysis found. Tools that
+//! only exist at runtime were generated or fetched at startup, which is how
+//! rug pulls hide from source review.
+//!
+//! The server runs in the target directory inside a [`sandbox`], with a
+//! scrubbed environment (`PATH` only) and a throwaway `HOME`, so it can
+//! neither pick up the caller's credentials nor change their files. Running
+//! it unconfined takes an explicit opt-in. Its file and network activity is
+//! watched ([`monitor`])
+//! from launch until the end of an observation window after enumeration;
+//! then it is killed.
+
+pub mod fuzz;
+pub mod monitor;
+pub mod sandbox;
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use super::runtime_proxy::jsonrpc::DEFAULT_MAX_MESSAGE_BYTES;
+use super::runtime_proxy::stdio::{read_frame, Frame};
+use crate::api::mcp_server::PROTOCOL_VERSION;
+use crate::detectors::toxic_flows::ToolProfile;
+use crate::models::config::ScanConfig;
+use crate::models::mcp_protocol::ToolDefinition;
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+use crate::utils::process;
+use monitor::{Behavior, Decoys};
+
+/// Default limit on launching and enumerating a server
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time the server keeps running under observation after
+/// enumeration
+pub const DEFAULT_OBSERVE: Duration = Duration::from_secs(5);
+
+/// Pages fetched per listing before giving up on a server that never stops
+/// returning `nextCursor`
+const MAX_PAGES: usize = 50;
+
+/// What a running server exposes
+#[derive(Debug, Clone, Default)]
+pub struct LiveSurface {
+    /// `serverInfo` from the `initialize` result
+    pub server_info: Value,
+    pub tools: Vec<ToolDefinition>,
+    pub prompts: Vec<Value>,
+    pub resources: Vec<Value>,
+    pub resource_templates: Vec<Value>,
+}
+
+/// How to launch and watch a server
+#[derive(Debug, Clone)]
+pub struct LaunchOptions {
+    /// Limit on starting the server and enumerating its surface
+    pub timeout: Duration,
+    /// Observation window after enumeration
+    pub observe: Duration,
+    /// Hosts the server is expected to talk to (from the egress inventory)
+    pub expected_hosts: Vec<String>,
+    /// Run the server without a sandbox, with only a scrubbed environment
+    pub unsandboxed: bool,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            observe: DEFAULT_OBSERVE,
+            expected_hosts: Vec::new(),
+            unsandboxed: false,
+        }
+    }
+}
+
+/// Everything learned from one launch of a server
+#[derive(Debug, Clone, Default)]
+pub struct LiveRun {
+    pub surface: LiveSurface,
+    pub behavior: Behavior,
+}
+
+impl LiveSurface {
+    /// The prompt and resource listings in the shape MCP returns them
+    pub fn listings(&self) -> Vec<Value> {
+        vec![
+            json!({ "prompts": self.prompts }),
+            json!({ "resources": self.resources }),
+            json!({ "resourceTemplates": self.resource_templates }),
+        ]
+    }
+}
+
+/// Guess how to start the server in `dir`
+///
+/// Uses the `bin` or `main` entry of `package.json`, or a conventional
+/// Python entry point. Returns `None` when nothing fits; callers then need
+/// an explicit command.
+pub fn infer_command(dir: &Path) -> Option<Vec<String>> {
+    let package = std::fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok());
+    if let Some(package) = package {
+        let bin = match package.get("bin") {
+            Some(Value::String(bin)) => Some(bin.as_str()),
+            Some(Value::Object(bins)) => bins.values().find_map(Value::as_str),
+            _ => None,
+        };
+        if let Some(entry) = bin.or_else(|| package.get("main").and_then(Value::as_str)) {
+            return Some(vec!["node".to_string(), entry.to_string()]);
+        }
+    }
+
+    ["server.py", "main.py", "__main__.py", "src/server.py"]
+        .iter()
+        .find(|file| dir.join(file).is_file())
+        .map(|file| vec!["python3".to_string(), file.to_string()])
+}
+
+/// Launch `command` in `dir`, enumerate its live surface, and watch what
+/// it does
+pub async fn launch(command: &[String], dir: &Path, options: &LaunchOptions) -> Result<LiveRun> {
+    info!(
+        "Launching MCP server for dynamic analysis: {}",
+        command.join(" ")
+    );
+
+    let home = tempfile::tempdir()?;
+    let decoys = Decoys::plant(home.path())?;
+    let mut child = spawn(
+        command,
+        dir,
+        home.path(),
+        Stdio::null(),
+        options.unsandboxed,
+    )?;
+
+    let stop = CancellationToken::new();
+    let sampler = {
+        let (pid, stop) = (child.id(), stop.clone());
+        tokio::task::spawn_blocking(move || monitor::sample(pid, stop))
+    };
+
+    let mut session = Session::new(&mut child)?;
+    let surface = tokio::time::timeout(options.timeout, session.enumerate())
+        .await
+        .with_context(|| format!("MCP server did not respond within {:?}", options.timeout))
+        .and_then(|surface| surface);
+    if surface.is_ok() {
+        // Keep the session open: many servers exit when stdin closes
+        tokio::time::sleep(options.observe).await;
+    }
+    stop.cancel();
+    drop(session);
+    if let Err(e) = child.kill().await {
+        debug!("Failed to stop MCP server: {}", e);
+    }
+
+    let mut behavior = sampler.await?;
+    behavior.credential_reads.extend(decoys.touched());
+    let expected = match crate::utils::network::ensure_online("Resolving expected hosts") {
+        Ok(()) => monitor::resolve(&options.expected_hosts).await,
+        Err(e) => {
+            warn!("{}; every connection counts as unexpected", e);
+            BTreeSet::new()
+        }
+    };
+    behavior
+        .connections
+        .retain(|addr| addr.port() != 53 && !expected.contains(&addr.ip()));
+
+    Ok(LiveRun {
+        surface: surface?,
+        behavior,
+    })
+}
+
+/// Start `command` in `dir` in a [`sandbox`] (unless `unsandboxed`), with
+/// a scrubbed environment and `home` as `HOME` (and `USERPROFILE` on
+/// Windows); it is killed when the handle is dropped
+fn spawn(
+    command: &[String],
+    dir: &Path,
+    home: &Path,
+    stderr: Stdio,
+    unsandboxed: bool,
+) -> Result<Child> {
+    let (program, args) = command
+        .split_first()
+        .context("No MCP server command given")?;
+    let mut server = if unsandboxed {
+        warn!("Running the MCP server without a sandbox; it can read and change your files");
+        let mut server = Command::new(process::resolve(program));
+        server.args(args);
+        server
+    } else {
+        sandbox::command(&process::resolve(program), args, dir, home)?
+    };
+    server
+        .current_dir(dir)
+        .env_clear()
+        .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+        .env("HOME", home);
+    if cfg!(windows) {
+        server.env("USERPROFILE", home);
+        for key in process::WINDOWS_BASE_ENV {
+            if let Some(value) = std::env::var_os(key) {
+                server.env(key, value);
+            }
+        }
+    }
+    server
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(stderr)
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start MCP server '{}'", program))
+}
+
+/// Findings for a launch: definition checks, the diff against the
+/// statically found tools, and observed behavior
+pub fn analyze(
+    run: &LiveRun,
+    static_tools: &[ToolProfile],
+    config: &ScanConfig,
+) -> Vec<Vulnerability> {
+    let surface = &run.surface;
+    let mut vulnerabilities =
+        crate::detectors::scan_live_surface(&surface.tools, &surface.listings(), config);
+    if static_tools.is_empty() {
+        warn!("No tool registrations found in the source; skipping the static/live tool diff");
+    } else {
+        vulnerabilities.extend(
+            diff_tools(static_tools, &surface.tools)
+                .into_iter()
+                .filter(|v| v.severity >= config.min_severity),
+        );
+    }
+    vulnerabilities.extend(
+        behavior_findings(&run.behavior)
+            .into_iter()
+            .filter(|v| v.severity >= config.min_severity),
+    );
+    vulnerabilities
+}
+
+/// Findings for what the server was seen doing
+pub fn behavior_findings(behavior: &Behavior) -> Vec<Vulnerability> {
+    let mut vulnerabilities = Vec::new();
+
+    for path in &behavior.credential_reads {
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("BEHAV-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::SensitiveFileAccess,
+                Severity::Critical,
+                "Server read credentials at runtime",
+                format!(
+                    "The running server opened {}, a credential file no MCP server needs \
+                     to read on its own",
+                    path
+                ),
+            )
+            .with_rule_id("dynamic/credential-read")
+            .with_location(Location::new(path.clone()))
+            .with_impact("Credentials of whoever runs the server can be stolen")
+            .with_remediation(
+                "Find the code that reads this path; if it is intended, take the credential \
+                 from explicit configuration instead",
+            )
+            .with_confidence(0.85),
+        );
+    }
+
+    for addr in &behavior.connections {
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("BEHAV-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::DataExfiltration,
+                Severity::High,
+                "Connection to an unexpected host",
+                format!(
+                    "The running server connected to {}, which none of the hosts found in \
+                     the source resolves to",
+                    addr
+                ),
+            )
+            .with_rule_id("dynamic/unexpected-connection")
+            .with_location(Location::new(format!("network#{}", addr)))
+            .with_impact("Data can leave the machine through endpoints nobody reviewed")
+            .with_remediation(
+                "Identify the code opening the connection and allowlist the host, or \
+                 remove it",
+            )
+            .with_confidence(0.6),
+        );
+    }
+
+    vulnerabilities
+}
+
+/// Compare the tools static analysis predicted with the ones the server
+/// exposes
+pub fn diff_tools(static_tools: &[ToolProfile], live: &[ToolDefinition]) -> Vec<Vulnerability> {
+    let predicted: BTreeSet<&str> = static_tools.iter().map(|t| t.name.as_str()).collect();
+    let exposed: BTreeSet<&str> = live.iter().map(|t| t.name.as_str()).collect();
+    let mut vulnerabilities = Vec::new();
+
+    for name in exposed.difference(&predicted) {
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("DYN-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::ShadowTool,
+                Severity::Medium,
+                "Tool only exists at runtime",
+                format!(
+                    "The server exposes tool '{}', but no registration for it was found in \
+                     the source; it is generated or fetched at startup",
+                    name
+                ),
+            )
+            .with_rule_id("dynamic/undeclared-live-tool")
+            .with_location(Location::new(format!("tools/list#{}", name)))
+            .with_impact(
+                "Source review does not cover what this tool does, and it can change \
+                 without a release",
+            )
+            .with_remediation("Register tools statically so their code can be reviewed and pinned")
+            .with_confidence(0.6),
+        );
+    }
+
+    for name in predicted.difference(&exposed) {
+        let tool = static_tools.iter().find(|t| t.name == *name);
+        let mut location = Location::new(
+            tool.and_then(|t| t.file.clone())
+                .unwrap_or_else(|| "<source>".to_string()),
+        );
+        if let Some(line) = tool.and_then(|t| t.line) {
+            location = location.with_line(line);
+        }
+        vulnerabilities.push(
+            Vulnerability::new(
+                format!("DYN-{:03}", vulnerabilities.len() + 1),
+                VulnerabilityType::RugPull,
+                Severity::Low,
+                "Registered tool missing at runtime",
+                format!(
+                    "Tool '{}' is registered in the source but was not exposed by the running \
+                     server; registration depends on runtime conditions",
+                    name
+                ),
+            )
+            .with_rule_id("dynamic/tool-missing-at-runtime")
+            .with_location(location)
+            .with_impact("The tool set can change between environments or over time")
+            .with_remediation(
+                "Check which conditions gate the registration and whether clients are \
+                 notified when the tool list changes",
+            )
+            .with_confidence(0.4),
+        );
+    }
+
+    vulnerabilities
+}
+
+/// A client session over the child's stdio
+struct Session {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: i64,
+}
+
+impl Session {
+    fn new(child: &mut Child) -> Result<Self> {
+        Ok(Self {
+            stdin: child.stdin.take().context("Server stdin unavailable")?,
+            stdout: BufReader::new(child.stdout.take().context("Server stdout unavailable")?),
+            next_id: 0,
+        })
+    }
+
+    async fn enumerate(&mut self) -> Result<LiveSurface> {
+        let init = self
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": { "name": crate::NAME, "version": crate::VERSION },
+                }),
+            )
+            .await?;
+        self.send(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .await?;
+
+        let capabilities = init.get("capabilities").cloned().unwrap_or_default();
+        let offers = |capability: &str| capabilities.get(capability).is_some();
+        let mut surface = LiveSurface {
+            server_info: init.get("serverInfo").cloned().unwrap_or_default(),
+            ..LiveSurface::default()
+        };
+        if offers("tools") {
+            surface.tools = self
+                .list("tools/list", "tools")
+                .await?
+                .iter()
+                .map(|tool| {
+                    let text =
+                        |key: &str| tool.get(key).and_then(Value::as_str).unwrap_or_default();
+                    ToolDefinition {
+                        name: text("name").to_string(),
+                        description: text("description").to_string(),
+                        input_schema: tool.get("inputSchema").cloned().unwrap_or(Value::Null),
+                    }
+                })
+                .collect();
+        }
+        if offers("prompts") {
+            surface.prompts = self.list("prompts/list", "prompts").await?;
+        }
+        if offers("resources") {
+            surface.resources = self.list("resources/list", "resources").await?;
+            surface.resource_templates = self
+                .list("resources/templates/list", "resourceTemplates")
+                .await?;
+        }
+        info!(
+            "Live surface: {} tools, {} prompts, {} resources, {} resource templates",
+            surface.tools.len(),
+            surface.prompts.len(),
+            surface.resources.len(),
+            surface.resource_templates.len()
+        );
+        Ok(surface)
+    }
+
+    /// All items of a paginated listing
+    async fn list(&mut self, method: &str, key: &str) -> Result<Vec<Value>> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.request(method, params).await?;
+            items.extend(
+                result
+                    .get(key)
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default(),
+            );
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Send a request and wait for its response
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.next_id += 1;
+        let id = json!(self.next_id);
+        self.send(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await?;
+
+        loop {
+            let frame = read_frame(&mut self.stdout, DEFAULT_MAX_MESSAGE_BYTES)
+                .await?
+                .with_context(|| format!("MCP server exited before answering {}", method))?;
+            let line = match frame {
+                Frame::Message(line) => line,
+                Frame::Oversized(len) => anyhow::bail!("MCP server sent a {} byte message", len),
+            };
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                debug!("Ignoring non-JSON server output: {}", line);
+                continue;
+            };
+
+            // Server requests and notifications while we wait; decline requests
+            // so the server does not block on us
+            if message.get("method").is_some() {
+                if let Some(request_id) = message.get("id") {
+                    self.send(&json!({
+                        "jsonrpc": "2.0",
+                        "id": request_id,
+                        "error": { "code": -32601, "message": "Not supported during analysis" },
+                    }))
+                    .await?;
+                }
+                continue;
+            }
+            if message.get("id") != Some(&id) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                anyhow::bail!("{} failed: {}", method, error);
+            }
+            return Ok(message.get("result").cloned().unwrap_or_default());
+        }
+    }
+
+    async fn send(&mut self, message: &Value) -> Result<()> {
+        self.stdin.write_all(message.to_string().as_bytes()).await?;
+        self.stdin.write_all(b"\n").await?;
+        self.stdin.flush().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_command() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(infer_command(dir.path()), None);
+
+        std::fs::write(dir.path().join("server.py"), "").unwrap();
+        assert_eq!(
+            infer_command(dir.path()),
+            Some(vec!["python3".to_string(), "server.py".to_string()])
+        );
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "x", "bin": {"x-mcp": "dist/index.js"}, "main": "lib.js"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            infer_command(dir.path()),
+            Some(vec!["node".to_string(), "dist/index.js".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_diff_tools() {
+        let profile = |name: &str| ToolProfile {
+            name: name.to_string(),
+            capabilities: Default::default(),
+            file: Some("server.py".to_string()),
+            line: Some(4),
+        };
+        let definition = |name: &str| ToolDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: Value::Null,
+        };
+        let vulns = diff_tools(
+            &[profile("read_file"), profile("debug_dump")],
+            &[definition("read_file"), definition("sync_remote")],
+        );
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(
+            vulns[0].rule_id.as_deref(),
+            Some("dynamic/undeclared-live-tool")
+        );
+        assert!(vulns[0].description.contains("'sync_remote'"));
+        assert_eq!(vulns[1].severity, Severity::Low);
+        assert_eq!(vulns[1].location.as_ref().unwrap().line, Some(4));
+    }
+
+    #[test]
+    fn test_behavior_findings() {
+        let mut behavior = Behavior::default();
+        assert!(behavior_findings(&behavior).is_empty());
+
+        behavior
+            .credential_reads
+            .insert("~/.aws/credentials".to_string());
+        behavior
+            .connections
+            .insert("203.0.113.7:443".parse().unwrap());
+        let vulns = behavior_findings(&behavior);
+        assert_eq!(vulns.len(), 2);
+        assert_eq!(vulns[0].severity, Severity::Critical);
+        assert_eq!(vulns[0].rule_id.as_deref(), Some("dynamic/credential-read"));
+        assert_eq!(
+            vulns[1].rule_id.as_deref(),
+            Some("dynamic/unexpected-connection")
+        );
+        assert!(vulns[1].description.contains("203.0.113.7:443"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Confinement of launched servers
+//!
+//! A server under dynamic analysis is untrusted code, so it runs inside a
+//! sandbox:
+//! - on Linux under bubblewrap (`bwrap`): the filesystem is read-only, `/tmp`
+//!   is private, the caller's home directory is hidden, and the server gets
+//!   its own user, PID, IPC, and UTS namespaces. The network stays shared so
+//!   its connections can be watched ([`super::monitor`]).
+//! - on macOS under `sandbox-exec`, with a profile that denies writes outside
+//!   the throwaway `HOME` and temporary directories and reads of the
+//!   caller's home directory
+//!
+//! The target directory stays readable, and so do toolchains installed under
+//! the caller's home directory (`~/.cargo`, `~/.nvm/versions/node/<v>`,
+//! `~/.local`): the parent of each `PATH` entry below it is exposed read-only.
+//!
+//! Where neither tool is available, launching fails unless the caller opted
+//! into running the server unconfined, with only a scrubbed environment.
+
+use anyhow::{Context, Result};
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// `program` with `args`, wrapped in the platform's sandbox
+///
+/// The server may write to `home` only, and reads `dir` as its working
+/// directory.
+pub fn command(program: &Path, args: &[String], dir: &Path, home: &Path) -> Result<Command> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let caller_home = std::env::var_os("HOME").map(PathBuf::from);
+    let exposed = exposed_toolchains(&path, caller_home.as_deref());
+
+    let (sandbox, mut sandbox_args) = if cfg!(target_os = "linux") {
+        let bwrap = find("bwrap", &path).context(NOT_AVAILABLE)?;
+        let args = bwrap_args(dir, home, caller_home.as_deref(), &exposed);
+        (bwrap, args)
+    } else if cfg!(target_os = "macos") {
+        let sandbox_exec = PathBuf::from("/usr/bin/sandbox-exec");
+        anyhow::ensure!(sandbox_exec.is_file(), NOT_AVAILABLE);
+        let args = sandbox_exec_args(dir, home, caller_home.as_deref(), &exposed)?;
+        (sandbox_exec, args)
+    } else {
+        anyhow::bail!(NOT_AVAILABLE);
+    };
+
+    sandbox_args.push(program.as_os_str().to_owned());
+    sandbox_args.extend(args.iter().map(OsString::from));
+    let mut command = Command::new(sandbox);
+    command.args(sandbox_args);
+    Ok(command)
+}
+
+const NOT_AVAILABLE: &str = "No sandbox to run the MCP server in: install bubblewrap (`bwrap`) on \
+     Linux, or opt into running it unconfined with full access to your files \
+     (`scan --dynamic-unsandboxed`, `fuzz --unsandboxed`)";
+
+/// Arguments to `bwrap` up to the server command
+fn bwrap_args(
+    dir: &Path,
+    home: &Path,
+    caller_home: Option<&Path>,
+    exposed: &[PathBuf],
+) -> Vec<OsString> {
+    let mut args: Vec<OsString> = [
+        "--unshare-all",
+        "--share-net",
+        "--die-with-parent",
+        "--new-session",
+        "--ro-bind",
+        "/",
+        "/",
+        "--dev",
+        "/dev",
+        "--proc",
+        "/proc",
+        "--tmpfs",
+        "/tmp",
+    ]
+    .iter()
+    .map(OsString::from)
+    .collect();
+    // Later mounts cover earlier ones, so the hidden home goes first
+    if let Some(caller_home) = caller_home.filter(|h| h.is_dir()) {
+        args.extend(["--tmpfs".into(), caller_home.as_os_str().to_owned()]);
+    }
+    for path in exposed {
+        args.extend(bind("--ro-bind", path));
+    }
+    args.extend(bind("--ro-bind", dir));
+    args.extend(bind("--bind", home));
+    args.extend(["--chdir".into(), dir.as_os_str().to_owned(), "--".into()]);
+    args
+}
+
+fn bind(option: &str, path: &Path) -> [OsString; 3] {
+    [
+        option.into(),
+        path.as_os_str().to_owned(),
+        path.as_os_str().to_owned(),
+    ]
+}
+
+/// Arguments to `sandbox-exec` up to the server command
+///
+/// Paths are passed as parameters, so they need no quoting in the profile.
+fn sandbox_exec_args(
+    dir: &Path,
+    home: &Path,
+    caller_home: Option<&Path>,
+    exposed: &[PathBuf],
+) -> Result<Vec<OsString>> {
+    // The kernel sees resolved paths (/private/var/..., not /var/...)
+    let real = |path: &Path| {
+        path.canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.display()))
+    };
+    let mut profile = String::from(
+        "(version 1)\n(allow default)\n(deny file-write*)\n\
+         (allow file-write* (subpath (param \"HOME\")) (subpath \"/private/tmp\") \
+         (subpath \"/private/var/folders\") (literal \"/dev/null\"))\n",
+    );
+    let mut params = vec![("HOME".to_string(), real(home)?)];
+    // Later rules take precedence, so the readable paths follow the denial
+    if let Some(caller_home) = caller_home.filter(|h| h.is_dir()) {
+        profile.push_str("(deny file-read* (subpath (param \"CALLER_HOME\")))\n");
+        params.push(("CALLER_HOME".to_string(), real(caller_home)?));
+    }
+    profile.push_str("(allow file-read* (subpath (param \"HOME\")) (subpath (param \"DIR\"))");
+    params.push(("DIR".to_string(), real(dir)?));
+    for (i, path) in exposed.iter().enumerate() {
+        profile.push_str(&format!(" (subpath (param \"EXPOSED_{}\"))", i));
+        params.push((format!("EXPOSED_{}", i), real(path)?));
+    }
+    profile.push_str(")\n");
+
+    let mut args = Vec::new();
+    for (name, path) in params {
+        let mut define = OsString::from(format!("{}=", name));
+        define.push(path);
+        args.extend(["-D".into(), define]);
+    }
+    args.extend(["-p".into(), profile.into()]);
+    Ok(args)
+}
+
+/// Directories under `caller_home` that `PATH` needs, to keep readable
+///
+/// Each entry's parent is taken (`~/.cargo` for `~/.cargo/bin`), since
+/// toolchains keep their libraries next to `bin`, unless that parent is the
+/// home directory itself.
+fn exposed_toolchains(path: &OsStr, caller_home: Option<&Path>) -> Vec<PathBuf> {
+    let Some(caller_home) = caller_home else {
+        return Vec::new();
+    };
+    let mut exposed: Vec<PathBuf> = std::env::split_paths(path)
+        .filter(|dir| dir.starts_with(caller_home) && dir != caller_home && dir.is_dir())
+        .map(|dir| match dir.parent() {
+            Some(parent) if parent != caller_home => parent.to_path_buf(),
+            _ => dir,
+        })
+        .collect();
+    exposed.sort();
+    exposed.dedup();
+    exposed
+}
+
+fn find(program: &str, path: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bwrap_hides_home_before_exposing_paths() {
+        let caller_home = tempfile::tempdir().unwrap();
+        let cargo_bin = caller_home.path().join(".cargo/bin");
+        let user_bin = caller_home.path().join("bin");
+        std::fs::create_dir_all(&cargo_bin).unwrap();
+        std::fs::create_dir_all(&user_bin).unwrap();
+        let path =
+            std::env::join_paths([cargo_bin.as_path(), &user_bin, Path::new("/usr/bin")]).unwrap();
+
+        let exposed = exposed_toolchains(&path, Some(caller_home.path()));
+        assert_eq!(exposed, vec![caller_home.path().join(".cargo"), user_bin]);
+
+        let args = bwrap_args(
+            Path::new("/src/server"),
+            Path::new("/tmp/home"),
+            Some(caller_home.path()),
+            &exposed,
+        );
+        let position = |arg: &Path| args.iter().position(|a| a == arg.as_os_str()).unwrap();
+        assert!(args.contains(&OsString::from("--share-net")));
+        assert!(position(caller_home.path()) < position(&exposed[0]));
+        assert!(position(caller_home.path()) < position(Path::new("/src/server")));
+        assert_eq!(args.last().unwrap(), "--");
+    }
+}
@@ -1,7 +1,6 @@
 //! Scanning engines
 
+pub mod ai_analysis;
+pub mod dynamic_analysis;
+pub mod runtime_proxy;
 pub mod static_analysis;
-
-// Phase 3+ engines
-// pub mod runtime_proxy;
-// pub mod ai_analysis;
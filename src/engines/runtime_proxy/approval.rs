@@ -0,0 +1,149 @@
+//! Operator approval of tool calls
+//!
+//! Calls to tools the guardrails mark `require_approval` are held while an
+//! operator decides. The question goes to the approval webhook if one is
+//! configured, and otherwise to the terminal the proxy runs in. stdin and
+//! stdout carry MCP traffic, so the terminal is opened directly (`/dev/tty`,
+//! `CONIN$`/`CONOUT$` on Windows). Anything short of an explicit yes - no
+//! terminal, a failing webhook, the timeout - denies the call.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::guardrails::Guardrails;
+
+/// Longest argument text shown in a terminal prompt
+const MAX_PROMPT_ARGUMENTS: usize = 500;
+
+/// A tool call waiting for a decision
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalRequest {
+    pub tool: String,
+    pub arguments: Value,
+    /// Request id, to answer a denied call
+    pub id: Option<Value>,
+}
+
+/// Asks the operator about held calls
+#[derive(Debug, Clone)]
+pub struct Approver {
+    webhook: Option<String>,
+    timeout: Duration,
+    /// One terminal prompt at a time
+    terminal: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Approver {
+    pub fn new(guardrails: &Guardrails) -> Self {
+        Self {
+            webhook: guardrails.approval_webhook.clone(),
+            timeout: guardrails.approval_timeout(),
+            terminal: Arc::default(),
+        }
+    }
+
+    /// An approver for new guardrails, sharing this one's terminal
+    pub fn reconfigure(&self, guardrails: &Guardrails) -> Self {
+        Self {
+            terminal: Arc::clone(&self.terminal),
+            ..Self::new(guardrails)
+        }
+    }
+
+    /// Whether the operator approves `request`
+    pub async fn decide(&self, request: &ApprovalRequest) -> bool {
+        let decision = match &self.webhook {
+            Some(url) => ask_webhook(url, request, self.timeout).await,
+            None => {
+                let _prompt = self.terminal.lock().await;
+                let question = request.clone();
+                let answer = tokio::task::spawn_blocking(move || ask_terminal(&question));
+                match tokio::time::timeout(self.timeout, answer).await {
+                    Ok(answer) => answer.context("Approval prompt failed").and_then(|a| a),
+                    Err(_) => Err(anyhow::anyhow!("no answer within {:?}", self.timeout)),
+                }
+            }
+        };
+        match decision {
+            Ok(approved) => {
+                info!(
+                    tool = %request.tool,
+                    "Call {} by operator",
+                    if approved { "approved" } else { "denied" }
+                );
+                approved
+            }
+            Err(e) => {
+                warn!("Denying call to '{}': {:#}", request.tool, e);
+                false
+            }
+        }
+    }
+}
+
+async fn ask_webhook(url: &str, request: &ApprovalRequest, timeout: Duration) -> Result<bool> {
+    let client = reqwest::Client::builder().timeout(timeout).build()?;
+    let body = json!({ "tool": request.tool, "arguments": request.arguments });
+    let answer: Value = client
+        .post(url)
+        .json(&body)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Approval webhook {} failed", url))?
+        .json()
+        .await
+        .context("Approval webhook returned invalid JSON")?;
+    answer
+        .get("approved")
+        .and_then(Value::as_bool)
+        .context("Approval webhook answer has no boolean \"approved\"")
+}
+
+fn ask_terminal(request: &ApprovalRequest) -> Result<bool> {
+    let (input, mut output) = open_terminal().context("No terminal to ask for approval")?;
+    let mut arguments = request.arguments.to_string();
+    if arguments.len() > MAX_PROMPT_ARGUMENTS {
+        let mut end = MAX_PROMPT_ARGUMENTS;
+        while !arguments.is_char_boundary(end) {
+            end -= 1;
+        }
+        arguments.truncate(end);
+        arguments.push('…');
+    }
+    write!(
+        output,
+        "\n⚠️  MCP tool call needs approval: {} {}\nAllow it? [y/N] ",
+        request.tool, arguments
+    )?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    BufReader::new(input).read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// The controlling terminal (input, output), apart from the stdio that
+/// carries MCP traffic
+#[cfg(unix)]
+pub(crate) fn open_terminal() -> std::io::Result<(std::fs::File, std::fs::File)> {
+    let tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")?;
+    Ok((tty.try_clone()?, tty))
+}
+
+#[cfg(windows)]
+pub(crate) fn open_terminal() -> std::io::Result<(std::fs::File, std::fs::File)> {
+    let input = std::fs::OpenOptions::new().read(true).open("CONIN$")?;
+    let output = std::fs::OpenOptions::new().write(true).open("CONOUT$")?;
+    Ok((input, output))
+}
@@ -0,0 +1,384 @@
+//! Tool-call argument enforcement
+//!
+//! Arguments of a `tools/call` are checked against:
+//! - the `inputSchema` the server declared for the tool in its latest
+//!   `tools/list` result. The JSON Schema keywords tool schemas use are
+//!   supported: `type`, `enum`, `const`, `properties`, `required`,
+//!   `additionalProperties`, `items`, the length, size, and range bounds,
+//!   `pattern`, and `anyOf`/`oneOf`/`allOf`. Other keywords are ignored.
+//! - the guardrails' own constraints, which hold whatever the server
+//!   declares: path arguments must stay inside the workspace, URLs must
+//!   match the allowlist, and strings have a maximum length
+//!
+//! Either kind of violation rejects the call.
+
+use globset::{Glob, GlobSetBuilder};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Component, Path, PathBuf};
+
+/// Argument names that hold file system paths
+static PATH_ARGUMENT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:[Pp]ath|[Ff]ile(?:[Nn]ame)?|[Dd]ir(?:ectory)?|[Ff]older|cwd)s?$|_(?:path|file|filename|dir|directory|folder)s?$|[a-z](?:Path|File|FileName|Filename|Dir|Directory|Folder)s?$",
+    )
+    .unwrap()
+});
+
+/// Schema nesting followed before giving up, against self-similar schemas
+const MAX_SCHEMA_DEPTH: usize = 32;
+
+/// Constraints on arguments beyond the server's schema
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArgumentRules {
+    /// Directories path arguments must stay inside; relative paths resolve
+    /// against the first
+    pub workspace: Vec<PathBuf>,
+    /// Glob patterns URL arguments must match
+    pub url_allowlist: Vec<String>,
+    /// Longest string argument, in characters
+    pub max_string_length: Option<usize>,
+}
+
+impl ArgumentRules {
+    /// Problems with a call's `arguments`, each prefixed with the argument
+    /// it concerns
+    pub fn check(&self, arguments: &Value) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut strings = Vec::new();
+        collect_strings(arguments, "", "", &mut strings);
+
+        for (pointer, key, value) in strings {
+            let at = display_pointer(&pointer);
+            if let Some(max) = self.max_string_length {
+                let len = value.chars().count();
+                if len > max {
+                    problems.push(format!("{}: {} characters (limit {})", at, len, max));
+                }
+            }
+            let url = url::Url::parse(value).ok().filter(|u| u.has_host());
+            if let Some(url) = url {
+                if !self.url_allowlist.is_empty() && !url_allowed(&self.url_allowlist, url.as_str())
+                {
+                    problems.push(format!("{}: URL {} is not on the allowlist", at, url));
+                }
+            } else if PATH_ARGUMENT.is_match(key) && !self.workspace.is_empty() {
+                let path = resolve(&self.workspace[0], value);
+                if !self
+                    .workspace
+                    .iter()
+                    .any(|root| path.starts_with(normalize(root)))
+                {
+                    problems.push(format!(
+                        "{}: path {} is outside the workspace",
+                        at,
+                        path.display()
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Problems with `value` under a JSON Schema
+pub fn check_schema(schema: &Value, value: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    validate(schema, value, "", 0, &mut problems);
+    problems
+}
+
+fn validate(schema: &Value, value: &Value, pointer: &str, depth: usize, out: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        // `true`, `{}`, and anything unrecognized accept every value
+        if schema == &Value::Bool(false) {
+            out.push(format!("{}: not allowed", display_pointer(pointer)));
+        }
+        return;
+    };
+    if depth > MAX_SCHEMA_DEPTH {
+        return;
+    }
+    let at = display_pointer(pointer);
+    let mut fail = |problem: String| out.push(format!("{}: {}", at, problem));
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            fail(format!(
+                "expected {}, got {}",
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            fail("not one of the allowed values".to_string());
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            fail(format!("must be {}", constant));
+        }
+    }
+
+    let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+    let count = |key: &str| schema.get(key).and_then(Value::as_u64).map(|n| n as usize);
+    match value {
+        Value::String(s) => {
+            let len = s.chars().count();
+            if count("maxLength").is_some_and(|max| len > max) {
+                fail(format!(
+                    "longer than {} characters",
+                    count("maxLength").unwrap()
+                ));
+            }
+            if count("minLength").is_some_and(|min| len < min) {
+                fail(format!(
+                    "shorter than {} characters",
+                    count("minLength").unwrap()
+                ));
+            }
+            let pattern = schema.get("pattern").and_then(Value::as_str);
+            if let Some(pattern) = pattern {
+                if Regex::new(pattern).is_ok_and(|re| !re.is_match(s)) {
+                    fail(format!("does not match {}", pattern));
+                }
+            }
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if bound("minimum").is_some_and(|min| n < min)
+                || bound("exclusiveMinimum").is_some_and(|min| n <= min)
+            {
+                fail(format!("{} is below the minimum", n));
+            }
+            if bound("maximum").is_some_and(|max| n > max)
+                || bound("exclusiveMaximum").is_some_and(|max| n >= max)
+            {
+                fail(format!("{} is above the maximum", n));
+            }
+        }
+        Value::Array(items) => {
+            if count("maxItems").is_some_and(|max| items.len() > max) {
+                fail(format!("more than {} items", count("maxItems").unwrap()));
+            }
+            if count("minItems").is_some_and(|min| items.len() < min) {
+                fail(format!("fewer than {} items", count("minItems").unwrap()));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    let pointer = format!("{}/{}", pointer, i);
+                    validate(item_schema, item, &pointer, depth + 1, out);
+                }
+            }
+        }
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required = schema.get("required").and_then(Value::as_array);
+            for name in required.into_iter().flatten().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    fail(format!("missing required property '{}'", name));
+                }
+            }
+            for (name, property) in object {
+                let pointer = format!("{}/{}", pointer, name);
+                match properties.and_then(|p| p.get(name)) {
+                    Some(property_schema) => {
+                        validate(property_schema, property, &pointer, depth + 1, out)
+                    }
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => out.push(format!(
+                            "{}: unexpected property",
+                            display_pointer(&pointer)
+                        )),
+                        Some(additional) => {
+                            validate(additional, property, &pointer, depth + 1, out)
+                        }
+                        None => {}
+                    },
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let subschemas = |key: &str| {
+        schema
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|s| s.as_slice())
+    };
+    let passes = |s: &Value| check_nested(s, value, pointer, depth).is_empty();
+    if let Some(all) = subschemas("allOf") {
+        for s in all {
+            validate(s, value, pointer, depth + 1, out);
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(any) = subschemas(key) {
+            if !any.is_empty() && !any.iter().any(passes) {
+                out.push(format!("{}: matches none of the {} schemas", at, key));
+            }
+        }
+    }
+}
+
+fn check_nested(schema: &Value, value: &Value, pointer: &str, depth: usize) -> Vec<String> {
+    let mut problems = Vec::new();
+    validate(schema, value, pointer, depth + 1, &mut problems);
+    problems
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Every string in `value` with its JSON pointer and the name of the
+/// property holding it
+fn collect_strings<'a>(
+    value: &'a Value,
+    pointer: &str,
+    key: &'a str,
+    out: &mut Vec<(String, &'a str, &'a str)>,
+) {
+    match value {
+        Value::String(s) => out.push((pointer.to_string(), key, s)),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_strings(item, &format!("{}/{}", pointer, i), key, out);
+            }
+        }
+        Value::Object(object) => {
+            for (name, item) in object {
+                collect_strings(item, &format!("{}/{}", pointer, name), name, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn display_pointer(pointer: &str) -> String {
+    if pointer.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("arguments{}", pointer)
+    }
+}
+
+fn url_allowed(patterns: &[String], url: &str) -> bool {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder.build().is_ok_and(|set| set.is_match(url))
+}
+
+/// `value` as an absolute path, relative ones taken from `base`, with `.`
+/// and `..` resolved lexically (the proxy cannot see the server's
+/// file system; `~` is not expanded and so never escapes)
+fn resolve(base: &Path, value: &str) -> PathBuf {
+    normalize(&base.join(value))
+}
+
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_check_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "city": { "type": "string", "maxLength": 20 },
+                "days": { "type": "integer", "minimum": 1, "maximum": 7 },
+                "units": { "enum": ["metric", "imperial"] },
+            },
+            "required": ["city"],
+            "additionalProperties": false,
+        });
+        assert!(check_schema(&schema, &json!({ "city": "Oslo", "days": 3 })).is_empty());
+        assert_eq!(
+            check_schema(
+                &schema,
+                &json!({ "days": 9.5, "units": "kelvin", "shell": "id" })
+            ),
+            vec![
+                "arguments: missing required property 'city'",
+                "arguments/days: expected integer, got number",
+                "arguments/shell: unexpected property",
+                "arguments/units: not one of the allowed values",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_argument_rules() {
+        let rules = ArgumentRules {
+            workspace: vec![PathBuf::from("/work/project")],
+            url_allowlist: vec!["https://api.github.com/*".to_string()],
+            max_string_length: Some(100),
+        };
+        let ok = json!({
+            "path": "src/main.rs",
+            "url": "https://api.github.com/repos/acme/app",
+            "query": "weather",
+        });
+        assert!(rules.check(&ok).is_empty());
+
+        let bad = json!({
+            "filePath": "../../../etc/passwd",
+            "targets": [{ "url": "https://evil.example/collect" }],
+            "note": "x".repeat(101),
+        });
+        assert_eq!(
+            rules.check(&bad),
+            vec![
+                "arguments/filePath: path /etc/passwd is outside the workspace",
+                "arguments/note: 101 characters (limit 100)",
+                "arguments/targets/0/url: URL https://evil.example/collect is not on the allowlist",
+            ]
+        );
+    }
+}
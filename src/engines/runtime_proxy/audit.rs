@@ -0,0 +1,276 @@
+//! Audit log of proxied activity
+//!
+//! One JSON object per line: tool calls and what became of them, findings,
+//! and blocked messages, each tagged with the server it concerns. When one
+//! proxy fronts several servers, they all write to the same file, as do the
+//! [`dlp`](super::dlp) records of blocked content.
+//!
+//! With [`AuditFormat::Ocsf`], findings and blocked messages are written as
+//! OCSF Detection Findings instead (see [`crate::output::ocsf`]), and tool
+//! calls are left out.
+//!
+//! The proxy [`prune`]s its logs to the configured retention when it starts
+//! and every [`PRUNE_INTERVAL`] after, dropping the oldest entries.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use super::events::ProxyEvent;
+use crate::models::config::RetentionPolicy;
+use crate::output::ocsf;
+
+/// How often a running proxy prunes its audit logs
+pub const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Keeps entries from being appended while a log is rewritten
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// How the proxy writes its audit log
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AuditFormat {
+    /// MCP Sentinel's own entries
+    #[default]
+    Jsonl,
+    /// OCSF Detection Findings
+    Ocsf,
+}
+
+/// Append one entry to the audit log at `path`
+pub fn append(path: &Path, mut entry: Value) -> Result<()> {
+    if let Some(fields) = entry.as_object_mut() {
+        fields.insert(
+            "timestamp".to_string(),
+            json!(chrono::Utc::now().to_rfc3339()),
+        );
+    }
+    write_line(path, &entry)
+}
+
+/// Append `entry` as it is
+fn write_line(path: &Path, entry: &Value) -> Result<()> {
+    let write = || -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", entry)
+    };
+    let _guard = LOG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    write().with_context(|| format!("Failed to write audit log '{}'", path.display()))
+}
+
+/// Drop entries older than `retention` allows, then the oldest entries
+/// until the rest fit its size; returns how many were dropped
+///
+/// The log is rewritten, so entries another process appends meanwhile can
+/// be lost.
+pub fn prune(path: &Path, retention: &RetentionPolicy) -> Result<usize> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read audit log '{}'", path.display()))?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    // Entries are appended in order, so the expired ones come first
+    let mut start = match retention.cutoff() {
+        Some(cutoff) => lines
+            .iter()
+            .position(|line| !before(line, cutoff))
+            .unwrap_or(lines.len()),
+        None => 0,
+    };
+    if let Some(max_bytes) = retention.max_bytes() {
+        let mut size: u64 = lines[start..].iter().map(|l| l.len() as u64 + 1).sum();
+        while size > max_bytes && start < lines.len() {
+            size -= lines[start].len() as u64 + 1;
+            start += 1;
+        }
+    }
+    if start == 0 {
+        return Ok(0);
+    }
+
+    let kept: String = lines[start..].iter().map(|l| format!("{}\n", l)).collect();
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let rewrite = || -> std::io::Result<()> {
+        std::fs::write(&temp, kept)?;
+        std::fs::rename(&temp, path)
+    };
+    rewrite().with_context(|| format!("Failed to prune audit log '{}'", path.display()))?;
+    Ok(start)
+}
+
+/// Whether an entry was written before `cutoff` (entries without a
+/// readable timestamp are kept)
+fn before(line: &str, cutoff: DateTime<Utc>) -> bool {
+    let Ok(entry) = serde_json::from_str::<Value>(line) else {
+        return false;
+    };
+    // OCSF events have `time`, in milliseconds
+    let time = match entry["timestamp"].as_str() {
+        Some(timestamp) => timestamp.parse::<DateTime<Utc>>().ok(),
+        None => entry["time"]
+            .as_i64()
+            .and_then(DateTime::from_timestamp_millis),
+    };
+    time.is_some_and(|time| time < cutoff)
+}
+
+/// Prune `paths` to `retention` now and every [`PRUNE_INTERVAL`]
+pub async fn prune_periodically(paths: Vec<PathBuf>, retention: RetentionPolicy) {
+    let mut ticks = tokio::time::interval(PRUNE_INTERVAL);
+    loop {
+        ticks.tick().await;
+        for path in &paths {
+            match prune(path, &retention) {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {} entries from {}", pruned, path.display()),
+                Err(e) => warn!("{:#}", e),
+            }
+        }
+    }
+}
+
+/// The audit entry for an event, if it is one that gets recorded
+pub fn entry(server: Option<&str>, event: &ProxyEvent) -> Option<Value> {
+    let entry = match event {
+        ProxyEvent::ToolCall { tool, outcome } => json!({
+            "event": "tool_call",
+            "tool": tool,
+            "outcome": outcome.to_string(),
+        }),
+        ProxyEvent::Finding {
+            direction,
+            violation,
+        } => json!({
+            "event": "finding",
+            "direction": direction.to_string(),
+            "rule": violation.rule,
+            "severity": violation.severity,
+            "message": violation.message,
+        }),
+        ProxyEvent::Blocked {
+            direction,
+            violation,
+        } => json!({
+            "event": "blocked",
+            "direction": direction.to_string(),
+            "rule": violation.rule,
+            "severity": violation.severity,
+            "message": violation.message,
+        }),
+        ProxyEvent::Message { .. } | ProxyEvent::Peer { .. } => return None,
+    };
+    let mut entry = entry;
+    if let Some(server) = server {
+        entry["server"] = json!(server);
+    }
+    Some(entry)
+}
+
+/// Record the events of one server's interceptor to `log`, passing every
+/// event on to `forward` (the dashboard)
+pub async fn record(
+    mut events: mpsc::Receiver<ProxyEvent>,
+    server: Option<String>,
+    log: Option<PathBuf>,
+    format: AuditFormat,
+    forward: Option<mpsc::Sender<ProxyEvent>>,
+) {
+    while let Some(event) = events.recv().await {
+        if let Some(path) = &log {
+            let written = match format {
+                AuditFormat::Jsonl => entry(server.as_deref(), &event).map(|e| append(path, e)),
+                AuditFormat::Ocsf => {
+                    ocsf::proxy_event(server.as_deref(), &event).map(|e| write_line(path, &e))
+                }
+            };
+            if let Some(Err(e)) = written {
+                warn!("{:#}", e);
+            }
+        }
+        if let Some(forward) = &forward {
+            let _ = forward.try_send(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::runtime_proxy::events::CallOutcome;
+
+    #[test]
+    fn test_audit_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let call = ProxyEvent::ToolCall {
+            tool: "delete_repository".to_string(),
+            outcome: CallOutcome::Blocked,
+        };
+        append(&path, entry(Some("github"), &call).unwrap()).unwrap();
+        let message = ProxyEvent::Message {
+            direction: crate::engines::runtime_proxy::Direction::ClientToServer,
+            bytes: 10,
+        };
+        assert!(entry(Some("github"), &message).is_none());
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let recorded: Value = serde_json::from_str(log.trim()).unwrap();
+        assert_eq!(recorded["server"], "github");
+        assert_eq!(recorded["outcome"], "blocked");
+        assert!(recorded["timestamp"].is_string());
+    }
+
+    #[test]
+    fn test_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let old = (Utc::now() - chrono::Duration::days(100)).to_rfc3339();
+        let lines = [
+            json!({"event": "blocked", "timestamp": old}).to_string(),
+            json!({"event": "finding", "timestamp": old}).to_string(),
+        ];
+        std::fs::write(&path, lines.join("\n") + "\n").unwrap();
+        let call = ProxyEvent::ToolCall {
+            tool: "read_file".to_string(),
+            outcome: CallOutcome::Forwarded,
+        };
+        append(&path, entry(None, &call).unwrap()).unwrap();
+        append(&path, entry(None, &call).unwrap()).unwrap();
+
+        let by_age = RetentionPolicy {
+            max_age_days: Some(90),
+            max_size_mb: None,
+        };
+        assert_eq!(prune(&path, &RetentionPolicy::default()).unwrap(), 0);
+        assert_eq!(prune(&path, &by_age).unwrap(), 2);
+        let log = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.lines().all(|line| line.contains("tool_call")));
+
+        let by_size = RetentionPolicy {
+            max_age_days: None,
+            max_size_mb: Some(0),
+        };
+        assert_eq!(prune(&path, &by_size).unwrap(), 2);
+        assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+        assert_eq!(
+            prune(&dir.path().join("missing.jsonl"), &by_age).unwrap(),
+            0
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Credentials for authenticated upstreams
+//!
+//! Tokens never appear on the command line, where any local user can read
+//! them from the process list. Flags take a reference to a secret instead:
+//! - `env:NAME`: an environment variable
+//! - `file:PATH`: the contents of a file (trailing newline removed)
+//! - `cmd:COMMAND`: the output of a secrets manager CLI, e.g.
+//!   `cmd:op read op://prod/mcp/token` or
+//!   `cmd:vault kv get -field=token secret/mcp` (split on whitespace, not
+//!   run through a shell)
+//!
+//! The upstream gets either a static bearer token or one obtained with the
+//! OAuth 2.0 client credentials grant, cached until shortly before it
+//! expires and fetched again when the upstream rejects it.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Token endpoint timeout
+const TOKEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Refresh tokens this long before they expire
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+/// Where a secret comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    Env(String),
+    File(std::path::PathBuf),
+    Command(Vec<String>),
+}
+
+impl FromStr for SecretRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (scheme, rest) = s.split_once(':').unwrap_or(("", s));
+        let secret = match scheme {
+            "env" if !rest.is_empty() => SecretRef::Env(rest.to_string()),
+            "file" if !rest.is_empty() => SecretRef::File(rest.into()),
+            "cmd" if !rest.trim().is_empty() => {
+                SecretRef::Command(rest.split_whitespace().map(str::to_string).collect())
+            }
+            _ => bail!("Secret reference must be env:NAME, file:PATH, or cmd:COMMAND"),
+        };
+        Ok(secret)
+    }
+}
+
+impl SecretRef {
+    /// Read the secret
+    pub async fn resolve(&self) -> Result<Secret> {
+        let value = match self {
+            SecretRef::Env(name) => std::env::var(name)
+                .with_context(|| format!("Environment variable {} is not set", name))?,
+            SecretRef::File(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read secret file '{}'", path.display()))?,
+            SecretRef::Command(command) => {
+                let output = tokio::process::Command::new(&command[0])
+                    .args(&command[1..])
+                    .stdin(std::process::Stdio::null())
+                    .output()
+                    .await
+                    .with_context(|| format!("Failed to run '{}'", command[0]))?;
+                if !output.status.success() {
+                    bail!("'{}' failed: {}", command[0], output.status);
+                }
+                String::from_utf8(output.stdout)
+                    .with_context(|| format!("'{}' printed a non-UTF-8 secret", command[0]))?
+            }
+        };
+        let value = value.trim_end_matches(['\r', '\n']).to_string();
+        if value.is_empty() {
+            bail!("Secret from {:?} is empty", self);
+        }
+        Ok(Secret(value))
+    }
+}
+
+/// A secret value; never printed
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+/// OAuth 2.0 client credentials of the upstream
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCredentials {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: SecretRef,
+    pub scope: Option<String>,
+}
+
+/// A bearer token and when it expires, shared by clones of a [`TokenSource`]
+type CachedToken = Arc<Mutex<Option<(Secret, Option<Instant>)>>>;
+
+/// Source of the upstream's bearer token
+#[derive(Debug, Clone)]
+pub enum TokenSource {
+    Static(Secret),
+    OAuth {
+        credentials: ClientCredentials,
+        cached: CachedToken,
+    },
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+impl TokenSource {
+    pub fn oauth(credentials: ClientCredentials) -> Self {
+        TokenSource::OAuth {
+            credentials,
+            cached: Arc::default(),
+        }
+    }
+
+    /// The current bearer token
+    pub async fn token(&self, client: &reqwest::Client) -> Result<Secret> {
+        let (credentials, cached) = match self {
+            TokenSource::Static(token) => return Ok(token.clone()),
+            TokenSource::OAuth {
+                credentials,
+                cached,
+            } => (credentials, cached),
+        };
+        let mut cached = cached.lock().await;
+        if let Some((token, expires)) = cached.as_ref() {
+            // Without an expiry, keep the token until the upstream rejects it
+            if expires.is_none_or(|at| Instant::now() + EXPIRY_MARGIN < at) {
+                return Ok(token.clone());
+            }
+        }
+        let (token, lifetime) = fetch_token(client, credentials).await?;
+        *cached = Some((
+            token.clone(),
+            lifetime.and_then(|l| Instant::now().checked_add(l)),
+        ));
+        Ok(token)
+    }
+
+    /// Drop a token the upstream rejected, so the next call fetches a new one
+    pub async fn invalidate(&self) {
+        if let TokenSource::OAuth { cached, .. } = self {
+            *cached.lock().await = None;
+        }
+    }
+}
+
+async fn fetch_token(
+    client: &reqwest::Client,
+    credentials: &ClientCredentials,
+) -> Result<(Secret, Option<Duration>)> {
+    let secret = credentials.client_secret.resolve().await?;
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", credentials.client_id.as_str()),
+        ("client_secret", secret.expose()),
+    ];
+    if let Some(scope) = &credentials.scope {
+        form.push(("scope", scope.as_str()));
+    }
+    let response: TokenResponse = client
+        .post(&credentials.token_url)
+        .timeout(TOKEN_TIMEOUT)
+        .form(&form)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Token request to {} failed", credentials.token_url))?
+        .json()
+        .await
+        .context("Token endpoint returned an invalid response")?;
+    let lifetime = response.expires_in.map(Duration::from_secs);
+    Ok((Secret(response.access_token), lifetime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_secret_refs() {
+        assert_eq!(
+            "cmd:vault kv get -field=token secret/mcp"
+                .parse::<SecretRef>()
+                .unwrap(),
+            SecretRef::Command(
+                ["vault", "kv", "get", "-field=token", "secret/mcp"]
+                    .map(String::from)
+                    .to_vec()
+            )
+        );
+        assert!("ghp_plaintexttoken".parse::<SecretRef>().is_err());
+        assert!("env:".parse::<SecretRef>().is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        std::fs::write(&path, "s3cr3t\n").unwrap();
+        let secret = SecretRef::File(path).resolve().await.unwrap();
+        assert_eq!(secret.expose(), "s3cr3t");
+        assert_eq!(format!("{:?}", secret), "Secret(***)");
+    }
+}
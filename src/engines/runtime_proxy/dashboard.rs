@@ -0,0 +1,390 @@
+//! Live proxy dashboard
+//!
+//! `proxy --dashboard` shows the session, recent tool calls, blocked
+//! messages, and findings as they happen. The proxy's stdin and stdout
+//! carry MCP traffic, so the view is drawn on the controlling terminal
+//! (`/dev/tty`, or the console on Windows), like approval prompts. `q`
+//! closes it; the proxy keeps running.
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{self, Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table};
+use ratatui::{Frame, Terminal};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TryRecvError};
+use tokio_util::sync::CancellationToken;
+
+use super::approval;
+use super::events::{CallOutcome, ProxyEvent};
+use super::jsonrpc::Violation;
+use super::Direction;
+use crate::models::vulnerability::Severity;
+
+/// Rows kept per panel
+const HISTORY: usize = 200;
+
+/// Redraw interval, and how long a key press may wait
+const TICK: Duration = Duration::from_millis(250);
+
+/// A tool call as listed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub time: String,
+    pub tool: String,
+    pub outcome: CallOutcome,
+}
+
+/// A finding or blocked message as listed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub time: String,
+    pub direction: Direction,
+    pub violation: Violation,
+}
+
+/// Traffic in one direction
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Traffic {
+    pub messages: usize,
+    pub bytes: usize,
+}
+
+/// What the dashboard shows, built up from proxy events
+#[derive(Debug, Clone, Default)]
+pub struct Dashboard {
+    /// The proxied server command or URL
+    pub target: String,
+    pub client: Option<String>,
+    pub server: Option<String>,
+    pub sent: Traffic,
+    pub received: Traffic,
+    /// Newest first
+    pub calls: VecDeque<Call>,
+    pub blocked: VecDeque<Alert>,
+    pub findings: VecDeque<Alert>,
+    pub blocked_total: usize,
+    pub findings_total: usize,
+}
+
+impl Dashboard {
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn apply(&mut self, event: ProxyEvent) {
+        let time = chrono::Local::now().format("%H:%M:%S").to_string();
+        match event {
+            ProxyEvent::Message { direction, bytes } => {
+                let traffic = match direction {
+                    Direction::ClientToServer => &mut self.sent,
+                    Direction::ServerToClient => &mut self.received,
+                };
+                traffic.messages += 1;
+                traffic.bytes += bytes;
+            }
+            ProxyEvent::Peer { direction, name } => match direction {
+                Direction::ClientToServer => self.client = Some(name),
+                Direction::ServerToClient => self.server = Some(name),
+            },
+            ProxyEvent::ToolCall { tool, outcome } => {
+                // A decision updates the call it was waiting for
+                let pending = self.calls.iter_mut().find(|call| {
+                    call.tool == tool && call.outcome == CallOutcome::AwaitingApproval
+                });
+                match pending {
+                    Some(call)
+                        if matches!(outcome, CallOutcome::Approved | CallOutcome::Denied) =>
+                    {
+                        call.outcome = outcome
+                    }
+                    _ => push(
+                        &mut self.calls,
+                        Call {
+                            time,
+                            tool,
+                            outcome,
+                        },
+                    ),
+                }
+            }
+            ProxyEvent::Finding {
+                direction,
+                violation,
+            } => {
+                self.findings_total += 1;
+                push(
+                    &mut self.findings,
+                    Alert {
+                        time,
+                        direction,
+                        violation,
+                    },
+                );
+            }
+            ProxyEvent::Blocked {
+                direction,
+                violation,
+            } => {
+                self.blocked_total += 1;
+                push(
+                    &mut self.blocked,
+                    Alert {
+                        time,
+                        direction,
+                        violation,
+                    },
+                );
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, uptime: Duration) {
+        let rows = Layout::default()
+            .direction(layout::Direction::Vertical)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(6),
+                Constraint::Percentage(40),
+                Constraint::Length(1),
+            ])
+            .split(frame.size());
+        let alerts = Layout::default()
+            .direction(layout::Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[2]);
+
+        frame.render_widget(self.session(uptime), rows[0]);
+        frame.render_widget(self.call_table(), rows[1]);
+        frame.render_widget(
+            alert_list("Blocked", self.blocked_total, &self.blocked),
+            alerts[0],
+        );
+        frame.render_widget(
+            alert_list("Findings", self.findings_total, &self.findings),
+            alerts[1],
+        );
+        frame.render_widget(
+            Paragraph::new(" q: close the dashboard (the proxy keeps running)")
+                .style(Style::default().fg(Color::DarkGray)),
+            rows[3],
+        );
+    }
+
+    fn session(&self, uptime: Duration) -> Paragraph<'_> {
+        let secs = uptime.as_secs();
+        let peer = |name: &Option<String>| name.clone().unwrap_or_else(|| "?".to_string());
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Server  ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(&self.target),
+            ]),
+            Line::from(format!(
+                "Peers   {} -> {}    up {:02}:{:02}:{:02}",
+                peer(&self.client),
+                peer(&self.server),
+                secs / 3600,
+                secs / 60 % 60,
+                secs % 60
+            )),
+            Line::from(format!(
+                "Traffic {} messages ({} bytes) sent, {} ({} bytes) received    {} findings, \
+                 {} blocked",
+                self.sent.messages,
+                self.sent.bytes,
+                self.received.messages,
+                self.received.bytes,
+                self.findings_total,
+                self.blocked_total
+            )),
+        ];
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" MCP Sentinel proxy "),
+        )
+    }
+
+    fn call_table(&self) -> Table<'_> {
+        let rows = self.calls.iter().map(|call| {
+            let color = match call.outcome {
+                CallOutcome::Forwarded | CallOutcome::Approved => Color::Green,
+                CallOutcome::AwaitingApproval => Color::Yellow,
+                CallOutcome::Blocked | CallOutcome::Denied => Color::Red,
+            };
+            Row::new(vec![
+                Cell::from(call.time.as_str()),
+                Cell::from(call.tool.as_str()),
+                Cell::from(call.outcome.to_string()).style(Style::default().fg(color)),
+            ])
+        });
+        Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Min(20),
+                Constraint::Length(18),
+            ],
+        )
+        .header(
+            Row::new(vec!["Time", "Tool", "Outcome"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Recent tool calls "),
+        )
+    }
+}
+
+fn push<T>(rows: &mut VecDeque<T>, row: T) {
+    rows.push_front(row);
+    rows.truncate(HISTORY);
+}
+
+fn alert_list<'a>(title: &str, total: usize, alerts: &'a VecDeque<Alert>) -> List<'a> {
+    let items = alerts.iter().map(|alert| {
+        let color = match alert.violation.severity {
+            Severity::Critical | Severity::High => Color::Red,
+            Severity::Medium => Color::Yellow,
+            Severity::Low | Severity::Info => Color::Reset,
+        };
+        ListItem::new(Line::from(vec![
+            Span::raw(format!("{} ", alert.time)),
+            Span::styled(
+                format!("{:<8} ", alert.violation.severity.to_badge()),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!("{} ", alert.violation.rule),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("({}) {}", alert.direction, alert.violation.message)),
+        ]))
+    });
+    List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ({}) ", title, total)),
+    )
+}
+
+/// Show the dashboard until the operator closes it or `shutdown` fires
+pub async fn run(
+    events: mpsc::Receiver<ProxyEvent>,
+    target: String,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || show(events, target, shutdown)).await?
+}
+
+fn show(
+    events: mpsc::Receiver<ProxyEvent>,
+    target: String,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let (_, mut output) =
+        approval::open_terminal().context("No terminal to show the dashboard on")?;
+    enable_raw_mode()?;
+    execute!(output, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(output))?;
+
+    let result = refresh(&mut terminal, events, Dashboard::new(target), shutdown);
+
+    // Restore the terminal even if drawing failed
+    let _ = disable_raw_mode();
+    let _ = execute!(terminal.backend_mut(), LeaveAlternateScreen);
+    let _ = terminal.show_cursor();
+    result
+}
+
+fn refresh<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    mut events: mpsc::Receiver<ProxyEvent>,
+    mut dashboard: Dashboard,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let started = Instant::now();
+    while !shutdown.is_cancelled() {
+        loop {
+            match events.try_recv() {
+                Ok(event) => dashboard.apply(event),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            }
+        }
+        terminal.draw(|frame| dashboard.draw(frame, started.elapsed()))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            let ctrl_c =
+                key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if key.kind == KeyEventKind::Press
+                && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc))
+            {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_state() {
+        let mut dashboard = Dashboard::new("npx my-mcp-server");
+        let call = |tool: &str, outcome| ProxyEvent::ToolCall {
+            tool: tool.to_string(),
+            outcome,
+        };
+        dashboard.apply(ProxyEvent::Peer {
+            direction: Direction::ServerToClient,
+            name: "files 1.2.0".to_string(),
+        });
+        dashboard.apply(ProxyEvent::Message {
+            direction: Direction::ClientToServer,
+            bytes: 120,
+        });
+        dashboard.apply(call("read_file", CallOutcome::Forwarded));
+        dashboard.apply(call("delete_file", CallOutcome::AwaitingApproval));
+        dashboard.apply(call("delete_file", CallOutcome::Denied));
+        dashboard.apply(ProxyEvent::Blocked {
+            direction: Direction::ClientToServer,
+            violation: Violation::new("guardrails/tool-denied", Severity::High, "denied"),
+        });
+
+        assert_eq!(dashboard.server.as_deref(), Some("files 1.2.0"));
+        assert_eq!(
+            dashboard.sent,
+            Traffic {
+                messages: 1,
+                bytes: 120
+            }
+        );
+        let outcomes: Vec<_> = dashboard.calls.iter().map(|c| c.outcome).collect();
+        assert_eq!(outcomes, vec![CallOutcome::Denied, CallOutcome::Forwarded]);
+        assert_eq!(dashboard.blocked_total, 1);
+
+        for _ in 0..HISTORY + 5 {
+            dashboard.apply(call("read_file", CallOutcome::Forwarded));
+        }
+        assert_eq!(dashboard.calls.len(), HISTORY);
+    }
+}
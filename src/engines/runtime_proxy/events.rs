@@ -0,0 +1,96 @@
+//! Proxy activity, as it happens
+//!
+//! The [`Interceptor`](super::Interceptor) reports what it sees to an
+//! optional listener (the [`dashboard`](super::dashboard)). Events are
+//! dropped rather than queued when the listener falls behind, so a slow or
+//! closed view never holds up traffic.
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use super::jsonrpc::Violation;
+use super::Direction;
+
+/// Events a listener can fall this far behind before they are dropped
+pub const EVENT_BUFFER: usize = 1024;
+
+/// Something the proxy saw or did
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProxyEvent {
+    /// A message arrived
+    Message { direction: Direction, bytes: usize },
+    /// The client or server introduced itself during `initialize`
+    Peer { direction: Direction, name: String },
+    /// A `tools/call` request and what became of it
+    ToolCall { tool: String, outcome: CallOutcome },
+    Finding {
+        direction: Direction,
+        violation: Violation,
+    },
+    /// A message was stopped or replaced with an error
+    Blocked {
+        direction: Direction,
+        violation: Violation,
+    },
+}
+
+/// What became of a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallOutcome {
+    Forwarded,
+    Blocked,
+    AwaitingApproval,
+    Approved,
+    Denied,
+}
+
+impl std::fmt::Display for CallOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            CallOutcome::Forwarded => "forwarded",
+            CallOutcome::Blocked => "blocked",
+            CallOutcome::AwaitingApproval => "awaiting approval",
+            CallOutcome::Approved => "approved",
+            CallOutcome::Denied => "denied",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Where the interceptor sends its events; does nothing without a listener
+#[derive(Debug, Clone, Default)]
+pub struct EventSink(Option<mpsc::Sender<ProxyEvent>>);
+
+impl EventSink {
+    pub fn new(sender: mpsc::Sender<ProxyEvent>) -> Self {
+        Self(Some(sender))
+    }
+
+    pub fn emit(&self, event: ProxyEvent) {
+        if let Some(sender) = &self.0 {
+            let _ = sender.try_send(event);
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.0.as_ref().is_some_and(|s| !s.is_closed())
+    }
+
+    /// Peers named in an `initialize` request (`clientInfo`) or its result
+    /// (`serverInfo`)
+    pub fn emit_peers(&self, direction: Direction, message: &Value) {
+        let info = match direction {
+            Direction::ClientToServer => message.pointer("/params/clientInfo"),
+            Direction::ServerToClient => message.pointer("/result/serverInfo"),
+        };
+        let Some(name) = info.and_then(|i| i.get("name")).and_then(Value::as_str) else {
+            return;
+        };
+        let version = info.and_then(|i| i.get("version")).and_then(Value::as_str);
+        let name = match version {
+            Some(version) => format!("{} {}", name, version),
+            None => name.to_string(),
+        };
+        self.emit(ProxyEvent::Peer { direction, name });
+    }
+}
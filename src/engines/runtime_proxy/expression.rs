@@ -0,0 +1,563 @@
+//! Policy expressions
+//!
+//! A small CEL-like language for guardrail rules that depend on a call's
+//! arguments, which tool name globs cannot express:
+//!
+//! ```text
+//! tool == "write_file" && !args.path.within(workspace)
+//! tool.startsWith("send_") && args.to.endsWith("@example.com") == false
+//! server == "github" && args.repo in ["prod", "infra"]
+//! ```
+//!
+//! Values are JSON: `null`, booleans, numbers, strings, lists, and maps.
+//! - literals: `"text"` or `'text'`, `42`, `1.5`, `true`, `false`, `null`,
+//!   `[a, b]`
+//! - operators, loosest first: `||`, `&&`, `== != < <= > >= in`, `!` and
+//!   unary `-`; parentheses group
+//! - `x.field` and `x[key]` read maps and lists; a missing field is `null`,
+//!   as is any field of `null`
+//! - `x in list` tests membership, `x in "text"` a substring, `x in map` a
+//!   key
+//! - string methods: `startsWith`, `endsWith`, `contains`, `matches`
+//!   (regular expression), `lower`, `upper`, and `within(dir)`, which holds
+//!   if the absolute path stays inside `dir` (or any of a list of
+//!   directories) once `.` and `..` are resolved; `size()` also counts list
+//!   and map entries, and `contains` also searches lists
+//! - every method can be called as a function: `size(args.files)`
+//!
+//! Anything else - a method on the wrong type, comparing a string with a
+//! number - is an evaluation error, which the caller decides how to treat.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::path::Path;
+
+use super::arguments::normalize;
+
+/// Deepest nesting parsed, so a hostile expression cannot overflow the stack
+const MAX_DEPTH: usize = 64;
+
+/// A parsed expression
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl TryFrom<String> for Expression {
+    type Error = anyhow::Error;
+
+    fn try_from(source: String) -> Result<Self> {
+        source.parse()
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = anyhow::Error;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let tokens =
+            tokenize(source).with_context(|| format!("Invalid expression '{}'", source))?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let root = parser
+            .expression(0)
+            .and_then(|root| match parser.tokens.get(parser.pos) {
+                None => Ok(root),
+                Some(token) => bail!("unexpected {:?}", token),
+            })
+            .with_context(|| format!("Invalid expression '{}'", source))?;
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl Expression {
+    /// The value of the expression, with `variables` in scope
+    pub fn evaluate(&self, variables: &Map<String, Value>) -> Result<Value> {
+        self.root.evaluate(variables)
+    }
+
+    /// Whether the expression holds; it must evaluate to a boolean
+    pub fn holds(&self, variables: &Map<String, Value>) -> Result<bool> {
+        match self.evaluate(variables)? {
+            Value::Bool(holds) => Ok(holds),
+            other => bail!("'{}' is {}, not a boolean", self.source, type_name(&other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Punct(&'static str),
+}
+
+/// Operators and punctuation, longest first
+const PUNCTUATION: &[&str] = &[
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "-", "(", ")", "[", "]", ".", ",",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '"' || c == '\'' {
+            let mut text = String::new();
+            let mut chars = rest[1..].char_indices();
+            let end = loop {
+                match chars.next() {
+                    Some((i, q)) if q == c => break i + 2,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, 'n')) => text.push('\n'),
+                        Some((_, 't')) => text.push('\t'),
+                        Some((_, escaped)) => text.push(escaped),
+                        None => bail!("unterminated string"),
+                    },
+                    Some((_, other)) => text.push(other),
+                    None => bail!("unterminated string"),
+                }
+            };
+            tokens.push(Token::Str(text));
+            rest = &rest[end..];
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .unwrap_or(rest.len());
+            let number = rest[..len]
+                .parse()
+                .with_context(|| format!("invalid number '{}'", &rest[..len]))?;
+            tokens.push(Token::Num(number));
+            rest = &rest[len..];
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..len].to_string()));
+            rest = &rest[len..];
+        } else {
+            let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) else {
+                bail!("unexpected character '{}'", c);
+            };
+            tokens.push(Token::Punct(punct));
+            rest = &rest[punct.len()..];
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(Value),
+    Variable(String),
+    List(Vec<Node>),
+    Field(Box<Node>, String),
+    Index(Box<Node>, Box<Node>),
+    /// A method call; functions take their target as the first argument
+    Call(String, Vec<Node>),
+    Not(Box<Node>),
+    Negate(Box<Node>),
+    Binary(Box<Node>, Op, Box<Node>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Punct(p)) if *p == punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<()> {
+        if !self.eat(punct) {
+            match self.peek() {
+                Some(token) => bail!("expected '{}', found {:?}", punct, token),
+                None => bail!("expected '{}' at the end", punct),
+            }
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self, depth: usize) -> Result<Node> {
+        if depth > MAX_DEPTH {
+            bail!("nested too deeply");
+        }
+        self.or(depth + 1)
+    }
+
+    fn or(&mut self, depth: usize) -> Result<Node> {
+        let mut left = self.and(depth)?;
+        while self.eat("||") {
+            let right = self.and(depth)?;
+            left = Node::Binary(Box::new(left), Op::Or, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self, depth: usize) -> Result<Node> {
+        let mut left = self.comparison(depth)?;
+        while self.eat("&&") {
+            let right = self.comparison(depth)?;
+            left = Node::Binary(Box::new(left), Op::And, Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn comparison(&mut self, depth: usize) -> Result<Node> {
+        let left = self.unary(depth)?;
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => Op::Eq,
+            Some(Token::Punct("!=")) => Op::Ne,
+            Some(Token::Punct("<")) => Op::Lt,
+            Some(Token::Punct("<=")) => Op::Le,
+            Some(Token::Punct(">")) => Op::Gt,
+            Some(Token::Punct(">=")) => Op::Ge,
+            Some(Token::Ident(word)) if word == "in" => Op::In,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.unary(depth)?;
+        Ok(Node::Binary(Box::new(left), op, Box::new(right)))
+    }
+
+    fn unary(&mut self, depth: usize) -> Result<Node> {
+        if depth > MAX_DEPTH {
+            bail!("nested too deeply");
+        }
+        if self.eat("!") {
+            return Ok(Node::Not(Box::new(self.unary(depth + 1)?)));
+        }
+        if self.eat("-") {
+            return Ok(Node::Negate(Box::new(self.unary(depth + 1)?)));
+        }
+        self.postfix(depth)
+    }
+
+    fn postfix(&mut self, depth: usize) -> Result<Node> {
+        let mut node = self.primary(depth)?;
+        loop {
+            if self.eat(".") {
+                let name = self.identifier()?;
+                node = match self.eat("(") {
+                    true => {
+                        let mut args = vec![node];
+                        args.extend(self.arguments(")", depth)?);
+                        Node::Call(name, args)
+                    }
+                    false => Node::Field(Box::new(node), name),
+                };
+            } else if self.eat("[") {
+                let key = self.expression(depth)?;
+                self.expect("]")?;
+                node = Node::Index(Box::new(node), Box::new(key));
+            } else {
+                return Ok(node);
+            }
+        }
+    }
+
+    fn primary(&mut self, depth: usize) -> Result<Node> {
+        let Some(token) = self.peek().cloned() else {
+            bail!("unexpected end of expression");
+        };
+        self.pos += 1;
+        Ok(match token {
+            Token::Str(text) => Node::Literal(Value::String(text)),
+            Token::Num(number) => Node::Literal(number_value(number)),
+            Token::Ident(word) => match word.as_str() {
+                "true" => Node::Literal(Value::Bool(true)),
+                "false" => Node::Literal(Value::Bool(false)),
+                "null" => Node::Literal(Value::Null),
+                _ if self.eat("(") => Node::Call(word, self.arguments(")", depth)?),
+                _ => Node::Variable(word),
+            },
+            Token::Punct("(") => {
+                let inner = self.expression(depth)?;
+                self.expect(")")?;
+                inner
+            }
+            Token::Punct("[") => Node::List(self.arguments("]", depth)?),
+            Token::Punct(punct) => bail!("unexpected '{}'", punct),
+        })
+    }
+
+    fn identifier(&mut self) -> Result<String> {
+        match self.peek().cloned() {
+            Some(Token::Ident(name)) => {
+                self.pos += 1;
+                Ok(name)
+            }
+            other => bail!("expected a name, found {:?}", other),
+        }
+    }
+
+    /// Comma-separated expressions up to `close`
+    fn arguments(&mut self, close: &str, depth: usize) -> Result<Vec<Node>> {
+        let mut items = Vec::new();
+        if self.eat(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.expression(depth)?);
+            if self.eat(close) {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+}
+
+impl Node {
+    fn evaluate(&self, variables: &Map<String, Value>) -> Result<Value> {
+        Ok(match self {
+            Node::Literal(value) => value.clone(),
+            Node::Variable(name) => match variables.get(name) {
+                Some(value) => value.clone(),
+                None => bail!("unknown variable '{}'", name),
+            },
+            Node::List(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.evaluate(variables))
+                    .collect::<Result<_>>()?,
+            ),
+            Node::Field(target, name) => match target.evaluate(variables)? {
+                Value::Object(fields) => fields.get(name).cloned().unwrap_or(Value::Null),
+                Value::Null => Value::Null,
+                other => bail!("{} has no field '{}'", type_name(&other), name),
+            },
+            Node::Index(target, key) => {
+                match (target.evaluate(variables)?, key.evaluate(variables)?) {
+                    (Value::Object(fields), Value::String(key)) => {
+                        fields.get(&key).cloned().unwrap_or(Value::Null)
+                    }
+                    (Value::Array(items), Value::Number(i)) => i
+                        .as_u64()
+                        .and_then(|i| items.get(i as usize))
+                        .cloned()
+                        .unwrap_or(Value::Null),
+                    (Value::Null, _) => Value::Null,
+                    (target, key) => {
+                        bail!(
+                            "cannot index {} with {}",
+                            type_name(&target),
+                            type_name(&key)
+                        )
+                    }
+                }
+            }
+            Node::Call(name, args) => {
+                let args: Vec<Value> = args
+                    .iter()
+                    .map(|arg| arg.evaluate(variables))
+                    .collect::<Result<_>>()?;
+                call(name, &args)?
+            }
+            Node::Not(inner) => match inner.evaluate(variables)? {
+                Value::Bool(value) => Value::Bool(!value),
+                other => bail!("cannot negate {}", type_name(&other)),
+            },
+            Node::Negate(inner) => match inner.evaluate(variables)?.as_f64() {
+                Some(number) => number_value(-number),
+                None => bail!("cannot negate a non-number"),
+            },
+            Node::Binary(left, Op::And, right) => {
+                Value::Bool(boolean(left, variables)? && boolean(right, variables)?)
+            }
+            Node::Binary(left, Op::Or, right) => {
+                Value::Bool(boolean(left, variables)? || boolean(right, variables)?)
+            }
+            Node::Binary(left, op, right) => {
+                let (left, right) = (left.evaluate(variables)?, right.evaluate(variables)?);
+                Value::Bool(compare(&left, *op, &right)?)
+            }
+        })
+    }
+}
+
+fn boolean(node: &Node, variables: &Map<String, Value>) -> Result<bool> {
+    match node.evaluate(variables)? {
+        Value::Bool(value) => Ok(value),
+        other => bail!("expected a boolean, found {}", type_name(&other)),
+    }
+}
+
+fn compare(left: &Value, op: Op, right: &Value) -> Result<bool> {
+    let ordering = || -> Result<Ordering> {
+        match (left, right) {
+            (Value::Number(a), Value::Number(b)) => {
+                let (a, b) = (a.as_f64().unwrap_or(0.0), b.as_f64().unwrap_or(0.0));
+                a.partial_cmp(&b).context("cannot compare NaN")
+            }
+            (Value::String(a), Value::String(b)) => Ok(a.cmp(b)),
+            _ => bail!(
+                "cannot compare {} with {}",
+                type_name(left),
+                type_name(right)
+            ),
+        }
+    };
+    Ok(match op {
+        Op::Eq => equal(left, right),
+        Op::Ne => !equal(left, right),
+        Op::Lt => ordering()? == Ordering::Less,
+        Op::Le => ordering()? != Ordering::Greater,
+        Op::Gt => ordering()? == Ordering::Greater,
+        Op::Ge => ordering()? != Ordering::Less,
+        Op::In => match (left, right) {
+            (_, Value::Array(items)) => items.iter().any(|item| equal(left, item)),
+            (Value::String(needle), Value::String(text)) => text.contains(needle.as_str()),
+            (Value::String(key), Value::Object(fields)) => fields.contains_key(key),
+            _ => bail!(
+                "cannot look for {} in {}",
+                type_name(left),
+                type_name(right)
+            ),
+        },
+        Op::And | Op::Or => unreachable!("evaluated with short-circuit"),
+    })
+}
+
+/// JSON equality, with `1 == 1.0`
+fn equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value> {
+    let text = |i: usize| match args.get(i) {
+        Some(Value::String(text)) => Ok(text.as_str()),
+        Some(other) => bail!("{}() needs a string, not {}", name, type_name(other)),
+        None => bail!("{}() is missing an argument", name),
+    };
+    let arity = match name {
+        "size" | "lower" | "upper" => 1,
+        "startsWith" | "endsWith" | "contains" | "matches" | "within" => 2,
+        _ => bail!("unknown function '{}'", name),
+    };
+    if args.len() != arity {
+        bail!("wrong number of arguments to {}()", name);
+    }
+    Ok(match name {
+        "size" => Value::from(match &args[0] {
+            Value::String(text) => text.chars().count(),
+            Value::Array(items) => items.len(),
+            Value::Object(fields) => fields.len(),
+            other => bail!("{} has no size", type_name(other)),
+        }),
+        "lower" => Value::String(text(0)?.to_lowercase()),
+        "upper" => Value::String(text(0)?.to_uppercase()),
+        "contains" => match &args[0] {
+            Value::Array(items) => Value::Bool(items.iter().any(|item| equal(item, &args[1]))),
+            _ => Value::Bool(text(0)?.contains(text(1)?)),
+        },
+        "startsWith" => Value::Bool(text(0)?.starts_with(text(1)?)),
+        "endsWith" => Value::Bool(text(0)?.ends_with(text(1)?)),
+        "matches" => {
+            let pattern = regex::Regex::new(text(1)?).context("invalid regular expression")?;
+            Value::Bool(pattern.is_match(text(0)?))
+        }
+        "within" => {
+            let path = normalize(Path::new(text(0)?));
+            let dirs = match &args[1] {
+                Value::Array(dirs) => dirs.iter().filter_map(Value::as_str).collect(),
+                _ => vec![text(1)?],
+            };
+            Value::Bool(
+                path.is_absolute()
+                    && dirs
+                        .into_iter()
+                        .any(|dir| path.starts_with(normalize(Path::new(dir)))),
+            )
+        }
+        _ => unreachable!("arity checked above"),
+    })
+}
+
+/// Integers stay integers
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Value::from(number as i64)
+    } else {
+        Value::from(number)
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a map",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_evaluate_expressions() {
+        let variables = json!({
+            "tool": "write_file",
+            "args": { "path": "/home/me/project/../.ssh/id_rsa", "files": [1, 2, 3] },
+            "workspace": "/home/me/project",
+        });
+        let variables = variables.as_object().unwrap();
+        let holds = |source: &str| source.parse::<Expression>().unwrap().holds(variables);
+
+        assert!(holds(r#"tool == "write_file" && !args.path.within(workspace)"#).unwrap());
+        assert!(holds("args.path.startsWith(workspace)").unwrap());
+        assert!(holds("size(args.files) >= 3 && 2 in args.files").unwrap());
+        assert!(holds("args.missing.deeper == null || false").unwrap());
+        assert!(holds(r#"tool.matches("^write_") && !(tool in ["read_file", 'x'])"#).unwrap());
+        assert!(holds("args.path.startsWith(1)").is_err());
+        assert!(holds("args.path").is_err());
+
+        assert!("tool ==".parse::<Expression>().is_err());
+        assert!("tool == 'x' extra".parse::<Expression>().is_err());
+        assert!("(".repeat(200).parse::<Expression>().is_err());
+    }
+}
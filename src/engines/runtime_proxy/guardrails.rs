@@ -0,0 +1,323 @@
+//! Guardrails: the proxy's policy file
+//!
+//! A YAML file passed with `--guardrails` (default
+//! `~/.mcp-sentinel/guardrails.yaml` if it exists):
+//!
+//! ```yaml
+//! # Tools whose calls wait for an operator's decision (glob patterns)
+//! require_approval:
+//!   - delete_file
+//!   - send_*
+//! # Tools the model never sees and cannot call; with allow_tools set, only
+//! # matching tools are available
+//! deny_tools: ["*_admin"]
+//! allow_tools: []
+//! # Rules for the server proxied with `--server-name github`, added to the
+//! # ones above (its allow_tools replaces the global list)
+//! servers:
+//!   github:
+//!     deny_tools: [delete_repository]
+//! # Constraints on tool-call arguments, on top of the server's own schema
+//! arguments:
+//!   workspace: [/home/me/project]
+//!   url_allowlist: ["https://api.github.com/*"]
+//!   max_string_length: 10000
+//! # Response size and session content volume; over a limit is reported,
+//! # and with block: true the response is replaced with an error
+//! limits:
+//!   max_response_bytes: 1048576
+//!   max_session_bytes: 10485760
+//!   block: true
+//! # Credentials and personal data in tool results and sampling messages;
+//! # blocked messages are logged by hash to the audit log
+//! dlp:
+//!   block_secrets: true
+//!   block_pii: false
+//!   audit_log: /var/log/mcp-sentinel/audit.jsonl
+//! # Rules on tool calls as expressions over `tool`, `args` (the call's
+//! # arguments), `server` (--server-name), and the variables below; the
+//! # first rule that holds decides, and a rule that cannot be evaluated
+//! # for a call applies to it
+//! variables:
+//!   workspace: /home/me/project
+//! rules:
+//!   - name: write-outside-workspace
+//!     when: tool == "write_file" && !args.path.within(workspace)
+//!     action: deny
+//!   - name: external-email
+//!     when: tool == "send_email" && !args.to.endsWith("@example.com")
+//!     action: require_approval
+//! # Ask this URL instead of the terminal; it answers {"approved": true|false}
+//! approval_webhook: https://approvals.example.com/mcp
+//! # Calls not decided in time are denied
+//! approval_timeout_secs: 120
+//! ```
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSetBuilder};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use super::arguments::ArgumentRules;
+use super::dlp::DlpRules;
+use super::expression::Expression;
+use super::volume::VolumeLimits;
+
+/// Names every rule expression sees, set per call
+const CALL_VARIABLES: [&str; 3] = ["tool", "args", "server"];
+
+/// How long an operator has to decide by default
+const DEFAULT_APPROVAL_TIMEOUT_SECS: u64 = 300;
+
+/// Proxy policy
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Guardrails {
+    /// Tool name patterns whose calls need approval
+    pub require_approval: Vec<String>,
+    /// Tool name patterns available to the client; empty allows all
+    pub allow_tools: Vec<String>,
+    /// Tool name patterns hidden from the client and blocked
+    pub deny_tools: Vec<String>,
+    /// Additional rules per server, by `--server-name`
+    pub servers: BTreeMap<String, ServerRules>,
+    pub arguments: ArgumentRules,
+    pub limits: VolumeLimits,
+    pub dlp: DlpRules,
+    /// Expression rules on tool calls, in order
+    pub rules: Vec<PolicyRule>,
+    /// Values the rule expressions can refer to by name
+    pub variables: BTreeMap<String, Value>,
+    /// Where approval requests go instead of the terminal
+    pub approval_webhook: Option<String>,
+    pub approval_timeout_secs: u64,
+}
+
+/// Tool rules of one server
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerRules {
+    pub require_approval: Vec<String>,
+    pub allow_tools: Vec<String>,
+    pub deny_tools: Vec<String>,
+    /// Checked after the global rules
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A rule deciding tool calls by expression
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyRule {
+    pub name: String,
+    /// Holds for the calls the rule applies to
+    pub when: Expression,
+    pub action: RuleAction,
+}
+
+/// What a rule does to the calls it applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleAction {
+    Deny,
+    RequireApproval,
+}
+
+impl Default for Guardrails {
+    fn default() -> Self {
+        Self {
+            require_approval: Vec::new(),
+            allow_tools: Vec::new(),
+            deny_tools: Vec::new(),
+            servers: BTreeMap::new(),
+            arguments: ArgumentRules::default(),
+            limits: VolumeLimits::default(),
+            dlp: DlpRules::default(),
+            rules: Vec::new(),
+            variables: BTreeMap::new(),
+            approval_webhook: None,
+            approval_timeout_secs: DEFAULT_APPROVAL_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl Guardrails {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read guardrails '{}'", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Invalid guardrails file '{}'", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let guardrails: Self = serde_yaml::from_str(content)?;
+        let patterns = [
+            &guardrails.require_approval,
+            &guardrails.allow_tools,
+            &guardrails.deny_tools,
+        ]
+        .into_iter()
+        .chain(guardrails.servers.values().flat_map(|rules| {
+            [
+                &rules.require_approval,
+                &rules.allow_tools,
+                &rules.deny_tools,
+            ]
+        }))
+        .flatten()
+        .chain(&guardrails.arguments.url_allowlist);
+        for pattern in patterns {
+            Glob::new(pattern).with_context(|| format!("Invalid tool pattern '{}'", pattern))?;
+        }
+        if let Some(name) = CALL_VARIABLES
+            .iter()
+            .find(|name| guardrails.variables.contains_key(**name))
+        {
+            anyhow::bail!(
+                "Variable '{}' is set for each call and cannot be defined",
+                name
+            );
+        }
+        Ok(guardrails)
+    }
+
+    /// The rules for one server: its section merged into the global rules
+    pub fn for_server(mut self, name: &str) -> Self {
+        if let Some(rules) = self.servers.remove(name) {
+            self.require_approval.extend(rules.require_approval);
+            self.deny_tools.extend(rules.deny_tools);
+            self.rules.extend(rules.rules);
+            if !rules.allow_tools.is_empty() {
+                self.allow_tools = rules.allow_tools;
+            }
+        }
+        self
+    }
+
+    /// Whether calls to `tool` wait for approval
+    pub fn requires_approval(&self, tool: &str) -> bool {
+        matches_any(&self.require_approval, tool)
+    }
+
+    /// Whether the client may see and call `tool`
+    pub fn allows_tool(&self, tool: &str) -> bool {
+        (self.allow_tools.is_empty() || matches_any(&self.allow_tools, tool))
+            && !matches_any(&self.deny_tools, tool)
+    }
+
+    /// The first rule that holds for a call of `tool` with `arguments`,
+    /// with the error if its expression could not be evaluated
+    pub fn rule_for(
+        &self,
+        tool: &str,
+        arguments: &Value,
+        server: Option<&str>,
+    ) -> Option<(&PolicyRule, Option<String>)> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let mut variables: Map<String, Value> = self
+            .variables
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        let call = [json!(tool), arguments.clone(), json!(server)];
+        for (name, value) in CALL_VARIABLES.iter().zip(call) {
+            variables.insert(name.to_string(), value);
+        }
+        self.rules
+            .iter()
+            .find_map(|rule| match rule.when.holds(&variables) {
+                Ok(true) => Some((rule, None)),
+                Ok(false) => None,
+                Err(e) => Some((rule, Some(format!("{:#}", e)))),
+            })
+    }
+
+    pub fn approval_timeout(&self) -> Duration {
+        Duration::from_secs(self.approval_timeout_secs)
+    }
+}
+
+/// Whether `name` matches any of the glob `patterns`
+fn matches_any(patterns: &[String], name: &str) -> bool {
+    if patterns.is_empty() {
+        return false;
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        if let Ok(glob) = Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder.build().is_ok_and(|set| set.is_match(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_guardrails() {
+        let guardrails = Guardrails::parse(
+            "require_approval: [delete_file, 'send_*']\napproval_timeout_secs: 30\n",
+        )
+        .unwrap();
+        assert!(guardrails.requires_approval("delete_file"));
+        assert!(guardrails.requires_approval("send_email"));
+        assert!(!guardrails.requires_approval("read_file"));
+        assert_eq!(guardrails.approval_timeout(), Duration::from_secs(30));
+
+        assert!(Guardrails::parse("require_approval: ['[']").is_err());
+        assert!(Guardrails::parse("requires_approval: [x]").is_err());
+    }
+
+    #[test]
+    fn test_expression_rules() {
+        let yaml = "variables:\n  workspace: /home/me/project\n\
+                    rules:\n  \
+                      - name: write-outside-workspace\n    \
+                        when: tool == 'write_file' && !args.path.within(workspace)\n    \
+                        action: deny\n";
+        let guardrails = Guardrails::parse(yaml).unwrap();
+        let rule = |tool: &str, path: &str| {
+            let arguments = json!({ "path": path });
+            guardrails
+                .rule_for(tool, &arguments, None)
+                .map(|(rule, error)| (rule.action, error.is_some()))
+        };
+        assert_eq!(rule("write_file", "/home/me/project/src/main.rs"), None);
+        assert_eq!(
+            rule("write_file", "/home/me/project/../.bashrc"),
+            Some((RuleAction::Deny, false))
+        );
+        assert_eq!(rule("read_file", "/etc/passwd"), None);
+        // Without a path the rule cannot be evaluated, and applies
+        let arguments = json!({});
+        let (_, error) = guardrails.rule_for("write_file", &arguments, None).unwrap();
+        assert!(error.is_some());
+
+        assert!(Guardrails::parse("rules: [{ name: x, when: 'tool ==', action: deny }]").is_err());
+        assert!(Guardrails::parse("variables: { tool: x }").is_err());
+    }
+
+    #[test]
+    fn test_server_tool_rules() {
+        let yaml = "deny_tools: ['*_admin']\n\
+                    servers:\n  \
+                      github:\n    \
+                        allow_tools: ['get_*', 'list_*', delete_repository]\n    \
+                        deny_tools: [delete_repository]\n";
+        let global = Guardrails::parse(yaml).unwrap();
+        assert!(global.allows_tool("delete_repository"));
+        assert!(!global.allows_tool("user_admin"));
+
+        let github = global.for_server("github");
+        assert!(github.allows_tool("get_issue"));
+        assert!(!github.allows_tool("create_issue"));
+        assert!(!github.allows_tool("delete_repository"));
+        assert!(!github.allows_tool("list_admin"));
+    }
+}
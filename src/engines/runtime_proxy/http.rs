@@ -0,0 +1,398 @@
+//! Streamable HTTP upstream
+//!
+//! Puts the proxy in front of a remote MCP server. The client still talks
+//! stdio to the proxy (`mcp-sentinel proxy --upstream https://...`); each
+//! message it sends is POSTed to the upstream endpoint, and the answer,
+//! a JSON body or an SSE stream, is inspected message by message on its
+//! way back. The `Mcp-Session-Id` the server assigns is sent with every
+//! later request.
+//!
+//! The connection is meant for production servers:
+//! - plain `http://` is refused except to loopback addresses
+//! - `--ca-bundle` replaces the system roots with the given CAs
+//! - `--client-cert`/`--client-key` authenticate the proxy with mTLS
+//! - a bearer token, static or from OAuth client credentials ([`auth`]),
+//!   goes into the `Authorization` header
+//! - redirects are not followed, so the token only goes to the upstream
+//!
+//! Messages the server sends on its own, outside a response stream, are not
+//! received: the optional standalone GET stream is not opened.
+
+use anyhow::{bail, Context, Result};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Certificate, Identity, StatusCode, Url};
+use serde_json::json;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::BufReader;
+use tokio::sync::{mpsc, Mutex};
+use tracing::{info, warn};
+
+use super::auth::TokenSource;
+use super::stdio::{self, Frame};
+use super::{jsonrpc, Direction, Interceptor};
+
+const SESSION_HEADER: &str = "Mcp-Session-Id";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Room for the `data: ` field name and a CR on top of the message cap
+const FIELD_SLACK: usize = 8;
+
+/// JSON-RPC "Internal error" code, for requests the upstream never answered
+const INTERNAL_ERROR: i64 = -32603;
+
+/// A remote MCP server and how to reach it
+#[derive(Debug, Clone)]
+pub struct Upstream {
+    pub url: Url,
+    /// PEM file of the CAs trusted for the upstream, instead of the system
+    /// roots
+    pub ca_bundle: Option<PathBuf>,
+    /// PEM certificate chain and PKCS#8 key presented to the upstream
+    pub client_identity: Option<(PathBuf, PathBuf)>,
+    pub token: Option<TokenSource>,
+}
+
+/// Parse an upstream or token endpoint URL, refusing plain HTTP to anything
+/// but the local machine
+pub fn parse_url(url: &str) -> Result<Url> {
+    let parsed = Url::parse(url).with_context(|| format!("Invalid URL '{}'", url))?;
+    let host = parsed.host_str().unwrap_or_default();
+    let loopback = host == "localhost"
+        || host
+            .trim_matches(['[', ']'])
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    match parsed.scheme() {
+        "https" | "wss" => Ok(parsed),
+        "http" | "ws" if loopback => Ok(parsed),
+        "http" => bail!("Refusing plain HTTP to '{}'; use https://", url),
+        "ws" => bail!("Refusing unencrypted WebSocket to '{}'; use wss://", url),
+        scheme => bail!("Unsupported URL scheme '{}' in '{}'", scheme, url),
+    }
+}
+
+impl Upstream {
+    /// HTTP client with the upstream's TLS settings
+    pub fn client(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(certificates) = self.ca_certificates()? {
+            builder = builder.tls_built_in_root_certs(false);
+            for certificate in certificates {
+                let certificate = Certificate::from_pem(certificate.as_bytes())
+                    .context("Invalid certificate in CA bundle")?;
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if let Some((cert, key)) = self.identity_pem()? {
+            let identity = Identity::from_pkcs8_pem(&cert, &key)
+                .context("Invalid client certificate or key")?;
+            builder = builder.identity(identity);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// The PEM certificates of `--ca-bundle`, if given
+    pub(crate) fn ca_certificates(&self) -> Result<Option<Vec<String>>> {
+        let Some(path) = &self.ca_bundle else {
+            return Ok(None);
+        };
+        let pem = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read CA bundle '{}'", path.display()))?;
+        let certificates = pem_certificates(&pem);
+        if certificates.is_empty() {
+            bail!("No certificates in CA bundle '{}'", path.display());
+        }
+        Ok(Some(certificates))
+    }
+
+    /// The PEM client certificate and key, if given
+    pub(crate) fn identity_pem(&self) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let Some((cert, key)) = &self.client_identity else {
+            return Ok(None);
+        };
+        let read = |path: &PathBuf| {
+            std::fs::read(path).with_context(|| format!("Failed to read '{}'", path.display()))
+        };
+        Ok(Some((read(cert)?, read(key)?)))
+    }
+}
+
+/// The PEM blocks of the certificates in a bundle
+fn pem_certificates(pem: &str) -> Vec<String> {
+    const END: &str = "-----END CERTIFICATE-----";
+    pem.split_inclusive(END)
+        .filter_map(|block| {
+            let start = block.find("-----BEGIN CERTIFICATE-----")?;
+            block.ends_with(END).then(|| block[start..].to_string())
+        })
+        .collect()
+}
+
+/// Proxy the client on stdio to `upstream` until the client closes
+pub async fn run(upstream: Upstream, interceptor: Interceptor) -> Result<()> {
+    info!("Proxying remote MCP server: {}", upstream.url);
+    let client = upstream.client()?;
+
+    let (to_client, client_queue) = mpsc::channel::<String>(64);
+    let (to_server, mut server_queue) = mpsc::channel::<String>(64);
+    let client_writer = tokio::spawn(stdio::drain(client_queue, tokio::io::stdout()));
+
+    let session = Session {
+        client,
+        upstream: Arc::new(upstream),
+        id: Arc::default(),
+        interceptor: interceptor.clone(),
+        to_client: to_client.clone(),
+        to_server: to_server.clone(),
+    };
+    // Requests run concurrently; a slow tool call does not hold up others
+    let sender = tokio::spawn(async move {
+        while let Some(message) = server_queue.recv().await {
+            let session = session.clone();
+            tokio::spawn(async move { session.send(message).await });
+        }
+    });
+
+    let result = stdio::pump(
+        BufReader::new(tokio::io::stdin()),
+        Direction::ClientToServer,
+        interceptor,
+        to_server,
+        to_client,
+    )
+    .await;
+    // Requests still in flight can no longer be answered
+    sender.abort();
+    client_writer.abort();
+    result
+}
+
+/// State shared by the requests of one MCP session
+#[derive(Clone)]
+struct Session {
+    client: reqwest::Client,
+    upstream: Arc<Upstream>,
+    /// `Mcp-Session-Id` assigned by the server
+    id: Arc<Mutex<Option<String>>>,
+    interceptor: Interceptor,
+    to_client: mpsc::Sender<String>,
+    to_server: mpsc::Sender<String>,
+}
+
+impl Session {
+    /// POST one message upstream and pass on what comes back; a request
+    /// that fails is answered with an error so the client does not hang
+    async fn send(&self, message: String) {
+        let Err(e) = self.exchange(&message).await else {
+            return;
+        };
+        warn!("Upstream request failed: {:#}", e);
+        let reply = jsonrpc::message_id(&message)
+            .filter(|_| !jsonrpc::is_response(&message))
+            .map(|id| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": INTERNAL_ERROR,
+                        "message": format!("Upstream MCP server request failed: {:#}", e),
+                    },
+                })
+                .to_string()
+            });
+        if let Some(reply) = reply {
+            let _ = self.to_client.send(reply).await;
+        }
+    }
+
+    async fn exchange(&self, message: &str) -> Result<()> {
+        let mut response = self.post(message).await?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            if let Some(token) = &self.upstream.token {
+                // The token may have been revoked or expired early
+                token.invalidate().await;
+                response = self.post(message).await?;
+            }
+        }
+        let mut response = response
+            .error_for_status()
+            .context("Upstream rejected the request")?;
+        if let Some(id) = response.headers().get(SESSION_HEADER) {
+            *self.id.lock().await = id.to_str().ok().map(str::to_string);
+        }
+        if response.status() == StatusCode::ACCEPTED {
+            return Ok(());
+        }
+
+        let events = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        let max = self.interceptor.config().max_message_bytes;
+        let mut parser = EventParser::new(max);
+        let mut body = Vec::new();
+        let mut len = 0;
+        while let Some(chunk) = response.chunk().await? {
+            if events {
+                for frame in parser.feed(&chunk) {
+                    self.deliver(frame).await;
+                }
+            } else {
+                len += chunk.len();
+                if len <= max {
+                    body.extend_from_slice(&chunk);
+                }
+            }
+        }
+        if !events && len > 0 {
+            let frame = match len > max {
+                true => Frame::Oversized(len),
+                false => Frame::Message(String::from_utf8_lossy(&body).into_owned()),
+            };
+            self.deliver(frame).await;
+        }
+        Ok(())
+    }
+
+    async fn post(&self, message: &str) -> Result<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(self.upstream.url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "application/json, text/event-stream")
+            .body(message.to_string());
+        if let Some(id) = self.id.lock().await.clone() {
+            request = request.header(SESSION_HEADER, id);
+        }
+        if let Some(token) = &self.upstream.token {
+            let token = token.token(&self.client).await?;
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token.expose()));
+        }
+        request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach {}", self.upstream.url))
+    }
+
+    async fn deliver(&self, frame: Frame) {
+        stdio::deliver(&self.interceptor, &frame, &self.to_client, &self.to_server).await;
+    }
+}
+
+/// Incremental parser of a `text/event-stream` body into messages, one per
+/// event, with the same size cap as stdio lines
+#[derive(Debug)]
+struct EventParser {
+    max_bytes: usize,
+    line: Vec<u8>,
+    line_len: usize,
+    data: String,
+    /// Size of the current event once it went over the cap
+    oversized: Option<usize>,
+}
+
+impl EventParser {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            line: Vec::new(),
+            line_len: 0,
+            data: String::new(),
+            oversized: None,
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8]) -> Vec<Frame> {
+        let mut frames = Vec::new();
+        for &byte in chunk {
+            if byte != b'\n' {
+                // A line over the cap is not buffered past it
+                self.line_len += 1;
+                if self.line_len <= self.max_bytes + FIELD_SLACK {
+                    self.line.push(byte);
+                }
+                continue;
+            }
+            let (line, len) = (
+                std::mem::take(&mut self.line),
+                std::mem::take(&mut self.line_len),
+            );
+            if len > self.max_bytes + FIELD_SLACK {
+                self.oversized = Some(self.oversized.unwrap_or(self.data.len()) + len);
+                self.data.clear();
+                continue;
+            }
+            let line = String::from_utf8_lossy(&line);
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+            if line.is_empty() {
+                let data = std::mem::take(&mut self.data);
+                match self.oversized.take() {
+                    Some(len) => frames.push(Frame::Oversized(len)),
+                    None if !data.is_empty() => frames.push(Frame::Message(data)),
+                    None => {}
+                }
+                continue;
+            }
+            let Some(value) = line.strip_prefix("data:") else {
+                // event:, id:, retry:, and comments carry no message
+                continue;
+            };
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            if let Some(size) = &mut self.oversized {
+                *size += value.len() + 1;
+                continue;
+            }
+            if !self.data.is_empty() {
+                self.data.push('\n');
+            }
+            self.data.push_str(value);
+            if self.data.len() > self.max_bytes {
+                self.oversized = Some(self.data.len());
+                self.data.clear();
+            }
+        }
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url_requires_tls() {
+        assert!(parse_url("https://mcp.example.com/mcp").is_ok());
+        assert!(parse_url("wss://mcp.example.com/ws").is_ok());
+        assert!(parse_url("ws://[::1]:8765").is_ok());
+        assert!(parse_url("ws://mcp.example.com/ws").is_err());
+        assert!(parse_url("http://127.0.0.1:3000/mcp").is_ok());
+        assert!(parse_url("http://localhost:3000/mcp").is_ok());
+        assert!(parse_url("http://mcp.example.com/mcp").is_err());
+        assert!(parse_url("http://10.0.0.5/mcp").is_err());
+        assert!(parse_url("ftp://mcp.example.com").is_err());
+    }
+
+    #[test]
+    fn test_event_stream_frames() {
+        let mut parser = EventParser::new(32);
+        let mut frames = parser.feed(b"event: message\r\ndata: {\"id\":1,");
+        frames.extend(parser.feed(b"\"result\":{}}\r\n\r\n: keepalive\n\n"));
+        frames
+            .extend(parser.feed(format!("data: {}\n\ndata: {{}}\n\n", "x".repeat(40)).as_bytes()));
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Message("{\"id\":1,\"result\":{}}".to_string()),
+                Frame::Oversized(46),
+                Frame::Message("{}".to_string()),
+            ]
+        );
+    }
+}
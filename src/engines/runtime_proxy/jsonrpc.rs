@@ -0,0 +1,333 @@
+//! Structural validation of MCP JSON-RPC messages
+//!
+//! Every message crossing the proxy is checked against the JSON-RPC 2.0
+//! envelope MCP uses before any policy looks at its contents:
+//! - the message is a JSON object (or a non-empty batch of them) within the
+//!   configured size limit
+//! - `jsonrpc` is `"2.0"`
+//! - ids are strings or integers, never `null` or fractional
+//! - method names are short, printable, and non-empty
+//! - `params` is an object, as MCP requires
+//! - responses carry exactly one of `result` and `error`, and errors have
+//!   an integer `code` and a string `message`
+//!
+//! Malformed messages are what parser-confusion attacks against clients and
+//! servers look like, so each problem is reported as a [`Violation`] with a
+//! severity the proxy can block on.
+
+use serde_json::{Map, Value};
+
+use crate::models::vulnerability::Severity;
+
+/// Default limit on a single message (4 MiB)
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// Longest method name accepted; MCP's own are well under 64 characters
+const MAX_METHOD_LEN: usize = 128;
+
+/// Shape of a valid message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Request,
+    Notification,
+    Response,
+    Batch,
+}
+
+/// A problem with a message, structural or in its content
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// Short kebab-case rule ID, e.g. `invalid-id`
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(rule: impl Into<String>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Violation for a message that was never read in full
+    pub fn oversized(len: usize, max: usize) -> Self {
+        Self::new(
+            "oversized-message",
+            Severity::High,
+            format!("Message of {} bytes exceeds the {} byte limit", len, max),
+        )
+    }
+}
+
+/// Parse one raw message without checking its structure
+pub fn parse(raw: &str, max_bytes: usize) -> Result<Value, Violation> {
+    if raw.len() > max_bytes {
+        return Err(Violation::oversized(raw.len(), max_bytes));
+    }
+    serde_json::from_str(raw).map_err(|e| {
+        Violation::new(
+            "invalid-json",
+            Severity::High,
+            format!("Message is not valid JSON: {}", e),
+        )
+    })
+}
+
+/// Parse and validate one raw message
+///
+/// Returns the parsed message and its kind, or every violation found.
+pub fn validate(raw: &str, max_bytes: usize) -> Result<(Value, MessageKind), Vec<Violation>> {
+    let value = parse(raw, max_bytes).map_err(|violation| vec![violation])?;
+    let (kind, violations) = check(&value);
+    if violations.is_empty() {
+        Ok((value, kind))
+    } else {
+        Err(violations)
+    }
+}
+
+/// Kind of a parsed message and every structural violation in it
+pub fn check(value: &Value) -> (MessageKind, Vec<Violation>) {
+    let mut violations = Vec::new();
+    let kind = match value {
+        Value::Array(items) if items.is_empty() => {
+            violations.push(Violation::new(
+                "empty-batch",
+                Severity::Medium,
+                "Batch contains no messages",
+            ));
+            MessageKind::Batch
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Object(message) => {
+                        check_message(message, &mut violations);
+                    }
+                    _ => violations.push(not_an_object()),
+                }
+            }
+            MessageKind::Batch
+        }
+        Value::Object(message) => check_message(message, &mut violations),
+        _ => {
+            violations.push(not_an_object());
+            MessageKind::Request
+        }
+    };
+    (kind, violations)
+}
+
+/// The `id` of a message, if it has a usable one
+///
+/// Used to answer a blocked request with an error instead of leaving the
+/// client waiting.
+pub fn message_id(raw: &str) -> Option<Value> {
+    let value: Value = serde_json::from_str(raw).ok()?;
+    match value.get("id")? {
+        id @ Value::String(_) => Some(id.clone()),
+        id @ Value::Number(n) if n.is_i64() || n.is_u64() => Some(id.clone()),
+        _ => None,
+    }
+}
+
+/// Whether a message is a response (an id but no method)
+pub fn is_response(raw: &str) -> bool {
+    serde_json::from_str::<Value>(raw)
+        .is_ok_and(|v| v.get("id").is_some() && v.get("method").is_none())
+}
+
+fn not_an_object() -> Violation {
+    Violation::new(
+        "invalid-envelope",
+        Severity::High,
+        "Message is not a JSON-RPC object",
+    )
+}
+
+fn check_message(message: &Map<String, Value>, violations: &mut Vec<Violation>) -> MessageKind {
+    if message.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        violations.push(Violation::new(
+            "invalid-version",
+            Severity::Medium,
+            "Missing or wrong \"jsonrpc\" version (expected \"2.0\")",
+        ));
+    }
+
+    let id = message.get("id");
+    if let Some(id) = id {
+        let valid = match id {
+            Value::String(_) => true,
+            Value::Number(n) => n.is_i64() || n.is_u64(),
+            _ => false,
+        };
+        if !valid {
+            violations.push(Violation::new(
+                "invalid-id",
+                Severity::Medium,
+                format!("Request id must be a string or integer, got {}", id),
+            ));
+        }
+    }
+
+    if let Some(method) = message.get("method") {
+        check_method(method, violations);
+        match message.get("params") {
+            None | Some(Value::Object(_)) => {}
+            Some(_) => violations.push(Violation::new(
+                "invalid-params",
+                Severity::Medium,
+                "\"params\" must be an object",
+            )),
+        }
+        if message.contains_key("result") || message.contains_key("error") {
+            violations.push(Violation::new(
+                "ambiguous-message",
+                Severity::High,
+                "Message has both a method and a result or error",
+            ));
+        }
+        return if id.is_some() {
+            MessageKind::Request
+        } else {
+            MessageKind::Notification
+        };
+    }
+
+    if id.is_none() {
+        violations.push(Violation::new(
+            "invalid-envelope",
+            Severity::Medium,
+            "Message has neither a method nor an id",
+        ));
+    }
+    match (message.get("result"), message.get("error")) {
+        (Some(_), None) => {}
+        (None, Some(error)) => check_error(error, violations),
+        (Some(_), Some(_)) => violations.push(Violation::new(
+            "ambiguous-message",
+            Severity::High,
+            "Response has both a result and an error",
+        )),
+        (None, None) => violations.push(Violation::new(
+            "invalid-envelope",
+            Severity::Medium,
+            "Response has neither a result nor an error",
+        )),
+    }
+    MessageKind::Response
+}
+
+fn check_method(method: &Value, violations: &mut Vec<Violation>) {
+    let Some(method) = method.as_str() else {
+        violations.push(Violation::new(
+            "invalid-method",
+            Severity::Medium,
+            "\"method\" must be a string",
+        ));
+        return;
+    };
+    if method.is_empty() || method.len() > MAX_METHOD_LEN {
+        violations.push(Violation::new(
+            "invalid-method",
+            Severity::Medium,
+            format!("Method name length {} is out of range", method.len()),
+        ));
+    } else if method.chars().any(|c| c.is_control() || c.is_whitespace()) {
+        violations.push(Violation::new(
+            "invalid-method",
+            Severity::High,
+            format!(
+                "Method name {:?} contains whitespace or control characters",
+                method
+            ),
+        ));
+    }
+}
+
+fn check_error(error: &Value, violations: &mut Vec<Violation>) {
+    let valid = error.get("code").is_some_and(|c| c.is_i64())
+        && error.get("message").is_some_and(Value::is_string);
+    if !valid {
+        violations.push(Violation::new(
+            "invalid-error",
+            Severity::Medium,
+            "Error must have an integer \"code\" and a string \"message\"",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(raw: &str) -> Vec<String> {
+        validate(raw, DEFAULT_MAX_MESSAGE_BYTES)
+            .unwrap_err()
+            .into_iter()
+            .map(|v| v.rule)
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_messages() {
+        let cases = [
+            (
+                r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"x"}}"#,
+                MessageKind::Request,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                MessageKind::Notification,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","id":"a","result":{"tools":[]}}"#,
+                MessageKind::Response,
+            ),
+            (
+                r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32601,"message":"nope"}}"#,
+                MessageKind::Response,
+            ),
+        ];
+        for (raw, kind) in cases {
+            assert_eq!(validate(raw, DEFAULT_MAX_MESSAGE_BYTES).unwrap().1, kind);
+        }
+    }
+
+    #[test]
+    fn test_malformed_messages() {
+        assert_eq!(rules("{not json"), vec!["invalid-json"]);
+        assert_eq!(rules(r#""hello""#), vec!["invalid-envelope"]);
+        assert_eq!(
+            rules(r#"{"jsonrpc":"1.0","id":null,"method":"tools/list"}"#),
+            vec!["invalid-version", "invalid-id"]
+        );
+        assert_eq!(
+            rules(r#"{"jsonrpc":"2.0","id":1.5,"method":"tools\ncall","params":[1]}"#),
+            vec!["invalid-id", "invalid-method", "invalid-params"]
+        );
+        assert_eq!(
+            rules(r#"{"jsonrpc":"2.0","id":3,"result":{},"error":{"code":1,"message":""}}"#),
+            vec!["ambiguous-message"]
+        );
+        assert_eq!(
+            rules(r#"{"jsonrpc":"2.0","id":3,"error":{"code":"x"}}"#),
+            vec!["invalid-error"]
+        );
+        assert_eq!(rules("[]"), vec!["empty-batch"]);
+    }
+
+    #[test]
+    fn test_size_limit_and_message_id() {
+        let raw = r#"{"jsonrpc":"2.0","id":"req-7","method":"tools/list"}"#;
+        let err = validate(raw, 16).unwrap_err();
+        assert_eq!(err[0].rule, "oversized-message");
+        assert_eq!(message_id(raw), Some(Value::from("req-7")));
+        assert_eq!(message_id(r#"{"id":null}"#), None);
+        assert!(!is_response(raw));
+        assert!(is_response(r#"{"jsonrpc":"2.0","id":"req-7","result":{}}"#));
+    }
+}
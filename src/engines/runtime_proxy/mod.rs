@@ -0,0 +1,881 @@
+//! Runtime MCP proxy
+//!
+//! Sits between an MCP client and server and inspects every JSON-RPC
+//! message in both directions. The [`Interceptor`] decides per message
+//! whether to forward it; transports ([`stdio`] for local servers, [`http`]
+//! and [`websocket`] for remote ones over TLS) only move bytes.
+//!
+//! Messages that are not JSON are dropped. The rest are validated
+//! structurally ([`jsonrpc`]) and have their content checked:
+//! - tool names and descriptions in `tools/list` results, for invisible
+//!   Unicode and homoglyphs
+//! - prompt and resource definitions and `prompts/get` messages, for
+//!   injection payloads and credentials in URIs
+//! - `sampling/createMessage` requests from the server, for injection
+//!   payloads and attempts to extract the conversation ([`sampling`])
+//!
+//! Findings are logged as WARN events, and shown live with `--dashboard`
+//! ([`dashboard`]). With `--block-on-risk` set, messages
+//! whose worst finding reaches that severity are dropped. A blocked request
+//! is answered with a JSON-RPC error, and a blocked response is replaced by
+//! one, so neither side hangs waiting.
+//!
+//! The [`guardrails`] policy then applies: denied tools are removed from
+//! `tools/list` results, so the model never sees them, and calls to them
+//! are blocked; calls whose [`arguments`] break the tool's declared schema
+//! or the guardrails' constraints are rejected; rules written as
+//! [`expression`]s deny calls or hold them for approval; calls to tools marked
+//! `require_approval` are held until an operator approves or denies them
+//! ([`approval`]); responses over the size and session [`volume`] limits
+//! are reported or blocked; tool results and sampling messages carrying
+//! credentials (or, if configured, personal data) are blocked and recorded
+//! in the audit log ([`dlp`]). Changes to the guardrails file apply to
+//! running sessions without restarting them ([`reload`]).
+//!
+//! In [`quarantine`] mode, servers missing from the signed allowlist are
+//! not started at all.
+//!
+//! One proxy can also front every server of an MCP configuration file
+//! ([`multiplex`]), each under its own guardrails, with tool calls, findings
+//! and blocks from all of them recorded in one [`audit`] log.
+
+pub mod approval;
+pub mod arguments;
+pub mod audit;
+pub mod auth;
+pub mod dashboard;
+pub mod dlp;
+pub mod events;
+pub mod expression;
+pub mod guardrails;
+pub mod http;
+pub mod jsonrpc;
+pub mod multiplex;
+pub mod quarantine;
+pub mod reload;
+pub mod sampling;
+pub mod stdio;
+pub mod volume;
+pub mod websocket;
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn, Span};
+
+use crate::detectors::{hidden_unicode, prompts_resources};
+use crate::models::vulnerability::{Severity, Vulnerability};
+use crate::utils::telemetry;
+use approval::{ApprovalRequest, Approver};
+use events::{CallOutcome, EventSink, ProxyEvent};
+use guardrails::{Guardrails, RuleAction};
+use jsonrpc::Violation;
+use volume::VolumeMeter;
+
+/// JSON-RPC "Invalid Request" error code
+const INVALID_REQUEST: i64 = -32600;
+
+/// Requests awaiting a response beyond which the `rpc` spans of unanswered
+/// ones are dropped
+const MAX_OPEN_RPC_SPANS: usize = 1024;
+
+/// Which way a message travels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+impl Direction {
+    /// Stable identifier for span fields (snake_case)
+    pub fn id(&self) -> &'static str {
+        match self {
+            Direction::ClientToServer => "client_to_server",
+            Direction::ServerToClient => "server_to_client",
+        }
+    }
+
+    /// The direction responses to messages going this way travel
+    pub fn reverse(&self) -> Self {
+        match self {
+            Direction::ClientToServer => Direction::ServerToClient,
+            Direction::ServerToClient => Direction::ClientToServer,
+        }
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::ClientToServer => write!(f, "client -> server"),
+            Direction::ServerToClient => write!(f, "server -> client"),
+        }
+    }
+}
+
+/// Proxy settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyConfig {
+    /// Largest message accepted, in bytes
+    pub max_message_bytes: usize,
+    /// Drop messages with findings at or above this severity
+    pub block_on: Option<Severity>,
+    pub guardrails: Guardrails,
+    /// Name of the proxied server, for the audit log
+    pub server: Option<String>,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            max_message_bytes: jsonrpc::DEFAULT_MAX_MESSAGE_BYTES,
+            block_on: None,
+            guardrails: Guardrails::default(),
+            server: None,
+        }
+    }
+}
+
+/// What to do with an inspected message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// Pass the message on unchanged
+    Forward,
+    /// Pass this message on instead (an error in place of a blocked response)
+    Replace(String),
+    /// Drop the message; `reply` is sent back to its sender when the
+    /// message was a request that expects an answer
+    Block { reply: Option<String> },
+    /// Hold the message until the operator decides; forward it if approved,
+    /// otherwise answer it with [`denied_reply`]
+    Hold(ApprovalRequest),
+}
+
+/// Per-message policy of the proxy
+#[derive(Debug, Clone)]
+pub struct Interceptor {
+    /// Replaced whole on [`reload`](Self::reload), so each message is
+    /// judged by one policy throughout
+    policy: Arc<RwLock<Arc<Policy>>>,
+    /// `inputSchema` of each tool in the latest `tools/list` result
+    schemas: Arc<Mutex<HashMap<String, Value>>>,
+    /// `rpc` span of each forwarded request awaiting its response, by
+    /// direction and id
+    rpc_spans: Arc<Mutex<HashMap<(Direction, String), Span>>>,
+    events: EventSink,
+}
+
+/// The settings an interceptor judges by
+#[derive(Debug)]
+struct Policy {
+    config: ProxyConfig,
+    approver: Approver,
+    volume: VolumeMeter,
+}
+
+impl Default for Interceptor {
+    fn default() -> Self {
+        Self::new(ProxyConfig::default())
+    }
+}
+
+impl Interceptor {
+    pub fn new(config: ProxyConfig) -> Self {
+        let policy = Policy {
+            approver: Approver::new(&config.guardrails),
+            volume: VolumeMeter::new(config.guardrails.limits.clone()),
+            config,
+        };
+        Self {
+            policy: Arc::new(RwLock::new(Arc::new(policy))),
+            schemas: Arc::default(),
+            rpc_spans: Arc::default(),
+            events: EventSink::default(),
+        }
+    }
+
+    /// Judge later messages by `guardrails`, in this interceptor and all its
+    /// clones. Open sessions carry on: the tool schemas seen and the session
+    /// volume counted so far are kept, and held calls still wait for their
+    /// decision.
+    pub fn reload(&self, guardrails: Guardrails) {
+        let mut policy = self.policy.write().unwrap_or_else(PoisonError::into_inner);
+        let reloaded = Policy {
+            approver: policy.approver.reconfigure(&guardrails),
+            volume: policy.volume.with_limits(guardrails.limits.clone()),
+            config: ProxyConfig {
+                guardrails,
+                ..policy.config.clone()
+            },
+        };
+        *policy = Arc::new(reloaded);
+    }
+
+    /// Report activity to `events` (for the dashboard)
+    pub fn with_events(mut self, events: mpsc::Sender<ProxyEvent>) -> Self {
+        self.events = EventSink::new(events);
+        self
+    }
+
+    pub fn config(&self) -> ProxyConfig {
+        self.policy().config.clone()
+    }
+
+    pub fn approver(&self) -> Approver {
+        self.policy().approver.clone()
+    }
+
+    pub fn events(&self) -> &EventSink {
+        &self.events
+    }
+
+    /// Inspect one raw message
+    pub fn inspect(&self, direction: Direction, raw: &str) -> Verdict {
+        self.events.emit(ProxyEvent::Message {
+            direction,
+            bytes: raw.len(),
+        });
+        let verdict = self.judge(direction, raw);
+        if tracing::span_enabled!(target: telemetry::SPAN_TARGET, tracing::Level::INFO) {
+            self.trace_rpc(direction, raw, &verdict);
+        }
+        if direction == Direction::ClientToServer && self.events.is_active() {
+            let outcome = match &verdict {
+                Verdict::Forward | Verdict::Replace(_) => CallOutcome::Forwarded,
+                Verdict::Block { .. } => CallOutcome::Blocked,
+                Verdict::Hold(_) => CallOutcome::AwaitingApproval,
+            };
+            let message = serde_json::from_str(raw).unwrap_or(Value::Null);
+            for (_, tool, _) in tool_calls(&message) {
+                self.events.emit(ProxyEvent::ToolCall {
+                    tool: tool.to_string(),
+                    outcome,
+                });
+            }
+        }
+        verdict
+    }
+
+    /// Open an `rpc` span for each forwarded request and close it when its
+    /// response passes the other way, so the span covers the round-trip
+    fn trace_rpc(&self, direction: Direction, raw: &str, verdict: &Verdict) {
+        let Ok(message) = serde_json::from_str::<Value>(raw) else {
+            return;
+        };
+        let messages = match &message {
+            Value::Array(items) => items.iter().collect(),
+            single => vec![single],
+        };
+        let forwarded = !matches!(verdict, Verdict::Block { .. });
+        let mut spans = self
+            .rpc_spans
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for message in messages {
+            let method = message.get("method").and_then(Value::as_str);
+            let id = message
+                .get("id")
+                .filter(|id| !id.is_null())
+                .map(Value::to_string);
+            match (method, id) {
+                (Some(method), Some(id)) if forwarded => {
+                    if spans.len() >= MAX_OPEN_RPC_SPANS {
+                        debug!("Dropping spans of {} unanswered requests", spans.len());
+                        spans.clear();
+                    }
+                    let span = telemetry::rpc_span(method, direction.id());
+                    spans.insert((direction, id), span);
+                }
+                // Notifications get no response; their span ends right away
+                (Some(method), None) => drop(telemetry::rpc_span(method, direction.id())),
+                (None, Some(id)) => {
+                    spans.remove(&(direction.reverse(), id));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn policy(&self) -> Arc<Policy> {
+        let policy = self.policy.read().unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(&policy)
+    }
+
+    fn judge(&self, direction: Direction, raw: &str) -> Verdict {
+        let policy = self.policy();
+        let max = policy.config.max_message_bytes;
+        let message = match jsonrpc::parse(raw, max) {
+            Ok(message) => message,
+            // Nothing in a message that cannot be read can be checked
+            Err(violation) => {
+                self.report(direction, &violation);
+                self.blocked(direction, &violation);
+                return Verdict::Block { reply: None };
+            }
+        };
+        self.events.emit_peers(direction, &message);
+        // A malformed envelope still gets the guardrails and DLP below, so
+        // breaking the structure cannot smuggle a denied call past them
+        let (_, mut violations) = jsonrpc::check(&message);
+        violations.extend(inspect_content(direction, &message));
+        if !violations.is_empty() {
+            let verdict = self.decide(&policy, direction, raw, &violations);
+            if verdict != Verdict::Forward {
+                return verdict;
+            }
+        }
+        if direction == Direction::ServerToClient {
+            if let Some((violation, block)) = policy.volume.measure(raw.len(), &message) {
+                self.report(direction, &violation);
+                if block {
+                    self.blocked(direction, &violation);
+                    let error = message
+                        .get("id")
+                        .map(|id| error_reply(id.clone(), &violation));
+                    return match error {
+                        Some(error) => Verdict::Replace(error),
+                        None => Verdict::Block { reply: None },
+                    };
+                }
+            }
+        }
+        if let Some(verdict) = self.prevent_leak(&policy, direction, raw, &message) {
+            return verdict;
+        }
+        self.enforce(&policy, direction, message)
+    }
+
+    /// Block a message carrying credentials or personal data, per the
+    /// guardrails' DLP rules
+    fn prevent_leak(
+        &self,
+        policy: &Policy,
+        direction: Direction,
+        raw: &str,
+        message: &Value,
+    ) -> Option<Verdict> {
+        let dlp = &policy.config.guardrails.dlp;
+        let leak = dlp.inspect(message)?;
+        for violation in &leak.violations {
+            self.report(direction, violation);
+        }
+        if !leak.block {
+            return None;
+        }
+        if let Err(e) = dlp.audit(policy.config.server.as_deref(), direction, raw, &leak) {
+            warn!("{:#}", e);
+        }
+        let worst = leak.violations.iter().max_by_key(|v| v.severity)?;
+        self.blocked(direction, worst);
+        let error = message.get("id").map(|id| error_reply(id.clone(), worst));
+        Some(match error {
+            Some(error) if jsonrpc::is_response(raw) => Verdict::Replace(error),
+            reply => Verdict::Block { reply },
+        })
+    }
+
+    /// Apply the guardrails to a message that passed inspection
+    fn enforce(&self, policy: &Policy, direction: Direction, mut message: Value) -> Verdict {
+        let guardrails = &policy.config.guardrails;
+        // A batch cannot be answered with one error
+        let batch = message.is_array();
+
+        if direction == Direction::ServerToClient {
+            let mut hidden = Vec::new();
+            for item in messages_mut(&mut message) {
+                if let Some(tools) = item
+                    .pointer_mut("/result/tools")
+                    .and_then(Value::as_array_mut)
+                {
+                    tools.retain(|tool| {
+                        let name = tool.get("name").and_then(Value::as_str).unwrap_or_default();
+                        let allowed = guardrails.allows_tool(name);
+                        if !allowed {
+                            hidden.push(name.to_string());
+                        }
+                        allowed
+                    });
+                    self.remember_schemas(tools);
+                }
+            }
+            if hidden.is_empty() {
+                return Verdict::Forward;
+            }
+            info!("Hid denied tools from tools/list: {}", hidden.join(", "));
+            return Verdict::Replace(message.to_string());
+        }
+
+        for (call, tool, params) in tool_calls(&message) {
+            if !guardrails.allows_tool(tool) {
+                let violation = Violation::new(
+                    "guardrails/tool-denied",
+                    Severity::High,
+                    format!("Call to denied tool '{}'", tool),
+                );
+                self.report(direction, &violation);
+                self.blocked(direction, &violation);
+                let reply = call.get("id").filter(|_| !batch);
+                return Verdict::Block {
+                    reply: reply.map(|id| error_reply(id.clone(), &violation)),
+                };
+            }
+            let args = params.get("arguments").unwrap_or(&Value::Null);
+            let schema = self.schemas.lock().ok().and_then(|s| s.get(tool).cloned());
+            let mut problems = schema.map_or_else(Vec::new, |s| arguments::check_schema(&s, args));
+            problems.extend(guardrails.arguments.check(args));
+            if !problems.is_empty() {
+                let violation = Violation::new(
+                    "guardrails/invalid-arguments",
+                    Severity::High,
+                    format!("Invalid arguments for '{}': {}", tool, problems.join("; ")),
+                );
+                self.report(direction, &violation);
+                self.blocked(direction, &violation);
+                let reply = call.get("id").filter(|_| !batch);
+                return Verdict::Block {
+                    reply: reply.map(|id| error_reply(id.clone(), &violation)),
+                };
+            }
+            let approval = match guardrails.rule_for(tool, args, policy.config.server.as_deref()) {
+                Some((rule, error)) if rule.action == RuleAction::Deny => {
+                    let message = match error {
+                        Some(error) => format!(
+                            "Call to '{}' denied by rule '{}', which could not be evaluated: {}",
+                            tool, rule.name, error
+                        ),
+                        None => format!(
+                            "Call to '{}' denied by rule '{}': {}",
+                            tool, rule.name, rule.when
+                        ),
+                    };
+                    let violation =
+                        Violation::new(format!("policy/{}", rule.name), Severity::High, message);
+                    self.report(direction, &violation);
+                    self.blocked(direction, &violation);
+                    let reply = call.get("id").filter(|_| !batch);
+                    return Verdict::Block {
+                        reply: reply.map(|id| error_reply(id.clone(), &violation)),
+                    };
+                }
+                Some((rule, error)) => {
+                    if let Some(error) = error {
+                        warn!(
+                            "Rule '{}' could not be evaluated for a call to '{}', holding it: {}",
+                            rule.name, tool, error
+                        );
+                    }
+                    true
+                }
+                None => false,
+            };
+            if approval || guardrails.requires_approval(tool) {
+                return Verdict::Hold(ApprovalRequest {
+                    tool: tool.to_string(),
+                    arguments: params.get("arguments").cloned().unwrap_or(Value::Null),
+                    id: call.get("id").cloned().filter(|_| !batch),
+                });
+            }
+        }
+        Verdict::Forward
+    }
+
+    fn remember_schemas(&self, tools: &[Value]) {
+        let Ok(mut schemas) = self.schemas.lock() else {
+            return;
+        };
+        for tool in tools {
+            if let (Some(name), Some(schema)) = (
+                tool.get("name").and_then(Value::as_str),
+                tool.get("inputSchema"),
+            ) {
+                schemas.insert(name.to_string(), schema.clone());
+            }
+        }
+    }
+
+    /// Verdict for a message too large to be read in full
+    pub fn oversized(&self, direction: Direction, len: usize) -> Verdict {
+        let violation = Violation::oversized(len, self.policy().config.max_message_bytes);
+        self.report(direction, &violation);
+        self.blocked(direction, &violation);
+        // Forwarding half a message would only corrupt the stream
+        Verdict::Block { reply: None }
+    }
+
+    fn decide(
+        &self,
+        policy: &Policy,
+        direction: Direction,
+        raw: &str,
+        violations: &[Violation],
+    ) -> Verdict {
+        for violation in violations {
+            self.report(direction, violation);
+        }
+        let worst = violations.iter().map(|v| v.severity).max();
+        let blocked = matches!(
+            (policy.config.block_on, worst),
+            (Some(threshold), Some(worst)) if worst >= threshold
+        );
+        if !blocked {
+            return Verdict::Forward;
+        }
+        self.blocked(direction, &violations[0]);
+        let error = jsonrpc::message_id(raw).map(|id| error_reply(id, &violations[0]));
+        match error {
+            Some(error) if jsonrpc::is_response(raw) => Verdict::Replace(error),
+            reply => Verdict::Block { reply },
+        }
+    }
+
+    fn report(&self, direction: Direction, violation: &Violation) {
+        warn!(
+            rule = %violation.rule,
+            severity = ?violation.severity,
+            "Proxy finding ({}): {}",
+            direction,
+            violation.message
+        );
+        self.events.emit(ProxyEvent::Finding {
+            direction,
+            violation: violation.clone(),
+        });
+    }
+
+    fn blocked(&self, direction: Direction, violation: &Violation) {
+        self.events.emit(ProxyEvent::Blocked {
+            direction,
+            violation: violation.clone(),
+        });
+    }
+}
+
+/// The `tools/call` requests in a message (or batch): (request, tool name,
+/// params)
+fn tool_calls(message: &Value) -> Vec<(&Value, &str, &Value)> {
+    let messages = match message {
+        Value::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
+    messages
+        .into_iter()
+        .filter(|m| m.get("method").and_then(Value::as_str) == Some("tools/call"))
+        .filter_map(|m| {
+            let params = m.get("params")?;
+            Some((m, params.get("name")?.as_str()?, params))
+        })
+        .collect()
+}
+
+fn messages_mut(message: &mut Value) -> Vec<&mut Value> {
+    match message {
+        Value::Array(items) => items.iter_mut().collect(),
+        single => vec![single],
+    }
+}
+
+/// Content checks on a structurally valid message (or batch)
+fn inspect_content(direction: Direction, message: &Value) -> Vec<Violation> {
+    let messages = match message {
+        Value::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
+
+    let mut violations = Vec::new();
+    for message in messages {
+        if direction != Direction::ServerToClient {
+            continue;
+        }
+        let tools = message.pointer("/result/tools").and_then(Value::as_array);
+        for tool in tools.into_iter().flatten() {
+            let text = |key: &str| tool.get(key).and_then(Value::as_str).unwrap_or_default();
+            let name = text("name");
+            let surface = format!("{}\n{}", name, text("description"));
+            let findings = hidden_unicode::detect(&surface, "tools/list").unwrap_or_default();
+            violations.extend(findings.iter().map(|vuln| {
+                finding_violation("hidden_unicode", &format!("tool '{}'", name), vuln)
+            }));
+        }
+        if message.get("method").and_then(Value::as_str) == Some(sampling::METHOD) {
+            violations.extend(sampling::inspect(
+                message.get("params").unwrap_or(&Value::Null),
+            ));
+        }
+        if let Some(result) = message.get("result") {
+            let findings = prompts_resources::detect_live(result);
+            violations.extend(findings.iter().map(|vuln| {
+                let subject = vuln.location.as_ref().map_or("", |l| l.file.as_str());
+                finding_violation("prompts_resources", subject, vuln)
+            }));
+        }
+    }
+    violations
+}
+
+/// A detector finding as a proxy violation
+fn finding_violation(detector: &str, subject: &str, vuln: &Vulnerability) -> Violation {
+    Violation::new(
+        format!(
+            "{}/{}",
+            detector,
+            vuln.rule_id.as_deref().unwrap_or_default()
+        ),
+        vuln.severity,
+        format!("{} in {}: {}", vuln.title, subject, vuln.description),
+    )
+}
+
+/// JSON-RPC error answering a blocked request
+fn error_reply(id: Value, violation: &Violation) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": INVALID_REQUEST,
+            "message": format!("Blocked by MCP Sentinel: {}", violation.message),
+            "data": { "rule": &violation.rule },
+        },
+    })
+    .to_string()
+}
+
+/// JSON-RPC error answering a call the operator did not approve
+pub fn denied_reply(request: &ApprovalRequest) -> Option<String> {
+    let violation = Violation::new(
+        "approval/denied",
+        Severity::High,
+        format!("call to '{}' was not approved", request.tool),
+    );
+    Some(error_reply(request.id.clone()?, &violation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_only_without_threshold() {
+        let interceptor = Interceptor::default();
+        let raw = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":[]}"#;
+        assert_eq!(
+            interceptor.inspect(Direction::ClientToServer, raw),
+            Verdict::Forward
+        );
+    }
+
+    #[test]
+    fn test_block_replies_to_requests() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            block_on: Some(Severity::Medium),
+            ..ProxyConfig::default()
+        });
+        let raw = r#"{"jsonrpc":"2.0","id":9,"method":"tools/call","params":[]}"#;
+        let Verdict::Block { reply: Some(reply) } =
+            interceptor.inspect(Direction::ClientToServer, raw)
+        else {
+            panic!("expected a blocked request with a reply");
+        };
+        let reply: Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(reply["id"], 9);
+        assert_eq!(reply["error"]["code"], INVALID_REQUEST);
+
+        assert_eq!(
+            interceptor.inspect(Direction::ServerToClient, "garbage"),
+            Verdict::Block { reply: None }
+        );
+        assert_eq!(
+            interceptor.inspect(
+                Direction::ServerToClient,
+                r#"{"jsonrpc":"2.0","id":9,"result":{}}"#
+            ),
+            Verdict::Forward
+        );
+    }
+
+    #[test]
+    fn test_hold_calls_needing_approval() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            guardrails: Guardrails::parse("require_approval: ['delete_*']").unwrap(),
+            ..ProxyConfig::default()
+        });
+        let call = |tool: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "tools/call",
+                "params": { "name": tool, "arguments": { "path": "/tmp/x" } },
+            })
+            .to_string()
+        };
+        assert_eq!(
+            interceptor.inspect(Direction::ClientToServer, &call("read_file")),
+            Verdict::Forward
+        );
+        let Verdict::Hold(request) =
+            interceptor.inspect(Direction::ClientToServer, &call("delete_file"))
+        else {
+            panic!("expected the call to be held");
+        };
+        assert_eq!(request.arguments["path"], "/tmp/x");
+        let reply: Value = serde_json::from_str(&denied_reply(&request).unwrap()).unwrap();
+        assert_eq!(reply["id"], 4);
+        assert_eq!(reply["error"]["data"]["rule"], "approval/denied");
+    }
+
+    #[test]
+    fn test_denied_tools() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            guardrails: Guardrails::parse("deny_tools: [delete_file]").unwrap(),
+            ..ProxyConfig::default()
+        });
+        let list = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "tools": [{ "name": "read_file" }, { "name": "delete_file" }] },
+        });
+        let Verdict::Replace(filtered) =
+            interceptor.inspect(Direction::ServerToClient, &list.to_string())
+        else {
+            panic!("expected the tool list to be filtered");
+        };
+        let filtered: Value = serde_json::from_str(&filtered).unwrap();
+        assert_eq!(
+            filtered["result"]["tools"],
+            json!([{ "name": "read_file" }])
+        );
+
+        let call =
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"delete_file"}}"#;
+        let Verdict::Block { reply: Some(reply) } =
+            interceptor.inspect(Direction::ClientToServer, call)
+        else {
+            panic!("expected the call to be blocked");
+        };
+        let reply: Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(reply["error"]["data"]["rule"], "guardrails/tool-denied");
+    }
+
+    #[test]
+    fn test_malformed_messages_are_not_waved_through() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            guardrails: Guardrails::parse("deny_tools: [delete_file]").unwrap(),
+            ..ProxyConfig::default()
+        });
+        // The wrong version is only a Medium violation, below any threshold
+        let call =
+            r#"{"jsonrpc":"1.0","id":2,"method":"tools/call","params":{"name":"delete_file"}}"#;
+        let Verdict::Block { reply: Some(reply) } =
+            interceptor.inspect(Direction::ClientToServer, call)
+        else {
+            panic!("expected the call to be blocked");
+        };
+        let reply: Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(reply["error"]["data"]["rule"], "guardrails/tool-denied");
+
+        for direction in [Direction::ClientToServer, Direction::ServerToClient] {
+            assert_eq!(
+                interceptor.inspect(direction, r#"{"jsonrpc":"2.0","id":3,"#),
+                Verdict::Block { reply: None }
+            );
+        }
+    }
+
+    #[test]
+    fn test_reload_applies_to_clones() {
+        let interceptor = Interceptor::default();
+        let session = interceptor.clone();
+        let call =
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"delete_file"}}"#;
+        assert_eq!(
+            session.inspect(Direction::ClientToServer, call),
+            Verdict::Forward
+        );
+
+        interceptor.reload(Guardrails::parse("deny_tools: [delete_file]").unwrap());
+        assert!(matches!(
+            session.inspect(Direction::ClientToServer, call),
+            Verdict::Block { reply: Some(_) }
+        ));
+        assert_eq!(
+            session.config().max_message_bytes,
+            jsonrpc::DEFAULT_MAX_MESSAGE_BYTES
+        );
+    }
+
+    #[test]
+    fn test_argument_enforcement() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            guardrails: Guardrails::parse("arguments: { workspace: [/work] }").unwrap(),
+            ..ProxyConfig::default()
+        });
+        let list = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "tools": [{
+                "name": "read_file",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": { "path": { "type": "string" } },
+                    "required": ["path"],
+                },
+            }] },
+        });
+        interceptor.inspect(Direction::ServerToClient, &list.to_string());
+
+        let call = |arguments: Value| {
+            let call = json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": { "name": "read_file", "arguments": arguments },
+            });
+            interceptor.inspect(Direction::ClientToServer, &call.to_string())
+        };
+        assert_eq!(call(json!({ "path": "notes.md" })), Verdict::Forward);
+        assert!(matches!(
+            call(json!({ "file": "notes.md" })),
+            Verdict::Block { reply: Some(_) }
+        ));
+        assert!(matches!(
+            call(json!({ "path": "/home/dev/.ssh/id_rsa" })),
+            Verdict::Block { reply: Some(_) }
+        ));
+    }
+
+    #[test]
+    fn test_hidden_unicode_in_tool_list() {
+        let interceptor = Interceptor::new(ProxyConfig {
+            block_on: Some(Severity::High),
+            ..ProxyConfig::default()
+        });
+        let tools = |description: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "tools": [{ "name": "add", "description": description }] },
+            })
+            .to_string()
+        };
+        assert_eq!(
+            interceptor.inspect(Direction::ServerToClient, &tools("Adds numbers")),
+            Verdict::Forward
+        );
+        assert!(matches!(
+            interceptor.inspect(
+                Direction::ServerToClient,
+                &tools("Adds numbers\u{200B}\u{202E}")
+            ),
+            Verdict::Replace(_)
+        ));
+
+        let violations = inspect_content(
+            Direction::ServerToClient,
+            &serde_json::from_str(&tools("Adds\u{200D} numbers")).unwrap(),
+        );
+        assert_eq!(
+            violations[0].rule,
+            "hidden_unicode/invisible-unicode-characters"
+        );
+    }
+}
@@ -0,0 +1,662 @@
+//! Several servers behind one proxy
+//!
+//! With `--config`, the proxy starts every server of an MCP configuration
+//! file (`mcpServers`) and presents them to the client as a single server:
+//! - tools and prompts are namespaced as `<server>__<name>`; calls go to
+//!   their server under the original name
+//! - resources keep their URIs and are routed to the server that listed
+//!   them
+//! - `initialize` and the list methods go to every server and the answers
+//!   are merged; list pagination is not forwarded, so each server's first
+//!   page is what the client sees
+//! - requests a server sends the client (sampling, roots) get IDs assigned
+//!   by the proxy, so two servers cannot answer each other's
+//!
+//! Each server has its own [`Interceptor`], built from its section of the
+//! guardrails, so per-server policies apply exactly as they would with one
+//! proxy per server. The interceptor sees the server's own tool names.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::BufReader;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use super::events::{CallOutcome, ProxyEvent};
+use super::stdio::{self, Frame};
+use super::{denied_reply, Direction, Interceptor, Verdict};
+use crate::models::mcp_protocol::ServerConfig;
+use crate::utils::process;
+
+/// Between the server name and a tool or prompt name
+pub const SEPARATOR: &str = "__";
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Methods sent to every server, with the answers merged
+const FAN_OUT: &[&str] = &[
+    "initialize",
+    "tools/list",
+    "prompts/list",
+    "resources/list",
+    "resources/templates/list",
+    "logging/setLevel",
+];
+
+/// One proxied server
+#[derive(Debug, Clone)]
+pub struct Backend {
+    pub name: String,
+    pub command: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub interceptor: Interceptor,
+}
+
+impl Backend {
+    pub fn new(name: &str, server: &ServerConfig, interceptor: Interceptor) -> Self {
+        let mut command = vec![server.command.clone()];
+        command.extend(server.args.iter().flatten().cloned());
+        Self {
+            name: name.to_string(),
+            command,
+            env: server.env.clone().unwrap_or_default(),
+            interceptor,
+        }
+    }
+}
+
+/// The servers of an MCP configuration file that are started with a
+/// command, by name
+pub fn servers(path: &Path) -> Result<BTreeMap<String, ServerConfig>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read MCP configuration '{}'", path.display()))?;
+    let config: Value = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid MCP configuration '{}'", path.display()))?;
+    let entries = config
+        .get("mcpServers")
+        .and_then(Value::as_object)
+        .with_context(|| format!("No mcpServers in '{}'", path.display()))?;
+
+    let mut servers = BTreeMap::new();
+    for (name, entry) in entries {
+        if name.contains(SEPARATOR) {
+            anyhow::bail!(
+                "MCP server name '{}' contains '{}', which separates server and tool names",
+                name,
+                SEPARATOR
+            );
+        }
+        match serde_json::from_value::<ServerConfig>(entry.clone()) {
+            Ok(server) => {
+                servers.insert(name.clone(), server);
+            }
+            Err(_) => warn!(
+                "Skipping MCP server '{}': only servers started with a command can be proxied",
+                name
+            ),
+        }
+    }
+    if servers.is_empty() {
+        anyhow::bail!("No MCP servers to proxy in '{}'", path.display());
+    }
+    Ok(servers)
+}
+
+/// Where a routed message goes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    Client(String),
+    /// To the server at this index, through its interceptor
+    Server(usize, String),
+}
+
+/// A request sent to every server, waiting for their answers
+#[derive(Debug)]
+struct FanOut {
+    method: String,
+    id: Value,
+    /// By server; `Null` for servers that are gone
+    answers: Vec<Option<Value>>,
+}
+
+/// Routing state of one client session
+#[derive(Debug, Default)]
+pub struct Router {
+    names: Vec<String>,
+    alive: Vec<bool>,
+    /// Requests sent to every server, by client request ID
+    fan_outs: HashMap<String, FanOut>,
+    /// Requests from servers, by the ID the client sees: (server, their ID)
+    server_requests: HashMap<String, (usize, Value)>,
+    next_id: u64,
+    /// Which server listed each resource URI
+    resources: HashMap<String, usize>,
+}
+
+impl Router {
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            alive: vec![true; names.len()],
+            names,
+            ..Self::default()
+        }
+    }
+
+    /// Route a message from the client
+    pub fn from_client(&mut self, raw: &str) -> Vec<Route> {
+        let Ok(message @ Value::Object(_)) = serde_json::from_str::<Value>(raw) else {
+            let reply = error(
+                Value::Null,
+                INVALID_REQUEST,
+                "Expected a single JSON-RPC message",
+            );
+            return vec![Route::Client(reply)];
+        };
+        let id = message.get("id").cloned();
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        match (id, method) {
+            (Some(id), None) => self.client_response(id, message),
+            (Some(id), Some(method)) => self.client_request(id, &method, message),
+            // Notifications concern every server
+            (None, Some(_)) => self
+                .live()
+                .map(|server| Route::Server(server, raw.to_string()))
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Route a message from the server at `server` that passed its
+    /// interceptor
+    pub fn from_server(&mut self, server: usize, raw: &str) -> Vec<Route> {
+        let Ok(mut message) = serde_json::from_str::<Value>(raw) else {
+            return Vec::new();
+        };
+        let id = message.get("id").cloned();
+        let is_request = message.get("method").is_some();
+        match id {
+            Some(id) if !is_request => {
+                let key = id.to_string();
+                match self.fan_outs.get_mut(&key) {
+                    Some(fan_out) => {
+                        fan_out.answers[server].get_or_insert(message);
+                        self.complete(&key).into_iter().collect()
+                    }
+                    None => vec![Route::Client(raw.to_string())],
+                }
+            }
+            Some(id) => {
+                self.next_id += 1;
+                let proxy_id = json!(format!("{}-{}", self.names[server], self.next_id));
+                self.server_requests
+                    .insert(proxy_id.to_string(), (server, id));
+                message["id"] = proxy_id;
+                vec![Route::Client(message.to_string())]
+            }
+            None => vec![Route::Client(raw.to_string())],
+        }
+    }
+
+    /// The server at `server` is gone; requests waiting on it complete
+    /// without it
+    pub fn disconnect(&mut self, server: usize) -> Vec<Route> {
+        self.alive[server] = false;
+        let keys: Vec<String> = self.fan_outs.keys().cloned().collect();
+        let mut routes = Vec::new();
+        for key in keys {
+            if let Some(fan_out) = self.fan_outs.get_mut(&key) {
+                fan_out.answers[server].get_or_insert(Value::Null);
+            }
+            routes.extend(self.complete(&key));
+        }
+        routes
+    }
+
+    fn live(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.names.len()).filter(|&server| self.alive[server])
+    }
+
+    fn client_request(&mut self, id: Value, method: &str, mut message: Value) -> Vec<Route> {
+        if method == "ping" {
+            return vec![Route::Client(result(id, json!({})))];
+        }
+        if FAN_OUT.contains(&method) {
+            let answers: Vec<Option<Value>> = self
+                .alive
+                .iter()
+                .map(|alive| (!alive).then_some(Value::Null))
+                .collect();
+            if answers.iter().all(Option::is_some) {
+                let reply = error(id, INVALID_REQUEST, "No MCP server is running");
+                return vec![Route::Client(reply)];
+            }
+            let raw = message.to_string();
+            self.fan_outs.insert(
+                id.to_string(),
+                FanOut {
+                    method: method.to_string(),
+                    id,
+                    answers,
+                },
+            );
+            return self
+                .live()
+                .map(|server| Route::Server(server, raw.clone()))
+                .collect();
+        }
+
+        let target = match method {
+            "tools/call" | "prompts/get" => {
+                let name = message
+                    .pointer("/params/name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let target = name.split_once(SEPARATOR).and_then(|(server, original)| {
+                    let server = self.live().find(|&i| self.names[i] == server)?;
+                    Some((server, original.to_string()))
+                });
+                target.map(|(server, original)| {
+                    message["params"]["name"] = json!(original);
+                    server
+                })
+            }
+            "resources/read" | "resources/subscribe" | "resources/unsubscribe" => message
+                .pointer("/params/uri")
+                .and_then(Value::as_str)
+                .and_then(|uri| self.resources.get(uri).copied())
+                .filter(|&server| self.alive[server]),
+            _ => {
+                let reply = error(
+                    id,
+                    METHOD_NOT_FOUND,
+                    format!("Method '{}' is not supported with multiple servers", method),
+                );
+                return vec![Route::Client(reply)];
+            }
+        };
+        match target {
+            Some(server) => vec![Route::Server(server, message.to_string())],
+            None => {
+                let subject = message.get("params").cloned().unwrap_or(Value::Null);
+                let reply = error(
+                    id,
+                    INVALID_PARAMS,
+                    format!("No running server provides {} {}", method, subject),
+                );
+                vec![Route::Client(reply)]
+            }
+        }
+    }
+
+    fn client_response(&mut self, id: Value, mut message: Value) -> Vec<Route> {
+        match self.server_requests.remove(&id.to_string()) {
+            Some((server, original)) => {
+                message["id"] = original;
+                vec![Route::Server(server, message.to_string())]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// The merged answer of a request all servers have answered
+    fn complete(&mut self, key: &str) -> Option<Route> {
+        if !self.fan_outs.get(key)?.answers.iter().all(Option::is_some) {
+            return None;
+        }
+        let fan_out = self.fan_outs.remove(key)?;
+        let mut results = Vec::new();
+        for (server, answer) in fan_out.answers.into_iter().enumerate() {
+            let answer = answer.unwrap_or(Value::Null);
+            match answer.get("result") {
+                Some(result) => results.push((server, result.clone())),
+                None if answer.is_null() => {}
+                None => {
+                    let error = answer.get("error").unwrap_or(&Value::Null);
+                    warn!(
+                        "MCP server '{}' failed {}: {}",
+                        self.names[server], fan_out.method, error
+                    );
+                }
+            }
+        }
+
+        let merged = match fan_out.method.as_str() {
+            "initialize" if results.is_empty() => {
+                let reply = error(fan_out.id, INVALID_REQUEST, "No MCP server initialized");
+                return Some(Route::Client(reply));
+            }
+            "initialize" => {
+                let mut capabilities = Map::new();
+                for (_, result) in &results {
+                    if let Some(Value::Object(theirs)) = result.get("capabilities") {
+                        for (key, value) in theirs {
+                            capabilities.entry(key).or_insert_with(|| value.clone());
+                        }
+                    }
+                }
+                json!({
+                    "protocolVersion": results[0].1.get("protocolVersion"),
+                    "capabilities": capabilities,
+                    "serverInfo": {
+                        "name": "mcp-sentinel",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                })
+            }
+            "tools/list" => json!({ "tools": self.namespaced(&results, "tools") }),
+            "prompts/list" => json!({ "prompts": self.namespaced(&results, "prompts") }),
+            "resources/list" => {
+                let mut resources = Vec::new();
+                for (server, result) in &results {
+                    for resource in listed(result, "resources") {
+                        if let Some(uri) = resource.get("uri").and_then(Value::as_str) {
+                            self.resources.insert(uri.to_string(), *server);
+                        }
+                        resources.push(resource.clone());
+                    }
+                }
+                json!({ "resources": resources })
+            }
+            "resources/templates/list" => {
+                let templates: Vec<&Value> = results
+                    .iter()
+                    .flat_map(|(_, result)| listed(result, "resourceTemplates"))
+                    .collect();
+                json!({ "resourceTemplates": templates })
+            }
+            _ => json!({}),
+        };
+        Some(Route::Client(result(fan_out.id, merged)))
+    }
+
+    /// The entries of a list result with names prefixed by their server
+    fn namespaced(&self, results: &[(usize, Value)], key: &str) -> Vec<Value> {
+        let mut entries = Vec::new();
+        for (server, result) in results {
+            for entry in listed(result, key) {
+                let mut entry = entry.clone();
+                if let Some(name) = entry.get("name").and_then(Value::as_str) {
+                    entry["name"] = json!(format!("{}{}{}", self.names[*server], SEPARATOR, name));
+                }
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+}
+
+fn listed<'a>(result: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    result
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn result(id: Value, result: Value) -> String {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string()
+}
+
+fn error(id: Value, code: i64, message: impl Into<String>) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+    .to_string()
+}
+
+/// What the operator decided about a held call
+enum Decision {
+    Approved(usize, String),
+    Denied(usize, String),
+}
+
+/// Start every backend and proxy the client on stdio to all of them until
+/// the client closes
+pub async fn run(backends: Vec<Backend>, max_bytes: usize) -> Result<()> {
+    let names: Vec<String> = backends.iter().map(|b| b.name.clone()).collect();
+    info!("Proxying MCP servers: {}", names.join(", "));
+
+    let (frames, mut from_servers) = mpsc::channel::<(usize, Option<Frame>)>(64);
+    let mut to_servers = Vec::new();
+    // Killed when dropped, at the end of the session
+    let mut children = Vec::new();
+    for (server, backend) in backends.iter().enumerate() {
+        let (program, args) = backend
+            .command
+            .split_first()
+            .with_context(|| format!("No command for MCP server '{}'", backend.name))?;
+        let mut child = Command::new(process::resolve(program))
+            .args(args)
+            .envs(&backend.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start MCP server '{}'", backend.name))?;
+        let server_in = child.stdin.take().context("Server stdin unavailable")?;
+        let server_out = child.stdout.take().context("Server stdout unavailable")?;
+
+        let (sender, queue) = mpsc::channel::<String>(64);
+        tokio::spawn(stdio::drain(queue, server_in));
+        to_servers.push(sender);
+        let frames = frames.clone();
+        tokio::spawn(async move {
+            let mut reader = BufReader::new(server_out);
+            while let Ok(Some(frame)) = stdio::read_frame(&mut reader, max_bytes).await {
+                if frames.send((server, Some(frame))).await.is_err() {
+                    return;
+                }
+            }
+            let _ = frames.send((server, None)).await;
+        });
+        children.push(child);
+    }
+    drop(frames);
+
+    let (client_frames, mut from_client) = mpsc::channel::<Frame>(64);
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(tokio::io::stdin());
+        while let Ok(Some(frame)) = stdio::read_frame(&mut reader, max_bytes).await {
+            if client_frames.send(frame).await.is_err() {
+                return;
+            }
+        }
+    });
+    let (to_client, client_queue) = mpsc::channel::<String>(64);
+    let client_writer = tokio::spawn(stdio::drain(client_queue, tokio::io::stdout()));
+    let (decide, mut decisions) = mpsc::channel::<Decision>(16);
+
+    let mut router = Router::new(names);
+    loop {
+        let routes = tokio::select! {
+            frame = from_client.recv() => match frame {
+                None => break,
+                Some(Frame::Message(line)) if line.trim().is_empty() => continue,
+                Some(Frame::Message(line)) => router.from_client(&line),
+                Some(Frame::Oversized(len)) => {
+                    warn!("Dropped a {} byte message from the client: over the size limit", len);
+                    continue;
+                }
+            },
+            Some((server, frame)) = from_servers.recv() => {
+                let Some(frame) = frame else {
+                    warn!("MCP server '{}' exited", backends[server].name);
+                    let routes = router.disconnect(server);
+                    deliver(routes, &mut router, &backends, &to_servers, &to_client, &decide).await;
+                    continue;
+                };
+                let interceptor = &backends[server].interceptor;
+                match stdio::verdict(interceptor, Direction::ServerToClient, &frame) {
+                    (Verdict::Forward, Some(line)) => router.from_server(server, line),
+                    (Verdict::Replace(message), _) => router.from_server(server, &message),
+                    (Verdict::Block { reply: Some(reply) }, _) => {
+                        let _ = to_servers[server].send(reply).await;
+                        continue;
+                    }
+                    _ => continue,
+                }
+            },
+            Some(decision) = decisions.recv() => match decision {
+                Decision::Approved(server, message) => {
+                    let _ = to_servers[server].send(message).await;
+                    continue;
+                }
+                Decision::Denied(server, reply) => router.from_server(server, &reply),
+            },
+        };
+        deliver(
+            routes,
+            &mut router,
+            &backends,
+            &to_servers,
+            &to_client,
+            &decide,
+        )
+        .await;
+    }
+    drop(to_client);
+    let _ = client_writer.await;
+    drop(children);
+    Ok(())
+}
+
+/// Send routed messages on; messages to a server pass its interceptor first
+async fn deliver(
+    routes: Vec<Route>,
+    router: &mut Router,
+    backends: &[Backend],
+    to_servers: &[mpsc::Sender<String>],
+    to_client: &mpsc::Sender<String>,
+    decide: &mpsc::Sender<Decision>,
+) {
+    let mut queue = VecDeque::from(routes);
+    while let Some(route) = queue.pop_front() {
+        let (server, message) = match route {
+            Route::Client(message) => {
+                let _ = to_client.send(message).await;
+                continue;
+            }
+            Route::Server(server, message) => (server, message),
+        };
+        let interceptor = &backends[server].interceptor;
+        match interceptor.inspect(Direction::ClientToServer, &message) {
+            Verdict::Forward => {
+                let _ = to_servers[server].send(message).await;
+            }
+            Verdict::Replace(replacement) => {
+                let _ = to_servers[server].send(replacement).await;
+            }
+            // The proxy answers for the server
+            Verdict::Block { reply: Some(reply) } => {
+                queue.extend(router.from_server(server, &reply));
+            }
+            Verdict::Block { reply: None } => {}
+            Verdict::Hold(request) => {
+                let (approver, decide) = (interceptor.approver(), decide.clone());
+                let events = interceptor.events().clone();
+                tokio::spawn(async move {
+                    let approved = approver.decide(&request).await;
+                    events.emit(ProxyEvent::ToolCall {
+                        tool: request.tool.clone(),
+                        outcome: match approved {
+                            true => CallOutcome::Approved,
+                            false => CallOutcome::Denied,
+                        },
+                    });
+                    let decision = if approved {
+                        Decision::Approved(server, message)
+                    } else {
+                        match denied_reply(&request) {
+                            Some(reply) => Decision::Denied(server, reply),
+                            None => return,
+                        }
+                    };
+                    let _ = decide.send(decision).await;
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(routes: Vec<Route>) -> Value {
+        match routes.as_slice() {
+            [Route::Client(message)] => serde_json::from_str(message).unwrap(),
+            other => panic!("expected one reply to the client, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_namespaced_tools() {
+        let mut router = Router::new(vec!["github".to_string(), "files".to_string()]);
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+        assert_eq!(router.from_client(list).len(), 2);
+
+        let tools = |names: &[&str]| {
+            let tools: Vec<Value> = names.iter().map(|n| json!({ "name": n })).collect();
+            json!({ "jsonrpc": "2.0", "id": 1, "result": { "tools": tools } }).to_string()
+        };
+        assert!(router.from_server(1, &tools(&["read_file"])).is_empty());
+        let merged = reply(router.from_server(0, &tools(&["create_issue"])));
+        assert_eq!(merged["id"], 1);
+        assert_eq!(
+            merged["result"]["tools"],
+            json!([{ "name": "github__create_issue" }, { "name": "files__read_file" }])
+        );
+
+        let call = r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"files__read_file"}}"#;
+        let routes = router.from_client(call);
+        let [Route::Server(1, routed)] = <[Route; 1]>::try_from(routes).unwrap() else {
+            panic!("tools/call was not routed to 'files'");
+        };
+        assert!(routed.contains(r#""name":"read_file""#));
+        let unknown =
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"jira__x"}}"#;
+        assert_eq!(
+            reply(router.from_client(unknown))["error"]["code"],
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_server_request_ids() {
+        let mut router = Router::new(vec!["a".to_string(), "b".to_string()]);
+        let sampling = r#"{"jsonrpc":"2.0","id":0,"method":"sampling/createMessage"}"#;
+        let first = reply(router.from_server(0, sampling));
+        let second = reply(router.from_server(1, sampling));
+        assert_ne!(first["id"], second["id"]);
+
+        let answer = json!({ "jsonrpc": "2.0", "id": second["id"], "result": {} });
+        assert_eq!(
+            router.from_client(&answer.to_string()),
+            vec![Route::Server(
+                1,
+                r#"{"id":0,"jsonrpc":"2.0","result":{}}"#.to_string()
+            )]
+        );
+
+        // A fan-out completes without a server that exits
+        let init = r#"{"jsonrpc":"2.0","id":5,"method":"initialize","params":{}}"#;
+        router.from_client(init);
+        let answer = r#"{"jsonrpc":"2.0","id":5,"result":{"protocolVersion":"2025-06-18","capabilities":{"tools":{}}}}"#;
+        assert!(router.from_server(0, answer).is_empty());
+        let merged = reply(router.disconnect(1));
+        assert_eq!(merged["result"]["serverInfo"]["name"], "mcp-sentinel");
+        assert_eq!(merged["result"]["capabilities"], json!({ "tools": {} }));
+    }
+}
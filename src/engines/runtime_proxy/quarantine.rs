@@ -0,0 +1,104 @@
+//! Quarantine mode
+//!
+//! With `--quarantine`, the proxy only launches servers whose command is on
+//! the signed allowlist that `scan --approve` maintains. Any other server
+//! is never started: every request from the client is answered with a
+//! "quarantined pending scan" error instead, so a newly added server stays
+//! unusable until someone scans it.
+
+use serde_json::json;
+use std::path::Path;
+
+use super::jsonrpc;
+use crate::storage::allowlist::{self, ServerAllowlist};
+
+/// JSON-RPC error code of a quarantined server (implementation-defined
+/// server error range)
+pub const QUARANTINED: i64 = -32001;
+
+/// Why `command` may not run, or `None` if the allowlist approves it
+///
+/// A missing, unsigned, or tampered allowlist approves nothing.
+pub fn check(command: &[String], allowlist_path: &Path, public_key: &Path) -> Option<String> {
+    let allowlist = allowlist::load_public_key(public_key)
+        .and_then(|key| ServerAllowlist::load_verified(allowlist_path, &key));
+    match allowlist {
+        Ok(allowlist) if allowlist.find(command).is_some() => None,
+        Ok(_) => Some(format!(
+            "'{}' is not on the server allowlist",
+            command.join(" ")
+        )),
+        Err(e) => Some(format!("{:#}", e)),
+    }
+}
+
+/// The error answering a request to a quarantined server; notifications
+/// and responses get none
+pub fn reply(raw: &str, reason: &str) -> Option<String> {
+    if jsonrpc::is_response(raw) {
+        return None;
+    }
+    let id = jsonrpc::message_id(raw)?;
+    Some(
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": QUARANTINED,
+                "message": format!(
+                    "MCP server quarantined pending scan: {}. Run `mcp-sentinel scan --approve` \
+                     on it to allow it.",
+                    reason
+                ),
+            },
+        })
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_quarantine() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (list, key_path) = (
+            temp_dir.path().join("allowlist.json"),
+            temp_dir.path().join("allowlist.key"),
+        );
+        let key = allowlist::load_or_create_signing_key(&key_path).unwrap();
+        let public = allowlist::public_key_path(&key_path);
+        let approved = vec!["node".to_string(), "server.js".to_string()];
+
+        assert!(check(&approved, &list, &public)
+            .unwrap()
+            .contains("Failed to read allowlist"));
+        let mut allowlist = ServerAllowlist::default();
+        allowlist.approve(&approved, ".");
+        allowlist.save_signed(&list, &key).unwrap();
+        assert_eq!(check(&approved, &list, &public), None);
+        assert!(check(
+            &["node".to_string(), "other.js".to_string()],
+            &list,
+            &public
+        )
+        .is_some());
+
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#;
+        let error: Value = serde_json::from_str(&reply(request, "not approved").unwrap()).unwrap();
+        assert_eq!(error["error"]["code"], QUARANTINED);
+        assert!(error["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("quarantined pending scan"));
+        assert_eq!(
+            reply(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                "x"
+            ),
+            None
+        );
+    }
+}
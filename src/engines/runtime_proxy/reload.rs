@@ -0,0 +1,48 @@
+//! Guardrails hot-reload
+//!
+//! A running proxy watches its guardrails file and applies every change to
+//! the sessions it carries, so rules can be tightened during an incident
+//! without restarting each agent's MCP connection. The file is checked every
+//! few seconds; a file that no longer parses is reported and the policy in
+//! force stays. A file that does not exist yet is picked up once created.
+//!
+//! The rule bundle is not watched: its rules only run in `scan`. The proxy
+//! inspects traffic with built-in detectors (secrets, PII, hidden Unicode,
+//! prompt and resource injection), so `rules update` never needs a restart.
+
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::guardrails::Guardrails;
+
+/// How often the guardrails file is checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Call `apply` with the guardrails at `path` whenever the file changes and
+/// still parses. Runs until the task is dropped.
+pub async fn watch<F>(path: PathBuf, apply: F)
+where
+    F: Fn(Guardrails),
+{
+    let mut seen = std::fs::read(&path).ok();
+    let mut ticks = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        ticks.tick().await;
+        // Deleted or mid-write: wait for the next version
+        let Ok(content) = std::fs::read(&path) else {
+            continue;
+        };
+        if seen.as_ref() == Some(&content) {
+            continue;
+        }
+        seen = Some(content);
+        match Guardrails::load(&path) {
+            Ok(guardrails) => {
+                info!("Reloaded guardrails from {}", path.display());
+                apply(guardrails);
+            }
+            Err(e) => warn!("Keeping the current guardrails: {:#}", e),
+        }
+    }
+}
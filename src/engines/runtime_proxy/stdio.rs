@@ -0,0 +1,257 @@
+//! stdio transport
+//!
+//! The proxy launches the MCP server as a child process and stands in for
+//! it on its own stdin/stdout, so a client config only has to prefix the
+//! server command with `mcp-sentinel proxy --`. Messages are newline
+//! delimited, as the MCP stdio transport specifies; the server's stderr is
+//! passed through untouched.
+//!
+//! Lines are read with a hard size cap, so an oversized message is skipped
+//! without ever being buffered in full.
+
+use anyhow::{Context, Result};
+use std::process::Stdio;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::events::{CallOutcome, ProxyEvent};
+use super::{denied_reply, quarantine, Direction, Interceptor, Verdict};
+use crate::utils::process;
+
+/// One newline-delimited frame
+#[derive(Debug, PartialEq)]
+pub enum Frame {
+    Message(String),
+    /// A line longer than the limit; its content was discarded
+    Oversized(usize),
+}
+
+/// Read the next frame, or `None` at end of stream
+pub async fn read_frame<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    max_bytes: usize,
+) -> Result<Option<Frame>> {
+    let mut line = Vec::new();
+    let mut len = 0;
+    loop {
+        let buf = reader.fill_buf().await?;
+        if buf.is_empty() {
+            if len == 0 {
+                return Ok(None);
+            }
+            break;
+        }
+        let (chunk, done) = match buf.iter().position(|&b| b == b'\n') {
+            Some(i) => (&buf[..i], Some(i + 1)),
+            None => (buf, None),
+        };
+        len += chunk.len();
+        if len <= max_bytes {
+            line.extend_from_slice(chunk);
+        }
+        let consumed = done.unwrap_or(buf.len());
+        reader.consume(consumed);
+        if done.is_some() {
+            break;
+        }
+    }
+
+    if len > max_bytes {
+        return Ok(Some(Frame::Oversized(len)));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(Some(Frame::Message(
+        String::from_utf8_lossy(&line).into_owned(),
+    )))
+}
+
+/// Run `command` as an MCP server behind the proxy until either side closes
+pub async fn run(command: &[String], interceptor: Interceptor) -> Result<()> {
+    let (program, args) = command
+        .split_first()
+        .context("No MCP server command given")?;
+    info!("Proxying stdio MCP server: {}", command.join(" "));
+
+    let mut child = Command::new(process::resolve(program))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start MCP server '{}'", program))?;
+    let server_in = child.stdin.take().context("Server stdin unavailable")?;
+    let server_out = child.stdout.take().context("Server stdout unavailable")?;
+
+    // Either side may be answered by the proxy instead of its peer (blocked
+    // requests), so each side's output goes through one queue and writer
+    let (to_client, client_queue) = mpsc::channel::<String>(64);
+    let (to_server, server_queue) = mpsc::channel::<String>(64);
+    let client_writer = tokio::spawn(drain(client_queue, tokio::io::stdout()));
+    let server_writer = tokio::spawn(drain(server_queue, server_in));
+
+    let upstream = pump(
+        BufReader::new(tokio::io::stdin()),
+        Direction::ClientToServer,
+        interceptor.clone(),
+        to_server.clone(),
+        to_client.clone(),
+    );
+    let downstream = pump(
+        BufReader::new(server_out),
+        Direction::ServerToClient,
+        interceptor,
+        to_client,
+        to_server,
+    );
+
+    tokio::select! {
+        result = upstream => result?,
+        result = downstream => result?,
+        status = child.wait() => debug!("MCP server exited: {}", status?),
+    }
+    // The pumps and their senders are gone; flush what is still queued. The
+    // server may already be gone, so its queue is best effort.
+    let _ = server_writer.await;
+    client_writer.await?
+}
+
+/// Stand in for a quarantined server without starting it: answer every
+/// request from the client with an error explaining why
+pub async fn refuse(reason: &str, max_bytes: usize) -> Result<()> {
+    warn!("MCP server quarantined pending scan: {}", reason);
+    let mut reader = BufReader::new(tokio::io::stdin());
+    let mut stdout = tokio::io::stdout();
+    while let Some(frame) = read_frame(&mut reader, max_bytes).await? {
+        let Frame::Message(line) = frame else {
+            continue;
+        };
+        if let Some(reply) = quarantine::reply(&line, reason) {
+            write_line(&mut stdout, &reply).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Read messages travelling in `direction` and route them by verdict:
+/// forwarded and replacement messages go on, replies go `back` to the sender
+pub(crate) async fn pump<R>(
+    mut reader: R,
+    direction: Direction,
+    interceptor: Interceptor,
+    forward: mpsc::Sender<String>,
+    back: mpsc::Sender<String>,
+) -> Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let max = interceptor.config().max_message_bytes;
+    while let Some(frame) = read_frame(&mut reader, max).await? {
+        let (target, message) = match verdict(&interceptor, direction, &frame) {
+            (Verdict::Forward, Some(line)) => (&forward, line.to_string()),
+            (Verdict::Replace(message), _) => (&forward, message),
+            (Verdict::Block { reply: Some(reply) }, _) => (&back, reply),
+            (Verdict::Hold(request), Some(line)) => {
+                // Other messages keep flowing while the operator decides
+                let (approver, line) = (interceptor.approver(), line.to_string());
+                let (forward, back) = (forward.clone(), back.clone());
+                let events = interceptor.events().clone();
+                tokio::spawn(async move {
+                    let approved = approver.decide(&request).await;
+                    events.emit(ProxyEvent::ToolCall {
+                        tool: request.tool.clone(),
+                        outcome: match approved {
+                            true => CallOutcome::Approved,
+                            false => CallOutcome::Denied,
+                        },
+                    });
+                    if approved {
+                        let _ = forward.send(line).await;
+                    } else if let Some(reply) = denied_reply(&request) {
+                        let _ = back.send(reply).await;
+                    }
+                });
+                continue;
+            }
+            _ => continue,
+        };
+        if target.send(message).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Route one message from a remote server by verdict: forwarded and
+/// replacement messages go to the client, replies back to the server
+pub(crate) async fn deliver(
+    interceptor: &Interceptor,
+    frame: &Frame,
+    to_client: &mpsc::Sender<String>,
+    to_server: &mpsc::Sender<String>,
+) {
+    let (target, message) = match verdict(interceptor, Direction::ServerToClient, frame) {
+        (Verdict::Forward, Some(line)) => (to_client, line.to_string()),
+        (Verdict::Replace(message), _) => (to_client, message),
+        (Verdict::Block { reply: Some(reply) }, _) => (to_server, reply),
+        _ => return,
+    };
+    let _ = target.send(message).await;
+}
+
+pub(crate) async fn drain<W: AsyncWrite + Unpin>(
+    mut queue: mpsc::Receiver<String>,
+    mut writer: W,
+) -> Result<()> {
+    while let Some(line) = queue.recv().await {
+        write_line(&mut writer, &line).await?;
+    }
+    Ok(())
+}
+
+pub(crate) fn verdict<'a>(
+    interceptor: &Interceptor,
+    direction: Direction,
+    frame: &'a Frame,
+) -> (Verdict, Option<&'a str>) {
+    match frame {
+        Frame::Message(line) if line.trim().is_empty() => (Verdict::Block { reply: None }, None),
+        Frame::Message(line) => (interceptor.inspect(direction, line), Some(line)),
+        Frame::Oversized(len) => (interceptor.oversized(direction, *len), None),
+    }
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, line: &str) -> Result<()> {
+    writer.write_all(line.as_bytes()).await?;
+    writer.write_all(b"\n").await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_frame_caps_line_length() {
+        let input = format!("{{\"a\":1}}\r\n{}\n{{\"b\":2}}", "x".repeat(100));
+        let mut reader = BufReader::with_capacity(8, input.as_bytes());
+
+        let mut frames = Vec::new();
+        while let Some(frame) = read_frame(&mut reader, 32).await.unwrap() {
+            frames.push(frame);
+        }
+        assert_eq!(
+            frames,
+            vec![
+                Frame::Message("{\"a\":1}".to_string()),
+                Frame::Oversized(100),
+                Frame::Message("{\"b\":2}".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Response-size and exfiltration-volume guards
+//!
+//! A compromised or prompt-injected session often shows up as volume: a
+//! tool that normally returns a few lines suddenly hands the model whole
+//! source trees or credential stores. Two limits catch that:
+//! - the size of a single response
+//! - the content returned over the session by tool results
+//!   (`content[].text`/`blob`) and resource reads (`contents[]`)
+//!
+//! Crossing a limit is reported; with `block` set, the offending response
+//! is replaced with an error instead of reaching the model.
+
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::jsonrpc::Violation;
+use crate::models::vulnerability::Severity;
+
+/// Volume limits of the guardrails
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct VolumeLimits {
+    /// Largest response passed to the client, in bytes
+    pub max_response_bytes: Option<usize>,
+    /// Most tool-result and resource content per session, in bytes
+    pub max_session_bytes: Option<usize>,
+    /// Replace responses over a limit with an error instead of only
+    /// reporting them
+    pub block: bool,
+}
+
+/// Content volume of one proxy session
+#[derive(Debug, Clone, Default)]
+pub struct VolumeMeter {
+    limits: VolumeLimits,
+    session_bytes: Arc<AtomicUsize>,
+}
+
+impl VolumeMeter {
+    pub fn new(limits: VolumeLimits) -> Self {
+        Self {
+            limits,
+            session_bytes: Arc::default(),
+        }
+    }
+
+    /// The same meter, with the total counted so far, under new limits
+    pub fn with_limits(&self, limits: VolumeLimits) -> Self {
+        Self {
+            limits,
+            session_bytes: Arc::clone(&self.session_bytes),
+        }
+    }
+
+    /// Count a server response of `len` bytes; returns the limit it breaks
+    /// and whether to block it
+    pub fn measure(&self, len: usize, message: &Value) -> Option<(Violation, bool)> {
+        if let Some(max) = self.limits.max_response_bytes.filter(|max| len > *max) {
+            let violation = Violation::new(
+                "guardrails/response-too-large",
+                Severity::High,
+                format!("Response of {} bytes exceeds the {} byte limit", len, max),
+            );
+            return Some((violation, self.limits.block));
+        }
+
+        let content = content_bytes(message);
+        if content == 0 {
+            return None;
+        }
+        let before = self.session_bytes.fetch_add(content, Ordering::Relaxed);
+        let max = self.limits.max_session_bytes?;
+        let total = before + content;
+        // Report the crossing once; keep blocking after it
+        let crossed = before <= max && total > max;
+        if total <= max || !(crossed || self.limits.block) {
+            return None;
+        }
+        let violation = Violation::new(
+            "guardrails/session-volume",
+            Severity::High,
+            format!(
+                "Session has returned {} bytes of tool and resource content, over the {} \
+                 byte limit",
+                total, max
+            ),
+        );
+        Some((violation, self.limits.block))
+    }
+}
+
+/// Bytes of tool-result and resource content in a response (or batch)
+pub fn content_bytes(message: &Value) -> usize {
+    let messages = match message {
+        Value::Array(items) => items.iter().collect(),
+        single => vec![single],
+    };
+    messages
+        .into_iter()
+        .filter_map(|m| m.get("result"))
+        .flat_map(|result| {
+            ["content", "contents"]
+                .into_iter()
+                .filter_map(|key| result.get(key).and_then(Value::as_array))
+                .flatten()
+        })
+        .map(|block| {
+            ["text", "blob"]
+                .iter()
+                .filter_map(|key| block.get(*key).and_then(Value::as_str))
+                .map(str::len)
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_session_volume() {
+        let meter = VolumeMeter::new(VolumeLimits {
+            max_response_bytes: Some(10_000),
+            max_session_bytes: Some(250),
+            block: false,
+        });
+        let result = |text: &str| {
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "content": [{ "type": "text", "text": text }] },
+            })
+        };
+        let chunk = "x".repeat(100);
+        assert!(meter.measure(150, &result(&chunk)).is_none());
+        assert!(meter.measure(150, &result(&chunk)).is_none());
+        let (violation, block) = meter.measure(150, &result(&chunk)).unwrap();
+        assert_eq!(violation.rule, "guardrails/session-volume");
+        assert!(!block);
+        // Reported once when only alerting
+        assert!(meter.measure(150, &result(&chunk)).is_none());
+
+        let (violation, _) = meter.measure(20_000, &result("")).unwrap();
+        assert_eq!(violation.rule, "guardrails/response-too-large");
+        assert_eq!(
+            content_bytes(
+                &json!({ "result": { "contents": [{ "uri": "file:///a", "blob": "AAAA" }] } })
+            ),
+            4
+        );
+    }
+}
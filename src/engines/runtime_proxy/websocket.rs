@@ -0,0 +1,126 @@
+//! WebSocket upstream
+//!
+//! For MCP servers with a WebSocket transport (`--upstream wss://...`). One
+//! connection carries JSON-RPC messages both ways, one per text frame. As
+//! with the [`http`](super::http) upstream, the client talks stdio to the
+//! proxy, every message in either direction goes through the same
+//! [`Interceptor`], and the same TLS and token settings apply: the CA
+//! bundle and client certificate to the TLS handshake, the bearer token to
+//! the `Authorization` header of the upgrade request.
+//!
+//! Frames are read whole (up to tungstenite's own 64 MiB cap); messages
+//! over `--max-message-size` are dropped like oversized stdio lines.
+//! Binary frames are not part of the transport and are ignored.
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use tokio::io::BufReader;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{AUTHORIZATION, SEC_WEBSOCKET_PROTOCOL};
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::Connector;
+use tracing::{debug, info, warn};
+
+use super::http::Upstream;
+use super::stdio::{self, Frame};
+use super::{Direction, Interceptor};
+
+/// Subprotocol of MCP over WebSocket
+const SUBPROTOCOL: &str = "mcp";
+
+/// Proxy the client on stdio to the WebSocket `upstream` until either side
+/// closes
+pub async fn run(upstream: Upstream, interceptor: Interceptor) -> Result<()> {
+    info!("Proxying WebSocket MCP server: {}", upstream.url);
+    let mut request = upstream.url.as_str().into_client_request()?;
+    let headers = request.headers_mut();
+    headers.insert(
+        SEC_WEBSOCKET_PROTOCOL,
+        HeaderValue::from_static(SUBPROTOCOL),
+    );
+    if let Some(token) = &upstream.token {
+        let token = token.token(&upstream.client()?).await?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token.expose()))
+            .context("Upstream token is not a valid header value")?;
+        value.set_sensitive(true);
+        headers.insert(AUTHORIZATION, value);
+    }
+    let connector = match upstream.url.scheme() {
+        "wss" => Some(Connector::NativeTls(tls_connector(&upstream)?)),
+        _ => None,
+    };
+    let (socket, _) =
+        tokio_tungstenite::connect_async_tls_with_config(request, None, false, connector)
+            .await
+            .with_context(|| format!("Failed to connect to {}", upstream.url))?;
+    let (mut sink, mut stream) = socket.split();
+
+    let (to_client, client_queue) = mpsc::channel::<String>(64);
+    let (to_server, mut server_queue) = mpsc::channel::<String>(64);
+    let client_writer = tokio::spawn(stdio::drain(client_queue, tokio::io::stdout()));
+    let server_writer = tokio::spawn(async move {
+        while let Some(message) = server_queue.recv().await {
+            sink.send(Message::Text(message)).await?;
+        }
+        sink.close().await?;
+        anyhow::Ok(())
+    });
+
+    let outgoing = stdio::pump(
+        BufReader::new(tokio::io::stdin()),
+        Direction::ClientToServer,
+        interceptor.clone(),
+        to_server.clone(),
+        to_client.clone(),
+    );
+    let max = interceptor.config().max_message_bytes;
+    let incoming = async {
+        while let Some(message) = stream.next().await {
+            let frame = match message.context("WebSocket upstream failed")? {
+                Message::Text(text) if text.len() > max => Frame::Oversized(text.len()),
+                Message::Text(text) => Frame::Message(text),
+                Message::Binary(data) => {
+                    warn!("Ignoring {} byte binary frame from the server", data.len());
+                    continue;
+                }
+                Message::Close(close) => {
+                    debug!("WebSocket upstream closed: {:?}", close);
+                    break;
+                }
+                // Pings are answered by tungstenite
+                _ => continue,
+            };
+            stdio::deliver(&interceptor, &frame, &to_client, &to_server).await;
+        }
+        anyhow::Ok(())
+    };
+
+    let result = tokio::select! {
+        result = outgoing => result,
+        result = incoming => result,
+    };
+    server_writer.abort();
+    client_writer.abort();
+    result
+}
+
+/// TLS settings of the upstream for the WebSocket handshake
+fn tls_connector(upstream: &Upstream) -> Result<native_tls::TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+    if let Some(certificates) = upstream.ca_certificates()? {
+        builder.disable_built_in_roots(true);
+        for certificate in certificates {
+            let certificate = native_tls::Certificate::from_pem(certificate.as_bytes())
+                .context("Invalid certificate in CA bundle")?;
+            builder.add_root_certificate(certificate);
+        }
+    }
+    if let Some((cert, key)) = upstream.identity_pem()? {
+        let identity = native_tls::Identity::from_pkcs8(&cert, &key)
+            .context("Invalid client certificate or key")?;
+        builder.identity(identity);
+    }
+    Ok(builder.build()?)
+}
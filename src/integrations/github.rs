@@ -0,0 +1,473 @@
+//! GitHub issues and check runs for findings
+//!
+//! With `scan --github-issues`, open findings at or above the `--fail-on`
+//! threshold that have never had an issue get one. Issues are labelled
+//! `mcp-sentinel` and carry the finding's fingerprint in a hidden comment,
+//! so a finding is filed once however many scans see it, and closing its
+//! issue is how it is dismissed.
+//!
+//! With `scan --github-check`, the scan is published as a check run on the
+//! scanned commit, each finding annotated on its line.
+//!
+//! Authentication is either `GITHUB_TOKEN` (as in GitHub Actions) or a
+//! GitHub App: `GITHUB_APP_ID` and `GITHUB_APP_PRIVATE_KEY` (the PEM, or a
+//! path to it), exchanged for a token of the app's installation on the
+//! repository. `GITHUB_API_URL` points at GitHub Enterprise Server.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::{Severity, Vulnerability};
+use crate::utils::network;
+
+pub const TOKEN_ENV: &str = "GITHUB_TOKEN";
+pub const APP_ID_ENV: &str = "GITHUB_APP_ID";
+pub const APP_KEY_ENV: &str = "GITHUB_APP_PRIVATE_KEY";
+pub const API_URL_ENV: &str = "GITHUB_API_URL";
+
+/// Label on every issue MCP Sentinel opens
+pub const LABEL: &str = "mcp-sentinel";
+
+/// Name of the check run
+pub const CHECK_NAME: &str = "MCP Sentinel";
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const PAGE_SIZE: usize = 100;
+/// GitHub takes at most this many annotations per request
+const MAX_ANNOTATIONS: usize = 50;
+const MARKER_PREFIX: &str = "<!-- mcp-sentinel:fingerprint=";
+
+/// Client for one repository
+pub struct GithubClient {
+    http: reqwest::Client,
+    api: String,
+    /// `owner/repo`
+    repo: String,
+    token: String,
+}
+
+impl GithubClient {
+    /// Authenticate for `repo` (`owner/repo`), as a GitHub App if one is
+    /// configured and with `GITHUB_TOKEN` otherwise
+    pub async fn connect(repo: &str) -> Result<Self> {
+        network::ensure_online("GitHub integration")?;
+        anyhow::ensure!(
+            repo.split('/').filter(|part| !part.is_empty()).count() == 2,
+            "Invalid GitHub repository '{}': expected OWNER/REPO",
+            repo
+        );
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("mcp-sentinel/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        let api = std::env::var(API_URL_ENV)
+            .unwrap_or_else(|_| DEFAULT_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+        let mut client = Self {
+            http,
+            api,
+            repo: repo.to_string(),
+            token: String::new(),
+        };
+
+        client.token = match std::env::var(APP_ID_ENV) {
+            Ok(app_id) => client.installation_token(&app_id).await?,
+            Err(_) => std::env::var(TOKEN_ENV).with_context(|| {
+                format!(
+                    "GitHub needs a token in {} or a GitHub App in {} and {}",
+                    TOKEN_ENV, APP_ID_ENV, APP_KEY_ENV
+                )
+            })?,
+        };
+        Ok(client)
+    }
+
+    /// Token of the app's installation on the repository
+    async fn installation_token(&mut self, app_id: &str) -> Result<String> {
+        let key = std::env::var(APP_KEY_ENV)
+            .with_context(|| format!("{} is set but {} is not", APP_ID_ENV, APP_KEY_ENV))?;
+        let pem = match key.trim_start().starts_with("-----BEGIN") {
+            true => key,
+            false => std::fs::read_to_string(&key)
+                .with_context(|| format!("Failed to read GitHub App key '{}'", key))?,
+        };
+        self.token = app_jwt(app_id, &pem)?;
+
+        let installation = self
+            .send(
+                self.http
+                    .get(self.url(&format!("repos/{}/installation", self.repo))),
+            )
+            .await
+            .with_context(|| format!("GitHub App {} is not installed on {}", app_id, self.repo))?;
+        let id = installation["id"]
+            .as_u64()
+            .context("GitHub did not return the installation ID")?;
+        let access = self
+            .send(
+                self.http
+                    .post(self.url(&format!("app/installations/{}/access_tokens", id))),
+            )
+            .await?;
+        access["token"]
+            .as_str()
+            .map(str::to_string)
+            .context("GitHub did not return an installation token")
+    }
+
+    /// Open an issue for each open finding at or above `threshold` that
+    /// never had one; returns the numbers of the new issues
+    pub async fn open_issues(&self, result: &ScanResult, threshold: Severity) -> Result<Vec<u64>> {
+        let filed = self.filed_fingerprints().await?;
+        debug!("{} findings already have GitHub issues", filed.len());
+
+        let mut opened = Vec::new();
+        for vuln in new_findings(result, threshold, &filed) {
+            let issue = self
+                .send(
+                    self.http
+                        .post(self.url(&format!("repos/{}/issues", self.repo)))
+                        .json(&json!({
+                            "title": issue_title(vuln),
+                            "body": issue_body(result, vuln),
+                            "labels": [LABEL],
+                        })),
+                )
+                .await?;
+            opened.extend(issue["number"].as_u64());
+        }
+        Ok(opened)
+    }
+
+    /// Fingerprints of the findings with an issue, open or closed
+    async fn filed_fingerprints(&self) -> Result<BTreeSet<String>> {
+        let mut fingerprints = BTreeSet::new();
+        for page in 1.. {
+            let issues = self
+                .send(
+                    self.http
+                        .get(self.url(&format!("repos/{}/issues", self.repo)))
+                        .query(&[
+                            ("labels", LABEL.to_string()),
+                            ("state", "all".to_string()),
+                            ("per_page", PAGE_SIZE.to_string()),
+                            ("page", page.to_string()),
+                        ]),
+                )
+                .await?;
+            let issues = issues.as_array().cloned().unwrap_or_default();
+            fingerprints.extend(
+                issues
+                    .iter()
+                    .filter_map(|issue| marked_fingerprint(issue["body"].as_str()?))
+                    .map(str::to_string),
+            );
+            if issues.len() < PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(fingerprints)
+    }
+
+    /// Publish the scan as a check run on commit `sha`, annotating findings
+    /// at paths relative to `root` (the repository checkout); returns the
+    /// check run's URL
+    pub async fn publish_check(
+        &self,
+        sha: &str,
+        result: &ScanResult,
+        root: &Path,
+        threshold: Severity,
+    ) -> Result<String> {
+        let annotations: Vec<Value> = result
+            .open_vulnerabilities()
+            .filter_map(|vuln| annotation(vuln, root))
+            .collect();
+        let mut batches = annotations.chunks(MAX_ANNOTATIONS);
+        let output = |batch: Option<&[Value]>| {
+            json!({
+                "title": check_title(result),
+                "summary": check_summary(result, threshold),
+                "annotations": batch.unwrap_or_default(),
+            })
+        };
+
+        let check = self
+            .send(
+                self.http
+                    .post(self.url(&format!("repos/{}/check-runs", self.repo)))
+                    .json(&json!({
+                        "name": CHECK_NAME,
+                        "head_sha": sha,
+                        "status": "completed",
+                        "conclusion": conclusion(result, threshold),
+                        "output": output(batches.next()),
+                    })),
+            )
+            .await?;
+        let id = check["id"]
+            .as_u64()
+            .context("GitHub did not return the check run ID")?;
+        // Annotations of later updates are added to the earlier ones
+        for batch in batches {
+            self.send(
+                self.http
+                    .patch(self.url(&format!("repos/{}/check-runs/{}", self.repo, id)))
+                    .json(&json!({ "output": output(Some(batch)) })),
+            )
+            .await?;
+        }
+        Ok(check["html_url"].as_str().unwrap_or_default().to_string())
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.api, path)
+    }
+
+    /// Send an authenticated request, returning the JSON reply
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let response = request
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await
+            .context("GitHub request failed")?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("GitHub returned {}: {}", status, body.trim());
+        }
+        Ok(serde_json::from_str(&body).unwrap_or(Value::Null))
+    }
+}
+
+/// JSON web token that authenticates as the GitHub App itself
+fn app_jwt(app_id: &str, pem: &str) -> Result<String> {
+    use jsonwebtoken::{Algorithm, EncodingKey, Header};
+
+    let now = chrono::Utc::now().timestamp();
+    // Backdated against clock drift; GitHub allows at most ten minutes
+    let claims = json!({ "iat": now - 60, "exp": now + 540, "iss": app_id });
+    let key = EncodingKey::from_rsa_pem(pem.as_bytes())
+        .context("Invalid GitHub App private key (expected an RSA PEM)")?;
+    Ok(jsonwebtoken::encode(
+        &Header::new(Algorithm::RS256),
+        &claims,
+        &key,
+    )?)
+}
+
+/// The commit checked out in `dir`
+pub async fn head_commit(dir: &Path) -> Result<String> {
+    git(dir, &["rev-parse", "HEAD"]).await.with_context(|| {
+        format!(
+            "'{}' is not in a git checkout; pass --github-sha",
+            dir.display()
+        )
+    })
+}
+
+/// The root of the git checkout holding `dir`, which annotation paths are
+/// relative to
+pub async fn checkout_root(dir: &Path) -> PathBuf {
+    match git(dir, &["rev-parse", "--show-toplevel"]).await {
+        Some(root) => PathBuf::from(root),
+        None => dir.to_path_buf(),
+    }
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    (output.status.success() && !stdout.trim().is_empty()).then(|| stdout.trim().to_string())
+}
+
+fn new_findings<'a>(
+    result: &'a ScanResult,
+    threshold: Severity,
+    filed: &'a BTreeSet<String>,
+) -> impl Iterator<Item = &'a Vulnerability> {
+    result.open_vulnerabilities().filter(move |v| {
+        v.severity >= threshold
+            && v.fingerprint
+                .as_ref()
+                .is_some_and(|fingerprint| !filed.contains(fingerprint))
+    })
+}
+
+fn marked_fingerprint(body: &str) -> Option<&str> {
+    let start = body.find(MARKER_PREFIX)? + MARKER_PREFIX.len();
+    let rest = &body[start..];
+    Some(rest[..rest.find(" -->")?].trim())
+}
+
+fn issue_title(vuln: &Vulnerability) -> String {
+    match &vuln.location {
+        Some(location) => format!("[MCP Sentinel] {} in {}", vuln.title, location.file),
+        None => format!("[MCP Sentinel] {}", vuln.title),
+    }
+}
+
+/// Issue body, in Markdown
+fn issue_body(result: &ScanResult, vuln: &Vulnerability) -> String {
+    let mut body = format!("**Severity:** {}\n", vuln.severity.to_badge());
+    if let Some(rule) = &vuln.rule_id {
+        body.push_str(&format!("**Rule:** `{}`\n", rule));
+    }
+    if let Some(location) = &vuln.location {
+        body.push_str(&format!("**Location:** `{}`\n", location.format()));
+    }
+    body.push_str(&format!("\n{}\n", vuln.description));
+    if let Some(snippet) = &vuln.code_snippet {
+        body.push_str(&format!("\n```\n{}\n```\n", snippet));
+    }
+    if let Some(impact) = &vuln.impact {
+        body.push_str(&format!("\n**Impact:** {}\n", impact));
+    }
+    if let Some(remediation) = &vuln.remediation {
+        body.push_str(&format!("\n**Remediation:** {}\n", remediation));
+    }
+    body.push_str(&format!(
+        "\n---\nFound by MCP Sentinel scan `{}`. Close this issue to dismiss the finding.\n{}{} -->\n",
+        result.scan_id,
+        MARKER_PREFIX,
+        vuln.fingerprint.as_deref().unwrap_or_default()
+    ));
+    body
+}
+
+fn annotation(vuln: &Vulnerability, root: &Path) -> Option<Value> {
+    let location = vuln.location.as_ref()?;
+    let line = location.line.unwrap_or(1);
+    let level = match vuln.severity {
+        Severity::Critical | Severity::High => "failure",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "notice",
+    };
+    let mut message = vuln.description.clone();
+    if let Some(remediation) = &vuln.remediation {
+        message.push_str(&format!("\n\nRemediation: {}", remediation));
+    }
+    Some(json!({
+        "path": repository_path(&location.file, root),
+        "start_line": line,
+        "end_line": line,
+        "annotation_level": level,
+        "title": format!("[{}] {}", vuln.severity.to_badge(), vuln.title),
+        "message": message,
+    }))
+}
+
+/// `file` relative to the checkout `root`, with forward slashes
+fn repository_path(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+    let relative = match (path.canonicalize(), root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.strip_prefix(&root).map(Path::to_path_buf).ok(),
+        _ => None,
+    };
+    let relative = relative.unwrap_or_else(|| path.strip_prefix("./").unwrap_or(path).into());
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn conclusion(result: &ScanResult, threshold: Severity) -> &'static str {
+    if result.has_issues_at_level(threshold) {
+        "failure"
+    } else if result.metadata.incomplete {
+        "neutral"
+    } else {
+        "success"
+    }
+}
+
+fn check_title(result: &ScanResult) -> String {
+    match result.open_vulnerabilities().count() {
+        0 => "No findings".to_string(),
+        1 => "1 finding".to_string(),
+        count => format!("{} findings", count),
+    }
+}
+
+fn check_summary(result: &ScanResult, threshold: Severity) -> String {
+    let summary = &result.summary;
+    let mut text = format!(
+        "| Critical | High | Medium | Low |\n|---|---|---|---|\n| {} | {} | {} | {} |\n\n\
+         Risk score: **{}**/100. Findings at or above {} fail this check.",
+        summary.critical,
+        summary.high,
+        summary.medium,
+        summary.low,
+        summary.risk_score,
+        threshold.to_badge()
+    );
+    if result.metadata.incomplete {
+        text.push_str("\n\n⚠️ The scan was cancelled; results are incomplete.");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, VulnerabilityType};
+
+    fn finding(severity: Severity, file: &str) -> Vulnerability {
+        Vulnerability::new(
+            "SEC-001",
+            VulnerabilityType::HardcodedCredentials,
+            severity,
+            "Hardcoded AWS key",
+            "Desc",
+        )
+        .with_location(Location::new(file).with_line(12))
+    }
+
+    #[test]
+    fn test_issue_dedupe() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        result.add_vulnerabilities(vec![
+            finding(Severity::Critical, "server/a.py"),
+            finding(Severity::High, "server/b.py"),
+            finding(Severity::Medium, "server/c.py"),
+        ]);
+        let filed_for = &result.vulnerabilities[0];
+        let body = issue_body(&result, filed_for);
+        let fingerprint = marked_fingerprint(&body).unwrap();
+        assert_eq!(Some(fingerprint), filed_for.fingerprint.as_deref());
+
+        let filed = BTreeSet::from([fingerprint.to_string()]);
+        let new: Vec<_> = new_findings(&result, Severity::High, &filed)
+            .map(|v| v.location.as_ref().unwrap().file.as_str())
+            .collect();
+        assert_eq!(new, ["server/b.py"]);
+    }
+
+    #[test]
+    fn test_annotation() {
+        let vuln = finding(Severity::Medium, "./server/tools.py");
+        let annotation = annotation(&vuln, Path::new("/nonexistent")).unwrap();
+        assert_eq!(annotation["path"], "server/tools.py");
+        assert_eq!(annotation["start_line"], 12);
+        assert_eq!(annotation["annotation_level"], "warning");
+
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        assert_eq!(conclusion(&result, Severity::High), "success");
+        result.add_vulnerabilities(vec![vuln]);
+        assert_eq!(conclusion(&result, Severity::High), "success");
+        assert_eq!(conclusion(&result, Severity::Medium), "failure");
+    }
+}
@@ -0,0 +1,488 @@
+//! Jira issues for findings
+//!
+//! `scan --jira` keeps one issue per finding in the project named by the
+//! `[jira]` table of `sentinel.toml`:
+//! - open findings at or above `min_severity` (high by default) without an
+//!   unresolved issue get one
+//! - unresolved issues whose finding is still open are updated from the
+//!   latest scan
+//! - unresolved issues whose finding is gone (fixed or triaged) are
+//!   commented on and moved to a done status, unless the scan was cut short
+//!
+//! Issues carry the label `mcp-sentinel-<fingerprint>` as their dedupe key,
+//! `mcp-sentinel-target-<name>` for the scanned target (`target` in the
+//! config, or the directory's name), plus `mcp-sentinel` and the configured
+//! labels. A scan only updates and closes issues with its own target label,
+//! so targets sharing a project leave each other's issues alone.
+//!
+//! Credentials come from `JIRA_API_TOKEN`, with `JIRA_EMAIL` for Jira Cloud
+//! (basic auth); without an email the token is sent as a personal access
+//! token, as Jira Data Center expects.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::models::project_config::JiraConfig;
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::Vulnerability;
+use crate::utils::network;
+
+pub const TOKEN_ENV: &str = "JIRA_API_TOKEN";
+pub const EMAIL_ENV: &str = "JIRA_EMAIL";
+
+/// Label on every issue MCP Sentinel files
+pub const LABEL: &str = "mcp-sentinel";
+
+/// Prefix of the label naming the scanned target
+const TARGET_LABEL_PREFIX: &str = "mcp-sentinel-target-";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const PAGE_SIZE: usize = 100;
+/// Jira rejects longer summaries
+const MAX_SUMMARY_CHARS: usize = 250;
+
+/// An issue filed for a finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub key: String,
+    pub fingerprint: String,
+    /// Name from the target label, if the issue has one
+    pub target: Option<String>,
+    /// In a done status category
+    pub resolved: bool,
+}
+
+/// What a sync changed, by issue key
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub closed: Vec<String>,
+}
+
+/// Changes that bring the issues in line with a scan
+#[derive(Debug, Default)]
+struct Plan<'a> {
+    create: Vec<&'a Vulnerability>,
+    update: Vec<(String, &'a Vulnerability)>,
+    close: Vec<String>,
+}
+
+fn plan<'a>(
+    result: &'a ScanResult,
+    issues: &[Issue],
+    config: &JiraConfig,
+    target: &str,
+) -> Plan<'a> {
+    let open: BTreeMap<&str, &Vulnerability> = result
+        .open_vulnerabilities()
+        .filter_map(|v| Some((v.fingerprint.as_deref()?, v)))
+        .collect();
+    // Issues of other targets are theirs to update and close
+    let unresolved: BTreeMap<&str, &Issue> = issues
+        .iter()
+        .filter(|issue| !issue.resolved && issue.target.as_deref() == Some(target))
+        .map(|issue| (issue.fingerprint.as_str(), issue))
+        .collect();
+
+    let mut plan = Plan::default();
+    for (&fingerprint, &vuln) in &open {
+        match unresolved.get(fingerprint) {
+            Some(issue) => plan.update.push((issue.key.clone(), vuln)),
+            // A finding whose issue was resolved gets a new one if it returns
+            None if vuln.severity >= config.min_severity => plan.create.push(vuln),
+            None => {}
+        }
+    }
+    if !result.metadata.incomplete {
+        plan.close = unresolved
+            .iter()
+            .filter(|(fingerprint, _)| !open.contains_key(*fingerprint))
+            .map(|(_, issue)| issue.key.clone())
+            .collect();
+    }
+    plan
+}
+
+enum Auth {
+    Basic { email: String, token: String },
+    Bearer(String),
+}
+
+/// Client for one Jira project
+pub struct JiraClient {
+    config: JiraConfig,
+    http: reqwest::Client,
+    auth: Auth,
+}
+
+impl JiraClient {
+    pub fn new(config: JiraConfig) -> Result<Self> {
+        network::ensure_online("Jira issues")?;
+        let token = std::env::var(TOKEN_ENV)
+            .with_context(|| format!("Jira needs an API token in {}", TOKEN_ENV))?;
+        let auth = match std::env::var(EMAIL_ENV) {
+            Ok(email) => Auth::Basic { email, token },
+            Err(_) => Auth::Bearer(token),
+        };
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        Ok(Self { config, http, auth })
+    }
+
+    /// File, update, and close issues to match `result`
+    pub async fn sync(&self, result: &ScanResult) -> Result<SyncReport> {
+        let target = target_name(&self.config, &result.target);
+        let issues = self.issues(&target).await?;
+        debug!("{} Jira issues filed for {}", issues.len(), target);
+        let plan = plan(result, &issues, &self.config, &target);
+
+        let mut report = SyncReport::default();
+        for vuln in plan.create {
+            let key = self.create(result, vuln, &target).await?;
+            report.created.push(key);
+        }
+        for (key, vuln) in plan.update {
+            self.update(&key, result, vuln).await?;
+            report.updated.push(key);
+        }
+        for key in plan.close {
+            let comment = format!(
+                "No longer found by MCP Sentinel (scan {} of {}).",
+                result.scan_id, result.target
+            );
+            if self.close(&key, &comment).await? {
+                report.closed.push(key);
+            } else {
+                warn!("Jira issue {} has no transition to a done status", key);
+            }
+        }
+        Ok(report)
+    }
+
+    /// The issues filed for `target` with this configuration
+    async fn issues(&self, target: &str) -> Result<Vec<Issue>> {
+        let target_label = target_label(target);
+        let jql = std::iter::once(format!("project = \"{}\"", self.config.project))
+            .chain(
+                self.labels()
+                    .chain([target_label.as_str()])
+                    .map(|label| format!("labels = \"{}\"", label)),
+            )
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let page_size = PAGE_SIZE.to_string();
+        let mut issues = Vec::new();
+        loop {
+            let start = issues.len().to_string();
+            let page = self
+                .send(self.http.get(self.url("search")).query(&[
+                    ("jql", jql.as_str()),
+                    ("fields", "labels,status"),
+                    ("startAt", start.as_str()),
+                    ("maxResults", page_size.as_str()),
+                ]))
+                .await?;
+            let found = page["issues"].as_array().cloned().unwrap_or_default();
+            issues.extend(found.iter().map(parse_issue));
+            let total = page["total"].as_u64().unwrap_or(0) as usize;
+            if found.is_empty() || issues.len() >= total {
+                break;
+            }
+        }
+        // Issues without a fingerprint label were not filed by a scan
+        Ok(issues.into_iter().flatten().collect())
+    }
+
+    async fn create(
+        &self,
+        result: &ScanResult,
+        vuln: &Vulnerability,
+        target: &str,
+    ) -> Result<String> {
+        let fingerprint = vuln.fingerprint.as_deref().unwrap_or_default();
+        let labels: Vec<String> = self
+            .labels()
+            .map(str::to_string)
+            .chain([fingerprint_label(fingerprint), target_label(target)])
+            .collect();
+        let created = self
+            .send(self.http.post(self.url("issue")).json(&json!({
+                "fields": {
+                    "project": { "key": self.config.project },
+                    "issuetype": { "name": self.config.issue_type },
+                    "summary": summary(vuln),
+                    "description": description(result, vuln),
+                    "labels": labels,
+                }
+            })))
+            .await?;
+        created["key"]
+            .as_str()
+            .map(str::to_string)
+            .context("Jira did not return the key of the new issue")
+    }
+
+    async fn update(&self, key: &str, result: &ScanResult, vuln: &Vulnerability) -> Result<()> {
+        self.send(
+            self.http
+                .put(self.url(&format!("issue/{}", key)))
+                .json(&json!({
+                    "fields": {
+                        "summary": summary(vuln),
+                        "description": description(result, vuln),
+                    }
+                })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Comment on the issue and move it to a done status, if its workflow
+    /// has a transition to one
+    async fn close(&self, key: &str, comment: &str) -> Result<bool> {
+        let transitions = self
+            .send(
+                self.http
+                    .get(self.url(&format!("issue/{}/transitions", key))),
+            )
+            .await?;
+        let done = transitions["transitions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|t| t["to"]["statusCategory"]["key"] == "done")
+            .and_then(|t| t["id"].as_str());
+        let Some(id) = done else {
+            return Ok(false);
+        };
+
+        self.send(
+            self.http
+                .post(self.url(&format!("issue/{}/comment", key)))
+                .json(&json!({ "body": comment })),
+        )
+        .await?;
+        self.send(
+            self.http
+                .post(self.url(&format!("issue/{}/transitions", key)))
+                .json(&json!({ "transition": { "id": id } })),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    fn labels(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(LABEL).chain(self.config.labels.iter().map(String::as_str))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!(
+            "{}/rest/api/2/{}",
+            self.config.url.trim_end_matches('/'),
+            path
+        )
+    }
+
+    /// Send an authenticated request, returning the JSON reply (null if empty)
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value> {
+        let request = match &self.auth {
+            Auth::Basic { email, token } => request.basic_auth(email, Some(token)),
+            Auth::Bearer(token) => request.bearer_auth(token),
+        };
+        let response = request.send().await.context("Jira request failed")?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("Jira returned {}: {}", status, body.trim());
+        }
+        Ok(serde_json::from_str(&body).unwrap_or(Value::Null))
+    }
+}
+
+fn fingerprint_label(fingerprint: &str) -> String {
+    format!("{}-{}", LABEL, fingerprint)
+}
+
+fn target_label(target: &str) -> String {
+    format!("{}{}", TARGET_LABEL_PREFIX, target)
+}
+
+/// Name of the scanned target in its label: `target` from the config, or
+/// the name of the scanned directory
+fn target_name(config: &JiraConfig, scanned: &str) -> String {
+    let name = config.target.clone().unwrap_or_else(|| {
+        let path = Path::new(scanned);
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        path.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| scanned.to_string())
+    });
+    // Jira labels cannot contain spaces
+    name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+fn parse_issue(issue: &Value) -> Option<Issue> {
+    let prefix = format!("{}-", LABEL);
+    let labels: BTreeSet<&str> = issue["fields"]["labels"]
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    let target = labels
+        .iter()
+        .find_map(|l| l.strip_prefix(TARGET_LABEL_PREFIX));
+    let fingerprint = labels
+        .iter()
+        .filter(|l| !l.starts_with(TARGET_LABEL_PREFIX))
+        .find_map(|l| l.strip_prefix(&prefix))?;
+    Some(Issue {
+        key: issue["key"].as_str()?.to_string(),
+        fingerprint: fingerprint.to_string(),
+        target: target.map(str::to_string),
+        resolved: issue["fields"]["status"]["statusCategory"]["key"] == "done",
+    })
+}
+
+fn summary(vuln: &Vulnerability) -> String {
+    let summary = match &vuln.location {
+        Some(location) => format!("[MCP Sentinel] {} in {}", vuln.title, location.file),
+        None => format!("[MCP Sentinel] {}", vuln.title),
+    };
+    summary.chars().take(MAX_SUMMARY_CHARS).collect()
+}
+
+/// Issue description, in Jira wiki markup
+fn description(result: &ScanResult, vuln: &Vulnerability) -> String {
+    let mut text = format!("*Severity:* {}\n", vuln.severity.to_badge());
+    if let Some(rule) = &vuln.rule_id {
+        text.push_str(&format!("*Rule:* {}\n", rule));
+    }
+    if let Some(location) = &vuln.location {
+        text.push_str(&format!("*Location:* {}\n", location.format()));
+    }
+    text.push_str(&format!("\n{}\n", vuln.description));
+    if let Some(snippet) = &vuln.code_snippet {
+        text.push_str(&format!("\n{{noformat}}\n{}\n{{noformat}}\n", snippet));
+    }
+    if let Some(impact) = &vuln.impact {
+        text.push_str(&format!("\n*Impact:* {}\n", impact));
+    }
+    if let Some(remediation) = &vuln.remediation {
+        text.push_str(&format!("\n*Remediation:* {}\n", remediation));
+    }
+    text.push_str(&format!(
+        "\n----\nFingerprint {}, last seen by scan {} of {}",
+        vuln.fingerprint.as_deref().unwrap_or("-"),
+        result.scan_id,
+        result.target
+    ));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Severity, VulnerabilityType};
+
+    fn finding(fingerprint: &str, severity: Severity) -> Vulnerability {
+        let mut vuln = Vulnerability::new(
+            "SEC-001",
+            VulnerabilityType::HardcodedCredentials,
+            severity,
+            "Hardcoded AWS key",
+            "Desc",
+        );
+        vuln.fingerprint = Some(fingerprint.to_string());
+        vuln
+    }
+
+    fn issue(key: &str, fingerprint: &str, resolved: bool) -> Issue {
+        Issue {
+            key: key.to_string(),
+            fingerprint: fingerprint.to_string(),
+            target: Some("srv".to_string()),
+            resolved,
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let config: JiraConfig =
+            toml::from_str("url = \"https://jira.example.com\"\nproject = \"SEC\"").unwrap();
+        let mut result = ScanResult::new("/srv", vec!["static".to_string()]);
+        result.add_vulnerabilities(vec![
+            finding("aaaa", Severity::Critical),
+            finding("bbbb", Severity::High),
+            finding("cccc", Severity::Medium),
+            finding("dddd", Severity::High),
+        ]);
+        let other_target = Issue {
+            target: Some("other".to_string()),
+            ..issue("SEC-5", "gggg", false)
+        };
+        let issues = [
+            issue("SEC-1", "bbbb", false),
+            issue("SEC-2", "dddd", true),
+            issue("SEC-3", "eeee", false),
+            issue("SEC-4", "ffff", true),
+            other_target,
+        ];
+
+        let planned = plan(&result, &issues, &config, "srv");
+        let created: Vec<_> = planned
+            .create
+            .iter()
+            .filter_map(|v| v.fingerprint.as_deref())
+            .collect();
+        assert_eq!(created, ["aaaa", "dddd"]);
+        assert_eq!(planned.update.len(), 1);
+        assert_eq!(planned.update[0].0, "SEC-1");
+        // SEC-5 belongs to another target
+        assert_eq!(planned.close, ["SEC-3"]);
+
+        // Nothing is closed on the strength of a partial scan
+        result.metadata.incomplete = true;
+        assert!(plan(&result, &issues, &config, "srv").close.is_empty());
+    }
+
+    #[test]
+    fn test_parse_issue() {
+        let found = json!({
+            "key": "SEC-7",
+            "fields": {
+                "labels": [
+                    "mcp-sentinel",
+                    "mcp-sentinel-target-srv",
+                    "mcp-sentinel-0123abcd",
+                    "team-a",
+                ],
+                "status": { "statusCategory": { "key": "done" } },
+            }
+        });
+        assert_eq!(parse_issue(&found), Some(issue("SEC-7", "0123abcd", true)));
+        let manual = json!({ "key": "SEC-8", "fields": { "labels": ["mcp-sentinel"] } });
+        assert_eq!(parse_issue(&manual), None);
+    }
+
+    #[test]
+    fn test_target_name() {
+        let config: JiraConfig =
+            toml::from_str("url = \"https://jira.example.com\"\nproject = \"SEC\"").unwrap();
+        assert_eq!(
+            target_name(&config, "/srv/weather server"),
+            "weather-server"
+        );
+        let named = JiraConfig {
+            target: Some("weather".to_string()),
+            ..config
+        };
+        assert_eq!(target_name(&named, "/srv/weather server"), "weather");
+    }
+}
@@ -0,0 +1,4 @@
+//! Issue trackers and code hosts that findings are reported to
+
+pub mod github;
+pub mod jira;
@@ -9,36 +9,55 @@
 //! # Quick Start
 //!
 //! ```no_run
-//! use mcp_sentinel::scanner::Scanner;
-//! use mcp_sentinel::models::ScanConfig;
+//! use mcp_sentinel::{DetectorKind, Scanner};
 //!
 //! #[tokio::main]
 //! async fn main() -> anyhow::Result<()> {
-//!     let config = ScanConfig::default();
-//!     let scanner = Scanner::new(config);
+//!     let scanner = Scanner::builder()
+//!         .detectors([DetectorKind::Secrets, DetectorKind::CommandInjection])
+//!         .exclude(["tests/"])
+//!         .min_confidence(0.8)
+//!         .build();
 //!     let results = scanner.scan_directory("./mcp-server").await?;
 //!     println!("Found {} vulnerabilities", results.vulnerabilities.len());
 //!     Ok(())
 //! }
 //! ```
 
+// The detector core (`detectors`, `models`) has no I/O and also builds for
+// wasm32 with `--no-default-features`; everything else needs `native`.
+#[cfg(feature = "native")]
+pub mod api;
+pub mod bindings;
+#[cfg(feature = "native")]
 pub mod cli;
 pub mod detectors;
+#[cfg(feature = "native")]
 pub mod engines;
+#[cfg(feature = "native")]
+pub mod integrations;
 pub mod models;
 pub mod output;
+#[cfg(feature = "native")]
+pub mod remediation;
+#[cfg(feature = "native")]
 pub mod storage;
+#[cfg(feature = "native")]
 pub mod utils;
 
 // Re-export common types
+pub use detectors::DetectorKind;
 pub use models::{
     config::ScanConfig,
-    scan_result::ScanResult,
-    vulnerability::{Severity, Vulnerability},
+    scan_result::{ScanMetadata, ScanResult, ScanSummary},
+    vulnerability::{Location, Severity, Vulnerability, VulnerabilityType},
 };
 
 // Core scanner API
+#[cfg(feature = "native")]
 pub mod scanner;
+#[cfg(feature = "native")]
+pub use scanner::{ScanEvent, Scanner, ScannerBuilder};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
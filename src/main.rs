@@ -1,9 +1,11 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
 use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
-use mcp_sentinel::cli;
+use mcp_sentinel::cli::{self, LlmProvider, OutputFormat, SeverityLevel};
+use mcp_sentinel::models::vulnerability::TriageState;
 
 #[derive(Parser)]
 #[command(
@@ -24,88 +26,32 @@ struct Cli {
     /// Disable colored output
     #[arg(long, global = true)]
     no_color: bool,
+
+    /// Export tracing spans to this OTLP gRPC endpoint (requires the `otel` feature)
+    #[arg(
+        long,
+        global = true,
+        env = "OTEL_EXPORTER_OTLP_ENDPOINT",
+        value_name = "URL"
+    )]
+    otlp_endpoint: Option<String>,
+
+    /// Make no network requests (air-gapped environments)
+    #[arg(long, global = true, env = "MCP_SENTINEL_OFFLINE")]
+    offline: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Scan MCP server or configuration for vulnerabilities
     #[command(visible_alias = "s")]
-    Scan {
-        /// Path to MCP server directory, GitHub URL, or config file
-        #[arg(value_name = "TARGET")]
-        target: String,
-
-        /// Scanning mode
-        #[arg(long, value_enum, default_value = "quick")]
-        mode: ScanMode,
-
-        /// LLM provider for deep mode
-        #[arg(long, value_enum)]
-        llm_provider: Option<LlmProvider>,
-
-        /// Specific model name
-        #[arg(long)]
-        llm_model: Option<String>,
-
-        /// API key (or use env var)
-        #[arg(long, env = "MCP_SENTINEL_API_KEY")]
-        llm_api_key: Option<String>,
-
-        /// Output format
-        #[arg(short, long, value_enum, default_value = "terminal")]
-        output: OutputFormat,
-
-        /// Save report to file
-        #[arg(long, value_name = "PATH")]
-        output_file: Option<String>,
-
-        /// Minimum severity to report
-        #[arg(long, value_enum, default_value = "low")]
-        severity: SeverityLevel,
-
-        /// Exit with code 1 if vulnerabilities >= level found
-        #[arg(long, value_enum)]
-        fail_on: Option<SeverityLevel>,
-
-        /// Custom configuration file
-        #[arg(short, long)]
-        config: Option<String>,
-    },
+    Scan(cli::scan::ScanArgs),
 
     /// Run as transparent MCP proxy for runtime monitoring
-    Proxy {
-        /// MCP configuration file to proxy
-        #[arg(short, long)]
-        config: Option<String>,
-
-        /// Proxy listen port
-        #[arg(short, long, default_value = "8080")]
-        port: u16,
+    Proxy(cli::proxy::ProxyArgs),
 
-        /// Custom guardrails rules file (YAML)
-        #[arg(short, long)]
-        guardrails: Option<String>,
-
-        /// Save all MCP traffic to log file
-        #[arg(long)]
-        log_traffic: bool,
-
-        /// Traffic log destination
-        #[arg(long)]
-        log_file: Option<String>,
-
-        /// Block requests >= risk level
-        #[arg(long, value_enum)]
-        block_on_risk: Option<SeverityLevel>,
-
-        /// Send alerts to webhook URL
-        #[arg(long)]
-        alert_webhook: Option<String>,
-
-        /// Launch web dashboard
-        #[arg(short, long)]
-        dashboard: bool,
-    },
+    /// Send malformed and hostile protocol input to an MCP server
+    Fuzz(cli::fuzz::FuzzArgs),
 
     /// Continuous scanning with file watching
     Monitor {
@@ -132,13 +78,28 @@ enum Commands {
         /// Alert on vulnerabilities >= level
         #[arg(long, value_enum)]
         alert_on: Option<SeverityLevel>,
+
+        /// Expose Prometheus metrics on this address (e.g. 127.0.0.1:9090)
+        #[arg(long, value_name = "ADDR")]
+        metrics_addr: Option<std::net::SocketAddr>,
     },
 
     /// Comprehensive security audit (all engines)
     Audit {
         /// Path to MCP server directory
-        #[arg(value_name = "TARGET")]
-        target: String,
+        #[arg(value_name = "TARGET", required_unless_present = "discover")]
+        target: Option<String>,
+
+        /// List the servers configured in the MCP clients on this machine
+        /// (Claude Desktop, Cursor, VS Code, Windsurf, Cline) and offer to
+        /// scan each one that runs from a local directory or an npx/uvx
+        /// package
+        #[arg(long, conflicts_with = "target")]
+        discover: bool,
+
+        /// With --discover, scan every discovered server without asking
+        #[arg(short, long, requires = "discover")]
+        yes: bool,
 
         /// Include runtime analysis (temporary proxy)
         #[arg(long)]
@@ -169,6 +130,24 @@ enum Commands {
         output_file: Option<String>,
     },
 
+    /// Run as a long-lived daemon exposing a REST API
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8585")]
+        listen: std::net::SocketAddr,
+
+        /// Maximum number of scan jobs running concurrently
+        #[arg(long, default_value = "4")]
+        max_jobs: usize,
+
+        /// Also serve the gRPC API on this address (requires the `grpc` feature)
+        #[arg(long, value_name = "ADDR")]
+        grpc_listen: Option<std::net::SocketAddr>,
+    },
+
+    /// Run as an MCP server over stdio so agents can ask Sentinel to vet servers
+    McpServe,
+
     /// Initialize configuration
     Init {
         /// Config file location
@@ -176,6 +155,25 @@ enum Commands {
         config_path: String,
     },
 
+    /// Record a triage decision for a finding
+    Triage {
+        /// Finding fingerprint, as shown in scan reports
+        #[arg(value_name = "FINGERPRINT")]
+        fingerprint: String,
+
+        /// Triage state (`open` clears an earlier decision)
+        #[arg(long, value_enum)]
+        state: TriageState,
+
+        /// Why the finding was triaged this way
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Project directory holding .sentinel/triage.json
+        #[arg(long, default_value = ".")]
+        project: String,
+    },
+
     /// Manage whitelisted tools and servers
     Whitelist {
         #[command(subcommand)]
@@ -187,36 +185,53 @@ enum Commands {
         #[command(subcommand)]
         command: RulesCommands,
     },
-}
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum ScanMode {
-    Quick,
-    Deep,
-}
+    /// Run scans on a schedule
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommands,
+    },
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum LlmProvider {
-    Openai,
-    Anthropic,
-    Local,
-}
+    /// Prune scan history and audit logs to the retention limits
+    Gc {
+        /// Drop records older than this many days
+        #[arg(long)]
+        max_age_days: Option<u32>,
 
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum OutputFormat {
-    Terminal,
-    Json,
-    Html,
-    Pdf,
-    Sarif,
-}
+        /// Drop the oldest records past this size, in MB
+        #[arg(long)]
+        max_size_mb: Option<u64>,
+
+        /// Further audit logs to prune (e.g. from proxy --audit-log)
+        #[arg(long)]
+        audit_log: Vec<String>,
+    },
+
+    /// Make an SVG status badge from a JSON scan report, for a README
+    Badge {
+        /// Report written by `scan --output json`
+        #[arg(long, value_name = "PATH")]
+        input: String,
 
-#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum SeverityLevel {
-    Low,
-    Medium,
-    High,
-    Critical,
+        /// Where to write the SVG (default: stdout)
+        #[arg(long, value_name = "PATH")]
+        output: Option<String>,
+    },
+
+    /// Time each detector on a directory to find slow patterns
+    Bench {
+        /// Path to MCP server directory
+        #[arg(value_name = "TARGET")]
+        target: String,
+
+        /// Number of slowest files to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// Project config file (default: TARGET/sentinel.toml if present)
+        #[arg(short, long)]
+        config: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -265,6 +280,56 @@ enum RulesCommands {
         #[arg(value_name = "TRAFFIC")]
         traffic: String,
     },
+    /// Download the latest signed detection rule bundle
+    ///
+    /// Needs a release signing key in rules/release-signing-key.pub; builds
+    /// without one refuse to update.
+    Update {
+        /// Release URL or bundle URL to fetch from
+        #[arg(long)]
+        url: Option<String>,
+    },
+    /// Pack rules and cached IOC feeds for an offline machine
+    ExportBundle {
+        #[arg(value_name = "PATH")]
+        path: String,
+    },
+    /// Install a bundle created by export-bundle
+    ImportBundle {
+        #[arg(value_name = "PATH")]
+        path: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleCommands {
+    /// Scan a directory on a cron expression
+    Add {
+        /// Cron expression, e.g. "0 3 * * *" or @daily
+        #[arg(value_name = "CRON")]
+        cron: String,
+
+        /// Directory to scan
+        #[arg(long)]
+        target: String,
+
+        /// Where to report new findings: slack, slack:<url>, or a webhook URL
+        #[arg(long)]
+        notify: Vec<String>,
+
+        /// Report new findings at or above this severity
+        #[arg(long, value_enum, default_value = "medium")]
+        min_severity: SeverityLevel,
+    },
+    /// Show scheduled scans
+    List,
+    /// Remove a scheduled scan
+    Remove {
+        #[arg(value_name = "ID")]
+        id: String,
+    },
+    /// Run scheduled scans until interrupted
+    Run,
 }
 
 #[tokio::main]
@@ -278,71 +343,51 @@ async fn main() -> Result<()> {
         "mcp_sentinel=info,warn"
     };
 
+    #[cfg(feature = "otel")]
+    let otel_layer = cli
+        .otlp_endpoint
+        .as_deref()
+        .map(mcp_sentinel::utils::telemetry::otlp_layer)
+        .transpose()?;
+    #[cfg(not(feature = "otel"))]
+    let otel_layer: Option<tracing_subscriber::layer::Identity> = None;
+
+    // Log lines on the terminal would tear through the proxy dashboard
+    let dashboard = matches!(&cli.command, Commands::Proxy(args) if args.dashboard)
+        && std::io::stderr().is_terminal();
+    let env_filter = if dashboard {
+        tracing_subscriber::EnvFilter::new("off")
+    } else {
+        tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| filter.into())
+    };
+
     tracing_subscriber::registry()
         .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| filter.into()),
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(env_filter),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
         .init();
 
+    #[cfg(not(feature = "otel"))]
+    if cli.otlp_endpoint.is_some() {
+        tracing::warn!("--otlp-endpoint ignored: built without the `otel` feature");
+    }
+
     // Set color preference
     if cli.no_color {
         std::env::set_var("NO_COLOR", "1");
     }
+    mcp_sentinel::utils::network::set_offline(cli.offline);
 
     info!("🛡️  MCP Sentinel v{}", env!("CARGO_PKG_VERSION"));
 
     // Execute command
-    match cli.command {
-        Commands::Scan {
-            target,
-            mode,
-            llm_provider,
-            llm_model,
-            llm_api_key,
-            output,
-            output_file,
-            severity,
-            fail_on,
-            config,
-        } => {
-            cli::scan::execute(
-                target,
-                mode,
-                llm_provider,
-                llm_model,
-                llm_api_key,
-                output,
-                output_file,
-                severity,
-                fail_on,
-                config,
-            )
-            .await
-        }
-        Commands::Proxy {
-            config,
-            port,
-            guardrails,
-            log_traffic,
-            log_file,
-            block_on_risk,
-            alert_webhook,
-            dashboard,
-        } => {
-            cli::proxy::execute(
-                config,
-                port,
-                guardrails,
-                log_traffic,
-                log_file,
-                block_on_risk,
-                alert_webhook,
-                dashboard,
-            )
-            .await
-        }
+    let result = match cli.command {
+        Commands::Scan(args) => cli::scan::execute(args).await,
+        Commands::Proxy(args) => cli::proxy::execute(args).await,
+        Commands::Fuzz(args) => cli::fuzz::execute(args).await,
         Commands::Monitor {
             target,
             interval,
@@ -350,11 +395,23 @@ async fn main() -> Result<()> {
             daemon,
             pid_file,
             alert_on,
+            metrics_addr,
         } => {
-            cli::monitor::execute(target, interval, watch, daemon, pid_file, alert_on).await
+            cli::monitor::execute(
+                target,
+                interval,
+                watch,
+                daemon,
+                pid_file,
+                alert_on,
+                metrics_addr,
+            )
+            .await
         }
         Commands::Audit {
             target,
+            discover,
+            yes,
             include_proxy,
             duration,
             comprehensive,
@@ -366,6 +423,8 @@ async fn main() -> Result<()> {
         } => {
             cli::audit::execute(
                 target,
+                discover,
+                yes,
                 include_proxy,
                 duration,
                 comprehensive,
@@ -377,7 +436,19 @@ async fn main() -> Result<()> {
             )
             .await
         }
+        Commands::Serve {
+            listen,
+            max_jobs,
+            grpc_listen,
+        } => cli::serve::execute(listen, max_jobs, grpc_listen).await,
+        Commands::McpServe => cli::mcp_serve::execute().await,
         Commands::Init { config_path } => cli::init::execute(config_path).await,
+        Commands::Triage {
+            fingerprint,
+            state,
+            reason,
+            project,
+        } => cli::triage::execute(fingerprint, state, reason, project).await,
         Commands::Whitelist { command } => match command {
             WhitelistCommands::Add {
                 item_type,
@@ -393,6 +464,34 @@ async fn main() -> Result<()> {
             RulesCommands::Validate { path } => cli::rules::validate(path).await,
             RulesCommands::List => cli::rules::list().await,
             RulesCommands::Test { rules, traffic } => cli::rules::test(rules, traffic).await,
+            RulesCommands::Update { url } => cli::rules::update(url).await,
+            RulesCommands::ExportBundle { path } => cli::rules::export_bundle(path).await,
+            RulesCommands::ImportBundle { path } => cli::rules::import_bundle(path).await,
         },
-    }
+        Commands::Schedule { command } => match command {
+            ScheduleCommands::Add {
+                cron,
+                target,
+                notify,
+                min_severity,
+            } => cli::schedule::add(cron, target, notify, min_severity).await,
+            ScheduleCommands::List => cli::schedule::list().await,
+            ScheduleCommands::Remove { id } => cli::schedule::remove(id).await,
+            ScheduleCommands::Run => cli::schedule::run().await,
+        },
+        Commands::Gc {
+            max_age_days,
+            max_size_mb,
+            audit_log,
+        } => cli::gc::execute(max_age_days, max_size_mb, audit_log).await,
+        Commands::Badge { input, output } => cli::badge::execute(input, output).await,
+        Commands::Bench {
+            target,
+            top,
+            config,
+        } => cli::bench::execute(target, top, config).await,
+    };
+
+    mcp_sentinel::utils::telemetry::shutdown();
+    result
 }
@@ -1,9 +1,14 @@
 //! Configuration data model
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::project_config::{PathOverrides, SeverityOverride};
 use super::vulnerability::Severity;
+use crate::detectors::{
+    bundle::RuleBundle, gitleaks::GitleaksRules, ioc::IocSet, yara::YaraRules, DetectorKind,
+};
 
 /// LLM provider configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -29,9 +34,43 @@ pub struct ScanConfig {
     /// Scanning mode
     pub mode: ScanMode,
 
-    /// Minimum severity to report
+    /// Minimum severity to report; severities set by a severity override
+    /// are reported regardless
     pub min_severity: Severity,
 
+    /// Minimum confidence (0.0 to 1.0) to report
+    #[serde(default)]
+    pub min_confidence: f32,
+
+    /// Detectors to run on each file
+    #[serde(default = "default_detectors")]
+    pub detectors: Vec<DetectorKind>,
+
+    /// Severity remapping from `sentinel.toml`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severity_overrides: Vec<SeverityOverride>,
+
+    /// Per-path policies from `sentinel.toml`
+    #[serde(default, skip_serializing_if = "PathOverrides::is_empty")]
+    pub path_overrides: PathOverrides,
+
+    /// Rules and allowlists imported from a gitleaks config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitleaks: Option<GitleaksRules>,
+
+    /// Compiled YARA rules for the `yara` detector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yara: Option<YaraRules>,
+
+    /// Threat-intel indicators for the `ioc` detector
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iocs: Option<IocSet>,
+
+    /// Rule bundle installed by `rules update`, used when newer than the
+    /// built-in one
+    #[serde(skip)]
+    pub rule_bundle: Option<RuleBundle>,
+
     /// LLM configuration for AI analysis
     #[serde(skip_serializing_if = "Option::is_none")]
     pub llm: Option<LlmConfig>,
@@ -50,6 +89,11 @@ pub struct ScanConfig {
 
     /// Number of parallel workers
     pub parallel_workers: usize,
+
+    /// Files read concurrently, ahead of the detectors, so slow disks and
+    /// network filesystems don't serialize the scan on I/O
+    #[serde(default = "default_io_concurrency")]
+    pub io_concurrency: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -64,6 +108,14 @@ impl Default for ScanConfig {
         Self {
             mode: ScanMode::Quick,
             min_severity: Severity::Low,
+            min_confidence: 0.0,
+            detectors: default_detectors(),
+            severity_overrides: Vec::new(),
+            path_overrides: PathOverrides::default(),
+            gitleaks: None,
+            yara: None,
+            iocs: None,
+            rule_bundle: None,
             llm: None,
             enable_tree_sitter: true,
             enable_semgrep: false, // External dependency, off by default
@@ -78,10 +130,19 @@ impl Default for ScanConfig {
                 "*.map".to_string(),
             ],
             parallel_workers: num_cpus::get(),
+            io_concurrency: default_io_concurrency(),
         }
     }
 }
 
+fn default_io_concurrency() -> usize {
+    16
+}
+
+fn default_detectors() -> Vec<DetectorKind> {
+    DetectorKind::ALL.to_vec()
+}
+
 /// Main application configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -102,6 +163,88 @@ pub struct AppConfig {
 
     /// Cache directory
     pub cache_path: PathBuf,
+
+    /// Scan history database
+    #[serde(default = "default_history_path")]
+    pub history_path: PathBuf,
+
+    /// Rule bundles installed by `rules update`
+    #[serde(default = "default_rules_path")]
+    pub rules_path: PathBuf,
+
+    /// Signed allowlist of servers approved by `scan --approve`
+    #[serde(default = "default_allowlist_path")]
+    pub allowlist_path: PathBuf,
+
+    /// Key that signs the allowlist (public half next to it, as `.pub`)
+    #[serde(default = "default_allowlist_key_path")]
+    pub allowlist_key_path: PathBuf,
+
+    /// Messages the proxy blocked for carrying credentials or personal data
+    #[serde(default = "default_audit_log_path")]
+    pub audit_log_path: PathBuf,
+
+    /// Scans run by `schedule run`
+    #[serde(default = "default_schedules_path")]
+    pub schedules_path: PathBuf,
+
+    /// How much scan history and audit log to keep
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+/// Retention of what accumulates while the daemon and proxy run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Scan history database
+    pub history: RetentionPolicy,
+
+    /// Proxy audit logs
+    pub audit_log: RetentionPolicy,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            history: RetentionPolicy {
+                max_age_days: Some(180),
+                max_size_mb: Some(500),
+            },
+            audit_log: RetentionPolicy {
+                max_age_days: Some(90),
+                max_size_mb: Some(100),
+            },
+        }
+    }
+}
+
+/// Limits past which the oldest records are pruned (none keeps everything)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Records from before this time are pruned
+    pub fn cutoff(&self) -> Option<DateTime<Utc>> {
+        self.max_age_days
+            .map(|days| Utc::now() - chrono::Duration::days(days.into()))
+    }
+
+    /// Records past this many bytes are pruned, oldest first
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_size_mb.map(|mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    /// Whether nothing is ever pruned
+    pub fn keeps_everything(&self) -> bool {
+        self.max_age_days.is_none() && self.max_size_mb.is_none()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -139,10 +282,53 @@ impl Default for AppConfig {
             },
             whitelist_path: config_dir.join("whitelist.json"),
             cache_path: config_dir.join("cache"),
+            history_path: config_dir.join("history"),
+            rules_path: config_dir.join("rules"),
+            allowlist_path: config_dir.join("allowlist.json"),
+            allowlist_key_path: config_dir.join("allowlist.key"),
+            audit_log_path: config_dir.join("audit.jsonl"),
+            schedules_path: config_dir.join("schedules.json"),
+            retention: RetentionConfig::default(),
         }
     }
 }
 
+impl AppConfig {
+    /// Rule bundle installed by `rules update` (signature at `<path>.sig`)
+    pub fn rule_bundle_path(&self) -> PathBuf {
+        self.rules_path.join("bundle.json")
+    }
+
+    /// Cached IOC feed downloads
+    pub fn feeds_cache_path(&self) -> PathBuf {
+        self.cache_path.join("feeds")
+    }
+}
+
+fn default_history_path() -> PathBuf {
+    AppConfig::default().history_path
+}
+
+fn default_rules_path() -> PathBuf {
+    AppConfig::default().rules_path
+}
+
+fn default_allowlist_path() -> PathBuf {
+    AppConfig::default().allowlist_path
+}
+
+fn default_allowlist_key_path() -> PathBuf {
+    AppConfig::default().allowlist_key_path
+}
+
+fn default_audit_log_path() -> PathBuf {
+    AppConfig::default().audit_log_path
+}
+
+fn default_schedules_path() -> PathBuf {
+    AppConfig::default().schedules_path
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -162,4 +348,16 @@ mod tests {
         assert_eq!(config.scan.default_mode, ScanMode::Quick);
         assert_eq!(config.proxy.port, 8080);
     }
+
+    #[test]
+    fn test_retention_policy() {
+        let keep_all = RetentionPolicy::default();
+        assert!(keep_all.keeps_everything());
+        assert!(keep_all.cutoff().is_none());
+
+        let policy: RetentionPolicy = serde_json::from_str(r#"{"max_size_mb": 2}"#).unwrap();
+        assert_eq!(policy.max_bytes(), Some(2 * 1024 * 1024));
+        assert!(policy.cutoff().is_none());
+        assert!(!policy.keeps_everything());
+    }
 }
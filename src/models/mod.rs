@@ -2,6 +2,7 @@
 
 pub mod config;
 pub mod mcp_protocol;
+pub mod project_config;
 pub mod scan_result;
 pub mod vulnerability;
 
@@ -0,0 +1,621 @@
+//! Per-project configuration (`sentinel.toml`)
+//!
+//! Lives next to the scanned code and is committed with it, so every developer
+//! and CI job scans the project the same way.
+//!
+//! # Example
+//!
+//! ```toml
+//! # Only our own fixtures use shelve, and they never see untrusted input
+//! [[severity_overrides]]
+//! rule = "deserialization/python-shelve-usage"
+//! severity = "low"
+//!
+//! # Traversal literals in docs are examples, not code
+//! [[severity_overrides]]
+//! detector = "path_traversal"
+//! paths = ["docs/**"]
+//! severity = "info"
+//!
+//! # Tests hold fake credentials; nothing there is worse than medium
+//! [[override]]
+//! paths = ["tests/**"]
+//! disable = ["secrets"]
+//! max_severity = "medium"
+//!
+//! # Reuse the rules and allowlists we already maintain for gitleaks
+//! # (defaults to .gitleaks.toml in the scan target when present)
+//! [secrets]
+//! gitleaks_config = "ci/gitleaks.toml"
+//!
+//! # Hunt for known droppers (needs the `yara` feature)
+//! [yara]
+//! rules = ["security/yara/"]
+//!
+//! # Flag known-malicious domains, IPs, and file hashes
+//! [ioc]
+//! lists = ["security/iocs.txt"]
+//! feeds = ["https://intel.example.com/mcp-c2.txt"]
+//!
+//! # File Jira issues for new findings with `scan --jira`
+//! [jira]
+//! url = "https://example.atlassian.net"
+//! project = "SEC"
+//! target = "github-server"
+//! ```
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use super::config::ScanConfig;
+use super::vulnerability::{Severity, Vulnerability};
+use crate::detectors::gitleaks::{GitleaksRules, GITLEAKS_CONFIG_FILE};
+use crate::detectors::ioc::IocSet;
+use crate::detectors::yara::YaraRules;
+use crate::detectors::DetectorKind;
+
+/// File name looked up in the scan target
+pub const PROJECT_CONFIG_FILE: &str = "sentinel.toml";
+
+/// Contents of `sentinel.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Severity remapping, applied in order (later entries win)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severity_overrides: Vec<SeverityOverride>,
+
+    /// Policies for parts of the repository, as `[[override]]` tables
+    #[serde(default, rename = "override", skip_serializing_if = "Vec::is_empty")]
+    pub path_overrides: Vec<PathOverride>,
+
+    /// Secrets detector settings
+    #[serde(default, skip_serializing_if = "SecretsConfig::is_empty")]
+    pub secrets: SecretsConfig,
+
+    /// YARA detector settings
+    #[serde(default, skip_serializing_if = "YaraConfig::is_empty")]
+    pub yara: YaraConfig,
+
+    /// IOC detector settings
+    #[serde(default, skip_serializing_if = "IocConfig::is_empty")]
+    pub ioc: IocConfig,
+
+    /// Where `scan --jira` files issues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira: Option<JiraConfig>,
+}
+
+/// `[secrets]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SecretsConfig {
+    /// gitleaks.toml to import rules and allowlists from, relative to
+    /// `sentinel.toml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitleaks_config: Option<PathBuf>,
+}
+
+impl SecretsConfig {
+    fn is_empty(&self) -> bool {
+        self.gitleaks_config.is_none()
+    }
+}
+
+/// `[yara]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YaraConfig {
+    /// Rule files or directories of `.yar`/`.yara` files, relative to
+    /// `sentinel.toml`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<PathBuf>,
+}
+
+impl YaraConfig {
+    fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// `[ioc]` table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IocConfig {
+    /// IOC list files, relative to `sentinel.toml`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lists: Vec<PathBuf>,
+
+    /// Feed URLs, downloaded and cached by `scan` before the lists are loaded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feeds: Vec<String>,
+}
+
+impl IocConfig {
+    fn is_empty(&self) -> bool {
+        self.lists.is_empty() && self.feeds.is_empty()
+    }
+}
+
+/// `[jira]` table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JiraConfig {
+    /// Base URL of the Jira site
+    pub url: String,
+
+    /// Project key
+    pub project: String,
+
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+
+    /// Labels added to every issue
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+
+    /// Name that tells this target's issues apart from those of other
+    /// targets filing into the same project (default: the name of the
+    /// scanned directory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Findings below this severity get no issue
+    #[serde(default = "default_jira_severity")]
+    pub min_severity: Severity,
+}
+
+fn default_issue_type() -> String {
+    "Bug".to_string()
+}
+
+fn default_jira_severity() -> Severity {
+    Severity::High
+}
+
+impl ProjectConfig {
+    /// Load a project config file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid project config {}", path.display()))?;
+        config.validate()?;
+        if let (Some(gitleaks), Some(dir)) = (&mut config.secrets.gitleaks_config, path.parent()) {
+            *gitleaks = dir.join(&*gitleaks);
+        }
+        if let Some(dir) = path.parent() {
+            for rules in &mut config.yara.rules {
+                *rules = dir.join(&*rules);
+            }
+            for list in &mut config.ioc.lists {
+                *list = dir.join(&*list);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Load `sentinel.toml` from the scan target, if present
+    ///
+    /// A `.gitleaks.toml` in the target is picked up as well unless the
+    /// project config names another one.
+    pub fn discover(target: &Path) -> Result<Option<Self>> {
+        let path = target.join(PROJECT_CONFIG_FILE);
+        let mut config = if path.is_file() {
+            Some(Self::load(&path)?)
+        } else {
+            None
+        };
+
+        let gitleaks = target.join(GITLEAKS_CONFIG_FILE);
+        if gitleaks.is_file() {
+            let secrets = &mut config.get_or_insert_with(Self::default).secrets;
+            secrets.gitleaks_config.get_or_insert(gitleaks);
+        }
+        Ok(config)
+    }
+
+    /// Apply this project's settings on top of `config`
+    ///
+    /// Fails if the referenced gitleaks config, YARA rules, or IOC lists
+    /// cannot be loaded.
+    pub fn apply_to(&self, config: &mut ScanConfig) -> Result<()> {
+        config
+            .severity_overrides
+            .extend(self.severity_overrides.iter().cloned());
+        config
+            .path_overrides
+            .extend(self.path_overrides.iter().cloned());
+        if let Some(path) = &self.secrets.gitleaks_config {
+            config.gitleaks = Some(GitleaksRules::load(path)?);
+        }
+        if !self.yara.rules.is_empty() {
+            config.yara = Some(YaraRules::load(&self.yara.rules)?);
+        }
+        if !self.ioc.lists.is_empty() {
+            config.iocs = Some(IocSet::load(&self.ioc.lists)?);
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<()> {
+        for (i, rule) in self.severity_overrides.iter().enumerate() {
+            anyhow::ensure!(
+                rule.rule.is_some() || rule.detector.is_some() || !rule.paths.is_empty(),
+                "severity_overrides[{}] must set at least one of rule, detector, or paths",
+                i
+            );
+            for pattern in &rule.paths {
+                Glob::new(pattern).with_context(|| {
+                    format!("severity_overrides[{}]: invalid glob '{}'", i, pattern)
+                })?;
+            }
+        }
+        for (i, rule) in self.path_overrides.iter().enumerate() {
+            anyhow::ensure!(!rule.paths.is_empty(), "override[{}] must set paths", i);
+            anyhow::ensure!(
+                !rule.disable.is_empty() || rule.max_severity.is_some(),
+                "override[{}] must set disable or max_severity",
+                i
+            );
+            for pattern in &rule.paths {
+                Glob::new(pattern)
+                    .with_context(|| format!("override[{}]: invalid glob '{}'", i, pattern))?;
+            }
+        }
+        if let Some(jira) = &self.jira {
+            for label in &jira.labels {
+                anyhow::ensure!(
+                    !label.is_empty() && !label.contains(char::is_whitespace),
+                    "jira: invalid label '{}' (labels cannot contain spaces)",
+                    label
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Remap the severity of findings matching every criterion that is set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SeverityOverride {
+    /// Rule ID as shown in reports (e.g. "secrets/generic-api-key")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+
+    /// Detector ID (e.g. "path_traversal")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detector: Option<DetectorKind>,
+
+    /// Globs matched against the file path or any trailing part of it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+
+    /// Severity to report instead
+    pub severity: Severity,
+}
+
+/// Apply `overrides` to the findings of one file
+///
+/// Globs are compiled once per call; invalid ones are ignored here since
+/// [`ProjectConfig::load`] already rejects them. Returns, for each finding,
+/// whether an override set its severity.
+pub fn apply_severity_overrides(
+    overrides: &[SeverityOverride],
+    file_path: &str,
+    vulnerabilities: &mut [Vulnerability],
+) -> Vec<bool> {
+    let mut overridden = vec![false; vulnerabilities.len()];
+    if overrides.is_empty() || vulnerabilities.is_empty() {
+        return overridden;
+    }
+
+    let suffixes = path_suffixes(file_path);
+    let path_matches: Vec<bool> = overrides
+        .iter()
+        .map(|o| {
+            o.paths.is_empty()
+                || build_globset(&o.paths)
+                    .is_some_and(|set| suffixes.iter().any(|p| set.is_match(p)))
+        })
+        .collect();
+
+    for (vuln, overridden) in vulnerabilities.iter_mut().zip(&mut overridden) {
+        for (rule, path_match) in overrides.iter().zip(&path_matches) {
+            let rule_match = rule
+                .rule
+                .as_ref()
+                .is_none_or(|r| vuln.rule_id.as_ref() == Some(r));
+            let detector_match = rule.detector.is_none_or(|d| vuln.detector == Some(d));
+            if *path_match && rule_match && detector_match {
+                vuln.severity = rule.severity;
+                *overridden = true;
+            }
+        }
+    }
+    overridden
+}
+
+/// Policy for part of the repository (`[[override]]` table)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathOverride {
+    /// Globs matched against the file path or any trailing part of it
+    pub paths: Vec<String>,
+
+    /// Detector IDs not run on matching files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disable: Vec<DetectorKind>,
+
+    /// Findings in matching files are reported at most this severe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_severity: Option<Severity>,
+}
+
+/// [`PathOverride`]s with their globs compiled, as [`ScanConfig`] holds them
+///
+/// Policies are looked up for every scanned file and every server-level
+/// finding, so globs are compiled once when overrides are added. Serializes
+/// as the plain list of overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<PathOverride>", into = "Vec<PathOverride>")]
+pub struct PathOverrides {
+    overrides: Vec<PathOverride>,
+    /// Compiled `paths` of each override; empty (matching nothing) for
+    /// invalid globs, which [`ProjectConfig::load`] already rejects
+    globs: Vec<GlobSet>,
+}
+
+impl PathOverrides {
+    /// Add `overrides` after the existing ones
+    pub fn extend(&mut self, overrides: impl IntoIterator<Item = PathOverride>) {
+        for rule in overrides {
+            self.globs
+                .push(build_globset(&rule.paths).unwrap_or_else(GlobSet::empty));
+            self.overrides.push(rule);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
+
+impl From<Vec<PathOverride>> for PathOverrides {
+    fn from(overrides: Vec<PathOverride>) -> Self {
+        let mut compiled = Self::default();
+        compiled.extend(overrides);
+        compiled
+    }
+}
+
+impl From<PathOverrides> for Vec<PathOverride> {
+    fn from(compiled: PathOverrides) -> Self {
+        compiled.overrides
+    }
+}
+
+impl PartialEq for PathOverrides {
+    fn eq(&self, other: &Self) -> bool {
+        self.overrides == other.overrides
+    }
+}
+
+/// What the [`PathOverride`]s matching one file change for it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathPolicy {
+    /// Detectors not run on the file
+    pub disabled: Vec<DetectorKind>,
+
+    /// Highest severity reported for the file
+    pub max_severity: Option<Severity>,
+}
+
+impl PathPolicy {
+    /// Combine the `overrides` matching `file_path`
+    ///
+    /// Disabled detectors add up; for `max_severity`, later entries win,
+    /// like severity overrides.
+    pub fn for_file(overrides: &PathOverrides, file_path: &str) -> Self {
+        let mut policy = Self::default();
+        if overrides.is_empty() {
+            return policy;
+        }
+
+        let suffixes = path_suffixes(file_path);
+        for (rule, globs) in overrides.overrides.iter().zip(&overrides.globs) {
+            if !suffixes.iter().any(|p| globs.is_match(p)) {
+                continue;
+            }
+            policy.disabled.extend(&rule.disable);
+            if rule.max_severity.is_some() {
+                policy.max_severity = rule.max_severity;
+            }
+        }
+        policy
+    }
+
+    /// Whether `detector` runs on the file
+    pub fn enables(&self, detector: DetectorKind) -> bool {
+        !self.disabled.contains(&detector)
+    }
+
+    /// Lower findings above `max_severity` to it
+    pub fn cap(&self, vulnerabilities: &mut [Vulnerability]) {
+        if let Some(max) = self.max_severity {
+            for vuln in vulnerabilities {
+                vuln.severity = vuln.severity.min(max);
+            }
+        }
+    }
+}
+
+fn build_globset(patterns: &[String]) -> Option<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).ok()?);
+    }
+    builder.build().ok()
+}
+
+/// `a/b/c.py` -> [`a/b/c.py`, `b/c.py`, `c.py`], so `docs/**` matches
+/// regardless of where the scan root is
+fn path_suffixes(file_path: &str) -> Vec<PathBuf> {
+    let components: Vec<Component> = Path::new(file_path)
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    (0..components.len())
+        .map(|i| components[i..].iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::VulnerabilityType;
+
+    fn finding(detector: DetectorKind, rule_id: &str) -> Vulnerability {
+        let mut vuln = Vulnerability::new(
+            "T-001",
+            VulnerabilityType::PathTraversal,
+            Severity::High,
+            "Test",
+            "Desc",
+        )
+        .with_rule_id(rule_id);
+        vuln.detector = Some(detector);
+        vuln
+    }
+
+    #[test]
+    fn test_parse_and_apply_overrides() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+            [[severity_overrides]]
+            rule = "deserialization/python-shelve-usage"
+            severity = "low"
+
+            [[severity_overrides]]
+            detector = "path_traversal"
+            paths = ["docs/**"]
+            severity = "info"
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        let mut vulns = vec![
+            finding(DetectorKind::PathTraversal, "path_traversal"),
+            finding(
+                DetectorKind::Deserialization,
+                "deserialization/python-shelve-usage",
+            ),
+        ];
+        apply_severity_overrides(
+            &config.severity_overrides,
+            "./server/docs/guide.md",
+            &mut vulns,
+        );
+        assert_eq!(vulns[0].severity, Severity::Info);
+        assert_eq!(vulns[1].severity, Severity::Low);
+
+        let mut vulns = vec![finding(DetectorKind::PathTraversal, "path_traversal")];
+        apply_severity_overrides(
+            &config.severity_overrides,
+            "./server/src/files.py",
+            &mut vulns,
+        );
+        assert_eq!(vulns[0].severity, Severity::High);
+    }
+
+    #[test]
+    fn test_override_without_criteria_rejected() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+            [[severity_overrides]]
+            severity = "low"
+            "#,
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_path_overrides() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+            [[override]]
+            paths = ["tests/**"]
+            disable = ["secrets"]
+            max_severity = "medium"
+
+            [[override]]
+            paths = ["tests/e2e/**"]
+            max_severity = "low"
+            "#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+        let overrides = PathOverrides::from(config.path_overrides);
+
+        let policy = PathPolicy::for_file(&overrides, "./server/tests/e2e/run.py");
+        assert!(!policy.enables(DetectorKind::Secrets));
+        assert!(policy.enables(DetectorKind::PathTraversal));
+        assert_eq!(policy.max_severity, Some(Severity::Low));
+
+        let policy = PathPolicy::for_file(&overrides, "./server/tests/unit.py");
+        let mut vulns = vec![finding(DetectorKind::PathTraversal, "path_traversal")];
+        policy.cap(&mut vulns);
+        assert_eq!(vulns[0].severity, Severity::Medium);
+
+        let policy = PathPolicy::for_file(&overrides, "./server/src/files.py");
+        assert_eq!(policy, PathPolicy::default());
+
+        // Globs are compiled again when read back
+        let json = serde_json::to_string(&overrides).unwrap();
+        let restored: PathOverrides = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, overrides);
+        let policy = PathPolicy::for_file(&restored, "tests/unit.py");
+        assert!(!policy.enables(DetectorKind::Secrets));
+
+        let empty: ProjectConfig =
+            toml::from_str("[[override]]\npaths = [\"tests/**\"]\n").unwrap();
+        assert!(empty.validate().is_err());
+    }
+
+    #[test]
+    fn test_discover_gitleaks_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(GITLEAKS_CONFIG_FILE),
+            "[[rules]]\nid = \"acme-token\"\nregex = 'acme_[a-z0-9]{16}'\n",
+        )
+        .unwrap();
+
+        let project = ProjectConfig::discover(dir.path()).unwrap().unwrap();
+        let mut config = ScanConfig::default();
+        project.apply_to(&mut config).unwrap();
+        assert_eq!(config.gitleaks.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_ioc_lists_relative_to_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("security")).unwrap();
+        std::fs::write(dir.path().join("security/iocs.txt"), "evil.example\n").unwrap();
+        std::fs::write(
+            dir.path().join(PROJECT_CONFIG_FILE),
+            "[ioc]\nlists = [\"security/iocs.txt\"]\n",
+        )
+        .unwrap();
+
+        let project = ProjectConfig::discover(dir.path()).unwrap().unwrap();
+        let mut config = ScanConfig::default();
+        project.apply_to(&mut config).unwrap();
+        assert_eq!(config.iocs.unwrap().len(), 1);
+    }
+}
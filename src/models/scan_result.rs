@@ -2,9 +2,15 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
 use uuid::Uuid;
 
 use super::vulnerability::{Severity, Vulnerability};
+use crate::detectors::capabilities::CapabilityUse;
+use crate::detectors::egress::Endpoint;
+use crate::detectors::toxic_flows::ToolProfile;
+use crate::detectors::DetectorKind;
 
 /// Summary statistics for scan results
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,12 +20,20 @@ pub struct ScanSummary {
     pub high: usize,
     pub medium: usize,
     pub low: usize,
+    #[serde(default)]
+    pub info: usize,
+    /// Findings with a triage decision; not included in the counts above
+    #[serde(default)]
+    pub triaged: usize,
     pub risk_score: u8, // 0-100
 }
 
 impl ScanSummary {
     /// Create a summary from a list of vulnerabilities
-    pub fn from_vulnerabilities(vulnerabilities: &[Vulnerability]) -> Self {
+    ///
+    /// Only open findings count towards the severity counts and risk score.
+    pub fn from_vulnerabilities(all: &[Vulnerability]) -> Self {
+        let vulnerabilities: Vec<&Vulnerability> = all.iter().filter(|v| v.is_open()).collect();
         let critical = vulnerabilities
             .iter()
             .filter(|v| v.severity == Severity::Critical)
@@ -36,11 +50,19 @@ impl ScanSummary {
             .iter()
             .filter(|v| v.severity == Severity::Low)
             .count();
+        let info = vulnerabilities
+            .iter()
+            .filter(|v| v.severity == Severity::Info)
+            .count();
 
         // Risk score calculation: weighted by severity
-        // Critical: 40 points, High: 20 points, Medium: 5 points, Low: 1 point
+        // Critical: 40 points, High: 20 points, Medium: 5 points, Low: 1 point, Info: 0
         // Capped at 100
-        let risk_score = ((critical * 40 + high * 20 + medium * 5 + low).min(100)) as u8;
+        let points: usize = vulnerabilities
+            .iter()
+            .map(|v| v.severity.risk_weight())
+            .sum();
+        let risk_score = points.min(100) as u8;
 
         Self {
             total_issues: vulnerabilities.len(),
@@ -48,11 +70,86 @@ impl ScanSummary {
             high,
             medium,
             low,
+            info,
+            triaged: all.len() - vulnerabilities.len(),
             risk_score,
         }
     }
 }
 
+/// Files listed in the hotspots section of the reports
+pub const HOTSPOTS: usize = 5;
+
+/// Directory levels below the target shown in the reports' heatmap
+pub const HEATMAP_DEPTH: usize = 4;
+
+/// A file's share of the scan's risk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileRisk {
+    pub file: String,
+    /// Sum of [`Vulnerability::risk`] over the file's open findings
+    /// (uncapped, unlike the scan's risk score)
+    pub score: f32,
+    /// Open findings in the file
+    pub findings: usize,
+    /// Most severe of them
+    pub worst: Severity,
+}
+
+/// Risk of the open findings under a directory, with its subdirectories
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DirectoryRisk {
+    /// Path relative to the scan target, `/`-separated; empty for the target
+    pub path: String,
+    /// Sum of [`Vulnerability::risk`] over the findings anywhere below
+    pub score: f32,
+    /// Open findings anywhere below
+    pub findings: usize,
+    /// Subdirectories with findings, riskiest first
+    pub children: Vec<DirectoryRisk>,
+}
+
+impl DirectoryRisk {
+    fn new(path: String) -> Self {
+        Self {
+            path,
+            score: 0.0,
+            findings: 0,
+            children: Vec::new(),
+        }
+    }
+
+    /// Last component of the path, `.` for the target
+    pub fn name(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((_, name)) => name,
+            None if self.path.is_empty() => ".",
+            None => &self.path,
+        }
+    }
+
+    fn sort(&mut self) {
+        self.children
+            .sort_by(|a, b| b.score.total_cmp(&a.score).then(a.path.cmp(&b.path)));
+        for child in &mut self.children {
+            child.sort();
+        }
+    }
+}
+
+/// One of several MCP servers in the scanned repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerReport {
+    /// Name from the server's manifest, else its directory's
+    pub name: String,
+    /// Directory relative to the scan target, `/`-separated; empty for the
+    /// target
+    pub root: String,
+    /// Counts and risk score of the findings under `root` that belong to no
+    /// server nested deeper
+    pub summary: ScanSummary,
+}
+
 /// Metadata about the scan
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScanMetadata {
@@ -62,6 +159,60 @@ pub struct ScanMetadata {
     pub llm_provider: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub llm_model: Option<String>,
+    /// Set when the scan was cancelled before every file was scanned
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub incomplete: bool,
+    /// Where the scan spent its time, and what it read or skipped
+    #[serde(default)]
+    pub stats: ScanStats,
+}
+
+/// Performance breakdown of a scan, printed by `scan --stats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanStats {
+    /// Time spent discovering files
+    pub discovery_ms: u64,
+    /// Time spent in each detector, in microseconds
+    pub detector_us: BTreeMap<DetectorKind, u64>,
+    pub files_scanned: usize,
+    pub bytes_scanned: u64,
+    /// Discovered files that were not scanned, by reason
+    pub files_skipped: BTreeMap<SkipReason, usize>,
+}
+
+/// Why a discovered file was not scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Larger than `max_file_size`
+    TooLarge,
+    /// Binary, not UTF-8, or not readable
+    Unreadable,
+    /// Minified bundle without a source map
+    Minified,
+}
+
+impl SkipReason {
+    /// Human-readable label for reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkipReason::TooLarge => "too large",
+            SkipReason::Unreadable => "unreadable",
+            SkipReason::Minified => "minified without source map",
+        }
+    }
+}
+
+impl ScanStats {
+    /// Count a file skipped for `reason`
+    pub fn skip(&mut self, reason: SkipReason) {
+        *self.files_skipped.entry(reason).or_default() += 1;
+    }
+
+    /// Total time spent in detectors, in milliseconds
+    pub fn detectors_ms(&self) -> u64 {
+        self.detector_us.values().sum::<u64>() / 1000
+    }
 }
 
 /// Complete scan result
@@ -88,6 +239,23 @@ pub struct ScanResult {
     /// List of detected vulnerabilities
     pub vulnerabilities: Vec<Vulnerability>,
 
+    /// Hardcoded network endpoints found in the source (egress inventory)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub egress: Vec<Endpoint>,
+
+    /// Tool registrations found in the source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ToolProfile>,
+
+    /// Imports and API calls that give the server access to its host
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<CapabilityUse>,
+
+    /// Servers of a monorepo, each with its own summary; empty unless the
+    /// target holds more than one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<ServerReport>,
+
     /// Scan metadata
     pub metadata: ScanMetadata,
 }
@@ -107,33 +275,233 @@ impl ScanResult {
                 high: 0,
                 medium: 0,
                 low: 0,
+                info: 0,
+                triaged: 0,
                 risk_score: 0,
             },
             vulnerabilities: Vec::new(),
+            egress: Vec::new(),
+            tools: Vec::new(),
+            capabilities: Vec::new(),
+            servers: Vec::new(),
             metadata: ScanMetadata {
                 scan_duration_ms: 0,
                 engines_used: Vec::new(),
                 llm_provider: None,
                 llm_model: None,
+                incomplete: false,
+                stats: ScanStats::default(),
             },
         }
     }
 
-    /// Add a vulnerability to the result
+    /// Add a vulnerability to the result, fingerprinting it relative to the target
     pub fn add_vulnerability(&mut self, vuln: Vulnerability) {
-        self.vulnerabilities.push(vuln);
-        self.update_summary();
+        self.add_vulnerabilities(vec![vuln]);
     }
 
     /// Add multiple vulnerabilities
+    ///
+    /// Findings without a fingerprint get one, and an ID derived from it
+    /// (see [`Vulnerability::identify`]).
     pub fn add_vulnerabilities(&mut self, vulns: Vec<Vulnerability>) {
-        self.vulnerabilities.extend(vulns);
+        let root = Path::new(&self.target);
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for fingerprint in self
+            .vulnerabilities
+            .iter()
+            .filter_map(|v| v.fingerprint.clone())
+        {
+            *seen.entry(fingerprint).or_default() += 1;
+        }
+        for mut vuln in vulns {
+            if vuln.fingerprint.is_none() {
+                vuln.identify(root, &mut seen);
+            }
+            self.vulnerabilities.push(vuln);
+        }
         self.update_summary();
     }
 
+    /// Findings that haven't been triaged
+    pub fn open_vulnerabilities(&self) -> impl Iterator<Item = &Vulnerability> {
+        self.vulnerabilities.iter().filter(|v| v.is_open())
+    }
+
+    /// Number of open findings `scan --fix` can patch
+    pub fn fixable_count(&self) -> usize {
+        self.open_vulnerabilities()
+            .filter(|v| v.auto_fixable)
+            .count()
+    }
+
+    /// Findings with a triage decision
+    pub fn triaged_vulnerabilities(&self) -> impl Iterator<Item = &Vulnerability> {
+        self.vulnerabilities.iter().filter(|v| !v.is_open())
+    }
+
+    /// Open findings that `previous` did not have, by fingerprint
+    pub fn new_findings<'a>(&'a self, previous: &ScanResult) -> Vec<&'a Vulnerability> {
+        let known: std::collections::HashSet<&str> = previous
+            .vulnerabilities
+            .iter()
+            .filter_map(|v| v.fingerprint.as_deref())
+            .collect();
+        self.open_vulnerabilities()
+            .filter(|v| {
+                !v.fingerprint
+                    .as_deref()
+                    .is_some_and(|fp| known.contains(fp))
+            })
+            .collect()
+    }
+
+    /// The `n` files whose open findings carry the most risk, riskiest first
+    ///
+    /// Findings without a location are left out.
+    pub fn hotspots(&self, n: usize) -> Vec<FileRisk> {
+        let mut files: BTreeMap<&str, FileRisk> = BTreeMap::new();
+        for vuln in self.open_vulnerabilities() {
+            let Some(location) = &vuln.location else {
+                continue;
+            };
+            let risk = files.entry(&location.file).or_insert_with(|| FileRisk {
+                file: location.file.clone(),
+                score: 0.0,
+                findings: 0,
+                worst: vuln.severity,
+            });
+            risk.score += vuln.risk();
+            risk.findings += 1;
+            risk.worst = risk.worst.max(vuln.severity);
+        }
+
+        // Ties keep path order
+        let mut files: Vec<FileRisk> = files.into_values().collect();
+        files.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.worst.cmp(&a.worst))
+                .then(b.findings.cmp(&a.findings))
+        });
+        files.truncate(n);
+        files
+    }
+
+    /// Open findings rolled up by directory, relative to the target
+    ///
+    /// Findings without a location are left out.
+    pub fn directory_risk(&self) -> DirectoryRisk {
+        let root = Path::new(&self.target);
+        let mut tree = DirectoryRisk::new(String::new());
+        for vuln in self.open_vulnerabilities() {
+            let Some(location) = &vuln.location else {
+                continue;
+            };
+            let file = Path::new(&location.file);
+            let directories = file
+                .strip_prefix(root)
+                .unwrap_or(file)
+                .parent()
+                .into_iter()
+                .flat_map(|dir| dir.components())
+                .filter_map(|c| match c {
+                    Component::Normal(name) => Some(name.to_string_lossy()),
+                    _ => None,
+                });
+
+            let risk = vuln.risk();
+            let mut node = &mut tree;
+            node.score += risk;
+            node.findings += 1;
+            for name in directories {
+                let path = match node.path.as_str() {
+                    "" => name.into_owned(),
+                    parent => format!("{}/{}", parent, name),
+                };
+                let index = match node.children.iter().position(|c| c.path == path) {
+                    Some(index) => index,
+                    None => {
+                        node.children.push(DirectoryRisk::new(path));
+                        node.children.len() - 1
+                    }
+                };
+                node = &mut node.children[index];
+                node.score += risk;
+                node.findings += 1;
+            }
+        }
+        tree.sort();
+        tree
+    }
+
+    /// Report findings per server as well, for the servers at `dirs`
+    ///
+    /// `dirs` are paths as scanned, named; a directory listed twice keeps
+    /// its first name.
+    pub fn set_servers(&mut self, dirs: impl IntoIterator<Item = (String, PathBuf)>) {
+        self.servers.clear();
+        for (name, dir) in dirs {
+            let root = self.relative_path(&dir.to_string_lossy());
+            if !self.servers.iter().any(|s| s.root == root) {
+                self.servers.push(ServerReport {
+                    name,
+                    root,
+                    summary: ScanSummary::from_vulnerabilities(&[]),
+                });
+            }
+        }
+        self.servers.sort_by(|a, b| a.root.cmp(&b.root));
+        self.update_summary();
+    }
+
+    /// Index into `servers` of the server a finding belongs to: the one
+    /// with the deepest root above its file
+    pub fn server_of(&self, vuln: &Vulnerability) -> Option<usize> {
+        let file = self.relative_path(&vuln.location.as_ref()?.file);
+        self.servers
+            .iter()
+            .enumerate()
+            .filter(|(_, server)| {
+                server.root.is_empty()
+                    || file
+                        .strip_prefix(&server.root)
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .max_by_key(|(_, server)| server.root.len())
+            .map(|(i, _)| i)
+    }
+
+    /// `file` relative to the target, `/`-separated
+    fn relative_path(&self, file: &str) -> String {
+        let file = Path::new(file);
+        file.strip_prefix(&self.target)
+            .unwrap_or(file)
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
     /// Update summary statistics based on current vulnerabilities
-    fn update_summary(&mut self) {
+    pub(crate) fn update_summary(&mut self) {
         self.summary = ScanSummary::from_vulnerabilities(&self.vulnerabilities);
+        if self.servers.is_empty() {
+            return;
+        }
+
+        let mut findings = vec![Vec::new(); self.servers.len()];
+        for vuln in &self.vulnerabilities {
+            if let Some(i) = self.server_of(vuln) {
+                findings[i].push(vuln.clone());
+            }
+        }
+        for (server, findings) in self.servers.iter_mut().zip(findings) {
+            server.summary = ScanSummary::from_vulnerabilities(&findings);
+        }
     }
 
     /// Filter vulnerabilities by minimum severity
@@ -144,10 +512,9 @@ impl ScanResult {
             .collect()
     }
 
-    /// Check if scan found any issues at or above a severity level
+    /// Check if scan found any open issues at or above a severity level
     pub fn has_issues_at_level(&self, min_severity: Severity) -> bool {
-        self.vulnerabilities
-            .iter()
+        self.open_vulnerabilities()
             .any(|v| v.severity >= min_severity)
     }
 
@@ -178,6 +545,37 @@ mod tests {
     use super::*;
     use crate::models::vulnerability::{Vulnerability, VulnerabilityType};
 
+    #[test]
+    fn test_new_findings() {
+        let finding = |id: &str, file: &str| {
+            Vulnerability::new(
+                id,
+                VulnerabilityType::CommandInjection,
+                Severity::High,
+                "Test",
+                "Desc",
+            )
+            .with_location(crate::models::vulnerability::Location::new(file))
+        };
+        let mut previous = ScanResult::new("/srv", vec!["static".to_string()]);
+        previous.add_vulnerability(finding("H-001", "/srv/a.py"));
+        let mut current = ScanResult::new("/srv", vec!["static".to_string()]);
+        current.add_vulnerability(finding("H-001", "/srv/a.py"));
+        current.add_vulnerability(finding("H-002", "/srv/b.py"));
+
+        let new: Vec<&str> = current
+            .new_findings(&previous)
+            .iter()
+            .map(|v| v.id.as_str())
+            .collect();
+        assert_eq!(new, vec![current.vulnerabilities[1].id.as_str()]);
+        // IDs follow the finding, not the order it was found in
+        assert_eq!(
+            current.vulnerabilities[0].id,
+            previous.vulnerabilities[0].id
+        );
+    }
+
     #[test]
     fn test_scan_summary_calculation() {
         let vulns = vec![
@@ -214,6 +612,124 @@ mod tests {
         assert_eq!(summary.risk_score, 65);
     }
 
+    #[test]
+    fn test_hotspots() {
+        let mut result = ScanResult::new("/srv", vec!["static".to_string()]);
+        for (severity, confidence, file) in [
+            (Severity::Medium, 1.0, "/srv/a.py"),
+            (Severity::Medium, 1.0, "/srv/a.py"),
+            (Severity::High, 0.5, "/srv/b.py"),
+            (Severity::Critical, 0.9, "/srv/c.py"),
+            (Severity::Low, 1.0, "/srv/d.py"),
+        ] {
+            result.add_vulnerability(
+                Vulnerability::new(
+                    "T-001",
+                    VulnerabilityType::CommandInjection,
+                    severity,
+                    "Test",
+                    "Desc",
+                )
+                .with_location(crate::models::vulnerability::Location::new(file))
+                .with_confidence(confidence),
+            );
+        }
+
+        let hotspots = result.hotspots(3);
+        let files: Vec<&str> = hotspots.iter().map(|h| h.file.as_str()).collect();
+        // a.py and b.py tie at 10 points; b.py's finding is worse
+        assert_eq!(files, vec!["/srv/c.py", "/srv/b.py", "/srv/a.py"]);
+        assert_eq!(hotspots[0].score, 36.0);
+        assert_eq!(hotspots[2].findings, 2);
+        assert_eq!(hotspots[2].worst, Severity::Medium);
+    }
+
+    #[test]
+    fn test_directory_risk() {
+        let mut result = ScanResult::new("/srv", vec!["static".to_string()]);
+        for (severity, file) in [
+            (Severity::High, "/srv/packages/api/src/server.py"),
+            (Severity::Medium, "/srv/packages/api/tools.py"),
+            (Severity::Critical, "/srv/packages/billing/index.ts"),
+            (Severity::Low, "/srv/setup.py"),
+        ] {
+            result.add_vulnerability(
+                Vulnerability::new(
+                    "T-001",
+                    VulnerabilityType::CommandInjection,
+                    severity,
+                    "Test",
+                    "Desc",
+                )
+                .with_location(crate::models::vulnerability::Location::new(file)),
+            );
+        }
+
+        let tree = result.directory_risk();
+        assert_eq!((tree.name(), tree.score, tree.findings), (".", 66.0, 4));
+        let packages = &tree.children[0];
+        assert_eq!((packages.path.as_str(), packages.findings), ("packages", 3));
+        let paths: Vec<&str> = packages.children.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["packages/billing", "packages/api"]);
+        let api = &packages.children[1];
+        assert_eq!((api.score, api.findings), (25.0, 2));
+        assert_eq!(api.children[0].name(), "src");
+    }
+
+    #[test]
+    fn test_servers() {
+        let mut result = ScanResult::new("/srv", vec!["static".to_string()]);
+        for (severity, file) in [
+            (Severity::High, "/srv/servers/weather/src/server.py"),
+            (Severity::Critical, "/srv/servers/billing/index.ts"),
+            (Severity::Medium, "/srv/servers/billing/index.ts"),
+            (Severity::Low, "/srv/scripts/release.sh"),
+        ] {
+            result.add_vulnerability(
+                Vulnerability::new(
+                    "T-001",
+                    VulnerabilityType::CommandInjection,
+                    severity,
+                    "Test",
+                    "Desc",
+                )
+                .with_location(crate::models::vulnerability::Location::new(file)),
+            );
+        }
+        result.set_servers([
+            ("weather".to_string(), PathBuf::from("/srv/servers/weather")),
+            ("billing".to_string(), PathBuf::from("/srv/servers/billing")),
+            ("dup".to_string(), PathBuf::from("/srv/servers/billing")),
+            (
+                "weather-x".to_string(),
+                PathBuf::from("/srv/servers/weather-x"),
+            ),
+        ]);
+
+        let roots: Vec<(&str, &str)> = result
+            .servers
+            .iter()
+            .map(|s| (s.name.as_str(), s.root.as_str()))
+            .collect();
+        assert_eq!(
+            roots,
+            vec![
+                ("billing", "servers/billing"),
+                ("weather", "servers/weather"),
+                ("weather-x", "servers/weather-x"),
+            ]
+        );
+        let billing = &result.servers[0].summary;
+        assert_eq!(
+            (billing.critical, billing.medium, billing.risk_score),
+            (1, 1, 45)
+        );
+        assert_eq!(result.servers[1].summary.high, 1);
+        assert_eq!(result.servers[2].summary.total_issues, 0);
+        assert_eq!(result.server_of(&result.vulnerabilities[3]), None);
+        assert_eq!(result.summary.total_issues, 4);
+    }
+
     #[test]
     fn test_scan_result_add_vulnerabilities() {
         let mut result = ScanResult::new("test-target", vec!["static".to_string()]);
@@ -29,16 +29,25 @@
 //! .with_confidence(0.95);
 //! ```
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::Path;
+
+use crate::detectors::DetectorKind;
 
 /// Severity level of a vulnerability
 ///
-/// Ordered from least to most severe: Low < Medium < High < Critical
+/// Ordered from least to most severe: Info < Low < Medium < High < Critical
 /// This ordering is used for sorting and risk score calculation.
+///
+/// Detectors never emit `Info` themselves; it is reached through severity
+/// overrides in `sentinel.toml` and doesn't count towards the risk score.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
+    Info,
     Low,
     Medium,
     High,
@@ -49,6 +58,7 @@ impl Severity {
     /// Convert severity to emoji badge
     pub fn to_emoji(&self) -> &'static str {
         match self {
+            Severity::Info => "⚪",
             Severity::Low => "🔵",
             Severity::Medium => "🟡",
             Severity::High => "🟠",
@@ -59,12 +69,24 @@ impl Severity {
     /// Convert severity to text badge
     pub fn to_badge(&self) -> &'static str {
         match self {
+            Severity::Info => "INFO",
             Severity::Low => "LOW",
             Severity::Medium => "MEDIUM",
             Severity::High => "HIGH",
             Severity::Critical => "CRITICAL",
         }
     }
+
+    /// Points a finding of this severity adds to a risk score
+    pub fn risk_weight(&self) -> usize {
+        match self {
+            Severity::Info => 0,
+            Severity::Low => 1,
+            Severity::Medium => 5,
+            Severity::High => 20,
+            Severity::Critical => 40,
+        }
+    }
 }
 
 /// Type of vulnerability detected
@@ -79,15 +101,33 @@ pub enum VulnerabilityType {
     RugPull,
     ShadowTool,
     CommandInjection,
+    CodeInjection,
     PathTraversal,
     SqlInjection,
+    NoSqlInjection,
+    LdapInjection,
+    XpathInjection,
+    XxeInjection,
+    TemplateInjection,
+    OpenRedirect,
+    HeaderInjection,
+    PrototypePollution,
+    RegexDos,
+    InsecurePermissions,
+    InsecureTransport,
+    InsecureContainerConfig,
+    CloudMisconfiguration,
     UnsafeDeserialization,
+    UnsafeReflection,
+    MemorySafety,
     HardcodedCredentials,
     SecretsLeakage,
     PiiExposure,
     CrossOriginEscalation,
     BehavioralAnomaly,
     SupplyChainAttack,
+    MaliciousPayload,
+    Robustness,
 }
 
 impl VulnerabilityType {
@@ -102,19 +142,72 @@ impl VulnerabilityType {
             VulnerabilityType::RugPull => "MCP Rug Pull",
             VulnerabilityType::ShadowTool => "Shadow Tool",
             VulnerabilityType::CommandInjection => "Command Injection",
+            VulnerabilityType::CodeInjection => "Code Injection",
             VulnerabilityType::PathTraversal => "Path Traversal",
             VulnerabilityType::SqlInjection => "SQL Injection",
+            VulnerabilityType::NoSqlInjection => "NoSQL Injection",
+            VulnerabilityType::LdapInjection => "LDAP Injection",
+            VulnerabilityType::XpathInjection => "XPath Injection",
+            VulnerabilityType::XxeInjection => "XML External Entity (XXE)",
+            VulnerabilityType::TemplateInjection => "Server-Side Template Injection",
+            VulnerabilityType::OpenRedirect => "Open Redirect",
+            VulnerabilityType::HeaderInjection => "HTTP Header Injection (CRLF)",
+            VulnerabilityType::PrototypePollution => "Prototype Pollution",
+            VulnerabilityType::RegexDos => "Regular Expression DoS (ReDoS)",
+            VulnerabilityType::InsecurePermissions => "Insecure File Permissions",
+            VulnerabilityType::InsecureTransport => "Insecure Transport",
+            VulnerabilityType::InsecureContainerConfig => "Insecure Container Configuration",
+            VulnerabilityType::CloudMisconfiguration => "Cloud Misconfiguration",
             VulnerabilityType::UnsafeDeserialization => "Unsafe Deserialization",
+            VulnerabilityType::UnsafeReflection => "Unsafe Reflection",
+            VulnerabilityType::MemorySafety => "Memory Safety Violation",
             VulnerabilityType::HardcodedCredentials => "Hardcoded Credentials",
             VulnerabilityType::SecretsLeakage => "Secrets Leakage",
             VulnerabilityType::PiiExposure => "PII Exposure",
             VulnerabilityType::CrossOriginEscalation => "Cross-Origin Escalation",
             VulnerabilityType::BehavioralAnomaly => "Behavioral Anomaly",
             VulnerabilityType::SupplyChainAttack => "Supply Chain Attack",
+            VulnerabilityType::MaliciousPayload => "Malicious Payload",
+            VulnerabilityType::Robustness => "Robustness Failure",
         }
     }
 }
 
+/// Triage decision recorded for a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "native", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageState {
+    /// Not triaged (clears an earlier decision)
+    Open,
+    /// Real issue, risk accepted
+    Accepted,
+    /// Not a real issue
+    FalsePositive,
+    /// Fixed; kept so a regression is noticed if it reappears
+    Fixed,
+}
+
+impl std::fmt::Display for TriageState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            TriageState::Open => "open",
+            TriageState::Accepted => "accepted",
+            TriageState::FalsePositive => "false-positive",
+            TriageState::Fixed => "fixed",
+        })
+    }
+}
+
+/// Triage decision attached to a finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Triage {
+    pub state: TriageState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
 /// Location of a vulnerability in source code
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Location {
@@ -123,6 +216,10 @@ pub struct Location {
     pub line: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub column: Option<usize>,
+    /// Column just past the end of the match (exclusive, like SARIF's
+    /// `endColumn`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
 }
 
 impl Location {
@@ -131,6 +228,7 @@ impl Location {
             file: file.into(),
             line: None,
             column: None,
+            end_column: None,
         }
     }
 
@@ -144,6 +242,11 @@ impl Location {
         self
     }
 
+    pub fn with_end_column(mut self, end_column: usize) -> Self {
+        self.end_column = Some(end_column);
+        self
+    }
+
     /// Format location as "file:line:column"
     pub fn format(&self) -> String {
         match (self.line, self.column) {
@@ -160,18 +263,43 @@ pub struct AiAnalysis {
     pub model: String,
     pub explanation: String,
     pub confidence: f32,
+    /// Unified diff proposed by the model (`scan --llm-fix`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_patch: Option<String>,
 }
 
 /// A detected vulnerability
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Vulnerability {
-    /// Unique identifier (e.g., "C-001")
+    /// Unique identifier: the detector's prefix and the start of the
+    /// fingerprint once added to a [`ScanResult`](super::scan_result::ScanResult)
+    /// (e.g., "CMD-3f2a9c1b"), so it stays the same across runs
     pub id: String,
 
     /// Type of vulnerability
     #[serde(rename = "type")]
     pub vuln_type: VulnerabilityType,
 
+    /// Stable rule identifier (e.g., "deserialization/python-shelve-usage")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+
+    /// Detector that produced the finding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detector: Option<DetectorKind>,
+
+    /// Stable hash identifying this finding across scans (see [`Vulnerability::compute_fingerprint`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+
+    /// Triage decision, if the finding has been triaged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub triage: Option<Triage>,
+
+    /// Whether `scan --fix` can fix this finding mechanically
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_fixable: bool,
+
     /// Severity level
     pub severity: Severity,
 
@@ -204,6 +332,10 @@ pub struct Vulnerability {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example_fix: Option<String>,
 
+    /// Regex the detector matched the line with, for `scan --explain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
     /// Additional evidence/context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub evidence: Option<HashMap<String, serde_json::Value>>,
@@ -225,6 +357,11 @@ impl Vulnerability {
         Self {
             id: id.into(),
             vuln_type,
+            rule_id: None,
+            detector: None,
+            fingerprint: None,
+            triage: None,
+            auto_fixable: false,
             severity,
             confidence: 1.0,
             location: None,
@@ -234,11 +371,88 @@ impl Vulnerability {
             remediation: None,
             code_snippet: None,
             example_fix: None,
+            pattern: None,
             evidence: None,
             ai_analysis: None,
         }
     }
 
+    /// Compute the fingerprint of this finding relative to the scan `root`
+    ///
+    /// Hashes the rule, the file path relative to `root`, and the trimmed
+    /// code snippet. Line numbers are left out so the fingerprint survives
+    /// unrelated edits above the finding.
+    pub fn compute_fingerprint(&self, root: &Path) -> String {
+        let rule = match &self.rule_id {
+            Some(rule) => rule.clone(),
+            None => self.vuln_type.name().to_string(),
+        };
+        let file = self
+            .location
+            .as_ref()
+            .map(|l| {
+                let path = Path::new(&l.file);
+                path.strip_prefix(root)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .unwrap_or_default();
+        let content = self.code_snippet.as_deref().unwrap_or(&self.title).trim();
+
+        let mut hasher = Sha256::new();
+        for part in [rule.as_str(), file.as_str(), content] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        hasher.finalize()[..8]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Set the fingerprint relative to the scan `root` and replace the
+    /// detector's counter ID (`CMD-003`) with one derived from it
+    /// (`CMD-3f2a9c1b`)
+    ///
+    /// `seen` counts the fingerprints identified so far in the scan; repeats
+    /// of one (the same line twice in a file) get a `-2`, `-3`, ... suffix so
+    /// IDs stay unique.
+    pub fn identify(&mut self, root: &Path, seen: &mut HashMap<String, usize>) {
+        let fingerprint = self.compute_fingerprint(root);
+        let prefix = self
+            .id
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .trim_end_matches('-');
+        let prefix = if prefix.is_empty() { "MCP" } else { prefix };
+        let count = seen.entry(fingerprint.clone()).or_default();
+        *count += 1;
+        self.id = match *count {
+            1 => format!("{}-{}", prefix, &fingerprint[..8]),
+            n => format!("{}-{}-{}", prefix, &fingerprint[..8], n),
+        };
+        self.fingerprint = Some(fingerprint);
+    }
+
+    /// Whether the finding still needs attention (not triaged, or reopened)
+    pub fn is_open(&self) -> bool {
+        self.triage
+            .as_ref()
+            .is_none_or(|t| t.state == TriageState::Open)
+    }
+
+    /// Points the finding adds to file and directory risk: its severity's
+    /// weight scaled by confidence
+    pub fn risk(&self) -> f32 {
+        self.severity.risk_weight() as f32 * self.confidence
+    }
+
+    /// Builder method to set rule ID
+    pub fn with_rule_id(mut self, rule_id: impl Into<String>) -> Self {
+        self.rule_id = Some(rule_id.into());
+        self
+    }
+
     /// Builder method to set location
     pub fn with_location(mut self, location: Location) -> Self {
         self.location = Some(location);
@@ -263,6 +477,12 @@ impl Vulnerability {
         self
     }
 
+    /// Builder method to set the matched pattern
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
     /// Builder method to set code snippet
     pub fn with_code_snippet(mut self, snippet: impl Into<String>) -> Self {
         self.code_snippet = Some(snippet.into());
@@ -291,6 +511,7 @@ mod tests {
         assert!(Severity::Critical > Severity::High);
         assert!(Severity::High > Severity::Medium);
         assert!(Severity::Medium > Severity::Low);
+        assert!(Severity::Low > Severity::Info);
     }
 
     #[test]
@@ -324,4 +545,37 @@ mod tests {
         assert!(vuln.location.is_some());
         assert!(vuln.impact.is_some());
     }
+
+    #[test]
+    fn test_identify() {
+        let finding = |id: &str, line: usize| {
+            Vulnerability::new(
+                id,
+                VulnerabilityType::CommandInjection,
+                Severity::Critical,
+                "Command Injection",
+                "Unsafe command execution",
+            )
+            .with_rule_id("command_injection/os-system-usage")
+            .with_location(Location::new("/srv/server/tools.py").with_line(line))
+            .with_code_snippet("os.system(cmd)")
+        };
+        let root = Path::new("/srv/server");
+
+        let mut seen = HashMap::new();
+        let mut first = finding("CMD-001", 10);
+        first.identify(root, &mut seen);
+        let fingerprint = first.fingerprint.clone().unwrap();
+        assert_eq!(first.id, format!("CMD-{}", &fingerprint[..8]));
+
+        // Same line twice in the file
+        let mut repeat = finding("CMD-002", 20);
+        repeat.identify(root, &mut seen);
+        assert_eq!(repeat.id, format!("CMD-{}-2", &fingerprint[..8]));
+
+        // A later run numbering the finding differently
+        let mut rerun = finding("CMD-007", 12);
+        rerun.identify(root, &mut HashMap::new());
+        assert_eq!(rerun.id, first.id);
+    }
 }
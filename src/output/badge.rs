@@ -0,0 +1,93 @@
+//! SVG status badge
+//!
+//! A shields.io-style badge, `sentinel | 0 critical / 3 high`, for server
+//! maintainers to embed in their README. The right half is green with no
+//! open findings above low, yellow for medium, orange for high, and red for
+//! critical.
+
+use anyhow::Result;
+
+use super::html::escape;
+use crate::models::scan_result::ScanResult;
+
+const LABEL: &str = "sentinel";
+
+/// Generate the badge for `result`
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let summary = &result.summary;
+    let message = format!("{} critical / {} high", summary.critical, summary.high);
+    let color = if summary.critical > 0 {
+        "#e05d44"
+    } else if summary.high > 0 {
+        "#fe7d37"
+    } else if summary.medium > 0 {
+        "#dfb317"
+    } else {
+        "#4c1"
+    };
+
+    let label_width = text_width(LABEL) + 10;
+    let message_width = text_width(&message) + 10;
+    let width = label_width + message_width;
+    let title = format!("{}: {}", LABEL, message);
+
+    Ok(format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{title}">
+<title>{title}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+<text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{label}</text><text x="{label_x}" y="14">{label}</text>
+<text x="{message_x}" y="15" fill="#010101" fill-opacity=".3">{message}</text><text x="{message_x}" y="14">{message}</text>
+</g>
+</svg>
+"##,
+        title = escape(&title),
+        label = LABEL,
+        message = escape(&message),
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    ))
+}
+
+/// Approximate width of `text` in 11px Verdana, which the badge uses
+fn text_width(text: &str) -> usize {
+    let tenths: usize = text
+        .chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | '|' | '!' | '\'' => 32,
+            ' ' | 'f' | 'r' | 't' | '/' | '(' | ')' => 45,
+            'm' | 'w' => 96,
+            c if c.is_ascii_uppercase() => 75,
+            _ => 68,
+        })
+        .sum();
+    tenths.div_ceil(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+    #[test]
+    fn test_generate_badge() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        let svg = generate(&result).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>sentinel: 0 critical / 0 high</title>"));
+        assert!(svg.contains("fill=\"#4c1\""));
+
+        result.add_vulnerability(Vulnerability::new(
+            "H-001",
+            VulnerabilityType::CommandInjection,
+            Severity::High,
+            "Test",
+            "Desc",
+        ));
+        let svg = generate(&result).unwrap();
+        assert!(svg.contains(">0 critical / 1 high</text>"));
+        assert!(svg.contains("fill=\"#fe7d37\""));
+    }
+}
@@ -0,0 +1,135 @@
+//! DefectDojo output generator
+//!
+//! Findings in DefectDojo's Generic Findings Import format, for importing
+//! with the "Generic Findings Import" scan type. The fingerprint becomes
+//! `unique_id_from_tool`, so DefectDojo deduplicates re-imports the same
+//! way later scans match findings, and triage decisions carry over as its
+//! false positive and risk accepted flags.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::{Severity, TriageState, Vulnerability};
+
+/// Generate a DefectDojo Generic Findings Import report
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let findings: Vec<Value> = result
+        .vulnerabilities
+        .iter()
+        .map(|vuln| finding(result, vuln))
+        .collect();
+    Ok(serde_json::to_string_pretty(
+        &json!({ "findings": findings }),
+    )?)
+}
+
+fn finding(result: &ScanResult, vuln: &Vulnerability) -> Value {
+    let mut description = vuln.description.clone();
+    if let Some(snippet) = &vuln.code_snippet {
+        description.push_str(&format!("\n\n```\n{}\n```", snippet));
+    }
+    let state = vuln.triage.as_ref().map_or(TriageState::Open, |t| t.state);
+    let mut tags = vec!["mcp-sentinel".to_string()];
+    tags.extend(vuln.detector.map(|d| d.id().to_string()));
+
+    let mut finding = json!({
+        "title": vuln.title,
+        "description": description,
+        "severity": severity(vuln.severity),
+        "date": result.timestamp.format("%Y-%m-%d").to_string(),
+        "vuln_id_from_tool": vuln.rule_id.as_deref().unwrap_or(vuln.vuln_type.name()),
+        "active": state == TriageState::Open,
+        "verified": false,
+        "false_p": state == TriageState::FalsePositive,
+        "risk_accepted": state == TriageState::Accepted,
+        "is_mitigated": state == TriageState::Fixed,
+        // Findings without a source location come from running the server
+        "static_finding": vuln.location.is_some(),
+        "dynamic_finding": vuln.location.is_none(),
+        "tags": tags,
+    });
+    let optional = [
+        (
+            "unique_id_from_tool",
+            vuln.fingerprint.clone().map(Value::from),
+        ),
+        ("mitigation", vuln.remediation.clone().map(Value::from)),
+        ("impact", vuln.impact.clone().map(Value::from)),
+        (
+            "file_path",
+            vuln.location.as_ref().map(|l| l.file.clone().into()),
+        ),
+        (
+            "line",
+            vuln.location.as_ref().and_then(|l| l.line).map(Value::from),
+        ),
+    ];
+    for (field, value) in optional {
+        if let Some(value) = value {
+            finding[field] = value;
+        }
+    }
+    finding
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "Info",
+        Severity::Low => "Low",
+        Severity::Medium => "Medium",
+        Severity::High => "High",
+        Severity::Critical => "Critical",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, Triage, VulnerabilityType};
+
+    #[test]
+    fn test_generic_findings_format() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        let mut accepted = Vulnerability::new(
+            "SEC-002",
+            VulnerabilityType::CommandInjection,
+            Severity::Medium,
+            "Shell command built from input",
+            "Desc",
+        );
+        accepted.triage = Some(Triage {
+            state: TriageState::Accepted,
+            reason: None,
+            updated_at: chrono::Utc::now(),
+        });
+        result.add_vulnerabilities(vec![
+            Vulnerability::new(
+                "SEC-001",
+                VulnerabilityType::HardcodedCredentials,
+                Severity::Critical,
+                "Hardcoded AWS key",
+                "Desc",
+            )
+            .with_location(Location::new("server/config.py").with_line(42))
+            .with_remediation("Move credentials to environment variables"),
+            accepted,
+        ]);
+
+        let report: Value = serde_json::from_str(&generate(&result).unwrap()).unwrap();
+        let findings = report["findings"].as_array().unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0]["severity"], "Critical");
+        assert_eq!(findings[0]["file_path"], "server/config.py");
+        assert_eq!(findings[0]["line"], 42);
+        assert_eq!(
+            findings[0]["unique_id_from_tool"].as_str(),
+            result.vulnerabilities[0].fingerprint.as_deref()
+        );
+        assert_eq!(findings[0]["active"], true);
+        assert_eq!(findings[1]["active"], false);
+        assert_eq!(findings[1]["risk_accepted"], true);
+        assert_eq!(findings[1]["dynamic_finding"], true);
+        assert!(findings[1].get("file_path").is_none());
+    }
+}
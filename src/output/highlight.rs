@@ -0,0 +1,223 @@
+//! Syntax highlighting of code snippets
+//!
+//! Snippets are highlighted with syntect for the language of the finding's
+//! file (by extension, plain text if unknown), and the part the detector
+//! matched is underlined. The terminal renderer and the HTML report share
+//! this.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+use super::html::escape;
+use crate::models::vulnerability::Vulnerability;
+
+const THEME: &str = "base16-ocean.dark";
+
+static SYNTAXES: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEMES: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// A snippet line (0-based) and the byte range on it that the detector matched
+pub type SnippetSpan = (usize, Range<usize>);
+
+/// A highlighted run of text, and whether it is part of the matched span
+type Piece<'a> = (Style, &'a str, bool);
+
+/// Syntax for `file`, by extension
+fn syntax_for(file: &str) -> &'static SyntaxReference {
+    let extension = Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    // The default syntax set has no TypeScript; JavaScript is close enough
+    let extension = match extension.as_str() {
+        "ts" | "tsx" | "mts" | "cts" | "jsx" | "mjs" | "cjs" => "js",
+        other => other,
+    };
+    SYNTAXES
+        .find_syntax_by_extension(extension)
+        .unwrap_or_else(|| SYNTAXES.find_syntax_plain_text())
+}
+
+/// Where in `snippet` the finding's pattern matched
+///
+/// The match at the finding's column is preferred when a line has several.
+/// Findings without a pattern fall back to their columns, if they fit the
+/// snippet's first line.
+pub fn snippet_span(vuln: &Vulnerability, snippet: &str) -> Option<SnippetSpan> {
+    let location = vuln.location.as_ref();
+    let column = location.and_then(|l| l.column);
+    let char_column = |line: &str, byte: usize| line[..byte].chars().count() + 1;
+
+    if let Some(regex) = vuln.pattern.as_deref().and_then(|p| Regex::new(p).ok()) {
+        let found = snippet.lines().enumerate().find_map(|(i, line)| {
+            let m = regex
+                .find_iter(line)
+                .find(|m| Some(char_column(line, m.start())) == column)
+                .or_else(|| regex.find(line))?;
+            Some((i, m.range()))
+        });
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    let (start, end) = (column?, location?.end_column?);
+    let line = snippet.lines().next()?;
+    let byte = |column: usize| {
+        line.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(line.len()))
+            .nth(column.checked_sub(1)?)
+    };
+    Some((0, byte(start)?..byte(end)?))
+}
+
+/// Highlight `snippet` line by line, splitting runs at the span's edges
+fn pieces<'a>(snippet: &'a str, file: &str, span: Option<&SnippetSpan>) -> Vec<Vec<Piece<'a>>> {
+    let mut highlighter = HighlightLines::new(syntax_for(file), &THEMES.themes[THEME]);
+    LinesWithEndings::from(snippet)
+        .enumerate()
+        .map(|(i, line)| {
+            let regions = highlighter
+                .highlight_line(line, &SYNTAXES)
+                .unwrap_or_else(|_| vec![(Style::default(), line)]);
+            let span = span.filter(|(l, _)| *l == i).map(|(_, r)| r.clone());
+
+            let mut pieces = Vec::new();
+            let mut offset = 0;
+            for (style, text) in regions {
+                let (start, end) = (offset, offset + text.len());
+                offset = end;
+                let (a, b) = match &span {
+                    Some(r) => (
+                        r.start.clamp(start, end) - start,
+                        r.end.clamp(start, end) - start,
+                    ),
+                    None => (text.len(), text.len()),
+                };
+                for (part, matched) in [
+                    (&text[..a], false),
+                    (&text[a..b], true),
+                    (&text[b..], false),
+                ] {
+                    let part = part.trim_end_matches(['\r', '\n']);
+                    if !part.is_empty() {
+                        pieces.push((style, part, matched));
+                    }
+                }
+            }
+            pieces
+        })
+        .collect()
+}
+
+/// `snippet` as lines with 24-bit ANSI colors, the span underlined
+pub fn terminal(snippet: &str, file: &str, span: Option<SnippetSpan>) -> Vec<String> {
+    pieces(snippet, file, span.as_ref())
+        .into_iter()
+        .map(|line| {
+            let mut out = String::new();
+            for (style, text, matched) in line {
+                let c = style.foreground;
+                out.push_str(&format!("\x1b[38;2;{};{};{}m", c.r, c.g, c.b));
+                if matched {
+                    out.push_str(&format!("\x1b[4m{}\x1b[24m", text));
+                } else {
+                    out.push_str(text);
+                }
+            }
+            out.push_str("\x1b[0m");
+            out
+        })
+        .collect()
+}
+
+/// `snippet` as HTML (for a `<pre>`), the span in `<u class="match">`
+pub fn html(snippet: &str, file: &str, span: Option<SnippetSpan>) -> String {
+    pieces(snippet, file, span.as_ref())
+        .into_iter()
+        .map(|line| {
+            let mut out = String::new();
+            for (style, text, matched) in line {
+                let c = style.foreground;
+                let colored = format!(
+                    "<span style=\"color:#{:02x}{:02x}{:02x}\">{}</span>",
+                    c.r,
+                    c.g,
+                    c.b,
+                    escape(text)
+                );
+                if matched {
+                    out.push_str(&format!("<u class=\"match\">{}</u>", colored));
+                } else {
+                    out.push_str(&colored);
+                }
+            }
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, Severity, VulnerabilityType};
+
+    fn finding(snippet: &str) -> Vulnerability {
+        Vulnerability::new(
+            "CMD-001",
+            VulnerabilityType::CommandInjection,
+            Severity::Critical,
+            "os.system() usage",
+            "Desc",
+        )
+        .with_location(
+            Location::new("tools.py")
+                .with_line(3)
+                .with_column(17)
+                .with_end_column(27),
+        )
+        .with_pattern(r"os\.system\s*\(")
+        .with_code_snippet(snippet)
+    }
+
+    #[test]
+    fn test_snippet_span() {
+        let snippet = "os.system(a) or os.system(b)";
+        let span = snippet_span(&finding(snippet), snippet).unwrap();
+        assert_eq!(span, (0, 16..26));
+
+        // No pattern: the columns
+        let mut vuln = finding(snippet);
+        vuln.pattern = None;
+        assert_eq!(snippet_span(&vuln, snippet), Some((0, 16..26)));
+    }
+
+    #[test]
+    fn test_highlight_underlines_match() {
+        let snippet = "x = 1 < 2 and os.system(cmd)";
+        let span = snippet_span(&finding(snippet), snippet);
+
+        let html = html(snippet, "tools.py", span.clone());
+        assert!(html.contains("&lt;"));
+        let underlined: String = html
+            .split("<u class=\"match\">")
+            .skip(1)
+            .map(|part| part.split("</u>").next().unwrap())
+            .collect();
+        assert!(underlined.contains("system"));
+        assert!(!underlined.contains("cmd"));
+
+        let lines = terminal(snippet, "tools.py", span);
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].contains("\x1b[4m"));
+    }
+}
@@ -0,0 +1,487 @@
+//! HTML report generator
+//!
+//! A single self-contained page - styles and the chart script inline, no
+//! external assets - so it can be attached to a ticket or opened from a CI
+//! artifact offline. Open findings are listed most severe first, each with
+//! its code snippet highlighted for the file's language.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use super::highlight;
+use crate::models::scan_result::{DirectoryRisk, ScanResult, HEATMAP_DEPTH, HOTSPOTS};
+use crate::models::vulnerability::{Severity, Vulnerability};
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem auto; max-width: 64rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.meta { color: #59636e; margin-top: 0; }
+.summary { display: flex; gap: 1rem; margin: 1.5rem 0; }
+.count { border-radius: 6px; padding: 0.75rem 1rem; min-width: 5rem; text-align: center; color: #fff; }
+.count b { display: block; font-size: 1.5rem; }
+.finding { border: 1px solid #d1d9e0; border-left-width: 6px; border-radius: 6px; margin: 1rem 0; padding: 0.5rem 1rem; }
+.finding h2 { font-size: 1.1rem; margin: 0.5rem 0; }
+.badge { border-radius: 4px; color: #fff; font-size: 0.75rem; padding: 0.1rem 0.4rem; margin-right: 0.5rem; }
+.fixable { border: 1px solid #1a7f37; border-radius: 4px; color: #1a7f37; font-size: 0.75rem; padding: 0.1rem 0.4rem; margin-left: 0.5rem; }
+.location { font-family: ui-monospace, monospace; color: #59636e; }
+pre { background: #2b303b; border-radius: 6px; padding: 0.75rem; overflow-x: auto; }
+table.servers, table.hotspots, table.heatmap { border-collapse: collapse; margin-bottom: 1.5rem; }
+.servers th, .servers td, .hotspots th, .hotspots td, .heatmap th, .heatmap td { border-bottom: 1px solid #d1d9e0; padding: 0.4rem 0.75rem; text-align: left; }
+.charts { display: flex; flex-wrap: wrap; gap: 2rem; margin: 1.5rem 0; }
+.charts figure { margin: 0; }
+.charts figcaption { font-weight: 600; margin-bottom: 0.5rem; }
+td.heat { width: 12rem; }
+td.heat div { height: 0.75rem; border-radius: 3px; }
+u.match { text-decoration: underline wavy #ff5f5f; text-underline-offset: 3px; }
+.critical { background: #a40e26; border-left-color: #a40e26; }
+.high { background: #d1242f; border-left-color: #d1242f; }
+.medium { background: #bc4c00; border-left-color: #bc4c00; }
+.low { background: #9a6700; border-left-color: #9a6700; }
+.info { background: #0969da; border-left-color: #0969da; }
+.finding.critical, .finding.high, .finding.medium, .finding.low, .finding.info { background: #fff; }
+"#;
+
+/// Draws the charts from the JSON in `#chart-data` on their canvases
+const CHARTS: &str = r##"
+(function () {
+  var data = JSON.parse(document.getElementById("chart-data").textContent);
+  var font = "12px -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif";
+
+  function pie(id, rows) {
+    var canvas = document.getElementById(id);
+    var ctx = canvas.getContext("2d");
+    var total = rows.reduce(function (sum, r) { return sum + r[1]; }, 0);
+    var angle = -Math.PI / 2, y = 20;
+    ctx.font = font;
+    rows.forEach(function (r) {
+      if (!r[1]) return;
+      var slice = (r[1] / total) * 2 * Math.PI;
+      ctx.beginPath();
+      ctx.moveTo(90, 100);
+      ctx.arc(90, 100, 80, angle, angle + slice);
+      ctx.fillStyle = r[2];
+      ctx.fill();
+      angle += slice;
+      ctx.fillRect(200, y - 10, 12, 12);
+      ctx.fillStyle = "#1f2328";
+      ctx.fillText(r[0] + " (" + r[1] + ")", 218, y);
+      y += 20;
+    });
+  }
+
+  function bars(id, rows, color) {
+    var canvas = document.getElementById(id);
+    if (!canvas || !rows.length) return;
+    var ctx = canvas.getContext("2d");
+    var max = rows.reduce(function (m, r) { return Math.max(m, r[1]); }, 0);
+    canvas.height = rows.length * 24 + 8;
+    ctx.font = font;
+    ctx.textBaseline = "middle";
+    rows.forEach(function (r, i) {
+      var y = i * 24 + 4, label = r[0].length > 24 ? "\u2026" + r[0].slice(-23) : r[0];
+      ctx.fillStyle = "#1f2328";
+      ctx.fillText(label, 0, y + 9);
+      ctx.fillStyle = color;
+      ctx.fillRect(170, y, Math.max(2, (r[1] / max) * 230), 18);
+      ctx.fillStyle = "#1f2328";
+      ctx.fillText(String(r[1]), 176 + (r[1] / max) * 230, y + 9);
+    });
+  }
+
+  pie("severity-chart", data.severity);
+  bars("directory-chart", data.directories, "#bc4c00");
+  bars("detector-chart", data.detectors, "#0969da");
+})();
+"##;
+
+/// Bars per chart; the rest are left out
+const CHART_ROWS: usize = 10;
+
+/// Generate the HTML report
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(html, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+    writeln!(
+        html,
+        "<title>MCP Sentinel report - {}</title>",
+        escape(&result.target)
+    )?;
+    writeln!(html, "<style>{}</style>\n</head>\n<body>", STYLE)?;
+
+    writeln!(html, "<h1>MCP Sentinel report</h1>")?;
+    let mut meta = format!(
+        "{} &middot; {} &middot; risk score {}/100",
+        escape(&result.target),
+        result.timestamp.format("%Y-%m-%d %H:%M UTC"),
+        result.summary.risk_score
+    );
+    let fixable = result.fixable_count();
+    if fixable > 0 {
+        write!(meta, " &middot; {} fixable with --fix", fixable)?;
+    }
+    writeln!(html, "<p class=\"meta\">{}</p>", meta)?;
+
+    let summary = &result.summary;
+    writeln!(html, "<div class=\"summary\">")?;
+    for (severity, count) in [
+        (Severity::Critical, summary.critical),
+        (Severity::High, summary.high),
+        (Severity::Medium, summary.medium),
+        (Severity::Low, summary.low),
+        (Severity::Info, summary.info),
+    ] {
+        writeln!(
+            html,
+            "<div class=\"count {}\"><b>{}</b>{}</div>",
+            class(severity),
+            count,
+            severity.to_badge()
+        )?;
+    }
+    writeln!(html, "</div>")?;
+
+    servers(&mut html, result)?;
+    charts(&mut html, result)?;
+    hotspots(&mut html, result)?;
+    heatmap(&mut html, result)?;
+
+    let mut findings: Vec<&Vulnerability> = result.open_vulnerabilities().collect();
+    findings.sort_by_key(|v| std::cmp::Reverse(v.severity));
+    if findings.is_empty() {
+        writeln!(html, "<p>No issues found.</p>")?;
+    }
+    for vuln in findings {
+        finding(&mut html, vuln)?;
+    }
+
+    writeln!(html, "</body>\n</html>")?;
+    Ok(html)
+}
+
+/// Severity pie, findings per top-level directory, and findings per
+/// detector, drawn by [`CHARTS`] from data embedded in the page
+fn charts(html: &mut String, result: &ScanResult) -> Result<()> {
+    if result.summary.total_issues == 0 {
+        return Ok(());
+    }
+
+    let summary = &result.summary;
+    let severity: Vec<_> = [
+        (Severity::Critical, summary.critical),
+        (Severity::High, summary.high),
+        (Severity::Medium, summary.medium),
+        (Severity::Low, summary.low),
+        (Severity::Info, summary.info),
+    ]
+    .into_iter()
+    .map(|(severity, count)| serde_json::json!([severity.to_badge(), count, color(severity)]))
+    .collect();
+
+    let mut directories: Vec<(String, usize)> = result
+        .directory_risk()
+        .children
+        .into_iter()
+        .map(|dir| (format!("{}/", dir.path), dir.findings))
+        .collect();
+    directories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    directories.truncate(CHART_ROWS);
+
+    let mut detectors: BTreeMap<&str, usize> = BTreeMap::new();
+    for vuln in result.open_vulnerabilities() {
+        let name = match vuln.detector {
+            Some(detector) => detector.name(),
+            None => vuln.vuln_type.name(),
+        };
+        *detectors.entry(name).or_default() += 1;
+    }
+    let mut detectors: Vec<(&str, usize)> = detectors.into_iter().collect();
+    detectors.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    detectors.truncate(CHART_ROWS);
+
+    let data = serde_json::json!({
+        "severity": severity,
+        "directories": directories,
+        "detectors": detectors,
+    });
+
+    writeln!(html, "<div class=\"charts\">")?;
+    writeln!(
+        html,
+        "<figure><figcaption>Severity</figcaption><canvas id=\"severity-chart\" width=\"320\" height=\"200\"></canvas></figure>"
+    )?;
+    if !directories.is_empty() {
+        writeln!(
+            html,
+            "<figure><figcaption>Findings by directory</figcaption><canvas id=\"directory-chart\" width=\"440\"></canvas></figure>"
+        )?;
+    }
+    writeln!(
+        html,
+        "<figure><figcaption>Findings by detector</figcaption><canvas id=\"detector-chart\" width=\"440\"></canvas></figure>"
+    )?;
+    writeln!(html, "</div>")?;
+    // `</` would end the script element early
+    writeln!(
+        html,
+        "<script type=\"application/json\" id=\"chart-data\">{}</script>",
+        data.to_string().replace("</", "<\\/")
+    )?;
+    writeln!(html, "<script>{}</script>", CHARTS)?;
+    Ok(())
+}
+
+/// Counts and risk score of each server of a monorepo
+fn servers(html: &mut String, result: &ScanResult) -> Result<()> {
+    if result.servers.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(html, "<h2>Servers</h2>")?;
+    writeln!(
+        html,
+        "<table class=\"servers\">\n<tr><th>Server</th><th>Path</th><th>Risk score</th><th>Critical</th><th>High</th><th>Medium</th><th>Low</th></tr>"
+    )?;
+    for server in &result.servers {
+        let summary = &server.summary;
+        writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"location\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&server.name),
+            escape(if server.root.is_empty() { "." } else { &server.root }),
+            summary.risk_score,
+            summary.critical,
+            summary.high,
+            summary.medium,
+            summary.low
+        )?;
+    }
+    writeln!(html, "</table>")?;
+    Ok(())
+}
+
+/// The files with the most risk, as a table
+fn hotspots(html: &mut String, result: &ScanResult) -> Result<()> {
+    let hotspots = result.hotspots(HOTSPOTS);
+    if hotspots.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(html, "<h2>Hotspots</h2>")?;
+    writeln!(
+        html,
+        "<table class=\"hotspots\">\n<tr><th>File</th><th>Score</th><th>Findings</th><th>Worst</th></tr>"
+    )?;
+    for hotspot in hotspots {
+        writeln!(
+            html,
+            "<tr><td class=\"location\">{}</td><td>{:.1}</td><td>{}</td><td><span class=\"badge {}\">{}</span></td></tr>",
+            escape(&hotspot.file),
+            hotspot.score,
+            hotspot.findings,
+            class(hotspot.worst),
+            hotspot.worst.to_badge()
+        )?;
+    }
+    writeln!(html, "</table>")?;
+    Ok(())
+}
+
+/// Findings rolled up by directory, with a bar for each one's share of the risk
+fn heatmap(html: &mut String, result: &ScanResult) -> Result<()> {
+    let tree = result.directory_risk();
+    if tree.children.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(html, "<h2>Risk by directory</h2>")?;
+    writeln!(
+        html,
+        "<table class=\"heatmap\">\n<tr><th>Directory</th><th>Score</th><th>Findings</th><th></th></tr>"
+    )?;
+    directory(html, &tree, tree.score, 0)?;
+    writeln!(html, "</table>")?;
+    Ok(())
+}
+
+fn directory(html: &mut String, dir: &DirectoryRisk, total: f32, depth: usize) -> Result<()> {
+    let share = if total > 0.0 { dir.score / total } else { 0.0 };
+    let color = match share {
+        s if s >= 0.5 => "#a40e26",
+        s if s >= 0.25 => "#d1242f",
+        s if s >= 0.1 => "#bc4c00",
+        _ => "#9a6700",
+    };
+    writeln!(
+        html,
+        "<tr><td class=\"location\" style=\"padding-left:{:.2}rem\">{}/</td><td>{:.1}</td><td>{}</td><td class=\"heat\"><div style=\"width:{:.0}%;background:{}\"></div></td></tr>",
+        0.75 + depth as f32 * 1.25,
+        escape(dir.name()),
+        dir.score,
+        dir.findings,
+        share * 100.0,
+        color
+    )?;
+
+    if depth < HEATMAP_DEPTH {
+        for child in &dir.children {
+            directory(html, child, total, depth + 1)?;
+        }
+    }
+    Ok(())
+}
+
+fn finding(html: &mut String, vuln: &Vulnerability) -> Result<()> {
+    writeln!(html, "<section class=\"finding {}\">", class(vuln.severity))?;
+    let fixable = if vuln.auto_fixable {
+        "<span class=\"fixable\">fixable with --fix</span>"
+    } else {
+        ""
+    };
+    writeln!(
+        html,
+        "<h2><span class=\"badge {}\">{}</span>{}{}</h2>",
+        class(vuln.severity),
+        vuln.severity.to_badge(),
+        escape(&vuln.title),
+        fixable
+    )?;
+
+    let file = vuln.location.as_ref().map_or("", |l| l.file.as_str());
+    if let Some(location) = &vuln.location {
+        let mut place = location.file.clone();
+        if let Some(line) = location.line {
+            write!(place, ":{}", line)?;
+        }
+        writeln!(
+            html,
+            "<p class=\"location\">{} &middot; {}</p>",
+            escape(&place),
+            escape(&vuln.id)
+        )?;
+    }
+    writeln!(html, "<p>{}</p>", escape(&vuln.description))?;
+
+    if let Some(snippet) = &vuln.code_snippet {
+        let span = highlight::snippet_span(vuln, snippet);
+        writeln!(
+            html,
+            "<pre><code>{}</code></pre>",
+            highlight::html(snippet, file, span)
+        )?;
+    }
+    if let Some(impact) = &vuln.impact {
+        writeln!(html, "<p><b>Impact:</b> {}</p>", escape(impact))?;
+    }
+    if let Some(remediation) = &vuln.remediation {
+        writeln!(html, "<p><b>Fix:</b> {}</p>", escape(remediation))?;
+    }
+    writeln!(html, "</section>")?;
+    Ok(())
+}
+
+/// Matches the severity's CSS class
+fn color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "#0969da",
+        Severity::Low => "#9a6700",
+        Severity::Medium => "#bc4c00",
+        Severity::High => "#d1242f",
+        Severity::Critical => "#a40e26",
+    }
+}
+
+fn class(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "info",
+        Severity::Low => "low",
+        Severity::Medium => "medium",
+        Severity::High => "high",
+        Severity::Critical => "critical",
+    }
+}
+
+/// Escape text for use in HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, VulnerabilityType};
+
+    #[test]
+    fn test_generate_html_report() {
+        let mut result = ScanResult::new("./<server>", vec!["static".to_string()]);
+        result.add_vulnerability(
+            Vulnerability::new(
+                "CMD-001",
+                VulnerabilityType::CommandInjection,
+                Severity::Critical,
+                "os.system() usage",
+                "Shell command built from input",
+            )
+            .with_location(Location::new("src/tools.py").with_line(3).with_column(1))
+            .with_pattern(r"os\.system\s*\(")
+            .with_code_snippet("os.system(\"ls \" + path)")
+            .with_remediation("Use subprocess.run with a list"),
+        );
+
+        let html = generate(&result).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("./&lt;server&gt;"));
+        assert!(html.contains("src/tools.py:3"));
+        assert!(html.contains("<td class=\"location\">src/tools.py</td><td>40.0</td>"));
+        assert!(html.contains(">src/</td><td>40.0</td><td>1</td>"));
+        assert!(html.contains("<u class=\"match\">"));
+        assert!(html.contains("&quot;") && !html.contains("\"ls"));
+        assert!(html.contains("Use subprocess.run with a list"));
+        assert!(!html.contains("fixable with --fix"));
+        assert!(html.contains("<canvas id=\"severity-chart\""));
+        assert!(!html.contains("<script src"));
+
+        let data = html
+            .split("<script type=\"application/json\" id=\"chart-data\">")
+            .nth(1)
+            .and_then(|rest| rest.split("</script>").next())
+            .unwrap();
+        let data: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(
+            data["severity"][0],
+            serde_json::json!(["CRITICAL", 1, "#a40e26"])
+        );
+        assert_eq!(data["directories"], serde_json::json!([["src/", 1]]));
+        assert_eq!(data["detectors"][0][1], 1);
+    }
+
+    #[test]
+    fn test_fixable_findings_marked() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        let mut vuln = Vulnerability::new(
+            "SQL-001",
+            VulnerabilityType::SqlInjection,
+            Severity::High,
+            "SQL built with an f-string",
+            "Query built from input",
+        );
+        vuln.auto_fixable = true;
+        result.add_vulnerability(vuln);
+
+        let html = generate(&result).unwrap();
+        assert!(html.contains("&middot; 1 fixable with --fix</p>"));
+        assert!(html.contains("<span class=\"fixable\">fixable with --fix</span></h2>"));
+    }
+}
@@ -0,0 +1,161 @@
+//! Localized report text
+//!
+//! Catalogs in `locales/` translate the terminal report's labels and, per
+//! vulnerability type, a title, impact, and remediation. `scan --lang`
+//! picks the catalog; machine-readable formats stay in English so tooling
+//! that matches on their text keeps working.
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::models::scan_result::ScanResult;
+
+/// Language of human-readable reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "native", derive(clap::ValueEnum))]
+pub enum Language {
+    /// English
+    #[default]
+    En,
+    /// Japanese
+    Ja,
+    /// German
+    De,
+    /// Simplified Chinese
+    Zh,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::En, Language::Ja, Language::De, Language::Zh];
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::En => include_str!("../../locales/en.toml"),
+            Language::Ja => include_str!("../../locales/ja.toml"),
+            Language::De => include_str!("../../locales/de.toml"),
+            Language::Zh => include_str!("../../locales/zh.toml"),
+        }
+    }
+
+    fn catalog(self) -> &'static Catalog {
+        &CATALOGS[&self]
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Catalog {
+    /// Report labels by key
+    report: HashMap<String, String>,
+    /// Finding text by vulnerability type, as serialized (`command_injection`)
+    #[serde(default)]
+    findings: HashMap<String, FindingText>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindingText {
+    title: String,
+    impact: String,
+    remediation: String,
+}
+
+static CATALOGS: Lazy<HashMap<Language, Catalog>> = Lazy::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|lang| {
+            let catalog = toml::from_str(lang.source()).expect("built-in locale catalog");
+            (lang, catalog)
+        })
+        .collect()
+});
+
+static LANGUAGE: OnceCell<Language> = OnceCell::new();
+
+/// Set the language of report labels for this process (first call wins)
+pub fn set_language(lang: Language) {
+    let _ = LANGUAGE.set(lang);
+}
+
+/// Language of report labels, English unless set
+pub fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Report label `key` in the current language, falling back to English
+pub fn text(key: &'static str) -> &'static str {
+    [language(), Language::En]
+        .into_iter()
+        .find_map(|lang| lang.catalog().report.get(key))
+        .map_or(key, String::as_str)
+}
+
+/// Replace finding titles, impact, and remediation with `lang`'s text for
+/// their vulnerability type. The detector's English title is kept in
+/// parentheses, since it is usually more specific than the type's.
+pub fn localize(result: &mut ScanResult, lang: Language) {
+    let catalog = lang.catalog();
+    if catalog.findings.is_empty() {
+        return;
+    }
+    for vuln in &mut result.vulnerabilities {
+        let key = serde_json::to_value(&vuln.vuln_type)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default();
+        let Some(text) = catalog.findings.get(&key) else {
+            continue;
+        };
+        vuln.title = format!("{} ({})", text.title, vuln.title);
+        vuln.impact = Some(text.impact.clone());
+        vuln.remediation = Some(text.remediation.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+    #[test]
+    fn test_catalogs_complete() {
+        let english = Language::En.catalog();
+        for lang in Language::ALL {
+            let catalog = lang.catalog();
+            for key in english.report.keys() {
+                assert!(catalog.report.contains_key(key), "{:?} lacks {}", lang, key);
+            }
+            if lang != Language::En {
+                // Every vulnerability type has a translation
+                assert_eq!(catalog.findings.len(), 35, "{:?}", lang);
+            }
+        }
+    }
+
+    #[test]
+    fn test_localize() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        result.add_vulnerabilities(vec![Vulnerability::new(
+            "SEC-001",
+            VulnerabilityType::SqlInjection,
+            Severity::High,
+            "Query built with f-string",
+            "Desc",
+        )
+        .with_remediation("Use cursor.execute with parameters")]);
+        let fingerprint = result.vulnerabilities[0].fingerprint.clone();
+
+        localize(&mut result, Language::En);
+        assert_eq!(result.vulnerabilities[0].title, "Query built with f-string");
+
+        localize(&mut result, Language::De);
+        let vuln = &result.vulnerabilities[0];
+        assert_eq!(vuln.title, "SQL-Injection (Query built with f-string)");
+        assert!(vuln
+            .remediation
+            .as_deref()
+            .unwrap()
+            .contains("parametrisierte"));
+        assert!(vuln.impact.is_some());
+        assert_eq!(vuln.fingerprint, fingerprint);
+    }
+}
@@ -0,0 +1,152 @@
+//! JSONL output generator
+//!
+//! One JSON object per line, tagged by `record`: a `finding` line per
+//! finding, then a single `summary` line with the scan's summary and
+//! metadata. Finding lines are the finding's JSON with the tag added, so the
+//! tag must not be `type`, which findings already use for their kind.
+//!
+//! [`JsonlWriter`] appends finding lines to the report file while the scan
+//! runs, so a crash or OOM late in a long scan leaves everything found so
+//! far on disk. The summary line is only written when the scan finishes;
+//! a report without one is from a scan that never completed.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::models::scan_result::{ScanMetadata, ScanResult, ScanSummary};
+use crate::models::vulnerability::Vulnerability;
+
+#[derive(Serialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum Record<'a> {
+    Finding(&'a Vulnerability),
+    Summary {
+        scan_id: &'a str,
+        target: &'a str,
+        summary: &'a ScanSummary,
+        metadata: &'a ScanMetadata,
+    },
+}
+
+impl<'a> Record<'a> {
+    fn summary(result: &'a ScanResult) -> Self {
+        Record::Summary {
+            scan_id: &result.scan_id,
+            target: &result.target,
+            summary: &result.summary,
+            metadata: &result.metadata,
+        }
+    }
+
+    fn line(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)? + "\n")
+    }
+}
+
+/// Generate a JSONL report of a finished scan
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let mut lines = String::new();
+    for vuln in &result.vulnerabilities {
+        lines.push_str(&Record::Finding(vuln).line()?);
+    }
+    lines.push_str(&Record::summary(result).line()?);
+    Ok(lines)
+}
+
+/// Writes a JSONL report incrementally, one finding at a time
+pub struct JsonlWriter {
+    file: File,
+    /// IDs of findings already written
+    written: HashSet<String>,
+}
+
+impl JsonlWriter {
+    /// Create (or truncate) the report file
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create report '{}'", path.display()))?;
+        Ok(Self {
+            file,
+            written: HashSet::new(),
+        })
+    }
+
+    /// Append a finding, flushed to disk before returning
+    pub fn write_finding(&mut self, vuln: &Vulnerability) -> Result<()> {
+        if !self.written.insert(vuln.id.clone()) {
+            return Ok(());
+        }
+        self.write(&Record::Finding(vuln).line()?)
+    }
+
+    /// Append the findings of `result` not written yet (such as those of
+    /// server-wide analysis) and the summary line
+    pub fn finish(mut self, result: &ScanResult) -> Result<()> {
+        for vuln in &result.vulnerabilities {
+            self.write_finding(vuln)?;
+        }
+        self.write(&Record::summary(result).line()?)
+    }
+
+    fn write(&mut self, line: &str) -> Result<()> {
+        self.file.write_all(line.as_bytes())?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Severity, VulnerabilityType};
+    use serde_json::Value;
+
+    #[test]
+    fn test_incremental_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.jsonl");
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        result.add_vulnerabilities(vec![
+            Vulnerability::new(
+                "SEC-001",
+                VulnerabilityType::HardcodedCredentials,
+                Severity::Critical,
+                "Hardcoded AWS key",
+                "Desc",
+            ),
+            Vulnerability::new(
+                "FLOW-001",
+                VulnerabilityType::ToxicFlow,
+                Severity::High,
+                "Toxic flow",
+                "Desc",
+            ),
+        ]);
+
+        let mut writer = JsonlWriter::create(&path).unwrap();
+        writer.write_finding(&result.vulnerabilities[0]).unwrap();
+        // Lines written so far are on disk without the summary
+        let partial = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(partial.lines().count(), 1);
+
+        writer.finish(&result).unwrap();
+        let lines: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["record"], "finding");
+        assert_eq!(lines[0]["type"], "hardcoded_credentials");
+        assert_eq!(lines[0]["id"], result.vulnerabilities[0].id.as_str());
+        assert!(lines[1]["id"].as_str().unwrap().starts_with("FLOW-"));
+        assert_eq!(lines[2]["record"], "summary");
+        assert_eq!(lines[2]["summary"]["total_issues"], 2);
+
+        assert_eq!(generate(&result).unwrap().lines().count(), 3);
+    }
+}
@@ -1,9 +1,21 @@
 //! Output formatters
 
+#[cfg(feature = "native")]
+pub mod badge;
+pub mod defectdojo;
+#[cfg(feature = "native")]
+pub mod highlight;
+#[cfg(feature = "native")]
+pub mod html;
+pub mod i18n;
 pub mod json;
+#[cfg(feature = "native")]
+pub mod jsonl;
+pub mod ocsf;
+pub mod porcelain;
+#[cfg(feature = "native")]
 pub mod terminal;
 
 // Phase 2+ outputs
-// pub mod html;
 // pub mod pdf;
 // pub mod sarif;
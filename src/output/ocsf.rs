@@ -0,0 +1,238 @@
+//! OCSF output generator
+//!
+//! Events in the Open Cybersecurity Schema Framework (1.1.0), one JSON
+//! object per line, for SIEM pipelines such as Amazon Security Lake:
+//! - scan findings are Vulnerability Findings (class 2002), with the
+//!   fingerprint as `finding_info.uid` and triage as the finding status
+//! - proxy findings and blocked messages are Detection Findings (class
+//!   2004), written by `proxy --audit-format ocsf`
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::{Severity, TriageState, Vulnerability};
+
+/// OCSF schema version the events follow
+pub const SCHEMA_VERSION: &str = "1.1.0";
+
+const FINDINGS_CATEGORY: u32 = 2;
+const VULNERABILITY_FINDING: u32 = 2002;
+const DETECTION_FINDING: u32 = 2004;
+/// Activity of every event: a finding was created
+const CREATE: u32 = 1;
+
+/// Generate an OCSF report: one Vulnerability Finding per line
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let mut lines = String::new();
+    for vuln in &result.vulnerabilities {
+        let event = vulnerability_finding(result, vuln);
+        lines.push_str(&serde_json::to_string(&event)?);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+fn vulnerability_finding(result: &ScanResult, vuln: &Vulnerability) -> Value {
+    let (status_id, status) = match vuln.triage.as_ref().map(|t| t.state) {
+        None | Some(TriageState::Open) => (1, "New"),
+        Some(TriageState::Accepted | TriageState::FalsePositive) => (3, "Suppressed"),
+        Some(TriageState::Fixed) => (4, "Resolved"),
+    };
+
+    let mut vulnerability = json!({
+        "title": vuln.title,
+        "desc": vuln.description,
+        "severity": severity(vuln.severity).1,
+    });
+    if let Some(location) = &vuln.location {
+        let name = location.file.rsplit(['/', '\\']).next().unwrap_or_default();
+        let mut code = json!({ "file": { "name": name, "path": location.file, "type_id": 1 } });
+        if let Some(line) = location.line {
+            code["start_line"] = json!(line);
+        }
+        vulnerability["affected_code"] = json!([code]);
+    }
+    if let Some(remediation) = &vuln.remediation {
+        vulnerability["remediation"] = json!({ "desc": remediation });
+    }
+
+    let mut finding_info = json!({
+        "uid": vuln.fingerprint.as_deref().unwrap_or(&vuln.id),
+        "title": vuln.title,
+        "desc": vuln.description,
+        "types": [vuln.vuln_type.name()],
+    });
+    if let Some(rule) = &vuln.rule_id {
+        finding_info["analytic"] = analytic(rule);
+    }
+
+    let mut event = base(
+        VULNERABILITY_FINDING,
+        "Vulnerability Finding",
+        vuln.severity,
+        result.timestamp.timestamp_millis(),
+    );
+    event["status_id"] = json!(status_id);
+    event["status"] = json!(status);
+    event["confidence_score"] = json!((vuln.confidence * 100.0).round() as u8);
+    event["finding_info"] = finding_info;
+    event["vulnerabilities"] = json!([vulnerability]);
+    event["resources"] = json!([{ "name": result.target, "type": "MCP server" }]);
+    event["metadata"]["correlation_uid"] = json!(result.scan_id);
+    event
+}
+
+/// The Detection Finding for a proxy event, if it is a finding or a block
+#[cfg(feature = "native")]
+pub fn proxy_event(
+    server: Option<&str>,
+    event: &crate::engines::runtime_proxy::events::ProxyEvent,
+) -> Option<Value> {
+    use crate::engines::runtime_proxy::events::ProxyEvent;
+
+    let (direction, violation, blocked) = match event {
+        ProxyEvent::Finding {
+            direction,
+            violation,
+        } => (direction, violation, false),
+        ProxyEvent::Blocked {
+            direction,
+            violation,
+        } => (direction, violation, true),
+        _ => return None,
+    };
+    let (action_id, action) = match blocked {
+        true => (2, "Denied"),
+        false => (1, "Allowed"),
+    };
+    let (disposition_id, disposition) = match blocked {
+        true => (2, "Blocked"),
+        false => (1, "Allowed"),
+    };
+
+    let mut finding = base(
+        DETECTION_FINDING,
+        "Detection Finding",
+        violation.severity,
+        chrono::Utc::now().timestamp_millis(),
+    );
+    finding["message"] = json!(violation.message);
+    finding["action_id"] = json!(action_id);
+    finding["action"] = json!(action);
+    finding["disposition_id"] = json!(disposition_id);
+    finding["disposition"] = json!(disposition);
+    finding["finding_info"] = json!({
+        "uid": uuid::Uuid::new_v4().to_string(),
+        "title": violation.message,
+        "types": [direction.to_string()],
+        "analytic": analytic(&violation.rule),
+    });
+    if let Some(server) = server {
+        finding["resources"] = json!([{ "name": server, "type": "MCP server" }]);
+    }
+    Some(finding)
+}
+
+/// Fields every event has
+fn base(class_uid: u32, class_name: &str, severity_level: Severity, time: i64) -> Value {
+    let (severity_id, severity) = severity(severity_level);
+    json!({
+        "activity_id": CREATE,
+        "activity_name": "Create",
+        "category_uid": FINDINGS_CATEGORY,
+        "category_name": "Findings",
+        "class_uid": class_uid,
+        "class_name": class_name,
+        "type_uid": class_uid * 100 + CREATE,
+        "type_name": format!("{}: Create", class_name),
+        "severity_id": severity_id,
+        "severity": severity,
+        "time": time,
+        "metadata": {
+            "version": SCHEMA_VERSION,
+            "product": {
+                "name": "MCP Sentinel",
+                "vendor_name": "MCP Sentinel",
+                "version": crate::VERSION,
+            },
+        },
+    })
+}
+
+fn analytic(rule: &str) -> Value {
+    json!({ "name": rule, "type_id": 1, "type": "Rule" })
+}
+
+fn severity(severity: Severity) -> (u8, &'static str) {
+    match severity {
+        Severity::Info => (1, "Informational"),
+        Severity::Low => (2, "Low"),
+        Severity::Medium => (3, "Medium"),
+        Severity::High => (4, "High"),
+        Severity::Critical => (5, "Critical"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, VulnerabilityType};
+
+    #[test]
+    fn test_vulnerability_findings() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        result.add_vulnerabilities(vec![Vulnerability::new(
+            "SEC-001",
+            VulnerabilityType::HardcodedCredentials,
+            Severity::High,
+            "Hardcoded AWS key",
+            "Desc",
+        )
+        .with_rule_id("secrets/aws-access-key")
+        .with_location(Location::new("server/config.py").with_line(42))]);
+
+        let report = generate(&result).unwrap();
+        assert_eq!(report.lines().count(), 1);
+        let event: Value = serde_json::from_str(report.trim()).unwrap();
+        assert_eq!(event["class_uid"], 2002);
+        assert_eq!(event["type_uid"], 200201);
+        assert_eq!(event["severity_id"], 4);
+        assert_eq!(event["status"], "New");
+        assert_eq!(
+            event["finding_info"]["uid"].as_str(),
+            result.vulnerabilities[0].fingerprint.as_deref()
+        );
+        assert_eq!(
+            event["finding_info"]["analytic"]["name"],
+            "secrets/aws-access-key"
+        );
+        let code = &event["vulnerabilities"][0]["affected_code"][0];
+        assert_eq!(code["file"]["name"], "config.py");
+        assert_eq!(code["start_line"], 42);
+    }
+
+    #[cfg(feature = "native")]
+    #[test]
+    fn test_proxy_events() {
+        use crate::engines::runtime_proxy::events::{CallOutcome, ProxyEvent};
+        use crate::engines::runtime_proxy::jsonrpc::Violation;
+        use crate::engines::runtime_proxy::Direction;
+
+        let blocked = ProxyEvent::Blocked {
+            direction: Direction::ServerToClient,
+            violation: Violation::new("dlp/aws-key", Severity::Critical, "AWS key in response"),
+        };
+        let event = proxy_event(Some("github"), &blocked).unwrap();
+        assert_eq!(event["class_uid"], 2004);
+        assert_eq!(event["disposition"], "Blocked");
+        assert_eq!(event["severity_id"], 5);
+        assert_eq!(event["resources"][0]["name"], "github");
+
+        let call = ProxyEvent::ToolCall {
+            tool: "read_file".to_string(),
+            outcome: CallOutcome::Forwarded,
+        };
+        assert!(proxy_event(None, &call).is_none());
+    }
+}
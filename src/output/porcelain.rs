@@ -0,0 +1,108 @@
+//! Porcelain output generator
+//!
+//! One line per open finding, for shell scripts and editor plugins:
+//!
+//! ```text
+//! <severity>\t<rule>\t<file>\t<line>
+//! ```
+//!
+//! The format is stable across versions: always four tab-separated fields,
+//! severity in lowercase (`info` to `critical`), the rule id or else the
+//! vulnerability type (`command_injection`), and an empty file or line when
+//! the finding has none. Tabs and line breaks inside fields become spaces.
+//! New information is never added as extra fields; use `--output json`.
+
+use anyhow::Result;
+
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::{Severity, Vulnerability};
+
+/// Generate porcelain output
+pub fn generate(result: &ScanResult) -> Result<String> {
+    let mut lines = String::new();
+    for vuln in result.open_vulnerabilities() {
+        lines.push_str(&line(vuln)?);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+fn line(vuln: &Vulnerability) -> Result<String> {
+    let severity = match vuln.severity {
+        Severity::Info => "info",
+        Severity::Low => "low",
+        Severity::Medium => "medium",
+        Severity::High => "high",
+        Severity::Critical => "critical",
+    };
+    let rule = match &vuln.rule_id {
+        Some(rule) => rule.clone(),
+        None => serde_json::to_value(&vuln.vuln_type)?
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    };
+    let (file, line) = match &vuln.location {
+        Some(location) => (
+            location.file.clone(),
+            location.line.map(|l| l.to_string()).unwrap_or_default(),
+        ),
+        None => (String::new(), String::new()),
+    };
+    Ok([severity, rule.as_str(), file.as_str(), line.as_str()]
+        .map(field)
+        .join("\t"))
+}
+
+fn field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, Triage, TriageState, VulnerabilityType};
+
+    #[test]
+    fn test_porcelain_lines() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        let mut accepted = Vulnerability::new(
+            "SEC-003",
+            VulnerabilityType::PathTraversal,
+            Severity::High,
+            "Accepted",
+            "Desc",
+        );
+        accepted.triage = Some(Triage {
+            state: TriageState::Accepted,
+            reason: None,
+            updated_at: chrono::Utc::now(),
+        });
+        result.add_vulnerabilities(vec![
+            Vulnerability::new(
+                "SEC-001",
+                VulnerabilityType::HardcodedCredentials,
+                Severity::Critical,
+                "Hardcoded AWS key",
+                "Desc",
+            )
+            .with_rule_id("secrets/aws-access-key")
+            .with_location(Location::new("src/my\tconfig.py").with_line(42)),
+            Vulnerability::new(
+                "SEC-002",
+                VulnerabilityType::ToolPoisoning,
+                Severity::Medium,
+                "Hidden instructions",
+                "Desc",
+            ),
+            accepted,
+        ]);
+
+        let output = generate(&result).unwrap();
+        assert_eq!(
+            output,
+            "critical\tsecrets/aws-access-key\tsrc/my config.py\t42\n\
+             medium\ttool_poisoning\t\t\n"
+        );
+    }
+}
@@ -4,16 +4,81 @@ use anyhow::Result;
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
 use crossterm::style::{Color, Stylize};
 
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::detectors::capabilities;
+use crate::detectors::egress::EndpointKind;
 use crate::models::{
-    scan_result::ScanResult,
+    scan_result::{DirectoryRisk, ScanResult, HEATMAP_DEPTH, HOTSPOTS},
     vulnerability::{Severity, Vulnerability},
 };
+use crate::output::highlight;
+use crate::output::i18n::text;
+
+/// What [`render_with`] adds to the default report
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// Show each finding's rule, pattern, and matched span
+    pub explain: bool,
+    /// Headings the open findings are listed under
+    pub group_by: GroupBy,
+    /// Order of the groups and of the findings in each
+    pub sort: SortBy,
+    /// Print only the summary and the most severe findings
+    pub summary_only: bool,
+}
+
+/// Headings the terminal report lists open findings under
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    #[default]
+    Severity,
+    File,
+    Detector,
+}
+
+/// Order of the terminal report's groups and findings
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortBy {
+    /// Groups with the most severe finding first; findings most severe
+    /// first, otherwise in scan order
+    #[default]
+    Severity,
+    /// Groups with the most findings first
+    Count,
+    /// Groups by name, findings by file and line
+    Path,
+}
+
+/// Findings listed by `--summary-only`
+const TOP_FINDINGS: usize = 5;
+
+/// Open findings under one heading of the terminal report
+struct Group<'a> {
+    /// Set when grouping by severity
+    severity: Option<Severity>,
+    label: String,
+    vulns: Vec<&'a Vulnerability>,
+}
 
 /// Render scan results to terminal
 pub fn render(result: &ScanResult) -> Result<()> {
+    render_with(result, &RenderOptions::default())
+}
+
+/// Render scan results to terminal with `options`
+pub fn render_with(result: &ScanResult, options: &RenderOptions) -> Result<()> {
     // Check if colors should be disabled
     let use_color = std::env::var("NO_COLOR").is_err();
 
+    if options.summary_only {
+        print_summary(result, use_color);
+        print_servers(result, use_color);
+        print_top_findings(result, use_color);
+        return Ok(());
+    }
+
     println!();
     print_header(use_color);
     println!();
@@ -24,11 +89,15 @@ pub fn render(result: &ScanResult) -> Result<()> {
     print_separator();
     print_summary(result, use_color);
     print_separator();
+    print_servers(result, use_color);
 
     if !result.vulnerabilities.is_empty() {
         println!();
-        print_vulnerabilities(result, use_color);
+        print_vulnerabilities(result, options, use_color);
     }
+    print_hotspots(result, use_color);
+    print_heatmap(result, use_color);
+    print_capabilities(result, use_color);
 
     println!();
     print_footer(result, use_color);
@@ -56,19 +125,18 @@ fn print_header(use_color: bool) {
 fn print_scan_info(result: &ScanResult, use_color: bool) {
     if use_color {
         println!(
-            "📂 Scanning: {}",
+            "📂 {}: {}",
+            text("scanning"),
             result.target.clone().with(Color::Cyan)
         );
         println!(
-            "🔍 Engines: {}",
-            result
-                .engines
-                .join(" | ")
-                .with(Color::Green)
+            "🔍 {}: {}",
+            text("engines"),
+            result.engines.join(" | ").with(Color::Green)
         );
     } else {
-        println!("📂 Scanning: {}", result.target);
-        println!("🔍 Engines: {}", result.engines.join(" | "));
+        println!("📂 {}: {}", text("scanning"), result.target);
+        println!("🔍 {}: {}", text("engines"), result.engines.join(" | "));
     }
 }
 
@@ -77,93 +145,473 @@ fn print_separator() {
 }
 
 fn print_summary(result: &ScanResult, use_color: bool) {
-    println!("📊 SCAN RESULTS");
+    println!("📊 {}", text("scan_results"));
     println!();
 
     let risk_badge = result.severity_badge();
 
     if use_color {
-        println!("Risk Score: {}/100 {}", result.summary.risk_score, risk_badge);
+        println!(
+            "{}: {}/100 {}",
+            text("risk_score"),
+            result.summary.risk_score,
+            risk_badge
+        );
     } else {
-        println!("Risk Score: {}/100 {}", result.summary.risk_score, risk_badge);
+        println!(
+            "{}: {}/100 {}",
+            text("risk_score"),
+            result.summary.risk_score,
+            risk_badge
+        );
     }
 
     println!();
 
     // Print counts by severity
-    print_severity_count("CRITICAL", result.summary.critical, Severity::Critical, use_color);
+    print_severity_count(
+        "CRITICAL",
+        result.summary.critical,
+        Severity::Critical,
+        use_color,
+    );
     print_severity_count("HIGH", result.summary.high, Severity::High, use_color);
     print_severity_count("MEDIUM", result.summary.medium, Severity::Medium, use_color);
     print_severity_count("LOW", result.summary.low, Severity::Low, use_color);
+    if result.summary.info > 0 {
+        print_severity_count("INFO", result.summary.info, Severity::Info, use_color);
+    }
+    if result.summary.triaged > 0 {
+        println!("✅ {}: {}", text("triaged"), result.summary.triaged);
+    }
+    let fixable = result.fixable_count();
+    if fixable > 0 {
+        println!("🩹 {}: {}", text("fixable"), fixable);
+    }
+}
+
+/// One line for each of the most severe open findings
+fn print_top_findings(result: &ScanResult, use_color: bool) {
+    let top = top_findings(result, TOP_FINDINGS);
+    if top.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{}:", text("top_findings"));
+    for vuln in top {
+        let location = vuln
+            .location
+            .as_ref()
+            .map(|l| format!(" ({})", l.format()))
+            .unwrap_or_default();
+        if use_color {
+            println!(
+                "  {} [{}] {}{}",
+                vuln.severity.to_emoji(),
+                vuln.id.clone().with(Color::Cyan),
+                vuln.title,
+                location.with(Color::DarkGrey)
+            );
+        } else {
+            println!(
+                "  {} [{}] {}{}",
+                vuln.severity.to_emoji(),
+                vuln.id,
+                vuln.title,
+                location
+            );
+        }
+    }
+}
+
+/// The `n` most severe open findings, the more confident first on ties
+fn top_findings(result: &ScanResult, n: usize) -> Vec<&Vulnerability> {
+    let mut vulns: Vec<&Vulnerability> = result.open_vulnerabilities().collect();
+    vulns.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(b.confidence.total_cmp(&a.confidence))
+    });
+    vulns.truncate(n);
+    vulns
 }
 
 fn print_severity_count(label: &str, count: usize, severity: Severity, use_color: bool) {
     let emoji = severity.to_emoji();
 
     if use_color {
-        let color = match severity {
-            Severity::Critical => Color::Red,
-            Severity::High => Color::DarkYellow,
-            Severity::Medium => Color::Yellow,
-            Severity::Low => Color::Blue,
-        };
-
+        let color = severity_color(severity);
         println!(
-            "{} {} Issues: {}",
+            "{} {} {}: {}",
             emoji,
             label.with(color).bold(),
+            text("issues"),
             count.to_string().with(color)
         );
     } else {
-        println!("{} {} Issues: {}", emoji, label, count);
+        println!("{} {} {}: {}", emoji, label, text("issues"), count);
     }
 }
 
-fn print_vulnerabilities(result: &ScanResult, use_color: bool) {
-    // Group by severity and print
-    for severity in &[
-        Severity::Critical,
-        Severity::High,
-        Severity::Medium,
-        Severity::Low,
-    ] {
-        let vulns: Vec<&Vulnerability> = result
-            .vulnerabilities
-            .iter()
-            .filter(|v| &v.severity == severity)
-            .collect();
-
-        if vulns.is_empty() {
-            continue;
-        }
-
+fn print_vulnerabilities(result: &ScanResult, options: &RenderOptions, use_color: bool) {
+    for group in group_vulnerabilities(result, options) {
         print_separator();
-        if use_color {
-            let color = match severity {
-                Severity::Critical => Color::Red,
-                Severity::High => Color::DarkYellow,
-                Severity::Medium => Color::Yellow,
-                Severity::Low => Color::Blue,
-            };
-            println!(
-                "{} {} ISSUES",
+        match group.severity {
+            Some(severity) if use_color => println!(
+                "{} {} {}",
                 severity.to_emoji(),
-                severity.to_badge().with(color).bold()
-            );
-        } else {
-            println!("{} {} ISSUES", severity.to_emoji(), severity.to_badge());
+                severity.to_badge().with(severity_color(severity)).bold(),
+                text("issues_heading")
+            ),
+            Some(severity) => println!(
+                "{} {} {}",
+                severity.to_emoji(),
+                severity.to_badge(),
+                text("issues_heading")
+            ),
+            None => {
+                let emoji = match options.group_by {
+                    GroupBy::Detector => "🔎",
+                    _ => "📄",
+                };
+                if use_color {
+                    println!(
+                        "{} {} ({})",
+                        emoji,
+                        group.label.with(Color::Cyan).bold(),
+                        group.vulns.len()
+                    );
+                } else {
+                    println!("{} {} ({})", emoji, group.label, group.vulns.len());
+                }
+            }
         }
         print_separator();
         println!();
 
-        for vuln in vulns {
-            print_vulnerability(vuln, use_color);
+        for vuln in group.vulns {
+            print_vulnerability(vuln, options, use_color);
             println!();
         }
     }
+
+    print_triaged(result, use_color);
+    print_egress(result, use_color);
+}
+
+/// Open findings grouped and sorted as `options` ask
+fn group_vulnerabilities<'a>(result: &'a ScanResult, options: &RenderOptions) -> Vec<Group<'a>> {
+    let mut groups: Vec<Group<'a>> = Vec::new();
+    for vuln in result.open_vulnerabilities() {
+        let (severity, label) = match options.group_by {
+            GroupBy::Severity => (Some(vuln.severity), vuln.severity.to_badge().to_string()),
+            GroupBy::File => (None, file_of(vuln).to_string()),
+            GroupBy::Detector => (
+                None,
+                match vuln.detector {
+                    Some(detector) => detector.name().to_string(),
+                    None => vuln.vuln_type.name().to_string(),
+                },
+            ),
+        };
+        match groups.iter_mut().find(|g| g.label == label) {
+            Some(group) => group.vulns.push(vuln),
+            None => groups.push(Group {
+                severity,
+                label,
+                vulns: vec![vuln],
+            }),
+        }
+    }
+
+    let worst = |group: &Group| group.vulns.iter().map(|v| v.severity).max();
+    match options.sort {
+        SortBy::Severity => {
+            groups.sort_by(|a, b| worst(b).cmp(&worst(a)).then(a.label.cmp(&b.label)))
+        }
+        SortBy::Count => groups.sort_by(|a, b| {
+            b.vulns
+                .len()
+                .cmp(&a.vulns.len())
+                .then(worst(b).cmp(&worst(a)))
+        }),
+        // Severity groups keep their natural order
+        SortBy::Path if options.group_by == GroupBy::Severity => {
+            groups.sort_by_key(|g| std::cmp::Reverse(worst(g)))
+        }
+        SortBy::Path => groups.sort_by(|a, b| a.label.cmp(&b.label)),
+    }
+    for group in &mut groups {
+        match options.sort {
+            SortBy::Path => group
+                .vulns
+                .sort_by(|a, b| file_of(a).cmp(file_of(b)).then(line_of(a).cmp(&line_of(b)))),
+            _ => group.vulns.sort_by_key(|v| std::cmp::Reverse(v.severity)),
+        }
+    }
+    groups
+}
+
+fn file_of(vuln: &Vulnerability) -> &str {
+    vuln.location.as_ref().map_or("-", |l| l.file.as_str())
+}
+
+fn line_of(vuln: &Vulnerability) -> Option<usize> {
+    vuln.location.as_ref().and_then(|l| l.line)
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Critical => Color::Red,
+        Severity::High => Color::DarkYellow,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Blue,
+        Severity::Info => Color::Grey,
+    }
+}
+
+fn print_triaged(result: &ScanResult, use_color: bool) {
+    let triaged: Vec<&Vulnerability> = result.triaged_vulnerabilities().collect();
+    if triaged.is_empty() {
+        return;
+    }
+
+    print_separator();
+    if use_color {
+        println!("✅ {}", text("triaged_heading").with(Color::Green).bold());
+    } else {
+        println!("✅ {}", text("triaged_heading"));
+    }
+    print_separator();
+    println!();
+
+    for vuln in triaged {
+        let Some(triage) = &vuln.triage else {
+            continue;
+        };
+        let reason = triage
+            .reason
+            .as_ref()
+            .map(|r| format!(" - {}", r))
+            .unwrap_or_default();
+        println!("[{}] {} ({}{})", vuln.id, vuln.title, triage.state, reason);
+    }
+    println!();
+}
+
+fn print_egress(result: &ScanResult, use_color: bool) {
+    if result.egress.is_empty() {
+        return;
+    }
+
+    // Unique hosts per kind, with the number of references
+    let mut hosts: BTreeMap<(EndpointKind, &str), usize> = BTreeMap::new();
+    for endpoint in &result.egress {
+        *hosts
+            .entry((endpoint.kind, endpoint.host.as_str()))
+            .or_default() += 1;
+    }
+
+    print_separator();
+    if use_color {
+        println!("🌐 {}", text("egress_heading").with(Color::Cyan).bold());
+    } else {
+        println!("🌐 {}", text("egress_heading"));
+    }
+    print_separator();
+    println!();
+
+    for ((kind, host), count) in hosts {
+        println!("  {:<12} {} ({}×)", kind.label(), host, count);
+    }
+    println!();
+}
+
+/// Counts and risk score of each server of a monorepo
+fn print_servers(result: &ScanResult, use_color: bool) {
+    if result.servers.is_empty() {
+        return;
+    }
+
+    println!();
+    if use_color {
+        println!("🧩 {}", text("servers_heading").with(Color::Cyan).bold());
+    } else {
+        println!("🧩 {}", text("servers_heading"));
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            text("server"),
+            text("path"),
+            text("risk_score"),
+            Severity::Critical.to_badge(),
+            Severity::High.to_badge(),
+            Severity::Medium.to_badge(),
+            Severity::Low.to_badge(),
+        ]);
+    for server in &result.servers {
+        let summary = &server.summary;
+        table.add_row(vec![
+            server.name.clone(),
+            match server.root.as_str() {
+                "" => ".".to_string(),
+                root => root.to_string(),
+            },
+            summary.risk_score.to_string(),
+            summary.critical.to_string(),
+            summary.high.to_string(),
+            summary.medium.to_string(),
+            summary.low.to_string(),
+        ]);
+    }
+    println!("{}", table);
+    print_separator();
+}
+
+/// The files with the most risk, so reviewers know where to start
+fn print_hotspots(result: &ScanResult, use_color: bool) {
+    let hotspots = result.hotspots(HOTSPOTS);
+    if hotspots.is_empty() {
+        return;
+    }
+
+    println!();
+    print_separator();
+    if use_color {
+        println!("🔥 {}", text("hotspots_heading").with(Color::Cyan).bold());
+    } else {
+        println!("🔥 {}", text("hotspots_heading"));
+    }
+    print_separator();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            text("file"),
+            text("score"),
+            text("findings"),
+            text("worst"),
+        ]);
+    for hotspot in hotspots {
+        table.add_row(vec![
+            hotspot.file,
+            format!("{:.1}", hotspot.score),
+            hotspot.findings.to_string(),
+            format!("{} {}", hotspot.worst.to_emoji(), hotspot.worst.to_badge()),
+        ]);
+    }
+    println!("{}", table);
+}
+
+/// Findings rolled up by directory, each shaded by its share of the risk
+fn print_heatmap(result: &ScanResult, use_color: bool) {
+    let tree = result.directory_risk();
+    if tree.children.is_empty() {
+        return;
+    }
+
+    println!();
+    print_separator();
+    if use_color {
+        println!(
+            "🗺️  {}",
+            text("directories_heading").with(Color::Cyan).bold()
+        );
+    } else {
+        println!("🗺️  {}", text("directories_heading"));
+    }
+    print_separator();
+    print_directory(&tree, tree.score, 0, use_color);
+}
+
+fn print_directory(dir: &DirectoryRisk, total: f32, depth: usize, use_color: bool) {
+    let share = if total > 0.0 { dir.score / total } else { 0.0 };
+    let cells = (share * 10.0).round() as usize;
+    let bar = format!("{}{}", "█".repeat(cells), "░".repeat(10 - cells));
+    let label = format!("{}{}/", "  ".repeat(depth), dir.name());
+    if use_color {
+        let color = match share {
+            s if s >= 0.5 => Color::Red,
+            s if s >= 0.25 => Color::DarkYellow,
+            s if s >= 0.1 => Color::Yellow,
+            _ => Color::Blue,
+        };
+        println!(
+            "  {} {:>7.1}  {} ({})",
+            bar.with(color),
+            dir.score,
+            label,
+            dir.findings
+        );
+    } else {
+        println!("  {} {:>7.1}  {} ({})", bar, dir.score, label, dir.findings);
+    }
+
+    if depth < HEATMAP_DEPTH {
+        for child in &dir.children {
+            print_directory(child, total, depth + 1, use_color);
+        }
+    }
 }
 
-fn print_vulnerability(vuln: &Vulnerability, use_color: bool) {
+/// Capability matrix: what the server can do, with the evidence
+fn print_capabilities(result: &ScanResult, use_color: bool) {
+    if result.capabilities.is_empty() {
+        return;
+    }
+
+    println!();
+    print_separator();
+    if use_color {
+        println!(
+            "🧰 {}",
+            text("capabilities_heading").with(Color::Cyan).bold()
+        );
+    } else {
+        println!("🧰 {}", text("capabilities_heading"));
+    }
+    print_separator();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            text("capability"),
+            text("uses"),
+            text("via"),
+            text("first_seen"),
+        ]);
+    for (capability, uses) in capabilities::matrix(&result.capabilities) {
+        let Some(first) = uses.first() else {
+            table.add_row(vec![capability.label(), "-", "", ""]);
+            continue;
+        };
+        let mut signals: Vec<&str> = uses.iter().map(|u| u.signal.as_str()).collect();
+        signals.sort_unstable();
+        signals.dedup();
+        let via = match signals.len() {
+            0..=3 => signals.join(", "),
+            n => format!("{}, +{} more", signals[..3].join(", "), n - 3),
+        };
+        table.add_row(vec![
+            capability.label().to_string(),
+            uses.len().to_string(),
+            via,
+            format!("{}:{}", first.file, first.line),
+        ]);
+    }
+    println!("{}", table);
+}
+
+fn print_vulnerability(vuln: &Vulnerability, options: &RenderOptions, use_color: bool) {
     // ID and Title
     if use_color {
         println!(
@@ -178,12 +626,41 @@ fn print_vulnerability(vuln: &Vulnerability, use_color: bool) {
     // Location
     if let Some(location) = &vuln.location {
         if use_color {
-            println!("  Location: {}", location.format().with(Color::DarkGrey));
+            println!(
+                "  {}: {}",
+                text("location"),
+                location.format().with(Color::DarkGrey)
+            );
         } else {
-            println!("  Location: {}", location.format());
+            println!("  {}: {}", text("location"), location.format());
         }
     }
 
+    // Fingerprint, for `mcp-sentinel triage`
+    if let Some(fingerprint) = &vuln.fingerprint {
+        if use_color {
+            println!(
+                "  {}: {}",
+                text("fingerprint"),
+                fingerprint.clone().with(Color::DarkGrey)
+            );
+        } else {
+            println!("  {}: {}", text("fingerprint"), fingerprint);
+        }
+    }
+
+    if vuln.auto_fixable {
+        if use_color {
+            println!("  🩹 {}", text("fixable").with(Color::Green));
+        } else {
+            println!("  🩹 {}", text("fixable"));
+        }
+    }
+
+    if options.explain {
+        print_explanation(vuln, use_color);
+    }
+
     println!();
 
     // Description
@@ -193,9 +670,13 @@ fn print_vulnerability(vuln: &Vulnerability, use_color: bool) {
     if let Some(impact) = &vuln.impact {
         println!();
         if use_color {
-            println!("  ⚠️  Impact: {}", impact.with(Color::DarkYellow));
+            println!(
+                "  ⚠️  {}: {}",
+                text("impact"),
+                impact.as_str().with(Color::DarkYellow)
+            );
         } else {
-            println!("  ⚠️  Impact: {}", impact);
+            println!("  ⚠️  {}: {}", text("impact"), impact);
         }
     }
 
@@ -203,20 +684,28 @@ fn print_vulnerability(vuln: &Vulnerability, use_color: bool) {
     if let Some(remediation) = &vuln.remediation {
         println!();
         if use_color {
-            println!("  🔧 Remediation: {}", remediation.with(Color::Green));
+            println!(
+                "  🔧 {}: {}",
+                text("remediation"),
+                remediation.as_str().with(Color::Green)
+            );
         } else {
-            println!("  🔧 Remediation: {}", remediation);
+            println!("  🔧 {}: {}", text("remediation"), remediation);
         }
     }
 
     // Code snippet
     if let Some(snippet) = &vuln.code_snippet {
         println!();
-        println!("  Code:");
-        for line in snippet.lines() {
-            if use_color {
-                println!("    {}", line.with(Color::DarkGrey));
-            } else {
+        println!("  {}:", text("code"));
+        if use_color {
+            let file = vuln.location.as_ref().map_or("", |l| l.file.as_str());
+            let span = highlight::snippet_span(vuln, snippet);
+            for line in highlight::terminal(snippet, file, span) {
+                println!("    {}", line);
+            }
+        } else {
+            for line in snippet.lines() {
                 println!("    {}", line);
             }
         }
@@ -226,15 +715,82 @@ fn print_vulnerability(vuln: &Vulnerability, use_color: bool) {
     if let Some(ai) = &vuln.ai_analysis {
         println!();
         if use_color {
-            println!("  🤖 AI Analysis ({}):", ai.model.with(Color::Magenta));
+            println!(
+                "  🤖 AI Analysis ({}):",
+                ai.model.as_str().with(Color::Magenta)
+            );
         } else {
             println!("  🤖 AI Analysis ({}):", ai.model);
         }
         println!("  {}", ai.explanation);
         println!("  Confidence: {:.0}%", ai.confidence * 100.0);
+        if let Some(patch) = &ai.suggested_patch {
+            println!("  Suggested patch (not applied):");
+            for line in patch.lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+}
+
+/// Rule, pattern, and matched span of a finding, for `scan --explain`
+fn print_explanation(vuln: &Vulnerability, use_color: bool) {
+    let rule = vuln.rule_id.as_deref().unwrap_or(vuln.vuln_type.name());
+    println!("  {}: {}", text("rule"), rule);
+
+    let Some(pattern) = &vuln.pattern else {
+        println!("  {}: {}", text("pattern"), text("pattern_unknown"));
+        return;
+    };
+    println!("  {}: {}", text("pattern"), pattern);
+
+    let Some((line, span)) = matched_span(vuln, pattern) else {
+        return;
+    };
+    let before = line[..span.start].trim_start();
+    let matched = &line[span.clone()];
+    let after = line[span.end..].trim_end();
+    if use_color {
+        println!(
+            "  {}: {}{}{}",
+            text("match"),
+            before,
+            matched.with(Color::Red).bold().underlined(),
+            after
+        );
+    } else {
+        println!("  {}: {}>>{}<<{}", text("match"), before, matched, after);
     }
 }
 
+/// The line `pattern` matched, from the source file if it is still there
+/// or else the code snippet, with the range of the match (the one at the
+/// finding's column if the line has several)
+fn matched_span(vuln: &Vulnerability, pattern: &str) -> Option<(String, Range<usize>)> {
+    let regex = regex::Regex::new(pattern).ok()?;
+    let column = vuln.location.as_ref().and_then(|location| location.column);
+    let source_line = vuln.location.as_ref().and_then(|location| {
+        let content = std::fs::read_to_string(&location.file).ok()?;
+        let line = content.lines().nth(location.line?.checked_sub(1)?)?;
+        Some(line.to_string())
+    });
+    let snippet_lines = vuln
+        .code_snippet
+        .iter()
+        .flat_map(|snippet| snippet.lines().map(str::to_string));
+    source_line
+        .into_iter()
+        .chain(snippet_lines)
+        .find_map(|line| {
+            let span = regex
+                .find_iter(&line)
+                .find(|m| Some(line[..m.start()].chars().count() + 1) == column)
+                .or_else(|| regex.find(&line))?
+                .range();
+            Some((line, span))
+        })
+}
+
 fn print_footer(result: &ScanResult, use_color: bool) {
     let duration = result.metadata.scan_duration_ms;
     let duration_str = if duration < 1000 {
@@ -244,10 +800,61 @@ fn print_footer(result: &ScanResult, use_color: bool) {
     };
 
     if use_color {
-        println!("⏱️  Scan completed in {}", duration_str.with(Color::Green));
+        println!(
+            "⏱️  {} {}",
+            text("scan_completed"),
+            duration_str.with(Color::Green)
+        );
     } else {
-        println!("⏱️  Scan completed in {}", duration_str);
+        println!("⏱️  {} {}", text("scan_completed"), duration_str);
+    }
+}
+
+/// Print where the scan spent its time and what it read or skipped
+///
+/// Goes to stderr, so it can accompany machine-readable reports on stdout.
+pub fn print_stats(result: &ScanResult) {
+    let stats = &result.metadata.stats;
+    let total = result.metadata.scan_duration_ms;
+    let detectors = stats.detectors_ms();
+    let other = total.saturating_sub(stats.discovery_ms + detectors);
+
+    eprintln!("📈 SCAN STATISTICS");
+    eprintln!("  Discovery:  {}ms", stats.discovery_ms);
+    eprintln!("  Detectors:  {}ms", detectors);
+    eprintln!(
+        "  Other:      {}ms (reading files, server-wide analysis)",
+        other
+    );
+    eprintln!("  Total:      {}ms", total);
+    eprintln!();
+    eprintln!(
+        "  Scanned {} files ({} KB)",
+        stats.files_scanned,
+        stats.bytes_scanned / 1024
+    );
+    for (reason, count) in &stats.files_skipped {
+        eprintln!("  Skipped {} files: {}", count, reason.label());
+    }
+
+    if stats.detector_us.is_empty() {
+        return;
+    }
+    let mut timings: Vec<_> = stats.detector_us.iter().collect();
+    timings.sort_by(|a, b| b.1.cmp(a.1));
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec!["Detector", "Time"]);
+    for (detector, us) in timings {
+        table.add_row(vec![
+            detector.id().to_string(),
+            format!("{:.1}ms", *us as f64 / 1000.0),
+        ]);
     }
+    eprintln!();
+    eprintln!("{}", table);
 }
 
 /// Print a simple table of vulnerabilities
@@ -256,7 +863,12 @@ pub fn print_summary_table(result: &ScanResult) {
     table
         .load_preset(UTF8_FULL)
         .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec!["ID", "Severity", "Type", "Location"]);
+        .set_header(vec![
+            text("id"),
+            text("severity"),
+            text("type"),
+            text("location"),
+        ]);
 
     for vuln in &result.vulnerabilities {
         let location = vuln
@@ -281,6 +893,138 @@ mod tests {
     use super::*;
     use crate::models::vulnerability::{Location, Vulnerability, VulnerabilityType};
 
+    #[test]
+    fn test_matched_span() {
+        let vuln = Vulnerability::new(
+            "CMD-001",
+            VulnerabilityType::CommandInjection,
+            Severity::Critical,
+            "Command Injection: os.system() usage",
+            "Desc",
+        )
+        .with_location(Location::new("missing.py").with_line(3))
+        .with_pattern(r"os\.system\s*\(")
+        .with_code_snippet("os.system(cmd)");
+
+        let (line, span) = matched_span(&vuln, vuln.pattern.as_deref().unwrap()).unwrap();
+        assert_eq!(&line[span], "os.system(");
+        assert!(matched_span(&vuln, "subprocess").is_none());
+
+        // Second of two matches on the line
+        let vuln = vuln
+            .with_location(Location::new("missing.py").with_line(3).with_column(17))
+            .with_code_snippet("os.system(a) or os.system(b)");
+        let (_, span) = matched_span(&vuln, vuln.pattern.as_deref().unwrap()).unwrap();
+        assert_eq!(span.start, 16);
+    }
+
+    #[test]
+    fn test_group_vulnerabilities() {
+        let mut result = ScanResult::new("test-target", vec!["static".to_string()]);
+        for (id, severity, file, line) in [
+            ("A-001", Severity::Medium, "b.py", 9),
+            ("A-002", Severity::Critical, "a.py", 4),
+            ("A-003", Severity::Low, "b.py", 2),
+        ] {
+            result.add_vulnerability(
+                Vulnerability::new(
+                    id,
+                    VulnerabilityType::CommandInjection,
+                    severity,
+                    id,
+                    "Desc",
+                )
+                .with_location(Location::new(file).with_line(line)),
+            );
+        }
+        let labels = |options: &RenderOptions| -> Vec<(String, Vec<Severity>)> {
+            group_vulnerabilities(&result, options)
+                .into_iter()
+                .map(|g| (g.label, g.vulns.iter().map(|v| v.severity).collect()))
+                .collect()
+        };
+
+        let by_severity = labels(&RenderOptions::default());
+        assert_eq!(
+            by_severity
+                .iter()
+                .map(|(l, _)| l.as_str())
+                .collect::<Vec<_>>(),
+            vec!["CRITICAL", "MEDIUM", "LOW"]
+        );
+
+        let options = RenderOptions {
+            group_by: GroupBy::File,
+            ..Default::default()
+        };
+        assert_eq!(
+            labels(&options),
+            vec![
+                ("a.py".to_string(), vec![Severity::Critical]),
+                ("b.py".to_string(), vec![Severity::Medium, Severity::Low]),
+            ]
+        );
+
+        let options = RenderOptions {
+            group_by: GroupBy::File,
+            sort: SortBy::Count,
+            ..Default::default()
+        };
+        assert_eq!(labels(&options)[0].0, "b.py");
+
+        // Findings by line
+        let options = RenderOptions {
+            group_by: GroupBy::File,
+            sort: SortBy::Path,
+            ..Default::default()
+        };
+        assert_eq!(labels(&options)[1].1, vec![Severity::Low, Severity::Medium]);
+
+        let options = RenderOptions {
+            group_by: GroupBy::Detector,
+            ..Default::default()
+        };
+        assert_eq!(labels(&options).len(), 1);
+    }
+
+    #[test]
+    fn test_top_findings() {
+        let mut result = ScanResult::new("test-target", vec!["static".to_string()]);
+        for (i, (severity, confidence)) in [
+            (Severity::Low, 1.0),
+            (Severity::High, 0.6),
+            (Severity::High, 0.9),
+            (Severity::Critical, 0.5),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            result.add_vulnerability(
+                Vulnerability::new(
+                    format!("A-{:03}", i),
+                    VulnerabilityType::CommandInjection,
+                    severity,
+                    format!("Finding {}", i),
+                    "Desc",
+                )
+                .with_confidence(confidence),
+            );
+        }
+
+        let top: Vec<&str> = top_findings(&result, 3)
+            .iter()
+            .map(|v| v.title.as_str())
+            .collect();
+        assert_eq!(top, vec!["Finding 3", "Finding 2", "Finding 1"]);
+        assert_eq!(top_findings(&result, TOP_FINDINGS).len(), 4);
+
+        let options = RenderOptions {
+            summary_only: true,
+            ..Default::default()
+        };
+        assert!(render_with(&result, &options).is_ok());
+    }
+
     #[test]
     fn test_render_empty_result() {
         let result = ScanResult::new("test-target", vec!["static".to_string()]);
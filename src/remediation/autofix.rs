@@ -0,0 +1,313 @@
+//! Mechanical auto-fixes
+//!
+//! Only patterns with a single, behaviour-preserving rewrite are fixed:
+//!
+//! | Rule | Rewrite |
+//! |------|---------|
+//! | `deserialization/python-yaml-load-without-safeloader` | `yaml.load(f)` -> `yaml.safe_load(f)` |
+//! | `command_injection/subprocess-call-with-shell-true` | `run("ls -la", shell=True)` -> `run(["ls", "-la"])` |
+//! | `sql_injection/*` | `execute(f"... {x}")` -> `execute("... %s", (x,))` |
+//!
+//! Anything else (dynamic shell commands, string concatenation, ...) needs a
+//! human and is left alone. Fixes work line by line on the flagged line only.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::models::{scan_result::ScanResult, vulnerability::Vulnerability};
+
+static YAML_LOAD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"yaml\.load\s*\("#).unwrap());
+
+static SUBPROCESS_LITERAL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(subprocess\.(?:call|run|Popen)\s*\(\s*)["']([^"']+)["']"#).unwrap()
+});
+static SHELL_TRUE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\s*,\s*shell\s*=\s*True"#).unwrap());
+
+static SQL_FSTRING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(execute\s*\(\s*)f(["'])([^"']*)["']\s*\)"#).unwrap());
+static FSTRING_FIELD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\{([^{}]+)\}"#).unwrap());
+
+/// Characters that make a command string depend on shell parsing
+const SHELL_METACHARACTERS: &[char] = &[
+    '$', '`', '|', ';', '&', '<', '>', '*', '?', '(', ')', '{', '}', '~', '\\', '"', '\'',
+];
+
+/// Rewrite of a single flagged line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineFix {
+    pub replacement: String,
+    pub description: &'static str,
+}
+
+/// Compute the fix for `line` flagged by `rule_id`, if it is mechanical
+pub fn fix_line(rule_id: &str, line: &str) -> Option<LineFix> {
+    match rule_id {
+        "deserialization/python-yaml-load-without-safeloader" => {
+            YAML_LOAD.is_match(line).then(|| LineFix {
+                replacement: YAML_LOAD.replace(line, "yaml.safe_load(").into_owned(),
+                description: "Use yaml.safe_load()",
+            })
+        }
+        "command_injection/subprocess-call-with-shell-true" => fix_subprocess_shell(line),
+        // Any SQL rule, built-in or from a newer bundle, flagging an f-string query
+        rule if rule.starts_with("sql_injection/") => fix_sql_fstring(line),
+        _ => None,
+    }
+}
+
+fn fix_subprocess_shell(line: &str) -> Option<LineFix> {
+    let caps = SUBPROCESS_LITERAL.captures(line)?;
+    let command = &caps[2];
+    if command.contains(SHELL_METACHARACTERS) {
+        return None;
+    }
+
+    let args: Vec<String> = command
+        .split_whitespace()
+        .map(|arg| format!("\"{}\"", arg))
+        .collect();
+    let rewritten = SUBPROCESS_LITERAL.replace(line, |c: &regex::Captures| {
+        format!("{}[{}]", &c[1], args.join(", "))
+    });
+    if !SHELL_TRUE.is_match(&rewritten) {
+        return None;
+    }
+
+    Some(LineFix {
+        replacement: SHELL_TRUE.replace(&rewritten, "").into_owned(),
+        description: "Pass the command as an argument list without shell=True",
+    })
+}
+
+fn fix_sql_fstring(line: &str) -> Option<LineFix> {
+    let caps = SQL_FSTRING.captures(line)?;
+    let quote = &caps[2];
+    let template = &caps[3];
+
+    let params: Vec<&str> = FSTRING_FIELD
+        .captures_iter(template)
+        .map(|c| c.get(1).unwrap().as_str().trim())
+        .collect();
+    if params.is_empty() {
+        return None;
+    }
+
+    let query = FSTRING_FIELD.replace_all(template, "%s");
+    let params = if params.len() == 1 {
+        format!("({},)", params[0])
+    } else {
+        format!("({})", params.join(", "))
+    };
+    let replacement = SQL_FSTRING.replace(line, |c: &regex::Captures| {
+        format!("{}{q}{}{q}, {})", &c[1], query, params, q = quote)
+    });
+
+    Some(LineFix {
+        replacement: replacement.into_owned(),
+        description: "Parameterize the query (adjust %s to your driver's placeholder style)",
+    })
+}
+
+/// All fixes for one file
+#[derive(Debug, Clone)]
+pub struct FilePatch {
+    pub path: PathBuf,
+    pub original: String,
+    pub patched: String,
+    /// Fingerprints of the findings this patch fixes
+    pub fixes: Vec<String>,
+}
+
+impl FilePatch {
+    /// Unified diff of the patch
+    pub fn unified_diff(&self) -> String {
+        let name = self.path.to_string_lossy();
+        TextDiff::from_lines(&self.original, &self.patched)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{}", name), &format!("b/{}", name))
+            .to_string()
+    }
+
+    /// Write the patched content back to disk
+    pub fn apply(&self) -> Result<()> {
+        std::fs::write(&self.path, &self.patched)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+/// Plan fixes for every mechanically fixable finding in `result`
+///
+/// Marks those findings as auto-fixable. Files are re-read from disk, and a
+/// finding is skipped if its line no longer matches the scanned snippet.
+pub fn plan(result: &mut ScanResult) -> Result<Vec<FilePatch>> {
+    let mut by_file: BTreeMap<String, Vec<&mut Vulnerability>> = BTreeMap::new();
+    for vuln in &mut result.vulnerabilities {
+        if let Some(file) = vuln.location.as_ref().map(|l| l.file.clone()) {
+            by_file.entry(file).or_default().push(vuln);
+        }
+    }
+
+    let mut patches = Vec::new();
+    for (file, vulns) in by_file {
+        let path = PathBuf::from(&file);
+        let Ok(original) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut lines: Vec<String> = original.split_inclusive('\n').map(str::to_string).collect();
+        let mut fixes = Vec::new();
+
+        for vuln in vulns {
+            let (Some(rule_id), Some(line_no)) = (
+                vuln.rule_id.as_deref(),
+                vuln.location.as_ref().and_then(|l| l.line),
+            ) else {
+                continue;
+            };
+            let Some(line) = line_no.checked_sub(1).and_then(|i| lines.get_mut(i)) else {
+                continue;
+            };
+
+            let body = line.trim_end_matches(['\r', '\n']);
+            let ending = line[body.len()..].to_string();
+            if vuln
+                .code_snippet
+                .as_deref()
+                .is_some_and(|s| s.trim() != body.trim())
+            {
+                continue; // File changed since the scan
+            }
+            if let Some(fix) = fix_line(rule_id, body) {
+                *line = format!("{}{}", fix.replacement, ending);
+                vuln.auto_fixable = true;
+                fixes.extend(vuln.fingerprint.clone());
+            }
+        }
+
+        let patched = lines.concat();
+        if patched != original {
+            patches.push(FilePatch {
+                path,
+                original,
+                patched,
+                fixes,
+            });
+        }
+    }
+
+    Ok(patches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Location, Severity, VulnerabilityType};
+
+    #[test]
+    fn test_yaml_load_fix() {
+        let fix = fix_line(
+            "deserialization/python-yaml-load-without-safeloader",
+            "    data = yaml.load(stream)",
+        )
+        .unwrap();
+        assert_eq!(fix.replacement, "    data = yaml.safe_load(stream)");
+    }
+
+    #[test]
+    fn test_subprocess_shell_fix() {
+        let rule = "command_injection/subprocess-call-with-shell-true";
+        let fix = fix_line(
+            rule,
+            r#"subprocess.run("git status --short", shell=True, check=True)"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fix.replacement,
+            r#"subprocess.run(["git", "status", "--short"], check=True)"#
+        );
+
+        // Dynamic or shell-dependent commands need a human
+        assert!(fix_line(rule, r#"subprocess.run(f"ls {path}", shell=True)"#).is_none());
+        assert!(fix_line(rule, r#"subprocess.run("ls | wc -l", shell=True)"#).is_none());
+    }
+
+    #[test]
+    fn test_sql_fstring_fix() {
+        let fix = fix_line(
+            "sql_injection/execute-fstring",
+            r#"cursor.execute(f"SELECT * FROM users WHERE id = {user_id} AND org = {org.id}")"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fix.replacement,
+            r#"cursor.execute("SELECT * FROM users WHERE id = %s AND org = %s", (user_id, org.id))"#
+        );
+        assert!(fix_line(
+            "sql_injection/execute-concatenation",
+            r#"cursor.execute("SELECT " + col)"#
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_plan_produces_diff_and_marks_finding() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("loader.py");
+        std::fs::write(&file, "import yaml\n\ndata = yaml.load(stream)\n").unwrap();
+
+        let mut result = ScanResult::new(dir.path().to_string_lossy(), vec![]);
+        result.add_vulnerability(
+            Vulnerability::new(
+                "DESER-001",
+                VulnerabilityType::UnsafeDeserialization,
+                Severity::Critical,
+                "yaml.load",
+                "Desc",
+            )
+            .with_rule_id("deserialization/python-yaml-load-without-safeloader")
+            .with_location(Location::new(file.to_string_lossy()).with_line(3))
+            .with_code_snippet("data = yaml.load(stream)"),
+        );
+
+        let patches = plan(&mut result).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert!(result.vulnerabilities[0].auto_fixable);
+        assert!(patches[0]
+            .unified_diff()
+            .contains("+data = yaml.safe_load(stream)"));
+
+        patches[0].apply().unwrap();
+        assert!(std::fs::read_to_string(&file)
+            .unwrap()
+            .contains("yaml.safe_load"));
+    }
+
+    #[test]
+    fn test_plan_fixes_scanned_sql_fstring() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("users.py");
+        let source = "cursor.execute(f\"SELECT * FROM users WHERE id = {user_id}\")\n";
+        std::fs::write(&file, source).unwrap();
+
+        let path = file.to_string_lossy();
+        let vulns = crate::detectors::scan_content(source, &path, &Default::default());
+        assert!(vulns
+            .iter()
+            .any(|v| v.rule_id.as_deref() == Some("sql_injection/execute-fstring")));
+        let mut result = ScanResult::new(dir.path().to_string_lossy(), vec![]);
+        for vuln in vulns {
+            result.add_vulnerability(vuln);
+        }
+
+        let patches = plan(&mut result).unwrap();
+        assert_eq!(patches.len(), 1);
+        assert_eq!(
+            patches[0].patched,
+            "cursor.execute(\"SELECT * FROM users WHERE id = %s\", (user_id,))\n"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! LLM-proposed patches for findings without a mechanical fix
+//!
+//! The configured LLM sees the function that contains the flagged line and
+//! returns a rewritten version of it. The result is only ever a proposal: it
+//! is attached to the finding as a unified diff for review, and the caller
+//! decides whether to apply it.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use super::autofix::FilePatch;
+use crate::engines::ai_analysis::LlmClient;
+use crate::models::vulnerability::{AiAnalysis, Vulnerability};
+
+/// Longest function (in lines) sent to the LLM
+const MAX_REGION_LINES: usize = 80;
+
+/// Lines of context either side of the finding when no function is found
+const CONTEXT_LINES: usize = 10;
+
+static FUNCTION_START: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(?:export\s+)?(?:pub(?:\([^)]*\))?\s+)?(?:async\s+)?(?:def|function|fn|func)\b|=>\s*\{\s*$"#,
+    )
+    .unwrap()
+});
+static CODE_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?s)```[\w+-]*\r?\n(.*?)```"#).unwrap());
+
+const SYSTEM_PROMPT: &str = "You are a security engineer fixing a vulnerability in an MCP server. \
+Rewrite the given code so the vulnerability is fixed with the smallest possible change. \
+Keep names, signatures, indentation, and behaviour otherwise identical. \
+Reply with the complete rewritten code in a single fenced code block and nothing else.";
+
+/// Ask the LLM for a patch fixing `vuln`
+///
+/// Returns `None` when the finding has no location, the file changed since the
+/// scan, or the model's answer does not change the code. On success the diff
+/// is also stored in the finding's `ai_analysis` so reports can show it.
+pub async fn propose(client: &LlmClient, vuln: &mut Vulnerability) -> Result<Option<FilePatch>> {
+    let Some((file, line_no)) = vuln
+        .location
+        .as_ref()
+        .and_then(|l| Some((l.file.clone(), l.line?)))
+    else {
+        return Ok(None);
+    };
+    let path = PathBuf::from(&file);
+    let original =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", file))?;
+    let lines: Vec<&str> = original.split_inclusive('\n').collect();
+
+    let Some(index) = line_no.checked_sub(1).filter(|&i| i < lines.len()) else {
+        return Ok(None);
+    };
+    if vuln
+        .code_snippet
+        .as_deref()
+        .is_some_and(|s| s.trim() != lines[index].trim())
+    {
+        return Ok(None); // File changed since the scan
+    }
+
+    let region = enclosing_function(&lines, index);
+    let code = lines[region.clone()].concat();
+    let prompt = format!(
+        "Vulnerability: {} ({})\n{}\n\nFile: {} (lines {}-{}, flagged line {})\n\n```\n{}```",
+        vuln.title,
+        vuln.rule_id.as_deref().unwrap_or(&vuln.id),
+        vuln.description,
+        file,
+        region.start + 1,
+        region.end,
+        line_no,
+        code
+    );
+
+    let reply = client.complete(SYSTEM_PROMPT, &prompt).await?;
+    let Some(rewritten) = extract_code_block(&reply) else {
+        return Ok(None);
+    };
+    let mut rewritten = match_line_endings(&rewritten, &code);
+    if code.ends_with('\n') && !rewritten.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    if rewritten.trim() == code.trim() {
+        return Ok(None);
+    }
+
+    let patched = [
+        lines[..region.start].concat(),
+        rewritten,
+        lines[region.end..].concat(),
+    ]
+    .concat();
+    let patch = FilePatch {
+        path,
+        original,
+        patched,
+        fixes: vuln.fingerprint.clone().into_iter().collect(),
+    };
+
+    let diff = patch.unified_diff();
+    match &mut vuln.ai_analysis {
+        Some(ai) => ai.suggested_patch = Some(diff),
+        None => {
+            vuln.ai_analysis = Some(AiAnalysis {
+                model: client.model().to_string(),
+                explanation: "Proposed patch generated by the LLM; review before applying."
+                    .to_string(),
+                confidence: vuln.confidence,
+                suggested_patch: Some(diff),
+            })
+        }
+    }
+    Ok(Some(patch))
+}
+
+/// Line range of the function containing `index`
+///
+/// Indentation based, which covers Python and conventionally formatted
+/// brace languages. Falls back to a fixed window around the line.
+pub fn enclosing_function(lines: &[&str], index: usize) -> Range<usize> {
+    let fallback =
+        index.saturating_sub(CONTEXT_LINES)..(index + CONTEXT_LINES + 1).min(lines.len());
+
+    let lowest = index.saturating_sub(MAX_REGION_LINES);
+    let Some(start) = (lowest..=index)
+        .rev()
+        .find(|&i| FUNCTION_START.is_match(lines[i]))
+    else {
+        return fallback;
+    };
+    let indent = indentation(lines[start]);
+
+    let limit = (start + MAX_REGION_LINES).min(lines.len());
+    let mut end = limit;
+    for (i, line) in lines.iter().enumerate().take(limit).skip(start + 1) {
+        if line.trim().is_empty() || indentation(line) > indent {
+            continue;
+        }
+        // Closing braces at the function's own indentation belong to it
+        end = if line.trim_start().starts_with(['}', ')']) {
+            i + 1
+        } else {
+            i
+        };
+        break;
+    }
+
+    if end <= index {
+        return fallback;
+    }
+    // Drop trailing blank lines
+    while end > index + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+    start..end
+}
+
+/// Contents of the first fenced code block in an LLM reply
+pub fn extract_code_block(reply: &str) -> Option<String> {
+    CODE_BLOCK
+        .captures(reply)
+        .map(|c| c[1].to_string())
+        .filter(|code| !code.trim().is_empty())
+}
+
+/// `code` with CRLF line endings if `original` uses them
+///
+/// Models answer with LF; splicing that into a Windows file would leave it
+/// with mixed endings and every rewritten line in the diff.
+pub fn match_line_endings(code: &str, original: &str) -> String {
+    let lf = code.replace("\r\n", "\n");
+    if original.contains("\r\n") {
+        lf.replace('\n', "\r\n")
+    } else {
+        lf
+    }
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(source: &str) -> Vec<&str> {
+        source.split_inclusive('\n').collect()
+    }
+
+    #[test]
+    fn test_enclosing_python_function() {
+        let source =
+            "import os\n\ndef run(cmd):\n    x = 1\n    os.system(cmd)\n\ndef other():\n    pass\n";
+        let lines = split(source);
+        assert_eq!(enclosing_function(&lines, 4), 2..5);
+    }
+
+    #[test]
+    fn test_enclosing_js_function_includes_closing_brace() {
+        let source = "const a = 1;\nfunction run(cmd) {\n  exec(cmd);\n}\nrun('ls');\n";
+        let lines = split(source);
+        assert_eq!(enclosing_function(&lines, 2), 1..4);
+    }
+
+    #[test]
+    fn test_extract_code_block() {
+        let reply = "Here is the fix:\n```python\nsubprocess.run([cmd])\n```\nDone.";
+        assert_eq!(
+            extract_code_block(reply).as_deref(),
+            Some("subprocess.run([cmd])\n")
+        );
+        assert_eq!(extract_code_block("no code here"), None);
+    }
+
+    #[test]
+    fn test_match_line_endings() {
+        let original = "def run(cmd):\r\n    os.system(cmd)\r\n";
+        let rewritten =
+            extract_code_block("```python\ndef run(cmd):\n    subprocess.run([cmd])\n```").unwrap();
+        assert_eq!(
+            match_line_endings(&rewritten, original),
+            "def run(cmd):\r\n    subprocess.run([cmd])\r\n"
+        );
+        assert_eq!(match_line_endings("a\r\nb\n", "a\nb\n"), "a\nb\n");
+    }
+}
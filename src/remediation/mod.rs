@@ -0,0 +1,9 @@
+//! Remediation helpers
+//!
+//! - `autofix` - Mechanical fixes for well-understood patterns, emitted as
+//!   unified diffs (`scan --fix-dry-run`) or applied in place (`scan --fix`)
+//! - `llm_fix` - LLM-proposed patches for everything else, applied only after
+//!   explicit confirmation (`scan --llm-fix`)
+
+pub mod autofix;
+pub mod llm_fix;
@@ -0,0 +1,199 @@
+//! Signed server allowlist for quarantine mode
+//!
+//! `scan --approve` adds a server's launch command to the allowlist (by
+//! default `~/.mcp-sentinel/allowlist.json`) when the scan passes, and
+//! re-signs the file with a local Ed25519 key. The signature is detached,
+//! at `<path>.sig`, like rule bundles. `proxy --quarantine` only launches
+//! servers whose exact command is on a list with a valid signature.
+//!
+//! The signing key is created on first use. Its public half is written
+//! next to it (`allowlist.pub`) so an organization can ship one allowlist
+//! and public key to every machine and keep the private key elsewhere.
+
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::detectors::bundle;
+
+/// A server that passed a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowedServer {
+    /// Launch command, arguments separated by single spaces
+    pub command: String,
+
+    /// What was scanned to approve it
+    pub scanned: String,
+
+    pub approved_at: DateTime<Utc>,
+}
+
+/// Allowlist document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerAllowlist {
+    pub servers: Vec<AllowedServer>,
+}
+
+impl ServerAllowlist {
+    /// Load an allowlist without checking its signature, returning an empty
+    /// one if the file doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read allowlist '{}'", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid allowlist file '{}'", path.display()))
+    }
+
+    /// Load an allowlist whose signature matches `key`
+    pub fn load_verified(path: &Path, key: &VerifyingKey) -> Result<Self> {
+        let json = std::fs::read(path)
+            .with_context(|| format!("Failed to read allowlist '{}'", path.display()))?;
+        let signature_path = bundle::signature_path(path);
+        let signature = std::fs::read_to_string(&signature_path)
+            .with_context(|| format!("Allowlist '{}' is not signed", path.display()))?;
+        bundle::verify(key, &json, &signature)
+            .with_context(|| format!("Allowlist '{}' failed verification", path.display()))?;
+        serde_json::from_slice(&json)
+            .with_context(|| format!("Invalid allowlist file '{}'", path.display()))
+    }
+
+    /// Save the allowlist and its signature, creating parent directories as
+    /// needed
+    pub fn save_signed(&self, path: &Path, key: &SigningKey) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        let signature = STANDARD.encode(key.sign(json.as_bytes()).to_bytes());
+        std::fs::write(path, &json)
+            .with_context(|| format!("Failed to write allowlist '{}'", path.display()))?;
+        std::fs::write(bundle::signature_path(path), signature)
+            .with_context(|| format!("Failed to write signature of '{}'", path.display()))
+    }
+
+    /// Approve a command, replacing any earlier approval of it
+    pub fn approve(&mut self, command: &[String], scanned: impl Into<String>) {
+        let command = command_line(command);
+        self.servers.retain(|s| s.command != command);
+        self.servers.push(AllowedServer {
+            command,
+            scanned: scanned.into(),
+            approved_at: Utc::now(),
+        });
+    }
+
+    /// The approval of a command, if any
+    pub fn find(&self, command: &[String]) -> Option<&AllowedServer> {
+        let command = command_line(command);
+        self.servers.iter().find(|s| s.command == command)
+    }
+}
+
+/// Where the public half of the signing key at `key_path` lives
+pub fn public_key_path(key_path: &Path) -> PathBuf {
+    key_path.with_extension("pub")
+}
+
+/// Load the signing key, creating it (and its public key file) on first use
+pub fn load_or_create_signing_key(path: &Path) -> Result<SigningKey> {
+    if path.exists() {
+        let encoded = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signing key '{}'", path.display()))?;
+        let seed: [u8; 32] = STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .with_context(|| format!("Invalid signing key '{}'", path.display()))?;
+        return Ok(SigningKey::from_bytes(&seed));
+    }
+
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).context("No entropy source for a signing key")?;
+    let key = SigningKey::from_bytes(&seed);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    write_private(path, &STANDARD.encode(seed))?;
+    std::fs::write(
+        public_key_path(path),
+        STANDARD.encode(key.verifying_key().to_bytes()),
+    )?;
+    Ok(key)
+}
+
+/// Read a base64 Ed25519 public key file
+pub fn load_public_key(path: &Path) -> Result<VerifyingKey> {
+    let encoded = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read public key '{}'", path.display()))?;
+    let bytes: [u8; 32] = STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .with_context(|| format!("Invalid public key '{}'", path.display()))?;
+    VerifyingKey::from_bytes(&bytes)
+        .with_context(|| format!("Invalid public key '{}'", path.display()))
+}
+
+fn command_line(command: &[String]) -> String {
+    command.join(" ")
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("Failed to create signing key '{}'", path.display()))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to create signing key '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("allowlist.json");
+        let key = load_or_create_signing_key(&temp_dir.path().join("allowlist.key")).unwrap();
+        let public =
+            load_public_key(&public_key_path(&temp_dir.path().join("allowlist.key"))).unwrap();
+        assert_eq!(public, key.verifying_key());
+
+        let command = vec!["npx".to_string(), "weather-mcp@1.2.0".to_string()];
+        let mut allowlist = ServerAllowlist::default();
+        allowlist.approve(&command, "./weather");
+        allowlist.save_signed(&path, &key).unwrap();
+
+        let loaded = ServerAllowlist::load_verified(&path, &public).unwrap();
+        assert!(loaded.find(&command).is_some());
+        assert!(loaded
+            .find(&["npx".to_string(), "weather-mcp".to_string()])
+            .is_none());
+
+        // Adding a server by hand breaks the signature
+        let tampered = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("weather-mcp@1.2.0", "evil-mcp");
+        std::fs::write(&path, tampered).unwrap();
+        assert!(ServerAllowlist::load_verified(&path, &public).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Threat-intel feed cache
+//!
+//! IOC feeds listed in `sentinel.toml` are downloaded at the start of a scan
+//! into a cache directory (by default `~/.mcp-sentinel/cache/feeds`), one
+//! file per URL. When a download fails the last cached copy is used, so an
+//! unreachable feed never blocks a scan. In offline mode only cached copies
+//! are used.
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::utils::network;
+
+const FEED_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Cache file for a feed URL
+pub fn cache_file(url: &str, cache_dir: &Path) -> PathBuf {
+    let name: String = Sha256::digest(url.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    cache_dir.join(format!("{}.txt", name))
+}
+
+/// Download `urls` into `cache_dir` and return the cached files available
+///
+/// Feeds that fail to download fall back to their previous copy, or are
+/// skipped with a warning when there is none.
+pub async fn refresh(urls: &[String], cache_dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for url in urls {
+        let path = cache_file(url, cache_dir);
+        match download(url, &path).await {
+            Ok(()) => debug!("Updated feed {} -> {}", url, path.display()),
+            Err(e) if path.is_file() => {
+                warn!("Using cached copy of feed {}: {:#}", url, e);
+            }
+            Err(e) => {
+                warn!("Skipping feed {}: {:#}", url, e);
+                continue;
+            }
+        }
+        files.push(path);
+    }
+    files
+}
+
+async fn download(url: &str, path: &Path) -> Result<()> {
+    network::ensure_online("Refreshing IOC feeds")?;
+    let client = reqwest::Client::builder().timeout(FEED_TIMEOUT).build()?;
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .text()
+        .await?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, body).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unreachable_feed_uses_cached_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let cached = "http://127.0.0.1:9/cached.txt".to_string();
+        let missing = "http://127.0.0.1:9/missing.txt".to_string();
+        std::fs::write(cache_file(&cached, dir.path()), "evil.example\n").unwrap();
+
+        let files = refresh(&[cached.clone(), missing], dir.path()).await;
+        assert_eq!(files, vec![cache_file(&cached, dir.path())]);
+    }
+}
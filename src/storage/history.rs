@@ -0,0 +1,257 @@
+//! Scan history persistence
+//!
+//! Completed scan results are stored in an embedded sled database (by default
+//! `~/.mcp-sentinel/history`) so the daemon and CLI can look up previous runs.
+//!
+//! # Layout
+//!
+//! - `scans` tree: `scan_id` → JSON-encoded [`ScanResult`]
+//! - `by_time` tree: `<timestamp millis, zero-padded>-<scan_id>` → `scan_id`,
+//!   giving chronological iteration without decoding every result
+//!
+//! A store opened [`with_retention`](HistoryStore::with_retention) prunes the
+//! oldest scans after each one it records; `mcp-sentinel gc` prunes on demand.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::debug;
+
+use crate::models::config::RetentionPolicy;
+use crate::models::scan_result::{ScanResult, ScanSummary};
+
+/// Lightweight description of a stored scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub scan_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub target: String,
+    pub summary: ScanSummary,
+}
+
+impl From<&ScanResult> for HistoryEntry {
+    fn from(result: &ScanResult) -> Self {
+        Self {
+            scan_id: result.scan_id.clone(),
+            timestamp: result.timestamp,
+            target: result.target.clone(),
+            summary: result.summary.clone(),
+        }
+    }
+}
+
+/// Persistent store of scan results
+pub struct HistoryStore {
+    scans: sled::Tree,
+    by_time: sled::Tree,
+    retention: Option<RetentionPolicy>,
+}
+
+impl HistoryStore {
+    /// Open (or create) the history database at `path`
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = sled::open(path)
+            .with_context(|| format!("Failed to open history database '{}'", path.display()))?;
+
+        Ok(Self {
+            scans: db.open_tree("scans")?,
+            by_time: db.open_tree("by_time")?,
+            retention: None,
+        })
+    }
+
+    /// Prune to `retention` whenever a scan is recorded
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    /// Store a completed scan result
+    pub fn record(&self, result: &ScanResult) -> Result<()> {
+        let encoded = serde_json::to_vec(result)?;
+        self.scans.insert(result.scan_id.as_bytes(), encoded)?;
+        self.by_time
+            .insert(time_key(result).as_bytes(), result.scan_id.as_bytes())?;
+        self.scans.flush()?;
+
+        if let Some(retention) = &self.retention {
+            let pruned = self.prune(retention)?;
+            if pruned > 0 {
+                debug!("Pruned {} scans from history", pruned);
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete scans older than `retention` allows, then the oldest scans
+    /// until the rest fit its size; returns how many were deleted
+    pub fn prune(&self, retention: &RetentionPolicy) -> Result<usize> {
+        // (by_time key, scan ID) of the scans to delete, oldest first
+        let mut expired = Vec::new();
+        if let Some(cutoff) = retention.cutoff() {
+            let end = format!("{:020}", cutoff.timestamp_millis().max(0));
+            for item in self.by_time.range(..end.as_bytes()) {
+                expired.push(item?);
+            }
+        }
+        if let Some(max_bytes) = retention.max_bytes() {
+            let mut size = 0;
+            for item in self.scans.iter() {
+                size += item?.1.len() as u64;
+            }
+            for (_, scan_id) in &expired {
+                size -= self.stored_size(scan_id)?;
+            }
+            for item in self.by_time.iter().skip(expired.len()) {
+                if size <= max_bytes {
+                    break;
+                }
+                let (key, scan_id) = item?;
+                size -= self.stored_size(&scan_id)?;
+                expired.push((key, scan_id));
+            }
+        }
+
+        for (key, scan_id) in &expired {
+            self.scans.remove(scan_id)?;
+            self.by_time.remove(key)?;
+        }
+        if !expired.is_empty() {
+            self.scans.flush()?;
+        }
+        Ok(expired.len())
+    }
+
+    fn stored_size(&self, scan_id: &[u8]) -> Result<u64> {
+        Ok(self
+            .scans
+            .get(scan_id)?
+            .map_or(0, |bytes| bytes.len() as u64))
+    }
+
+    /// Fetch a stored scan result by ID
+    pub fn get(&self, scan_id: &str) -> Result<Option<ScanResult>> {
+        match self.scans.get(scan_id.as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the most recent scans, newest first
+    pub fn recent(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let mut entries = Vec::new();
+
+        for item in self.by_time.iter().rev() {
+            if entries.len() >= limit {
+                break;
+            }
+            let (_, scan_id) = item?;
+            let scan_id = String::from_utf8_lossy(&scan_id).to_string();
+            if let Some(result) = self.get(&scan_id)? {
+                entries.push(HistoryEntry::from(&result));
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// The most recent scan of `target`
+    pub fn latest_for(&self, target: &str) -> Result<Option<ScanResult>> {
+        for item in self.by_time.iter().rev() {
+            let (_, scan_id) = item?;
+            let Some(result) = self.get(&String::from_utf8_lossy(&scan_id))? else {
+                continue;
+            };
+            if result.target == target {
+                return Ok(Some(result));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Number of stored scans
+    pub fn len(&self) -> usize {
+        self.scans.len()
+    }
+
+    /// Whether the store holds no scans
+    pub fn is_empty(&self) -> bool {
+        self.scans.is_empty()
+    }
+}
+
+fn time_key(result: &ScanResult) -> String {
+    format!(
+        "{:020}-{}",
+        result.timestamp.timestamp_millis().max(0),
+        result.scan_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_list_recent() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::open(temp_dir.path()).unwrap();
+
+        let first = ScanResult::new("first", vec!["static".to_string()]);
+        let mut second = ScanResult::new("second", vec!["static".to_string()]);
+        second.timestamp = first.timestamp + chrono::Duration::seconds(1);
+
+        store.record(&first).unwrap();
+        store.record(&second).unwrap();
+
+        let recent = store.recent(10).unwrap();
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].target, "second");
+        assert_eq!(store.get(&first.scan_id).unwrap().unwrap().target, "first");
+        assert_eq!(
+            store.latest_for("first").unwrap().unwrap().scan_id,
+            first.scan_id
+        );
+        assert!(store.latest_for("third").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_prune() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::open(temp_dir.path()).unwrap();
+
+        let mut old = ScanResult::new("old", vec!["static".to_string()]);
+        old.timestamp = chrono::Utc::now() - chrono::Duration::days(40);
+        let mut older = ScanResult::new("older", vec!["static".to_string()]);
+        older.timestamp = old.timestamp - chrono::Duration::days(1);
+        let recent = ScanResult::new("recent", vec!["static".to_string()]);
+        for result in [&older, &old, &recent] {
+            store.record(result).unwrap();
+        }
+
+        let by_size = RetentionPolicy {
+            max_age_days: None,
+            max_size_mb: Some(0),
+        };
+        let by_age = RetentionPolicy {
+            max_age_days: Some(30),
+            max_size_mb: None,
+        };
+        assert_eq!(store.prune(&RetentionPolicy::default()).unwrap(), 0);
+        assert_eq!(store.prune(&by_age).unwrap(), 2);
+        assert_eq!(store.len(), 1);
+        assert!(store.get(&recent.scan_id).unwrap().is_some());
+        assert_eq!(store.prune(&by_size).unwrap(), 1);
+        assert!(store.is_empty());
+
+        // A store with retention prunes as it records
+        let other_dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::open(other_dir.path())
+            .unwrap()
+            .with_retention(by_age);
+        store.record(&old).unwrap();
+        store.record(&recent).unwrap();
+        assert_eq!(store.recent(10).unwrap().len(), 1);
+    }
+}
@@ -1,6 +1,13 @@
 //! Storage and persistence
 
+pub mod allowlist;
+pub mod feeds;
+pub mod history;
+pub mod offline;
+pub mod schedule;
+pub mod triage;
+pub mod whitelist;
+
 // Phase 3+ storage
-// pub mod whitelist;
 // pub mod state;
 // pub mod cache;
@@ -0,0 +1,181 @@
+//! Offline bundles for air-gapped environments
+//!
+//! `rules export-bundle` packs the data a connected machine has downloaded
+//! into one `.tar.gz`; `rules import-bundle` installs it on a machine that
+//! runs with `--offline`. The archive layout mirrors `~/.mcp-sentinel`:
+//!
+//! - `manifest.json` - [`OfflineManifest`]
+//! - `rules/bundle.json`, `rules/bundle.json.sig` - the installed rule bundle
+//! - `feeds/<hash>.txt` - cached IOC feeds, named by [`feeds::cache_file`]
+//!   so `[ioc] feeds` in `sentinel.toml` resolve to them unchanged
+//!
+//! Importing verifies the rule bundle signature before installing anything
+//! and keeps an installed bundle that is newer than the imported one.
+//!
+//! [`feeds::cache_file`]: super::feeds::cache_file
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::Path;
+use tracing::{debug, warn};
+
+use crate::detectors::bundle::{self, RuleBundle};
+use crate::models::config::AppConfig;
+
+const MANIFEST: &str = "manifest.json";
+
+/// Summary of an offline bundle's contents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OfflineManifest {
+    pub created_at: DateTime<Utc>,
+    /// Version of mcp-sentinel that exported the bundle
+    pub exported_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_bundle_version: Option<u64>,
+    /// Number of cached IOC feeds
+    pub feeds: usize,
+}
+
+/// Write an offline bundle of `app`'s rule bundle and feed cache to `dest`
+pub fn export(app: &AppConfig, dest: &Path) -> Result<OfflineManifest> {
+    let file =
+        File::create(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let rules = app.rule_bundle_path();
+    let rule_bundle_version = if rules.is_file() {
+        // Never hand out a bundle the importer would reject
+        let version = RuleBundle::load_verified(&rules)?.version;
+        archive.append_path_with_name(&rules, "rules/bundle.json")?;
+        archive.append_path_with_name(bundle::signature_path(&rules), "rules/bundle.json.sig")?;
+        Some(version)
+    } else {
+        None
+    };
+
+    let mut feeds = 0;
+    let feed_dir = app.feeds_cache_path();
+    if feed_dir.is_dir() {
+        for entry in std::fs::read_dir(&feed_dir)? {
+            let path = entry?.path();
+            if let (true, Some(name)) = (path.is_file(), path.file_name()) {
+                archive.append_path_with_name(&path, Path::new("feeds").join(name))?;
+                feeds += 1;
+            }
+        }
+    }
+
+    let manifest = OfflineManifest {
+        created_at: Utc::now(),
+        exported_by: env!("CARGO_PKG_VERSION").to_string(),
+        rule_bundle_version,
+        feeds,
+    };
+    let json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    archive.append_data(&mut header, MANIFEST, json.as_slice())?;
+
+    archive.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+/// Install an offline bundle exported by [`export`] into `app`'s directories
+pub fn import(app: &AppConfig, source: &Path) -> Result<OfflineManifest> {
+    let staging = tempfile::tempdir()?;
+    let file =
+        File::open(source).with_context(|| format!("Failed to open {}", source.display()))?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(staging.path())
+        .with_context(|| format!("{} is not an offline bundle", source.display()))?;
+
+    let manifest: OfflineManifest = serde_json::from_slice(
+        &std::fs::read(staging.path().join(MANIFEST))
+            .with_context(|| format!("{} has no {}", source.display(), MANIFEST))?,
+    )?;
+
+    let rules = staging.path().join("rules/bundle.json");
+    if rules.is_file() {
+        let imported = RuleBundle::load_verified(&rules)?;
+        let installed = app.rule_bundle_path();
+        match RuleBundle::load_verified(&installed).map(|b| b.version) {
+            Ok(current) if current >= imported.version => warn!(
+                "Keeping installed rule bundle version {} (bundle has {})",
+                current, imported.version
+            ),
+            _ => {
+                std::fs::create_dir_all(&app.rules_path)?;
+                std::fs::copy(&rules, &installed)?;
+                std::fs::copy(
+                    bundle::signature_path(&rules),
+                    bundle::signature_path(&installed),
+                )?;
+                debug!("Installed rule bundle version {}", imported.version);
+            }
+        }
+    }
+
+    let feeds = staging.path().join("feeds");
+    if feeds.is_dir() {
+        let dest = app.feeds_cache_path();
+        std::fs::create_dir_all(&dest)?;
+        for entry in std::fs::read_dir(&feeds)? {
+            let path = entry?.path();
+            if let Some(name) = path.file_name() {
+                std::fs::copy(&path, dest.join(name))?;
+            }
+        }
+    }
+
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::feeds;
+
+    fn app_in(dir: &Path) -> AppConfig {
+        AppConfig {
+            rules_path: dir.join("rules"),
+            cache_path: dir.join("cache"),
+            ..AppConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_feeds_round_trip() {
+        let connected = tempfile::tempdir().unwrap();
+        let offline = tempfile::tempdir().unwrap();
+        let source = app_in(connected.path());
+        let feed = feeds::cache_file("https://feeds.example/bad.txt", &source.feeds_cache_path());
+        std::fs::create_dir_all(feed.parent().unwrap()).unwrap();
+        std::fs::write(&feed, "evil.example\n").unwrap();
+
+        let archive = connected.path().join("offline.tar.gz");
+        let exported = export(&source, &archive).unwrap();
+        assert_eq!(exported.feeds, 1);
+        assert_eq!(exported.rule_bundle_version, None);
+
+        let target = app_in(offline.path());
+        assert_eq!(import(&target, &archive).unwrap(), exported);
+        let imported = target.feeds_cache_path().join(feed.file_name().unwrap());
+        assert_eq!(std::fs::read_to_string(imported).unwrap(), "evil.example\n");
+    }
+
+    #[test]
+    fn test_unsigned_rule_bundle_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = app_in(dir.path());
+        std::fs::create_dir_all(&app.rules_path).unwrap();
+        std::fs::write(app.rule_bundle_path(), r#"{"version": 99, "rules": []}"#).unwrap();
+        std::fs::write(bundle::signature_path(&app.rule_bundle_path()), "AAAA").unwrap();
+
+        assert!(export(&app, &dir.path().join("offline.tar.gz")).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Scheduled scan persistence
+//!
+//! Scans added with `schedule add` are stored as a JSON document (by default
+//! `~/.mcp-sentinel/schedules.json`) that `schedule run` reads. The daemon
+//! re-reads it before each wait, so schedules added or removed while it
+//! runs take effect without a restart.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::models::vulnerability::Severity;
+use crate::utils::cron::CronSchedule;
+
+/// A scan that runs on a cron expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Short ID, as shown by `schedule list`
+    pub id: String,
+
+    /// When to run (see [`crate::utils::cron`])
+    pub cron: String,
+
+    /// Directory to scan (absolute)
+    pub target: String,
+
+    /// Where regressions are reported (see [`crate::utils::notify`])
+    #[serde(default)]
+    pub notify: Vec<String>,
+
+    /// New findings below this severity are not reported
+    pub min_severity: Severity,
+
+    pub added_at: DateTime<Utc>,
+}
+
+impl Schedule {
+    pub fn new(
+        cron: impl Into<String>,
+        target: impl Into<String>,
+        notify: Vec<String>,
+        min_severity: Severity,
+    ) -> Result<Self> {
+        let cron = cron.into();
+        cron.parse::<CronSchedule>()?;
+        let id = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        Ok(Self {
+            id,
+            cron,
+            target: target.into(),
+            notify,
+            min_severity,
+            added_at: Utc::now(),
+        })
+    }
+
+    pub fn cron_schedule(&self) -> Result<CronSchedule> {
+        self.cron.parse()
+    }
+}
+
+/// Schedules document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleStore {
+    pub schedules: Vec<Schedule>,
+}
+
+impl ScheduleStore {
+    /// Load the schedules, returning none if the file doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schedules '{}'", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid schedules file '{}'", path.display()))
+    }
+
+    /// Save the schedules, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write schedules '{}'", path.display()))
+    }
+
+    /// Remove the schedule with the given ID, returning whether one existed
+    pub fn remove(&mut self, id: &str) -> bool {
+        let before = self.schedules.len();
+        self.schedules.retain(|s| s.id != id);
+        self.schedules.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schedules.json");
+
+        let mut store = ScheduleStore::load(&path).unwrap();
+        assert!(store.schedules.is_empty());
+        let schedule = Schedule::new(
+            "0 3 * * *",
+            "/srv/servers",
+            vec!["slack".to_string()],
+            Severity::Medium,
+        )
+        .unwrap();
+        let id = schedule.id.clone();
+        store.schedules.push(schedule);
+        store.save(&path).unwrap();
+
+        let mut loaded = ScheduleStore::load(&path).unwrap();
+        assert_eq!(loaded, store);
+        assert!(loaded.remove(&id));
+        assert!(!loaded.remove(&id));
+
+        assert!(Schedule::new("every night", "/srv", Vec::new(), Severity::Low).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Triage persistence
+//!
+//! Triage decisions live in the scanned project at `.sentinel/triage.json`,
+//! next to the other per-project scan state, so they are reviewed and
+//! committed with the code. Entries are keyed by finding fingerprint, which
+//! stays stable across scans as long as the flagged code doesn't change.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::scan_result::ScanResult;
+use crate::models::vulnerability::{Triage, TriageState};
+
+/// Triage file location relative to the project root
+pub const TRIAGE_FILE: &str = ".sentinel/triage.json";
+
+/// Triage document: fingerprint -> decision
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TriageStore {
+    #[serde(default)]
+    pub entries: BTreeMap<String, Triage>,
+}
+
+impl TriageStore {
+    /// Triage file path for a project
+    pub fn path_for(project: &Path) -> PathBuf {
+        project.join(TRIAGE_FILE)
+    }
+
+    /// Load a triage file, returning an empty store if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read triage file '{}'", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid triage file '{}'", path.display()))
+    }
+
+    /// Save the triage file, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write triage file '{}'", path.display()))
+    }
+
+    /// Record a decision; `TriageState::Open` removes any existing one
+    pub fn set(&mut self, fingerprint: &str, state: TriageState, reason: Option<String>) {
+        if state == TriageState::Open {
+            self.entries.remove(fingerprint);
+            return;
+        }
+        self.entries.insert(
+            fingerprint.to_string(),
+            Triage {
+                state,
+                reason,
+                updated_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Look up the decision for a fingerprint
+    pub fn get(&self, fingerprint: &str) -> Option<&Triage> {
+        self.entries.get(fingerprint)
+    }
+
+    /// Attach decisions to matching findings and refresh the summary
+    pub fn apply(&self, result: &mut ScanResult) {
+        for vuln in &mut result.vulnerabilities {
+            vuln.triage = vuln
+                .fingerprint
+                .as_deref()
+                .and_then(|fp| self.get(fp))
+                .cloned();
+        }
+        result.update_summary();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Severity, Vulnerability, VulnerabilityType};
+
+    #[test]
+    fn test_triaged_findings_leave_summary() {
+        let mut result = ScanResult::new("server", vec!["static".to_string()]);
+        result.add_vulnerability(
+            Vulnerability::new(
+                "SEC-001",
+                VulnerabilityType::SecretsLeakage,
+                Severity::Critical,
+                "Key",
+                "Desc",
+            )
+            .with_code_snippet("KEY = 'test'"),
+        );
+        let fingerprint = result.vulnerabilities[0].fingerprint.clone().unwrap();
+
+        let mut store = TriageStore::default();
+        store.set(
+            &fingerprint,
+            TriageState::FalsePositive,
+            Some("test fixture".into()),
+        );
+        store.apply(&mut result);
+
+        assert_eq!(result.summary.critical, 0);
+        assert_eq!(result.summary.triaged, 1);
+        assert!(!result.has_issues_at_level(Severity::Low));
+
+        store.set(&fingerprint, TriageState::Open, None);
+        store.apply(&mut result);
+        assert_eq!(result.summary.critical, 1);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = TriageStore::path_for(dir.path());
+
+        let mut store = TriageStore::default();
+        store.set("abc123", TriageState::Accepted, None);
+        store.save(&path).unwrap();
+
+        assert_eq!(TriageStore::load(&path).unwrap(), store);
+    }
+}
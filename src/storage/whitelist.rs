@@ -0,0 +1,124 @@
+//! Whitelist persistence
+//!
+//! Trusted tools and servers are stored as a JSON document (by default
+//! `~/.mcp-sentinel/whitelist.json`). Entries are keyed by content hash so a
+//! tool whose definition changes (rug pull) no longer matches its entry.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A single whitelisted tool or server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhitelistEntry {
+    /// Kind of item ("tool", "server", ...)
+    pub item_type: String,
+
+    /// Display name of the item
+    pub name: String,
+
+    /// Content hash identifying the exact trusted definition
+    pub hash: String,
+
+    /// When the entry was added
+    pub added_at: DateTime<Utc>,
+}
+
+impl WhitelistEntry {
+    pub fn new(
+        item_type: impl Into<String>,
+        name: impl Into<String>,
+        hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            item_type: item_type.into(),
+            name: name.into(),
+            hash: hash.into(),
+            added_at: Utc::now(),
+        }
+    }
+}
+
+/// Whitelist document
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Whitelist {
+    pub entries: Vec<WhitelistEntry>,
+}
+
+impl Whitelist {
+    /// Load a whitelist, returning an empty one if the file doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read whitelist '{}'", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid whitelist file '{}'", path.display()))
+    }
+
+    /// Save the whitelist, creating parent directories as needed
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write whitelist '{}'", path.display()))
+    }
+
+    /// Add an entry, replacing any existing entry with the same hash
+    pub fn add(&mut self, entry: WhitelistEntry) {
+        self.entries.retain(|e| e.hash != entry.hash);
+        self.entries.push(entry);
+    }
+
+    /// Remove the entry with the given hash, returning whether one existed
+    pub fn remove(&mut self, hash: &str) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.hash != hash);
+        self.entries.len() != before
+    }
+
+    /// Check whether a hash is whitelisted
+    pub fn contains(&self, hash: &str) -> bool {
+        self.entries.iter().any(|e| e.hash == hash)
+    }
+
+    /// Merge entries from another whitelist (imported entries win on conflict)
+    pub fn merge(&mut self, other: Whitelist) {
+        for entry in other.entries {
+            self.add(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_replaces_same_hash() {
+        let mut whitelist = Whitelist::default();
+        whitelist.add(WhitelistEntry::new("tool", "read_file", "abc123"));
+        whitelist.add(WhitelistEntry::new("tool", "read_file_v2", "abc123"));
+
+        assert_eq!(whitelist.entries.len(), 1);
+        assert_eq!(whitelist.entries[0].name, "read_file_v2");
+        assert!(whitelist.remove("abc123"));
+        assert!(!whitelist.contains("abc123"));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("nested").join("whitelist.json");
+
+        let mut whitelist = Whitelist::default();
+        whitelist.add(WhitelistEntry::new("server", "github", "def456"));
+        whitelist.save(&path).unwrap();
+
+        assert_eq!(Whitelist::load(&path).unwrap(), whitelist);
+    }
+}
@@ -0,0 +1,486 @@
+//! MCP client configurations on this machine
+//!
+//! Desktop clients keep the servers they launch in a JSON file under the
+//! user's home or config directory. `audit --discover` reads the well-known
+//! ones to list every server the machine would run.
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+use super::package::{Ecosystem, PackageSpec};
+use crate::detectors::cross_server::{self, ConfiguredServer};
+
+/// Directory levels above a server's entry file searched for its manifest
+const MANIFEST_SEARCH_DEPTH: usize = 3;
+
+/// Files that mark the top of a server's source tree
+const PROJECT_MANIFESTS: &[&str] = &[
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "Cargo.toml",
+    "go.mod",
+];
+
+/// A command that downloads a registry package and runs it
+struct Launcher {
+    ecosystem: Ecosystem,
+    /// Options naming the package explicitly (`npx -p pkg bin`)
+    package_options: &'static [&'static str],
+    /// Other options followed by a value
+    value_options: &'static [&'static str],
+}
+
+/// `npx`, `npm exec`, `pnpm dlx`, and `bunx`
+const NPX: Launcher = Launcher {
+    ecosystem: Ecosystem::Npm,
+    package_options: &["-p", "--package"],
+    value_options: &["-c", "--call", "--registry", "--cache", "--userconfig"],
+};
+
+/// `uvx` and `uv tool run`
+const UVX: Launcher = Launcher {
+    ecosystem: Ecosystem::Pypi,
+    package_options: &["--from"],
+    value_options: &[
+        "--with",
+        "--with-editable",
+        "--with-requirements",
+        "--python",
+        "-p",
+        "--index",
+        "--index-url",
+        "--extra-index-url",
+        "--default-index",
+        "--find-links",
+        "-f",
+        "--directory",
+        "--project",
+        "--env-file",
+    ],
+};
+
+/// An MCP client whose configuration can be discovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Client {
+    ClaudeDesktop,
+    Cursor,
+    VsCode,
+    Windsurf,
+    Cline,
+}
+
+impl Client {
+    pub const ALL: &'static [Client] = &[
+        Client::ClaudeDesktop,
+        Client::Cursor,
+        Client::VsCode,
+        Client::Windsurf,
+        Client::Cline,
+    ];
+
+    /// Human-readable name for listings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Client::ClaudeDesktop => "Claude Desktop",
+            Client::Cursor => "Cursor",
+            Client::VsCode => "VS Code",
+            Client::Windsurf => "Windsurf",
+            Client::Cline => "Cline",
+        }
+    }
+
+    /// Files the client may keep its servers in
+    ///
+    /// `home` is the user's home directory, `config` the platform's config
+    /// directory (`~/Library/Application Support` on macOS, `~/.config` on
+    /// Linux, `%APPDATA%` on Windows) and `local` its local data directory
+    /// (`%LOCALAPPDATA%` on Windows).
+    pub fn config_paths(&self, home: &Path, config: &Path, local: &Path) -> Vec<PathBuf> {
+        let vscode_user = config.join("Code").join("User");
+        match self {
+            Client::ClaudeDesktop => {
+                let mut paths = vec![config.join("Claude").join("claude_desktop_config.json")];
+                paths.extend(
+                    packaged_app_data(local, "Claude_")
+                        .map(|roaming| roaming.join("Claude").join("claude_desktop_config.json")),
+                );
+                paths
+            }
+            Client::Cursor => vec![home.join(".cursor").join("mcp.json")],
+            Client::VsCode => vec![
+                vscode_user.join("mcp.json"),
+                vscode_user.join("settings.json"),
+            ],
+            Client::Windsurf => vec![home
+                .join(".codeium")
+                .join("windsurf")
+                .join("mcp_config.json")],
+            Client::Cline => vec![vscode_user
+                .join("globalStorage")
+                .join("saoudrizwan.claude-dev")
+                .join("settings")
+                .join("cline_mcp_settings.json")],
+        }
+    }
+}
+
+/// A client configuration file and the servers it lists
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub client: Client,
+    pub path: PathBuf,
+    pub servers: Vec<ConfiguredServer>,
+}
+
+/// Roaming app data of the Windows Store (MSIX) installs whose package
+/// family starts with `family`
+///
+/// Packaged apps have their `%APPDATA%` writes redirected to
+/// `%LOCALAPPDATA%\Packages\<family>_<publisher id>\LocalCache\Roaming`.
+fn packaged_app_data<'a>(local: &Path, family: &'a str) -> impl Iterator<Item = PathBuf> + 'a {
+    std::fs::read_dir(local.join("Packages"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(move |entry| entry.file_name().to_string_lossy().starts_with(family))
+        .map(|entry| entry.path().join("LocalCache").join("Roaming"))
+}
+
+/// Configurations of the known clients installed for the current user
+pub fn discover() -> Vec<ClientConfig> {
+    let Some(home) = dirs::home_dir() else {
+        warn!("No home directory; cannot look for MCP client configurations");
+        return Vec::new();
+    };
+    // On Windows `%APPDATA%` and `%LOCALAPPDATA%` win, as redirected
+    // profiles may point them away from the known folders
+    let windows_env = |key: &str| {
+        std::env::var_os(key)
+            .filter(|_| cfg!(windows))
+            .map(PathBuf::from)
+    };
+    let config = windows_env("APPDATA")
+        .or_else(dirs::config_dir)
+        .unwrap_or_else(|| home.join(".config"));
+    let local = windows_env("LOCALAPPDATA")
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| home.join(".local").join("share"));
+    discover_in(&home, &config, &local)
+}
+
+/// [`discover`] with explicit home, config and local data directories
+pub fn discover_in(home: &Path, config: &Path, local: &Path) -> Vec<ClientConfig> {
+    let mut found = Vec::new();
+    for client in Client::ALL {
+        for path in client.config_paths(home, config, local) {
+            if !path.is_file() {
+                continue;
+            }
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Cannot read {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let servers = parse(&content, &path);
+            debug!(
+                "{}: {} servers in {}",
+                client.name(),
+                servers.len(),
+                path.display()
+            );
+            found.push(ClientConfig {
+                client: *client,
+                path,
+                servers,
+            });
+        }
+    }
+    found
+}
+
+/// Servers of a client configuration file
+///
+/// VS Code's `settings.json` nests them under `mcp`; everything else uses
+/// the `mcpServers` or `servers` layout of [`cross_server::parse_config`].
+pub fn parse(content: &str, path: &Path) -> Vec<ConfiguredServer> {
+    let file = path.to_string_lossy();
+    let servers = cross_server::parse_config(content, &file);
+    if !servers.is_empty() {
+        return servers;
+    }
+    serde_json::from_str::<Value>(content)
+        .ok()
+        .and_then(|settings| settings.get("mcp").map(Value::to_string))
+        .map(|mcp| cross_server::parse_config(&mcp, &file))
+        .unwrap_or_default()
+}
+
+/// Source directory of a stdio server launched from local files
+///
+/// The first absolute argument naming an existing file or directory is
+/// taken (`node /opt/weather/dist/index.js`), and a file is widened to the
+/// closest directory up to three levels above it with a project manifest,
+/// or its own directory.
+pub fn local_root(server: &ConfiguredServer) -> Option<PathBuf> {
+    let path = server
+        .command
+        .iter()
+        .chain(&server.args)
+        .map(Path::new)
+        .find(|path| path.is_absolute() && path.exists())?;
+    if path.is_dir() {
+        return Some(path.to_path_buf());
+    }
+
+    let dir = path.parent()?;
+    let project = dir
+        .ancestors()
+        .take(MANIFEST_SEARCH_DEPTH + 1)
+        .find(|ancestor| {
+            PROJECT_MANIFESTS
+                .iter()
+                .any(|manifest| ancestor.join(manifest).is_file())
+        });
+    Some(project.unwrap_or(dir).to_path_buf())
+}
+
+/// Registry package a stdio server is launched from
+///
+/// Recognizes `npx -y pkg@1.2.3`, `uvx --from pkg==0.6 tool`, their
+/// `npm exec`/`pnpm dlx`/`bunx`/`uv tool run` spellings, and the
+/// `cmd /c npx ...` wrapper common on Windows. Local paths, URLs, and git
+/// references are not registry packages and yield `None`. The version is
+/// `None` when the launcher would run the latest release.
+pub fn launched_package(server: &ConfiguredServer) -> Option<PackageSpec> {
+    let mut words = server
+        .command
+        .iter()
+        .chain(&server.args)
+        .map(String::as_str);
+    let mut program = program_name(words.next()?);
+    if program == "cmd" {
+        words.next().filter(|w| w.eq_ignore_ascii_case("/c"))?;
+        program = program_name(words.next()?);
+    }
+    let launcher = match program.as_str() {
+        "npx" | "bunx" => NPX,
+        "npm" if words.next() == Some("exec") => NPX,
+        "pnpm" if words.next() == Some("dlx") => NPX,
+        "uvx" => UVX,
+        "uv" if words.next() == Some("tool") && words.next() == Some("run") => UVX,
+        _ => return None,
+    };
+
+    let mut explicit = None;
+    let mut positional = None;
+    while let Some(word) = words.next() {
+        if word == "--" {
+            positional = words.next();
+            break;
+        }
+        let (option, value) = match word.split_once('=') {
+            Some((option, value)) if word.starts_with("--") => (option, Some(value)),
+            _ => (word, None),
+        };
+        if launcher.package_options.contains(&option) {
+            explicit = Some(value.or_else(|| words.next())?);
+        } else if launcher.value_options.contains(&option) {
+            if value.is_none() {
+                words.next();
+            }
+        } else if !word.starts_with('-') {
+            positional = Some(word);
+            break;
+        }
+    }
+
+    let spec = explicit.or(positional).filter(|spec| !spec.is_empty())?;
+    let is_registry_name = !spec.contains([':', '\\'])
+        && !spec.starts_with('.')
+        && !Path::new(spec).is_absolute()
+        && (spec.starts_with('@') || !spec.contains('/'));
+    if !is_registry_name {
+        return None;
+    }
+    let (name, version) = match launcher.ecosystem {
+        Ecosystem::Npm => {
+            // The `@` of a scope is not the version separator
+            let scope = usize::from(spec.starts_with('@'));
+            match spec[scope..].find('@') {
+                Some(at) => (&spec[..scope + at], Some(&spec[scope + at + 1..])),
+                None => (spec, None),
+            }
+        }
+        Ecosystem::Pypi => {
+            let end = spec
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(spec.len());
+            let rest = spec[end..].trim_start();
+            let rest = match rest.strip_prefix('[') {
+                Some(extras) => extras.split_once(']').map_or("", |(_, rest)| rest),
+                None => rest,
+            };
+            let version = rest
+                .strip_prefix("==")
+                .or_else(|| rest.strip_prefix('@'))
+                .map(str::trim);
+            (&spec[..end], version)
+        }
+    };
+    if name.is_empty() {
+        return None;
+    }
+    let version = version
+        .filter(|v| !v.is_empty() && *v != "latest")
+        .map(str::to_string);
+    Some(PackageSpec::new(launcher.ecosystem, name, version))
+}
+
+/// Lowercase file stem of a program (`C:\nodejs\npx.cmd` -> `npx`)
+fn program_name(program: &str) -> String {
+    let base = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    let stem = base.rsplit_once('.').map_or(base, |(stem, _)| stem);
+    stem.to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_client_configs() {
+        let home = tempfile::tempdir().unwrap();
+        let config = home.path().join(".config");
+
+        let server = home.path().join("src/weather");
+        std::fs::create_dir_all(server.join("dist")).unwrap();
+        std::fs::write(server.join("package.json"), "{}").unwrap();
+        std::fs::write(server.join("dist/index.js"), "").unwrap();
+        let entry = server.join("dist/index.js");
+
+        std::fs::create_dir_all(home.path().join(".cursor")).unwrap();
+        std::fs::write(
+            home.path().join(".cursor/mcp.json"),
+            serde_json::json!({
+                "mcpServers": {
+                    "weather": {"command": "node", "args": [entry]},
+                    "github": {"url": "https://api.example.com/mcp"}
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::fs::create_dir_all(config.join("Code/User")).unwrap();
+        std::fs::write(
+            config.join("Code/User/settings.json"),
+            r#"{"editor.tabSize": 2, "mcp": {"servers": {"fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}}}}"#,
+        )
+        .unwrap();
+
+        let local = home.path().join("AppData/Local");
+        let packaged = local.join("Packages/Claude_pzs8sxrjxfjjc/LocalCache/Roaming/Claude");
+        std::fs::create_dir_all(&packaged).unwrap();
+        std::fs::write(
+            packaged.join("claude_desktop_config.json"),
+            "{\r\n  \"mcpServers\": {\r\n    \"files\": {\"command\": \"npx\", \"args\": [\"-y\", \"@modelcontextprotocol/server-filesystem\", \"C:\\\\Users\\\\me\"]}\r\n  }\r\n}\r\n",
+        )
+        .unwrap();
+
+        let found = discover_in(home.path(), &config, &local);
+        let clients: Vec<(Client, usize)> =
+            found.iter().map(|c| (c.client, c.servers.len())).collect();
+        assert_eq!(
+            clients,
+            vec![
+                (Client::ClaudeDesktop, 1),
+                (Client::Cursor, 2),
+                (Client::VsCode, 1)
+            ]
+        );
+
+        let servers = &found[1].servers;
+        let weather = servers.iter().find(|s| s.name == "weather").unwrap();
+        assert_eq!(local_root(weather), Some(server));
+        let github = servers.iter().find(|s| s.name == "github").unwrap();
+        assert_eq!(local_root(github), None);
+        assert_eq!(local_root(&found[2].servers[0]), None);
+    }
+
+    #[test]
+    fn test_launched_package() {
+        let package = |command: &str| {
+            let mut words = command.split_whitespace().map(str::to_string);
+            let server = ConfiguredServer {
+                name: "server".to_string(),
+                command: words.next(),
+                args: words.collect(),
+                url: None,
+                config_file: "mcp.json".to_string(),
+            };
+            launched_package(&server).map(|spec| spec.requirement())
+        };
+
+        assert_eq!(
+            package("npx -y @modelcontextprotocol/server-filesystem /tmp").as_deref(),
+            Some("@modelcontextprotocol/server-filesystem")
+        );
+        assert_eq!(
+            package("npx --yes weather-mcp@1.2.0").as_deref(),
+            Some("weather-mcp@1.2.0")
+        );
+        assert_eq!(
+            package("cmd /c npx -y @acme/mcp@latest").as_deref(),
+            Some("@acme/mcp")
+        );
+        assert_eq!(
+            package("npx -p @acme/tools@2.0.1 acme-mcp").as_deref(),
+            Some("@acme/tools@2.0.1")
+        );
+        assert_eq!(
+            package("C:\\nodejs\\npx.cmd -y pkg").as_deref(),
+            Some("pkg")
+        );
+        assert_eq!(package("npm exec -y -- pkg@3").as_deref(), Some("pkg@3"));
+        assert_eq!(
+            package("uvx mcp-server-fetch").as_deref(),
+            Some("mcp-server-fetch")
+        );
+        assert_eq!(
+            package("uvx --python 3.12 mcp-server-git@0.6.2 --repository .").as_deref(),
+            Some("mcp-server-git==0.6.2")
+        );
+        assert_eq!(
+            package("uvx --from mcp-server-time[cli]==0.6.2 mcp-server-time").as_deref(),
+            Some("mcp-server-time==0.6.2")
+        );
+        assert_eq!(
+            package("uv tool run --from=mcp-proxy mcp-proxy").as_deref(),
+            Some("mcp-proxy")
+        );
+
+        assert_eq!(package("node /opt/weather/index.js"), None);
+        assert_eq!(package("npx -y github:acme/mcp"), None);
+        assert_eq!(package("npx -y ./local-server"), None);
+        assert_eq!(
+            package("uvx --from git+https://github.com/acme/mcp mcp"),
+            None
+        );
+        assert_eq!(package("npx -y"), None);
+        assert_eq!(
+            package("npx -y éclair-mcp@1.0").as_deref(),
+            Some("éclair-mcp@1.0")
+        );
+        let blank = ConfiguredServer {
+            name: "server".to_string(),
+            command: Some("npx".to_string()),
+            args: vec!["-y".to_string(), String::new()],
+            url: None,
+            config_file: "mcp.json".to_string(),
+        };
+        assert_eq!(launched_package(&blank), None);
+    }
+}
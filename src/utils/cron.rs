@@ -0,0 +1,206 @@
+//! Cron expressions
+//!
+//! The five standard fields - minute, hour, day of month, month, day of
+//! week - each `*`, a number, a range (`1-5`), a list (`1,15`), or a step
+//! (`*/15`, `0-30/10`). Months and weekdays also take three-letter names
+//! (`jan`, `mon`), and Sunday is both 0 and 7. When day of month and day of
+//! week are both restricted, a day matching either runs, as in Vixie cron.
+//! `@hourly`, `@daily`, `@weekly`, `@monthly`, and `@yearly` stand for the
+//! usual expressions. Times are local.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone};
+
+/// How far ahead to look for the next run before giving up (`0 0 30 2 *`
+/// never runs)
+const SEARCH_DAYS: i64 = 366 * 5;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Day of month is `*`
+    any_day: bool,
+    /// Day of week is `*`
+    any_weekday: bool,
+}
+
+impl std::str::FromStr for CronSchedule {
+    type Err = anyhow::Error;
+
+    fn from_str(expression: &str) -> Result<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            bail!(
+                "Invalid cron expression '{}': expected 5 fields (minute hour day month weekday)",
+                expression
+            );
+        };
+        let parse = |field: &str, min: u32, max: u32, names: &[&str]| {
+            parse_field(field, min, max, names)
+                .with_context(|| format!("Invalid cron expression '{}'", expression))
+        };
+        let mut weekdays = parse(weekday, 0, 7, &WEEKDAYS)?;
+        // Sunday is 0 and 7
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+        Ok(Self {
+            minutes: parse(minute, 0, 59, &[])?,
+            hours: parse(hour, 0, 23, &[])?,
+            days: parse(day, 1, 31, &[])?,
+            months: parse(month, 1, 12, &MONTHS)?,
+            weekdays,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+impl CronSchedule {
+    /// The first time after `after` the schedule runs, if any in the next
+    /// few years
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.date_naive();
+        for offset in 0..SEARCH_DAYS {
+            let date = start + Duration::days(offset);
+            if !self.runs_on(date) {
+                continue;
+            }
+            for hour in bits(self.hours) {
+                for minute in bits(self.minutes) {
+                    let Some(time) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    // Times skipped by a daylight saving change do not run
+                    let Some(time) = Local.from_local_datetime(&time).earliest() else {
+                        continue;
+                    };
+                    if time > after {
+                        return Some(time);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn runs_on(&self, date: NaiveDate) -> bool {
+        let day = has(self.days, date.day());
+        let weekday = has(self.weekdays, date.weekday().num_days_from_sunday());
+        let matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        matches && has(self.months, date.month())
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn bits(set: u64) -> impl Iterator<Item = u32> {
+    (0..64).filter(move |&bit| has(set, bit))
+}
+
+/// A field as a bit set of the values it allows
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64> {
+    let value = |text: &str| -> Result<u32> {
+        let named = names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(text))
+            .map(|i| i as u32 + min);
+        let value = match named {
+            Some(value) => value,
+            None => text
+                .parse()
+                .with_context(|| format!("'{}' is not a number", text))?,
+        };
+        if !(min..=max).contains(&value) {
+            bail!("{} is outside {}-{}", value, min, max);
+        }
+        Ok(value)
+    };
+
+    let mut set = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().context("invalid step")?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            bail!("step of 0 in '{}'", part);
+        }
+        let (first, last) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((first, last)) => (value(first)?, value(last)?),
+                // `5/10` runs from 5 to the end
+                None if part.contains('/') => (value(range)?, max),
+                None => (value(range)?, value(range)?),
+            },
+        };
+        if first > last {
+            bail!("range {} is backwards", range);
+        }
+        for value in (first..=last).step_by(step) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_run() {
+        let at = |y, mo, d, h, mi| Local.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap();
+        let cron = |expression: &str| expression.parse::<CronSchedule>().unwrap();
+
+        let daily = cron("0 3 * * *");
+        assert_eq!(
+            daily.next_after(at(2024, 5, 1, 2, 59)),
+            Some(at(2024, 5, 1, 3, 0))
+        );
+        assert_eq!(
+            daily.next_after(at(2024, 5, 1, 3, 0)),
+            Some(at(2024, 5, 2, 3, 0))
+        );
+        // 2024-05-04 is a Saturday
+        assert_eq!(
+            cron("*/15 9-17 * * mon-fri").next_after(at(2024, 5, 3, 17, 50)),
+            Some(at(2024, 5, 6, 9, 0))
+        );
+        // Either day field matches when both are restricted
+        assert_eq!(
+            cron("0 0 15 * 0").next_after(at(2024, 5, 1, 0, 0)),
+            Some(at(2024, 5, 5, 0, 0))
+        );
+        assert_eq!(cron("@monthly"), cron("0 0 1 * *"));
+        assert_eq!(cron("0 0 30 2 *").next_after(at(2024, 5, 1, 0, 0)), None);
+
+        assert!("0 3 * *".parse::<CronSchedule>().is_err());
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+    }
+}
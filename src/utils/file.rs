@@ -4,6 +4,9 @@ use anyhow::Result;
 use std::path::Path;
 use walkdir::WalkDir;
 
+/// Average line length above which JavaScript is treated as minified
+pub const MINIFIED_AVG_LINE_CHARS: usize = 200;
+
 /// Discover files to scan in a directory
 pub fn discover_files(path: &Path, exclude_patterns: &[String]) -> Result<Vec<std::path::PathBuf>> {
     let mut files = Vec::new();
@@ -25,11 +28,22 @@ pub fn discover_files(path: &Path, exclude_patterns: &[String]) -> Result<Vec<st
                 continue;
             }
 
+            if crate::detectors::dockerfile::is_dockerfile(&path_str)
+                || crate::detectors::secrets::is_config_file(&path_str)
+                || crate::detectors::dependencies::is_manifest(&path_str)
+            {
+                files.push(path.to_path_buf());
+                continue;
+            }
+
             // Only scan text files (Python, JavaScript, TypeScript, etc.)
             if let Some(ext) = path.extension() {
                 match ext.to_str() {
                     Some("py") | Some("js") | Some("ts") | Some("jsx") | Some("tsx")
-                    | Some("json") | Some("yaml") | Some("yml") => {
+                    | Some("json") | Some("yaml") | Some("yml") | Some("java") | Some("php")
+                    | Some("c") | Some("cc") | Some("cpp") | Some("h") | Some("cs")
+                    | Some("rs") | Some("sh") | Some("bash") | Some("zsh") | Some("tf")
+                    | Some("tfvars") => {
                         files.push(path.to_path_buf());
                     }
                     _ => {}
@@ -46,6 +60,61 @@ pub fn read_file(path: &Path) -> Result<String> {
     Ok(std::fs::read_to_string(path)?)
 }
 
+/// Whether a JavaScript file is a minified bundle: a `.min.js` name, or an
+/// average line length over [`MINIFIED_AVG_LINE_CHARS`]
+///
+/// Patterns report one finding per line, so scanning a bundle turns every
+/// dependency it inlines into findings on the same few lines.
+pub fn is_minified(file_path: &str, content: &str) -> bool {
+    let name = file_path.to_ascii_lowercase();
+    if !name.ends_with(".js") {
+        return false;
+    }
+    name.ends_with(".min.js")
+        || content.len() / content.lines().count().max(1) > MINIFIED_AVG_LINE_CHARS
+}
+
+/// The original sources embedded in a bundle's source map, as
+/// `(path, content)` pairs
+///
+/// The map is found through the bundle's `//# sourceMappingURL=` comment
+/// (relative file URLs only) or next to it as `<bundle>.map`. Each source is
+/// named `<map path>/<source>` so findings point into the map while keeping
+/// the source's extension. Returns `None` when there is no readable map or it
+/// has no `sourcesContent`.
+pub fn sourcemap_sources(bundle: &Path, content: &str) -> Option<Vec<(String, String)>> {
+    let referenced = content
+        .lines()
+        .rev()
+        .find_map(|line| line.trim().strip_prefix("//# sourceMappingURL="))
+        .map(str::trim)
+        .filter(|url| !url.contains(':'))
+        .and_then(|url| Some(bundle.parent()?.join(url)));
+    let sibling = bundle.with_file_name(format!("{}.map", bundle.file_name()?.to_str()?));
+    let map_path = referenced.filter(|p| p.is_file()).unwrap_or(sibling);
+
+    let map: serde_json::Value = serde_json::from_str(&read_file(&map_path).ok()?).ok()?;
+    let names = map.get("sources")?.as_array()?;
+    let contents = map.get("sourcesContent")?.as_array()?;
+
+    let map_path = map_path.to_string_lossy();
+    let sources: Vec<(String, String)> = names
+        .iter()
+        .zip(contents)
+        .filter_map(|(name, content)| {
+            // `webpack:///./src/server.ts` -> `src/server.ts`
+            let name = name.as_str()?;
+            let name = name.split_once(":///").map_or(name, |(_, rest)| rest);
+            let name = name.trim_start_matches("./");
+            Some((
+                format!("{}/{}", map_path, name),
+                content.as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    (!sources.is_empty()).then_some(sources)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,4 +125,40 @@ mod tests {
         let files = discover_files(temp_dir.path(), &[]).unwrap();
         assert_eq!(files.len(), 0);
     }
+
+    #[test]
+    fn test_is_minified() {
+        let bundle = format!("!function(e){{{}}}();", "var a=1;".repeat(100));
+        assert!(is_minified("dist/app.js", &bundle));
+        assert!(is_minified("vendor/jquery.min.js", "var a = 1;\n"));
+        assert!(!is_minified(
+            "src/server.js",
+            "const a = 1;\nconst b = 2;\n"
+        ));
+        assert!(!is_minified("data.json", &bundle));
+    }
+
+    #[test]
+    fn test_sourcemap_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("app.js");
+        std::fs::write(
+            dir.path().join("app.js.map"),
+            r#"{"version":3,"sources":["webpack:///./src/server.ts","webpack:///./src/gen.ts"],
+                "sourcesContent":["exec(req.body.cmd)",null]}"#,
+        )
+        .unwrap();
+
+        let sources = sourcemap_sources(&bundle, "!function(){}();").unwrap();
+        assert_eq!(sources.len(), 1);
+        assert!(sources[0].0.ends_with("app.js.map/src/server.ts"));
+        assert_eq!(sources[0].1, "exec(req.body.cmd)");
+
+        let inline = sourcemap_sources(
+            &bundle,
+            "//# sourceMappingURL=data:application/json;base64,e30=",
+        );
+        assert!(inline.is_some(), "falls back to the sibling map");
+        assert!(sourcemap_sources(&dir.path().join("other.js"), "").is_none());
+    }
 }
@@ -0,0 +1,190 @@
+//! Prometheus metrics
+//!
+//! Process-wide counters and histograms for MCP security events. Long-running
+//! modes (monitor, proxy, daemon) expose them on `/metrics` so existing
+//! observability stacks can scrape and alert on them.
+//!
+//! # Exported Metrics
+//!
+//! - `mcp_sentinel_scans_total` - Completed scans
+//! - `mcp_sentinel_scan_duration_seconds` - Scan wall time histogram
+//! - `mcp_sentinel_findings_total{severity}` - Findings by severity
+//! - `mcp_sentinel_messages_scanned_total` - Proxied JSON-RPC messages inspected
+//! - `mcp_sentinel_blocked_calls_total` - Proxied calls blocked by policy
+//! - `mcp_sentinel_message_latency_seconds` - Added latency per proxied message
+
+use anyhow::Result;
+use axum::{http::header, response::IntoResponse, routing::get, Router};
+use once_cell::sync::Lazy;
+use prometheus::{
+    Histogram, HistogramOpts, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tracing::info;
+
+use crate::models::{scan_result::ScanResult, vulnerability::Severity};
+
+/// Registry holding all MCP Sentinel metrics
+///
+/// A dedicated registry (rather than the prometheus default) keeps the
+/// exported set limited to our own metrics when embedded in other tools.
+static REGISTRY: Lazy<Registry> =
+    Lazy::new(|| Registry::new_custom(Some("mcp_sentinel".to_string()), None).unwrap());
+
+static SCANS_TOTAL: Lazy<IntCounter> =
+    Lazy::new(|| register(IntCounter::new("scans_total", "Number of completed scans").unwrap()));
+
+static SCAN_DURATION: Lazy<Histogram> = Lazy::new(|| {
+    register(
+        Histogram::with_opts(
+            HistogramOpts::new("scan_duration_seconds", "Wall time of completed scans").buckets(
+                vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0],
+            ),
+        )
+        .unwrap(),
+    )
+});
+
+static FINDINGS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("findings_total", "Findings reported, by severity"),
+            &["severity"],
+        )
+        .unwrap(),
+    )
+});
+
+static MESSAGES_SCANNED: Lazy<IntCounter> = Lazy::new(|| {
+    register(
+        IntCounter::new(
+            "messages_scanned_total",
+            "Number of proxied JSON-RPC messages inspected",
+        )
+        .unwrap(),
+    )
+});
+
+static BLOCKED_CALLS: Lazy<IntCounter> = Lazy::new(|| {
+    register(
+        IntCounter::new(
+            "blocked_calls_total",
+            "Number of proxied calls blocked by policy",
+        )
+        .unwrap(),
+    )
+});
+
+static MESSAGE_LATENCY: Lazy<Histogram> = Lazy::new(|| {
+    register(
+        Histogram::with_opts(
+            HistogramOpts::new(
+                "message_latency_seconds",
+                "Inspection latency added to each proxied message",
+            )
+            .buckets(vec![
+                0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.5,
+            ]),
+        )
+        .unwrap(),
+    )
+});
+
+/// Register a collector with the MCP Sentinel registry and hand it back
+fn register<C>(collector: C) -> C
+where
+    C: prometheus::core::Collector + Clone + 'static,
+{
+    REGISTRY
+        .register(Box::new(collector.clone()))
+        .expect("metric registered twice");
+    collector
+}
+
+/// Record a completed scan: duration and findings by severity
+pub fn record_scan(result: &ScanResult) {
+    SCANS_TOTAL.inc();
+    SCAN_DURATION.observe(result.metadata.scan_duration_ms as f64 / 1000.0);
+
+    for vuln in &result.vulnerabilities {
+        record_finding(vuln.severity);
+    }
+}
+
+/// Record a single finding (used by the proxy for runtime detections)
+pub fn record_finding(severity: Severity) {
+    FINDINGS_TOTAL
+        .with_label_values(&[&severity.to_badge().to_lowercase()])
+        .inc();
+}
+
+/// Record a proxied message and the latency its inspection added
+pub fn record_message(latency: Duration) {
+    MESSAGES_SCANNED.inc();
+    MESSAGE_LATENCY.observe(latency.as_secs_f64());
+}
+
+/// Record a call blocked by policy
+pub fn record_blocked_call() {
+    BLOCKED_CALLS.inc();
+}
+
+/// Render all metrics in the Prometheus text exposition format
+pub fn render() -> String {
+    // Touch every metric so it is exported (with zero values) even before first use
+    Lazy::force(&SCANS_TOTAL);
+    Lazy::force(&SCAN_DURATION);
+    Lazy::force(&FINDINGS_TOTAL);
+    Lazy::force(&MESSAGES_SCANNED);
+    Lazy::force(&BLOCKED_CALLS);
+    Lazy::force(&MESSAGE_LATENCY);
+
+    TextEncoder::new()
+        .encode_to_string(&REGISTRY.gather())
+        .unwrap_or_default()
+}
+
+/// Router exposing `GET /metrics`, for mounting into other servers
+pub fn router() -> Router {
+    Router::new().route("/metrics", get(metrics_handler))
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render(),
+    )
+}
+
+/// Serve `/metrics` on the given address until the process exits
+pub async fn serve(addr: SocketAddr) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("📈 Metrics available at http://{}/metrics", addr);
+    axum::serve(listener, router()).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vulnerability::{Vulnerability, VulnerabilityType};
+
+    #[test]
+    fn test_render_includes_recorded_scan() {
+        let mut result = ScanResult::new("test-target", vec!["static".to_string()]);
+        result.add_vulnerability(Vulnerability::new(
+            "C-001",
+            VulnerabilityType::CommandInjection,
+            Severity::Critical,
+            "Test",
+            "Desc",
+        ));
+        record_scan(&result);
+
+        let output = render();
+        assert!(output.contains("mcp_sentinel_scans_total"));
+        assert!(output.contains("mcp_sentinel_findings_total{severity=\"critical\"}"));
+        assert!(output.contains("mcp_sentinel_blocked_calls_total"));
+    }
+}
@@ -1,6 +1,16 @@
 //! Utility functions
 
+pub mod clients;
+pub mod cron;
 pub mod file;
+pub mod metrics;
+pub mod monorepo;
+pub mod network;
+pub mod notify;
+pub mod package;
+pub mod process;
+pub mod provenance;
+pub mod telemetry;
 
 // Phase 2+ utilities
 // pub mod git;
@@ -0,0 +1,226 @@
+//! Detection of the MCP servers in a repository
+//!
+//! Monorepos often keep several servers side by side. A directory holds a
+//! server when it has a manifest depending on an MCP SDK (`package.json`,
+//! `pyproject.toml`, `requirements.txt`, `Cargo.toml`, `go.mod`), an MCP
+//! registry `server.json`, or an `mcp.json`. When a scan finds more than one,
+//! findings and scores are reported per server as well.
+
+use std::path::{Component, Path, PathBuf};
+
+/// npm packages that make a `package.json` an MCP server
+const NPM_SDKS: &[&str] = &["@modelcontextprotocol/sdk", "fastmcp", "mcp-framework"];
+
+/// PyPI packages that make a Python project an MCP server
+const PYTHON_SDKS: &[&str] = &["mcp", "fastmcp"];
+
+/// Crates that make a Rust package an MCP server
+const RUST_SDKS: &[&str] = &["rmcp", "mcp-sdk"];
+
+/// Go modules that make a Go module an MCP server
+const GO_SDKS: &[&str] = &[
+    "github.com/mark3labs/mcp-go",
+    "github.com/modelcontextprotocol/go-sdk",
+];
+
+/// An MCP server found in the scanned tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerRoot {
+    /// Name from the manifest, else the directory's
+    pub name: String,
+    /// Directory holding the marker, as scanned
+    pub dir: PathBuf,
+}
+
+/// The server that `file_path` marks, if it is a server manifest
+pub fn server_root(file_path: &str, content: &str) -> Option<ServerRoot> {
+    let path = Path::new(file_path);
+    let dir = path.parent()?;
+    // `.vscode/mcp.json` and friends configure a client, not a server
+    let hidden = dir.components().any(|c| match c {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+    if hidden {
+        return None;
+    }
+
+    let name = match path.file_name()?.to_str()? {
+        "package.json" => package_json(content)?,
+        "pyproject.toml" => pyproject(content)?,
+        "requirements.txt" => content
+            .lines()
+            .any(|line| PYTHON_SDKS.contains(&requirement_name(line).as_str()))
+            .then(String::new)?,
+        "Cargo.toml" => cargo_toml(content)?,
+        "go.mod" => go_mod(content)?,
+        "server.json" => serde_json::from_str::<serde_json::Value>(content)
+            .ok()?
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        "mcp.json" => String::new(),
+        _ => return None,
+    };
+    let name = match name.is_empty() {
+        false => name,
+        true => dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| ".".to_string()),
+    };
+    Some(ServerRoot {
+        name,
+        dir: dir.to_path_buf(),
+    })
+}
+
+/// Package name if it depends on an MCP SDK
+fn package_json(content: &str) -> Option<String> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+    let uses_sdk = ["dependencies", "peerDependencies"].iter().any(|key| {
+        manifest
+            .get(key)
+            .and_then(|deps| deps.as_object())
+            .is_some_and(|deps| NPM_SDKS.iter().any(|sdk| deps.contains_key(*sdk)))
+    });
+    uses_sdk.then(|| manifest["name"].as_str().unwrap_or_default().to_string())
+}
+
+/// Project name if it depends on an MCP SDK, PEP 621 or Poetry style
+fn pyproject(content: &str) -> Option<String> {
+    let manifest: toml::Value = toml::from_str(content).ok()?;
+    let project = manifest.get("project");
+    let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+    let pep621 = project
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())
+        .is_some_and(|deps| {
+            deps.iter()
+                .filter_map(|d| d.as_str())
+                .any(|d| PYTHON_SDKS.contains(&requirement_name(d).as_str()))
+        });
+    let poetry_deps = poetry
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_table())
+        .is_some_and(|deps| PYTHON_SDKS.iter().any(|sdk| deps.contains_key(*sdk)));
+    (pep621 || poetry_deps).then(|| {
+        project
+            .or(poetry)
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .unwrap_or_default()
+            .to_string()
+    })
+}
+
+/// Package name if it depends on an MCP SDK
+fn cargo_toml(content: &str) -> Option<String> {
+    let manifest: toml::Value = toml::from_str(content).ok()?;
+    let deps = manifest.get("dependencies")?.as_table()?;
+    RUST_SDKS
+        .iter()
+        .any(|sdk| deps.contains_key(*sdk))
+        .then(|| {
+            manifest
+                .get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or_default()
+                .to_string()
+        })
+}
+
+/// Last segment of the module path if it requires an MCP SDK
+fn go_mod(content: &str) -> Option<String> {
+    let requires_sdk = content.lines().any(|line| {
+        let line = line.trim().trim_start_matches("require").trim();
+        GO_SDKS
+            .iter()
+            .any(|sdk| line.split_whitespace().next() == Some(*sdk))
+    });
+    if !requires_sdk {
+        return None;
+    }
+    let module = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))
+        .unwrap_or_default();
+    Some(
+        module
+            .trim()
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// Normalized distribution name of a PEP 508 requirement (`mcp[cli]>=1.2`
+/// -> `mcp`)
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect::<String>()
+        .to_ascii_lowercase()
+        .replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_markers() {
+        let root = |file: &str, content: &str| {
+            server_root(file, content).map(|s| (s.name, s.dir.to_string_lossy().into_owned()))
+        };
+
+        let package =
+            r#"{"name": "@acme/weather", "dependencies": {"@modelcontextprotocol/sdk": "^1.0.0"}}"#;
+        assert_eq!(
+            root("repo/servers/weather/package.json", package),
+            Some((
+                "@acme/weather".to_string(),
+                "repo/servers/weather".to_string()
+            ))
+        );
+        let pyproject = "[project]\nname = \"billing\"\ndependencies = [\"mcp[cli]>=1.2\"]\n";
+        assert_eq!(
+            root("repo/servers/billing/pyproject.toml", pyproject)
+                .unwrap()
+                .0,
+            "billing"
+        );
+        assert_eq!(
+            root("repo/servers/files/requirements.txt", "FastMCP==2.3\n")
+                .unwrap()
+                .0,
+            "files"
+        );
+        let go = "module github.com/acme/search\n\nrequire github.com/mark3labs/mcp-go v0.20.0\n";
+        assert_eq!(root("repo/search/go.mod", go).unwrap().0, "search");
+        assert_eq!(
+            root(
+                "repo/rs/Cargo.toml",
+                "[package]\nname = \"notes\"\n\n[dependencies]\nrmcp = \"0.1\"\n"
+            )
+            .unwrap()
+            .0,
+            "notes"
+        );
+        assert!(root("repo/servers/crm/mcp.json", "{}").is_some());
+    }
+
+    #[test]
+    fn test_non_server_manifests() {
+        let web = r#"{"name": "web", "dependencies": {"react": "^18"}}"#;
+        assert_eq!(server_root("repo/web/package.json", web), None);
+        assert_eq!(server_root("repo/requirements.txt", "mcpx==1.0\n"), None);
+        assert_eq!(server_root("repo/.vscode/mcp.json", "{}"), None);
+        assert_eq!(server_root("repo/src/index.ts", ""), None);
+    }
+}
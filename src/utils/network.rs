@@ -0,0 +1,47 @@
+//! Offline mode
+//!
+//! `--offline` (or `MCP_SENTINEL_OFFLINE=1`) promises that a run makes no
+//! outbound network requests, for air-gapped environments. Networked
+//! features check [`ensure_online`] before connecting: optional ones (IOC
+//! feed refresh, LLM review during a scan) fall back to local data or are
+//! skipped with a warning, explicit ones (`rules update`, package fetching)
+//! fail with an error naming the flag.
+//!
+//! Data those features would download can be carried over with
+//! `rules export-bundle` / `rules import-bundle`.
+
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable offline mode for the process
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail if offline mode is on; `feature` names what needed the network
+pub fn ensure_online(feature: &str) -> Result<()> {
+    if is_offline() {
+        anyhow::bail!("{} needs network access, which --offline disables", feature);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_online() {
+        set_offline(true);
+        let err = ensure_online("Rule updates").unwrap_err();
+        assert!(err.to_string().contains("--offline"));
+        set_offline(false);
+        assert!(ensure_online("Rule updates").is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+//! Outgoing notifications
+//!
+//! A notification target is written as:
+//! - `slack`: the Slack incoming webhook in `SLACK_WEBHOOK_URL`
+//! - `slack:<url>`: that Slack incoming webhook
+//! - an `http(s)://` URL: a generic webhook, sent the notification as JSON
+//!
+//! Slack gets the text; generic webhooks get `{"text": ..., "details": ...}`
+//! with whatever structured details the sender attached.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::network;
+
+/// Environment variable holding the webhook of a bare `slack` target
+pub const SLACK_WEBHOOK_ENV: &str = "SLACK_WEBHOOK_URL";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where a notification goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notifier {
+    Slack(String),
+    Webhook(String),
+}
+
+/// Something worth telling someone about
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    /// One line
+    pub title: String,
+    /// Further lines, shown as a list
+    pub lines: Vec<String>,
+    /// Structured data for generic webhooks
+    pub details: Value,
+}
+
+impl std::str::FromStr for Notifier {
+    type Err = anyhow::Error;
+
+    fn from_str(target: &str) -> Result<Self> {
+        if target == "slack" {
+            let url = std::env::var(SLACK_WEBHOOK_ENV).with_context(|| {
+                format!(
+                    "Notifying 'slack' needs the webhook URL in {}",
+                    SLACK_WEBHOOK_ENV
+                )
+            })?;
+            return Ok(Self::Slack(checked_url(&url)?));
+        }
+        if let Some(url) = target.strip_prefix("slack:") {
+            return Ok(Self::Slack(checked_url(url)?));
+        }
+        Ok(Self::Webhook(checked_url(target)?))
+    }
+}
+
+/// Whether `target` is written as a notification target, without resolving
+/// it (a bare `slack` may get its URL only where notifications are sent)
+pub fn validate(target: &str) -> Result<()> {
+    match target {
+        "slack" => Ok(()),
+        _ => target.parse::<Notifier>().map(drop),
+    }
+}
+
+fn checked_url(url: &str) -> Result<String> {
+    let parsed = url::Url::parse(url).with_context(|| format!("Invalid webhook URL '{}'", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        bail!(
+            "Unsupported notification target '{}': use slack, slack:<url>, or an http(s) URL",
+            url
+        );
+    }
+    Ok(url.to_string())
+}
+
+impl Notifier {
+    pub async fn send(&self, notification: &Notification) -> Result<()> {
+        network::ensure_online("Notifications")?;
+        let text = std::iter::once(notification.title.clone())
+            .chain(notification.lines.iter().map(|line| format!("• {}", line)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let (url, body) = match self {
+            Notifier::Slack(url) => (url, json!({ "text": text })),
+            Notifier::Webhook(url) => (
+                url,
+                json!({ "text": text, "details": notification.details }),
+            ),
+        };
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        client
+            .post(url.as_str())
+            .json(&body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to notify {}", host(url)))?;
+        Ok(())
+    }
+}
+
+/// The host of a webhook URL, which may carry a secret in its path
+fn host(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_targets() {
+        assert_eq!(
+            "slack:https://hooks.slack.com/services/T0/B0/x"
+                .parse::<Notifier>()
+                .unwrap(),
+            Notifier::Slack("https://hooks.slack.com/services/T0/B0/x".to_string())
+        );
+        assert_eq!(
+            "https://alerts.example.com/mcp"
+                .parse::<Notifier>()
+                .unwrap(),
+            Notifier::Webhook("https://alerts.example.com/mcp".to_string())
+        );
+        assert!(validate("slack").is_ok());
+        assert!(validate("email").is_err());
+        assert!(validate("ftp://example.com").is_err());
+        assert_eq!(
+            host("https://hooks.slack.com/services/T0/B0/x"),
+            "hooks.slack.com"
+        );
+    }
+}
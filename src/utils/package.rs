@@ -0,0 +1,322 @@
+//! Package fetching for registry-published MCP servers
+//!
+//! Downloads the exact artifact a package manager would install and unpacks it
+//! into a directory that can be scanned like any local server. Fetching
+//! delegates to the ecosystem's own tooling (`npm pack`, `pip download`) so
+//! registry configuration, proxies, and auth tokens are honoured.
+//!
+//! Package specs may come from untrusted input (an agent calling the
+//! `scan_package` tool), so they are checked against the registry's name and
+//! version grammar before any tool runs, and no install scripts or build
+//! backends run: npm gets `--ignore-scripts`, and pip only downloads wheels.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::debug;
+
+use super::network;
+
+/// npm package names, optionally scoped; the registry also caps them at 214
+/// characters
+static NPM_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^(?:@[a-z0-9][a-z0-9._~-]*/)?[a-z0-9][a-z0-9._~-]*$").unwrap());
+/// npm versions, ranges, and dist-tags, but no URLs, paths, or git refs
+static NPM_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9A-Za-z*^~<>=][0-9A-Za-z.+*^~<>=| -]*$").unwrap());
+/// PEP 508 distribution names
+static PYPI_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^[a-z0-9](?:[a-z0-9._-]*[a-z0-9])?$").unwrap());
+/// PEP 440 versions
+static PYPI_VERSION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[0-9A-Za-z][0-9A-Za-z.!+_-]*$").unwrap());
+
+/// Longest npm package name the registry accepts
+const NPM_MAX_NAME_LEN: usize = 214;
+
+/// Package registry ecosystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ecosystem {
+    Npm,
+    Pypi,
+}
+
+impl std::str::FromStr for Ecosystem {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "npm" => Ok(Ecosystem::Npm),
+            "pypi" | "pip" => Ok(Ecosystem::Pypi),
+            other => anyhow::bail!("Unsupported ecosystem '{}' (expected npm or pypi)", other),
+        }
+    }
+}
+
+/// A package reference such as `npm:@scope/server@1.2.3`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageSpec {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl PackageSpec {
+    pub fn new(ecosystem: Ecosystem, name: impl Into<String>, version: Option<String>) -> Self {
+        Self {
+            ecosystem,
+            name: name.into(),
+            version,
+        }
+    }
+
+    /// Check the name and version against the registry's grammar
+    ///
+    /// Rejects anything the package tools would read as an option, a URL, a
+    /// path, or a VCS reference instead of a registry package.
+    pub fn validate(&self) -> Result<()> {
+        let (name_ok, version_ok) = match self.ecosystem {
+            Ecosystem::Npm => (
+                self.name.len() <= NPM_MAX_NAME_LEN && NPM_NAME.is_match(&self.name),
+                NPM_VERSION.is_match(self.version.as_deref().unwrap_or("0")),
+            ),
+            Ecosystem::Pypi => (
+                PYPI_NAME.is_match(&self.name),
+                PYPI_VERSION.is_match(self.version.as_deref().unwrap_or("0")),
+            ),
+        };
+        if !name_ok {
+            anyhow::bail!("Invalid {:?} package name '{}'", self.ecosystem, self.name);
+        }
+        if !version_ok {
+            anyhow::bail!(
+                "Invalid version '{}' for package '{}'",
+                self.version.as_deref().unwrap_or_default(),
+                self.name
+            );
+        }
+        Ok(())
+    }
+
+    /// Requirement string in the ecosystem's own syntax
+    pub fn requirement(&self) -> String {
+        match (&self.ecosystem, &self.version) {
+            (Ecosystem::Npm, Some(v)) => format!("{}@{}", self.name, v),
+            (Ecosystem::Pypi, Some(v)) => format!("{}=={}", self.name, v),
+            (_, None) => self.name.clone(),
+        }
+    }
+}
+
+/// A downloaded and unpacked package
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    /// The artifact as published (tarball, wheel, or sdist)
+    pub artifact: PathBuf,
+    /// Directory it was unpacked into
+    pub root: PathBuf,
+}
+
+/// Download and unpack a package into `dest`
+///
+/// PyPI packages that publish only an sdist are refused: building one to
+/// get at its files would run its `setup.py` on this machine.
+pub fn fetch(spec: &PackageSpec, dest: &Path) -> Result<Fetched> {
+    spec.validate()?;
+    network::ensure_online("Fetching packages")?;
+    let download_dir = dest.join("download");
+    let unpack_dir = dest.join("package");
+    std::fs::create_dir_all(&download_dir)?;
+    std::fs::create_dir_all(&unpack_dir)?;
+
+    match spec.ecosystem {
+        Ecosystem::Npm => run(Command::new(npm_program())
+            .args(["pack", "--ignore-scripts", "--pack-destination"])
+            .arg(&download_dir)
+            .arg("--")
+            .arg(spec.requirement()))
+        .with_context(|| format!("Failed to fetch npm package '{}'", spec.requirement()))?,
+        Ecosystem::Pypi => run(Command::new("pip")
+            .args([
+                "download",
+                "--no-deps",
+                "--only-binary=:all:",
+                "--quiet",
+                "--dest",
+            ])
+            .arg(&download_dir)
+            .arg("--")
+            .arg(spec.requirement()))
+        .with_context(|| {
+            format!(
+                "Failed to fetch PyPI package '{}' (only wheels are fetched)",
+                spec.requirement()
+            )
+        })?,
+    }
+
+    let artifact = std::fs::read_dir(&download_dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| p.is_file())
+        .with_context(|| format!("No artifact downloaded for '{}'", spec.requirement()))?;
+
+    unpack(&artifact, &unpack_dir)?;
+    Ok(Fetched {
+        artifact,
+        root: unpack_dir,
+    })
+}
+
+/// Version of a downloaded artifact, read from its file name
+///
+/// npm names tarballs `<scope>-<name>-<version>.tgz`. Wheels and sdists
+/// are `<name>-<version>…`, but older sdists keep dashes in the name, so the
+/// first part starting with a digit is taken.
+pub fn artifact_version(spec: &PackageSpec, artifact: &Path) -> Option<String> {
+    let file = artifact.file_name()?.to_str()?;
+    let version = match spec.ecosystem {
+        Ecosystem::Npm => {
+            let prefix = format!("{}-", spec.name.trim_start_matches('@').replace('/', "-"));
+            file.strip_prefix(&prefix)?.strip_suffix(".tgz")?
+        }
+        Ecosystem::Pypi => {
+            let stem = [".whl", ".tar.gz", ".zip"]
+                .iter()
+                .find_map(|ext| file.strip_suffix(ext))?;
+            stem.split('-')
+                .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))?
+        }
+    };
+    Some(version.to_string())
+}
+
+/// Unpack a `.tgz`/`.tar.gz`, `.whl`, or `.zip` archive
+///
+/// Both extractors reject entries that would escape `dest` (zip-slip).
+pub fn unpack(archive: &Path, dest: &Path) -> Result<()> {
+    let name = archive
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    debug!("Unpacking {} into {}", archive.display(), dest.display());
+
+    if name.ends_with(".tgz") || name.ends_with(".tar.gz") {
+        let file = std::fs::File::open(archive)?;
+        tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(dest)?;
+    } else if name.ends_with(".whl") || name.ends_with(".zip") {
+        let file = std::fs::File::open(archive)?;
+        zip::ZipArchive::new(file)?.extract(dest)?;
+    } else {
+        anyhow::bail!("Unsupported archive format: {}", archive.display());
+    }
+
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    debug!("Running {:?}", command);
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "{:?} exited with {}: {}",
+            command.get_program(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn npm_program() -> &'static str {
+    if cfg!(windows) {
+        "npm.cmd"
+    } else {
+        "npm"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement_syntax() {
+        let npm = PackageSpec::new(Ecosystem::Npm, "@scope/server", Some("1.2.3".into()));
+        assert_eq!(npm.requirement(), "@scope/server@1.2.3");
+
+        let pypi = PackageSpec::new(Ecosystem::Pypi, "mcp-server-git", Some("0.6.2".into()));
+        assert_eq!(pypi.requirement(), "mcp-server-git==0.6.2");
+    }
+
+    #[test]
+    fn test_validate_rejects_non_registry_specs() {
+        let npm = |name: &str, version: Option<&str>| {
+            PackageSpec::new(Ecosystem::Npm, name, version.map(str::to_string)).validate()
+        };
+        let pypi = |name: &str, version: Option<&str>| {
+            PackageSpec::new(Ecosystem::Pypi, name, version.map(str::to_string)).validate()
+        };
+
+        assert!(npm("@modelcontextprotocol/server-filesystem", Some("^2025.8.0")).is_ok());
+        assert!(npm("weather-mcp", Some("latest")).is_ok());
+        assert!(pypi("mcp-server-fetch", Some("0.6.2")).is_ok());
+        assert!(pypi("Flask_Login", None).is_ok());
+
+        assert!(npm("--registry=http://evil.example", None).is_err());
+        assert!(npm("-g", None).is_err());
+        assert!(npm("git+https://github.com/acme/mcp.git", None).is_err());
+        assert!(npm("github:acme/mcp", None).is_err());
+        assert!(npm("https://evil.example/pkg.tgz", None).is_err());
+        assert!(npm("../local", None).is_err());
+        assert!(npm("pkg", Some("git+ssh://git@github.com/acme/mcp")).is_err());
+        assert!(npm("pkg", Some("file:../evil")).is_err());
+        assert!(pypi("--index-url=http://evil.example/simple", None).is_err());
+        assert!(pypi("-r", None).is_err());
+        assert!(pypi("git+https://github.com/acme/mcp", None).is_err());
+        assert!(pypi("pkg @ https://evil.example/pkg.whl", None).is_err());
+        assert!(pypi("pkg", Some("--pre")).is_err());
+        assert!(pypi("pkg", Some("1.0; python_version<'4'")).is_err());
+    }
+
+    #[test]
+    fn test_artifact_version() {
+        let npm = PackageSpec::new(
+            Ecosystem::Npm,
+            "@modelcontextprotocol/server-filesystem",
+            None,
+        );
+        assert_eq!(
+            artifact_version(
+                &npm,
+                Path::new("dl/modelcontextprotocol-server-filesystem-2025.8.21.tgz")
+            )
+            .as_deref(),
+            Some("2025.8.21")
+        );
+        let pypi = PackageSpec::new(Ecosystem::Pypi, "mcp-server-fetch", None);
+        assert_eq!(
+            artifact_version(&pypi, Path::new("mcp_server_fetch-0.6.2-py3-none-any.whl"))
+                .as_deref(),
+            Some("0.6.2")
+        );
+        assert_eq!(
+            artifact_version(&pypi, Path::new("mcp-server-fetch-0.6.2.tar.gz")).as_deref(),
+            Some("0.6.2")
+        );
+    }
+
+    #[test]
+    fn test_parse_ecosystem() {
+        assert_eq!("NPM".parse::<Ecosystem>().unwrap(), Ecosystem::Npm);
+        assert_eq!("pip".parse::<Ecosystem>().unwrap(), Ecosystem::Pypi);
+        assert!("cargo".parse::<Ecosystem>().is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Starting MCP server commands the way the client would
+//!
+//! Client configs name programs as a shell would (`npx`, `uvx`, `node`).
+//! On Windows, `CreateProcess` only finds `.exe` files on `PATH`, but npm and
+//! many Python tools install `.cmd` shims, so the program is looked up with
+//! `PATH` and `PATHEXT` first. Elsewhere it is used as is.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried when `PATHEXT` is unset
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Environment variables Windows programs need to start at all, kept when a
+/// server's environment is scrubbed
+pub const WINDOWS_BASE_ENV: &[&str] = &[
+    "SystemRoot",
+    "SystemDrive",
+    "windir",
+    "ComSpec",
+    "PATHEXT",
+    "TEMP",
+    "TMP",
+];
+
+/// `program` as it should be passed to `Command::new`
+pub fn resolve(program: &str) -> PathBuf {
+    if cfg!(windows) {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+        if let Some(found) = find_program(program, &path, &pathext) {
+            return found;
+        }
+    }
+    PathBuf::from(program)
+}
+
+/// Look `program` up in the directories of `path`, trying each extension
+/// of `pathext` (`;`-separated) unless it has one
+///
+/// Programs given with a directory are not looked up.
+pub fn find_program(program: &str, path: &OsStr, pathext: &str) -> Option<PathBuf> {
+    let name = Path::new(program);
+    if name.components().count() != 1 {
+        return None;
+    }
+    let extensions: Vec<&str> = match name.extension() {
+        Some(_) => vec![""],
+        None => pathext.split(';').filter(|ext| !ext.is_empty()).collect(),
+    };
+    std::env::split_paths(path).find_map(|dir| {
+        extensions
+            .iter()
+            .map(|ext| dir.join(format!("{}{}", program, ext)))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_program() {
+        let bin = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(bin.path().join("npx.CMD"), "").unwrap();
+        std::fs::write(other.path().join("uvx.EXE"), "").unwrap();
+        let path = std::env::join_paths([bin.path(), other.path()]).unwrap();
+
+        assert_eq!(
+            find_program("npx", &path, DEFAULT_PATHEXT),
+            Some(bin.path().join("npx.CMD"))
+        );
+        assert_eq!(
+            find_program("uvx", &path, DEFAULT_PATHEXT),
+            Some(other.path().join("uvx.EXE"))
+        );
+        assert_eq!(
+            find_program("npx.CMD", &path, DEFAULT_PATHEXT),
+            Some(bin.path().join("npx.CMD"))
+        );
+        assert_eq!(find_program("node", &path, DEFAULT_PATHEXT), None);
+        assert_eq!(find_program("./npx", &path, DEFAULT_PATHEXT), None);
+    }
+
+    /// npm installs `npx` as a batch file, which `CreateProcess` does not
+    /// find by name
+    #[cfg(windows)]
+    #[test]
+    fn test_resolved_cmd_shim_starts() {
+        let bin = tempfile::tempdir().unwrap();
+        std::fs::write(
+            bin.path().join("echo-server.cmd"),
+            "@echo {\"jsonrpc\":\"2.0\"}\r\n",
+        )
+        .unwrap();
+
+        let program = find_program("echo-server", bin.path().as_os_str(), DEFAULT_PATHEXT).unwrap();
+        let output = std::process::Command::new(program).output().unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim_end(),
+            r#"{"jsonrpc":"2.0"}"#
+        );
+    }
+}
@@ -0,0 +1,686 @@
+//! Package integrity and provenance verification
+//!
+//! For a package fetched with [`super::package::fetch`]:
+//! - the artifact's hash is compared with the registry's (`dist.integrity`
+//!   on npm, the file's `sha256` digest on PyPI)
+//! - the registry's provenance attestation (npm provenance, PyPI PEP 740)
+//!   is looked up; its in-toto statement must name the artifact's digest,
+//!   and the repository it was built from must be the one the package
+//!   declares
+//! - the source repository is cloned at the release tag and the published
+//!   files are compared with it: modified files, and source files that do
+//!   not exist in the repository at all, are what a tampered release looks
+//!   like
+//!
+//! Attestations are checked for presence and content. Their Sigstore
+//! certificate chain is not verified here; `npm audit signatures` and
+//! `pypi-attestations verify` do that.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use serde_json::Value;
+use sha2::{Digest, Sha256, Sha512};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use walkdir::WalkDir;
+
+use super::network;
+use super::package::{Ecosystem, PackageSpec};
+use crate::models::vulnerability::{Location, Severity, Vulnerability, VulnerabilityType};
+
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most file differences reported per package
+const MAX_DIFFERENCES: usize = 20;
+
+/// Directories that hold build output rather than source
+const GENERATED_DIRS: &[&str] = &["dist", "build", "lib", "out", "node_modules", "__pycache__"];
+
+/// Files the packaging tools write or rewrite
+const PACKAGING_FILES: &[&str] = &[
+    "package.json",
+    "PKG-INFO",
+    "METADATA",
+    "RECORD",
+    "WHEEL",
+    "setup.cfg",
+];
+
+/// Extensions of files that run
+const SOURCE_EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "ts", "py", "sh"];
+
+/// What the registry says about one release
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegistryRecord {
+    /// Resolved version
+    pub version: String,
+    /// Expected artifact digest: (algorithm, hex)
+    pub digest: Option<(&'static str, String)>,
+    /// Source repository the package declares, normalized
+    pub repository: Option<String>,
+    pub attestation: Option<Attestation>,
+}
+
+/// The parts of a provenance attestation that are checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attestation {
+    /// Digests of the statement's subjects: (algorithm, hex)
+    pub subjects: Vec<(String, String)>,
+    /// Repository the artifact was built from, normalized
+    pub repository: Option<String>,
+}
+
+/// Check the fetched `artifact` (unpacked into `root`) against its registry
+/// record, its provenance, and its source repository
+pub async fn verify(
+    spec: &PackageSpec,
+    artifact: &Path,
+    root: &Path,
+) -> Result<Vec<Vulnerability>> {
+    network::ensure_online("Verifying package provenance")?;
+    let client = reqwest::Client::builder()
+        .timeout(REGISTRY_TIMEOUT)
+        .build()?;
+    let file_name = artifact
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let record = match spec.ecosystem {
+        Ecosystem::Npm => npm_record(&client, spec).await?,
+        Ecosystem::Pypi => pypi_record(&client, spec, &file_name).await?,
+    };
+    let bytes = std::fs::read(artifact)
+        .with_context(|| format!("Failed to read {}", artifact.display()))?;
+
+    let location = Location::new(format!("{}@{}", spec.name, record.version));
+    let mut vulnerabilities = check_record(&record, &bytes, &location);
+
+    let repository = record
+        .attestation
+        .as_ref()
+        .and_then(|a| a.repository.clone())
+        .or_else(|| record.repository.clone());
+    match repository {
+        Some(url) => match clone_release(&url, &record.version).await {
+            Ok(checkout) => {
+                let differences = compare_trees(&package_root(root), checkout.path());
+                vulnerabilities.extend(difference_findings(&differences, &url));
+            }
+            Err(e) => warn!("Skipping source comparison for {}: {:#}", spec.name, e),
+        },
+        None => debug!("{} declares no source repository", spec.name),
+    }
+
+    for (i, vuln) in vulnerabilities.iter_mut().enumerate() {
+        vuln.id = format!("PROV-{:03}", i + 1);
+    }
+    info!(
+        "Provenance of {}: {} finding(s)",
+        spec.requirement(),
+        vulnerabilities.len()
+    );
+    Ok(vulnerabilities)
+}
+
+/// Findings from the registry record alone
+pub fn check_record(
+    record: &RegistryRecord,
+    artifact: &[u8],
+    location: &Location,
+) -> Vec<Vulnerability> {
+    let mut vulnerabilities = Vec::new();
+    let finding = |severity, rule: &str, title: &str, description: String| {
+        Vulnerability::new(
+            String::new(),
+            VulnerabilityType::SupplyChainAttack,
+            severity,
+            title,
+            description,
+        )
+        .with_rule_id(format!("provenance/{}", rule))
+        .with_location(location.clone())
+    };
+
+    if let Some((algorithm, expected)) = &record.digest {
+        let actual = hex_digest(algorithm, artifact);
+        if actual.as_deref() != Some(expected.as_str()) {
+            vulnerabilities.push(
+                finding(
+                    Severity::Critical,
+                    "checksum-mismatch",
+                    "Artifact does not match the registry checksum",
+                    format!(
+                        "The downloaded artifact's {} is {}, but the registry lists {}",
+                        algorithm,
+                        actual.unwrap_or_default(),
+                        expected
+                    ),
+                )
+                .with_impact("The artifact was altered between the registry and this machine")
+                .with_remediation("Do not install it; check for a compromised mirror or proxy")
+                .with_confidence(0.95),
+            );
+        }
+    }
+
+    let Some(attestation) = &record.attestation else {
+        vulnerabilities.push(
+            finding(
+                Severity::Low,
+                "no-provenance",
+                "Package has no provenance attestation",
+                "The registry has no provenance attestation for this release, so nothing ties \
+                 the artifact to a source repository or build"
+                    .to_string(),
+            )
+            .with_remediation(
+                "Ask the maintainers to publish with provenance (npm publish --provenance, \
+                 PyPI trusted publishing)",
+            )
+            .with_confidence(0.9),
+        );
+        return vulnerabilities;
+    };
+
+    let matches_subject = attestation.subjects.iter().any(|(algorithm, expected)| {
+        hex_digest(algorithm, artifact).as_deref() == Some(expected.as_str())
+    });
+    if !matches_subject {
+        vulnerabilities.push(
+            finding(
+                Severity::Critical,
+                "attestation-subject-mismatch",
+                "Provenance attestation is for a different artifact",
+                "None of the digests in the provenance statement match the published artifact"
+                    .to_string(),
+            )
+            .with_impact("The artifact was not produced by the attested build")
+            .with_remediation("Do not install it; report the release to the registry")
+            .with_confidence(0.9),
+        );
+    }
+    if let (Some(declared), Some(built)) = (&record.repository, &attestation.repository) {
+        if declared != built {
+            vulnerabilities.push(
+                finding(
+                    Severity::High,
+                    "repository-mismatch",
+                    "Package was built from a different repository than it declares",
+                    format!(
+                        "The package declares {} as its source, but its provenance says it was \
+                         built from {}",
+                        declared, built
+                    ),
+                )
+                .with_impact("The reviewed repository is not the code that was published")
+                .with_remediation("Review the repository named in the provenance before use")
+                .with_confidence(0.85),
+            );
+        }
+    }
+    vulnerabilities
+}
+
+/// A published file that does not match the source repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    Modified(String),
+    /// A source file the repository does not have
+    Added(String),
+}
+
+/// Compare an unpacked package with a checkout of its repository
+pub fn compare_trees(package: &Path, repository: &Path) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    let files = WalkDir::new(package)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file());
+
+    for entry in files {
+        let Ok(relative) = entry.path().strip_prefix(package) else {
+            continue;
+        };
+        let generated = relative.components().any(|c| {
+            let c = c.as_os_str().to_string_lossy();
+            GENERATED_DIRS.contains(&c.as_ref())
+                || c.ends_with(".dist-info")
+                || c.ends_with(".egg-info")
+        });
+        let name = entry.file_name().to_string_lossy();
+        if generated || PACKAGING_FILES.contains(&name.as_ref()) || name.ends_with(".map") {
+            continue;
+        }
+
+        // Wheels drop the `src/` layout directory
+        let source = [
+            repository.join(relative),
+            repository.join("src").join(relative),
+        ]
+        .into_iter()
+        .find(|p| p.is_file());
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        match source {
+            Some(source) => {
+                let published = std::fs::read(entry.path()).unwrap_or_default();
+                let original = std::fs::read(&source).unwrap_or_default();
+                if normalize(&published) != normalize(&original) {
+                    differences.push(Difference::Modified(relative));
+                }
+            }
+            None => {
+                let runs = Path::new(&relative)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| SOURCE_EXTENSIONS.contains(&e));
+                if runs {
+                    differences.push(Difference::Added(relative));
+                }
+            }
+        }
+    }
+    differences
+}
+
+fn difference_findings(differences: &[Difference], repository: &str) -> Vec<Vulnerability> {
+    if differences.len() > MAX_DIFFERENCES {
+        warn!(
+            "{} published files differ from {}; reporting the first {}",
+            differences.len(),
+            repository,
+            MAX_DIFFERENCES
+        );
+    }
+    differences
+        .iter()
+        .take(MAX_DIFFERENCES)
+        .map(|difference| {
+            let (file, severity, rule, title, description) = match difference {
+                Difference::Modified(file) => (
+                    file,
+                    Severity::High,
+                    "modified-file",
+                    "Published file differs from the source repository",
+                    format!(
+                        "{} in the package does not match the same file in {} at the release tag",
+                        file, repository
+                    ),
+                ),
+                Difference::Added(file) => (
+                    file,
+                    Severity::Medium,
+                    "unknown-file",
+                    "Published source file is not in the repository",
+                    format!(
+                        "{} ships in the package but does not exist in {} at the release tag",
+                        file, repository
+                    ),
+                ),
+            };
+            Vulnerability::new(
+                String::new(),
+                VulnerabilityType::SupplyChainAttack,
+                severity,
+                title,
+                description,
+            )
+            .with_rule_id(format!("provenance/{}", rule))
+            .with_location(Location::new(file.clone()))
+            .with_impact("Code that was never reviewed in the repository runs on install or use")
+            .with_remediation(
+                "Diff the file against the repository; a legitimate build step should be \
+                 reproducible from the tagged source",
+            )
+            .with_confidence(0.7)
+        })
+        .collect()
+}
+
+async fn npm_record(client: &reqwest::Client, spec: &PackageSpec) -> Result<RegistryRecord> {
+    let version = spec.version.as_deref().unwrap_or("latest");
+    let url = format!(
+        "https://registry.npmjs.org/{}/{}",
+        spec.name.replace('/', "%2F"),
+        version
+    );
+    let metadata = get_json(client, &url).await?;
+    let dist = metadata.get("dist").cloned().unwrap_or_default();
+
+    let digest = dist
+        .get("integrity")
+        .and_then(Value::as_str)
+        .and_then(|sri| sri.strip_prefix("sha512-"))
+        .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+        .map(|bytes| ("sha512", hex(&bytes)));
+    let repository = match metadata.get("repository") {
+        Some(Value::String(url)) => Some(url.as_str()),
+        Some(repository) => repository.get("url").and_then(Value::as_str),
+        None => None,
+    }
+    .and_then(normalize_repository);
+
+    let attestation = match dist
+        .get("attestations")
+        .and_then(|a| a.get("url"))
+        .and_then(Value::as_str)
+    {
+        Some(url) => {
+            let bundles = get_json(client, url).await?;
+            bundles
+                .get("attestations")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter(|a| {
+                    a.get("predicateType")
+                        .and_then(Value::as_str)
+                        .is_some_and(|t| t.starts_with("https://slsa.dev/provenance/"))
+                })
+                .find_map(|a| {
+                    let payload = a.pointer("/bundle/dsseEnvelope/payload")?.as_str()?;
+                    parse_statement(payload)
+                })
+        }
+        None => None,
+    };
+
+    Ok(RegistryRecord {
+        version: metadata
+            .get("version")
+            .and_then(Value::as_str)
+            .unwrap_or(version)
+            .to_string(),
+        digest,
+        repository,
+        attestation,
+    })
+}
+
+async fn pypi_record(
+    client: &reqwest::Client,
+    spec: &PackageSpec,
+    file_name: &str,
+) -> Result<RegistryRecord> {
+    let url = match &spec.version {
+        Some(version) => format!("https://pypi.org/pypi/{}/{}/json", spec.name, version),
+        None => format!("https://pypi.org/pypi/{}/json", spec.name),
+    };
+    let metadata = get_json(client, &url).await?;
+    let info = metadata.get("info").cloned().unwrap_or_default();
+    let version = info
+        .get("version")
+        .and_then(Value::as_str)
+        .or(spec.version.as_deref())
+        .unwrap_or_default()
+        .to_string();
+
+    let digest = metadata
+        .get("urls")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|f| f.get("filename").and_then(Value::as_str) == Some(file_name))
+        .and_then(|f| f.pointer("/digests/sha256")?.as_str())
+        .map(|sha256| ("sha256", sha256.to_ascii_lowercase()));
+    let project_urls = info.get("project_urls").and_then(Value::as_object);
+    let repository = [
+        "Source",
+        "Source Code",
+        "Repository",
+        "Code",
+        "GitHub",
+        "Homepage",
+    ]
+    .iter()
+    .find_map(|key| {
+        project_urls?
+            .get(*key)?
+            .as_str()
+            .and_then(normalize_repository)
+    });
+
+    // PEP 740 integrity API; a 404 means no attestations
+    let url = format!(
+        "https://pypi.org/integrity/{}/{}/{}/provenance",
+        spec.name, version, file_name
+    );
+    let attestation = match get_json(client, &url).await {
+        Ok(provenance) => provenance
+            .get("attestation_bundles")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .find_map(|bundle| {
+                let statement = bundle
+                    .get("attestations")?
+                    .as_array()?
+                    .iter()
+                    .find_map(|a| a.pointer("/envelope/statement")?.as_str())?;
+                let mut attestation = parse_statement(statement)?;
+                // The trusted publisher is the authoritative source
+                let publisher = bundle
+                    .pointer("/publisher/repository")
+                    .and_then(Value::as_str);
+                if let Some(repository) = publisher {
+                    attestation.repository =
+                        normalize_repository(&format!("https://github.com/{}", repository));
+                }
+                Some(attestation)
+            }),
+        Err(e) => {
+            debug!("No PyPI provenance for {}: {:#}", file_name, e);
+            None
+        }
+    };
+
+    Ok(RegistryRecord {
+        version,
+        digest,
+        repository,
+        attestation,
+    })
+}
+
+/// Subjects and source repository of a base64 in-toto statement
+pub fn parse_statement(payload: &str) -> Option<Attestation> {
+    let json = base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .ok()?;
+    let statement: Value = serde_json::from_slice(&json).ok()?;
+    let subjects = statement
+        .get("subject")?
+        .as_array()?
+        .iter()
+        .filter_map(|s| s.get("digest")?.as_object())
+        .flat_map(|digests| {
+            digests.iter().filter_map(|(algorithm, hex)| {
+                Some((algorithm.clone(), hex.as_str()?.to_ascii_lowercase()))
+            })
+        })
+        .collect();
+    // SLSA v1 (npm) and v0.2 layouts
+    let repository = statement
+        .pointer("/predicate/buildDefinition/externalParameters/workflow/repository")
+        .or_else(|| statement.pointer("/predicate/invocation/configSource/uri"))
+        .and_then(Value::as_str)
+        .and_then(normalize_repository);
+    Some(Attestation {
+        subjects,
+        repository,
+    })
+}
+
+/// `https://host/owner/repo` for the URL forms registries use
+/// (`git+https://...git`, `git@host:owner/repo`, `github:owner/repo`)
+pub fn normalize_repository(url: &str) -> Option<String> {
+    let url = url.trim();
+    let url = if let Some(path) = url.strip_prefix("github:") {
+        format!("https://github.com/{}", path)
+    } else if let Some(rest) = url.strip_prefix("git@") {
+        format!("https://{}", rest.replacen(':', "/", 1))
+    } else {
+        url.trim_start_matches("git+")
+            .replacen("git://", "https://", 1)
+            .replacen("ssh://git@", "https://", 1)
+    };
+    let parsed = url::Url::parse(&url).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    let mut segments = parsed.path_segments()?.filter(|s| !s.is_empty());
+    let owner = segments.next()?;
+    let repo = segments.next()?.trim_end_matches(".git");
+    Some(format!(
+        "https://{}/{}/{}",
+        host,
+        owner.to_ascii_lowercase(),
+        repo.to_ascii_lowercase()
+    ))
+}
+
+/// Shallow clone of `repository` at the tag of `version`
+async fn clone_release(repository: &str, version: &str) -> Result<tempfile::TempDir> {
+    for tag in [format!("v{}", version), version.to_string()] {
+        let dir = tempfile::tempdir()?;
+        let status = tokio::process::Command::new("git")
+            .args([
+                "clone", "--quiet", "--depth", "1", "--branch", &tag, repository,
+            ])
+            .arg(dir.path())
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await
+            .context("Failed to run git")?;
+        if status.success() {
+            return Ok(dir);
+        }
+    }
+    anyhow::bail!("No tag v{0} or {0} in {1}", version, repository)
+}
+
+/// The directory inside an unpacked archive that holds the package:
+/// `package/` for npm tarballs, `<name>-<version>/` for sdists
+fn package_root(root: &Path) -> PathBuf {
+    let entries: Vec<PathBuf> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() => only.clone(),
+        _ => root.to_path_buf(),
+    }
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<Value> {
+    client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to fetch {}", url))?
+        .json()
+        .await
+        .with_context(|| format!("Invalid JSON from {}", url))
+}
+
+fn hex_digest(algorithm: &str, bytes: &[u8]) -> Option<String> {
+    match algorithm {
+        "sha256" => Some(hex(&Sha256::digest(bytes))),
+        "sha512" => Some(hex(&Sha512::digest(bytes))),
+        _ => None,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// File content with line endings normalized
+fn normalize(bytes: &[u8]) -> Vec<u8> {
+    let text = String::from_utf8_lossy(bytes).replace("\r\n", "\n");
+    text.trim_end().as_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_repository() {
+        let expected = Some("https://github.com/acme/weather-mcp".to_string());
+        for url in [
+            "git+https://github.com/Acme/weather-mcp.git",
+            "git@github.com:acme/weather-mcp.git",
+            "github:acme/weather-mcp",
+            "https://github.com/acme/weather-mcp/tree/main",
+        ] {
+            assert_eq!(normalize_repository(url), expected, "{}", url);
+        }
+        assert_eq!(normalize_repository("not a url"), None);
+    }
+
+    #[test]
+    fn test_check_record() {
+        let artifact = b"package contents";
+        let sha512 = hex(&Sha512::digest(artifact));
+        let location = Location::new("weather-mcp@1.0.0");
+        let mut record = RegistryRecord {
+            version: "1.0.0".to_string(),
+            digest: Some(("sha512", sha512.clone())),
+            repository: Some("https://github.com/acme/weather-mcp".to_string()),
+            attestation: Some(Attestation {
+                subjects: vec![("sha512".to_string(), sha512)],
+                repository: Some("https://github.com/acme/weather-mcp".to_string()),
+            }),
+        };
+        assert!(check_record(&record, artifact, &location).is_empty());
+
+        record.attestation.as_mut().unwrap().repository =
+            Some("https://github.com/mallory/weather-mcp".to_string());
+        let rules: Vec<String> = check_record(&record, b"tampered", &location)
+            .into_iter()
+            .filter_map(|v| v.rule_id)
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                "provenance/checksum-mismatch",
+                "provenance/attestation-subject-mismatch",
+                "provenance/repository-mismatch",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_trees() {
+        let package = tempfile::tempdir().unwrap();
+        let repository = tempfile::tempdir().unwrap();
+        let write = |root: &Path, path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(repository.path(), "src/weather/server.py", "print('hi')\n");
+        write(repository.path(), "README.md", "# Weather\n");
+        write(package.path(), "weather/server.py", "print('hi')\r\n");
+        write(
+            package.path(),
+            "README.md",
+            "# Weather, now with telemetry\n",
+        );
+        write(package.path(), "weather/_hook.py", "import os\n");
+        write(package.path(), "weather-1.0.dist-info/RECORD", "...\n");
+        write(package.path(), "dist/bundle.js", "x\n");
+
+        assert_eq!(
+            compare_trees(package.path(), repository.path()),
+            vec![
+                Difference::Modified("README.md".to_string()),
+                Difference::Added("weather/_hook.py".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! OpenTelemetry tracing
+//!
+//! Scanner phases, per-file detector runs, and proxied JSON-RPC round-trips are
+//! recorded as `tracing` spans. With the `otel` feature enabled and an OTLP
+//! endpoint configured (`--otlp-endpoint` or `OTEL_EXPORTER_OTLP_ENDPOINT`),
+//! those spans are exported so slow detectors and proxy latency can be
+//! diagnosed in production deployments.
+//!
+//! # Span Names
+//!
+//! - `scan` - One directory scan (`target`)
+//! - `discovery` - File discovery phase
+//! - `scan_file` - All detectors on one file (`file`)
+//! - `detector` - A single detector run (`name`)
+//! - `rpc` - One proxied JSON-RPC round-trip (`method`, `direction`)
+
+use tracing::Span;
+
+/// Target that MCP Sentinel spans are recorded under
+pub const SPAN_TARGET: &str = "mcp_sentinel";
+
+/// Build a tracing layer that exports spans to an OTLP collector over gRPC
+///
+/// Must be called from within a Tokio runtime; spans are exported in batches
+/// by a background task. Call [`shutdown`] before exit to flush them.
+#[cfg(feature = "otel")]
+pub fn otlp_layer<S>(
+    endpoint: &str,
+) -> anyhow::Result<
+    tracing_subscriber::filter::Filtered<
+        tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>,
+        tracing_subscriber::filter::Targets,
+        S,
+    >,
+>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::Layer;
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+            opentelemetry_sdk::Resource::new(vec![
+                KeyValue::new("service.name", crate::NAME),
+                KeyValue::new("service.version", crate::VERSION),
+            ]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+
+    // Only export our own spans: exporting hyper/h2/tonic spans would feed
+    // the exporter's own traffic back into itself.
+    let filter =
+        tracing_subscriber::filter::Targets::new().with_target(SPAN_TARGET, tracing::Level::DEBUG);
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter))
+}
+
+/// Flush pending spans and shut down the exporter (no-op without `otel`)
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Span covering one proxied JSON-RPC round-trip
+///
+/// `direction` is `"client_to_server"` or `"server_to_client"`, the way the
+/// request travels. The proxy's interceptor opens it when a request passes
+/// and drops it when the response comes back.
+pub fn rpc_span(method: &str, direction: &'static str) -> Span {
+    tracing::info_span!(target: SPAN_TARGET, "rpc", method = %method, direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::runtime_proxy::{Direction, Interceptor};
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    /// Records the target and name of spans as they open and close
+    #[derive(Clone, Default)]
+    struct Recorder {
+        opened: Arc<Mutex<Vec<(String, String)>>>,
+        closed: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl<S> Layer<S> for Recorder
+    where
+        S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            let metadata = attrs.metadata();
+            self.opened
+                .lock()
+                .unwrap()
+                .push((metadata.target().to_string(), metadata.name().to_string()));
+        }
+
+        fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+            if let Some(span) = ctx.span(&id) {
+                self.closed.lock().unwrap().push(span.name().to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scanner_spans() {
+        let recorder = Recorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("server.py"), "import os\nos.system(cmd)\n").unwrap();
+
+        crate::scanner::Scanner::builder()
+            .build()
+            .scan_directory(dir.path())
+            .await
+            .unwrap();
+
+        let opened = recorder.opened.lock().unwrap();
+        for name in ["scan", "discovery", "scan_file", "detector"] {
+            assert!(
+                opened
+                    .iter()
+                    .any(|(target, span)| span == name && target.starts_with(SPAN_TARGET)),
+                "no {} span under {} in {:?}",
+                name,
+                SPAN_TARGET,
+                opened
+            );
+        }
+    }
+
+    #[test]
+    fn test_rpc_span_covers_round_trip() {
+        let recorder = Recorder::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+        let interceptor = Interceptor::default();
+        let rpc_spans = |spans: &[String]| spans.iter().filter(|s| *s == "rpc").count();
+
+        interceptor.inspect(
+            Direction::ClientToServer,
+            r#"{"jsonrpc":"2.0","id":7,"method":"tools/list"}"#,
+        );
+        assert!(recorder
+            .opened
+            .lock()
+            .unwrap()
+            .contains(&(SPAN_TARGET.to_string(), "rpc".to_string())));
+        assert_eq!(rpc_spans(&recorder.closed.lock().unwrap()), 0);
+
+        interceptor.inspect(
+            Direction::ServerToClient,
+            r#"{"jsonrpc":"2.0","id":7,"result":{"tools":[]}}"#,
+        );
+        assert_eq!(rpc_spans(&recorder.closed.lock().unwrap()), 1);
+    }
+}
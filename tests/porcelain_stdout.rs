@@ -0,0 +1,41 @@
+//! `scan --porcelain` keeps stdout to report records, so scripts can
+//! parse it while status messages go to stderr
+
+use assert_cmd::Command;
+
+#[test]
+fn test_porcelain_stdout_contains_only_records() {
+    let home = tempfile::tempdir().unwrap();
+    let target = tempfile::tempdir().unwrap();
+    std::fs::write(
+        target.path().join("server.py"),
+        "import os\n\
+         cursor.execute(f\"SELECT * FROM users WHERE id = {user_id}\")\n\
+         os.system(\"ping \" + host)\n",
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("mcp-sentinel")
+        .unwrap()
+        .env("HOME", home.path())
+        .args(["scan", "--porcelain", "--fix"])
+        .arg(target.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.is_empty());
+    for line in stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        assert_eq!(fields.len(), 4, "not a porcelain record: {:?}", line);
+        assert!(
+            ["info", "low", "medium", "high", "critical"].contains(&fields[0]),
+            "not a porcelain record: {:?}",
+            line
+        );
+    }
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("🔧 Fixed 1 finding(s)"));
+    assert!(stderr.contains("SENTINEL_RESULT "));
+}